@@ -0,0 +1,481 @@
+//! Agent runs for task cards
+//!
+//! Turns a [`RichTaskCard`] into an agent prompt and runs the agent on the
+//! card's branch in the background. The run reports back through
+//! [`Event::CardRun`] so the app can move the card across the board, record
+//! the resulting commits and log time and cost.
+//!
+//! # Run lifecycle
+//!
+//! 1. Refuse to start when the working tree has uncommitted tracked changes
+//! 2. Switch to the card branch, creating it from the current branch if needed
+//! 3. Run the agent ([`CardRunEvent::Started`], then [`CardRunEvent::Progress`])
+//! 4. Commit what the agent changed and collect every commit made on the
+//!    branch during the run ([`CardRunEvent::Finished`])
+
+use crate::ai::agent::{Agent, ProgressCallback};
+use crate::ai::evaluation::{Complexity, Task};
+use crate::ai::llm::LLMClient;
+use crate::ai::metrics::MetricsCollector;
+use crate::ai::tools::ToolRegistry;
+use crate::core::event::Event;
+use crate::git::{FileChange, GitService};
+use crate::infrastructure::board_store::STORE_DIR;
+use crate::infrastructure::git_card_integration::CardCommit;
+use crate::infrastructure::rich_task_cards::RichTaskCard;
+use anyhow::{Context, Result, bail};
+use chrono::{DateTime, TimeZone, Utc};
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+
+/// Author recorded on commits made for agent runs
+pub const AGENT_AUTHOR: &str = "toad-agent";
+
+/// What to run and where
+#[derive(Debug, Clone)]
+pub struct CardRunRequest {
+    /// Card being worked on
+    pub card_id: String,
+    /// Card title (used for the commit message)
+    pub title: String,
+    /// Prompt handed to the agent
+    pub prompt: String,
+    /// Branch to run on
+    pub branch: String,
+    /// Repository root
+    pub repo_path: PathBuf,
+}
+
+impl CardRunRequest {
+    /// Build a request for a card, running on `branch`
+    pub fn new(card: &RichTaskCard, branch: String, repo_path: PathBuf) -> Self {
+        Self {
+            card_id: card.id.clone(),
+            title: card.title.clone(),
+            prompt: card_prompt(card),
+            branch,
+            repo_path,
+        }
+    }
+}
+
+/// Outcome of a finished run
+#[derive(Debug, Clone)]
+pub struct CardRunReport {
+    /// Card that was worked on
+    pub card_id: String,
+    /// Branch the run happened on
+    pub branch: String,
+    /// Whether the agent reported success
+    pub success: bool,
+    /// Final agent response
+    pub summary: String,
+    /// Agent steps taken
+    pub steps: u32,
+    /// LLM cost in USD
+    pub cost_usd: f64,
+    /// Tokens used
+    pub total_tokens: u64,
+    /// When the agent started
+    pub started_at: DateTime<Utc>,
+    /// Wall-clock duration of the agent run
+    pub duration_ms: u64,
+    /// Commits made on the branch during the run, oldest first
+    pub commits: Vec<CardCommit>,
+}
+
+/// Progress of a card run, delivered as [`Event::CardRun`]
+#[derive(Debug, Clone)]
+pub enum CardRunEvent {
+    /// Branch checked out, agent starting
+    Started {
+        card_id: String,
+        branch: String,
+        /// Branch the card branch was created from (or left)
+        base_branch: String,
+    },
+    /// Agent step finished
+    Progress {
+        card_id: String,
+        step: usize,
+        cost_usd: f64,
+        last_tool: Option<String>,
+    },
+    /// Agent finished (successfully or not)
+    Finished(Box<CardRunReport>),
+    /// The run could not be carried out
+    Failed { card_id: String, error: String },
+}
+
+/// Agent prompt for a card: title, description and open checklist items
+///
+/// # Examples
+///
+/// ```
+/// use toad::ai::card_runner::card_prompt;
+/// use toad::infrastructure::rich_task_cards::{Priority, RichTaskCardManager};
+///
+/// let mut cards = RichTaskCardManager::new();
+/// let id = cards.create_card(
+///     "Fix login".to_string(),
+///     "Users are logged out after 5 minutes".to_string(),
+///     "todo".to_string(),
+///     Priority::High,
+///     "me".to_string(),
+/// );
+/// cards.create_subtask(&id, "Add a regression test".to_string());
+///
+/// let prompt = card_prompt(cards.get_card(&id).unwrap());
+/// assert!(prompt.contains("Fix login"));
+/// assert!(prompt.contains("- [ ] Add a regression test"));
+/// ```
+pub fn card_prompt(card: &RichTaskCard) -> String {
+    let mut prompt = format!("# Task {}: {}\n", card.id, card.title);
+    if !card.description.trim().is_empty() {
+        prompt.push('\n');
+        prompt.push_str(card.description.trim());
+        prompt.push('\n');
+    }
+    if !card.subtasks.is_empty() {
+        prompt.push_str("\n## Checklist\n\n");
+        for subtask in &card.subtasks {
+            let mark = if subtask.completed { "x" } else { " " };
+            prompt.push_str(&format!("- [{}] {}\n", mark, subtask.title));
+        }
+        prompt.push_str("\nItems marked [x] are already done.\n");
+    }
+    prompt.push_str(
+        "\nMake the changes in this repository. Keep them focused on the task \
+         and finish with a short summary of what you changed.\n",
+    );
+    prompt
+}
+
+/// Start an agent run for a card in the background
+///
+/// Sends [`Event::CardRun`] events to `event_tx`; the last one is always
+/// [`CardRunEvent::Finished`] or [`CardRunEvent::Failed`].
+pub fn start_card_run(
+    request: CardRunRequest,
+    client: Box<dyn LLMClient>,
+    event_tx: mpsc::UnboundedSender<Event>,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let card_id = request.card_id.clone();
+        let event = match run_card(request, client, event_tx.clone()).await {
+            Ok(report) => CardRunEvent::Finished(Box::new(report)),
+            Err(e) => CardRunEvent::Failed {
+                card_id,
+                error: format!("{:#}", e),
+            },
+        };
+        let _ = event_tx.send(Event::CardRun(event));
+    })
+}
+
+async fn run_card(
+    request: CardRunRequest,
+    client: Box<dyn LLMClient>,
+    event_tx: mpsc::UnboundedSender<Event>,
+) -> Result<CardRunReport> {
+    let service = GitService::new(&request.repo_path)?;
+
+    let changes = service.status().await?;
+    if changes
+        .iter()
+        .any(|c| !matches!(c, FileChange::Untracked(_)))
+    {
+        bail!("working tree has uncommitted changes; commit or stash them first");
+    }
+    // Untracked files that were already there are not the agent's work
+    let untracked_before: HashSet<PathBuf> =
+        changes.iter().map(|c| c.path().to_path_buf()).collect();
+
+    let base_branch = service.current_branch().await?;
+    if base_branch != request.branch {
+        let exists = service
+            .list_branches()
+            .await?
+            .iter()
+            .any(|b| b.name == request.branch);
+        if exists {
+            service.checkout_branch(&request.branch).await?;
+        } else {
+            service.create_branch(&request.branch, true).await?;
+        }
+    }
+    let start_head = service
+        .log(Some(1))
+        .await?
+        .first()
+        .map(|c| c.full_hash.clone())
+        .context("repository has no commits")?;
+
+    let _ = event_tx.send(Event::CardRun(CardRunEvent::Started {
+        card_id: request.card_id.clone(),
+        branch: request.branch.clone(),
+        base_branch,
+    }));
+
+    let progress_tx = event_tx.clone();
+    let progress_card = request.card_id.clone();
+    let progress_callback: ProgressCallback = Arc::new(move |progress| {
+        let _ = progress_tx.send(Event::CardRun(CardRunEvent::Progress {
+            card_id: progress_card.clone(),
+            step: progress.current_step.unwrap_or(0),
+            cost_usd: progress.total_cost,
+            last_tool: progress.last_tool,
+        }));
+    });
+    let agent =
+        Agent::new(client, ToolRegistry::m1_baseline()).with_progress_callback(progress_callback);
+
+    let task = card_task(&request, &start_head);
+    let mut metrics = MetricsCollector::new();
+    let started_at = Utc::now();
+    let result = agent
+        .execute_task_with_prompt(&task, Some(request.prompt.clone()), &mut metrics)
+        .await?;
+    let metrics = metrics.finish();
+
+    commit_agent_changes(&service, &request, &untracked_before).await?;
+    let commits = branch_commits(&service, &request, &start_head).await?;
+
+    Ok(CardRunReport {
+        card_id: request.card_id,
+        branch: request.branch,
+        success: result.success,
+        summary: result.final_response,
+        steps: result.steps,
+        cost_usd: metrics.cost_usd,
+        total_tokens: metrics.total_tokens(),
+        started_at,
+        duration_ms: metrics.duration_ms,
+        commits,
+    })
+}
+
+/// Agent task for a card (the prompt replaces the problem statement)
+fn card_task(request: &CardRunRequest, base_commit: &str) -> Task {
+    Task {
+        id: request.card_id.clone(),
+        repo: request.repo_path.display().to_string(),
+        base_commit: base_commit.to_string(),
+        problem_statement: request.prompt.clone(),
+        hints: None,
+        test_patch: String::new(),
+        files_to_modify: Vec::new(),
+        solution_patch: None,
+        complexity: Complexity::Medium,
+        metadata: HashMap::new(),
+    }
+}
+
+/// Commit what the agent left uncommitted
+///
+/// Skips files that were untracked before the run and the board database.
+async fn commit_agent_changes(
+    service: &GitService,
+    request: &CardRunRequest,
+    untracked_before: &HashSet<PathBuf>,
+) -> Result<()> {
+    let paths: Vec<PathBuf> = service
+        .status()
+        .await?
+        .iter()
+        .map(|c| c.path().to_path_buf())
+        .filter(|p| !untracked_before.contains(p) && !p.starts_with(STORE_DIR))
+        .collect();
+    if paths.is_empty() {
+        return Ok(());
+    }
+    for path in &paths {
+        service.stage(path).await?;
+    }
+    service
+        .commit(&format!("{} [{}]", request.title, request.card_id))
+        .await?;
+    Ok(())
+}
+
+/// Commits made on the card branch since `start_head`, oldest first
+async fn branch_commits(
+    service: &GitService,
+    request: &CardRunRequest,
+    start_head: &str,
+) -> Result<Vec<CardCommit>> {
+    let mut commits = Vec::new();
+    for info in service.log_range(start_head).await?.into_iter().rev() {
+        let (files, additions, deletions) = service
+            .commit_stats(&info.full_hash)
+            .await
+            .unwrap_or_default();
+        let committed_at = Utc
+            .timestamp_opt(info.timestamp, 0)
+            .single()
+            .unwrap_or_else(Utc::now);
+        commits.push(
+            CardCommit::new(
+                info.hash,
+                request.card_id.clone(),
+                info.message,
+                info.author,
+                committed_at,
+                request.branch.clone(),
+            )
+            .with_changes(files, additions, deletions),
+        );
+    }
+    Ok(commits)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai::llm::mock::MockResponseBuilder;
+    use crate::infrastructure::git_card_integration::CardBranch;
+    use crate::infrastructure::rich_task_cards::{Priority, RichTaskCardManager};
+    use std::process::Command;
+    use tempfile::TempDir;
+
+    fn git(dir: &std::path::Path, args: &[&str]) -> String {
+        let output = Command::new("git")
+            .current_dir(dir)
+            .args(args)
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "git {:?} failed", args);
+        String::from_utf8_lossy(&output.stdout).trim().to_string()
+    }
+
+    fn init_repo() -> TempDir {
+        let dir = TempDir::new().unwrap();
+        git(dir.path(), &["init", "-q", "-b", "main"]);
+        git(dir.path(), &["config", "user.email", "test@example.com"]);
+        git(dir.path(), &["config", "user.name", "Test"]);
+        std::fs::write(dir.path().join("README.md"), "hello\n").unwrap();
+        git(dir.path(), &["add", "."]);
+        git(dir.path(), &["commit", "-q", "-m", "init"]);
+        dir
+    }
+
+    fn card() -> RichTaskCard {
+        RichTaskCard::new(
+            "card-7".to_string(),
+            "Update readme".to_string(),
+            String::new(),
+            "todo".to_string(),
+            Priority::Medium,
+            "me".to_string(),
+        )
+    }
+
+    async fn collect(mut rx: mpsc::UnboundedReceiver<Event>) -> Vec<CardRunEvent> {
+        let mut events = Vec::new();
+        while let Some(Event::CardRun(event)) = rx.recv().await {
+            let done = matches!(
+                event,
+                CardRunEvent::Finished(_) | CardRunEvent::Failed { .. }
+            );
+            events.push(event);
+            if done {
+                break;
+            }
+        }
+        events
+    }
+
+    #[test]
+    fn test_card_prompt_marks_done_items() {
+        let mut cards = RichTaskCardManager::new();
+        let id = cards.create_card(
+            "Update readme".to_string(),
+            "Mention the install steps".to_string(),
+            "todo".to_string(),
+            Priority::Medium,
+            "me".to_string(),
+        );
+        let first = cards
+            .create_subtask(&id, "Install section".to_string())
+            .unwrap();
+        cards.create_subtask(&id, "Usage section".to_string());
+        let card = cards.get_card_mut(&id).unwrap();
+        card.complete_subtask(&first);
+
+        let prompt = card_prompt(card);
+        assert!(prompt.starts_with("# Task card-1: Update readme"));
+        assert!(prompt.contains("Mention the install steps"));
+        assert!(prompt.contains("- [x] Install section"));
+        assert!(prompt.contains("- [ ] Usage section"));
+    }
+
+    #[tokio::test]
+    async fn test_run_on_card_branch_records_commits() {
+        let dir = init_repo();
+        let branch = CardBranch::suggest_branch_name("card-7", "Update readme");
+        let request = CardRunRequest::new(&card(), branch.clone(), dir.path().to_path_buf());
+
+        let client = MockResponseBuilder::new()
+            .with_text("Done: nothing to change")
+            .build();
+        let (tx, rx) = mpsc::unbounded_channel();
+        start_card_run(request, Box::new(client), tx).await.unwrap();
+        let events = collect(rx).await;
+
+        assert!(matches!(
+            &events[0],
+            CardRunEvent::Started { branch: b, base_branch, .. } if *b == branch && base_branch == "main"
+        ));
+        let Some(CardRunEvent::Finished(report)) = events.last() else {
+            panic!("run did not finish: {:?}", events.last());
+        };
+        assert!(report.success);
+        assert!(report.commits.is_empty());
+        assert_eq!(git(dir.path(), &["branch", "--show-current"]), branch);
+    }
+
+    #[tokio::test]
+    async fn test_agent_changes_are_committed() {
+        let dir = init_repo();
+        let request = CardRunRequest::new(&card(), "card-7".to_string(), dir.path().to_path_buf());
+        let service = GitService::new(dir.path()).unwrap();
+        let start_head = git(dir.path(), &["rev-parse", "HEAD"]);
+
+        std::fs::write(dir.path().join("README.md"), "hello\nworld\n").unwrap();
+        std::fs::create_dir_all(dir.path().join(STORE_DIR)).unwrap();
+        std::fs::write(dir.path().join(STORE_DIR).join("x.json"), "{}").unwrap();
+        commit_agent_changes(&service, &request, &HashSet::new())
+            .await
+            .unwrap();
+
+        let commits = branch_commits(&service, &request, &start_head)
+            .await
+            .unwrap();
+        assert_eq!(commits.len(), 1);
+        assert_eq!(commits[0].message, "Update readme [card-7]");
+        assert_eq!(commits[0].files_changed, vec!["README.md".to_string()]);
+        assert_eq!((commits[0].additions, commits[0].deletions), (1, 0));
+    }
+
+    #[tokio::test]
+    async fn test_dirty_tree_is_refused() {
+        let dir = init_repo();
+        std::fs::write(dir.path().join("README.md"), "local edit\n").unwrap();
+        let request = CardRunRequest::new(&card(), "card-7".to_string(), dir.path().to_path_buf());
+
+        let client = MockResponseBuilder::new().with_text("unused").build();
+        let (tx, rx) = mpsc::unbounded_channel();
+        start_card_run(request, Box::new(client), tx).await.unwrap();
+        let events = collect(rx).await;
+
+        assert_eq!(events.len(), 1);
+        assert!(matches!(
+            &events[0],
+            CardRunEvent::Failed { error, .. } if error.contains("uncommitted changes")
+        ));
+        assert_eq!(git(dir.path(), &["branch", "--show-current"]), "main");
+    }
+}
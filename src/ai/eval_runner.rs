@@ -3,7 +3,10 @@
 /// This module provides infrastructure for running evaluations in the background
 /// while sending progress updates to the TUI event loop.
 use crate::ai::eval_commands::{CompareArgs, EvalArgs};
-use crate::ai::evaluation::{DatasetManager, EvaluationResults, Task, TaskLoader, TaskResult};
+use crate::ai::evaluation::{
+    DatasetManager, DatasetSource, EvaluationResults, ExperimentManager, Task, TaskLoader,
+    TaskResult,
+};
 use crate::ai::tools::ToolRegistry;
 use crate::config::ToadConfig;
 use crate::core::event::{EvaluationProgress, Event};
@@ -76,10 +79,23 @@ async fn run_evaluation_inner(
 
     // Run evaluation with progress updates
     let results = run_tasks_with_progress(tasks, &config, event_tx, cancel_rx).await?;
+    record_history(&args.dataset, &config, &results);
 
     Ok(results)
 }
 
+/// Add a finished run to the experiment history
+///
+/// Failures are logged; they don't fail the evaluation.
+fn record_history(dataset: &DatasetSource, config: &ToadConfig, results: &EvaluationResults) {
+    let recorded = serde_json::to_value(config)
+        .map_err(anyhow::Error::from)
+        .and_then(|config| ExperimentManager::default().record_run(&dataset.name(), &config, results));
+    if let Err(e) = recorded {
+        tracing::warn!("Failed to record run in experiment history: {}", e);
+    }
+}
+
 /// Load tasks from dataset
 async fn load_tasks(
     dataset: &crate::ai::evaluation::DatasetSource,
@@ -276,6 +292,7 @@ async fn run_single_task(
     // Build task result from agent result and metrics
     let final_metrics = metrics_collector.finish();
     let mut result = TaskResult::new(task.id.clone());
+    result.complexity = Some(task.complexity);
 
     result.duration_ms = final_metrics.duration_ms;
     result.cost_usd = final_metrics.cost_usd;
@@ -348,6 +365,9 @@ async fn run_comparison_inner(
     let test_results =
         run_tasks_with_progress(tasks, &test_config, event_tx.clone(), cancel_rx).await?;
 
+    record_history(&args.dataset, &baseline_config, &baseline_results);
+    record_history(&args.dataset, &test_config, &test_results);
+
     Ok((baseline_results, test_results))
 }
 
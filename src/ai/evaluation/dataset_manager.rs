@@ -51,6 +51,16 @@ impl DatasetSource {
                 .to_string(),
         }
     }
+
+    /// Name of the dataset in the experiment history
+    pub fn name(&self) -> String {
+        match self {
+            DatasetSource::Verified => "swebench-verified".to_string(),
+            DatasetSource::Lite => "swebench-lite".to_string(),
+            DatasetSource::Full => "swebench-full".to_string(),
+            DatasetSource::Local(path) => path.display().to_string(),
+        }
+    }
 }
 
 /// Manages SWE-bench datasets with caching
@@ -3,12 +3,17 @@
 /// This module provides infrastructure for managing A/B experiments,
 /// tracking results, and making decisions based on statistical evidence.
 use super::EvaluationResults;
+use super::history::{
+    self, HistoryFilter, Metric, RegressionCheck, RunMetrics, RunRecord, TrendPoint,
+};
+use super::storage::{PartialRun, StorageManager};
 use crate::ai::stats::{ComparisonResult, Recommendation};
 use crate::config::FeatureFlags;
-use anyhow::Result;
+use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::io::Write;
 use std::path::PathBuf;
 
 /// An experimental hypothesis to test
@@ -40,6 +45,10 @@ pub struct Experiment {
 
     /// Results (if completed)
     pub results: Option<ExperimentResults>,
+
+    /// Journaled evaluation run of this experiment (resumable if interrupted)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub run_id: Option<String>,
 }
 
 /// Status of an experiment
@@ -86,6 +95,9 @@ pub struct ExperimentManager {
 
     /// Loaded experiments
     experiments: HashMap<String, Experiment>,
+
+    /// Results directory whose unfinished runs are reported
+    results_dir: Option<PathBuf>,
 }
 
 impl ExperimentManager {
@@ -94,9 +106,16 @@ impl ExperimentManager {
         Self {
             experiments_dir,
             experiments: HashMap::new(),
+            results_dir: None,
         }
     }
 
+    /// Report unfinished runs journaled in `results_dir`
+    pub fn with_results_dir(mut self, results_dir: PathBuf) -> Self {
+        self.results_dir = Some(results_dir);
+        self
+    }
+
     /// Get the default experiments directory
     pub fn default_dir() -> PathBuf {
         let home = std::env::var("HOME").unwrap_or_else(|_| ".".to_string());
@@ -133,6 +152,7 @@ impl ExperimentManager {
             status: ExperimentStatus::Planned,
             created_at: Utc::now(),
             results: None,
+            run_id: None,
         };
 
         self.experiments.insert(id.clone(), experiment.clone());
@@ -158,6 +178,31 @@ impl ExperimentManager {
         Ok(())
     }
 
+    /// Link an experiment to the evaluation run producing its results
+    ///
+    /// The experiment is marked running.
+    pub fn attach_run(&mut self, id: &str, run_id: String) -> Result<()> {
+        if let Some(exp) = self.experiments.get_mut(id) {
+            exp.run_id = Some(run_id);
+            exp.status = ExperimentStatus::Running;
+        }
+
+        if let Some(exp) = self.experiments.get(id) {
+            self.save_experiment(exp)?;
+        }
+        Ok(())
+    }
+
+    /// Unfinished runs in the results directory, oldest first
+    ///
+    /// Empty without [`with_results_dir`](Self::with_results_dir).
+    pub fn partial_runs(&self) -> Result<Vec<PartialRun>> {
+        match &self.results_dir {
+            Some(dir) => StorageManager::new(dir).list_partial_runs(),
+            None => Ok(Vec::new()),
+        }
+    }
+
     /// Record experiment results
     pub fn record_results(
         &mut self,
@@ -198,6 +243,178 @@ impl ExperimentManager {
             .collect()
     }
 
+    /// Directory of the cross-run history (see [`history`])
+    fn history_dir(&self) -> PathBuf {
+        self.experiments_dir.join("history")
+    }
+
+    /// Add a finished run to the cross-run history
+    ///
+    /// `config` is the configuration snapshot the run is keyed by; runs with
+    /// equal snapshots share a config hash. The task results are kept so the
+    /// run can later be tested against a baseline.
+    pub fn record_run(
+        &self,
+        dataset: &str,
+        config: &serde_json::Value,
+        results: &EvaluationResults,
+    ) -> Result<RunRecord> {
+        let runs_dir = self.history_dir().join("runs");
+        std::fs::create_dir_all(&runs_dir)?;
+
+        let recorded_at = Utc::now();
+        let config_hash = history::config_hash(config);
+        let base_id = format!(
+            "{}-{}",
+            recorded_at.format("%Y%m%d-%H%M%S"),
+            &config_hash[..8]
+        );
+        let mut run_id = base_id.clone();
+        let mut n = 2;
+        while runs_dir.join(format!("{}.json", run_id)).exists() {
+            run_id = format!("{}-{}", base_id, n);
+            n += 1;
+        }
+
+        let record = RunRecord {
+            run_id,
+            recorded_at,
+            toad_commit: history::toad_commit(),
+            config_hash,
+            config_name: results.config_name.clone(),
+            dataset: dataset.to_string(),
+            metrics: RunMetrics::from_results(results),
+        };
+
+        let json = serde_json::to_string_pretty(results)?;
+        std::fs::write(runs_dir.join(format!("{}.json", record.run_id)), json)?;
+
+        let mut index = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(self.history_dir().join("index.jsonl"))?;
+        writeln!(index, "{}", serde_json::to_string(&record)?)?;
+
+        Ok(record)
+    }
+
+    /// All runs in the cross-run history, oldest first
+    ///
+    /// Unreadable index lines (e.g. from an interrupted write) are skipped.
+    pub fn history(&self) -> Result<Vec<RunRecord>> {
+        let path = self.history_dir().join("index.jsonl");
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+        let index = std::fs::read_to_string(path)?;
+        Ok(index
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .filter_map(|line| serde_json::from_str(line).ok())
+            .collect())
+    }
+
+    /// Task results of a run in the history
+    pub fn run_results(&self, run_id: &str) -> Result<EvaluationResults> {
+        let path = self
+            .history_dir()
+            .join("runs")
+            .join(format!("{}.json", run_id));
+        let json = std::fs::read_to_string(&path)
+            .with_context(|| format!("No results for history run {}", run_id))?;
+        Ok(serde_json::from_str(&json)?)
+    }
+
+    /// `metric` over the last `limit` selected runs, oldest first
+    pub fn trend(
+        &self,
+        metric: Metric,
+        filter: &HistoryFilter,
+        limit: usize,
+    ) -> Result<Vec<TrendPoint>> {
+        let runs: Vec<_> = self
+            .history()?
+            .into_iter()
+            .filter(|run| filter.matches(run))
+            .collect();
+        let skip = runs.len().saturating_sub(limit);
+        Ok(runs
+            .iter()
+            .skip(skip)
+            .map(|run| TrendPoint::new(run, metric))
+            .collect())
+    }
+
+    /// Pinned baseline run ID of each dataset
+    pub fn baselines(&self) -> Result<BTreeMap<String, String>> {
+        let path = self.history_dir().join("baselines.json");
+        if !path.exists() {
+            return Ok(BTreeMap::new());
+        }
+        Ok(serde_json::from_str(&std::fs::read_to_string(path)?)?)
+    }
+
+    /// Pin a run as the baseline of its dataset, replacing any earlier pin
+    pub fn pin_baseline(&self, run_id: &str) -> Result<RunRecord> {
+        let Some(record) = self.history()?.into_iter().find(|r| r.run_id == run_id) else {
+            anyhow::bail!("Run {} is not in the experiment history", run_id);
+        };
+
+        let mut baselines = self.baselines()?;
+        baselines.insert(record.dataset.clone(), record.run_id.clone());
+        std::fs::write(
+            self.history_dir().join("baselines.json"),
+            serde_json::to_string_pretty(&baselines)?,
+        )?;
+        Ok(record)
+    }
+
+    /// Test recent runs against the pinned baselines
+    ///
+    /// For each dataset with a pinned baseline, the latest later run of every
+    /// configuration is compared with it. Datasets without a baseline are
+    /// skipped.
+    pub fn regressions(&self, dataset: Option<&str>) -> Result<Vec<RegressionCheck>> {
+        let runs = self.history()?;
+        let mut checks = Vec::new();
+
+        for (pinned_dataset, baseline_id) in self.baselines()? {
+            if dataset.is_some_and(|d| d != pinned_dataset) {
+                continue;
+            }
+            let Some(baseline) = runs.iter().find(|r| r.run_id == baseline_id) else {
+                tracing::warn!("Pinned baseline {} is not in the history", baseline_id);
+                continue;
+            };
+
+            // Latest run of each configuration after the baseline
+            let mut latest: BTreeMap<&str, &RunRecord> = BTreeMap::new();
+            for run in runs.iter().filter(|r| {
+                r.dataset == pinned_dataset
+                    && r.run_id != baseline.run_id
+                    && r.recorded_at >= baseline.recorded_at
+            }) {
+                latest.insert(&run.config_hash, run);
+            }
+            if latest.is_empty() {
+                continue;
+            }
+
+            let baseline_results = self.run_results(&baseline.run_id)?;
+            for candidate in latest.into_values() {
+                let candidate_results = self.run_results(&candidate.run_id)?;
+                let comparison = ComparisonResult::compare(&baseline_results, &candidate_results);
+                checks.push(RegressionCheck::from_comparison(
+                    baseline.clone(),
+                    candidate.clone(),
+                    &comparison,
+                ));
+            }
+        }
+
+        Ok(checks)
+    }
+
     /// Save an experiment to disk
     fn save_experiment(&self, experiment: &Experiment) -> Result<()> {
         self.init()?;
@@ -250,6 +467,68 @@ impl ExperimentManager {
         report.push_str(&format!("- Completed: {}\n", completed.len()));
         report.push_str(&format!("- Failed: {}\n\n", failed.len()));
 
+        let partial = self.partial_runs().unwrap_or_else(|e| {
+            tracing::warn!("Failed to list partial runs: {}", e);
+            Vec::new()
+        });
+        if !partial.is_empty() {
+            report.push_str("## Partial Runs\n\n");
+            for run in partial {
+                let progress = match (run.manifest.planned_results, run.progress()) {
+                    (Some(planned), Some(p)) => {
+                        format!(
+                            "{}/{} results ({:.0}%)",
+                            run.completed_results,
+                            planned,
+                            p * 100.0
+                        )
+                    }
+                    _ => format!("{} results", run.completed_results),
+                };
+                let experiment = self
+                    .experiments
+                    .values()
+                    .find(|exp| exp.run_id.as_deref() == Some(run.run_id()))
+                    .map(|exp| format!(" for {}", exp.name))
+                    .unwrap_or_default();
+                report.push_str(&format!(
+                    "- {} ({:?}{}), started {}: {} (resume with `--resume {}`)\n",
+                    run.run_id(),
+                    run.manifest.kind,
+                    experiment,
+                    run.started_at().format("%Y-%m-%d %H:%M UTC"),
+                    progress,
+                    run.run_id()
+                ));
+            }
+            report.push('\n');
+        }
+
+        let checks = self.regressions(None).unwrap_or_else(|e| {
+            tracing::warn!("Failed to check for regressions: {}", e);
+            Vec::new()
+        });
+        let regressed: Vec<_> = checks.iter().filter(|c| c.is_regression()).collect();
+        if !regressed.is_empty() {
+            report.push_str("## Regressions\n\n");
+            for check in regressed {
+                for regression in &check.regressions {
+                    report.push_str(&format!(
+                        "- {} ({}, {}): {} {} -> {} vs baseline {} (p={:.4})\n",
+                        check.candidate.run_id,
+                        check.candidate.dataset,
+                        check.candidate.config_name,
+                        regression.metric.name(),
+                        regression.metric.format(regression.baseline),
+                        regression.metric.format(regression.candidate),
+                        check.baseline.run_id,
+                        regression.p_value
+                    ));
+                }
+            }
+            report.push('\n');
+        }
+
         if !completed.is_empty() {
             report.push_str("## Completed Experiments\n\n");
             for exp in completed {
@@ -409,5 +688,147 @@ mod tests {
         let report = manager.generate_report();
         assert!(report.contains("Experiment Report"));
         assert!(report.contains("Planned: 1"));
+        assert!(!report.contains("Partial Runs"));
+    }
+
+    #[test]
+    fn test_report_shows_partial_runs() {
+        use crate::ai::evaluation::{RunKind, TaskResult};
+
+        let temp_dir = TempDir::new().unwrap();
+        let results_dir = temp_dir.path().join("results");
+        let mut manager = ExperimentManager::new(temp_dir.path().join("experiments"))
+            .with_results_dir(results_dir.clone());
+
+        let journal = StorageManager::new(&results_dir)
+            .start_journal(RunKind::Compare, serde_json::json!({}), Some(4))
+            .unwrap();
+        journal
+            .append("A", &TaskResult::new("task-1".to_string()))
+            .unwrap();
+
+        let id = manager
+            .create_experiment(
+                "AST context".to_string(),
+                "H1".to_string(),
+                FeatureFlags::milestone_1(),
+                FeatureFlags::milestone_2(),
+                3.0,
+            )
+            .unwrap();
+        manager
+            .attach_run(&id, journal.run_id().to_string())
+            .unwrap();
+        assert_eq!(manager.get(&id).unwrap().status, ExperimentStatus::Running);
+
+        assert_eq!(manager.partial_runs().unwrap().len(), 1);
+        let report = manager.generate_report();
+        assert!(report.contains("## Partial Runs"));
+        assert!(report.contains("(Compare for AST context)"));
+        assert!(report.contains("1/4 results (25%)"));
+        assert!(report.contains(&format!("--resume {}", journal.run_id())));
+    }
+
+    fn results(config_name: &str, solved: usize, tasks: usize) -> EvaluationResults {
+        use crate::ai::evaluation::TaskResult;
+
+        let results = (0..tasks)
+            .map(|i| {
+                let mut result = TaskResult::new(format!("task-{}", i));
+                result.cost_usd = 0.01;
+                if i < solved {
+                    result.mark_solved();
+                }
+                result
+            })
+            .collect();
+        EvaluationResults::from_results(config_name.to_string(), results)
+    }
+
+    #[test]
+    fn test_record_run_and_trend() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = ExperimentManager::new(temp_dir.path().to_path_buf());
+        let config = serde_json::json!({"milestone": 1});
+
+        let first = manager
+            .record_run("swebench-lite", &config, &results("M1", 2, 10))
+            .unwrap();
+        let second = manager
+            .record_run("swebench-lite", &config, &results("M1", 5, 10))
+            .unwrap();
+        manager
+            .record_run(
+                "humaneval",
+                &serde_json::json!({"milestone": 2}),
+                &results("M2", 1, 4),
+            )
+            .unwrap();
+
+        assert_ne!(first.run_id, second.run_id);
+        assert_eq!(first.config_hash, second.config_hash);
+        assert_eq!(manager.history().unwrap().len(), 3);
+        assert_eq!(manager.run_results(&second.run_id).unwrap().tasks_solved, 5);
+
+        let filter = HistoryFilter {
+            dataset: Some("swebench-lite".to_string()),
+            ..Default::default()
+        };
+        let trend = manager.trend(Metric::Accuracy, &filter, 10).unwrap();
+        let values: Vec<f64> = trend.iter().map(|p| p.value).collect();
+        assert_eq!(values, vec![20.0, 50.0]);
+
+        let last = manager
+            .trend(Metric::Accuracy, &HistoryFilter::default(), 1)
+            .unwrap();
+        assert_eq!(last.len(), 1);
+        assert_eq!(last[0].value, 25.0);
+
+        // History files don't show up as experiments
+        let mut loaded = ExperimentManager::new(temp_dir.path().to_path_buf());
+        loaded.load_all().unwrap();
+        assert!(loaded.list().is_empty());
+    }
+
+    #[test]
+    fn test_regressions_against_pinned_baseline() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = ExperimentManager::new(temp_dir.path().to_path_buf());
+        let config = serde_json::json!({"milestone": 1});
+
+        assert!(manager.pin_baseline("missing").is_err());
+
+        let baseline = manager
+            .record_run("swebench-lite", &config, &results("M1", 30, 30))
+            .unwrap();
+        // Nothing is checked without a pinned baseline
+        manager
+            .record_run("swebench-lite", &config, &results("M1", 1, 30))
+            .unwrap();
+        assert!(manager.regressions(None).unwrap().is_empty());
+
+        manager.pin_baseline(&baseline.run_id).unwrap();
+        assert_eq!(
+            manager.baselines().unwrap().get("swebench-lite"),
+            Some(&baseline.run_id)
+        );
+
+        let checks = manager.regressions(None).unwrap();
+        assert_eq!(checks.len(), 1);
+        assert!(checks[0].is_regression());
+        assert_eq!(checks[0].regressions[0].metric, Metric::Accuracy);
+        assert!(manager.regressions(Some("humaneval")).unwrap().is_empty());
+
+        let report = manager.generate_report();
+        assert!(report.contains("## Regressions"));
+        assert!(report.contains("accuracy 100.00% -> 3.33%"));
+
+        // The latest run of the configuration is what counts
+        manager
+            .record_run("swebench-lite", &config, &results("M1", 30, 30))
+            .unwrap();
+        let checks = manager.regressions(None).unwrap();
+        assert_eq!(checks.len(), 1);
+        assert!(!checks[0].is_regression());
     }
 }
@@ -0,0 +1,430 @@
+//! Cross-run history of evaluation results
+//!
+//! Every finished run is appended to an index in the experiments directory,
+//! keyed by the TOAD commit it ran on, a hash of its configuration and its
+//! dataset. The task results of each run are kept next to the index so a
+//! newer run can be tested against a pinned baseline with the paired tests of
+//! [`ComparisonResult`].
+//!
+//! ```text
+//! {experiments_dir}/history/
+//! ├── index.jsonl          # One RunRecord per line, oldest first
+//! ├── baselines.json       # Pinned baseline run of each dataset
+//! └── runs/{run_id}.json   # EvaluationResults of each run
+//! ```
+
+use super::EvaluationResults;
+use crate::ai::llm::cassette::fnv1a;
+use crate::ai::stats::ComparisonResult;
+use anyhow::{Result, bail};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// One evaluation run in the history index
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunRecord {
+    /// History ID of the run (`{timestamp}-{config hash prefix}`)
+    pub run_id: String,
+
+    /// When the run was recorded
+    pub recorded_at: DateTime<Utc>,
+
+    /// Commit of TOAD the run was made with, if known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub toad_commit: Option<String>,
+
+    /// Hash of the configuration snapshot (see [`config_hash`])
+    pub config_hash: String,
+
+    /// Configuration name of the results
+    pub config_name: String,
+
+    /// Dataset or benchmark the run evaluated
+    pub dataset: String,
+
+    /// Summary metrics
+    pub metrics: RunMetrics,
+}
+
+/// Summary metrics of a recorded run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunMetrics {
+    /// Task results in the run
+    pub tasks: usize,
+
+    /// Solved task results
+    pub solved: usize,
+
+    /// Accuracy (% solved)
+    pub accuracy: f64,
+
+    /// Mean cost per task (USD)
+    pub mean_cost_usd: f64,
+
+    /// Mean duration per task (milliseconds)
+    pub mean_duration_ms: f64,
+
+    /// Mean total tokens per task
+    pub mean_tokens: f64,
+}
+
+impl RunMetrics {
+    /// Summarize evaluation results
+    pub fn from_results(results: &EvaluationResults) -> Self {
+        let tokens = results.results.iter().map(|r| r.total_tokens).sum::<u64>();
+        Self {
+            tasks: results.total_tasks,
+            solved: results.tasks_solved,
+            accuracy: results.accuracy,
+            mean_cost_usd: results.avg_cost_usd,
+            mean_duration_ms: results.avg_duration_ms,
+            mean_tokens: if results.results.is_empty() {
+                0.0
+            } else {
+                tokens as f64 / results.results.len() as f64
+            },
+        }
+    }
+}
+
+/// Metric tracked across runs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Metric {
+    /// Accuracy (% solved)
+    Accuracy,
+    /// Mean cost per task (USD)
+    Cost,
+    /// Mean duration per task (milliseconds)
+    Latency,
+    /// Mean total tokens per task
+    Tokens,
+}
+
+impl Metric {
+    /// Every metric, in display order
+    pub const ALL: [Metric; 4] = [
+        Metric::Accuracy,
+        Metric::Cost,
+        Metric::Latency,
+        Metric::Tokens,
+    ];
+
+    /// Parse a metric name (`accuracy`, `cost`, `latency` or `tokens`)
+    pub fn parse(name: &str) -> Result<Self> {
+        match name.to_lowercase().as_str() {
+            "accuracy" => Ok(Metric::Accuracy),
+            "cost" => Ok(Metric::Cost),
+            "latency" | "duration" => Ok(Metric::Latency),
+            "tokens" => Ok(Metric::Tokens),
+            _ => bail!(
+                "Unknown metric: '{}'. Use 'accuracy', 'cost', 'latency' or 'tokens'",
+                name
+            ),
+        }
+    }
+
+    /// Lowercase name of the metric
+    pub fn name(&self) -> &'static str {
+        match self {
+            Metric::Accuracy => "accuracy",
+            Metric::Cost => "cost",
+            Metric::Latency => "latency",
+            Metric::Tokens => "tokens",
+        }
+    }
+
+    /// Value of the metric in a run
+    pub fn value(&self, metrics: &RunMetrics) -> f64 {
+        match self {
+            Metric::Accuracy => metrics.accuracy,
+            Metric::Cost => metrics.mean_cost_usd,
+            Metric::Latency => metrics.mean_duration_ms,
+            Metric::Tokens => metrics.mean_tokens,
+        }
+    }
+
+    /// Format a value of the metric
+    pub fn format(&self, value: f64) -> String {
+        match self {
+            Metric::Accuracy => format!("{:.2}%", value),
+            Metric::Cost => format!("${:.4}", value),
+            Metric::Latency => format!("{:.0}ms", value),
+            Metric::Tokens => format!("{:.0}", value),
+        }
+    }
+}
+
+/// Selects runs of the history
+#[derive(Debug, Clone, Default)]
+pub struct HistoryFilter {
+    /// Only runs of this dataset
+    pub dataset: Option<String>,
+
+    /// Only runs whose configuration name or hash prefix matches
+    pub config: Option<String>,
+}
+
+impl HistoryFilter {
+    /// Whether a run is selected
+    pub fn matches(&self, record: &RunRecord) -> bool {
+        let dataset = self
+            .dataset
+            .as_ref()
+            .is_none_or(|dataset| &record.dataset == dataset);
+        let config = self.config.as_ref().is_none_or(|config| {
+            &record.config_name == config || record.config_hash.starts_with(config.as_str())
+        });
+        dataset && config
+    }
+}
+
+/// Value of a metric in one run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrendPoint {
+    /// History ID of the run
+    pub run_id: String,
+
+    /// When the run was recorded
+    pub recorded_at: DateTime<Utc>,
+
+    /// Commit of TOAD the run was made with, if known
+    pub toad_commit: Option<String>,
+
+    /// Configuration name of the run
+    pub config_name: String,
+
+    /// Value of the metric
+    pub value: f64,
+}
+
+impl TrendPoint {
+    /// Point of `metric` in a recorded run
+    pub fn new(record: &RunRecord, metric: Metric) -> Self {
+        Self {
+            run_id: record.run_id.clone(),
+            recorded_at: record.recorded_at,
+            toad_commit: record.toad_commit.clone(),
+            config_name: record.config_name.clone(),
+            value: metric.value(&record.metrics),
+        }
+    }
+}
+
+/// A metric that got significantly worse than the baseline
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Regression {
+    /// Metric that regressed
+    pub metric: Metric,
+
+    /// Value in the baseline run
+    pub baseline: f64,
+
+    /// Value in the candidate run
+    pub candidate: f64,
+
+    /// P-value of the test that found the difference
+    pub p_value: f64,
+}
+
+/// A run tested against the pinned baseline of its dataset
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegressionCheck {
+    /// Pinned baseline run
+    pub baseline: RunRecord,
+
+    /// Run tested against it
+    pub candidate: RunRecord,
+
+    /// Metrics that got significantly worse
+    pub regressions: Vec<Regression>,
+}
+
+impl RegressionCheck {
+    /// Find the regressions in a comparison of baseline (A) and candidate (B)
+    ///
+    /// Accuracy and cost use the comparison's significance tests; latency and
+    /// tokens are only checked when the runs have tasks in common, with the
+    /// paired Wilcoxon tests.
+    pub fn from_comparison(
+        baseline: RunRecord,
+        candidate: RunRecord,
+        comparison: &ComparisonResult,
+    ) -> Self {
+        let significance = &comparison.significance;
+        let paired = significance.paired.as_ref();
+        let worse = [
+            (
+                Metric::Accuracy,
+                significance.accuracy_significant && comparison.delta.accuracy < 0.0,
+                significance.accuracy_p_value,
+            ),
+            (
+                Metric::Cost,
+                significance.cost_significant && comparison.delta.cost_usd > 0.0,
+                significance.cost_p_value,
+            ),
+            (
+                Metric::Latency,
+                paired.is_some_and(|p| p.latency.significant && p.latency.median_delta > 0.0),
+                paired.map_or(1.0, |p| p.latency.p_value),
+            ),
+            (
+                Metric::Tokens,
+                paired.is_some_and(|p| p.tokens.significant && p.tokens.median_delta > 0.0),
+                paired.map_or(1.0, |p| p.tokens.p_value),
+            ),
+        ];
+
+        let regressions = worse
+            .into_iter()
+            .filter(|(_, regressed, _)| *regressed)
+            .map(|(metric, _, p_value)| Regression {
+                metric,
+                baseline: metric.value(&baseline.metrics),
+                candidate: metric.value(&candidate.metrics),
+                p_value,
+            })
+            .collect();
+
+        Self {
+            baseline,
+            candidate,
+            regressions,
+        }
+    }
+
+    /// Whether any metric regressed
+    pub fn is_regression(&self) -> bool {
+        !self.regressions.is_empty()
+    }
+}
+
+/// Stable hash of a configuration snapshot
+///
+/// serde_json objects keep their keys sorted, so equal snapshots hash equal
+/// regardless of how they were built.
+pub fn config_hash(config: &serde_json::Value) -> String {
+    format!("{:016x}", fnv1a(config.to_string().as_bytes()))
+}
+
+/// Commit of TOAD this binary was built from
+///
+/// Taken from `TOAD_GIT_COMMIT` at build time, else from the git checkout the
+/// binary was built in; None if neither is available.
+pub fn toad_commit() -> Option<String> {
+    if let Some(commit) = option_env!("TOAD_GIT_COMMIT") {
+        return Some(commit.to_string());
+    }
+    let output = std::process::Command::new("git")
+        .args(["rev-parse", "--short=12", "HEAD"])
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .output()
+        .ok()?;
+    let commit = String::from_utf8(output.stdout).ok()?.trim().to_string();
+    (output.status.success() && !commit.is_empty()).then_some(commit)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai::evaluation::TaskResult;
+
+    fn record(dataset: &str, config_name: &str, accuracy: f64) -> RunRecord {
+        RunRecord {
+            run_id: format!("{}-{}", dataset, accuracy),
+            recorded_at: Utc::now(),
+            toad_commit: None,
+            config_hash: "0123456789abcdef".to_string(),
+            config_name: config_name.to_string(),
+            dataset: dataset.to_string(),
+            metrics: RunMetrics {
+                tasks: 10,
+                solved: 0,
+                accuracy,
+                mean_cost_usd: 0.0,
+                mean_duration_ms: 0.0,
+                mean_tokens: 0.0,
+            },
+        }
+    }
+
+    fn results(solved: &[bool], cost: f64) -> EvaluationResults {
+        let results = solved
+            .iter()
+            .enumerate()
+            .map(|(i, &solved)| {
+                let mut result = TaskResult::new(format!("task-{}", i));
+                result.cost_usd = cost;
+                if solved {
+                    result.mark_solved();
+                }
+                result
+            })
+            .collect();
+        EvaluationResults::from_results("test".to_string(), results)
+    }
+
+    #[test]
+    fn test_metric_parse() {
+        assert_eq!(Metric::parse("Accuracy").unwrap(), Metric::Accuracy);
+        assert_eq!(Metric::parse("duration").unwrap(), Metric::Latency);
+        assert!(Metric::parse("speed").is_err());
+        for metric in Metric::ALL {
+            assert_eq!(Metric::parse(metric.name()).unwrap(), metric);
+        }
+    }
+
+    #[test]
+    fn test_config_hash_is_stable() {
+        let a = serde_json::json!({"model": "sonnet", "features": {"ast": true, "cache": false}});
+        let b = serde_json::json!({"features": {"cache": false, "ast": true}, "model": "sonnet"});
+        let c = serde_json::json!({"model": "haiku", "features": {"ast": true, "cache": false}});
+        assert_eq!(config_hash(&a), config_hash(&b));
+        assert_ne!(config_hash(&a), config_hash(&c));
+        assert_eq!(config_hash(&a).len(), 16);
+    }
+
+    #[test]
+    fn test_filter() {
+        let run = record("swebench-lite", "5 features", 40.0);
+        assert!(HistoryFilter::default().matches(&run));
+        let filter = HistoryFilter {
+            dataset: Some("swebench-lite".to_string()),
+            config: Some("0123".to_string()),
+        };
+        assert!(filter.matches(&run));
+        let filter = HistoryFilter {
+            config: Some("3 features".to_string()),
+            ..Default::default()
+        };
+        assert!(!filter.matches(&run));
+    }
+
+    #[test]
+    fn test_regression_check() {
+        let baseline = results(&[true; 30], 0.01);
+        let mut solved = vec![false; 30];
+        solved[0] = true;
+        let candidate = results(&solved, 0.01);
+        let comparison = ComparisonResult::compare(&baseline, &candidate);
+
+        let check = RegressionCheck::from_comparison(
+            record("d", "a", baseline.accuracy),
+            record("d", "b", candidate.accuracy),
+            &comparison,
+        );
+        assert!(check.is_regression());
+        assert_eq!(check.regressions[0].metric, Metric::Accuracy);
+        assert_eq!(check.regressions[0].baseline, 100.0);
+
+        // An improvement is not a regression
+        let comparison = ComparisonResult::compare(&candidate, &baseline);
+        let check = RegressionCheck::from_comparison(
+            record("d", "b", candidate.accuracy),
+            record("d", "a", baseline.accuracy),
+            &comparison,
+        );
+        assert!(!check.is_regression());
+    }
+}
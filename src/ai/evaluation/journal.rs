@@ -0,0 +1,387 @@
+//! Append-only journals of in-progress evaluation runs
+//!
+//! Every completed [`TaskResult`] is appended to a JSONL journal as soon as it
+//! is known, so a cancelled or crashed run loses at most the tasks that were
+//! running. Resuming the run (`--resume <run-id>`) reopens the journal and
+//! skips the tasks it already holds.
+//!
+//! # Layout
+//!
+//! ```text
+//! ./results/.tmp/run-{uuid}/
+//! ├── manifest.json   # RunManifest: kind, start time, config snapshot
+//! └── journal.jsonl   # One {"scope": ..., "result": TaskResult} per line
+//! ```
+//!
+//! A result's scope names the part of the run it belongs to: the arm of a
+//! comparison, or the benchmark of a multi-benchmark run.
+//!
+//! # Resuming
+//!
+//! A journal only resumes a run with an identical config snapshot, so results
+//! produced by different settings are never mixed. A line cut short by a crash
+//! is dropped when the run resumes, and a result recorded twice counts once
+//! (the latest wins).
+//!
+//! # Examples
+//!
+//! ```
+//! use toad::ai::evaluation::TaskResult;
+//! use toad::ai::evaluation::journal::RunKind;
+//! use toad::ai::evaluation::storage::StorageManager;
+//!
+//! # fn main() -> anyhow::Result<()> {
+//! let dir = tempfile::tempdir()?;
+//! let storage = StorageManager::new(dir.path());
+//! let snapshot = serde_json::json!({ "milestone": 2 });
+//!
+//! let journal = storage.start_journal(RunKind::Eval, snapshot.clone(), Some(10))?;
+//! journal.append("eval", &TaskResult::new("task-1".to_string()))?;
+//! let run_id = journal.run_id().to_string();
+//! drop(journal); // Interrupted
+//!
+//! let resumed = storage.resume_journal(&run_id, &snapshot)?;
+//! assert!(resumed.restored("eval", "task-1", None).is_some());
+//! assert!(storage.resume_journal(&run_id, &serde_json::json!({})).is_err());
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::ai::evaluation::TaskResult;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// File name of a run's manifest
+pub const MANIFEST_FILE: &str = "manifest.json";
+
+/// File name of a run's journal
+pub const JOURNAL_FILE: &str = "journal.jsonl";
+
+/// Which command started a run
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RunKind {
+    /// Single-configuration evaluation (`toad eval`)
+    Eval,
+    /// A/B comparison (`toad compare`)
+    Compare,
+    /// Multi-benchmark orchestrator run (`toad eval --benchmarks`)
+    Benchmarks,
+}
+
+/// Description of a resumable run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunManifest {
+    /// Run ID (e.g., "run-{uuid}")
+    pub run_id: String,
+
+    /// Command that started the run
+    pub kind: RunKind,
+
+    /// When the run was first started
+    pub started_at: DateTime<Utc>,
+
+    /// Task results the complete run produces, if known up front
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub planned_results: Option<usize>,
+
+    /// Settings that determine the results; resuming requires an exact match
+    pub config_snapshot: Value,
+}
+
+/// One line of a journal
+#[derive(Debug, Serialize, Deserialize)]
+struct JournalEntry {
+    scope: String,
+    result: TaskResult,
+}
+
+/// Scope, task ID and sample index of a journaled result
+type ResultKey = (String, String, Option<usize>);
+
+/// Open journal of an in-progress run
+///
+/// Appends are serialized internally, so one journal can be shared between
+/// concurrently running tasks.
+#[derive(Debug)]
+pub struct RunJournal {
+    dir: PathBuf,
+    manifest: RunManifest,
+    restored: HashMap<ResultKey, TaskResult>,
+    file: Mutex<File>,
+}
+
+impl RunJournal {
+    /// Start a new journal in `dir`
+    pub fn create(dir: PathBuf, manifest: RunManifest) -> Result<Self> {
+        std::fs::create_dir_all(&dir)
+            .with_context(|| format!("Failed to create run directory {}", dir.display()))?;
+        let json = serde_json::to_string_pretty(&manifest)?;
+        std::fs::write(dir.join(MANIFEST_FILE), json).context("Failed to write run manifest")?;
+        let file = Self::open_for_append(&dir)?;
+        Ok(Self {
+            dir,
+            manifest,
+            restored: HashMap::new(),
+            file: Mutex::new(file),
+        })
+    }
+
+    /// Reopen the journal in `dir` to resume its run
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the manifest can't be read or its config snapshot
+    /// differs from `config_snapshot`.
+    pub fn open(dir: PathBuf, config_snapshot: &Value) -> Result<Self> {
+        let manifest = read_manifest(&dir)?;
+        if &manifest.config_snapshot != config_snapshot {
+            anyhow::bail!(
+                "Run {} was started with a different configuration (differs in: {}); \
+                 rerun with the original settings or start a new run",
+                manifest.run_id,
+                differing_keys(&manifest.config_snapshot, config_snapshot).join(", ")
+            );
+        }
+
+        let restored = read_entries(&dir.join(JOURNAL_FILE))?
+            .into_iter()
+            .map(|entry| {
+                let key = (
+                    entry.scope,
+                    entry.result.task_id.clone(),
+                    entry.result.sample,
+                );
+                (key, entry.result)
+            })
+            .collect();
+        truncate_torn_line(&dir.join(JOURNAL_FILE))?;
+        let file = Self::open_for_append(&dir)?;
+        Ok(Self {
+            dir,
+            manifest,
+            restored,
+            file: Mutex::new(file),
+        })
+    }
+
+    fn open_for_append(dir: &Path) -> Result<File> {
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(dir.join(JOURNAL_FILE))
+            .context("Failed to open run journal")
+    }
+
+    /// ID of the journaled run
+    pub fn run_id(&self) -> &str {
+        &self.manifest.run_id
+    }
+
+    /// Manifest of the journaled run
+    pub fn manifest(&self) -> &RunManifest {
+        &self.manifest
+    }
+
+    /// Directory holding the manifest and journal
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Result recorded before the run was resumed, if any
+    pub fn restored(
+        &self,
+        scope: &str,
+        task_id: &str,
+        sample: Option<usize>,
+    ) -> Option<TaskResult> {
+        self.restored
+            .get(&(scope.to_string(), task_id.to_string(), sample))
+            .cloned()
+    }
+
+    /// Number of results recorded before the run was resumed
+    pub fn restored_count(&self) -> usize {
+        self.restored.len()
+    }
+
+    /// Record a completed result
+    ///
+    /// The line is flushed to disk before returning.
+    pub fn append(&self, scope: &str, result: &TaskResult) -> Result<()> {
+        let entry = JournalEntry {
+            scope: scope.to_string(),
+            result: result.clone(),
+        };
+        let mut line = serde_json::to_string(&entry).context("Failed to serialize task result")?;
+        line.push('\n');
+
+        let mut file = self.file.lock().unwrap_or_else(|e| e.into_inner());
+        file.write_all(line.as_bytes())
+            .and_then(|_| file.sync_data())
+            .context("Failed to append to run journal")
+    }
+}
+
+/// Read the manifest of the run in `dir`
+pub fn read_manifest(dir: &Path) -> Result<RunManifest> {
+    let path = dir.join(MANIFEST_FILE);
+    let json = std::fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read run manifest {}", path.display()))?;
+    serde_json::from_str(&json).context("Failed to parse run manifest")
+}
+
+/// Number of distinct results in the journal in `dir`
+pub fn count_results(dir: &Path) -> Result<usize> {
+    let keys: std::collections::HashSet<ResultKey> = read_entries(&dir.join(JOURNAL_FILE))?
+        .into_iter()
+        .map(|entry| (entry.scope, entry.result.task_id, entry.result.sample))
+        .collect();
+    Ok(keys.len())
+}
+
+fn read_entries(path: &Path) -> Result<Vec<JournalEntry>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let file = File::open(path).context("Failed to open run journal")?;
+    let mut entries = Vec::new();
+    for line in BufReader::new(file).lines() {
+        let line = line.context("Failed to read run journal")?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        match serde_json::from_str(&line) {
+            Ok(entry) => entries.push(entry),
+            // A crash mid-write leaves a partial last line; that task reruns
+            Err(e) => tracing::warn!(
+                "Skipping unreadable journal line in {}: {}",
+                path.display(),
+                e
+            ),
+        }
+    }
+    Ok(entries)
+}
+
+/// Cut off a last line left unfinished by a crash
+///
+/// Otherwise the next append would be glued onto it and lost with it.
+fn truncate_torn_line(path: &Path) -> Result<()> {
+    if !path.exists() {
+        return Ok(());
+    }
+    let bytes = std::fs::read(path).context("Failed to read run journal")?;
+    let complete = bytes.iter().rposition(|&b| b == b'\n').map_or(0, |i| i + 1);
+    if complete < bytes.len() {
+        tracing::warn!(
+            "Dropping unfinished last line of {} ({} bytes)",
+            path.display(),
+            bytes.len() - complete
+        );
+        OpenOptions::new()
+            .write(true)
+            .open(path)
+            .and_then(|file| file.set_len(complete as u64))
+            .context("Failed to repair run journal")?;
+    }
+    Ok(())
+}
+
+/// Top-level keys whose values differ between two snapshots
+fn differing_keys(a: &Value, b: &Value) -> Vec<String> {
+    match (a, b) {
+        (Value::Object(a), Value::Object(b)) => {
+            let mut keys: Vec<String> = a
+                .keys()
+                .chain(b.keys())
+                .filter(|key| a.get(*key) != b.get(*key))
+                .cloned()
+                .collect();
+            keys.sort();
+            keys.dedup();
+            keys
+        }
+        _ => vec!["config".to_string()],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    fn manifest(snapshot: Value) -> RunManifest {
+        RunManifest {
+            run_id: "run-test".to_string(),
+            kind: RunKind::Compare,
+            started_at: Utc::now(),
+            planned_results: Some(4),
+            config_snapshot: snapshot,
+        }
+    }
+
+    #[test]
+    fn test_resume_restores_latest_results() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path().join("run-test");
+        let snapshot = json!({"config_a": 1, "config_b": 2});
+
+        let journal = RunJournal::create(dir.clone(), manifest(snapshot.clone())).unwrap();
+        let mut result = TaskResult::new("task-1".to_string());
+        journal.append("A", &result).unwrap();
+        result.mark_solved();
+        journal.append("A", &result).unwrap();
+        journal
+            .append("B", &TaskResult::new("task-1".to_string()))
+            .unwrap();
+        drop(journal);
+
+        // Crash in the middle of a write
+        let mut file = OpenOptions::new()
+            .append(true)
+            .open(dir.join(JOURNAL_FILE))
+            .unwrap();
+        file.write_all(b"{\"scope\":\"B\",\"resu").unwrap();
+
+        let journal = RunJournal::open(dir.clone(), &snapshot).unwrap();
+        assert_eq!(journal.restored_count(), 2);
+        assert!(journal.restored("A", "task-1", None).unwrap().solved);
+        assert!(!journal.restored("B", "task-1", None).unwrap().solved);
+        assert!(journal.restored("A", "task-1", Some(0)).is_none());
+        assert_eq!(count_results(&dir).unwrap(), 2);
+
+        // The first append after resuming starts on a line of its own
+        let mut result = TaskResult::new("task-2".to_string());
+        result.mark_solved();
+        journal.append("B", &result).unwrap();
+        drop(journal);
+
+        let journal = RunJournal::open(dir.clone(), &snapshot).unwrap();
+        assert_eq!(journal.restored_count(), 3);
+        assert!(journal.restored("B", "task-2", None).unwrap().solved);
+        assert_eq!(count_results(&dir).unwrap(), 3);
+    }
+
+    #[test]
+    fn test_open_rejects_changed_config() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path().join("run-test");
+        RunJournal::create(
+            dir.clone(),
+            manifest(json!({"config_a": 1, "tasks": ["t"]})),
+        )
+        .unwrap();
+
+        let err = RunJournal::open(dir, &json!({"config_a": 2, "tasks": ["t"]})).unwrap_err();
+        assert!(err.to_string().contains("differs in: config_a"), "{}", err);
+    }
+}
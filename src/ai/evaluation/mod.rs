@@ -19,6 +19,9 @@ pub use dataset_manager::{DatasetInfo, DatasetManager, DatasetSource};
 pub mod experiment_manager;
 pub use experiment_manager::{Experiment, ExperimentManager, ExperimentResults, ExperimentStatus};
 
+pub mod history;
+pub use history::{HistoryFilter, Metric, RegressionCheck, RunRecord, TrendPoint};
+
 // Phase 1: Multi-benchmark evaluation models
 pub mod models;
 pub use models::{
@@ -27,16 +30,39 @@ pub use models::{
 
 // Phase 3: Storage and serialization
 pub mod storage;
-pub use storage::StorageManager;
+pub use storage::{PartialRun, StorageManager};
+
+pub mod journal;
+pub use journal::{RunJournal, RunKind};
+
+pub mod sampling;
+pub use sampling::{PassAtK, SamplingConfig, TaskSamples};
+
+pub mod report;
+pub use report::{EvaluationReport, ReportFormat};
 
 /// Complexity level of a task
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub enum Complexity {
     Simple,
     Medium,
     Hard,
 }
 
+impl Complexity {
+    /// All complexities, easiest first
+    pub const ALL: [Complexity; 3] = [Complexity::Simple, Complexity::Medium, Complexity::Hard];
+
+    /// Lowercase name ("simple", "medium", "hard")
+    pub fn name(&self) -> &'static str {
+        match self {
+            Complexity::Simple => "simple",
+            Complexity::Medium => "medium",
+            Complexity::Hard => "hard",
+        }
+    }
+}
+
 /// Metadata from multi-model racing (M3+)
 ///
 /// Tracks which model won the race, costs incurred, and performance metrics.
@@ -199,6 +225,16 @@ pub struct TaskResult {
     /// - "security_score": 0.85
     #[serde(skip_serializing_if = "Option::is_none", default)]
     pub quality_signals: Option<std::collections::HashMap<String, f64>>,
+
+    /// Index of this attempt among the task's samples (sampling mode only)
+    ///
+    /// See [`sampling`]: each sample of a task is stored as its own result.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub sample: Option<usize>,
+
+    /// Complexity of the task, if its benchmark categorizes tasks
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub complexity: Option<Complexity>,
 }
 
 impl TaskResult {
@@ -220,6 +256,8 @@ impl TaskResult {
             cascade_metadata: None,
             behavioral_metrics: None,
             quality_signals: None,
+            sample: None,
+            complexity: None,
         }
     }
 
@@ -275,6 +313,34 @@ pub struct ComplexityStats {
     pub avg_cost: f64,
 }
 
+impl ComplexityStats {
+    /// Stats of the results of each complexity, keyed by [`Complexity::name`]
+    ///
+    /// Results without a complexity are left out.
+    pub fn by_complexity(results: &[TaskResult]) -> HashMap<String, ComplexityStats> {
+        let mut groups: HashMap<Complexity, Vec<&TaskResult>> = HashMap::new();
+        for result in results {
+            if let Some(complexity) = result.complexity {
+                groups.entry(complexity).or_default().push(result);
+            }
+        }
+        groups
+            .into_iter()
+            .map(|(complexity, results)| {
+                let total = results.len();
+                let solved = results.iter().filter(|r| r.solved).count();
+                let stats = ComplexityStats {
+                    total,
+                    solved,
+                    accuracy: solved as f64 / total as f64 * 100.0,
+                    avg_cost: results.iter().map(|r| r.cost_usd).sum::<f64>() / total as f64,
+                };
+                (complexity.name().to_string(), stats)
+            })
+            .collect()
+    }
+}
+
 impl EvaluationResults {
     /// Compute results from individual task results
     pub fn from_results(config_name: String, results: Vec<TaskResult>) -> Self {
@@ -298,6 +364,8 @@ impl EvaluationResults {
             0.0
         };
 
+        let by_complexity = ComplexityStats::by_complexity(&results);
+
         Self {
             config_name,
             results,
@@ -306,11 +374,23 @@ impl EvaluationResults {
             avg_duration_ms,
             total_tasks,
             tasks_solved,
-            by_complexity: HashMap::new(), // TODO: compute
+            by_complexity,
             timestamp: Utc::now(),
         }
     }
 
+    /// Samples per task (one per task unless run in sampling mode)
+    pub fn task_samples(&self) -> Vec<TaskSamples> {
+        sampling::group_by_task(&self.results)
+    }
+
+    /// pass@k over the evaluated tasks with a 95% bootstrap CI
+    ///
+    /// Returns None if some task has fewer than `k` samples.
+    pub fn pass_at_k(&self, k: usize) -> Option<PassAtK> {
+        PassAtK::estimate(&self.task_samples(), k, 0.95)
+    }
+
     /// Print summary
     pub fn print_summary(&self) {
         println!("\n=== Evaluation Results: {} ===", self.config_name);
@@ -321,6 +401,20 @@ impl EvaluationResults {
         println!("Avg Cost: ${:.4}/task", self.avg_cost_usd);
         println!("Avg Duration: {:.2}s/task", self.avg_duration_ms / 1000.0);
         println!("Total Tasks: {}", self.total_tasks);
+
+        let tasks = self.task_samples();
+        let samples = tasks.iter().map(|t| t.samples).min().unwrap_or(0);
+        if samples > 1 {
+            println!("Samples: {} per task ({} tasks)", samples, tasks.len());
+            for k in [1, samples] {
+                if let Some(pass) = PassAtK::estimate(&tasks, k, 0.95) {
+                    println!(
+                        "pass@{}: {:.3} (95% CI {:.3}-{:.3})",
+                        k, pass.value, pass.ci_lower, pass.ci_upper
+                    );
+                }
+            }
+        }
     }
 }
 
@@ -331,6 +425,12 @@ pub struct EvaluationHarness {
 
     /// Results storage path
     results_path: PathBuf,
+
+    /// Attempts per task
+    sampling: SamplingConfig,
+
+    /// Journal that every completed result is appended to
+    journal: Option<RunJournal>,
 }
 
 impl EvaluationHarness {
@@ -339,24 +439,90 @@ impl EvaluationHarness {
         Self {
             tasks,
             results_path,
+            sampling: SamplingConfig::default(),
+            journal: None,
         }
     }
 
+    /// Attempt each task as configured by `sampling` (for pass@k)
+    pub fn with_sampling(mut self, sampling: SamplingConfig) -> Self {
+        self.sampling = sampling;
+        self
+    }
+
+    /// Append every completed result to `journal`, skipping results it
+    /// already holds (see [`journal`])
+    pub fn with_journal(mut self, journal: RunJournal) -> Self {
+        self.journal = Some(journal);
+        self
+    }
+
+    /// Journal of this run, if any
+    pub fn journal(&self) -> Option<&RunJournal> {
+        self.journal.as_ref()
+    }
+
+    /// Settings a resumed run must match: the configurations under
+    /// evaluation, the task IDs, and the sampling
+    pub fn run_snapshot(&self, configs: &[&ToadConfig]) -> Result<serde_json::Value> {
+        let task_ids: Vec<&str> = self.tasks.iter().map(|task| task.id.as_str()).collect();
+        Ok(serde_json::json!({
+            "configs": configs.iter().map(serde_json::to_value).collect::<Result<Vec<_>, _>>()?,
+            "tasks": task_ids,
+            "sampling": self.sampling,
+        }))
+    }
+
+    /// Number of task results one configuration produces
+    pub fn planned_results(&self) -> usize {
+        self.tasks.len() * self.sampling.samples.max(1)
+    }
+
     /// Run evaluation with a specific configuration
+    ///
+    /// In sampling mode the results hold one entry per sample, tagged with
+    /// [`TaskResult::sample`].
     pub async fn evaluate(&self, config: &ToadConfig) -> Result<EvaluationResults> {
+        self.evaluate_scope(config, "eval").await
+    }
+
+    /// Evaluate `config`, journaling its results under `scope`
+    async fn evaluate_scope(&self, config: &ToadConfig, scope: &str) -> Result<EvaluationResults> {
         let config_name = format!("{} features", config.features.enabled_count());
         let mut results = Vec::new();
 
         for task in &self.tasks {
-            let result = self.run_task(task, config).await?;
-            results.push(result);
+            for sample in self.sampling.samples() {
+                let index = self.sampling.is_sampling().then_some(sample.index);
+                if let Some(result) = self
+                    .journal
+                    .as_ref()
+                    .and_then(|journal| journal.restored(scope, &task.id, index))
+                {
+                    tracing::debug!("Restored result of task {} from journal", task.id);
+                    results.push(result);
+                    continue;
+                }
+
+                let mut result = self.run_task(task, config, &sample).await?;
+                result.sample = index;
+                if let Some(journal) = &self.journal {
+                    journal.append(scope, &result)?;
+                }
+                results.push(result);
+            }
         }
 
         Ok(EvaluationResults::from_results(config_name, results))
     }
 
     /// Run a single task with the agent
-    async fn run_task(&self, task: &Task, config: &ToadConfig) -> Result<TaskResult> {
+    async fn run_task(
+        &self,
+        task: &Task,
+        config: &ToadConfig,
+        sample: &sampling::Sample,
+    ) -> Result<TaskResult> {
         use crate::ai::agent::Agent;
         use crate::ai::llm::{AnthropicClient, LLMProvider, get_api_key};
         use crate::ai::metrics::MetricsCollector;
@@ -404,7 +570,13 @@ impl EvaluationHarness {
             cascade_cost = Some(tier.estimated_cost_usd());
 
             // Route to appropriate model based on task difficulty
-            let provider_config = router.route(task)?;
+            let mut provider_config = router.route(task)?;
+            if let Some(temperature) = sample.temperature {
+                provider_config = provider_config.with_temperature(temperature);
+            }
+            if let Some(seed) = sample.seed {
+                provider_config = provider_config.with_seed(seed);
+            }
 
             cascade_duration_ms = Some(routing_start.elapsed().as_millis() as u64);
 
@@ -461,6 +633,10 @@ impl EvaluationHarness {
                 llm_client = llm_client.with_prompt_caching(true);
             }
 
+            if let Some(temperature) = sample.temperature {
+                llm_client = llm_client.with_temperature(temperature);
+            }
+
             Box::new(llm_client)
         };
 
@@ -536,6 +712,7 @@ impl EvaluationHarness {
         // Build task result
         let final_metrics = metrics_collector.finish();
         let mut result = TaskResult::new(task.id.clone());
+        result.complexity = Some(task.complexity);
 
         result.duration_ms = final_metrics.duration_ms;
         result.cost_usd = final_metrics.cost_usd;
@@ -615,8 +792,8 @@ impl EvaluationHarness {
         config_a: &ToadConfig,
         config_b: &ToadConfig,
     ) -> Result<(EvaluationResults, EvaluationResults)> {
-        let results_a = self.evaluate(config_a).await?;
-        let results_b = self.evaluate(config_b).await?;
+        let results_a = self.evaluate_scope(config_a, "A").await?;
+        let results_b = self.evaluate_scope(config_b, "B").await?;
         Ok((results_a, results_b))
     }
 
@@ -636,6 +813,24 @@ impl EvaluationHarness {
 
         Ok(())
     }
+
+    /// Save an A/B comparison to disk (input for `toad report`)
+    pub fn save_comparison(&self, comparison: &crate::ai::stats::ComparisonResult) -> Result<()> {
+        std::fs::create_dir_all(&self.results_path)?;
+
+        let filename = format!(
+            "comparison_{}_vs_{}_{}.json",
+            comparison.config_a.replace(' ', "_"),
+            comparison.config_b.replace(' ', "_"),
+            Utc::now().timestamp()
+        );
+
+        let path = self.results_path.join(filename);
+        let json = serde_json::to_string_pretty(comparison)?;
+        std::fs::write(path, json)?;
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -678,6 +873,28 @@ mod tests {
         assert_eq!(eval.total_tasks, 10);
     }
 
+    #[test]
+    fn test_evaluation_results_pass_at_k() {
+        let mut results = vec![];
+        for task in 0..4 {
+            for sample in 0..3 {
+                let mut result = TaskResult::new(format!("task-{}", task));
+                result.sample = Some(sample);
+                if task < 2 && sample == 0 {
+                    result.mark_solved();
+                }
+                results.push(result);
+            }
+        }
+
+        let eval = EvaluationResults::from_results("sampled".to_string(), results);
+        assert_eq!(eval.task_samples().len(), 4);
+        let pass_at_1 = eval.pass_at_k(1).unwrap();
+        assert!((pass_at_1.value - 1.0 / 6.0).abs() < 1e-12);
+        assert_eq!(eval.pass_at_k(3).unwrap().value, 0.5);
+        assert!(eval.pass_at_k(4).is_none());
+    }
+
     #[tokio::test]
     #[ignore] // Requires ANTHROPIC_API_KEY, run with `cargo test -- --ignored`
     async fn test_harness_evaluate() {
@@ -49,6 +49,7 @@
 //! ```
 
 use crate::ai::evaluation::TaskResult;
+use crate::ai::evaluation::sampling::{PassAtK, TaskSamples, group_by_task};
 use crate::benchmarks::BenchmarkMetadata;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
@@ -181,6 +182,7 @@ pub struct BenchmarkResult {
 
     /// Success rate: tasks_solved / tasks_attempted
     ///
+    /// Counts every sample in sampling mode, which makes it pass@1.
     /// Range: [0.0, 1.0]
     pub success_rate: f64,
 
@@ -227,6 +229,18 @@ impl BenchmarkResult {
         let total_duration: u64 = self.task_results.iter().map(|t| t.duration_ms).sum();
         self.avg_duration_per_task_ms = total_duration as f64 / self.task_results.len() as f64;
     }
+
+    /// Samples per task (one per task unless run in sampling mode)
+    pub fn task_samples(&self) -> Vec<TaskSamples> {
+        group_by_task(&self.task_results)
+    }
+
+    /// pass@k over this benchmark's tasks with a 95% bootstrap CI
+    ///
+    /// Returns None if some task has fewer than `k` samples.
+    pub fn pass_at_k(&self, k: usize) -> Option<PassAtK> {
+        PassAtK::estimate(&self.task_samples(), k, 0.95)
+    }
 }
 
 /// Aggregate metrics across multiple benchmarks
@@ -0,0 +1,1320 @@
+//! Self-contained evaluation reports
+//!
+//! Turns one or more result files into a single offline HTML page (inline CSS
+//! and SVG charts, no external assets) or a Markdown document for pull
+//! requests.
+//!
+//! # Inputs
+//!
+//! The kind of each file is detected from its fields:
+//! - [`EvaluationRun`]: orchestrator runs (`toad eval --benchmarks`)
+//! - [`EvaluationResults`]: one configuration (`toad eval`, each arm of `toad compare`)
+//! - [`ComparisonResult`]: A/B statistics (`toad compare`)
+//!
+//! Every run or results file becomes one configuration of the report.
+//!
+//! # Sections
+//!
+//! - Summary: tasks, pass@1 with a 95% bootstrap CI, cost and tokens
+//! - Cost and latency distributions (quartiles, p90, extremes)
+//! - Accuracy by task complexity (if results carry one)
+//! - Behavior: [`BehavioralMetrics`] and tool usage per task
+//! - Task differences: tasks whose outcome differs between configurations
+//! - Comparisons: deltas and significance tests
+//! - Per-task results, linking each task to its trajectory
+//!
+//! # Trajectories
+//!
+//! With [`with_trajectories`](EvaluationReport::with_trajectories), a task
+//! links to `{dir}/{config}/{task}.{ext}` or `{dir}/{task}.{ext}` (extension
+//! `json`, `jsonl`, `md` or `txt`), if one exists. Characters other than
+//! letters, digits, `-`, `_` and `.` in names are replaced with `_`.
+//!
+//! # Examples
+//!
+//! ```
+//! use toad::ai::evaluation::report::{EvaluationReport, ReportFormat};
+//! use toad::ai::evaluation::{EvaluationResults, TaskResult};
+//!
+//! let mut solved = TaskResult::new("task-1".to_string());
+//! solved.mark_solved();
+//! let results = EvaluationResults::from_results(
+//!     "M1".to_string(),
+//!     vec![solved, TaskResult::new("task-2".to_string())],
+//! );
+//!
+//! let mut report = EvaluationReport::new("Nightly");
+//! report.add_results(&results);
+//!
+//! let markdown = report.render(ReportFormat::Markdown);
+//! assert!(markdown.contains("# Nightly"));
+//! assert!(report.render(ReportFormat::Html).contains("<svg"));
+//! ```
+
+use crate::ai::evaluation::models::{BehavioralMetrics, EvaluationRun};
+use crate::ai::evaluation::sampling::{PassAtK, group_by_task};
+use crate::ai::evaluation::{Complexity, ComplexityStats, EvaluationResults, TaskResult};
+use crate::ai::stats::ComparisonResult;
+use anyhow::{Context, Result};
+use chrono::Utc;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+
+/// Longest error excerpt shown for a task
+const MAX_ERROR_CHARS: usize = 120;
+
+/// Extensions a trajectory file may have, in lookup order
+const TRAJECTORY_EXTENSIONS: [&str; 4] = ["json", "jsonl", "md", "txt"];
+
+/// Output format of a report
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportFormat {
+    /// Single offline HTML page with SVG charts
+    Html,
+    /// GitHub-flavored Markdown (no charts)
+    Markdown,
+}
+
+impl ReportFormat {
+    /// Format named `name` (`html`, `markdown` or `md`)
+    pub fn parse(name: &str) -> Result<Self> {
+        match name.to_lowercase().as_str() {
+            "html" => Ok(Self::Html),
+            "markdown" | "md" => Ok(Self::Markdown),
+            _ => anyhow::bail!("Unknown report format: '{}' (use html or markdown)", name),
+        }
+    }
+
+    /// Format implied by the extension of `path` (HTML unless `.md`)
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("md" | "markdown") => Self::Markdown,
+            _ => Self::Html,
+        }
+    }
+
+    /// File extension of the format
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Html => "html",
+            Self::Markdown => "md",
+        }
+    }
+}
+
+/// Results of one configuration in a report
+#[derive(Debug, Clone)]
+pub struct ReportConfig {
+    /// Configuration name (run ID for orchestrator runs)
+    pub label: String,
+
+    /// Task results, one per task and sample
+    pub results: Vec<TaskResult>,
+
+    /// Run-level behavioral metrics, if the run computed them
+    pub behavioral: Option<BehavioralMetrics>,
+}
+
+/// Report over one or more evaluation results
+#[derive(Debug, Clone)]
+pub struct EvaluationReport {
+    title: String,
+    configs: Vec<ReportConfig>,
+    comparisons: Vec<ComparisonResult>,
+    trajectories: Option<PathBuf>,
+}
+
+impl EvaluationReport {
+    /// Create an empty report
+    pub fn new(title: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            configs: Vec::new(),
+            comparisons: Vec::new(),
+            trajectories: None,
+        }
+    }
+
+    /// Link tasks to trajectory files under `dir`
+    pub fn with_trajectories(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.trajectories = Some(dir.into());
+        self
+    }
+
+    /// Configurations in the report, in the order they were added
+    pub fn configs(&self) -> &[ReportConfig] {
+        &self.configs
+    }
+
+    /// Add the result file at `path` (run, results or comparison)
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file can't be read or is not one of the
+    /// supported result files.
+    pub fn add_file(&mut self, path: &Path) -> Result<()> {
+        let json = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let value: Value = serde_json::from_str(&json)
+            .with_context(|| format!("Failed to parse {}", path.display()))?;
+        let has = |key: &str| value.get(key).is_some();
+
+        if has("benchmark_results") {
+            let run: EvaluationRun = serde_json::from_value(value)
+                .with_context(|| format!("Invalid evaluation run {}", path.display()))?;
+            self.add_run(&run);
+        } else if has("config_a") && has("delta") {
+            let comparison: ComparisonResult = serde_json::from_value(value)
+                .with_context(|| format!("Invalid comparison {}", path.display()))?;
+            self.add_comparison(comparison);
+        } else if has("config_name") && has("results") {
+            let results: EvaluationResults = serde_json::from_value(value)
+                .with_context(|| format!("Invalid evaluation results {}", path.display()))?;
+            self.add_results(&results);
+        } else {
+            anyhow::bail!(
+                "{} is not an evaluation run, results or comparison file",
+                path.display()
+            );
+        }
+        Ok(())
+    }
+
+    /// Add an orchestrator run as one configuration
+    pub fn add_run(&mut self, run: &EvaluationRun) {
+        let results = run
+            .benchmark_results
+            .iter()
+            .flat_map(|b| b.task_results.iter().cloned())
+            .collect();
+        self.push_config(
+            run.run_id.clone(),
+            results,
+            run.aggregate_metrics.behavioral_metrics.clone(),
+        );
+    }
+
+    /// Add the results of one configuration
+    pub fn add_results(&mut self, results: &EvaluationResults) {
+        self.push_config(results.config_name.clone(), results.results.clone(), None);
+    }
+
+    /// Add an A/B comparison
+    pub fn add_comparison(&mut self, comparison: ComparisonResult) {
+        self.comparisons.push(comparison);
+    }
+
+    fn push_config(
+        &mut self,
+        label: String,
+        results: Vec<TaskResult>,
+        behavioral: Option<BehavioralMetrics>,
+    ) {
+        // Two runs of the same configuration stay apart
+        let taken = |label: &str| self.configs.iter().any(|c| c.label == label);
+        let mut unique = label.clone();
+        let mut n = 2;
+        while taken(&unique) {
+            unique = format!("{} ({})", label, n);
+            n += 1;
+        }
+        self.configs.push(ReportConfig {
+            label: unique,
+            results,
+            behavioral,
+        });
+    }
+
+    /// Render the report
+    pub fn render(&self, format: ReportFormat) -> String {
+        let sections = self.sections(format == ReportFormat::Html);
+        match format {
+            ReportFormat::Html => render_html(&self.title, &sections),
+            ReportFormat::Markdown => render_markdown(&self.title, &sections),
+        }
+    }
+
+    /// Render the report to `path`, creating its directory
+    pub fn write(&self, path: &Path, format: ReportFormat) -> Result<()> {
+        if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+            std::fs::create_dir_all(dir)
+                .with_context(|| format!("Failed to create {}", dir.display()))?;
+        }
+        std::fs::write(path, self.render(format))
+            .with_context(|| format!("Failed to write report {}", path.display()))
+    }
+
+    fn sections(&self, charts: bool) -> Vec<Section> {
+        let summaries: Vec<ConfigSummary> = self.configs.iter().map(ConfigSummary::new).collect();
+
+        let mut sections = vec![self.summary_section(&summaries, charts)];
+        if !self.configs.is_empty() {
+            sections.push(self.distribution_section(&summaries, charts));
+        }
+        sections.extend(self.complexity_section());
+        if !self.configs.is_empty() {
+            sections.push(self.behavior_section());
+        }
+        sections.extend(self.diff_section());
+        sections.extend(self.comparison_section());
+        sections.extend(self.tasks_section());
+        sections
+    }
+
+    fn summary_section(&self, summaries: &[ConfigSummary], charts: bool) -> Section {
+        let mut blocks = vec![Block::Text(format!(
+            "Generated {} from {} configuration(s) and {} comparison(s).",
+            Utc::now().format("%Y-%m-%d %H:%M UTC"),
+            self.configs.len(),
+            self.comparisons.len()
+        ))];
+        if self.configs.is_empty() {
+            return Section::new("Summary", blocks);
+        }
+
+        let mut table = Table::new(&[
+            "Configuration",
+            "Tasks",
+            "Attempts",
+            "Solved",
+            "pass@1",
+            "95% CI",
+            "Total cost",
+            "Mean tokens",
+        ]);
+        for (config, summary) in self.configs.iter().zip(summaries) {
+            let (pass, ci) = match &summary.pass_at_1 {
+                Some(p) => (
+                    percent(p.value),
+                    format!("{} – {}", percent(p.ci_lower), percent(p.ci_upper)),
+                ),
+                None => ("—".to_string(), "—".to_string()),
+            };
+            table.row(vec![
+                config.label.clone().into(),
+                summary.tasks.to_string().into(),
+                summary.attempts.to_string().into(),
+                summary.solved.to_string().into(),
+                pass.into(),
+                ci.into(),
+                usd(summary.total_cost).into(),
+                format!("{:.0}", summary.mean_tokens).into(),
+            ]);
+        }
+        blocks.push(Block::Table(table));
+        if charts {
+            let bars: Vec<(&str, Option<&PassAtK>)> = self
+                .configs
+                .iter()
+                .zip(summaries)
+                .map(|(c, s)| (c.label.as_str(), s.pass_at_1.as_ref()))
+                .collect();
+            blocks.push(Block::Chart(accuracy_chart(&bars)));
+        }
+        Section::new("Summary", blocks)
+    }
+
+    fn distribution_section(&self, summaries: &[ConfigSummary], charts: bool) -> Section {
+        let mut blocks = Vec::new();
+        for (title, pick, format) in [
+            (
+                "Cost per attempt",
+                (|s: &ConfigSummary| s.cost.as_ref())
+                    as fn(&ConfigSummary) -> Option<&Distribution>,
+                usd as fn(f64) -> String,
+            ),
+            ("Latency per attempt", |s| s.latency.as_ref(), seconds),
+        ] {
+            blocks.push(Block::Text(format!("**{}**", title)));
+            let mut table = Table::new(&[
+                "Configuration",
+                "Min",
+                "p25",
+                "Median",
+                "p75",
+                "p90",
+                "Max",
+                "Mean",
+            ]);
+            for (config, summary) in self.configs.iter().zip(summaries) {
+                let mut row: Vec<Cell> = vec![config.label.clone().into()];
+                match pick(summary) {
+                    Some(d) => row.extend(
+                        [d.min, d.p25, d.median, d.p75, d.p90, d.max, d.mean]
+                            .map(|v| Cell::from(format(v))),
+                    ),
+                    None => row.extend((0..7).map(|_| Cell::from("—"))),
+                }
+                table.row(row);
+            }
+            blocks.push(Block::Table(table));
+            if charts {
+                let boxes: Vec<(&str, Option<&Distribution>)> = self
+                    .configs
+                    .iter()
+                    .zip(summaries)
+                    .map(|(c, s)| (c.label.as_str(), pick(s)))
+                    .collect();
+                blocks.push(Block::Chart(box_chart(&boxes, format)));
+            }
+        }
+        Section::new("Cost and Latency", blocks)
+    }
+
+    fn complexity_section(&self) -> Option<Section> {
+        let stats: Vec<HashMap<String, ComplexityStats>> = self
+            .configs
+            .iter()
+            .map(|c| ComplexityStats::by_complexity(&c.results))
+            .collect();
+        if stats.iter().all(HashMap::is_empty) {
+            return None;
+        }
+
+        let mut headers = vec!["Complexity".to_string()];
+        headers.extend(self.configs.iter().map(|c| c.label.clone()));
+        let mut table = Table::from_headers(headers);
+        for complexity in Complexity::ALL {
+            let mut row: Vec<Cell> = vec![complexity.name().into()];
+            for config_stats in &stats {
+                row.push(match config_stats.get(complexity.name()) {
+                    Some(s) => format!(
+                        "{}/{} ({:.1}%, {}/attempt)",
+                        s.solved,
+                        s.total,
+                        s.accuracy,
+                        usd(s.avg_cost)
+                    )
+                    .into(),
+                    None => "—".into(),
+                });
+            }
+            table.row(row);
+        }
+        Some(Section::new("By Complexity", vec![Block::Table(table)]))
+    }
+
+    fn behavior_section(&self) -> Section {
+        let mut table = Table::new(&[
+            "Configuration",
+            "Hallucination rate",
+            "Tool calls / solved",
+            "Autonomy",
+            "Error recovery",
+            "Steps",
+            "API calls",
+            "Files read",
+            "Files written",
+            "Edits",
+            "Test runs",
+        ]);
+        for config in &self.configs {
+            let mut row: Vec<Cell> = vec![config.label.clone().into()];
+            match behavioral_metrics(config) {
+                Some(b) => row.extend([
+                    percent(b.hallucination_rate).into(),
+                    format!("{:.1}", b.tool_use_efficiency).into(),
+                    percent(b.autonomy_score).into(),
+                    percent(b.error_recovery_rate).into(),
+                ]),
+                None => row.extend((0..4).map(|_| Cell::from("—"))),
+            }
+            let mean = |f: fn(&TaskResult) -> f64| {
+                let n = config.results.len().max(1) as f64;
+                format!(
+                    "{:.1}",
+                    config.results.iter().map(f).fold(0.0, |a, b| a + b) / n
+                )
+            };
+            row.extend([
+                mean(|r| r.metrics.agent_steps as f64).into(),
+                mean(|r| r.api_calls as f64).into(),
+                mean(|r| r.metrics.files_read as f64).into(),
+                mean(|r| r.metrics.files_written as f64).into(),
+                mean(|r| r.metrics.edit_attempts as f64).into(),
+                mean(|r| r.metrics.test_runs as f64).into(),
+            ]);
+            table.row(row);
+        }
+        Section::new(
+            "Behavior and Tool Usage",
+            vec![
+                Block::Text(
+                    "Tool usage columns are means per attempt. Behavioral metrics come from the \
+                     run, or are averaged over the tasks that tracked them."
+                        .to_string(),
+                ),
+                Block::Table(table),
+            ],
+        )
+    }
+
+    fn diff_section(&self) -> Option<Section> {
+        if self.configs.len() < 2 {
+            return None;
+        }
+
+        let outcomes: Vec<HashMap<&str, Outcome>> =
+            self.configs.iter().map(|c| outcomes(&c.results)).collect();
+        let mut task_ids: Vec<&str> = Vec::new();
+        for config in &self.configs {
+            for result in &config.results {
+                if !task_ids.contains(&result.task_id.as_str()) {
+                    task_ids.push(&result.task_id);
+                }
+            }
+        }
+
+        let shared = task_ids
+            .iter()
+            .filter(|id| outcomes.iter().all(|o| o.contains_key(*id)))
+            .count();
+        let mut headers = vec!["Task".to_string()];
+        headers.extend(self.configs.iter().map(|c| c.label.clone()));
+        let mut table = Table::from_headers(headers);
+        let mut differing = 0;
+        for id in &task_ids {
+            let statuses: Vec<String> = outcomes
+                .iter()
+                .map(|o| {
+                    o.get(id)
+                        .map(Outcome::status)
+                        .unwrap_or_else(|| "—".to_string())
+                })
+                .collect();
+            if statuses.iter().all(|s| s == &statuses[0]) {
+                continue;
+            }
+            differing += 1;
+            let mut row: Vec<Cell> = vec![(*id).into()];
+            for (config, status) in self.configs.iter().zip(statuses) {
+                row.push(Cell {
+                    text: status,
+                    link: self.trajectory(&config.label, id),
+                });
+            }
+            table.row(row);
+        }
+
+        let mut blocks = vec![Block::Text(format!(
+            "{} of {} tasks have different outcomes ({} tasks ran in every configuration). \
+             Cells link to trajectories where available.",
+            differing,
+            task_ids.len(),
+            shared
+        ))];
+        if differing > 0 {
+            blocks.push(Block::Table(table));
+        }
+        Some(Section::new("Task Differences", blocks))
+    }
+
+    fn comparison_section(&self) -> Option<Section> {
+        if self.comparisons.is_empty() {
+            return None;
+        }
+        let mut blocks = Vec::new();
+        for comparison in &self.comparisons {
+            blocks.push(Block::Text(format!(
+                "**{} → {}**: recommendation {:?}",
+                comparison.config_a, comparison.config_b, comparison.recommendation
+            )));
+            let significance = &comparison.significance;
+            let paired = significance.paired.as_ref();
+            let mut table = Table::new(&["Metric", "Delta (B − A)", "p-value", "Significant"]);
+            table.row(vec![
+                "Accuracy".into(),
+                format!("{:+.2} pp", comparison.delta.accuracy).into(),
+                format!("{:.4}", significance.accuracy_p_value).into(),
+                yes_no(significance.accuracy_significant).into(),
+            ]);
+            table.row(vec![
+                "Cost".into(),
+                format!(
+                    "{:+.4} USD ({:+.1}%)",
+                    comparison.delta.cost_usd, comparison.delta.cost_pct
+                )
+                .into(),
+                format!("{:.4}", significance.cost_p_value).into(),
+                yes_no(significance.cost_significant).into(),
+            ]);
+            table.row(vec![
+                "Latency".into(),
+                format!(
+                    "{:+.0} ms ({:+.1}%)",
+                    comparison.delta.duration_ms, comparison.delta.duration_pct
+                )
+                .into(),
+                paired
+                    .map(|p| format!("{:.4}", p.latency.p_value))
+                    .unwrap_or_else(|| "—".to_string())
+                    .into(),
+                paired
+                    .map(|p| yes_no(p.latency.significant))
+                    .unwrap_or("—")
+                    .into(),
+            ]);
+            blocks.push(Block::Table(table));
+
+            if let Some(paired) = paired {
+                blocks.push(Block::Text(format!(
+                    "Paired on {} tasks: {} solved only by A, {} only by B{}.",
+                    paired.tasks,
+                    paired.accuracy.only_a,
+                    paired.accuracy.only_b,
+                    paired
+                        .required_tasks
+                        .map(|n| format!("; {} tasks needed for 80% power", n))
+                        .unwrap_or_default()
+                )));
+            }
+            if !comparison.pass_at_k.is_empty() {
+                let mut table = Table::new(&["k", "A", "B", "Delta", "Delta 95% CI"]);
+                for pass in &comparison.pass_at_k {
+                    table.row(vec![
+                        pass.k.to_string().into(),
+                        pass_with_ci(&pass.a).into(),
+                        pass_with_ci(&pass.b).into(),
+                        format!("{:+.3}", pass.delta).into(),
+                        pass.delta_ci
+                            .map(|(lo, hi)| format!("{:+.3} – {:+.3}", lo, hi))
+                            .unwrap_or_else(|| "—".to_string())
+                            .into(),
+                    ]);
+                }
+                blocks.push(Block::Table(table));
+            }
+        }
+        Some(Section::new("Comparisons", blocks))
+    }
+
+    fn tasks_section(&self) -> Option<Section> {
+        if self.configs.is_empty() {
+            return None;
+        }
+        let blocks = self
+            .configs
+            .iter()
+            .map(|config| {
+                let mut table =
+                    Table::new(&["Task", "Result", "Cost", "Duration", "Steps", "Error"]);
+                for result in &config.results {
+                    let id = match result.sample {
+                        Some(sample) => format!("{} #{}", result.task_id, sample),
+                        None => result.task_id.clone(),
+                    };
+                    table.row(vec![
+                        Cell {
+                            text: id,
+                            link: self.trajectory(&config.label, &result.task_id),
+                        },
+                        if result.solved { "solved" } else { "failed" }.into(),
+                        usd(result.cost_usd).into(),
+                        seconds(result.duration_ms as f64).into(),
+                        result.metrics.agent_steps.to_string().into(),
+                        result
+                            .error
+                            .as_deref()
+                            .map(error_excerpt)
+                            .unwrap_or_default()
+                            .into(),
+                    ]);
+                }
+                let solved = config.results.iter().filter(|r| r.solved).count();
+                Block::Details {
+                    summary: format!(
+                        "{}: {}/{} attempts solved",
+                        config.label,
+                        solved,
+                        config.results.len()
+                    ),
+                    table,
+                }
+            })
+            .collect();
+        Some(Section::new("Tasks", blocks))
+    }
+
+    /// Trajectory file of `task_id` in configuration `config`, if one exists
+    fn trajectory(&self, config: &str, task_id: &str) -> Option<String> {
+        let dir = self.trajectories.as_ref()?;
+        let task = file_name(task_id);
+        [dir.join(file_name(config)), dir.clone()]
+            .iter()
+            .flat_map(|dir| {
+                TRAJECTORY_EXTENSIONS
+                    .iter()
+                    .map(|ext| dir.join(format!("{}.{}", task, ext)))
+            })
+            .find(|path| path.is_file())
+            .map(|path| path.display().to_string())
+    }
+}
+
+/// Headline numbers of one configuration
+struct ConfigSummary {
+    tasks: usize,
+    attempts: usize,
+    solved: usize,
+    pass_at_1: Option<PassAtK>,
+    total_cost: f64,
+    mean_tokens: f64,
+    cost: Option<Distribution>,
+    latency: Option<Distribution>,
+}
+
+impl ConfigSummary {
+    fn new(config: &ReportConfig) -> Self {
+        let results = &config.results;
+        let attempts = results.len();
+        Self {
+            tasks: group_by_task(results).len(),
+            attempts,
+            solved: results.iter().filter(|r| r.solved).count(),
+            pass_at_1: PassAtK::estimate(&group_by_task(results), 1, 0.95),
+            // Folded from +0.0: an empty f64 sum is -0.0
+            total_cost: results.iter().fold(0.0, |sum, r| sum + r.cost_usd),
+            mean_tokens: results.iter().map(|r| r.total_tokens).sum::<u64>() as f64
+                / attempts.max(1) as f64,
+            cost: Distribution::new(results.iter().map(|r| r.cost_usd).collect()),
+            latency: Distribution::new(results.iter().map(|r| r.duration_ms as f64).collect()),
+        }
+    }
+}
+
+/// Summary of a sample of values
+#[derive(Debug, Clone, PartialEq)]
+struct Distribution {
+    min: f64,
+    p25: f64,
+    median: f64,
+    p75: f64,
+    p90: f64,
+    max: f64,
+    mean: f64,
+}
+
+impl Distribution {
+    /// Summarize `values`, or None if there are none
+    fn new(mut values: Vec<f64>) -> Option<Self> {
+        if values.is_empty() {
+            return None;
+        }
+        values.sort_by(f64::total_cmp);
+        let quantile = |q: f64| {
+            let rank = q * (values.len() - 1) as f64;
+            let (lo, hi) = (rank.floor() as usize, rank.ceil() as usize);
+            values[lo] + (values[hi] - values[lo]) * (rank - lo as f64)
+        };
+        Some(Self {
+            min: values[0],
+            p25: quantile(0.25),
+            median: quantile(0.5),
+            p75: quantile(0.75),
+            p90: quantile(0.9),
+            max: values[values.len() - 1],
+            mean: values.iter().sum::<f64>() / values.len() as f64,
+        })
+    }
+}
+
+/// Solved samples of one task
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Outcome {
+    solved: usize,
+    attempts: usize,
+}
+
+impl Outcome {
+    fn status(&self) -> String {
+        match self.attempts {
+            1 if self.solved == 1 => "solved".to_string(),
+            1 => "failed".to_string(),
+            n => format!("{}/{} solved", self.solved, n),
+        }
+    }
+}
+
+fn outcomes(results: &[TaskResult]) -> HashMap<&str, Outcome> {
+    let mut outcomes: HashMap<&str, Outcome> = HashMap::new();
+    for result in results {
+        let outcome = outcomes.entry(&result.task_id).or_insert(Outcome {
+            solved: 0,
+            attempts: 0,
+        });
+        outcome.attempts += 1;
+        outcome.solved += usize::from(result.solved);
+    }
+    outcomes
+}
+
+/// Run-level behavioral metrics, or the mean over tasks that tracked them
+fn behavioral_metrics(config: &ReportConfig) -> Option<BehavioralMetrics> {
+    if let Some(behavioral) = &config.behavioral {
+        return Some(behavioral.clone());
+    }
+    let tracked: Vec<&BehavioralMetrics> = config
+        .results
+        .iter()
+        .filter_map(|r| r.behavioral_metrics.as_ref())
+        .collect();
+    if tracked.is_empty() {
+        return None;
+    }
+    let mean = |f: fn(&BehavioralMetrics) -> f64| {
+        tracked.iter().map(|b| f(b)).sum::<f64>() / tracked.len() as f64
+    };
+    Some(BehavioralMetrics {
+        hallucination_rate: mean(|b| b.hallucination_rate),
+        tool_use_efficiency: mean(|b| b.tool_use_efficiency),
+        autonomy_score: mean(|b| b.autonomy_score),
+        error_recovery_rate: mean(|b| b.error_recovery_rate),
+    })
+}
+
+fn percent(value: f64) -> String {
+    format!("{:.1}%", value * 100.0)
+}
+
+fn usd(value: f64) -> String {
+    format!("${:.4}", value)
+}
+
+fn seconds(ms: f64) -> String {
+    format!("{:.1}s", ms / 1000.0)
+}
+
+fn yes_no(value: bool) -> &'static str {
+    if value { "yes" } else { "no" }
+}
+
+fn pass_with_ci(pass: &PassAtK) -> String {
+    format!(
+        "{:.3} [{:.3}, {:.3}]",
+        pass.value, pass.ci_lower, pass.ci_upper
+    )
+}
+
+/// First line of an error, shortened
+fn error_excerpt(error: &str) -> String {
+    let line = error.lines().next().unwrap_or_default();
+    if line.chars().count() <= MAX_ERROR_CHARS {
+        return line.to_string();
+    }
+    let mut excerpt: String = line.chars().take(MAX_ERROR_CHARS).collect();
+    excerpt.push('…');
+    excerpt
+}
+
+/// `name` with characters unsafe in file names replaced
+fn file_name(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+/// Titled part of a report
+struct Section {
+    title: String,
+    blocks: Vec<Block>,
+}
+
+impl Section {
+    fn new(title: &str, blocks: Vec<Block>) -> Self {
+        Self {
+            title: title.to_string(),
+            blocks,
+        }
+    }
+}
+
+enum Block {
+    /// Paragraph (Markdown `**bold**` is supported in both formats)
+    Text(String),
+    Table(Table),
+    /// SVG chart (HTML only)
+    Chart(String),
+    /// Collapsed table
+    Details {
+        summary: String,
+        table: Table,
+    },
+}
+
+struct Table {
+    headers: Vec<String>,
+    rows: Vec<Vec<Cell>>,
+}
+
+impl Table {
+    fn new(headers: &[&str]) -> Self {
+        Self::from_headers(headers.iter().map(|h| h.to_string()).collect())
+    }
+
+    fn from_headers(headers: Vec<String>) -> Self {
+        Self {
+            headers,
+            rows: Vec::new(),
+        }
+    }
+
+    fn row(&mut self, cells: Vec<Cell>) {
+        self.rows.push(cells);
+    }
+}
+
+struct Cell {
+    text: String,
+    link: Option<String>,
+}
+
+impl From<String> for Cell {
+    fn from(text: String) -> Self {
+        Self { text, link: None }
+    }
+}
+
+impl From<&str> for Cell {
+    fn from(text: &str) -> Self {
+        text.to_string().into()
+    }
+}
+
+fn render_markdown(title: &str, sections: &[Section]) -> String {
+    let mut out = format!("# {}\n", title);
+    for section in sections {
+        let _ = write!(out, "\n## {}\n", section.title);
+        for block in &section.blocks {
+            match block {
+                Block::Text(text) => {
+                    let _ = write!(out, "\n{}\n", text);
+                }
+                Block::Table(table) => {
+                    out.push('\n');
+                    markdown_table(&mut out, table);
+                }
+                Block::Chart(_) => {}
+                Block::Details { summary, table } => {
+                    let _ = write!(out, "\n<details>\n<summary>{}</summary>\n\n", summary);
+                    markdown_table(&mut out, table);
+                    out.push_str("\n</details>\n");
+                }
+            }
+        }
+    }
+    out
+}
+
+fn markdown_table(out: &mut String, table: &Table) {
+    let cell = |text: &str| text.replace('|', "\\|").replace('\n', " ");
+    let headers: Vec<String> = table.headers.iter().map(|h| cell(h)).collect();
+    let _ = writeln!(out, "| {} |", headers.join(" | "));
+    let _ = writeln!(out, "|{}", "---|".repeat(headers.len()));
+    for row in &table.rows {
+        let cells: Vec<String> = row
+            .iter()
+            .map(|c| match &c.link {
+                Some(link) => format!("[{}]({})", cell(&c.text), link.replace(' ', "%20")),
+                None => cell(&c.text),
+            })
+            .collect();
+        let _ = writeln!(out, "| {} |", cells.join(" | "));
+    }
+}
+
+const STYLE: &str = "body{font-family:system-ui,sans-serif;max-width:1100px;margin:2em auto;\
+padding:0 1em;color:#222}h1{border-bottom:2px solid #3a7d44}h2{margin-top:2em;color:#3a7d44}\
+table{border-collapse:collapse;margin:1em 0;font-size:.9em}th,td{border:1px solid #ccc;\
+padding:4px 8px;text-align:left}th{background:#f0f4f0}tr:nth-child(even) td{background:#fafafa}\
+svg{display:block;margin:1em 0}svg text{font-size:12px;font-family:system-ui,sans-serif}\
+summary{cursor:pointer;margin:.5em 0}";
+
+fn render_html(title: &str, sections: &[Section]) -> String {
+    let mut out = String::new();
+    let _ = write!(
+        out,
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>{}</title>\n<style>{}</style>\n</head>\n<body>\n<h1>{}</h1>\n",
+        escape(title),
+        STYLE,
+        escape(title)
+    );
+    for section in sections {
+        let _ = writeln!(out, "<h2>{}</h2>", escape(&section.title));
+        for block in &section.blocks {
+            match block {
+                Block::Text(text) => {
+                    let _ = writeln!(out, "<p>{}</p>", bold(&escape(text)));
+                }
+                Block::Table(table) => html_table(&mut out, table),
+                Block::Chart(svg) => {
+                    out.push_str(svg);
+                    out.push('\n');
+                }
+                Block::Details { summary, table } => {
+                    let _ = writeln!(out, "<details>\n<summary>{}</summary>", escape(summary));
+                    html_table(&mut out, table);
+                    out.push_str("</details>\n");
+                }
+            }
+        }
+    }
+    out.push_str("</body>\n</html>\n");
+    out
+}
+
+fn html_table(out: &mut String, table: &Table) {
+    out.push_str("<table>\n<tr>");
+    for header in &table.headers {
+        let _ = write!(out, "<th>{}</th>", escape(header));
+    }
+    out.push_str("</tr>\n");
+    for row in &table.rows {
+        out.push_str("<tr>");
+        for cell in row {
+            match &cell.link {
+                Some(link) => {
+                    let _ = write!(
+                        out,
+                        "<td><a href=\"{}\">{}</a></td>",
+                        escape(link),
+                        escape(&cell.text)
+                    );
+                }
+                None => {
+                    let _ = write!(out, "<td>{}</td>", escape(&cell.text));
+                }
+            }
+        }
+        out.push_str("</tr>\n");
+    }
+    out.push_str("</table>\n");
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Turn `**text**` pairs of escaped text into `<strong>`
+fn bold(text: &str) -> String {
+    let parts: Vec<&str> = text.split("**").collect();
+    if parts.len().is_multiple_of(2) {
+        return text.to_string(); // Unbalanced
+    }
+    parts
+        .iter()
+        .enumerate()
+        .map(|(i, part)| {
+            if i % 2 == 1 {
+                format!("<strong>{}</strong>", part)
+            } else {
+                part.to_string()
+            }
+        })
+        .collect()
+}
+
+/// Width of the label column of a chart
+const CHART_LABEL_WIDTH: f64 = 180.0;
+/// Width of the plot area of a chart
+const CHART_PLOT_WIDTH: f64 = 420.0;
+/// Height of one chart row
+const CHART_ROW_HEIGHT: f64 = 28.0;
+
+fn chart_open(rows: usize) -> (String, f64) {
+    let height = rows as f64 * CHART_ROW_HEIGHT + 24.0;
+    let width = CHART_LABEL_WIDTH + CHART_PLOT_WIDTH + 100.0;
+    (
+        format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" \
+             viewBox=\"0 0 {w} {h}\">",
+            w = width,
+            h = height
+        ),
+        height - 20.0,
+    )
+}
+
+fn chart_label(svg: &mut String, row: usize, label: &str) {
+    let y = row as f64 * CHART_ROW_HEIGHT + CHART_ROW_HEIGHT / 2.0 + 4.0;
+    let _ = write!(
+        svg,
+        "<text x=\"{}\" y=\"{}\" text-anchor=\"end\">{}</text>",
+        CHART_LABEL_WIDTH - 8.0,
+        y,
+        escape(label)
+    );
+}
+
+fn chart_axis(svg: &mut String, axis_y: f64, ticks: &[(f64, String)]) {
+    let _ = write!(
+        svg,
+        "<line x1=\"{x0}\" y1=\"{y}\" x2=\"{x1}\" y2=\"{y}\" stroke=\"#999\"/>",
+        x0 = CHART_LABEL_WIDTH,
+        x1 = CHART_LABEL_WIDTH + CHART_PLOT_WIDTH,
+        y = axis_y
+    );
+    for (fraction, label) in ticks {
+        let x = CHART_LABEL_WIDTH + fraction * CHART_PLOT_WIDTH;
+        let _ = write!(
+            svg,
+            "<text x=\"{}\" y=\"{}\" text-anchor=\"middle\" fill=\"#666\">{}</text>",
+            x,
+            axis_y + 14.0,
+            escape(label)
+        );
+    }
+}
+
+/// Horizontal bars of pass@1 with CI whiskers
+fn accuracy_chart(rows: &[(&str, Option<&PassAtK>)]) -> String {
+    let (mut svg, axis_y) = chart_open(rows.len());
+    let x = |value: f64| CHART_LABEL_WIDTH + value.clamp(0.0, 1.0) * CHART_PLOT_WIDTH;
+    for (i, (label, pass)) in rows.iter().enumerate() {
+        chart_label(&mut svg, i, label);
+        let Some(pass) = pass else { continue };
+        let top = i as f64 * CHART_ROW_HEIGHT + 6.0;
+        let mid = top + (CHART_ROW_HEIGHT - 12.0) / 2.0;
+        let _ = write!(
+            svg,
+            "<rect x=\"{}\" y=\"{}\" width=\"{:.1}\" height=\"{}\" fill=\"#6fb07a\"/>\
+             <line x1=\"{lo:.1}\" y1=\"{mid}\" x2=\"{hi:.1}\" y2=\"{mid}\" stroke=\"#222\"/>\
+             <line x1=\"{lo:.1}\" y1=\"{t}\" x2=\"{lo:.1}\" y2=\"{b}\" stroke=\"#222\"/>\
+             <line x1=\"{hi:.1}\" y1=\"{t}\" x2=\"{hi:.1}\" y2=\"{b}\" stroke=\"#222\"/>\
+             <text x=\"{tx}\" y=\"{ty}\">{}</text>",
+            CHART_LABEL_WIDTH,
+            top,
+            x(pass.value) - CHART_LABEL_WIDTH,
+            CHART_ROW_HEIGHT - 12.0,
+            percent(pass.value),
+            lo = x(pass.ci_lower),
+            hi = x(pass.ci_upper),
+            mid = mid,
+            t = mid - 4.0,
+            b = mid + 4.0,
+            tx = CHART_LABEL_WIDTH + CHART_PLOT_WIDTH + 8.0,
+            ty = mid + 4.0
+        );
+    }
+    let ticks: Vec<(f64, String)> = [0.0, 0.25, 0.5, 0.75, 1.0]
+        .iter()
+        .map(|&f| (f, percent(f)))
+        .collect();
+    chart_axis(&mut svg, axis_y, &ticks);
+    svg.push_str("</svg>");
+    svg
+}
+
+/// Horizontal box plots (min, quartiles, median, max)
+fn box_chart(rows: &[(&str, Option<&Distribution>)], format: fn(f64) -> String) -> String {
+    let (mut svg, axis_y) = chart_open(rows.len());
+    let scale = rows
+        .iter()
+        .filter_map(|(_, d)| d.map(|d| d.max))
+        .fold(0.0, f64::max);
+    let x = |value: f64| {
+        let fraction = if scale > 0.0 { value / scale } else { 0.0 };
+        CHART_LABEL_WIDTH + fraction * CHART_PLOT_WIDTH
+    };
+    for (i, (label, dist)) in rows.iter().enumerate() {
+        chart_label(&mut svg, i, label);
+        let Some(d) = dist else { continue };
+        let top = i as f64 * CHART_ROW_HEIGHT + 6.0;
+        let bottom = top + CHART_ROW_HEIGHT - 12.0;
+        let mid = (top + bottom) / 2.0;
+        let _ = write!(
+            svg,
+            "<line x1=\"{min:.1}\" y1=\"{mid}\" x2=\"{max:.1}\" y2=\"{mid}\" stroke=\"#555\"/>\
+             <rect x=\"{p25:.1}\" y=\"{top}\" width=\"{w:.1}\" height=\"{h}\" fill=\"#cfe6d2\" stroke=\"#3a7d44\"/>\
+             <line x1=\"{med:.1}\" y1=\"{top}\" x2=\"{med:.1}\" y2=\"{bottom}\" stroke=\"#222\" stroke-width=\"2\"/>\
+             <text x=\"{tx}\" y=\"{ty}\">{}</text>",
+            escape(&format!("median {}", format(d.median))),
+            min = x(d.min),
+            max = x(d.max),
+            p25 = x(d.p25),
+            w = x(d.p75) - x(d.p25),
+            h = bottom - top,
+            med = x(d.median),
+            mid = mid,
+            top = top,
+            bottom = bottom,
+            tx = CHART_LABEL_WIDTH + CHART_PLOT_WIDTH + 8.0,
+            ty = mid + 4.0
+        );
+    }
+    let ticks: Vec<(f64, String)> = [0.0, 0.5, 1.0]
+        .iter()
+        .map(|&f| (f, format(f * scale)))
+        .collect();
+    chart_axis(&mut svg, axis_y, &ticks);
+    svg.push_str("</svg>");
+    svg
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai::evaluation::models::BenchmarkResult;
+    use crate::benchmarks::BenchmarkMetadata;
+    use tempfile::TempDir;
+
+    fn result(id: &str, solved: bool, cost_usd: f64, complexity: Complexity) -> TaskResult {
+        let mut result = TaskResult::new(id.to_string());
+        if solved {
+            result.mark_solved();
+        } else {
+            result.mark_failed("Tests failed | exit 1\nlong trace".to_string());
+        }
+        result.cost_usd = cost_usd;
+        result.duration_ms = (cost_usd * 100_000.0) as u64;
+        result.metrics.agent_steps = 4;
+        result.complexity = Some(complexity);
+        result
+    }
+
+    fn baseline() -> EvaluationResults {
+        EvaluationResults::from_results(
+            "M1".to_string(),
+            vec![
+                result("django/1", true, 0.01, Complexity::Simple),
+                result("django/2", false, 0.02, Complexity::Hard),
+            ],
+        )
+    }
+
+    fn candidate() -> EvaluationResults {
+        EvaluationResults::from_results(
+            "M2".to_string(),
+            vec![
+                result("django/1", true, 0.02, Complexity::Simple),
+                result("django/2", true, 0.03, Complexity::Hard),
+            ],
+        )
+    }
+
+    #[test]
+    fn test_distribution_quantiles() {
+        let d = Distribution::new(vec![4.0, 1.0, 3.0, 2.0, 5.0]).unwrap();
+        assert_eq!(
+            (d.min, d.p25, d.median, d.p75, d.max),
+            (1.0, 2.0, 3.0, 4.0, 5.0)
+        );
+        assert!((d.p90 - 4.6).abs() < 1e-9);
+        assert_eq!(d.mean, 3.0);
+        assert!(Distribution::new(Vec::new()).is_none());
+    }
+
+    #[test]
+    fn test_markdown_report_sections() {
+        let dir = TempDir::new().unwrap();
+        std::fs::create_dir(dir.path().join("M2")).unwrap();
+        std::fs::write(dir.path().join("M2/django_2.json"), "{}").unwrap();
+
+        let mut report = EvaluationReport::new("Nightly").with_trajectories(dir.path());
+        report.add_results(&baseline());
+        report.add_results(&candidate());
+        report.add_comparison(ComparisonResult::compare(&baseline(), &candidate()));
+        let markdown = report.render(ReportFormat::Markdown);
+
+        for heading in [
+            "## Summary",
+            "## Cost and Latency",
+            "## By Complexity",
+            "## Behavior and Tool Usage",
+            "## Task Differences",
+            "## Comparisons",
+            "## Tasks",
+        ] {
+            assert!(markdown.contains(heading), "missing {}", heading);
+        }
+        assert!(markdown.contains("| hard | 0/1 (0.0%"));
+        assert!(markdown.contains("1 of 2 tasks have different outcomes"));
+        let link = dir.path().join("M2/django_2.json").display().to_string();
+        assert!(markdown.contains(&format!("[solved]({})", link)));
+        // Table cells stay on one row
+        assert!(markdown.contains("Tests failed \\| exit 1 |"));
+        assert!(!markdown.contains("<svg"));
+    }
+
+    #[test]
+    fn test_html_report_is_self_contained() {
+        let mut report = EvaluationReport::new("A <b> test");
+        report.add_results(&baseline());
+        report.add_results(&baseline());
+        let html = report.render(ReportFormat::Html);
+
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<title>A &lt;b&gt; test</title>"));
+        assert_eq!(html.matches("<svg").count(), 3);
+        assert!(html.contains("M1 (2)"));
+        assert!(!html.contains("<script"));
+        assert!(!html.contains("<link"));
+    }
+
+    #[test]
+    fn test_add_file_detects_kind() {
+        let dir = TempDir::new().unwrap();
+        let mut run = EvaluationRun::new("run-1".to_string(), serde_json::json!({}));
+        let mut benchmark = BenchmarkResult::new(BenchmarkMetadata {
+            name: "LiveCodeBench".to_string(),
+            version: "v1".to_string(),
+            total_tasks: 1,
+            dataset_url: None,
+            license: None,
+            contamination_risk: "LOW".to_string(),
+        });
+        benchmark.task_results = vec![result("lcb-1", true, 0.01, Complexity::Medium)];
+        run.benchmark_results.push(benchmark);
+
+        let files = [
+            ("run.json", serde_json::to_string(&run).unwrap()),
+            ("eval.json", serde_json::to_string(&baseline()).unwrap()),
+            (
+                "comparison.json",
+                serde_json::to_string(&ComparisonResult::compare(&baseline(), &candidate()))
+                    .unwrap(),
+            ),
+        ];
+        let mut report = EvaluationReport::new("Files");
+        for (name, json) in &files {
+            let path = dir.path().join(name);
+            std::fs::write(&path, json).unwrap();
+            report.add_file(&path).unwrap();
+        }
+        let labels: Vec<&str> = report.configs().iter().map(|c| c.label.as_str()).collect();
+        assert_eq!(labels, ["run-1", "M1"]);
+        assert_eq!(report.comparisons.len(), 1);
+
+        let other = dir.path().join("other.json");
+        std::fs::write(&other, "{\"name\": \"x\"}").unwrap();
+        assert!(report.add_file(&other).is_err());
+    }
+
+    #[test]
+    fn test_format_from_path() {
+        assert_eq!(
+            ReportFormat::from_path(Path::new("r/report.md")),
+            ReportFormat::Markdown
+        );
+        assert_eq!(
+            ReportFormat::from_path(Path::new("report.html")),
+            ReportFormat::Html
+        );
+        assert_eq!(
+            ReportFormat::parse("Markdown").unwrap(),
+            ReportFormat::Markdown
+        );
+        assert!(ReportFormat::parse("pdf").is_err());
+    }
+}
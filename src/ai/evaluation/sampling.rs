@@ -0,0 +1,337 @@
+//! Multi-sample evaluation and pass@k
+//!
+//! A single attempt per task makes accuracy on a small task set very noisy. In
+//! sampling mode every task is attempted `n` times (see [`SamplingConfig`]) and
+//! the results are summarized per task with the unbiased pass@k estimator from
+//! "Evaluating Large Language Models Trained on Code" (Chen et al., 2021):
+//!
+//! ```text
+//! pass@k = mean over tasks of 1 - C(n - c, k) / C(n, k)
+//! ```
+//!
+//! where `c` of a task's `n` samples were solved. pass@1 is the mean per-task
+//! solve rate.
+//!
+//! # Storage
+//!
+//! Each sample is stored as its own [`TaskResult`] with its index in
+//! [`TaskResult::sample`]; [`group_by_task`] collects them again.
+//!
+//! # Seeds
+//!
+//! Sample `i` is requested with seed `base + i` (see [`SamplingConfig::seed`]),
+//! so the attempts at a task differ from each other yet a rerun repeats them.
+//! Providers without a seed parameter (Anthropic) vary only by temperature.
+//!
+//! # Examples
+//!
+//! ```
+//! use toad::ai::evaluation::TaskResult;
+//! use toad::ai::evaluation::sampling::{PassAtK, group_by_task};
+//!
+//! let results: Vec<TaskResult> = [("a", true), ("a", false), ("b", false), ("b", false)]
+//!     .into_iter()
+//!     .enumerate()
+//!     .map(|(i, (id, solved))| {
+//!         let mut result = TaskResult::new(id.to_string());
+//!         result.sample = Some(i % 2);
+//!         result.solved = solved;
+//!         result
+//!     })
+//!     .collect();
+//!
+//! let tasks = group_by_task(&results);
+//! assert_eq!(tasks[0].solve_rate(), 0.5);
+//!
+//! let pass_at_2 = PassAtK::estimate(&tasks, 2, 0.95).unwrap();
+//! assert_eq!(pass_at_2.value, 0.5);
+//! ```
+
+use crate::ai::evaluation::TaskResult;
+use crate::ai::stats::StatisticalTest;
+use serde::{Deserialize, Serialize};
+use statrs::statistics::Statistics;
+use std::collections::HashMap;
+
+/// How many times each task is attempted, and how
+///
+/// # Examples
+///
+/// ```
+/// use toad::ai::evaluation::sampling::SamplingConfig;
+///
+/// let sampling = SamplingConfig::new(5).with_temperature(0.8);
+/// assert!(sampling.is_sampling());
+/// assert_eq!(sampling.samples().count(), 5);
+/// assert_eq!(sampling.sample(3).temperature, Some(0.8));
+/// assert_eq!(sampling.sample(3).seed, Some(3));
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SamplingConfig {
+    /// Attempts per task (`n`); 1 runs each task once
+    pub samples: usize,
+
+    /// Sampling temperature for every attempt (None: the client's default)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f32>,
+
+    /// Seed of the first attempt; later attempts add their index
+    ///
+    /// None seeds from 0 in sampling mode and sends no seed otherwise.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seed: Option<u64>,
+}
+
+impl Default for SamplingConfig {
+    fn default() -> Self {
+        Self {
+            samples: 1,
+            temperature: None,
+            seed: None,
+        }
+    }
+}
+
+impl SamplingConfig {
+    /// Attempt each task `samples` times (at least once)
+    pub fn new(samples: usize) -> Self {
+        Self {
+            samples: samples.max(1),
+            ..Self::default()
+        }
+    }
+
+    /// Sample at `temperature`
+    pub fn with_temperature(mut self, temperature: f32) -> Self {
+        self.temperature = Some(temperature);
+        self
+    }
+
+    /// Seed the first attempt with `seed`
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Whether tasks are attempted more than once
+    pub fn is_sampling(&self) -> bool {
+        self.samples > 1
+    }
+
+    /// Settings of the sample at `index`
+    pub fn sample(&self, index: usize) -> Sample {
+        let base_seed = self.seed.or(self.is_sampling().then_some(0));
+        Sample {
+            index,
+            temperature: self.temperature,
+            seed: base_seed.map(|base| base.wrapping_add(index as u64)),
+        }
+    }
+
+    /// Settings of every sample, in order
+    pub fn samples(&self) -> impl Iterator<Item = Sample> + '_ {
+        (0..self.samples.max(1)).map(|index| self.sample(index))
+    }
+}
+
+/// One attempt at a task in sampling mode
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Sample {
+    /// 0-based index among the task's samples
+    pub index: usize,
+
+    /// Sampling temperature (None: the client's default)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f32>,
+
+    /// Seed for providers that accept one (None: unseeded)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seed: Option<u64>,
+}
+
+/// Samples of one task and how many of them were solved
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TaskSamples {
+    /// Task ID
+    pub task_id: String,
+
+    /// Number of samples (`n`)
+    pub samples: usize,
+
+    /// Number of solved samples (`c`)
+    pub solved: usize,
+}
+
+impl TaskSamples {
+    /// Fraction of samples that solved the task
+    pub fn solve_rate(&self) -> f64 {
+        if self.samples == 0 {
+            0.0
+        } else {
+            self.solved as f64 / self.samples as f64
+        }
+    }
+
+    /// Unbiased pass@k of this task, or None if it has fewer than `k` samples
+    pub fn pass_at_k(&self, k: usize) -> Option<f64> {
+        (k >= 1 && k <= self.samples)
+            .then(|| StatisticalTest::pass_at_k(self.samples, self.solved, k))
+    }
+}
+
+/// Samples per task, in order of each task's first result
+pub fn group_by_task(results: &[TaskResult]) -> Vec<TaskSamples> {
+    let mut tasks: Vec<TaskSamples> = Vec::new();
+    let mut index: HashMap<&str, usize> = HashMap::new();
+    for result in results {
+        let i = *index.entry(result.task_id.as_str()).or_insert_with(|| {
+            tasks.push(TaskSamples {
+                task_id: result.task_id.clone(),
+                samples: 0,
+                solved: 0,
+            });
+            tasks.len() - 1
+        });
+        tasks[i].samples += 1;
+        if result.solved {
+            tasks[i].solved += 1;
+        }
+    }
+    tasks
+}
+
+/// pass@k over a set of tasks with a bootstrap confidence interval
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PassAtK {
+    /// Number of attempts allowed per task
+    pub k: usize,
+
+    /// Mean per-task pass@k, in [0.0, 1.0]
+    pub value: f64,
+
+    /// Lower bound of the confidence interval
+    pub ci_lower: f64,
+
+    /// Upper bound of the confidence interval
+    pub ci_upper: f64,
+
+    /// Number of tasks
+    pub tasks: usize,
+}
+
+impl PassAtK {
+    /// Estimate pass@k over `tasks`
+    ///
+    /// The interval comes from [`StatisticalTest::bootstrap_ci`] over the
+    /// per-task values. Returns None if there are no tasks or one of them has
+    /// fewer than `k` samples.
+    pub fn estimate(tasks: &[TaskSamples], k: usize, confidence_level: f64) -> Option<Self> {
+        let values = per_task_pass_at_k(tasks, k)?;
+        let (ci_lower, ci_upper) =
+            StatisticalTest::bootstrap_ci(&values, |s| s.mean(), confidence_level);
+        Some(Self {
+            k,
+            value: values.iter().mean(),
+            ci_lower,
+            ci_upper,
+            tasks: values.len(),
+        })
+    }
+}
+
+/// pass@k of each task, or None if there are no tasks or one has fewer than
+/// `k` samples
+pub fn per_task_pass_at_k(tasks: &[TaskSamples], k: usize) -> Option<Vec<f64>> {
+    if tasks.is_empty() {
+        return None;
+    }
+    tasks.iter().map(|task| task.pass_at_k(k)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn results(samples: &[(&str, bool)]) -> Vec<TaskResult> {
+        samples
+            .iter()
+            .map(|(id, solved)| {
+                let mut result = TaskResult::new(id.to_string());
+                if *solved {
+                    result.mark_solved();
+                }
+                result
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_sampling_config_defaults() {
+        let sampling = SamplingConfig::default();
+        assert!(!sampling.is_sampling());
+        assert_eq!(sampling.samples().count(), 1);
+        assert_eq!(SamplingConfig::new(0).samples, 1);
+
+        let indices: Vec<usize> = SamplingConfig::new(3).samples().map(|s| s.index).collect();
+        assert_eq!(indices, vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn test_samples_get_distinct_seeds() {
+        let seeds: Vec<Option<u64>> = SamplingConfig::new(3).samples().map(|s| s.seed).collect();
+        assert_eq!(seeds, vec![Some(0), Some(1), Some(2)]);
+
+        let sampling = SamplingConfig::new(2).with_seed(42);
+        assert_eq!(sampling.sample(0).seed, Some(42));
+        assert_eq!(sampling.sample(1).seed, Some(43));
+
+        // A single attempt stays unseeded unless asked
+        assert_eq!(SamplingConfig::default().sample(0).seed, None);
+        assert_eq!(SamplingConfig::new(1).with_seed(7).sample(0).seed, Some(7));
+    }
+
+    #[test]
+    fn test_group_by_task_keeps_first_seen_order() {
+        let results = results(&[
+            ("b", true),
+            ("a", false),
+            ("b", false),
+            ("a", false),
+            ("b", true),
+        ]);
+        let tasks = group_by_task(&results);
+
+        assert_eq!(tasks.len(), 2);
+        assert_eq!(tasks[0].task_id, "b");
+        assert_eq!((tasks[0].samples, tasks[0].solved), (3, 2));
+        assert_eq!((tasks[1].samples, tasks[1].solved), (2, 0));
+        assert!((tasks[0].solve_rate() - 2.0 / 3.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_pass_at_k_estimate() {
+        let tasks = vec![
+            TaskSamples {
+                task_id: "a".to_string(),
+                samples: 4,
+                solved: 1,
+            },
+            TaskSamples {
+                task_id: "b".to_string(),
+                samples: 4,
+                solved: 4,
+            },
+        ];
+
+        let pass_at_1 = PassAtK::estimate(&tasks, 1, 0.95).unwrap();
+        assert!((pass_at_1.value - 0.625).abs() < 1e-12);
+        assert!(pass_at_1.ci_lower <= pass_at_1.value && pass_at_1.value <= pass_at_1.ci_upper);
+
+        // Task a: 1 - C(3, 2) / C(4, 2) = 0.5
+        let pass_at_2 = PassAtK::estimate(&tasks, 2, 0.95).unwrap();
+        assert!((pass_at_2.value - 0.75).abs() < 1e-12);
+
+        assert!(PassAtK::estimate(&tasks, 5, 0.95).is_none());
+        assert!(PassAtK::estimate(&[], 1, 0.95).is_none());
+    }
+}
@@ -8,6 +8,7 @@
 //!
 //! - **Completed runs**: `./results/{run_id}.json`
 //! - **In-progress runs**: `./results/.tmp/{run_id}/` (incremental snapshots)
+//! - **Resumable runs**: `./results/.tmp/{run_id}/journal.jsonl` (see [`journal`](super::journal))
 //! - **Format**: Versioned JSON with explicit `format_version` field
 //! - **Atomic writes**: Write to .tmp file, then rename
 //!
@@ -32,8 +33,10 @@
 //! }
 //! ```
 
+use crate::ai::evaluation::journal::{self, RunJournal, RunKind, RunManifest};
 use crate::ai::evaluation::models::EvaluationRun;
 use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
 use std::path::{Path, PathBuf};
 use tokio::fs;
 use uuid::Uuid;
@@ -56,7 +59,9 @@ use uuid::Uuid;
 ///     └── run-{uuid}/          # In-progress runs
 ///         ├── snapshot-1.json  # Incremental snapshots
 ///         ├── snapshot-2.json
-///         └── ...
+///         ├── ...
+///         ├── manifest.json    # Resumable runs only
+///         └── journal.jsonl
 /// ```
 ///
 /// # Examples
@@ -344,10 +349,98 @@ impl StorageManager {
         Ok(runs)
     }
 
+    /// Start journaling a new resumable run
+    ///
+    /// Creates `.tmp/{run_id}/` with a fresh run ID. `config_snapshot` holds
+    /// the settings a resumed run must match; `planned_results` is the number
+    /// of task results the complete run produces, if known.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the run directory can't be created
+    pub fn start_journal(
+        &self,
+        kind: RunKind,
+        config_snapshot: serde_json::Value,
+        planned_results: Option<usize>,
+    ) -> Result<RunJournal> {
+        let manifest = RunManifest {
+            run_id: Self::generate_run_id(),
+            kind,
+            started_at: Utc::now(),
+            planned_results,
+            config_snapshot,
+        };
+        RunJournal::create(self.tmp_dir.join(&manifest.run_id), manifest)
+    }
+
+    /// Reopen the journal of an interrupted run
+    ///
+    /// # Errors
+    ///
+    /// Returns error if no interrupted run has this ID, or if it was started
+    /// with a different config snapshot
+    pub fn resume_journal(&self, run_id: &str, config_snapshot: &serde_json::Value) -> Result<RunJournal> {
+        let dir = self.tmp_dir.join(run_id);
+        if !dir.join(journal::MANIFEST_FILE).exists() {
+            anyhow::bail!(
+                "No interrupted run '{}' in {}",
+                run_id,
+                self.results_dir.display()
+            );
+        }
+        RunJournal::open(dir, config_snapshot)
+    }
+
+    /// Remove the journal of a run whose results have been saved
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the run directory can't be removed
+    pub fn finish_journal(&self, run_id: &str) -> Result<()> {
+        let dir = self.tmp_dir.join(run_id);
+        if dir.exists() {
+            std::fs::remove_dir_all(&dir)
+                .with_context(|| format!("Failed to remove run journal: {}", dir.display()))?;
+        }
+        Ok(())
+    }
+
+    /// Runs that were started with a journal but never finished, oldest first
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the temporary directory can't be read
+    pub fn list_partial_runs(&self) -> Result<Vec<PartialRun>> {
+        if !self.tmp_dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut runs = Vec::new();
+        for entry in std::fs::read_dir(&self.tmp_dir).context("Failed to read temporary directory")? {
+            let path = entry.context("Failed to read temporary directory entry")?.path();
+            if !path.join(journal::MANIFEST_FILE).exists() {
+                continue;
+            }
+            match journal::read_manifest(&path) {
+                Ok(manifest) => runs.push(PartialRun {
+                    completed_results: journal::count_results(&path)?,
+                    manifest,
+                }),
+                Err(e) => tracing::warn!("Skipping unreadable run in {}: {}", path.display(), e),
+            }
+        }
+
+        runs.sort_by_key(|run| run.manifest.started_at);
+        Ok(runs)
+    }
+
     /// Clean up abandoned temporary directories
     ///
     /// Removes `.tmp/{run_id}/` directories for runs that don't have a corresponding
     /// completed `.json` file. This happens when evaluations crash or are cancelled.
+    /// Runs with a journal are kept so they can still be resumed; remove them with
+    /// [`finish_journal`](Self::finish_journal).
     ///
     /// # Errors
     ///
@@ -384,7 +477,8 @@ impl StorageManager {
 
             // Check if corresponding completed run exists
             let completed_path = self.results_dir.join(format!("{}.json", dir_name));
-            if !completed_path.exists() {
+            let resumable = path.join(journal::MANIFEST_FILE).exists();
+            if !completed_path.exists() && !resumable {
                 // Abandoned run - remove temp directory
                 fs::remove_dir_all(&path)
                     .await
@@ -399,6 +493,36 @@ impl StorageManager {
     }
 }
 
+/// Journaled run that hasn't finished
+#[derive(Debug, Clone)]
+pub struct PartialRun {
+    /// How the run was started
+    pub manifest: RunManifest,
+
+    /// Task results recorded so far
+    pub completed_results: usize,
+}
+
+impl PartialRun {
+    /// Run ID to pass to `--resume`
+    pub fn run_id(&self) -> &str {
+        &self.manifest.run_id
+    }
+
+    /// When the run was first started
+    pub fn started_at(&self) -> DateTime<Utc> {
+        self.manifest.started_at
+    }
+
+    /// Fraction of the planned results recorded, if the plan is known
+    pub fn progress(&self) -> Option<f64> {
+        self.manifest
+            .planned_results
+            .filter(|&planned| planned > 0)
+            .map(|planned| (self.completed_results as f64 / planned as f64).min(1.0))
+    }
+}
+
 impl Default for StorageManager {
     fn default() -> Self {
         Self::new(Self::default_results_dir())
@@ -578,4 +702,30 @@ mod tests {
         assert!(storage.tmp_dir.join("run-completed").exists());
         assert!(!storage.tmp_dir.join("run-abandoned").exists());
     }
+
+    #[tokio::test]
+    async fn test_partial_runs_survive_cleanup() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = StorageManager::new(temp_dir.path());
+
+        let journal = storage
+            .start_journal(RunKind::Eval, json!({"milestone": 1}), Some(4))
+            .unwrap();
+        journal
+            .append("eval", &crate::ai::evaluation::TaskResult::new("task-1".to_string()))
+            .unwrap();
+        let run_id = journal.run_id().to_string();
+        drop(journal);
+
+        assert_eq!(storage.cleanup_tmp_dirs().await.unwrap(), 0);
+        let partial = storage.list_partial_runs().unwrap();
+        assert_eq!(partial.len(), 1);
+        assert_eq!(partial[0].run_id(), run_id);
+        assert_eq!(partial[0].completed_results, 1);
+        assert_eq!(partial[0].progress(), Some(0.25));
+
+        assert!(storage.resume_journal("run-missing", &json!({})).is_err());
+        storage.finish_journal(&run_id).unwrap();
+        assert!(storage.list_partial_runs().unwrap().is_empty());
+    }
 }
@@ -0,0 +1,726 @@
+/// Record/replay LLM client for deterministic agent tests
+///
+/// [`CassetteClient`] wraps a real client and records every request and its
+/// response (or stream events, or error) to a JSON cassette file. Replaying
+/// the cassette serves the same responses offline, so a real agent trajectory
+/// becomes a fast, network-free regression test for `Agent`, tools and UI
+/// streaming.
+///
+/// Requests are identified by a fingerprint of their messages (role and
+/// content, not timestamps) and tool schemas. In replay:
+/// - [`MatchMode::Strict`]: requests must arrive in recorded order and match
+///   exactly; a mismatch fails with a diff against the recorded request
+/// - [`MatchMode::Lenient`]: an unused interaction with the same fingerprint is
+///   served if there is one, otherwise the next unused one in order (the
+///   mismatch is logged as a warning)
+///
+/// # Examples
+///
+/// ```no_run
+/// use toad::ai::llm::{AnthropicClient, CassetteClient, MatchMode};
+///
+/// # fn main() -> anyhow::Result<()> {
+/// // Record a real run...
+/// let client = AnthropicClient::new(toad::ai::llm::get_api_key()?);
+/// let recorder = CassetteClient::record(Box::new(client), "tests/cassettes/fix_bug.json");
+///
+/// // ...and replay it in a test
+/// let replay = CassetteClient::replay("tests/cassettes/fix_bug.json")?
+///     .with_match_mode(MatchMode::Lenient);
+/// # Ok(())
+/// # }
+/// ```
+use super::{LLMClient, LLMResponse, Message, MessageStream, Role, StreamEvent};
+use crate::infrastructure::fs_util::atomic_write;
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use futures::Stream;
+use serde::{Deserialize, Serialize};
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context as TaskContext, Poll};
+
+/// Version of the cassette file format
+pub const CASSETTE_FORMAT_VERSION: u32 = 1;
+
+/// How replayed requests are matched against the cassette
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MatchMode {
+    /// Recorded order, exact match
+    #[default]
+    Strict,
+    /// Same fingerprint anywhere, else the next unused interaction
+    Lenient,
+}
+
+/// Recorded conversation with one model
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Cassette {
+    /// File format version
+    pub format_version: u32,
+
+    /// Model that produced the responses
+    pub model: String,
+
+    /// Requests and responses, in the order they happened
+    pub interactions: Vec<Interaction>,
+}
+
+impl Cassette {
+    /// Empty cassette for `model`
+    pub fn new(model: impl Into<String>) -> Self {
+        Self {
+            format_version: CASSETTE_FORMAT_VERSION,
+            model: model.into(),
+            interactions: Vec::new(),
+        }
+    }
+
+    /// Load a cassette file
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let json = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read cassette {}", path.display()))?;
+        let cassette: Self = serde_json::from_str(&json)
+            .with_context(|| format!("Failed to parse cassette {}", path.display()))?;
+        if cassette.format_version != CASSETTE_FORMAT_VERSION {
+            anyhow::bail!(
+                "Unsupported cassette format version {} in {} (expected {})",
+                cassette.format_version,
+                path.display(),
+                CASSETTE_FORMAT_VERSION
+            );
+        }
+        Ok(cassette)
+    }
+
+    /// Write the cassette file (atomically)
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        atomic_write(path, serde_json::to_string_pretty(self)?.as_bytes())
+            .with_context(|| format!("Failed to write cassette {}", path.display()))
+    }
+}
+
+/// One request and its response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Interaction {
+    pub request: RecordedRequest,
+    pub response: RecordedResponse,
+}
+
+/// What was sent to the model
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordedRequest {
+    /// Hash of the messages and tool schemas
+    pub fingerprint: String,
+
+    /// Whether the response was streamed
+    pub stream: bool,
+
+    /// Conversation sent
+    pub messages: Vec<RecordedMessage>,
+
+    /// Names of the tools offered
+    #[serde(default)]
+    pub tools: Vec<String>,
+}
+
+impl RecordedRequest {
+    fn new(messages: &[Message], tools: Option<&[serde_json::Value]>, stream: bool) -> Self {
+        let messages: Vec<RecordedMessage> = messages
+            .iter()
+            .map(|m| RecordedMessage {
+                role: m.role.clone(),
+                content: m.content.clone(),
+            })
+            .collect();
+        let tools = tools.unwrap_or_default();
+
+        // serde_json sorts object keys, so this is canonical
+        let canonical = serde_json::json!({ "messages": messages, "tools": tools }).to_string();
+        Self {
+            fingerprint: format!("{:016x}", fnv1a(canonical.as_bytes())),
+            stream,
+            messages,
+            tools: tools
+                .iter()
+                .map(|tool| tool["name"].as_str().unwrap_or("?").to_string())
+                .collect(),
+        }
+    }
+
+    /// Human-readable differences from `actual`
+    fn diff(&self, actual: &RecordedRequest) -> String {
+        let mut diff = String::new();
+        if self.stream != actual.stream {
+            let kind = |stream| if stream { "streamed" } else { "not streamed" };
+            let _ = writeln!(
+                diff,
+                "  response: recorded {}, requested {}",
+                kind(self.stream),
+                kind(actual.stream)
+            );
+        }
+        if self.messages.len() != actual.messages.len() {
+            let _ = writeln!(
+                diff,
+                "  messages: recorded {}, requested {}",
+                self.messages.len(),
+                actual.messages.len()
+            );
+        }
+        for (i, (recorded, requested)) in self.messages.iter().zip(&actual.messages).enumerate() {
+            if recorded == requested {
+                continue;
+            }
+            let _ = writeln!(diff, "  message {} ({:?}):", i, requested.role);
+            if recorded.role != requested.role {
+                let _ = writeln!(diff, "    - role {:?}", recorded.role);
+                let _ = writeln!(diff, "    + role {:?}", requested.role);
+            }
+            let recorded_lines: Vec<&str> = recorded.content.lines().collect();
+            let requested_lines: Vec<&str> = requested.content.lines().collect();
+            let line = (0..recorded_lines.len().max(requested_lines.len()))
+                .find(|&l| recorded_lines.get(l) != requested_lines.get(l))
+                .unwrap_or(0);
+            let _ = writeln!(
+                diff,
+                "    - line {}: {}",
+                line + 1,
+                recorded_lines.get(line).unwrap_or(&"<none>")
+            );
+            let _ = writeln!(
+                diff,
+                "    + line {}: {}",
+                line + 1,
+                requested_lines.get(line).unwrap_or(&"<none>")
+            );
+            break;
+        }
+        if self.tools != actual.tools {
+            let _ = writeln!(diff, "  tools:");
+            let _ = writeln!(diff, "    - {:?}", self.tools);
+            let _ = writeln!(diff, "    + {:?}", actual.tools);
+        }
+        if diff.is_empty() {
+            // Same names and messages: a tool schema changed
+            let _ = writeln!(diff, "  tool schemas differ");
+        }
+        diff
+    }
+}
+
+/// Message of a recorded request (timestamps aren't recorded)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordedMessage {
+    pub role: Role,
+    pub content: String,
+}
+
+/// What the model answered
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum RecordedResponse {
+    /// Complete response of `send_message`
+    Message { response: LLMResponse },
+
+    /// Events of `send_message_stream`, and the error that ended the stream
+    Stream {
+        events: Vec<StreamEvent>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        error: Option<String>,
+    },
+
+    /// The request failed
+    Error { message: String },
+}
+
+/// Recorded or replayed cassette with replay position
+#[derive(Debug)]
+struct CassetteState {
+    cassette: Cassette,
+    used: Vec<bool>,
+    next: usize,
+}
+
+/// Record/replay decorator around an [`LLMClient`]
+pub struct CassetteClient {
+    /// Client being recorded (None when replaying)
+    inner: Option<Box<dyn LLMClient>>,
+
+    /// Cassette file
+    path: PathBuf,
+
+    /// Model name reported to callers
+    model: String,
+
+    match_mode: MatchMode,
+
+    state: Arc<Mutex<CassetteState>>,
+}
+
+impl CassetteClient {
+    /// Record `inner`'s traffic to `path`
+    ///
+    /// The cassette is rewritten after every interaction, so it stays usable
+    /// if the run is interrupted.
+    pub fn record(inner: Box<dyn LLMClient>, path: impl Into<PathBuf>) -> Self {
+        let model = inner.model_name().to_string();
+        Self {
+            inner: Some(inner),
+            path: path.into(),
+            state: Arc::new(Mutex::new(CassetteState {
+                cassette: Cassette::new(model.clone()),
+                used: Vec::new(),
+                next: 0,
+            })),
+            model,
+            match_mode: MatchMode::Strict,
+        }
+    }
+
+    /// Serve the responses recorded in `path`, without network access
+    pub fn replay(path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        let cassette = Cassette::load(&path)?;
+        Ok(Self {
+            inner: None,
+            path,
+            model: cassette.model.clone(),
+            match_mode: MatchMode::Strict,
+            state: Arc::new(Mutex::new(CassetteState {
+                used: vec![false; cassette.interactions.len()],
+                cassette,
+                next: 0,
+            })),
+        })
+    }
+
+    /// Match replayed requests as `mode` describes
+    pub fn with_match_mode(mut self, mode: MatchMode) -> Self {
+        self.match_mode = mode;
+        self
+    }
+
+    /// Whether this client records (rather than replays)
+    pub fn is_recording(&self) -> bool {
+        self.inner.is_some()
+    }
+
+    /// Number of interactions in the cassette
+    pub fn interaction_count(&self) -> usize {
+        self.lock().cassette.interactions.len()
+    }
+
+    /// Interactions not replayed yet (0 once a replay consumed the cassette)
+    pub fn remaining(&self) -> usize {
+        self.lock().used.iter().filter(|used| !**used).count()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, CassetteState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Find the recorded interaction answering `request`
+    fn take_response(&self, request: &RecordedRequest) -> Result<RecordedResponse> {
+        let mut state = self.lock();
+        let CassetteState {
+            cassette,
+            used,
+            next,
+        } = &mut *state;
+        let interactions = &cassette.interactions;
+        let exhausted = || {
+            anyhow::anyhow!(
+                "Cassette {} has no interaction left for request {} ({} recorded)",
+                self.path.display(),
+                request.fingerprint,
+                interactions.len()
+            )
+        };
+
+        let index = match self.match_mode {
+            MatchMode::Strict => {
+                let index = *next;
+                let recorded = &interactions.get(index).ok_or_else(exhausted)?.request;
+                if recorded.fingerprint != request.fingerprint || recorded.stream != request.stream
+                {
+                    anyhow::bail!(
+                        "Request {} doesn't match cassette {}:\n{}",
+                        index,
+                        self.path.display(),
+                        recorded.diff(request)
+                    );
+                }
+                index
+            }
+            MatchMode::Lenient => {
+                let unused = || (0..interactions.len()).filter(|&i| !used[i]);
+                let found = unused().find(|&i| {
+                    let recorded = &interactions[i].request;
+                    recorded.fingerprint == request.fingerprint && recorded.stream == request.stream
+                });
+                match found {
+                    Some(index) => index,
+                    None => {
+                        let index = unused()
+                            .find(|&i| interactions[i].request.stream == request.stream)
+                            .ok_or_else(exhausted)?;
+                        tracing::warn!(
+                            "Request doesn't match cassette {}; replaying interaction {}:\n{}",
+                            self.path.display(),
+                            index,
+                            interactions[index].request.diff(request)
+                        );
+                        index
+                    }
+                }
+            }
+        };
+
+        used[index] = true;
+        *next = index + 1;
+        Ok(interactions[index].response.clone())
+    }
+}
+
+/// Append `interaction` to the cassette and rewrite the file
+fn commit(state: &Mutex<CassetteState>, path: &Path, interaction: Interaction) -> Result<()> {
+    let mut state = state.lock().unwrap_or_else(|e| e.into_inner());
+    state.cassette.interactions.push(interaction);
+    state.used.push(true);
+    state.cassette.save(path)
+}
+
+#[async_trait]
+impl LLMClient for CassetteClient {
+    async fn send_message(
+        &self,
+        messages: Vec<Message>,
+        tools: Option<Vec<serde_json::Value>>,
+    ) -> Result<LLMResponse> {
+        let request = RecordedRequest::new(&messages, tools.as_deref(), false);
+
+        let Some(inner) = &self.inner else {
+            return match self.take_response(&request)? {
+                RecordedResponse::Message { response } => Ok(response),
+                RecordedResponse::Error { message } => Err(anyhow::anyhow!(message)),
+                RecordedResponse::Stream { .. } => {
+                    anyhow::bail!("Cassette recorded a stream for a non-streaming request")
+                }
+            };
+        };
+
+        let result = inner.send_message(messages, tools).await;
+        let response = match &result {
+            Ok(response) => RecordedResponse::Message {
+                response: response.clone(),
+            },
+            Err(e) => RecordedResponse::Error {
+                message: format!("{:#}", e),
+            },
+        };
+        commit(&self.state, &self.path, Interaction { request, response })?;
+        result
+    }
+
+    async fn send_message_stream(
+        &self,
+        messages: Vec<Message>,
+        tools: Option<Vec<serde_json::Value>>,
+    ) -> Result<MessageStream> {
+        let request = RecordedRequest::new(&messages, tools.as_deref(), true);
+
+        let Some(inner) = &self.inner else {
+            return match self.take_response(&request)? {
+                RecordedResponse::Stream { events, error } => {
+                    let items = events
+                        .into_iter()
+                        .map(Ok)
+                        .chain(error.map(|e| Err(anyhow::anyhow!(e))));
+                    Ok(MessageStream::from_stream(futures::stream::iter(items)))
+                }
+                RecordedResponse::Error { message } => Err(anyhow::anyhow!(message)),
+                RecordedResponse::Message { .. } => {
+                    anyhow::bail!("Cassette recorded a complete message for a streaming request")
+                }
+            };
+        };
+
+        match inner.send_message_stream(messages, tools).await {
+            Ok(stream) => Ok(MessageStream::from_stream(RecordingStream {
+                inner: stream,
+                request: Some(request),
+                events: Vec::new(),
+                state: Arc::clone(&self.state),
+                path: self.path.clone(),
+            })),
+            Err(e) => {
+                let response = RecordedResponse::Error {
+                    message: format!("{:#}", e),
+                };
+                commit(&self.state, &self.path, Interaction { request, response })?;
+                Err(e)
+            }
+        }
+    }
+
+    fn model_name(&self) -> &str {
+        &self.model
+    }
+}
+
+/// Passes a live stream through, recording its events
+///
+/// The interaction is committed when the stream reports `MessageStop`, fails
+/// or ends; a stream dropped before then isn't recorded.
+struct RecordingStream {
+    inner: MessageStream,
+    request: Option<RecordedRequest>,
+    events: Vec<StreamEvent>,
+    state: Arc<Mutex<CassetteState>>,
+    path: PathBuf,
+}
+
+impl RecordingStream {
+    fn commit(&mut self, error: Option<String>) {
+        let Some(request) = self.request.take() else {
+            return;
+        };
+        let response = RecordedResponse::Stream {
+            events: std::mem::take(&mut self.events),
+            error,
+        };
+        if let Err(e) = commit(&self.state, &self.path, Interaction { request, response }) {
+            tracing::warn!("Failed to record stream: {:#}", e);
+        }
+    }
+}
+
+impl Stream for RecordingStream {
+    type Item = Result<StreamEvent>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut TaskContext<'_>) -> Poll<Option<Self::Item>> {
+        let item = Pin::new(&mut self.inner).poll_next(cx);
+        match &item {
+            Poll::Ready(Some(Ok(event))) => {
+                self.events.push(event.clone());
+                if matches!(event, StreamEvent::MessageStop) {
+                    self.commit(None);
+                }
+            }
+            Poll::Ready(Some(Err(e))) => self.commit(Some(format!("{:#}", e))),
+            Poll::Ready(None) => self.commit(None),
+            Poll::Pending => {}
+        }
+        item
+    }
+}
+
+/// 64-bit FNV-1a: stable across Rust versions, unlike `DefaultHasher`
+pub(crate) fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &byte| {
+        (hash ^ byte as u64).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai::llm::streaming::{ContentBlockStart, ContentDelta, StreamAccumulator};
+    use crate::ai::llm::{MockResponseBuilder, StopReason};
+    use futures::StreamExt;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    /// Streams one text block
+    struct StreamingMock;
+
+    #[async_trait]
+    impl LLMClient for StreamingMock {
+        async fn send_message(
+            &self,
+            _messages: Vec<Message>,
+            _tools: Option<Vec<serde_json::Value>>,
+        ) -> Result<LLMResponse> {
+            anyhow::bail!("API error (529): Overloaded")
+        }
+
+        async fn send_message_stream(
+            &self,
+            _messages: Vec<Message>,
+            _tools: Option<Vec<serde_json::Value>>,
+        ) -> Result<MessageStream> {
+            Ok(MessageStream::from_stream(futures::stream::iter(
+                [
+                    StreamEvent::ContentBlockStart {
+                        index: 0,
+                        content_block: ContentBlockStart::Text {
+                            text: "Hello".to_string(),
+                        },
+                    },
+                    StreamEvent::ContentBlockDelta {
+                        index: 0,
+                        delta: ContentDelta::TextDelta {
+                            text: " world".to_string(),
+                        },
+                    },
+                    StreamEvent::ContentBlockStop { index: 0 },
+                    StreamEvent::MessageStop,
+                ]
+                .into_iter()
+                .map(Ok),
+            )))
+        }
+
+        fn model_name(&self) -> &str {
+            "mock-streaming"
+        }
+    }
+
+    fn tools() -> Option<Vec<serde_json::Value>> {
+        Some(vec![
+            json!({"name": "read", "input_schema": {"type": "object"}}),
+        ])
+    }
+
+    #[tokio::test]
+    async fn test_record_then_replay() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("cassette.json");
+
+        let inner = MockResponseBuilder::new()
+            .with_tool("read", json!({"path": "src/lib.rs"}))
+            .with_text("Done")
+            .build();
+        let recorder = CassetteClient::record(Box::new(inner), &path);
+        recorder
+            .send_message(vec![Message::user("Read lib.rs")], tools())
+            .await
+            .unwrap();
+        recorder
+            .send_message(
+                vec![Message::user("Read lib.rs"), Message::assistant("ok")],
+                tools(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(recorder.interaction_count(), 2);
+
+        let replay = CassetteClient::replay(&path).unwrap();
+        assert!(!replay.is_recording());
+        assert_eq!(replay.model_name(), "mock-sequenced");
+        assert_eq!(replay.remaining(), 2);
+
+        // Timestamps differ from the recording; fingerprints don't
+        let first = replay
+            .send_message(vec![Message::user("Read lib.rs")], tools())
+            .await
+            .unwrap();
+        assert_eq!(first.stop_reason, StopReason::ToolUse);
+        assert_eq!(first.tool_uses[0].input["path"], "src/lib.rs");
+        let second = replay
+            .send_message(
+                vec![Message::user("Read lib.rs"), Message::assistant("ok")],
+                tools(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(second.content, "Done");
+        assert_eq!(replay.remaining(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_streams_and_errors_replay() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("cassette.json");
+
+        let recorder = CassetteClient::record(Box::new(StreamingMock), &path);
+        let mut stream = recorder
+            .send_message_stream(vec![Message::user("Hi")], None)
+            .await
+            .unwrap();
+        while stream.next().await.is_some() {}
+        assert!(
+            recorder
+                .send_message(vec![Message::user("Hi")], None)
+                .await
+                .is_err()
+        );
+
+        let replay = CassetteClient::replay(&path).unwrap();
+        let mut stream = replay
+            .send_message_stream(vec![Message::user("Hi")], None)
+            .await
+            .unwrap();
+        let mut acc = StreamAccumulator::new();
+        while let Some(event) = stream.next().await {
+            acc.process_event(event.unwrap()).unwrap();
+        }
+        assert_eq!(acc.text(), "Hello world");
+
+        let err = replay
+            .send_message(vec![Message::user("Hi")], None)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("Overloaded"));
+    }
+
+    #[tokio::test]
+    async fn test_strict_mismatch_shows_diff() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("cassette.json");
+
+        let inner = MockResponseBuilder::new()
+            .with_text("A")
+            .with_text("B")
+            .build();
+        let recorder = CassetteClient::record(Box::new(inner), &path);
+        recorder
+            .send_message(vec![Message::user("Fix the bug\nin parser.rs")], tools())
+            .await
+            .unwrap();
+        recorder
+            .send_message(vec![Message::user("Second")], None)
+            .await
+            .unwrap();
+
+        let strict = CassetteClient::replay(&path).unwrap();
+        let err = strict
+            .send_message(vec![Message::user("Fix the bug\nin lexer.rs")], None)
+            .await
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("Request 0 doesn't match"), "{}", err);
+        assert!(err.contains("- line 2: in parser.rs"), "{}", err);
+        assert!(err.contains("+ line 2: in lexer.rs"), "{}", err);
+        assert!(err.contains("- [\"read\"]"), "{}", err);
+
+        // Lenient matching finds the fingerprint out of order, then falls back to order
+        let lenient = CassetteClient::replay(&path)
+            .unwrap()
+            .with_match_mode(MatchMode::Lenient);
+        let second = lenient
+            .send_message(vec![Message::user("Second")], None)
+            .await
+            .unwrap();
+        assert_eq!(second.content, "B");
+        let first = lenient
+            .send_message(vec![Message::user("Something else")], None)
+            .await
+            .unwrap();
+        assert_eq!(first.content, "A");
+        assert!(
+            lenient
+                .send_message(vec![Message::user("More")], None)
+                .await
+                .is_err()
+        );
+    }
+}
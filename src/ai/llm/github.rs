@@ -20,6 +20,7 @@ pub struct GitHubClient {
     temperature: Option<f32>,
     max_tokens: Option<u32>,
     top_p: Option<f32>,
+    seed: Option<u64>,
 }
 
 impl GitHubClient {
@@ -36,6 +37,7 @@ impl GitHubClient {
             temperature: None,
             max_tokens: None,
             top_p: None,
+            seed: None,
         }
     }
 
@@ -63,6 +65,12 @@ impl GitHubClient {
         self
     }
 
+    /// Set sampling seed (best-effort determinism)
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
     /// Convert our messages to OpenAI/GitHub format
     fn to_github_messages(messages: &[Message]) -> Vec<GitHubMessage> {
         messages
@@ -104,6 +112,10 @@ impl LLMClient for GitHubClient {
             body["top_p"] = json!(top_p);
         }
 
+        if let Some(seed) = self.seed {
+            body["seed"] = json!(seed);
+        }
+
         // Add tools if provided
         if let Some(tools_list) = tools
             && !tools_list.is_empty()
@@ -250,12 +262,14 @@ mod tests {
             .with_model("gpt-4o")
             .with_temperature(0.7)
             .with_max_tokens(2048)
-            .with_top_p(0.9);
+            .with_top_p(0.9)
+            .with_seed(3);
 
         assert_eq!(client.model_name(), "gpt-4o");
         assert_eq!(client.temperature.unwrap(), 0.7);
         assert_eq!(client.max_tokens.unwrap(), 2048);
         assert_eq!(client.top_p.unwrap(), 0.9);
+        assert_eq!(client.seed, Some(3));
     }
 
     #[test]
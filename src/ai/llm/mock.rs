@@ -269,6 +269,13 @@ pub struct SequencedMockClient {
     current_index: std::sync::Mutex<usize>,
 }
 
+impl SequencedMockClient {
+    /// Get the number of times send_message was called
+    pub fn call_count(&self) -> usize {
+        *self.current_index.lock().unwrap()
+    }
+}
+
 #[async_trait]
 impl LLMClient for SequencedMockClient {
     async fn send_message(
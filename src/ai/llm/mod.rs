@@ -10,6 +10,7 @@ use serde::{Deserialize, Serialize};
 use std::env;
 
 pub mod anthropic;
+pub mod cassette;
 pub mod errors;
 pub mod github;
 pub mod mock;
@@ -20,6 +21,7 @@ pub mod rate_limiter;
 pub mod streaming;
 
 pub use anthropic::AnthropicClient;
+pub use cassette::{Cassette, CassetteClient, MatchMode};
 pub use errors::LLMError;
 pub use github::GitHubClient;
 pub use mock::{DeterministicLLMClient, MockResponseBuilder, SequencedMockClient};
@@ -73,7 +75,7 @@ pub struct ToolUse {
 }
 
 /// LLM response containing text and/or tool uses
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LLMResponse {
     pub content: String,
     pub tool_uses: Vec<ToolUse>,
@@ -153,6 +155,31 @@ pub trait LLMClient: Send + Sync {
     fn model_name(&self) -> &str;
 }
 
+/// Shared clients (the app keeps its client in an `Arc`) can be handed to
+/// APIs that take a `Box<dyn LLMClient>`, such as [`Agent`](crate::ai::agent::Agent)
+#[async_trait::async_trait]
+impl<T: LLMClient + ?Sized> LLMClient for std::sync::Arc<T> {
+    async fn send_message(
+        &self,
+        messages: Vec<Message>,
+        tools: Option<Vec<serde_json::Value>>,
+    ) -> Result<LLMResponse> {
+        (**self).send_message(messages, tools).await
+    }
+
+    async fn send_message_stream(
+        &self,
+        messages: Vec<Message>,
+        tools: Option<Vec<serde_json::Value>>,
+    ) -> Result<MessageStream> {
+        (**self).send_message_stream(messages, tools).await
+    }
+
+    fn model_name(&self) -> &str {
+        (**self).model_name()
+    }
+}
+
 /// Get API key from environment variable
 pub fn get_api_key() -> Result<String> {
     env::var("ANTHROPIC_API_KEY").context("ANTHROPIC_API_KEY environment variable not set")
@@ -20,6 +20,7 @@ pub struct OllamaClient {
     // Optional parameters
     temperature: Option<f32>,
     num_predict: Option<u32>, // Ollama's equivalent to max_tokens
+    seed: Option<u64>,
 }
 
 impl OllamaClient {
@@ -31,6 +32,7 @@ impl OllamaClient {
             http_client: reqwest::Client::new(),
             temperature: None,
             num_predict: None,
+            seed: None,
         }
     }
 
@@ -52,6 +54,12 @@ impl OllamaClient {
         self
     }
 
+    /// Set sampling seed
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
     /// Convert our messages to Ollama format
     fn to_ollama_messages(messages: &[Message]) -> Vec<OllamaMessage> {
         messages
@@ -98,6 +106,10 @@ impl LLMClient for OllamaClient {
             }
         }
 
+        if let Some(seed) = self.seed {
+            body["options"]["seed"] = json!(seed);
+        }
+
         // Make API request
         let response = self
             .http_client
@@ -188,12 +200,14 @@ mod tests {
         let client = OllamaClient::new("codellama")
             .with_base_url("http://192.168.1.100:11434")
             .with_temperature(0.7)
-            .with_num_predict(2048);
+            .with_num_predict(2048)
+            .with_seed(3);
 
         assert_eq!(client.model_name(), "codellama");
         assert_eq!(client.base_url, "http://192.168.1.100:11434");
         assert_eq!(client.temperature.unwrap(), 0.7);
         assert_eq!(client.num_predict.unwrap(), 2048);
+        assert_eq!(client.seed, Some(3));
     }
 
     #[test]
@@ -40,6 +40,10 @@ pub struct ProviderConfig {
     /// Temperature (0.0-1.0 or 0.0-2.0 depending on provider)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub temperature: Option<f32>,
+
+    /// Sampling seed (GitHub, Ollama; Anthropic has none)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seed: Option<u64>,
 }
 
 fn default_max_tokens() -> u32 {
@@ -55,6 +59,7 @@ impl Default for ProviderConfig {
             base_url: None,
             max_tokens: 4096,
             temperature: None,
+            seed: None,
         }
     }
 }
@@ -112,6 +117,12 @@ impl ProviderConfig {
         self
     }
 
+    /// Set sampling seed
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
     /// Get API key from config or environment
     fn resolve_api_key(&self, env_var: &str) -> Result<String> {
         if let Some(ref key) = self.api_key {
@@ -166,6 +177,10 @@ impl LLMProvider {
                     client = client.with_temperature(temp);
                 }
 
+                if let Some(seed) = config.seed {
+                    client = client.with_seed(seed);
+                }
+
                 Ok(Box::new(client))
             }
 
@@ -181,6 +196,10 @@ impl LLMProvider {
                     client = client.with_temperature(temp);
                 }
 
+                if let Some(seed) = config.seed {
+                    client = client.with_seed(seed);
+                }
+
                 if config.max_tokens > 0 {
                     client = client.with_num_predict(config.max_tokens);
                 }
@@ -243,11 +262,14 @@ mod tests {
 
     #[test]
     fn test_ollama_config() {
-        let config = ProviderConfig::ollama("llama2").with_base_url("http://192.168.1.100:11434");
+        let config = ProviderConfig::ollama("llama2")
+            .with_base_url("http://192.168.1.100:11434")
+            .with_seed(7);
 
         assert_eq!(config.provider, ProviderType::Ollama);
         assert_eq!(config.model, "llama2");
         assert_eq!(config.base_url.unwrap(), "http://192.168.1.100:11434");
+        assert_eq!(config.seed, Some(7));
     }
 
     #[test]
@@ -7,12 +7,13 @@ use anyhow::{Context, Result};
 use eventsource_stream::Eventsource;
 use futures::Stream;
 use pin_project::pin_project;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::pin::Pin;
 use std::task::{Context as TaskContext, Poll};
 
 /// Stream event from Claude API
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
 pub enum StreamEvent {
     /// Message started with initial metadata
     MessageStart { message: MessageStart },
@@ -46,7 +47,7 @@ pub enum StreamEvent {
 }
 
 /// Initial message metadata
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MessageStart {
     pub id: String,
     pub model: String,
@@ -57,7 +58,7 @@ pub struct MessageStart {
 }
 
 /// Content block start information
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum ContentBlockStart {
     Text { text: String },
@@ -66,7 +67,7 @@ pub enum ContentBlockStart {
 }
 
 /// Content delta (incremental content)
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum ContentDelta {
     TextDelta { text: String },
@@ -74,14 +75,14 @@ pub enum ContentDelta {
 }
 
 /// Message delta information
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MessageDeltaEvent {
     pub stop_reason: Option<StopReason>,
     pub stop_sequence: Option<String>,
 }
 
 /// Streaming usage information
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StreamUsage {
     pub input_tokens: u32,
     pub output_tokens: u32,
@@ -103,7 +104,7 @@ impl From<StreamUsage> for Usage {
 }
 
 /// API error during streaming
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ApiError {
     #[serde(rename = "type")]
     pub error_type: String,
@@ -182,8 +183,15 @@ impl MessageStream {
             }
         });
 
+        Self::from_stream(mapped_stream)
+    }
+
+    /// Wrap any stream of events (e.g., recorded ones)
+    pub fn from_stream(
+        stream: impl Stream<Item = Result<StreamEvent>> + Send + Unpin + 'static,
+    ) -> Self {
         Self {
-            inner: Box::new(mapped_stream),
+            inner: Box::new(stream),
         }
     }
 
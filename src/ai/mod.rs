@@ -4,6 +4,7 @@
 //! evaluation frameworks, and tooling.
 
 pub mod agent;
+pub mod card_runner;
 pub mod context;
 pub mod eval_commands;
 pub mod eval_runner;
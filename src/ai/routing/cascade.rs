@@ -120,6 +120,7 @@ impl CascadingRouter {
                 base_url: Some("http://localhost:11434".to_string()),
                 max_tokens: 4096,
                 temperature: Some(0.3),
+                seed: None,
             }),
 
             ModelTier::CloudPremium | ModelTier::CloudBest => {
@@ -135,6 +136,7 @@ impl CascadingRouter {
                     base_url: None,
                     max_tokens: 8192,
                     temperature: Some(0.3),
+                    seed: None,
                 })
             }
         }
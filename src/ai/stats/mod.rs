@@ -1,9 +1,13 @@
 /// Statistical testing for A/B comparisons
 use crate::ai::evaluation::EvaluationResults;
+use crate::ai::evaluation::sampling::{PassAtK, group_by_task, per_task_pass_at_k};
 use serde::{Deserialize, Serialize};
 use statrs::distribution::{ContinuousCDF, StudentsT};
 use statrs::statistics::Statistics;
 
+pub mod paired;
+pub use paired::{McNemarTest, PairedTests, WilcoxonTest};
+
 /// Result of comparing two configurations
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ComparisonResult {
@@ -21,6 +25,31 @@ pub struct ComparisonResult {
 
     /// Recommendation
     pub recommendation: Recommendation,
+
+    /// pass@k of both configurations (see [`ComparisonResult::with_pass_at_k`])
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pass_at_k: Vec<PassAtKComparison>,
+}
+
+/// pass@k of two configurations on the same benchmark
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PassAtKComparison {
+    /// Number of attempts allowed per task
+    pub k: usize,
+
+    /// pass@k of configuration A
+    pub a: PassAtK,
+
+    /// pass@k of configuration B
+    pub b: PassAtK,
+
+    /// Difference in pass@k (B - A)
+    pub delta: f64,
+
+    /// Bootstrap confidence interval of the per-task difference, if both
+    /// configurations ran the same tasks
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub delta_ci: Option<(f64, f64)>,
 }
 
 /// Delta between two configurations
@@ -46,6 +75,10 @@ pub struct DeltaMetrics {
 }
 
 /// Statistical significance test results
+///
+/// When both configurations ran the same tasks, accuracy and cost come from
+/// the paired tests in [`SignificanceTest::paired`] (corrected across
+/// metrics); otherwise from Welch's unpaired t-test.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SignificanceTest {
     /// Is the accuracy difference statistically significant? (p < 0.05)
@@ -54,7 +87,7 @@ pub struct SignificanceTest {
     /// P-value for accuracy difference
     pub accuracy_p_value: f64,
 
-    /// T-statistic for accuracy
+    /// Welch's t-statistic for accuracy (informational when paired)
     pub accuracy_t_stat: f64,
 
     /// Is the cost difference statistically significant?
@@ -65,6 +98,11 @@ pub struct SignificanceTest {
 
     /// Confidence level
     pub confidence: f64,
+
+    /// Paired tests of accuracy, cost, latency and tokens, if the
+    /// configurations have tasks in common
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub paired: Option<PairedTests>,
 }
 
 /// Recommendation based on comparison
@@ -105,17 +143,16 @@ impl ComparisonResult {
             api_calls: 0.0, // TODO: compute from results
         };
 
-        // Extract metrics for statistical testing
-        let metrics_a: Vec<f64> = results_a
-            .results
+        // Extract metrics for statistical testing (one solve rate per task, so
+        // repeated samples of a task are not counted as independent)
+        let metrics_a: Vec<f64> = group_by_task(&results_a.results)
             .iter()
-            .map(|r| if r.solved { 1.0 } else { 0.0 })
+            .map(|t| t.solve_rate())
             .collect();
 
-        let metrics_b: Vec<f64> = results_b
-            .results
+        let metrics_b: Vec<f64> = group_by_task(&results_b.results)
             .iter()
-            .map(|r| if r.solved { 1.0 } else { 0.0 })
+            .map(|t| t.solve_rate())
             .collect();
 
         let costs_a: Vec<f64> = results_a.results.iter().map(|r| r.cost_usd).collect();
@@ -124,13 +161,31 @@ impl ComparisonResult {
         let accuracy_test = Self::t_test(&metrics_a, &metrics_b);
         let cost_test = Self::t_test(&costs_a, &costs_b);
 
-        let significance = SignificanceTest {
-            accuracy_significant: accuracy_test.p_value < 0.05,
-            accuracy_p_value: accuracy_test.p_value,
-            accuracy_t_stat: accuracy_test.t_stat,
-            cost_significant: cost_test.p_value < 0.05,
-            cost_p_value: cost_test.p_value,
-            confidence: 0.95,
+        let confidence = 0.95;
+        let paired = PairedTests::from_results(
+            &results_a.results,
+            &results_b.results,
+            1.0 - confidence,
+        );
+        let significance = match &paired {
+            Some(tests) => SignificanceTest {
+                accuracy_significant: tests.accuracy.significant,
+                accuracy_p_value: tests.accuracy.p_value,
+                accuracy_t_stat: accuracy_test.t_stat,
+                cost_significant: tests.cost.significant,
+                cost_p_value: tests.cost.p_value,
+                confidence,
+                paired,
+            },
+            None => SignificanceTest {
+                accuracy_significant: accuracy_test.p_value < 0.05,
+                accuracy_p_value: accuracy_test.p_value,
+                accuracy_t_stat: accuracy_test.t_stat,
+                cost_significant: cost_test.p_value < 0.05,
+                cost_p_value: cost_test.p_value,
+                confidence,
+                paired: None,
+            },
         };
 
         let recommendation = Self::make_recommendation(&delta, &significance);
@@ -141,9 +196,74 @@ impl ComparisonResult {
             delta,
             significance,
             recommendation,
+            pass_at_k: Vec::new(),
         }
     }
 
+    /// Add pass@k comparisons for each of `ks`
+    ///
+    /// Values of `k` larger than the number of samples of some task are
+    /// skipped. When both configurations ran the same tasks, the confidence
+    /// interval of the difference is a paired bootstrap over tasks.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// let comparison = ComparisonResult::compare(&results_a, &results_b)
+    ///     .with_pass_at_k(&results_a, &results_b, &[1, 5]);
+    /// for pass in &comparison.pass_at_k {
+    ///     println!("pass@{}: {:+.3}", pass.k, pass.delta);
+    /// }
+    /// ```
+    pub fn with_pass_at_k(
+        mut self,
+        results_a: &EvaluationResults,
+        results_b: &EvaluationResults,
+        ks: &[usize],
+    ) -> Self {
+        const CONFIDENCE: f64 = 0.95;
+
+        let mut tasks_a = group_by_task(&results_a.results);
+        let mut tasks_b = group_by_task(&results_b.results);
+        tasks_a.sort_by(|x, y| x.task_id.cmp(&y.task_id));
+        tasks_b.sort_by(|x, y| x.task_id.cmp(&y.task_id));
+        let paired = tasks_a.len() == tasks_b.len()
+            && tasks_a
+                .iter()
+                .zip(&tasks_b)
+                .all(|(x, y)| x.task_id == y.task_id);
+
+        for &k in ks {
+            let (Some(a), Some(b)) = (
+                PassAtK::estimate(&tasks_a, k, CONFIDENCE),
+                PassAtK::estimate(&tasks_b, k, CONFIDENCE),
+            ) else {
+                continue;
+            };
+            let delta_ci = paired
+                .then(|| {
+                    let values_a = per_task_pass_at_k(&tasks_a, k)?;
+                    let values_b = per_task_pass_at_k(&tasks_b, k)?;
+                    let diffs: Vec<f64> =
+                        values_b.iter().zip(&values_a).map(|(b, a)| b - a).collect();
+                    Some(StatisticalTest::bootstrap_ci(
+                        &diffs,
+                        |s| s.mean(),
+                        CONFIDENCE,
+                    ))
+                })
+                .flatten();
+            self.pass_at_k.push(PassAtKComparison {
+                k,
+                delta: b.value - a.value,
+                a,
+                b,
+                delta_ci,
+            });
+        }
+        self
+    }
+
     /// Perform Welch's t-test
     fn t_test(sample_a: &[f64], sample_b: &[f64]) -> TTestResult {
         if sample_a.is_empty() || sample_b.is_empty() {
@@ -161,6 +281,16 @@ impl ComparisonResult {
         let n_a = sample_a.len() as f64;
         let n_b = sample_b.len() as f64;
 
+        // Identical constant samples (e.g. every cost is zero) carry no evidence
+        // of a difference, and would give an undefined t-distribution
+        if var_a / n_a + var_b / n_b == 0.0 {
+            return TTestResult {
+                t_stat: 0.0,
+                p_value: if mean_a == mean_b { 1.0 } else { 0.0 },
+                _degrees_of_freedom: 0.0,
+            };
+        }
+
         // Welch's t-statistic
         let t_stat = (mean_b - mean_a) / ((var_a / n_a) + (var_b / n_b)).sqrt();
 
@@ -195,6 +325,10 @@ impl ComparisonResult {
         //   - Cost increase > 30% for < +2% accuracy
         //   - Adds complexity without clear benefit
 
+        // With paired tests, cost and latency claims must be significant too
+        let cost_shown = sig.paired.as_ref().is_none_or(|p| p.cost.significant);
+        let latency_shown = sig.paired.as_ref().is_none_or(|p| p.latency.significant);
+
         // Check if we have enough data
         if !sig.accuracy_significant && delta.accuracy.abs() < 2.0 {
             return Recommendation::NeedMoreData;
@@ -205,11 +339,15 @@ impl ComparisonResult {
             return Recommendation::Adopt;
         }
 
-        if delta.cost_pct <= -20.0 && delta.accuracy >= -0.5 {
+        if delta.cost_pct <= -20.0 && delta.accuracy >= -0.5 && cost_shown {
             return Recommendation::Adopt;
         }
 
-        if delta.duration_pct <= -30.0 && delta.accuracy >= -0.5 && delta.cost_pct < 20.0 {
+        if delta.duration_pct <= -30.0
+            && delta.accuracy >= -0.5
+            && delta.cost_pct < 20.0
+            && latency_shown
+        {
             return Recommendation::Adopt;
         }
 
@@ -218,7 +356,7 @@ impl ComparisonResult {
             return Recommendation::Reject;
         }
 
-        if delta.cost_pct > 30.0 && delta.accuracy < 2.0 {
+        if delta.cost_pct > 30.0 && delta.accuracy < 2.0 && cost_shown {
             return Recommendation::Reject;
         }
 
@@ -260,7 +398,60 @@ impl ComparisonResult {
             self.significance.accuracy_significant
         );
         println!("  Cost significant: {}", self.significance.cost_significant);
+        if let Some(paired) = &self.significance.paired {
+            println!(
+                "  Paired on {} tasks (Benjamini-Hochberg corrected):",
+                paired.tasks
+            );
+            println!(
+                "    Accuracy (McNemar): {} only A, {} only B, p={:.4}{}",
+                paired.accuracy.only_a,
+                paired.accuracy.only_b,
+                paired.accuracy.p_value,
+                if paired.accuracy.significant { " *" } else { "" }
+            );
+            for (name, test) in [
+                ("Cost", &paired.cost),
+                ("Latency", &paired.latency),
+                ("Tokens", &paired.tokens),
+            ] {
+                println!(
+                    "    {} (Wilcoxon): median delta {:+.4}, p={:.4}{}",
+                    name,
+                    test.median_delta,
+                    test.p_value,
+                    if test.significant { " *" } else { "" }
+                );
+            }
+            if let Some(required) = paired.required_tasks {
+                println!(
+                    "  Tasks needed to detect this accuracy difference (80% power): {}",
+                    required
+                );
+            }
+        }
         println!();
+        if !self.pass_at_k.is_empty() {
+            println!("pass@k (95% CI):");
+            for pass in &self.pass_at_k {
+                print!(
+                    "  pass@{}: A {:.3} [{:.3}, {:.3}], B {:.3} [{:.3}, {:.3}], delta {:+.3}",
+                    pass.k,
+                    pass.a.value,
+                    pass.a.ci_lower,
+                    pass.a.ci_upper,
+                    pass.b.value,
+                    pass.b.ci_lower,
+                    pass.b.ci_upper,
+                    pass.delta
+                );
+                match pass.delta_ci {
+                    Some((lower, upper)) => println!(" [{:+.3}, {:+.3}]", lower, upper),
+                    None => println!(),
+                }
+            }
+            println!();
+        }
         println!("Recommendation: {:?}", self.recommendation);
 
         match self.recommendation {
@@ -379,6 +570,35 @@ impl StatisticalTest {
         }
     }
 
+    /// Unbiased pass@k estimate for a task with `c` of `n` samples solved
+    ///
+    /// Probability that at least one of `k` samples drawn without replacement
+    /// from the `n` solves the task: `1 - C(n - c, k) / C(n, k)`, computed as a
+    /// product to stay stable for large `n` (Chen et al., 2021).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use toad::ai::stats::StatisticalTest;
+    ///
+    /// assert!((StatisticalTest::pass_at_k(10, 3, 1) - 0.3).abs() < 1e-12);
+    /// assert_eq!(StatisticalTest::pass_at_k(10, 0, 5), 0.0);
+    /// assert_eq!(StatisticalTest::pass_at_k(10, 8, 3), 1.0); // every draw of 3 hits
+    /// assert!((StatisticalTest::pass_at_k(4, 1, 2) - 0.5).abs() < 1e-12);
+    /// ```
+    pub fn pass_at_k(n: usize, c: usize, k: usize) -> f64 {
+        if c == 0 || k == 0 {
+            return 0.0;
+        }
+        if n - c.min(n) < k {
+            return 1.0;
+        }
+        let miss: f64 = ((n - c + 1)..=n)
+            .map(|i| 1.0 - k as f64 / i as f64)
+            .product();
+        1.0 - miss
+    }
+
     /// Calculate bootstrap confidence interval using BCa method
     ///
     /// Uses the Bias-Corrected and Accelerated (BCa) bootstrap method with
@@ -765,6 +985,81 @@ mod tests {
         assert!(comparison.delta.cost_pct > 50.0);
     }
 
+    #[test]
+    fn test_comparison_uses_paired_tests() {
+        // B solves every task A solves plus 16 more
+        let solved_a: Vec<bool> = (0..30).map(|i| i % 3 == 0).collect();
+        let solved_b: Vec<bool> = (0..30).map(|i| i % 3 == 0 || i % 5 != 0).collect();
+        let results_a = create_mock_results("baseline", solved_a, vec![0.01; 30]);
+        let results_b = create_mock_results("improved", solved_b, vec![0.01; 30]);
+
+        let comparison = ComparisonResult::compare(&results_a, &results_b);
+        let paired = comparison.significance.paired.as_ref().unwrap();
+
+        assert_eq!(paired.tasks, 30);
+        assert_eq!(paired.accuracy.only_a, 0);
+        assert!(paired.accuracy.exact);
+        assert!(comparison.significance.accuracy_significant);
+        assert_eq!(
+            comparison.significance.accuracy_p_value,
+            paired.accuracy.p_value
+        );
+        assert!(!comparison.significance.cost_significant);
+        assert_eq!(comparison.recommendation, Recommendation::Adopt);
+    }
+
+    #[test]
+    fn test_pass_at_k_estimator() {
+        assert_eq!(StatisticalTest::pass_at_k(5, 0, 1), 0.0);
+        assert_eq!(StatisticalTest::pass_at_k(5, 5, 1), 1.0);
+        assert!((StatisticalTest::pass_at_k(5, 2, 1) - 0.4).abs() < 1e-12);
+        // 1 - C(3, 2) / C(5, 2) = 1 - 3 / 10
+        assert!((StatisticalTest::pass_at_k(5, 2, 2) - 0.7).abs() < 1e-12);
+        assert_eq!(StatisticalTest::pass_at_k(5, 4, 2), 1.0);
+        // Stable for large n
+        let p = StatisticalTest::pass_at_k(200, 3, 100);
+        assert!(p > 0.87 && p < 0.88, "{}", p);
+    }
+
+    #[test]
+    fn test_comparison_pass_at_k() {
+        // 10 tasks x 4 samples; B solves each task more often than A
+        let samples = |name: &str, solved_per_task: usize| {
+            let results: Vec<TaskResult> = (0..10)
+                .flat_map(|task| {
+                    (0..4).map(move |sample| {
+                        let mut result = TaskResult::new(format!("task-{}", task));
+                        result.sample = Some(sample);
+                        if sample < solved_per_task + task % 2 {
+                            result.mark_solved();
+                        }
+                        result
+                    })
+                })
+                .collect();
+            EvaluationResults::from_results(name.to_string(), results)
+        };
+        let results_a = samples("baseline", 1);
+        let results_b = samples("improved", 2);
+
+        let comparison =
+            ComparisonResult::compare(&results_a, &results_b).with_pass_at_k(
+                &results_a,
+                &results_b,
+                &[1, 2, 8],
+            );
+
+        // k = 8 exceeds the samples per task
+        assert_eq!(comparison.pass_at_k.len(), 2);
+        let pass_at_1 = &comparison.pass_at_k[0];
+        assert_eq!(pass_at_1.k, 1);
+        assert!((pass_at_1.a.value - 0.375).abs() < 1e-12);
+        assert!((pass_at_1.delta - 0.25).abs() < 1e-12);
+        // Every task improves by exactly one sample
+        assert_eq!(pass_at_1.delta_ci, Some((0.25, 0.25)));
+        assert!(comparison.pass_at_k[1].a.value > pass_at_1.a.value);
+    }
+
     #[test]
     fn test_cohens_d() {
         let sample_a = vec![1.0, 2.0, 3.0, 4.0, 5.0];
@@ -0,0 +1,508 @@
+//! Paired significance tests for same-task A/B comparisons
+//!
+//! [`ComparisonResult::compare`](super::ComparisonResult::compare) runs both
+//! configurations on the same tasks, so every task yields a pair of
+//! observations. Comparing each task with itself removes the between-task
+//! variance (some tasks are simply hard) that an unpaired test has to absorb:
+//!
+//! - Accuracy: McNemar's test on the discordant pairs (tasks solved by only
+//!   one configuration), exact binomial below 25 discordant pairs
+//! - Cost, latency and tokens: Wilcoxon signed-rank test on the per-task
+//!   differences, exact below 25 non-zero differences
+//!
+//! [`PairedTests::from_results`] runs all four and corrects their p-values
+//! with [`StatisticalTest::benjamini_hochberg`]. The sample size functions
+//! answer the inverse question: how many tasks a run needs to detect an
+//! effect.
+//!
+//! # Examples
+//!
+//! ```
+//! use toad::ai::stats::StatisticalTest;
+//!
+//! // B solved 9 tasks A missed, A solved 1 task B missed
+//! let test = StatisticalTest::mcnemar(1, 9);
+//! assert!(test.exact);
+//! assert!(test.p_value < 0.05);
+//!
+//! // Tasks needed when 20% of tasks are discordant and B gains 10 points
+//! let n = StatisticalTest::mcnemar_sample_size(0.05, 0.15, 0.05, 0.8).unwrap();
+//! assert_eq!(n, 155);
+//! ```
+
+use super::StatisticalTest;
+use crate::ai::evaluation::TaskResult;
+use serde::{Deserialize, Serialize};
+use statrs::distribution::{ContinuousCDF, Normal};
+use std::collections::HashMap;
+
+/// Below this many discordant pairs or non-zero differences, p-values are
+/// exact instead of normal approximations
+const EXACT_LIMIT: usize = 25;
+
+/// Asymptotic relative efficiency of the Wilcoxon signed-rank test against the
+/// paired t-test; 0.864 is its minimum over all distributions
+const WILCOXON_MIN_ARE: f64 = 0.864;
+
+/// Paired tests of every compared metric
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PairedTests {
+    /// Number of tasks both configurations ran
+    pub tasks: usize,
+
+    /// Solved/unsolved (McNemar)
+    pub accuracy: McNemarTest,
+
+    /// Per-task cost in USD (Wilcoxon)
+    pub cost: WilcoxonTest,
+
+    /// Per-task duration in milliseconds (Wilcoxon)
+    pub latency: WilcoxonTest,
+
+    /// Per-task total tokens (Wilcoxon)
+    pub tokens: WilcoxonTest,
+
+    /// Tasks needed to detect the observed accuracy difference with 80% power
+    ///
+    /// None if there is no difference to detect.
+    pub required_tasks: Option<usize>,
+}
+
+/// McNemar's test on paired solved/unsolved outcomes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct McNemarTest {
+    /// Tasks solved by A but not by B
+    pub only_a: usize,
+
+    /// Tasks solved by B but not by A
+    pub only_b: usize,
+
+    /// Whether the p-value is an exact binomial one
+    pub exact: bool,
+
+    /// Two-sided p-value
+    pub p_value: f64,
+
+    /// Significant at 5% (after correction when part of [`PairedTests`])
+    pub significant: bool,
+}
+
+/// Wilcoxon signed-rank test on per-task differences
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WilcoxonTest {
+    /// Number of non-zero differences (zero differences are dropped)
+    pub n: usize,
+
+    /// Rank sum of the positive differences (B > A)
+    pub w_plus: f64,
+
+    /// Median per-task difference (B - A)
+    pub median_delta: f64,
+
+    /// Whether the p-value comes from the exact null distribution
+    pub exact: bool,
+
+    /// Two-sided p-value
+    pub p_value: f64,
+
+    /// Significant at 5% (after correction when part of [`PairedTests`])
+    pub significant: bool,
+}
+
+/// Per-task means of one configuration's samples
+struct TaskOutcome {
+    solved: bool,
+    cost_usd: f64,
+    duration_ms: f64,
+    tokens: f64,
+}
+
+impl PairedTests {
+    /// Pair the tasks of two runs and test every metric
+    ///
+    /// Tasks that only one run contains are ignored. In sampling mode a task
+    /// counts as solved when at least half of its samples are, and its cost,
+    /// latency and tokens are the means over its samples. p-values are
+    /// corrected across the four metrics at false discovery rate `fdr_level`.
+    ///
+    /// Returns None if the runs have no task in common.
+    pub fn from_results(a: &[TaskResult], b: &[TaskResult], fdr_level: f64) -> Option<Self> {
+        let outcomes_a = task_outcomes(a);
+        let outcomes_b = task_outcomes(b);
+        let pairs: Vec<(&TaskOutcome, &TaskOutcome)> = outcomes_a
+            .iter()
+            .filter_map(|(id, a)| outcomes_b.get(id).map(|b| (a, b)))
+            .collect();
+        if pairs.is_empty() {
+            return None;
+        }
+
+        let only_a = pairs.iter().filter(|(a, b)| a.solved && !b.solved).count();
+        let only_b = pairs.iter().filter(|(a, b)| !a.solved && b.solved).count();
+        let diffs = |metric: fn(&TaskOutcome) -> f64| -> Vec<f64> {
+            pairs.iter().map(|(a, b)| metric(b) - metric(a)).collect()
+        };
+
+        let mut tests = Self {
+            tasks: pairs.len(),
+            accuracy: StatisticalTest::mcnemar(only_a, only_b),
+            cost: StatisticalTest::wilcoxon_signed_rank(&diffs(|t| t.cost_usd)),
+            latency: StatisticalTest::wilcoxon_signed_rank(&diffs(|t| t.duration_ms)),
+            tokens: StatisticalTest::wilcoxon_signed_rank(&diffs(|t| t.tokens)),
+            required_tasks: StatisticalTest::mcnemar_sample_size(
+                only_a as f64 / pairs.len() as f64,
+                only_b as f64 / pairs.len() as f64,
+                0.05,
+                0.8,
+            ),
+        };
+
+        let rejections = StatisticalTest::benjamini_hochberg(
+            &[
+                tests.accuracy.p_value,
+                tests.cost.p_value,
+                tests.latency.p_value,
+                tests.tokens.p_value,
+            ],
+            fdr_level,
+        );
+        tests.accuracy.significant = rejections[0];
+        tests.cost.significant = rejections[1];
+        tests.latency.significant = rejections[2];
+        tests.tokens.significant = rejections[3];
+
+        Some(tests)
+    }
+}
+
+fn task_outcomes(results: &[TaskResult]) -> HashMap<&str, TaskOutcome> {
+    let mut sums: HashMap<&str, (usize, usize, f64, f64, f64)> = HashMap::new();
+    for result in results {
+        let sum = sums.entry(result.task_id.as_str()).or_default();
+        sum.0 += 1;
+        sum.1 += usize::from(result.solved);
+        sum.2 += result.cost_usd;
+        sum.3 += result.duration_ms as f64;
+        sum.4 += result.total_tokens as f64;
+    }
+    sums.into_iter()
+        .map(|(id, (n, solved, cost, duration, tokens))| {
+            let n_f = n as f64;
+            let outcome = TaskOutcome {
+                solved: 2 * solved >= n,
+                cost_usd: cost / n_f,
+                duration_ms: duration / n_f,
+                tokens: tokens / n_f,
+            };
+            (id, outcome)
+        })
+        .collect()
+}
+
+impl StatisticalTest {
+    /// McNemar's test for paired binary outcomes
+    ///
+    /// Only the discordant pairs carry information: `only_a` tasks solved by A
+    /// alone and `only_b` by B alone. Under the null hypothesis each discordant
+    /// pair is equally likely to go either way, so below 25 pairs the p-value
+    /// is the exact two-sided binomial one; above, the continuity-corrected
+    /// chi-square approximation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use toad::ai::stats::StatisticalTest;
+    ///
+    /// assert_eq!(StatisticalTest::mcnemar(3, 3).p_value, 1.0);
+    /// // 2 * P(X <= 1) for X ~ Binomial(10, 0.5)
+    /// assert!((StatisticalTest::mcnemar(1, 9).p_value - 22.0 / 1024.0).abs() < 1e-12);
+    /// ```
+    pub fn mcnemar(only_a: usize, only_b: usize) -> McNemarTest {
+        let n = only_a + only_b;
+        let exact = n < EXACT_LIMIT;
+        let p_value = if n == 0 {
+            1.0
+        } else if exact {
+            // 2 * P(X <= min) for X ~ Binomial(n, 1/2)
+            let mut term = 0.5_f64.powi(n as i32);
+            let mut tail = 0.0;
+            for i in 0..=only_a.min(only_b) {
+                tail += term;
+                term *= (n - i) as f64 / (i + 1) as f64;
+            }
+            (2.0 * tail).min(1.0)
+        } else {
+            let diff = only_a.abs_diff(only_b) as f64;
+            let z = ((diff - 1.0).max(0.0)) / (n as f64).sqrt();
+            2.0 * (1.0 - standard_normal().cdf(z))
+        };
+
+        McNemarTest {
+            only_a,
+            only_b,
+            exact,
+            p_value,
+            significant: p_value < 0.05,
+        }
+    }
+
+    /// Wilcoxon signed-rank test on paired differences (B - A)
+    ///
+    /// Zero differences are dropped and tied magnitudes get their average
+    /// rank. Below 25 non-zero differences the p-value comes from the exact
+    /// permutation distribution of the rank sum (conditional on the ties);
+    /// above, from the normal approximation with tie and continuity
+    /// corrections.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use toad::ai::stats::StatisticalTest;
+    ///
+    /// // All 10 differences positive: 2 / 2^10
+    /// let diffs: Vec<f64> = (1..=10).map(|d| d as f64).collect();
+    /// let test = StatisticalTest::wilcoxon_signed_rank(&diffs);
+    /// assert!((test.p_value - 2.0 / 1024.0).abs() < 1e-12);
+    /// assert_eq!(test.median_delta, 5.5);
+    /// ```
+    pub fn wilcoxon_signed_rank(diffs: &[f64]) -> WilcoxonTest {
+        let median_delta = median(diffs);
+        let nonzero: Vec<f64> = diffs.iter().copied().filter(|d| *d != 0.0).collect();
+        let n = nonzero.len();
+        let exact = n < EXACT_LIMIT;
+        if n == 0 {
+            return WilcoxonTest {
+                n,
+                w_plus: 0.0,
+                median_delta,
+                exact,
+                p_value: 1.0,
+                significant: false,
+            };
+        }
+
+        let magnitudes: Vec<f64> = nonzero.iter().map(|d| d.abs()).collect();
+        let ranks = Self::rank_data(&magnitudes);
+        let w_plus: f64 = nonzero
+            .iter()
+            .zip(&ranks)
+            .filter(|(d, _)| **d > 0.0)
+            .map(|(_, r)| r)
+            .sum();
+
+        let p_value = if exact {
+            // Average ranks are multiples of 1/2, so doubled ranks are integers
+            let doubled: Vec<usize> = ranks.iter().map(|r| (2.0 * r).round() as usize).collect();
+            let total: usize = doubled.iter().sum();
+            let mut counts = vec![0.0_f64; total + 1];
+            counts[0] = 1.0;
+            for &rank in &doubled {
+                for sum in (rank..=total).rev() {
+                    counts[sum] += counts[sum - rank];
+                }
+            }
+            let all = 2.0_f64.powi(n as i32);
+            let observed = (2.0 * w_plus).round() as usize;
+            let lower: f64 = counts[..=observed].iter().sum::<f64>() / all;
+            let upper: f64 = counts[observed..].iter().sum::<f64>() / all;
+            (2.0 * lower.min(upper)).min(1.0)
+        } else {
+            let n_f = n as f64;
+            let mean = n_f * (n_f + 1.0) / 4.0;
+            let ties: f64 = tie_groups(&ranks).map(|t| t * t * t - t).sum();
+            let variance = n_f * (n_f + 1.0) * (2.0 * n_f + 1.0) / 24.0 - ties / 48.0;
+            if variance <= 0.0 {
+                1.0
+            } else {
+                let z = ((w_plus - mean).abs() - 0.5).max(0.0) / variance.sqrt();
+                2.0 * (1.0 - standard_normal().cdf(z))
+            }
+        };
+
+        WilcoxonTest {
+            n,
+            w_plus,
+            median_delta,
+            exact,
+            p_value,
+            significant: p_value < 0.05,
+        }
+    }
+
+    /// Tasks needed for McNemar's test to detect an accuracy difference
+    ///
+    /// `only_a_rate` and `only_b_rate` are the expected fractions of tasks
+    /// solved by only A and only B; their difference is the accuracy effect
+    /// and their sum the discordance. Uses Connor's (1987) formula for a
+    /// two-sided test at `alpha` with the given `power`.
+    ///
+    /// Returns None if the rates are equal (there is no effect to detect).
+    pub fn mcnemar_sample_size(
+        only_a_rate: f64,
+        only_b_rate: f64,
+        alpha: f64,
+        power: f64,
+    ) -> Option<usize> {
+        let effect = only_b_rate - only_a_rate;
+        let discordance = only_a_rate + only_b_rate;
+        if effect == 0.0 {
+            return None;
+        }
+        let normal = standard_normal();
+        let z_alpha = normal.inverse_cdf(1.0 - alpha / 2.0);
+        let z_beta = normal.inverse_cdf(power);
+        let root = z_alpha * discordance.sqrt() + z_beta * (discordance - effect * effect).sqrt();
+        Some((root * root / (effect * effect)).ceil() as usize)
+    }
+
+    /// Tasks needed for the Wilcoxon signed-rank test to detect an effect
+    ///
+    /// `effect_size` is the mean per-task difference divided by the standard
+    /// deviation of the differences (Cohen's d_z). The paired t-test sample
+    /// size is inflated by the Wilcoxon test's minimum relative efficiency
+    /// (0.864), so the result holds whatever the distribution of differences.
+    ///
+    /// Returns None for a zero effect.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use toad::ai::stats::StatisticalTest;
+    ///
+    /// let medium = StatisticalTest::wilcoxon_sample_size(0.5, 0.05, 0.8).unwrap();
+    /// let small = StatisticalTest::wilcoxon_sample_size(0.2, 0.05, 0.8).unwrap();
+    /// assert_eq!(medium, 37);
+    /// assert!(small > medium);
+    /// ```
+    pub fn wilcoxon_sample_size(effect_size: f64, alpha: f64, power: f64) -> Option<usize> {
+        if effect_size == 0.0 {
+            return None;
+        }
+        let normal = standard_normal();
+        let z = normal.inverse_cdf(1.0 - alpha / 2.0) + normal.inverse_cdf(power);
+        let paired_t = (z / effect_size).powi(2);
+        Some((paired_t / WILCOXON_MIN_ARE).ceil() as usize)
+    }
+}
+
+fn standard_normal() -> Normal {
+    Normal::new(0.0, 1.0).expect("standard normal parameters are valid")
+}
+
+fn median(values: &[f64]) -> f64 {
+    if values.is_empty() {
+        return 0.0;
+    }
+    let mut sorted = values.to_vec();
+    sorted.sort_by(|a, b| a.total_cmp(b));
+    let mid = sorted.len() / 2;
+    if sorted.len().is_multiple_of(2) {
+        (sorted[mid - 1] + sorted[mid]) / 2.0
+    } else {
+        sorted[mid]
+    }
+}
+
+/// Sizes of the groups of tied ranks
+fn tie_groups(ranks: &[f64]) -> impl Iterator<Item = f64> {
+    let mut counts: HashMap<u64, usize> = HashMap::new();
+    for rank in ranks {
+        *counts.entry(rank.to_bits()).or_default() += 1;
+    }
+    counts.into_values().filter(|&c| c > 1).map(|c| c as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(task_id: &str, solved: bool, cost_usd: f64) -> TaskResult {
+        let mut result = TaskResult::new(task_id.to_string());
+        result.solved = solved;
+        result.cost_usd = cost_usd;
+        result
+    }
+
+    #[test]
+    fn test_mcnemar_exact_and_approximate() {
+        let exact = StatisticalTest::mcnemar(2, 10);
+        assert!(exact.exact);
+        // 2 * (1 + 12 + 66) / 4096
+        assert!((exact.p_value - 158.0 / 4096.0).abs() < 1e-12);
+        assert!(exact.significant);
+
+        let approx = StatisticalTest::mcnemar(10, 30);
+        assert!(!approx.exact);
+        // z = (20 - 1) / sqrt(40)
+        assert!(
+            (approx.p_value - 0.00266).abs() < 1e-4,
+            "{}",
+            approx.p_value
+        );
+
+        assert_eq!(StatisticalTest::mcnemar(0, 0).p_value, 1.0);
+    }
+
+    #[test]
+    fn test_wilcoxon_signed_rank() {
+        // Ties and zeros: |d| = 1, 1, 2, 3 after dropping the zero
+        let test = StatisticalTest::wilcoxon_signed_rank(&[0.0, 1.0, -1.0, 2.0, 3.0]);
+        assert_eq!(test.n, 4);
+        assert_eq!(test.w_plus, 8.5);
+        assert!(test.exact);
+        assert!(test.p_value > 0.2 && test.p_value <= 1.0);
+
+        // Normal approximation agrees with the exact test's direction
+        let diffs: Vec<f64> = (0..40)
+            .map(|i| if i % 5 == 0 { -1.0 } else { i as f64 })
+            .collect();
+        let test = StatisticalTest::wilcoxon_signed_rank(&diffs);
+        assert!(!test.exact);
+        assert!(test.p_value < 0.001);
+
+        let none = StatisticalTest::wilcoxon_signed_rank(&[0.0, 0.0]);
+        assert_eq!((none.n, none.p_value), (0, 1.0));
+    }
+
+    #[test]
+    fn test_sample_sizes() {
+        assert_eq!(
+            StatisticalTest::mcnemar_sample_size(0.05, 0.15, 0.05, 0.8),
+            Some(155)
+        );
+        assert_eq!(
+            StatisticalTest::mcnemar_sample_size(0.1, 0.1, 0.05, 0.8),
+            None
+        );
+        assert_eq!(StatisticalTest::wilcoxon_sample_size(0.0, 0.05, 0.8), None);
+        // Higher power needs more tasks
+        assert!(
+            StatisticalTest::wilcoxon_sample_size(0.5, 0.05, 0.9)
+                > StatisticalTest::wilcoxon_sample_size(0.5, 0.05, 0.8)
+        );
+    }
+
+    #[test]
+    fn test_paired_tests_from_results() {
+        let a: Vec<TaskResult> = (0..12)
+            .map(|i| result(&format!("task-{}", i), i < 2, 0.01))
+            .chain([result("only-in-a", true, 0.01)])
+            .collect();
+        let b: Vec<TaskResult> = (0..12)
+            .map(|i| result(&format!("task-{}", i), i >= 1, 0.02))
+            .collect();
+
+        let tests = PairedTests::from_results(&a, &b, 0.05).unwrap();
+        assert_eq!(tests.tasks, 12);
+        assert_eq!((tests.accuracy.only_a, tests.accuracy.only_b), (1, 10));
+        assert!(tests.accuracy.significant);
+        assert!(tests.cost.significant);
+        assert!((tests.cost.median_delta - 0.01).abs() < 1e-12);
+        // Latency and tokens are identical
+        assert!(!tests.latency.significant && !tests.tokens.significant);
+        assert!(tests.required_tasks.is_some());
+
+        let c = vec![result("other", true, 0.0)];
+        assert!(PairedTests::from_results(&a, &c, 0.05).is_none());
+    }
+}
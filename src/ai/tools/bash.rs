@@ -1,5 +1,5 @@
 /// Bash tool - executes shell commands
-use super::{Tool, ToolResult};
+use super::{Tool, ToolResult, Workdir};
 use anyhow::{Context, Result};
 use serde_json::json;
 use std::collections::HashMap;
@@ -7,7 +7,9 @@ use std::process::Stdio;
 use tokio::process::Command;
 use tokio::time::{Duration, timeout};
 
-pub struct BashTool;
+pub struct BashTool {
+    workdir: Workdir,
+}
 
 impl Default for BashTool {
     fn default() -> Self {
@@ -17,7 +19,15 @@ impl Default for BashTool {
 
 impl BashTool {
     pub fn new() -> Self {
-        Self
+        Self {
+            workdir: Workdir::default(),
+        }
+    }
+
+    /// Work in `workdir` instead of the process's working directory
+    pub fn with_workdir(mut self, workdir: Workdir) -> Self {
+        self.workdir = workdir;
+        self
     }
 
     /// Default timeout for command execution (30 seconds)
@@ -78,6 +88,9 @@ impl Tool for BashTool {
                 c
             };
 
+            if let Some(root) = self.workdir.root() {
+                cmd.current_dir(root);
+            }
             cmd.stdout(Stdio::piped())
                 .stderr(Stdio::piped())
                 .output()
@@ -1,11 +1,12 @@
 /// Edit tool - performs search/replace operations on files
-use super::{Tool, ToolResult};
+use super::{Tool, ToolResult, Workdir};
 use anyhow::{Context, Result};
 use serde_json::json;
 use std::collections::HashMap;
-use std::path::PathBuf;
 
-pub struct EditTool;
+pub struct EditTool {
+    workdir: Workdir,
+}
 
 impl Default for EditTool {
     fn default() -> Self {
@@ -15,7 +16,15 @@ impl Default for EditTool {
 
 impl EditTool {
     pub fn new() -> Self {
-        Self
+        Self {
+            workdir: Workdir::default(),
+        }
+    }
+
+    /// Work in `workdir` instead of the process's working directory
+    pub fn with_workdir(mut self, workdir: Workdir) -> Self {
+        self.workdir = workdir;
+        self
     }
 }
 
@@ -72,7 +81,10 @@ impl Tool for EditTool {
 
         let replace_all = args.get("all").and_then(|v| v.as_bool()).unwrap_or(true);
 
-        let path_buf = PathBuf::from(path);
+        let path_buf = match self.workdir.resolve(path) {
+            Ok(path_buf) => path_buf,
+            Err(e) => return Ok(ToolResult::error(self.name(), e)),
+        };
 
         if !path_buf.exists() {
             return Ok(ToolResult::error(
@@ -1,5 +1,5 @@
 /// Git tools - git diff and git status
-use super::{Tool, ToolResult};
+use super::{Tool, ToolResult, Workdir};
 use anyhow::Result;
 use serde_json::json;
 use std::collections::HashMap;
@@ -7,7 +7,9 @@ use std::process::Stdio;
 use tokio::process::Command;
 
 /// Git diff tool - shows changes in the repository
-pub struct GitDiffTool;
+pub struct GitDiffTool {
+    workdir: Workdir,
+}
 
 impl Default for GitDiffTool {
     fn default() -> Self {
@@ -17,7 +19,15 @@ impl Default for GitDiffTool {
 
 impl GitDiffTool {
     pub fn new() -> Self {
-        Self
+        Self {
+            workdir: Workdir::default(),
+        }
+    }
+
+    /// Work in `workdir` instead of the process's working directory
+    pub fn with_workdir(mut self, workdir: Workdir) -> Self {
+        self.workdir = workdir;
+        self
     }
 }
 
@@ -62,8 +72,12 @@ impl Tool for GitDiffTool {
         let file = args.get("file").and_then(|v| v.as_str());
 
         // Build git diff command
+        let dir = match self.workdir.resolve(path) {
+            Ok(dir) => dir,
+            Err(e) => return Ok(ToolResult::error(self.name(), e)),
+        };
         let mut cmd = Command::new("git");
-        cmd.current_dir(path);
+        cmd.current_dir(dir);
         cmd.arg("diff");
 
         if staged {
@@ -108,7 +122,9 @@ impl Tool for GitDiffTool {
 }
 
 /// Git status tool - shows repository status
-pub struct GitStatusTool;
+pub struct GitStatusTool {
+    workdir: Workdir,
+}
 
 impl Default for GitStatusTool {
     fn default() -> Self {
@@ -118,7 +134,15 @@ impl Default for GitStatusTool {
 
 impl GitStatusTool {
     pub fn new() -> Self {
-        Self
+        Self {
+            workdir: Workdir::default(),
+        }
+    }
+
+    /// Work in `workdir` instead of the process's working directory
+    pub fn with_workdir(mut self, workdir: Workdir) -> Self {
+        self.workdir = workdir;
+        self
     }
 }
 
@@ -154,8 +178,12 @@ impl Tool for GitStatusTool {
         let short = args.get("short").and_then(|v| v.as_bool()).unwrap_or(false);
 
         // Build git status command
+        let dir = match self.workdir.resolve(path) {
+            Ok(dir) => dir,
+            Err(e) => return Ok(ToolResult::error(self.name(), e)),
+        };
         let mut cmd = Command::new("git");
-        cmd.current_dir(path);
+        cmd.current_dir(dir);
         cmd.arg("status");
 
         if short {
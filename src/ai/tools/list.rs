@@ -1,11 +1,12 @@
 /// List tool - lists files in a directory
-use super::{Tool, ToolResult};
+use super::{Tool, ToolResult, Workdir};
 use anyhow::Result;
 use serde_json::json;
 use std::collections::HashMap;
-use std::path::PathBuf;
 
-pub struct ListTool;
+pub struct ListTool {
+    workdir: Workdir,
+}
 
 impl Default for ListTool {
     fn default() -> Self {
@@ -15,7 +16,15 @@ impl Default for ListTool {
 
 impl ListTool {
     pub fn new() -> Self {
-        Self
+        Self {
+            workdir: Workdir::default(),
+        }
+    }
+
+    /// Work in `workdir` instead of the process's working directory
+    pub fn with_workdir(mut self, workdir: Workdir) -> Self {
+        self.workdir = workdir;
+        self
     }
 }
 
@@ -53,7 +62,10 @@ impl Tool for ListTool {
             .and_then(|v| v.as_bool())
             .unwrap_or(false);
 
-        let path_buf = PathBuf::from(path);
+        let path_buf = match self.workdir.resolve(path) {
+            Ok(path_buf) => path_buf,
+            Err(e) => return Ok(ToolResult::error(self.name(), e)),
+        };
 
         if !path_buf.exists() {
             return Ok(ToolResult::error(
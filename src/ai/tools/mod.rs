@@ -5,6 +5,7 @@
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::path::{Component, Path, PathBuf};
 
 pub mod bash;
 pub mod edit;
@@ -67,6 +68,65 @@ impl ToolResult {
     }
 }
 
+/// Directory the tools of a registry work in
+///
+/// The default is the process's working directory. Rooted at a directory,
+/// commands run there and file tools resolve relative paths against it and
+/// refuse paths outside it. Commands can still `cd` elsewhere: this keeps an
+/// agent in its workspace, it is not a security boundary.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Workdir {
+    root: Option<PathBuf>,
+}
+
+impl Workdir {
+    /// Work in `root`
+    pub fn at(root: impl AsRef<Path>) -> Self {
+        let root = root.as_ref();
+        let root = std::fs::canonicalize(root).unwrap_or_else(|_| root.to_path_buf());
+        Self { root: Some(root) }
+    }
+
+    /// Root directory, or None for the process's working directory
+    pub fn root(&self) -> Option<&Path> {
+        self.root.as_deref()
+    }
+
+    /// Path named by a tool argument
+    ///
+    /// Returns an error message for the model if the path leaves the root.
+    pub fn resolve(&self, path: &str) -> std::result::Result<PathBuf, String> {
+        let Some(root) = &self.root else {
+            return Ok(PathBuf::from(path));
+        };
+        let resolved = normalize(&root.join(path));
+        if resolved.starts_with(root) {
+            Ok(resolved)
+        } else {
+            Err(format!(
+                "Path {} is outside the workspace {}",
+                path,
+                root.display()
+            ))
+        }
+    }
+}
+
+/// Remove `.` and `..` components without touching the filesystem
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            other => normalized.push(other),
+        }
+    }
+    normalized
+}
+
 /// A tool call request from the LLM
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ToolCall {
@@ -146,31 +206,44 @@ impl ToolRegistry {
 
     /// Create registry with M1 baseline tools and feature flags
     pub fn m1_with_features(features: &crate::config::FeatureFlags) -> Self {
+        Self::m1_in_workdir(features, Workdir::default())
+    }
+
+    /// Create registry with M1 tools working in `workdir`
+    pub fn m1_in_workdir(features: &crate::config::FeatureFlags, workdir: Workdir) -> Self {
         let mut registry = Self::new();
 
         // Register all M1 baseline tools (8 total)
         // WriteTool uses tree-sitter validation if enabled
-        registry.register(Box::new(ReadTool::new()));
-        registry.register(Box::new(WriteTool::with_validation(
-            features.tree_sitter_validation,
-        )));
-        registry.register(Box::new(ListTool::new()));
-        registry.register(Box::new(EditTool::new()));
-        registry.register(Box::new(BashTool::new()));
-        registry.register(Box::new(GrepTool::new()));
-        registry.register(Box::new(GitDiffTool::new()));
-        registry.register(Box::new(GitStatusTool::new()));
+        registry.register(Box::new(ReadTool::new().with_workdir(workdir.clone())));
+        registry.register(Box::new(
+            WriteTool::with_validation(features.tree_sitter_validation)
+                .with_workdir(workdir.clone()),
+        ));
+        registry.register(Box::new(ListTool::new().with_workdir(workdir.clone())));
+        registry.register(Box::new(EditTool::new().with_workdir(workdir.clone())));
+        registry.register(Box::new(BashTool::new().with_workdir(workdir.clone())));
+        registry.register(Box::new(GrepTool::new().with_workdir(workdir.clone())));
+        registry.register(Box::new(GitDiffTool::new().with_workdir(workdir.clone())));
+        registry.register(Box::new(GitStatusTool::new().with_workdir(workdir)));
 
         registry
     }
 
     /// Create registry with M2 tools (M1 + smart test selection)
     pub fn m2_with_features(features: &crate::config::FeatureFlags) -> Self {
-        let mut registry = Self::m1_with_features(features);
+        Self::m2_in_workdir(features, Workdir::default())
+    }
+
+    /// Create registry with M2 tools working in `workdir`
+    pub fn m2_in_workdir(features: &crate::config::FeatureFlags, workdir: Workdir) -> Self {
+        let mut registry = Self::m1_in_workdir(features, workdir.clone());
 
         // Add M2 smart test selection tool if enabled
         if features.smart_test_selection {
-            registry.register(Box::new(RunTestsTool::with_smart_selection(true)));
+            registry.register(Box::new(
+                RunTestsTool::with_smart_selection(true).with_workdir(workdir),
+            ));
         }
 
         registry
@@ -267,4 +340,63 @@ mod tests {
             "M2 should have run_tests tool"
         );
     }
+
+    fn args(pairs: &[(&str, &str)]) -> HashMap<String, serde_json::Value> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), serde_json::json!(v)))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_workdir_roots_tools() {
+        use crate::config::FeatureFlags;
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let workdir = Workdir::at(dir.path());
+        let root = workdir.root().unwrap().to_path_buf();
+        let registry = ToolRegistry::m1_in_workdir(&FeatureFlags::milestone_1(), workdir);
+
+        // Relative writes land inside the workspace, not the process's cwd
+        let write = registry.get("write").unwrap();
+        let result = write
+            .execute(args(&[("path", "sub/out.txt"), ("content", "hi")]))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert_eq!(
+            std::fs::read_to_string(root.join("sub/out.txt")).unwrap(),
+            "hi"
+        );
+        assert!(!std::path::Path::new("sub/out.txt").exists());
+
+        let read = registry.get("read").unwrap();
+        let result = read
+            .execute(args(&[("path", "sub/out.txt")]))
+            .await
+            .unwrap();
+        assert_eq!(result.output, "hi");
+
+        // Commands run there too
+        let bash = registry.get("bash").unwrap();
+        let result = bash.execute(args(&[("command", "pwd")])).await.unwrap();
+        assert!(result.output.contains(&root.display().to_string()));
+
+        // Paths leaving the workspace are refused
+        for path in ["../escaped.txt", "/tmp/escaped.txt", "sub/../../x"] {
+            let result = write
+                .execute(args(&[("path", path), ("content", "x")]))
+                .await
+                .unwrap();
+            assert!(!result.success, "{}", path);
+            assert!(result.error.unwrap().contains("outside the workspace"));
+        }
+    }
+
+    #[test]
+    fn test_default_workdir_resolves_as_given() {
+        let workdir = Workdir::default();
+        assert_eq!(workdir.root(), None);
+        assert_eq!(workdir.resolve("a/../b").unwrap(), PathBuf::from("a/../b"));
+    }
 }
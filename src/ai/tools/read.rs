@@ -1,11 +1,12 @@
 /// Read tool - reads file contents
-use super::{Tool, ToolResult};
+use super::{Tool, ToolResult, Workdir};
 use anyhow::{Context, Result};
 use serde_json::json;
 use std::collections::HashMap;
-use std::path::PathBuf;
 
-pub struct ReadTool;
+pub struct ReadTool {
+    workdir: Workdir,
+}
 
 impl Default for ReadTool {
     fn default() -> Self {
@@ -15,7 +16,15 @@ impl Default for ReadTool {
 
 impl ReadTool {
     pub fn new() -> Self {
-        Self
+        Self {
+            workdir: Workdir::default(),
+        }
+    }
+
+    /// Work in `workdir` instead of the process's working directory
+    pub fn with_workdir(mut self, workdir: Workdir) -> Self {
+        self.workdir = workdir;
+        self
     }
 }
 
@@ -48,7 +57,10 @@ impl Tool for ReadTool {
             .and_then(|v| v.as_str())
             .context("Missing 'path' argument")?;
 
-        let path_buf = PathBuf::from(path);
+        let path_buf = match self.workdir.resolve(path) {
+            Ok(path_buf) => path_buf,
+            Err(e) => return Ok(ToolResult::error(self.name(), e)),
+        };
 
         match tokio::fs::read_to_string(&path_buf).await {
             Ok(contents) => Ok(ToolResult::success(self.name(), contents)),
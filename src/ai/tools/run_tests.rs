@@ -7,7 +7,7 @@
 /// - Fall back to all tests if selection fails
 ///
 /// Evidence: AutoCodeRover proven (+3-5 points with smart test selection)
-use super::{Tool, ToolResult};
+use super::{Tool, ToolResult, Workdir};
 use crate::ai::test_selection::TestSelector;
 use anyhow::Result;
 use serde_json::{Value, json};
@@ -18,6 +18,9 @@ use std::process::Command;
 pub struct RunTestsTool {
     /// Whether to use smart test selection
     pub smart_selection: bool,
+
+    /// Directory relative workspace paths resolve against
+    workdir: Workdir,
 }
 
 impl RunTestsTool {
@@ -25,6 +28,7 @@ impl RunTestsTool {
     pub fn new() -> Self {
         Self {
             smart_selection: false,
+            workdir: Workdir::default(),
         }
     }
 
@@ -32,9 +36,16 @@ impl RunTestsTool {
     pub fn with_smart_selection(enabled: bool) -> Self {
         Self {
             smart_selection: enabled,
+            workdir: Workdir::default(),
         }
     }
 
+    /// Work in `workdir` instead of the process's working directory
+    pub fn with_workdir(mut self, workdir: Workdir) -> Self {
+        self.workdir = workdir;
+        self
+    }
+
     /// Execute test command and capture output
     fn run_command(&self, command: &str, workspace: &PathBuf) -> Result<String> {
         tracing::info!("Running test command: {}", command);
@@ -115,7 +126,10 @@ impl Tool for RunTestsTool {
             .and_then(|v| v.as_str())
             .ok_or_else(|| anyhow::anyhow!("workspace_path is required"))?;
 
-        let workspace = PathBuf::from(workspace_str);
+        let workspace = match self.workdir.resolve(workspace_str) {
+            Ok(workspace) => workspace,
+            Err(e) => return Ok(ToolResult::error("run_tests", e)),
+        };
 
         if !workspace.exists() {
             return Ok(ToolResult::error(
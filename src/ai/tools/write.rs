@@ -1,13 +1,16 @@
 /// Write tool - writes content to a file with optional syntax validation
-use super::{Tool, ToolResult};
+use super::{Tool, ToolResult, Workdir};
 use anyhow::{Context, Result};
 use serde_json::json;
 use std::collections::HashMap;
-use std::path::{Path, PathBuf};
+use std::path::Path;
 
 pub struct WriteTool {
     /// Whether to validate syntax before writing
     validate_syntax: bool,
+
+    /// Directory relative paths resolve against
+    workdir: Workdir,
 }
 
 impl Default for WriteTool {
@@ -21,12 +24,22 @@ impl WriteTool {
     pub fn new() -> Self {
         Self {
             validate_syntax: false,
+            workdir: Workdir::default(),
         }
     }
 
     /// Create a WriteTool with optional syntax validation
     pub fn with_validation(validate_syntax: bool) -> Self {
-        Self { validate_syntax }
+        Self {
+            validate_syntax,
+            workdir: Workdir::default(),
+        }
+    }
+
+    /// Work in `workdir` instead of the process's working directory
+    pub fn with_workdir(mut self, workdir: Workdir) -> Self {
+        self.workdir = workdir;
+        self
     }
 
     /// Validate syntax using tree-sitter for supported languages
@@ -125,7 +138,10 @@ impl Tool for WriteTool {
             .and_then(|v| v.as_str())
             .context("Missing 'content' argument")?;
 
-        let path_buf = PathBuf::from(path);
+        let path_buf = match self.workdir.resolve(path) {
+            Ok(path_buf) => path_buf,
+            Err(e) => return Ok(ToolResult::error(self.name(), e)),
+        };
 
         // Validate syntax if enabled
         if self.validate_syntax
@@ -0,0 +1,128 @@
+//! Shared steps of the code generation benchmarks
+//!
+//! LiveCodeBench and the HumanEval/MBPP executors all get a program from the
+//! model (LiveCodeBench through an agent run, HumanEval/MBPP from a single
+//! completion), run it in a [`Sandbox`](crate::benchmarks::sandbox::Sandbox)
+//! and turn the verdicts into a [`TaskResult`]. The pieces they share live here.
+
+use crate::ai::evaluation::TaskResult;
+use crate::ai::llm::{AnthropicClient, LLMClient, Message, get_api_key};
+use crate::ai::metrics::MetricsCollector;
+use crate::benchmarks::ExecutionContext;
+use crate::benchmarks::sandbox::{TestVerdict, Verdict};
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Model used when no client is configured
+const DEFAULT_MODEL: &str = "claude-sonnet-4-20250514";
+
+/// The configured client, or an Anthropic client from the environment
+///
+/// The Anthropic client samples at the temperature of the context's sample,
+/// if any.
+pub(crate) fn resolve_client(
+    client: Option<&Arc<dyn LLMClient>>,
+    ctx: &ExecutionContext,
+) -> Result<Arc<dyn LLMClient>> {
+    if let Some(client) = client {
+        return Ok(Arc::clone(client));
+    }
+    let api_key = get_api_key()
+        .context("Failed to get API key. Set ANTHROPIC_API_KEY environment variable")?;
+    let mut client = AnthropicClient::new(api_key).with_model(DEFAULT_MODEL);
+    if let Some(temperature) = ctx.sample.and_then(|s| s.temperature) {
+        client = client.with_temperature(temperature);
+    }
+    Ok(Arc::new(client))
+}
+
+/// Ask the model for a solution within `timeout`, recording the call
+///
+/// Returns the reply text, or the message to fail the task with.
+pub(crate) async fn request_solution(
+    client: &dyn LLMClient,
+    prompt: String,
+    timeout: Duration,
+    metrics: &mut MetricsCollector,
+) -> std::result::Result<String, String> {
+    let request = client.send_message(vec![Message::user(prompt)], None);
+    let response = match tokio::time::timeout(timeout, request).await {
+        Ok(Ok(response)) => response,
+        Ok(Err(e)) => return Err(format!("Solution generation failed: {}", e)),
+        Err(_) => return Err(format!("Task exceeded timeout of {}s", timeout.as_secs())),
+    };
+
+    metrics.record_first_response();
+    metrics.record_agent_step();
+    let usage = &response.usage;
+    metrics.record_api_call(
+        usage.input_tokens as u64,
+        usage.output_tokens as u64,
+        usage.cache_read_tokens.unwrap_or(0) as u64,
+        usage.calculate_cost(),
+    );
+    Ok(response.content)
+}
+
+/// Copy the collected metrics onto a task result
+pub(crate) fn apply_metrics(result: &mut TaskResult, metrics: &mut MetricsCollector) {
+    let metrics = metrics.finish();
+    result.duration_ms = metrics.duration_ms;
+    result.cost_usd = metrics.cost_usd;
+    result.api_calls = metrics.api_calls;
+    result.total_tokens = metrics.total_tokens();
+    result.metrics = metrics;
+}
+
+/// Record per-test verdicts on a task result
+///
+/// Verdict counts go into `quality_signals` (`tests_total`, `tests_passed`,
+/// `pass_rate` and `verdict_ac`/`_wa`/`_tle`/`_re`). The task is solved when
+/// every test is accepted; otherwise the verdicts are summarized in `error`.
+pub(crate) fn record_verdicts(result: &mut TaskResult, verdicts: &[TestVerdict]) {
+    let passed = verdicts
+        .iter()
+        .filter(|v| v.verdict == Verdict::Accepted)
+        .count();
+
+    let signals = result.quality_signals.get_or_insert_with(HashMap::new);
+    signals.insert("tests_total".to_string(), verdicts.len() as f64);
+    signals.insert("tests_passed".to_string(), passed as f64);
+    signals.insert(
+        "pass_rate".to_string(),
+        if verdicts.is_empty() {
+            0.0
+        } else {
+            passed as f64 / verdicts.len() as f64
+        },
+    );
+    for verdict in Verdict::ALL {
+        let count = verdicts.iter().filter(|v| v.verdict == verdict).count();
+        signals.insert(
+            format!("verdict_{}", verdict.code().to_lowercase()),
+            count as f64,
+        );
+    }
+
+    if !verdicts.is_empty() && passed == verdicts.len() {
+        result.mark_solved();
+        return;
+    }
+    let codes: Vec<&str> = verdicts.iter().map(|v| v.verdict.code()).collect();
+    let mut error = format!(
+        "{}/{} tests passed: {}",
+        passed,
+        verdicts.len(),
+        codes.join(" ")
+    );
+    if let Some((index, detail)) = verdicts
+        .iter()
+        .enumerate()
+        .find_map(|(i, v)| v.detail.as_ref().map(|d| (i, d)))
+    {
+        error.push_str(&format!(" (test {}: {})", index + 1, detail));
+    }
+    result.mark_failed(error);
+}
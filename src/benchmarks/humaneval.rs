@@ -0,0 +1,936 @@
+//! HumanEval+/MBPP function-completion benchmark executor
+//!
+//! This module provides a `BenchmarkExecutor` implementation for function-level
+//! code generation benchmarks in the HumanEval/MBPP JSONL format. Each problem
+//! asks for a single function; the benchmark's test code is appended to the
+//! model's solution and the program is run once in a [`Sandbox`]. Problems are
+//! small and run offline, which makes this a quick smoke benchmark.
+//!
+//! # Benchmarks
+//!
+//! - **HumanEval+** (EvalPlus): 164 Python problems with extended tests
+//! - **MBPP+** (EvalPlus): 378 Python problems with extended tests
+//!
+//! Both were published in 2021 and are widely trained on, so their
+//! contamination risk is HIGH: use them to catch regressions, not to rank
+//! models.
+//!
+//! # Dataset
+//!
+//! Problems are read from a local JSONL file (a JSON array works too), by
+//! default `~/.toad/datasets/humaneval/problems.jsonl` or
+//! `~/.toad/datasets/mbpp/problems.jsonl` (see [`HumanEvalExecutor::with_dataset`]).
+//! Each problem has:
+//!
+//! - `task_id` (string or number; `name` in MultiPL-E files)
+//! - `prompt`: function signature and docstring, or `text` (MBPP) describing
+//!   the function in prose
+//! - `entry_point`: name of the function under test (inferred from the prompt
+//!   or `test_list` when missing)
+//! - `test` (`tests` in MultiPL-E files): harness code; a HumanEval-style
+//!   `def check(candidate)` is called with the entry point. Alternatively
+//!   `test_list` (MBPP): assert statements, run after `test_setup_code`
+//! - `language` (optional): `python` or `rust`, defaulting to the executor's
+//!   language
+//!
+//! # Execution
+//!
+//! The model is asked for the complete function in a fenced code block. The
+//! program is the solution followed by the test code:
+//!
+//! - **Python**: the prompt's imports, the solution, the test code and, for
+//!   HumanEval-style harnesses, `check(<entry_point>)`
+//! - **Rust**: the solution and the test code, which must define `fn main()`
+//!   (test code without one is wrapped in it). A leading `}` left over from
+//!   MultiPL-E's split of the prompt is dropped.
+//!
+//! The run gets one verdict: `AC` if it exits cleanly, `WA` on a failed
+//! assertion, `TLE` at the time limit and `RE` otherwise (including Rust
+//! compile errors). The task is solved when the verdict is `AC`.
+//!
+//! # References
+//!
+//! - HumanEval: "Evaluating Large Language Models Trained on Code" (Chen et al., 2021)
+//! - MBPP: "Program Synthesis with Large Language Models" (Austin et al., 2021)
+//! - EvalPlus: https://github.com/evalplus/evalplus
+//!
+//! # Examples
+//!
+//! ```rust,ignore
+//! use toad::benchmarks::{BenchmarkExecutor, ExecutionContext};
+//! use toad::benchmarks::humaneval::HumanEvalExecutor;
+//!
+//! #[tokio::main]
+//! async fn main() -> anyhow::Result<()> {
+//!     let mut executor = HumanEvalExecutor::mbpp().with_dataset("mbpp/problems.jsonl");
+//!     executor.setup().await?;
+//!
+//!     let ctx = ExecutionContext::default();
+//!     for task in executor.tasks().into_iter().take(5) {
+//!         let result = executor.run_task(&task, &ctx).await;
+//!         println!("{}: solved={}", task.id, result.solved);
+//!     }
+//!     Ok(())
+//! }
+//! ```
+
+use crate::ai::evaluation::{DatasetManager, TaskResult};
+use crate::ai::llm::LLMClient;
+use crate::ai::metrics::MetricsCollector;
+use crate::benchmarks::sandbox::{
+    Sandbox, SandboxLimits, TestVerdict, Verdict, extract_code_block, harness_verdict,
+};
+use crate::benchmarks::{BenchmarkExecutor, BenchmarkMetadata, ExecutionContext, Task, codegen};
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Language a problem is solved and tested in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Language {
+    /// Python 3
+    Python,
+    /// Rust (2021 edition, standard library only)
+    Rust,
+}
+
+impl Language {
+    /// Parse a language name (`python`, `py`, `rust`, `rs`), case-insensitively
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use toad::benchmarks::humaneval::Language;
+    ///
+    /// assert_eq!(Language::parse("RS"), Some(Language::Rust));
+    /// assert_eq!(Language::parse("go"), None);
+    /// ```
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "python" | "python3" | "py" => Some(Language::Python),
+            "rust" | "rs" => Some(Language::Rust),
+            _ => None,
+        }
+    }
+
+    /// Display name
+    pub fn name(&self) -> &'static str {
+        match self {
+            Language::Python => "Python",
+            Language::Rust => "Rust",
+        }
+    }
+
+    /// Tags of fenced code blocks in this language
+    fn fence_tags(&self) -> &'static [&'static str] {
+        match self {
+            Language::Python => &["python", "python3", "py"],
+            Language::Rust => &["rust", "rs"],
+        }
+    }
+
+    /// Keyword that starts a function definition
+    fn fn_keyword(&self) -> &'static str {
+        match self {
+            Language::Python => "def",
+            Language::Rust => "fn",
+        }
+    }
+}
+
+impl std::fmt::Display for Language {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// A function-completion problem with its test harness
+#[derive(Debug, Clone, PartialEq)]
+pub struct Problem {
+    /// Problem ID (e.g., "HumanEval/0", "Mbpp/2")
+    pub id: String,
+    /// Language the solution is written and tested in
+    pub language: Language,
+    /// Function signature and docstring, or a prose description
+    pub prompt: String,
+    /// Name of the function under test
+    pub entry_point: String,
+    /// Test code appended to the solution
+    pub test: String,
+}
+
+impl Problem {
+    /// Generic task for this problem
+    fn to_task(&self) -> Task {
+        let mut metadata = HashMap::new();
+        metadata.insert("language".to_string(), serde_json::json!(self.language));
+        metadata.insert(
+            "entry_point".to_string(),
+            serde_json::json!(self.entry_point),
+        );
+
+        Task {
+            id: self.id.clone(),
+            description: self.prompt.clone(),
+            expected_output: Some("All test cases pass".to_string()),
+            metadata,
+        }
+    }
+
+    /// Whether the prompt is code to complete rather than prose
+    fn prompt_is_code(&self) -> bool {
+        defined_function(&self.prompt, self.language).is_some()
+    }
+
+    /// Prompt asking the model for a solution
+    fn prompt(&self) -> String {
+        let language = self.language;
+        let tag = language.fence_tags()[0];
+        let mut prompt = if self.prompt_is_code() {
+            format!(
+                "Complete the following {} function.\n\n```{}\n{}\n```\n\n",
+                language,
+                tag,
+                self.prompt.trim_end()
+            )
+        } else {
+            format!(
+                "Write a {} function named `{}` for the following task.\n\n{}\n\n",
+                language,
+                self.entry_point,
+                self.prompt.trim_end()
+            )
+        };
+        prompt.push_str(&format!(
+            "Reply with the complete function `{}`, including its signature and any imports \
+             or helpers it needs, in a single ```{} code block. Do not include tests",
+            self.entry_point, tag
+        ));
+        prompt.push_str(match language {
+            Language::Python => ".",
+            Language::Rust => " or a `main` function.",
+        });
+        prompt
+    }
+
+    /// Program running the test code against `solution`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use toad::benchmarks::humaneval::{Language, Problem};
+    ///
+    /// let problem = Problem {
+    ///     id: "HumanEval/53".to_string(),
+    ///     language: Language::Python,
+    ///     prompt: "def add(x: int, y: int):\n    \"\"\"Add two numbers\"\"\"\n".to_string(),
+    ///     entry_point: "add".to_string(),
+    ///     test: "def check(candidate):\n    assert candidate(2, 3) == 5\n".to_string(),
+    /// };
+    /// let program = problem.program("def add(x, y):\n    return x + y\n");
+    /// assert!(program.ends_with("check(add)\n"));
+    /// ```
+    pub fn program(&self, solution: &str) -> String {
+        let mut program = String::new();
+        match self.language {
+            Language::Python => {
+                for line in self.prompt.lines() {
+                    if line.starts_with("import ") || line.starts_with("from ") {
+                        program.push_str(line);
+                        program.push('\n');
+                    }
+                }
+                program.push_str(&format!(
+                    "\n{}\n\n\n{}\n",
+                    solution.trim_end(),
+                    self.test.trim_end()
+                ));
+                if self.test.contains("def check(") {
+                    program.push_str(&format!("\n\ncheck({})\n", self.entry_point));
+                }
+            }
+            Language::Rust => {
+                program.push_str(&format!("{}\n\n", solution.trim_end()));
+                if self.test.contains("fn main") {
+                    program.push_str(&format!("{}\n", self.test.trim_end()));
+                } else {
+                    program.push_str(&format!("fn main() {{\n{}\n}}\n", self.test.trim_end()));
+                }
+            }
+        }
+        program
+    }
+}
+
+/// A problem as stored in the dataset file
+#[derive(Debug, Deserialize)]
+struct RawProblem {
+    #[serde(alias = "name")]
+    task_id: serde_json::Value,
+    #[serde(default)]
+    prompt: Option<String>,
+    #[serde(default)]
+    text: Option<String>,
+    #[serde(default)]
+    entry_point: Option<String>,
+    #[serde(default, alias = "tests")]
+    test: Option<String>,
+    #[serde(default)]
+    test_list: Vec<String>,
+    #[serde(default)]
+    test_setup_code: String,
+    #[serde(default)]
+    language: Option<String>,
+}
+
+impl RawProblem {
+    fn into_problem(self, default_language: Language) -> Result<Problem> {
+        let id = match self.task_id {
+            serde_json::Value::String(id) => id,
+            other => other.to_string(),
+        };
+        let language = match &self.language {
+            Some(name) => Language::parse(name)
+                .with_context(|| format!("Unsupported language '{}' in {}", name, id))?,
+            None => default_language,
+        };
+
+        let prompt = match (self.prompt, self.text) {
+            (Some(prompt), _) if !prompt.trim().is_empty() => prompt,
+            (_, Some(text)) if !self.test_list.is_empty() => format!(
+                "{}\n\nYour code should pass these tests:\n\n{}",
+                text.trim_end(),
+                self.test_list.join("\n")
+            ),
+            (_, Some(text)) => text,
+            _ => anyhow::bail!("Problem {} has no prompt", id),
+        };
+
+        let test = match self.test {
+            Some(test) if !test.trim().is_empty() => match language {
+                // MultiPL-E tests start by closing the prompt's function
+                Language::Rust => match test.trim_start().strip_prefix('}') {
+                    Some(rest) => rest.to_string(),
+                    None => test,
+                },
+                Language::Python => test,
+            },
+            _ if !self.test_list.is_empty() => {
+                let mut test = self.test_setup_code.trim_end().to_string();
+                if !test.is_empty() {
+                    test.push('\n');
+                }
+                test.push_str(&self.test_list.join("\n"));
+                test
+            }
+            _ => anyhow::bail!("Problem {} has no tests", id),
+        };
+
+        let entry_point = match self.entry_point.filter(|name| !name.trim().is_empty()) {
+            Some(name) => name.trim().to_string(),
+            None => defined_function(&prompt, language)
+                .or_else(|| self.test_list.first().and_then(|t| asserted_function(t)))
+                .with_context(|| format!("Cannot infer the entry point of {}", id))?
+                .to_string(),
+        };
+
+        Ok(Problem {
+            id,
+            language,
+            prompt,
+            entry_point,
+            test,
+        })
+    }
+}
+
+/// Name of the last function defined in `code`
+fn defined_function(code: &str, language: Language) -> Option<&str> {
+    let keyword = format!("{} ", language.fn_keyword());
+    code.lines().rev().find_map(|line| {
+        let line = line.trim_start();
+        let line = line.strip_prefix("pub ").unwrap_or(line);
+        let rest = line.strip_prefix(&keyword)?;
+        let end = rest.find(|c: char| !(c.is_alphanumeric() || c == '_'))?;
+        (end > 0).then(|| &rest[..end])
+    })
+}
+
+/// Builtins MBPP tests wrap the function under test in
+const ASSERT_WRAPPERS: [&str; 13] = [
+    "set",
+    "sorted",
+    "list",
+    "tuple",
+    "frozenset",
+    "dict",
+    "str",
+    "int",
+    "float",
+    "bool",
+    "abs",
+    "round",
+    "len",
+];
+
+/// First function called in an `assert` statement that is not a builtin
+/// wrapper or a method (`assert set(f(1)) == {2}` calls `f`)
+fn asserted_function(assertion: &str) -> Option<&str> {
+    let rest = assertion.trim_start().strip_prefix("assert")?;
+    let mut start = None;
+    for (i, c) in rest.char_indices() {
+        if c.is_alphanumeric() || c == '_' {
+            start.get_or_insert(i);
+            continue;
+        }
+        if let Some(begin) = start.take() {
+            let name = &rest[begin..i];
+            let is_method = rest[..begin].ends_with('.');
+            if c == '(' && !is_method && !ASSERT_WRAPPERS.contains(&name) {
+                return Some(name);
+            }
+        }
+    }
+    None
+}
+
+/// Load problems from a JSONL (or JSON array) file
+///
+/// Problems without a `language` are in `default_language`.
+///
+/// # Errors
+///
+/// Returns `Err` if the file cannot be read or a problem is malformed.
+pub fn load_problems(path: &Path, default_language: Language) -> Result<Vec<Problem>> {
+    let text = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read dataset {}", path.display()))?;
+
+    let raw: Vec<RawProblem> = if text.trim_start().starts_with('[') {
+        serde_json::from_str(&text).context("Failed to parse dataset")?
+    } else {
+        text.lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(i, line)| {
+                serde_json::from_str(line).with_context(|| {
+                    format!(
+                        "Failed to parse problem on line {} of {}",
+                        i + 1,
+                        path.display()
+                    )
+                })
+            })
+            .collect::<Result<_>>()?
+    };
+
+    raw.into_iter()
+        .map(|raw| raw.into_problem(default_language))
+        .collect()
+}
+
+/// HumanEval+/MBPP function-completion benchmark executor
+///
+/// Loads problems from a local dataset file, asks the model for each
+/// function and runs it with the problem's test code in a [`Sandbox`].
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// let mut executor = HumanEvalExecutor::new().with_language(Language::Rust);
+/// executor.setup().await?;
+///
+/// let metadata = executor.get_metadata();
+/// println!("Running {} {} tasks", metadata.total_tasks, metadata.name);
+/// ```
+pub struct HumanEvalExecutor {
+    /// Benchmark metadata (task count updated by setup())
+    metadata: BenchmarkMetadata,
+
+    /// Dataset file
+    dataset_path: PathBuf,
+
+    /// Language of problems that do not name one
+    language: Language,
+
+    /// Loaded problems (populated by setup())
+    problems: Vec<Problem>,
+
+    /// Model client (None: Anthropic client from the environment)
+    client: Option<Arc<dyn LLMClient>>,
+}
+
+impl HumanEvalExecutor {
+    /// Create a HumanEval+ executor
+    ///
+    /// Reads the dataset from the default cache location. The metadata holds
+    /// the size of the benchmark until [`setup`](BenchmarkExecutor::setup)
+    /// loads it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use toad::benchmarks::BenchmarkExecutor;
+    /// use toad::benchmarks::humaneval::HumanEvalExecutor;
+    ///
+    /// let executor = HumanEvalExecutor::new();
+    /// let metadata = executor.get_metadata();
+    ///
+    /// assert_eq!(metadata.name, "HumanEval+");
+    /// assert_eq!(metadata.total_tasks, 164);
+    /// ```
+    pub fn new() -> Self {
+        Self::with_metadata(
+            BenchmarkMetadata {
+                name: "HumanEval+".to_string(),
+                version: "v0.1.10".to_string(),
+                total_tasks: 164,
+                dataset_url: Some("https://github.com/evalplus/humanevalplus_release".to_string()),
+                license: Some("Apache-2.0".to_string()),
+                contamination_risk: "HIGH".to_string(), // Released 2021
+            },
+            "humaneval",
+        )
+    }
+
+    /// Create an MBPP+ executor
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use toad::benchmarks::BenchmarkExecutor;
+    /// use toad::benchmarks::humaneval::HumanEvalExecutor;
+    ///
+    /// let executor = HumanEvalExecutor::mbpp();
+    /// assert_eq!(executor.get_metadata().name, "MBPP+");
+    /// assert_eq!(executor.get_metadata().total_tasks, 378);
+    /// ```
+    pub fn mbpp() -> Self {
+        Self::with_metadata(
+            BenchmarkMetadata {
+                name: "MBPP+".to_string(),
+                version: "v0.2.0".to_string(),
+                total_tasks: 378,
+                dataset_url: Some("https://github.com/evalplus/mbppplus_release".to_string()),
+                license: Some("Apache-2.0".to_string()),
+                contamination_risk: "HIGH".to_string(), // Released 2021
+            },
+            "mbpp",
+        )
+    }
+
+    fn with_metadata(metadata: BenchmarkMetadata, cache_name: &str) -> Self {
+        Self {
+            metadata,
+            dataset_path: Self::default_dataset_path(cache_name),
+            language: Language::Python,
+            problems: Vec::new(),
+            client: None,
+        }
+    }
+
+    /// Default dataset location (`~/.toad/datasets/<name>/problems.jsonl`)
+    pub fn default_dataset_path(name: &str) -> PathBuf {
+        DatasetManager::default_cache_dir()
+            .join(name)
+            .join("problems.jsonl")
+    }
+
+    /// Read problems from `path` instead of the default location
+    pub fn with_dataset(mut self, path: impl Into<PathBuf>) -> Self {
+        self.dataset_path = path.into();
+        self
+    }
+
+    /// Language of problems that do not name one (default: Python)
+    pub fn with_language(mut self, language: Language) -> Self {
+        self.language = language;
+        self
+    }
+
+    /// Generate solutions with `client` instead of an Anthropic client
+    pub fn with_client(mut self, client: Arc<dyn LLMClient>) -> Self {
+        self.client = Some(client);
+        self
+    }
+
+    /// Loaded problems
+    pub fn problems(&self) -> &[Problem] {
+        &self.problems
+    }
+
+    /// Run a solution to a loaded problem with its test code
+    ///
+    /// Returns the verdict, or `None` if the problem is not loaded.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if the sandbox cannot be created.
+    pub async fn judge(
+        &self,
+        problem_id: &str,
+        solution: &str,
+        limits: SandboxLimits,
+    ) -> Result<Option<TestVerdict>> {
+        let Some(problem) = self.problems.iter().find(|p| p.id == problem_id) else {
+            return Ok(None);
+        };
+        let sandbox = Sandbox::new(limits)?;
+        Ok(Some(run_harness(&sandbox, problem, solution).await))
+    }
+}
+
+impl Default for HumanEvalExecutor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Run `solution` with the problem's test code in `sandbox`
+async fn run_harness(sandbox: &Sandbox, problem: &Problem, solution: &str) -> TestVerdict {
+    let program = problem.program(solution);
+    let run = match problem.language {
+        Language::Python => sandbox.run_python(&program, "").await,
+        Language::Rust => sandbox.run_rust(&program, "").await,
+    };
+    match run {
+        Ok(run) => harness_verdict(&run),
+        Err(e) => TestVerdict {
+            verdict: Verdict::RuntimeError,
+            duration_ms: 0,
+            detail: Some(format!("{:#}", e)),
+        },
+    }
+}
+
+#[async_trait]
+impl BenchmarkExecutor for HumanEvalExecutor {
+    /// Load problems from the dataset file
+    ///
+    /// After setup(), `get_metadata().total_tasks` is the number of problems
+    /// loaded.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the dataset file is missing or malformed.
+    async fn setup(&mut self) -> Result<()> {
+        tracing::info!(
+            "Loading {} problems from {:?}",
+            self.metadata.name,
+            self.dataset_path
+        );
+
+        let path = self.dataset_path.clone();
+        let language = self.language;
+        let problems = tokio::task::spawn_blocking(move || load_problems(&path, language))
+            .await
+            .context("Dataset loader panicked")??;
+
+        self.metadata.total_tasks = problems.len();
+        self.problems = problems;
+
+        tracing::info!(
+            "{} setup complete: {} problems loaded",
+            self.metadata.name,
+            self.problems.len()
+        );
+        Ok(())
+    }
+
+    /// Execute a single task
+    ///
+    /// Asks the model for the function (within `ctx.timeout`), then runs it
+    /// with the problem's test code in a sandbox limited by
+    /// [`SandboxLimits::from_context`].
+    async fn run_task(&self, task: &Task, ctx: &ExecutionContext) -> TaskResult {
+        tracing::info!("Running task: {}", task.id);
+        let mut result = TaskResult::new(task.id.clone());
+
+        let Some(problem) = self.problems.iter().find(|p| p.id == task.id) else {
+            result.mark_failed(format!("Task {} not found in loaded dataset", task.id));
+            return result;
+        };
+        let client = match codegen::resolve_client(self.client.as_ref(), ctx) {
+            Ok(client) => client,
+            Err(e) => {
+                result.mark_failed(format!("{:#}", e));
+                return result;
+            }
+        };
+
+        let mut metrics = MetricsCollector::new();
+        metrics.start();
+        let reply =
+            match codegen::request_solution(&*client, problem.prompt(), ctx.timeout, &mut metrics)
+                .await
+            {
+                Ok(reply) => reply,
+                Err(error) => {
+                    codegen::apply_metrics(&mut result, &mut metrics);
+                    result.mark_failed(error);
+                    return result;
+                }
+            };
+
+        let solution = extract_code_block(&reply, problem.language.fence_tags());
+        let sandbox = match Sandbox::new(SandboxLimits::from_context(ctx)) {
+            Ok(sandbox) => sandbox,
+            Err(e) => {
+                codegen::apply_metrics(&mut result, &mut metrics);
+                result.mark_failed(format!("{:#}", e));
+                return result;
+            }
+        };
+        metrics.record_test_run();
+        let verdict = run_harness(&sandbox, problem, solution).await;
+
+        codegen::apply_metrics(&mut result, &mut metrics);
+        codegen::record_verdicts(&mut result, &[verdict]);
+
+        tracing::info!(
+            "Task {} complete: solved={}, cost=${:.4}",
+            task.id,
+            result.solved,
+            result.cost_usd
+        );
+        result
+    }
+
+    /// Clean up resources after all tasks complete
+    ///
+    /// Sandboxes are removed after each task, so only the loaded problems
+    /// are released.
+    async fn cleanup(&mut self) -> Result<()> {
+        self.problems.clear();
+        Ok(())
+    }
+
+    /// Get metadata about this benchmark
+    fn get_metadata(&self) -> &BenchmarkMetadata {
+        &self.metadata
+    }
+
+    /// Loaded problems as generic tasks, in dataset order
+    fn tasks(&self) -> Vec<Task> {
+        self.problems.iter().map(Problem::to_task).collect()
+    }
+
+    /// Every task runs in its own sandbox
+    fn supports_concurrent_tasks(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai::llm::mock::MockResponseBuilder;
+    use tempfile::TempDir;
+
+    fn write_dataset(dir: &TempDir, problems: &[serde_json::Value]) -> PathBuf {
+        let path = dir.path().join("problems.jsonl");
+        let lines: Vec<String> = problems.iter().map(|p| p.to_string()).collect();
+        std::fs::write(&path, lines.join("\n")).unwrap();
+        path
+    }
+
+    fn humaneval_problem() -> serde_json::Value {
+        serde_json::json!({
+            "task_id": "HumanEval/0",
+            "prompt": "from typing import List\n\n\ndef total(xs: List[int]) -> int:\n    \"\"\"Sum of xs\"\"\"\n",
+            "entry_point": "total",
+            "test": "def check(candidate):\n    assert candidate([1, 2]) == 3\n    assert candidate([]) == 0\n",
+        })
+    }
+
+    fn mbpp_problem() -> serde_json::Value {
+        serde_json::json!({
+            "task_id": 2,
+            "text": "Write a function to find the shared elements from the given two lists.",
+            "test_list": ["assert set(similar_elements((3, 4), (4, 5))) == set((4,))"],
+            "test_setup_code": "",
+        })
+    }
+
+    async fn loaded_executor(
+        dir: &TempDir,
+        problems: &[serde_json::Value],
+        reply: &str,
+    ) -> HumanEvalExecutor {
+        let client = MockResponseBuilder::new().with_text(reply).build();
+        let mut executor = HumanEvalExecutor::new()
+            .with_dataset(write_dataset(dir, problems))
+            .with_client(Arc::new(client));
+        executor.setup().await.unwrap();
+        executor
+    }
+
+    #[test]
+    fn test_humaneval_metadata() {
+        let executor = HumanEvalExecutor::default();
+        let metadata = executor.get_metadata();
+        assert_eq!(metadata.name, "HumanEval+");
+        assert_eq!(metadata.contamination_risk, "HIGH");
+        assert!(executor.dataset_path.ends_with("humaneval/problems.jsonl"));
+
+        let executor = HumanEvalExecutor::mbpp();
+        assert!(executor.dataset_path.ends_with("mbpp/problems.jsonl"));
+    }
+
+    #[tokio::test]
+    async fn test_humaneval_setup_loads_formats() {
+        let dir = TempDir::new().unwrap();
+        let rust = serde_json::json!({
+            "name": "HumanEval_53_add",
+            "language": "rs",
+            "prompt": "/// Add two numbers\nfn add(x: isize, y: isize) -> isize {\n",
+            "tests": "}\n\nfn main() {\n    let candidate = add;\n    assert_eq!(candidate(2, 3), 5);\n}\n",
+        });
+        let executor =
+            loaded_executor(&dir, &[humaneval_problem(), mbpp_problem(), rust], "").await;
+
+        assert_eq!(executor.get_metadata().total_tasks, 3);
+        let problems = executor.problems();
+        assert_eq!(problems[0].language, Language::Python);
+        assert_eq!(problems[1].id, "2");
+        assert_eq!(problems[1].entry_point, "similar_elements");
+        assert!(
+            problems[1]
+                .prompt
+                .contains("Your code should pass these tests")
+        );
+        assert_eq!(problems[2].language, Language::Rust);
+        assert_eq!(problems[2].entry_point, "add");
+        assert!(problems[2].test.trim_start().starts_with("fn main"));
+
+        let tasks = executor.tasks();
+        assert_eq!(tasks[2].metadata["language"], serde_json::json!("rust"));
+    }
+
+    #[tokio::test]
+    async fn test_humaneval_setup_rejects_problem_without_tests() {
+        let dir = TempDir::new().unwrap();
+        let path = write_dataset(
+            &dir,
+            &[serde_json::json!({ "task_id": "x", "prompt": "def f():\n" })],
+        );
+        let mut executor = HumanEvalExecutor::new().with_dataset(path);
+        let err = executor.setup().await.unwrap_err();
+        assert!(format!("{:#}", err).contains("has no tests"));
+    }
+
+    #[tokio::test]
+    async fn test_humaneval_run_task_solves_python() {
+        let dir = TempDir::new().unwrap();
+        // Relies on the prompt's `List` import
+        let reply = "```python\ndef total(xs: List[int]) -> int:\n    return sum(xs)\n```";
+        let executor = loaded_executor(&dir, &[humaneval_problem()], reply).await;
+
+        let task = executor.tasks().remove(0);
+        let result = executor.run_task(&task, &ExecutionContext::default()).await;
+
+        assert!(result.solved, "error: {:?}", result.error);
+        assert_eq!(result.api_calls, 1);
+        assert_eq!(result.quality_signals.unwrap()["verdict_ac"], 1.0);
+    }
+
+    #[tokio::test]
+    async fn test_humaneval_run_task_reports_failed_assertion() {
+        let dir = TempDir::new().unwrap();
+        let reply = "```python\ndef similar_elements(a, b):\n    return a\n```";
+        let executor = loaded_executor(&dir, &[mbpp_problem()], reply).await;
+
+        let task = executor.tasks().remove(0);
+        let result = executor.run_task(&task, &ExecutionContext::default()).await;
+
+        assert!(!result.solved);
+        let error = result.error.unwrap();
+        assert!(error.starts_with("0/1 tests passed: WA"), "{}", error);
+        assert!(error.contains("AssertionError"));
+    }
+
+    #[tokio::test]
+    async fn test_humaneval_judge_rust() {
+        let dir = TempDir::new().unwrap();
+        let rust = serde_json::json!({
+            "task_id": "add",
+            "prompt": "fn add(x: i64, y: i64) -> i64 {\n",
+            "test": "assert_eq!(add(2, 3), 5);",
+        });
+        let mut executor = HumanEvalExecutor::new()
+            .with_language(Language::Rust)
+            .with_dataset(write_dataset(&dir, &[rust]));
+        executor.setup().await.unwrap();
+        // Test code without `fn main` is wrapped in one
+        assert!(executor.problems()[0].program("").contains("fn main() {\n"));
+        let limits = SandboxLimits::default();
+
+        let verdict = executor
+            .judge(
+                "add",
+                "fn add(x: i64, y: i64) -> i64 { x + y }",
+                limits.clone(),
+            )
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(verdict.verdict, Verdict::Accepted, "{:?}", verdict.detail);
+
+        let verdict = executor
+            .judge(
+                "add",
+                "fn add(x: i64, y: i64) -> i64 { x - y }",
+                limits.clone(),
+            )
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(verdict.verdict, Verdict::WrongAnswer);
+
+        let verdict = executor
+            .judge("add", "fn add(x: i64) -> i64 { x }", limits.clone())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(verdict.verdict, Verdict::RuntimeError);
+
+        assert!(
+            executor
+                .judge("missing", "", limits)
+                .await
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_infer_entry_point() {
+        assert_eq!(
+            asserted_function("assert not is_prime(4) == True"),
+            Some("is_prime")
+        );
+        assert_eq!(
+            asserted_function("assert set(similar_elements((3, 4), (4, 5)))"),
+            Some("similar_elements")
+        );
+        assert_eq!(
+            asserted_function("assert math.isclose(area(1), 3.14)"),
+            Some("area")
+        );
+        assert_eq!(asserted_function("x = f(1)"), None);
+        assert_eq!(
+            defined_function(
+                "def helper():\n    pass\ndef main_fn(x):\n",
+                Language::Python
+            ),
+            Some("main_fn")
+        );
+        assert_eq!(
+            defined_function("pub fn solve(n: u32) -> u32 {", Language::Rust),
+            Some("solve")
+        );
+    }
+}
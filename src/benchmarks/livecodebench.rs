@@ -1,59 +1,48 @@
-//! LiveCodeBench benchmark executor stub
+//! LiveCodeBench benchmark executor
 //!
-//! This module provides a stub implementation of `BenchmarkExecutor` for LiveCodeBench.
+//! This module provides a `BenchmarkExecutor` implementation for LiveCodeBench.
 //! LiveCodeBench is a contamination-free code generation benchmark released in 2024 that
 //! focuses on competitive programming problems with rigorous test cases.
 //!
-//! # Status: Future Implementation (Phase 3-4)
-//!
-//! This is currently a **stub** with `unimplemented!()` methods. The actual implementation
-//! will be added in Phase 3-4 of the evaluation system development.
-//!
 //! # LiveCodeBench Overview
 //!
 //! - **Release**: 2024 (post-training for most models)
 //! - **Task count**: ~400 problems
 //! - **Source**: LeetCode, AtCoder, Codeforces
-//! - **Languages**: Python, Java, C++, JavaScript
 //! - **Contamination risk**: LOW (released after model training cutoffs)
 //! - **Difficulty**: Easy, Medium, Hard
 //!
-//! # Dataset Structure
+//! # Dataset
 //!
-//! LiveCodeBench tasks include:
-//! - Problem description and constraints
-//! - Input/output examples
-//! - Hidden test cases for evaluation
-//! - Time and memory limits
-//! - Difficulty rating
+//! Problems are read from a locally cached copy of the `code_generation` release
+//! (default: `~/.toad/datasets/livecodebench/problems.jsonl`, see
+//! [`LiveCodeBenchExecutor::with_dataset`]). The file holds one problem per line
+//! (a JSON array works too) with the fields of the official release:
 //!
-//! # TODO: Implementation Checklist
+//! - `question_id`, `question_title`, `question_content`, `platform`, `difficulty`
+//! - `starter_code` (may be empty)
+//! - `public_test_cases`: JSON list of `{input, output, testtype}`, or a string
+//!   holding that list
+//! - `private_test_cases`: same, or the compressed string of the official
+//!   release (base64 of a zlib-compressed pickle of the JSON list)
 //!
-//! 1. **Dataset Integration** (Phase 3.1):
-//!    - [ ] Download LiveCodeBench dataset from official source
-//!    - [ ] Parse problem statements and test cases
-//!    - [ ] Implement difficulty classification
-//!    - [ ] Cache dataset locally
+//! Only `stdin` test cases are run. Problems without any (LeetCode-style
+//! `functional` problems) are skipped when the dataset is loaded.
 //!
-//! 2. **Task Execution** (Phase 3.2):
-//!    - [ ] Create sandboxed execution environment (Docker/Podman)
-//!    - [ ] Support multiple languages (Python, Java, C++, JS)
-//!    - [ ] Run code against hidden test cases
-//!    - [ ] Measure execution time and memory usage
-//!    - [ ] Handle compilation errors and runtime errors
+//! # Execution
 //!
-//! 3. **Metrics Collection** (Phase 3.3):
-//!    - [ ] Pass@k metric (k=1,5,10)
-//!    - [ ] Test case pass rate
-//!    - [ ] Execution time distribution
-//!    - [ ] Memory usage tracking
-//!    - [ ] Compilation success rate
+//! Each task is an agent run in a workspace of its own, with the agent's tools
+//! rooted there. The workspace holds the public test cases (`tests/1.in`,
+//! `tests/1.out`, ...) and a `check.py` script that runs `solution.py` against
+//! them, so the agent can test its program and fix it before answering. Hidden
+//! test cases never enter the workspace.
 //!
-//! 4. **Testing** (Phase 3.4):
-//!    - [ ] Unit tests for dataset loading
-//!    - [ ] Integration tests with mock LLM
-//!    - [ ] End-to-end test with 5 real tasks
-//!    - [ ] Performance benchmarks
+//! When the agent is done, the program is read from `solution.py` (or, if the
+//! agent never wrote it, taken from the code block of its final reply) and run
+//! in a [`Sandbox`] against the public test cases, then the hidden ones, with
+//! the time and memory limits from [`SandboxLimits::from_context`]. Each test
+//! gets a verdict (`AC`, `WA`, `TLE`, `RE`); the task is solved when all of
+//! them are `AC`.
 //!
 //! # References
 //!
@@ -69,37 +58,367 @@
 //!
 //! #[tokio::main]
 //! async fn main() -> anyhow::Result<()> {
-//!     // This will panic with unimplemented!() until Phase 3
-//!     let mut executor = LiveCodeBenchExecutor::new();
-//!     executor.setup().await?; // Panics: "not yet implemented"
+//!     let mut executor = LiveCodeBenchExecutor::new().with_dataset("lcb/problems.jsonl");
+//!     executor.setup().await?;
 //!
+//!     let ctx = ExecutionContext::default();
+//!     for task in executor.tasks().into_iter().take(5) {
+//!         let result = executor.run_task(&task, &ctx).await;
+//!         println!("{}: solved={}", task.id, result.solved);
+//!     }
 //!     Ok(())
 //! }
 //! ```
 
-use crate::ai::evaluation::TaskResult;
-use crate::benchmarks::{BenchmarkExecutor, BenchmarkMetadata, ExecutionContext, Task};
-use anyhow::Result;
+use crate::ai::agent::Agent;
+use crate::ai::evaluation::{self, Complexity, DatasetManager, TaskResult};
+use crate::ai::llm::LLMClient;
+use crate::ai::metrics::MetricsCollector;
+use crate::benchmarks::sandbox::{
+    IoTest, Sandbox, SandboxLimits, TestVerdict, Verdict, extract_code,
+};
+use crate::benchmarks::workspace::Workspace;
+use crate::benchmarks::{BenchmarkExecutor, BenchmarkMetadata, ExecutionContext, Task, codegen};
+use crate::config::ToadConfig;
+use anyhow::{Context, Result};
 use async_trait::async_trait;
+use base64::Engine;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Test type of stdin/stdout test cases
+const STDIN_TEST_TYPE: &str = "stdin";
+
+/// File the agent writes its program to
+const SOLUTION_FILE: &str = "solution.py";
 
-/// LiveCodeBench benchmark executor (stub)
+/// Script in the workspace that runs the solution against the public tests
 ///
-/// This is a placeholder implementation that will be completed in Phase 3-4.
-/// All methods currently call `unimplemented!()`.
+/// Outputs are compared like [`outputs_match`](crate::benchmarks::sandbox::outputs_match)
+/// does: line by line, ignoring whitespace differences and trailing blank lines.
+const CHECK_SCRIPT: &str = r#"import pathlib, subprocess, sys
+
+def lines(text):
+    rows = [line.split() for line in text.splitlines()]
+    while rows and not rows[-1]:
+        rows.pop()
+    return rows
+
+failed = 0
+for case in sorted(pathlib.Path("tests").glob("*.in"), key=lambda p: int(p.stem)):
+    expected = case.with_suffix(".out").read_text()
+    try:
+        with case.open() as stdin:
+            run = subprocess.run([sys.executable, "solution.py"], stdin=stdin,
+                                 capture_output=True, text=True, timeout=10)
+        got = run.stdout + run.stderr
+        ok = run.returncode == 0 and lines(run.stdout) == lines(expected)
+    except subprocess.TimeoutExpired:
+        got = "(timed out after 10s)\n"
+        ok = False
+    if ok:
+        print(f"test {case.stem}: passed")
+    else:
+        failed += 1
+        print(f"test {case.stem}: FAILED\n--- input\n{case.read_text()}--- expected\n{expected}--- got\n{got}")
+print(f"{failed} of the public tests failed" if failed else "all public tests passed")
+sys.exit(1 if failed else 0)
+"#;
+
+/// A LiveCodeBench problem with its runnable test cases
+#[derive(Debug, Clone, PartialEq)]
+pub struct Problem {
+    /// Problem ID (e.g., "abc301_a")
+    pub id: String,
+    /// Problem title
+    pub title: String,
+    /// Problem statement
+    pub content: String,
+    /// Source platform (atcoder, codeforces, leetcode)
+    pub platform: String,
+    /// Difficulty (easy, medium, hard)
+    pub difficulty: String,
+    /// Starter code shown with the problem (may be empty)
+    pub starter_code: String,
+    /// Test cases shown in the statement
+    pub public_tests: Vec<IoTest>,
+    /// Hidden test cases
+    pub private_tests: Vec<IoTest>,
+}
+
+impl Problem {
+    /// All test cases, public first
+    pub fn tests(&self) -> impl Iterator<Item = &IoTest> {
+        self.public_tests.iter().chain(&self.private_tests)
+    }
+
+    /// Generic task for this problem
+    fn to_task(&self) -> Task {
+        let mut metadata = HashMap::new();
+        metadata.insert("title".to_string(), serde_json::json!(self.title));
+        metadata.insert("platform".to_string(), serde_json::json!(self.platform));
+        metadata.insert("difficulty".to_string(), serde_json::json!(self.difficulty));
+        metadata.insert(
+            "public_tests".to_string(),
+            serde_json::json!(self.public_tests.len()),
+        );
+        metadata.insert(
+            "private_tests".to_string(),
+            serde_json::json!(self.private_tests.len()),
+        );
+
+        Task {
+            id: self.id.clone(),
+            description: self.content.clone(),
+            expected_output: Some("All test cases pass".to_string()),
+            metadata,
+        }
+    }
+
+    /// Instructions for the agent
+    fn prompt(&self) -> String {
+        let mut prompt = format!(
+            "Solve the following competitive programming problem in Python 3.\n\n\
+             # {}\n\n{}\n\n",
+            self.title, self.content
+        );
+        if !self.starter_code.trim().is_empty() {
+            prompt.push_str(&format!(
+                "Starter code:\n```python\n{}\n```\n\n",
+                self.starter_code.trim_end()
+            ));
+        }
+        prompt.push_str(&format!(
+            "Write a complete program that reads the input from stdin and writes the \
+             answer to stdout, and save it as {}. The public test cases are in tests/ \
+             (N.in is fed to stdin, N.out is the expected output); run `python3 check.py` \
+             to test your program against them, and fix it until they pass. Hidden test \
+             cases decide the result, so handle the full input constraints.",
+            SOLUTION_FILE
+        ));
+        prompt
+    }
+
+    /// Agent task for this problem, solved in `workspace`
+    fn agent_task(&self, workspace: &Path) -> evaluation::Task {
+        evaluation::Task {
+            id: self.id.clone(),
+            repo: self.platform.clone(),
+            base_commit: String::new(),
+            problem_statement: self.prompt(),
+            hints: Some(format!(
+                "Your workspace is {}, the working directory of your tools. \
+                 Paths outside it are refused.",
+                workspace.display()
+            )),
+            test_patch: String::new(),
+            files_to_modify: vec![PathBuf::from(SOLUTION_FILE)],
+            solution_patch: None,
+            complexity: match self.difficulty.as_str() {
+                "easy" => Complexity::Simple,
+                "hard" => Complexity::Hard,
+                _ => Complexity::Medium,
+            },
+            metadata: HashMap::new(),
+        }
+    }
+
+    /// Workspace holding the public tests, the check script and any starter code
+    fn workspace(&self) -> Result<Workspace> {
+        let workspace = Workspace::create("livecodebench")?;
+        let tests = workspace.path.join("tests");
+        std::fs::create_dir_all(&tests)?;
+        for (i, test) in self.public_tests.iter().enumerate() {
+            std::fs::write(tests.join(format!("{}.in", i + 1)), &test.input)?;
+            std::fs::write(tests.join(format!("{}.out", i + 1)), &test.output)?;
+        }
+        std::fs::write(workspace.path.join("check.py"), CHECK_SCRIPT)?;
+        if !self.starter_code.trim().is_empty() {
+            std::fs::write(workspace.path.join(SOLUTION_FILE), &self.starter_code)?;
+        }
+        Ok(workspace)
+    }
+}
+
+/// A problem as stored in the dataset file
+#[derive(Debug, Deserialize)]
+struct RawProblem {
+    question_id: String,
+    #[serde(default)]
+    question_title: String,
+    question_content: String,
+    #[serde(default)]
+    platform: String,
+    #[serde(default)]
+    difficulty: String,
+    #[serde(default)]
+    starter_code: String,
+    #[serde(default)]
+    public_test_cases: Option<serde_json::Value>,
+    #[serde(default)]
+    private_test_cases: Option<serde_json::Value>,
+}
+
+/// A test case as stored in the dataset file
+#[derive(Debug, Deserialize)]
+struct RawTestCase {
+    input: String,
+    output: String,
+    #[serde(default = "default_test_type")]
+    testtype: String,
+}
+
+fn default_test_type() -> String {
+    STDIN_TEST_TYPE.to_string()
+}
+
+impl RawProblem {
+    fn into_problem(self) -> Result<Problem> {
+        let id = self.question_id;
+        let public_tests = decode_test_cases(self.public_test_cases)
+            .with_context(|| format!("Invalid public test cases of {}", id))?;
+        let private_tests = decode_test_cases(self.private_test_cases)
+            .with_context(|| format!("Invalid private test cases of {}", id))?;
+        Ok(Problem {
+            id,
+            title: self.question_title,
+            content: self.question_content,
+            platform: self.platform,
+            difficulty: self.difficulty,
+            starter_code: self.starter_code,
+            public_tests,
+            private_tests,
+        })
+    }
+}
+
+/// Stdin test cases from a dataset field (list, JSON string or compressed string)
+fn decode_test_cases(value: Option<serde_json::Value>) -> Result<Vec<IoTest>> {
+    let cases: Vec<RawTestCase> = match value {
+        None | Some(serde_json::Value::Null) => Vec::new(),
+        Some(serde_json::Value::String(text)) if text.trim().is_empty() => Vec::new(),
+        Some(serde_json::Value::String(text)) => match serde_json::from_str(&text) {
+            Ok(cases) => cases,
+            Err(_) => serde_json::from_str(&decompress_test_cases(&text)?)
+                .context("Decompressed test cases are not a JSON list")?,
+        },
+        Some(value) => serde_json::from_value(value)?,
+    };
+    Ok(cases
+        .into_iter()
+        .filter(|case| case.testtype == STDIN_TEST_TYPE)
+        .map(|case| IoTest::new(case.input, case.output))
+        .collect())
+}
+
+/// JSON text of test cases in the official release encoding
+/// (`base64(zlib(pickle(json)))`)
+fn decompress_test_cases(text: &str) -> Result<String> {
+    let compressed = base64::engine::general_purpose::STANDARD
+        .decode(text.trim())
+        .context("Test cases are neither JSON nor base64")?;
+    let mut pickled = Vec::new();
+    flate2::read::ZlibDecoder::new(compressed.as_slice())
+        .read_to_end(&mut pickled)
+        .context("Failed to decompress test cases")?;
+    unpickle_string(&pickled).context("Test cases are not a pickled string")
+}
+
+/// The string of a pickle holding a single `str`
+///
+/// Supports the opcodes Python emits for strings (protocols 1-5): optional
+/// `PROTO` and `FRAME` headers followed by `SHORT_BINUNICODE`, `BINUNICODE`
+/// or `BINUNICODE8`.
+fn unpickle_string(data: &[u8]) -> Option<String> {
+    let mut pos = 0;
+    let read_len = |pos: &mut usize, width: usize| -> Option<usize> {
+        let bytes = data.get(*pos..*pos + width)?;
+        *pos += width;
+        let mut buf = [0u8; 8];
+        buf[..width].copy_from_slice(bytes);
+        usize::try_from(u64::from_le_bytes(buf)).ok()
+    };
+    loop {
+        let opcode = *data.get(pos)?;
+        pos += 1;
+        let len = match opcode {
+            // PROTO
+            0x80 => {
+                pos += 1;
+                continue;
+            }
+            // FRAME
+            0x95 => {
+                pos += 8;
+                continue;
+            }
+            // SHORT_BINUNICODE
+            0x8c => read_len(&mut pos, 1)?,
+            // BINUNICODE
+            b'X' => read_len(&mut pos, 4)?,
+            // BINUNICODE8
+            0x8d => read_len(&mut pos, 8)?,
+            _ => return None,
+        };
+        let bytes = data.get(pos..pos.checked_add(len)?)?;
+        return String::from_utf8(bytes.to_vec()).ok();
+    }
+}
+
+/// Load problems with stdin test cases from a JSONL (or JSON array) file
 ///
-/// # Future Design
+/// Returns the problems and the number skipped for lack of stdin tests.
+///
+/// # Errors
+///
+/// Returns `Err` if the file cannot be read or a problem is malformed.
+pub fn load_problems(path: &Path) -> Result<(Vec<Problem>, usize)> {
+    let text = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read LiveCodeBench dataset {}", path.display()))?;
+
+    let raw: Vec<RawProblem> = if text.trim_start().starts_with('[') {
+        serde_json::from_str(&text).context("Failed to parse LiveCodeBench dataset")?
+    } else {
+        text.lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(i, line)| {
+                serde_json::from_str(line).with_context(|| {
+                    format!(
+                        "Failed to parse problem on line {} of {}",
+                        i + 1,
+                        path.display()
+                    )
+                })
+            })
+            .collect::<Result<_>>()?
+    };
+
+    let mut problems = Vec::new();
+    let mut skipped = 0;
+    for raw in raw {
+        let problem = raw.into_problem()?;
+        if problem.tests().next().is_some() {
+            problems.push(problem);
+        } else {
+            skipped += 1;
+        }
+    }
+    Ok((problems, skipped))
+}
+
+/// LiveCodeBench benchmark executor
 ///
-/// The final implementation will:
-/// - Download and cache LiveCodeBench dataset from HuggingFace
-/// - Execute code in sandboxed environments (Docker containers)
-/// - Support multiple programming languages (Python, Java, C++, JS)
-/// - Run code against hidden test cases and measure pass@k
-/// - Collect execution metrics (time, memory, correctness)
+/// Loads problems from a cached dataset file, asks the model for a solution
+/// to each and judges it in a [`Sandbox`] against the public and hidden test
+/// cases.
 ///
 /// # Examples
 ///
 /// ```rust,ignore
-/// // Phase 3+ usage (not yet implemented)
 /// let mut executor = LiveCodeBenchExecutor::new();
 /// executor.setup().await?;
 ///
@@ -107,19 +426,30 @@ use async_trait::async_trait;
 /// println!("Running {} LiveCodeBench tasks", metadata.total_tasks);
 /// ```
 pub struct LiveCodeBenchExecutor {
-    /// Benchmark metadata (hardcoded for now)
+    /// Benchmark metadata (task count updated by setup())
     metadata: BenchmarkMetadata,
+
+    /// Dataset file
+    dataset_path: PathBuf,
+
+    /// Loaded problems (populated by setup())
+    problems: Vec<Problem>,
+
+    /// Model client (None: Anthropic client from the environment)
+    client: Option<Arc<dyn LLMClient>>,
 }
 
 impl LiveCodeBenchExecutor {
     /// Create a new LiveCodeBench executor
     ///
-    /// Returns a stub executor with hardcoded metadata. Actual dataset loading
-    /// will be implemented in Phase 3.
+    /// Reads the dataset from the default cache location. The metadata holds
+    /// the approximate size of the benchmark until [`setup`](BenchmarkExecutor::setup)
+    /// loads it.
     ///
     /// # Examples
     ///
     /// ```
+    /// use toad::benchmarks::BenchmarkExecutor;
     /// use toad::benchmarks::livecodebench::LiveCodeBenchExecutor;
     ///
     /// let executor = LiveCodeBenchExecutor::new();
@@ -132,13 +462,66 @@ impl LiveCodeBenchExecutor {
         let metadata = BenchmarkMetadata {
             name: "LiveCodeBench".to_string(),
             version: "2024-06".to_string(),
-            total_tasks: 400, // Approximate, actual count TBD
-            dataset_url: Some("https://huggingface.co/datasets/livecodebench/code_generation".to_string()),
+            total_tasks: 400, // Approximate until the dataset is loaded
+            dataset_url: Some(
+                "https://huggingface.co/datasets/livecodebench/code_generation".to_string(),
+            ),
             license: Some("Apache-2.0".to_string()),
             contamination_risk: "LOW".to_string(), // Released 2024
         };
 
-        Self { metadata }
+        Self {
+            metadata,
+            dataset_path: Self::default_dataset_path(),
+            problems: Vec::new(),
+            client: None,
+        }
+    }
+
+    /// Default dataset location (`~/.toad/datasets/livecodebench/problems.jsonl`)
+    pub fn default_dataset_path() -> PathBuf {
+        DatasetManager::default_cache_dir()
+            .join("livecodebench")
+            .join("problems.jsonl")
+    }
+
+    /// Read problems from `path` instead of the default location
+    pub fn with_dataset(mut self, path: impl Into<PathBuf>) -> Self {
+        self.dataset_path = path.into();
+        self
+    }
+
+    /// Run the agent with `client` instead of an Anthropic client
+    pub fn with_client(mut self, client: Arc<dyn LLMClient>) -> Self {
+        self.client = Some(client);
+        self
+    }
+
+    /// Loaded problems
+    pub fn problems(&self) -> &[Problem] {
+        &self.problems
+    }
+
+    /// Judge a solution to a loaded problem against all its test cases
+    ///
+    /// Returns one verdict per test, public tests first, or `None` if the
+    /// problem is not loaded.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if the sandbox cannot be created.
+    pub async fn judge(
+        &self,
+        problem_id: &str,
+        source: &str,
+        limits: SandboxLimits,
+    ) -> Result<Option<Vec<TestVerdict>>> {
+        let Some(problem) = self.problems.iter().find(|p| p.id == problem_id) else {
+            return Ok(None);
+        };
+        let tests: Vec<IoTest> = problem.tests().cloned().collect();
+        let sandbox = Sandbox::new(limits)?;
+        Ok(Some(sandbox.judge(source, &tests).await))
     }
 }
 
@@ -148,89 +531,149 @@ impl Default for LiveCodeBenchExecutor {
     }
 }
 
+/// Record per-test verdicts, including how many public tests passed
+fn apply_verdicts(result: &mut TaskResult, verdicts: &[TestVerdict], public_tests: usize) {
+    let public_passed = verdicts
+        .iter()
+        .take(public_tests)
+        .filter(|v| v.verdict == Verdict::Accepted)
+        .count();
+    result
+        .quality_signals
+        .get_or_insert_with(HashMap::new)
+        .insert("public_tests_passed".to_string(), public_passed as f64);
+    codegen::record_verdicts(result, verdicts);
+}
+
 #[async_trait]
 impl BenchmarkExecutor for LiveCodeBenchExecutor {
-    /// Initialize the benchmark executor
-    ///
-    /// # TODO (Phase 3.1)
+    /// Load problems from the cached dataset file
     ///
-    /// - Download dataset from HuggingFace
-    /// - Parse problem statements and test cases
-    /// - Cache dataset locally in ~/.toad/datasets/livecodebench/
-    /// - Validate dataset integrity
+    /// After setup(), `get_metadata().total_tasks` is the number of problems
+    /// with stdin test cases.
     ///
-    /// # Current Status
+    /// # Errors
     ///
-    /// This method is **not implemented** and will panic with `unimplemented!()`.
+    /// Returns error if the dataset file is missing or malformed.
     async fn setup(&mut self) -> Result<()> {
-        unimplemented!(
-            "LiveCodeBench setup not yet implemented. \
-             This will be completed in Phase 3 of the evaluation system. \
-             \
-             Planned implementation: \
-             - Download dataset from HuggingFace \
-             - Parse JSON with problems and test cases \
-             - Cache to ~/.toad/datasets/livecodebench/ \
-             - Update metadata.total_tasks with actual count"
-        )
+        tracing::info!(
+            "Loading LiveCodeBench problems from {:?}",
+            self.dataset_path
+        );
+
+        let path = self.dataset_path.clone();
+        let (problems, skipped) = tokio::task::spawn_blocking(move || load_problems(&path))
+            .await
+            .context("LiveCodeBench dataset loader panicked")??;
+        if skipped > 0 {
+            tracing::info!("Skipped {} problems without stdin test cases", skipped);
+        }
+
+        self.metadata.total_tasks = problems.len();
+        self.problems = problems;
+
+        tracing::info!(
+            "LiveCodeBench setup complete: {} problems loaded",
+            self.problems.len()
+        );
+        Ok(())
     }
 
     /// Execute a single task
     ///
-    /// # TODO (Phase 3.2)
-    ///
-    /// - Parse task requirements and constraints
-    /// - Generate code using LLM (with few-shot examples)
-    /// - Execute code in sandboxed environment (Docker)
-    /// - Run against hidden test cases
-    /// - Measure pass@k, execution time, memory usage
-    /// - Handle compilation/runtime errors gracefully
-    ///
-    /// # Current Status
-    ///
-    /// This method is **not implemented** and will panic with `unimplemented!()`.
-    async fn run_task(&self, _task: &Task, _ctx: &ExecutionContext) -> TaskResult {
-        unimplemented!(
-            "LiveCodeBench task execution not yet implemented. \
-             This will be completed in Phase 3 of the evaluation system. \
-             \
-             Planned implementation: \
-             - Send problem to LLM with few-shot examples \
-             - Generate code solution \
-             - Execute in sandboxed container \
-             - Run against test cases \
-             - Return TaskResult with pass@k metrics"
-        )
+    /// Runs the agent in a fresh workspace with the public tests (within
+    /// `ctx.timeout`), then judges its program in a sandbox against all of the
+    /// problem's test cases. Per-test verdicts are recorded in the result (see
+    /// [`TaskResult::quality_signals`]).
+    async fn run_task(&self, task: &Task, ctx: &ExecutionContext) -> TaskResult {
+        tracing::info!("Running task: {}", task.id);
+        let mut result = TaskResult::new(task.id.clone());
+
+        let Some(problem) = self.problems.iter().find(|p| p.id == task.id) else {
+            result.mark_failed(format!("Task {} not found in loaded dataset", task.id));
+            return result;
+        };
+        let client = match codegen::resolve_client(self.client.as_ref(), ctx) {
+            Ok(client) => client,
+            Err(e) => {
+                result.mark_failed(format!("{:#}", e));
+                return result;
+            }
+        };
+
+        let workspace = match problem.workspace() {
+            Ok(workspace) => workspace,
+            Err(e) => {
+                result.mark_failed(format!("Workspace setup failed: {:#}", e));
+                return result;
+            }
+        };
+        let config =
+            serde_json::from_value::<ToadConfig>(ctx.system_config.clone()).unwrap_or_default();
+        let agent = Agent::new(Box::new(client), workspace.tool_registry(&config.features))
+            .with_max_steps(ctx.max_steps as u32);
+
+        let mut metrics = MetricsCollector::new();
+        metrics.start();
+        let agent_task = problem.agent_task(&workspace.path);
+        let reply =
+            match tokio::time::timeout(ctx.timeout, agent.execute_task(&agent_task, &mut metrics))
+                .await
+            {
+                // The program is judged even when the agent gives up early
+                Ok(Ok(agent_result)) => agent_result.final_response,
+                Ok(Err(e)) => {
+                    codegen::apply_metrics(&mut result, &mut metrics);
+                    result.mark_failed(format!("Agent execution failed: {}", e));
+                    return result;
+                }
+                Err(_) => {
+                    codegen::apply_metrics(&mut result, &mut metrics);
+                    result.mark_failed(format!(
+                        "Task exceeded timeout of {}s",
+                        ctx.timeout.as_secs()
+                    ));
+                    return result;
+                }
+            };
+
+        let source = std::fs::read_to_string(workspace.path.join(SOLUTION_FILE))
+            .unwrap_or_else(|_| extract_code(&reply).to_string());
+        let tests: Vec<IoTest> = problem.tests().cloned().collect();
+        let sandbox = match Sandbox::new(SandboxLimits::from_context(ctx)) {
+            Ok(sandbox) => sandbox,
+            Err(e) => {
+                codegen::apply_metrics(&mut result, &mut metrics);
+                result.mark_failed(format!("{:#}", e));
+                return result;
+            }
+        };
+        metrics.record_test_run();
+        let verdicts = sandbox.judge(&source, &tests).await;
+
+        codegen::apply_metrics(&mut result, &mut metrics);
+        apply_verdicts(&mut result, &verdicts, problem.public_tests.len());
+
+        tracing::info!(
+            "Task {} complete: solved={}, cost=${:.4}",
+            task.id,
+            result.solved,
+            result.cost_usd
+        );
+        result
     }
 
     /// Clean up resources after all tasks complete
     ///
-    /// # TODO (Phase 3.3)
-    ///
-    /// - Stop and remove Docker containers
-    /// - Clean up temporary code files
-    /// - Close database connections (if any)
-    ///
-    /// # Current Status
-    ///
-    /// This method is **not implemented** and will panic with `unimplemented!()`.
+    /// Sandboxes are removed after each task and the dataset stays cached,
+    /// so only the loaded problems are released.
     async fn cleanup(&mut self) -> Result<()> {
-        unimplemented!(
-            "LiveCodeBench cleanup not yet implemented. \
-             This will be completed in Phase 3 of the evaluation system. \
-             \
-             Planned implementation: \
-             - Stop Docker containers \
-             - Remove temporary files \
-             - Close resources"
-        )
+        self.problems.clear();
+        Ok(())
     }
 
     /// Get metadata about this benchmark
     ///
-    /// Returns hardcoded metadata for LiveCodeBench. This method is implemented
-    /// and can be called safely.
-    ///
     /// # Examples
     ///
     /// ```
@@ -247,11 +690,78 @@ impl BenchmarkExecutor for LiveCodeBenchExecutor {
     fn get_metadata(&self) -> &BenchmarkMetadata {
         &self.metadata
     }
+
+    /// Loaded problems as generic tasks, in dataset order
+    fn tasks(&self) -> Vec<Task> {
+        self.problems.iter().map(Problem::to_task).collect()
+    }
+
+    /// Every task works in its own workspace and sandbox
+    fn supports_concurrent_tasks(&self) -> bool {
+        true
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::ai::llm::mock::MockResponseBuilder;
+    use std::io::Write;
+    use tempfile::TempDir;
+
+    const SUM: &str = "a, b = map(int, input().split())\nprint(a + b)\n";
+
+    /// Encode test cases like the official release does
+    fn compress(json: &str) -> String {
+        // Pickle protocol 4: PROTO, FRAME, BINUNICODE, MEMOIZE, STOP
+        let mut pickled = vec![0x80, 4, 0x95];
+        pickled.extend_from_slice(&(json.len() as u64 + 7).to_le_bytes());
+        pickled.push(b'X');
+        pickled.extend_from_slice(&(json.len() as u32).to_le_bytes());
+        pickled.extend_from_slice(json.as_bytes());
+        pickled.extend_from_slice(&[0x94, b'.']);
+
+        let mut encoder =
+            flate2::write::ZlibEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(&pickled).unwrap();
+        base64::engine::general_purpose::STANDARD.encode(encoder.finish().unwrap())
+    }
+
+    fn write_dataset(dir: &TempDir) -> PathBuf {
+        let stdin = |input: &str, output: &str| serde_json::json!({ "input": input, "output": output, "testtype": "stdin" });
+        let hidden = serde_json::json!([stdin("10 5\n", "15\n"), stdin("-1 1\n", "0\n")]);
+        let problems = [
+            serde_json::json!({
+                "question_id": "abc001_a",
+                "question_title": "Sum",
+                "question_content": "Print A + B.",
+                "platform": "atcoder",
+                "difficulty": "easy",
+                "starter_code": "",
+                "public_test_cases": serde_json::json!([stdin("1 2\n", "3\n")]).to_string(),
+                "private_test_cases": compress(&hidden.to_string()),
+            }),
+            serde_json::json!({
+                "question_id": "two-sum",
+                "question_content": "LeetCode style",
+                "public_test_cases": [{ "input": "[1]", "output": "1", "testtype": "functional" }],
+                "private_test_cases": "",
+            }),
+        ];
+        let path = dir.path().join("problems.jsonl");
+        let lines: Vec<String> = problems.iter().map(|p| p.to_string()).collect();
+        std::fs::write(&path, lines.join("\n")).unwrap();
+        path
+    }
+
+    async fn loaded_executor(dir: &TempDir, reply: &str) -> LiveCodeBenchExecutor {
+        let client = MockResponseBuilder::new().with_text(reply).build();
+        let mut executor = LiveCodeBenchExecutor::new()
+            .with_dataset(write_dataset(dir))
+            .with_client(Arc::new(client));
+        executor.setup().await.unwrap();
+        executor
+    }
 
     #[test]
     fn test_livecodebench_metadata() {
@@ -270,28 +780,142 @@ mod tests {
     fn test_livecodebench_default() {
         let executor = LiveCodeBenchExecutor::default();
         assert_eq!(executor.get_metadata().name, "LiveCodeBench");
+        assert!(
+            executor
+                .dataset_path
+                .ends_with("livecodebench/problems.jsonl")
+        );
     }
 
     #[tokio::test]
-    #[should_panic(expected = "not yet implemented")]
-    async fn test_livecodebench_setup_panics() {
-        let mut executor = LiveCodeBenchExecutor::new();
-        let _ = executor.setup().await;
+    async fn test_livecodebench_setup_loads_stdin_problems() {
+        let dir = TempDir::new().unwrap();
+        let executor = loaded_executor(&dir, "").await;
+
+        assert_eq!(executor.get_metadata().total_tasks, 1);
+        let problem = &executor.problems()[0];
+        assert_eq!(problem.public_tests, vec![IoTest::new("1 2\n", "3\n")]);
+        assert_eq!(problem.private_tests.len(), 2);
+        assert_eq!(problem.private_tests[0], IoTest::new("10 5\n", "15\n"));
+
+        let tasks = executor.tasks();
+        assert_eq!(tasks.len(), 1);
+        assert_eq!(tasks[0].id, "abc001_a");
+        assert_eq!(tasks[0].metadata["private_tests"], serde_json::json!(2));
     }
 
     #[tokio::test]
-    #[should_panic(expected = "not yet implemented")]
-    async fn test_livecodebench_run_task_panics() {
-        let executor = LiveCodeBenchExecutor::new();
-        let task = Task::example();
-        let ctx = ExecutionContext::default();
-        let _ = executor.run_task(&task, &ctx).await;
+    async fn test_livecodebench_setup_missing_dataset() {
+        let dir = TempDir::new().unwrap();
+        let mut executor = LiveCodeBenchExecutor::new().with_dataset(dir.path().join("none.jsonl"));
+        let err = executor.setup().await.unwrap_err();
+        assert!(format!("{:#}", err).contains("none.jsonl"));
+    }
+
+    #[tokio::test]
+    async fn test_livecodebench_run_task_solves_problem() {
+        let dir = TempDir::new().unwrap();
+        let reply = format!("Read both numbers.\n```python\n{}```", SUM);
+        let executor = loaded_executor(&dir, &reply).await;
+
+        let task = executor.tasks().remove(0);
+        let result = executor.run_task(&task, &ExecutionContext::default()).await;
+
+        assert!(result.solved, "error: {:?}", result.error);
+        assert_eq!(result.api_calls, 1);
+        assert!(result.cost_usd > 0.0);
+        let signals = result.quality_signals.unwrap();
+        assert_eq!(signals["tests_total"], 3.0);
+        assert_eq!(signals["verdict_ac"], 3.0);
     }
 
     #[tokio::test]
-    #[should_panic(expected = "not yet implemented")]
-    async fn test_livecodebench_cleanup_panics() {
-        let mut executor = LiveCodeBenchExecutor::new();
-        let _ = executor.cleanup().await;
+    async fn test_livecodebench_agent_fixes_solution_with_public_tests() {
+        let dir = TempDir::new().unwrap();
+        let log = |name: &str| dir.path().join(name).display().to_string();
+        let solution =
+            |source: &str| serde_json::json!({ "path": SOLUTION_FILE, "content": source });
+        let client = MockResponseBuilder::new()
+            .with_tool(
+                "write",
+                solution("a, b = map(int, input().split())\nprint(a - b)\n"),
+            )
+            .with_tool(
+                "bash",
+                serde_json::json!({ "command": format!("python3 check.py > {}", log("first.log")) }),
+            )
+            .with_tool("write", solution(SUM))
+            .with_tool(
+                "bash",
+                serde_json::json!({ "command": format!(
+                    "python3 check.py > {}; ls tests > {}",
+                    log("second.log"),
+                    log("tests.log")
+                ) }),
+            )
+            .with_text("Done.")
+            .build();
+        let mut executor = LiveCodeBenchExecutor::new()
+            .with_dataset(write_dataset(&dir))
+            .with_client(Arc::new(client));
+        executor.setup().await.unwrap();
+
+        let task = executor.tasks().remove(0);
+        let result = executor.run_task(&task, &ExecutionContext::default()).await;
+
+        // The program is read from solution.py, not the final reply
+        assert!(result.solved, "error: {:?}", result.error);
+        assert_eq!(result.api_calls, 5);
+
+        // The agent saw the failing public test, then the passing one
+        let first = std::fs::read_to_string(log("first.log")).unwrap();
+        assert!(first.contains("test 1: FAILED"), "{}", first);
+        assert!(
+            first.contains("--- expected\n3\n--- got\n-1\n"),
+            "{}",
+            first
+        );
+        let second = std::fs::read_to_string(log("second.log")).unwrap();
+        assert!(second.contains("all public tests passed"), "{}", second);
+
+        // Hidden tests never enter the workspace
+        let tests = std::fs::read_to_string(log("tests.log")).unwrap();
+        assert_eq!(tests, "1.in\n1.out\n");
+    }
+
+    #[tokio::test]
+    async fn test_livecodebench_run_task_reports_verdicts() {
+        let dir = TempDir::new().unwrap();
+        // Wrong on negative numbers only
+        let reply =
+            "```python\na, b = map(int, input().split())\nprint(abs(a + b) if a > 0 else 1)\n```";
+        let executor = loaded_executor(&dir, reply).await;
+
+        let task = executor.tasks().remove(0);
+        let result = executor.run_task(&task, &ExecutionContext::default()).await;
+
+        assert!(!result.solved);
+        assert_eq!(result.error.as_deref(), Some("2/3 tests passed: AC AC WA"));
+        let signals = result.quality_signals.unwrap();
+        assert_eq!(signals["public_tests_passed"], 1.0);
+        assert_eq!(signals["verdict_wa"], 1.0);
+    }
+
+    #[tokio::test]
+    async fn test_livecodebench_run_unknown_task() {
+        let dir = TempDir::new().unwrap();
+        let executor = loaded_executor(&dir, SUM).await;
+        let result = executor
+            .run_task(&Task::example(), &ExecutionContext::default())
+            .await;
+        assert!(!result.solved);
+        assert!(result.error.unwrap().contains("not found"));
+    }
+
+    #[test]
+    fn test_unpickle_string_short() {
+        let data = [0x80, 4, 0x8c, 2, b'h', b'i', 0x94, b'.'];
+        assert_eq!(unpickle_string(&data).as_deref(), Some("hi"));
+        assert_eq!(unpickle_string(&[0x80, 4, b'N', b'.']), None);
     }
 }
@@ -15,7 +15,10 @@
 //! - [`types`]: Core data structures (Task, BenchmarkMetadata, ProgressEvent)
 //! - `orchestrator`: Multi-benchmark concurrent executor (Phase 5)
 //! - [`swebench`]: SWE-bench adapter
-//! - `livecodebench`: LiveCodeBench stub (future implementation)
+//! - [`livecodebench`]: LiveCodeBench executor
+//! - [`humaneval`]: HumanEval+/MBPP+ function-completion executor
+//! - [`sandbox`]: Sandboxed execution of generated programs against test cases
+//! - [`suite`]: Repo-local task suites (TOML) verified by shell commands
 //!
 //! # Examples
 //!
@@ -47,6 +50,11 @@
 //!         // Return benchmark info
 //!         unimplemented!()
 //!     }
+//!
+//!     fn tasks(&self) -> Vec<Task> {
+//!         // Return the loaded tasks
+//!         Vec::new()
+//!     }
 //! }
 //! ```
 
@@ -56,7 +64,13 @@ use async_trait::async_trait;
 pub mod types;
 pub mod swebench;
 pub mod livecodebench;
+pub mod humaneval;
 pub mod orchestrator;
+pub mod sandbox;
+pub mod scheduler;
+pub mod suite;
+mod codegen;
+mod workspace;
 
 // Re-export core types for convenience
 pub use types::{BenchmarkMetadata, ExecutionContext, ProgressEvent, Task};
@@ -114,6 +128,10 @@ pub use orchestrator::{Orchestrator, OrchestratorConfig};
 ///     fn get_metadata(&self) -> &BenchmarkMetadata {
 ///         &self.metadata
 ///     }
+///
+///     fn tasks(&self) -> Vec<Task> {
+///         self.tasks.clone()
+///     }
 /// }
 /// ```
 #[async_trait]
@@ -147,6 +165,9 @@ pub trait BenchmarkExecutor: Send + Sync {
     ///
     /// Run the task with the provided execution context and return the result.
     /// This method is called multiple times (once per task) and must be thread-safe.
+    /// Executors that return true from `supports_concurrent_tasks()` get several
+    /// calls at once, so those calls must not share mutable state such as working
+    /// directories.
     ///
     /// # Parameters
     ///
@@ -234,6 +255,31 @@ pub trait BenchmarkExecutor: Send + Sync {
     /// }
     /// ```
     fn get_metadata(&self) -> &BenchmarkMetadata;
+
+    /// Tasks to run, in order
+    ///
+    /// Available after `setup()`; the orchestrator runs these (up to its
+    /// task limit) through `run_task()`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// fn tasks(&self) -> Vec<Task> {
+    ///     self.tasks.iter().map(Self::convert_task).collect()
+    /// }
+    /// ```
+    fn tasks(&self) -> Vec<Task>;
+
+    /// Whether several tasks of this benchmark may run at once
+    ///
+    /// Defaults to false: the orchestrator then runs tasks one at a time,
+    /// whatever its worker pool allows. Executors can return true once tasks
+    /// share no files, e.g. because each task gets its own
+    /// [`Sandbox`](sandbox::Sandbox), or runs the agent in its own workspace
+    /// with tools rooted there (see [`Workdir`](crate::ai::tools::Workdir)).
+    fn supports_concurrent_tasks(&self) -> bool {
+        false
+    }
 }
 
 /// Factory function to create a benchmark executor by name
@@ -247,7 +293,10 @@ pub trait BenchmarkExecutor: Send + Sync {
 /// - `"swebench-verified"`: SWE-bench Verified (500 tasks)
 /// - `"swebench-lite"`: SWE-bench Lite (300 tasks)
 /// - `"swebench-full"`: SWE-bench Full (2,294 tasks)
-/// - `"livecodebench"`: LiveCodeBench (stub, not yet implemented)
+/// - `"livecodebench"`: LiveCodeBench (from the locally cached dataset)
+/// - `"humaneval"`: HumanEval+ (164 tasks, from the locally cached dataset)
+/// - `"mbpp"`: MBPP+ (378 tasks, from the locally cached dataset)
+/// - `"suite:<path>"`: the repo-local task suite at `<path>` (see [`suite`])
 ///
 /// # Parameters
 ///
@@ -272,7 +321,7 @@ pub trait BenchmarkExecutor: Send + Sync {
 /// let executor = get_executor("swebench-verified").unwrap();
 /// assert_eq!(executor.get_metadata().name, "SWE-bench Verified");
 ///
-/// // Create LiveCodeBench executor (stub)
+/// // Create LiveCodeBench executor
 /// let executor = get_executor("livecodebench").unwrap();
 /// assert_eq!(executor.get_metadata().name, "LiveCodeBench");
 ///
@@ -283,6 +332,10 @@ pub trait BenchmarkExecutor: Send + Sync {
 pub fn get_executor(name: &str) -> Result<Box<dyn BenchmarkExecutor>> {
     use crate::ai::evaluation::DatasetSource;
 
+    if let Some(path) = name.strip_prefix("suite:") {
+        return Ok(Box::new(suite::SuiteExecutor::new(path)));
+    }
+
     match name.to_lowercase().as_str() {
         "swebench-verified" | "swebench_verified" | "verified" => {
             Ok(Box::new(swebench::SweBenchExecutor::new(
@@ -298,6 +351,12 @@ pub fn get_executor(name: &str) -> Result<Box<dyn BenchmarkExecutor>> {
         "livecodebench" | "live-code-bench" | "lcb" => {
             Ok(Box::new(livecodebench::LiveCodeBenchExecutor::new()))
         }
+        "humaneval" | "humaneval-plus" | "humaneval+" | "humaneval_plus" | "he" => {
+            Ok(Box::new(humaneval::HumanEvalExecutor::new()))
+        }
+        "mbpp" | "mbpp-plus" | "mbpp+" | "mbpp_plus" => {
+            Ok(Box::new(humaneval::HumanEvalExecutor::mbpp()))
+        }
         _ => anyhow::bail!(
             "Unknown benchmark: '{}'. \
              \
@@ -305,7 +364,10 @@ pub fn get_executor(name: &str) -> Result<Box<dyn BenchmarkExecutor>> {
              - swebench-verified (SWE-bench Verified, 500 tasks) \
              - swebench-lite (SWE-bench Lite, 300 tasks) \
              - swebench-full (SWE-bench Full, 2,294 tasks) \
-             - livecodebench (LiveCodeBench, stub)",
+             - livecodebench (LiveCodeBench, ~400 tasks) \
+             - humaneval (HumanEval+, 164 tasks) \
+             - mbpp (MBPP+, 378 tasks) \
+             - suite:<path> (repo-local task suite)",
             name
         ),
     }
@@ -347,6 +409,25 @@ mod tests {
         assert_eq!(metadata.total_tasks, 400);
     }
 
+    #[test]
+    fn test_get_executor_humaneval_and_mbpp() {
+        let executor = get_executor("humaneval").unwrap();
+        assert_eq!(executor.get_metadata().name, "HumanEval+");
+        assert_eq!(executor.get_metadata().total_tasks, 164);
+
+        let executor = get_executor("MBPP+").unwrap();
+        assert_eq!(executor.get_metadata().name, "MBPP+");
+        assert_eq!(executor.get_metadata().total_tasks, 378);
+    }
+
+    #[test]
+    fn test_get_executor_suite() {
+        // The path keeps its case
+        let executor = get_executor("suite:suites/Regressions.toml").unwrap();
+        assert_eq!(executor.get_metadata().name, "Regressions");
+        assert_eq!(executor.get_metadata().total_tasks, 0);
+    }
+
     #[test]
     fn test_get_executor_case_insensitive() {
         // Test case insensitivity
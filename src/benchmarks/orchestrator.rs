@@ -7,11 +7,19 @@
 //!
 //! The orchestrator follows these design principles:
 //!
-//! 1. **Concurrency**: Multiple benchmarks run in parallel via `tokio::spawn`
-//! 2. **Resource limits**: Configurable semaphore prevents resource exhaustion
+//! 1. **Concurrency**: Multiple benchmarks run in parallel via `tokio::spawn`, and
+//!    the tasks of each benchmark on a bounded worker pool
+//! 2. **Resource limits**: Configurable semaphore prevents resource exhaustion;
+//!    task concurrency adapts to rate limits, CPU and memory (see [`scheduler`])
 //! 3. **Progress tracking**: Unbounded channel streams [`ProgressEvent`] to consumers
 //! 4. **Cancellation**: Supports graceful shutdown via `CancellationToken`
-//! 5. **Fault isolation**: Benchmark panics don't crash the orchestrator
+//! 5. **Fault isolation**: Benchmark and task panics don't crash the orchestrator
+//! 6. **Determinism**: Results are stored in task order, whatever order tasks
+//!    finish in
+//! 7. **Resumability**: With a [`RunJournal`], every finished task is journaled
+//!    and tasks the journal already holds are not run again
+//!
+//! [`scheduler`]: crate::benchmarks::scheduler
 //!
 //! # Execution Flow
 //!
@@ -22,8 +30,8 @@
 //! 4. Each task:
 //!    - Acquires semaphore permit (limits concurrency)
 //!    - Sends BenchmarkStarted event
-//!    - Runs tasks sequentially within benchmark
-//!    - Sends TaskCompleted events
+//!    - Runs tasks on a worker pool within benchmark
+//!    - Sends TaskStarted and TaskCompleted events
 //!    - Sends BenchmarkCompleted event
 //!    - Releases semaphore permit
 //! 5. Main task waits for all benchmarks, sends EvaluationCompleted
@@ -43,6 +51,8 @@
 //!         task_limit: Some(10),
 //!         max_concurrent_benchmarks: 2,
 //!         execution_context: Default::default(),
+//!         sampling: Default::default(),
+//!         scheduler: Default::default(),
 //!     };
 //!
 //!     let cancel_token = CancellationToken::new();
@@ -63,16 +73,22 @@
 //! }
 //! ```
 
-use crate::ai::evaluation::{EvaluationRun, TaskResult, BenchmarkResult};
-use crate::benchmarks::{get_executor, BenchmarkMetadata, ExecutionContext};
-use crate::benchmarks::types::{ProgressEvent, Task};
+use crate::ai::evaluation::{EvaluationRun, RunJournal, TaskResult, BenchmarkResult, SamplingConfig};
+use crate::benchmarks::{get_executor, BenchmarkExecutor, BenchmarkMetadata, ExecutionContext, Task};
+use crate::benchmarks::scheduler::{ConcurrencyController, SchedulerConfig, SystemResources};
+use crate::benchmarks::types::ProgressEvent;
 use anyhow::{Context, Result};
+use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::sync::{mpsc, Semaphore};
+use tokio::task::JoinSet;
 use tokio_util::sync::CancellationToken;
 use crate::ai::evaluation::storage::StorageManager;
 
+/// How often resource usage is re-checked while tasks wait to start
+const RESOURCE_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
 /// Configuration for the orchestrator
 ///
 /// Defines which benchmarks to run, resource limits, and execution parameters.
@@ -88,6 +104,8 @@ use crate::ai::evaluation::storage::StorageManager;
 ///     task_limit: Some(50), // Limit to first 50 tasks per benchmark
 ///     max_concurrent_benchmarks: 2, // Run 2 benchmarks at once
 ///     execution_context: ExecutionContext::default(),
+///     sampling: Default::default(), // One attempt per task
+///     scheduler: Default::default(), // One task at a time per benchmark
 /// };
 /// ```
 #[derive(Debug, Clone, serde::Serialize)]
@@ -110,6 +128,34 @@ pub struct OrchestratorConfig {
     ///
     /// Contains timeouts, max steps, system config, sandbox config.
     pub execution_context: ExecutionContext,
+
+    /// Attempts per task
+    ///
+    /// With more than one sample, each task runs `samples` times and every
+    /// attempt is stored as its own [`TaskResult`] (see
+    /// [`crate::ai::evaluation::sampling`]).
+    pub sampling: SamplingConfig,
+
+    /// Task-level worker pool of each benchmark
+    ///
+    /// Default: one task at a time. With a higher limit, fewer run under rate
+    /// limits or when the machine is loaded; executors that don't support
+    /// concurrent tasks always run one at a time.
+    pub scheduler: SchedulerConfig,
+}
+
+impl OrchestratorConfig {
+    /// Settings a resumed run must match
+    ///
+    /// The scheduler is left out: it changes how fast results arrive, not
+    /// what they are, so a resumed run may use a different worker pool.
+    pub fn resume_snapshot(&self) -> Result<serde_json::Value> {
+        let mut snapshot = serde_json::to_value(self)?;
+        if let Some(fields) = snapshot.as_object_mut() {
+            fields.remove("scheduler");
+        }
+        Ok(snapshot)
+    }
 }
 
 impl Default for OrchestratorConfig {
@@ -119,6 +165,8 @@ impl Default for OrchestratorConfig {
             task_limit: None,
             max_concurrent_benchmarks: 2,
             execution_context: ExecutionContext::default(),
+            sampling: SamplingConfig::default(),
+            scheduler: SchedulerConfig::default(),
         }
     }
 }
@@ -131,13 +179,16 @@ impl Default for OrchestratorConfig {
 /// # Concurrency Model
 ///
 /// - **Benchmark-level parallelism**: Multiple benchmarks run concurrently (controlled by semaphore)
-/// - **Task-level serialization**: Within each benchmark, tasks run sequentially
-/// - **Rationale**: Task-level parallelism would complicate rate limiting and resource management
+/// - **Task-level parallelism**: Within each benchmark, tasks run on a worker pool whose
+///   size a [`ConcurrencyController`] adapts to rate limits, CPU and memory
+/// - **Isolation**: Each task runs in its own tokio task with its own [`ExecutionContext`];
+///   a panicking task is recorded as failed
+/// - **Samples**: In sampling mode the samples of a task run one after another
 ///
 /// # Cancellation
 ///
 /// The orchestrator supports graceful cancellation via `CancellationToken`. When cancelled:
-/// - In-flight tasks complete
+/// - In-flight tasks complete (the worker pool drains)
 /// - Remaining tasks are skipped
 /// - Progress events are sent for completed work
 /// - Partial results are returned
@@ -160,6 +211,9 @@ pub struct Orchestrator {
 
     /// Cancellation token for graceful shutdown
     cancel_token: CancellationToken,
+
+    /// Journal of finished tasks, for resuming an interrupted run
+    journal: Option<Arc<RunJournal>>,
 }
 
 impl Orchestrator {
@@ -184,9 +238,19 @@ impl Orchestrator {
         Self {
             config,
             cancel_token,
+            journal: None,
         }
     }
 
+    /// Journal every finished task, skipping tasks `journal` already holds
+    ///
+    /// The run takes the journal's run ID. Open the journal with a snapshot
+    /// from [`OrchestratorConfig::resume_snapshot`].
+    pub fn with_journal(mut self, journal: Arc<RunJournal>) -> Self {
+        self.journal = Some(journal);
+        self
+    }
+
     /// Run the evaluation across all configured benchmarks
     ///
     /// This method:
@@ -232,8 +296,11 @@ impl Orchestrator {
         // Create progress channel (unbounded to prevent backpressure)
         let (progress_tx, progress_rx) = mpsc::unbounded_channel::<ProgressEvent>();
 
-        // Generate unique run ID
-        let run_id = StorageManager::generate_run_id();
+        // Continue the journaled run, or generate a unique run ID
+        let run_id = match &self.journal {
+            Some(journal) => journal.run_id().to_string(),
+            None => StorageManager::generate_run_id(),
+        };
         let start_time = Instant::now();
 
         // Send initial progress event
@@ -255,6 +322,7 @@ impl Orchestrator {
             let progress_tx = progress_tx.clone();
             let cancel_token = self.cancel_token.clone();
             let semaphore = Arc::clone(&semaphore);
+            let journal = self.journal.clone();
 
             let handle = tokio::spawn(async move {
                 // Acquire semaphore permit (limits concurrent benchmarks)
@@ -287,6 +355,7 @@ impl Orchestrator {
                     &config,
                     progress_tx,
                     cancel_token,
+                    journal,
                 )
                 .await
             });
@@ -337,9 +406,9 @@ impl Orchestrator {
     /// This method:
     /// 1. Creates the benchmark executor
     /// 2. Sets up the benchmark (downloads datasets, etc.)
-    /// 3. Runs tasks sequentially
-    /// 4. Sends progress events for each task
-    /// 5. Returns the benchmark result
+    /// 3. Runs the executor's tasks on a worker pool (up to the task limit)
+    /// 4. Sends progress events as tasks start and finish
+    /// 5. Returns the benchmark result, with task results in task order
     ///
     /// # Arguments
     ///
@@ -347,6 +416,7 @@ impl Orchestrator {
     /// * `config` - Orchestrator configuration
     /// * `progress_tx` - Channel for sending progress events
     /// * `cancel_token` - Token for checking cancellation
+    /// * `journal` - Journal of finished tasks, if the run is resumable
     ///
     /// # Errors
     ///
@@ -359,6 +429,7 @@ impl Orchestrator {
         config: &OrchestratorConfig,
         progress_tx: mpsc::UnboundedSender<ProgressEvent>,
         cancel_token: CancellationToken,
+        journal: Option<Arc<RunJournal>>,
     ) -> Result<BenchmarkResult> {
         let benchmark_start = Instant::now();
 
@@ -373,9 +444,12 @@ impl Orchestrator {
         // Get metadata
         let metadata = executor.get_metadata().clone();
 
-        // Determine task list (mock for now - actual implementation depends on executor)
-        // TODO: Add get_tasks() method to BenchmarkExecutor trait in future
-        let total_tasks = config.task_limit.unwrap_or(metadata.total_tasks);
+        // Determine task list (limited by config)
+        let mut tasks = executor.tasks();
+        if let Some(limit) = config.task_limit {
+            tasks.truncate(limit);
+        }
+        let total_tasks = tasks.len();
 
         // Send benchmark started event
         let _ = progress_tx.send(ProgressEvent::BenchmarkStarted {
@@ -383,54 +457,27 @@ impl Orchestrator {
             total_tasks,
         });
 
-        // Run tasks sequentially
-        let mut task_results = Vec::new();
-        let mut tasks_solved = 0;
-        let mut total_cost = 0.0;
-
-        for task_index in 0..total_tasks {
-            // Check for cancellation
-            if cancel_token.is_cancelled() {
-                tracing::info!("Benchmark '{}' cancelled at task {}/{}", benchmark_name, task_index, total_tasks);
-                break;
-            }
-
-            // Create mock task (TODO: Get from executor)
-            let task = Task {
-                id: format!("{}-task-{}", benchmark_name, task_index),
-                description: format!("Task {} for {}", task_index, benchmark_name),
-                expected_output: None,
-                metadata: std::collections::HashMap::new(),
-            };
-
-            let task_start = Instant::now();
-
-            // Run task
-            let result = executor.run_task(&task, &config.execution_context).await;
-
-            let task_duration = task_start.elapsed();
-
-            // Track solved status
-            if result.solved {
-                tasks_solved += 1;
-            }
-
-            total_cost += result.cost_usd;
-
-            // Send progress event
-            let _ = progress_tx.send(ProgressEvent::TaskCompleted {
-                benchmark_name: metadata.name.clone(),
-                task_id: task.id.clone(),
-                task_index,
-                total_tasks,
-                solved: result.solved,
-                duration_ms: task_duration.as_millis() as u64,
-                cost_usd: result.cost_usd,
-            });
+        // Share the executor with the workers (setup and cleanup need it exclusively)
+        let mut executor: Arc<dyn BenchmarkExecutor> = Arc::from(executor);
+        let completed = Self::run_tasks(
+            &executor,
+            &tasks,
+            &metadata.name,
+            config,
+            &progress_tx,
+            &cancel_token,
+            journal.as_deref(),
+        )
+        .await;
 
-            task_results.push(result);
+        let mut task_results = Vec::new();
+        let mut total_task_ms = 0;
+        for (results, task_duration) in completed {
+            total_task_ms += task_duration.as_millis() as u64;
+            task_results.extend(results);
         }
-
+        let tasks_solved = task_results.iter().filter(|r| r.solved).count();
+        let total_cost: f64 = task_results.iter().map(|r| r.cost_usd).sum();
         let benchmark_duration = benchmark_start.elapsed();
 
         // Send benchmark completed event
@@ -442,8 +489,10 @@ impl Orchestrator {
             cost_usd: total_cost,
         });
 
-        // Cleanup
-        let _ = executor.cleanup().await;
+        // Cleanup (every worker has finished, so the executor is no longer shared)
+        if let Some(executor) = Arc::get_mut(&mut executor) {
+            let _ = executor.cleanup().await;
+        }
 
         // Calculate metrics
         let success_rate = if task_results.is_empty() {
@@ -458,10 +507,11 @@ impl Orchestrator {
             total_cost / task_results.len() as f64
         };
 
+        // Per-task wall time, not benchmark duration (tasks overlap)
         let avg_duration_per_task_ms = if task_results.is_empty() {
             0.0
         } else {
-            benchmark_duration.as_millis() as f64 / task_results.len() as f64
+            total_task_ms as f64 / task_results.len() as f64
         };
 
         // Create benchmark result
@@ -476,6 +526,181 @@ impl Orchestrator {
         })
     }
 
+    /// Run `tasks` on the worker pool
+    ///
+    /// Starts tasks while the [`ConcurrencyController`] allows (one at a time
+    /// if the executor doesn't support concurrent tasks), and stops starting
+    /// them once cancelled; tasks already running finish either way.
+    /// Tasks whose every sample is in `journal` are restored instead of run,
+    /// and every task that runs is journaled under `benchmark_name`.
+    /// Returns the results and wall time of every task that ran, in task order.
+    async fn run_tasks(
+        executor: &Arc<dyn BenchmarkExecutor>,
+        tasks: &[Task],
+        benchmark_name: &str,
+        config: &OrchestratorConfig,
+        progress_tx: &mpsc::UnboundedSender<ProgressEvent>,
+        cancel_token: &CancellationToken,
+        journal: Option<&RunJournal>,
+    ) -> Vec<(Vec<TaskResult>, Duration)> {
+        let total_tasks = tasks.len();
+        let mut scheduler = config.scheduler.clone();
+        if scheduler.max_concurrent_tasks > 1 && !executor.supports_concurrent_tasks() {
+            tracing::warn!(
+                "Benchmark '{}' does not support concurrent tasks; running them one at a time",
+                benchmark_name
+            );
+            scheduler = scheduler.with_max_tasks(1);
+        }
+        let mut controller = ConcurrencyController::new(scheduler);
+        let mut workers = JoinSet::new();
+        let mut worker_tasks = HashMap::new();
+
+        // Results by task index, so their order doesn't depend on completion order
+        let mut slots: Vec<Option<(Vec<TaskResult>, Duration)>> = tasks
+            .iter()
+            .map(|task| Self::restore_task(journal, benchmark_name, task, &config.sampling))
+            .collect();
+        let pending: Vec<usize> = (0..total_tasks).filter(|&i| slots[i].is_none()).collect();
+        if pending.len() < total_tasks {
+            tracing::info!(
+                "Benchmark '{}': restored {}/{} tasks from the run journal",
+                benchmark_name,
+                total_tasks - pending.len(),
+                total_tasks
+            );
+        }
+        let mut next_pending = 0;
+
+        loop {
+            // Start tasks while the controller allows
+            while next_pending < pending.len() && !cancel_token.is_cancelled() {
+                let resources = if workers.is_empty() { None } else { SystemResources::sample() };
+                if !controller.can_start(workers.len(), resources) {
+                    break;
+                }
+
+                let next_task = pending[next_pending];
+                let task = tasks[next_task].clone();
+                let _ = progress_tx.send(ProgressEvent::TaskStarted {
+                    benchmark_name: benchmark_name.to_string(),
+                    task_id: task.id.clone(),
+                    task_index: next_task,
+                    total_tasks,
+                    in_flight: workers.len() + 1,
+                });
+                let handle = workers.spawn(Self::run_samples(
+                    Arc::clone(executor),
+                    task,
+                    config.execution_context.clone(),
+                    config.sampling.clone(),
+                ));
+                worker_tasks.insert(handle.id(), next_task);
+                next_pending += 1;
+            }
+
+            if workers.is_empty() {
+                if next_pending < pending.len() {
+                    tracing::info!(
+                        "Benchmark '{}' cancelled with {}/{} tasks left",
+                        benchmark_name,
+                        pending.len() - next_pending,
+                        total_tasks
+                    );
+                }
+                break;
+            }
+
+            // Wait for a task to finish; re-check resources now and then if tasks are waiting
+            let joined = tokio::select! {
+                joined = workers.join_next_with_id() => joined,
+                _ = tokio::time::sleep(RESOURCE_POLL_INTERVAL), if next_pending < pending.len() => continue,
+            };
+            let Some(joined) = joined else { continue };
+
+            let (task_index, results, task_duration) = match joined {
+                Ok((id, (results, duration))) => (worker_tasks[&id], results, duration),
+                Err(e) => {
+                    // Task panicked: record it as failed and keep going
+                    let task_index = worker_tasks[&e.id()];
+                    let mut result = TaskResult::new(tasks[task_index].id.clone());
+                    result.mark_failed(format!("Task execution panicked: {}", e));
+                    (task_index, vec![result], Duration::ZERO)
+                }
+            };
+            results.iter().for_each(|result| controller.record(result));
+            if let Some(journal) = journal {
+                for result in &results {
+                    if let Err(e) = journal.append(benchmark_name, result) {
+                        tracing::warn!("Failed to journal task {}: {:#}", result.task_id, e);
+                    }
+                }
+            }
+
+            // Send progress event (solved if any sample solved the task)
+            let _ = progress_tx.send(ProgressEvent::TaskCompleted {
+                benchmark_name: benchmark_name.to_string(),
+                task_id: tasks[task_index].id.clone(),
+                task_index,
+                total_tasks,
+                solved: results.iter().any(|r| r.solved),
+                duration_ms: task_duration.as_millis() as u64,
+                cost_usd: results.iter().map(|r| r.cost_usd).sum(),
+                in_flight: workers.len(),
+            });
+
+            slots[task_index] = Some((results, task_duration));
+        }
+
+        slots.into_iter().flatten().collect()
+    }
+
+    /// Results of `task` from the journal, if it holds every sample
+    ///
+    /// A task with only some samples journaled runs again in full.
+    fn restore_task(
+        journal: Option<&RunJournal>,
+        benchmark_name: &str,
+        task: &Task,
+        sampling: &SamplingConfig,
+    ) -> Option<(Vec<TaskResult>, Duration)> {
+        let journal = journal?;
+        let results = sampling
+            .samples()
+            .map(|sample| {
+                let index = sampling.is_sampling().then_some(sample.index);
+                journal.restored(benchmark_name, &task.id, index)
+            })
+            .collect::<Option<Vec<_>>>()?;
+        let duration_ms = results.iter().map(|r| r.duration_ms).sum();
+        Some((results, Duration::from_millis(duration_ms)))
+    }
+
+    /// Run every sample of one task, in order
+    ///
+    /// Returns the results and the task's wall time.
+    async fn run_samples(
+        executor: Arc<dyn BenchmarkExecutor>,
+        task: Task,
+        execution_context: ExecutionContext,
+        sampling: SamplingConfig,
+    ) -> (Vec<TaskResult>, Duration) {
+        let task_start = Instant::now();
+        let mut results = Vec::with_capacity(sampling.samples);
+        for sample in sampling.samples() {
+            let ctx = ExecutionContext {
+                sample: Some(sample),
+                ..execution_context.clone()
+            };
+            let mut result = executor.run_task(&task, &ctx).await;
+            if sampling.is_sampling() {
+                result.sample = Some(sample.index);
+            }
+            results.push(result);
+        }
+        (results, task_start.elapsed())
+    }
+
     /// Aggregate metrics across multiple benchmark results
     ///
     /// Computes overall accuracy, total tasks, costs, and median latency.
@@ -536,6 +761,227 @@ impl Orchestrator {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::ai::evaluation::RunKind;
+    use async_trait::async_trait;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Solves even tasks after a delay that shrinks with the task index, so
+    /// later tasks finish first; task "panic" panics
+    struct SlowExecutor {
+        metadata: BenchmarkMetadata,
+        running: AtomicUsize,
+        max_running: AtomicUsize,
+        concurrent: bool,
+    }
+
+    impl SlowExecutor {
+        fn new() -> Self {
+            Self {
+                metadata: BenchmarkMetadata {
+                    name: "Slow".to_string(),
+                    version: "1.0".to_string(),
+                    total_tasks: 0,
+                    dataset_url: None,
+                    license: None,
+                    contamination_risk: "LOW".to_string(),
+                },
+                running: AtomicUsize::new(0),
+                max_running: AtomicUsize::new(0),
+                concurrent: true,
+            }
+        }
+    }
+
+    #[async_trait]
+    impl BenchmarkExecutor for SlowExecutor {
+        async fn setup(&mut self) -> Result<()> {
+            Ok(())
+        }
+
+        async fn run_task(&self, task: &Task, _ctx: &ExecutionContext) -> TaskResult {
+            let running = self.running.fetch_add(1, Ordering::SeqCst) + 1;
+            self.max_running.fetch_max(running, Ordering::SeqCst);
+            let index: u64 = task.id.trim_start_matches("task-").parse().unwrap_or(0);
+            tokio::time::sleep(Duration::from_millis(60 - 10 * index.min(5))).await;
+            self.running.fetch_sub(1, Ordering::SeqCst);
+            assert_ne!(task.id, "panic", "executor bug");
+
+            let mut result = TaskResult::new(task.id.clone());
+            if index % 2 == 0 {
+                result.mark_solved();
+            }
+            result
+        }
+
+        async fn cleanup(&mut self) -> Result<()> {
+            Ok(())
+        }
+
+        fn get_metadata(&self) -> &BenchmarkMetadata {
+            &self.metadata
+        }
+
+        fn tasks(&self) -> Vec<Task> {
+            Vec::new()
+        }
+
+        fn supports_concurrent_tasks(&self) -> bool {
+            self.concurrent
+        }
+    }
+
+    /// Ignores the load of the machine running the tests
+    fn unthrottled(max_tasks: usize) -> SchedulerConfig {
+        SchedulerConfig {
+            max_load_per_cpu: f64::INFINITY,
+            min_available_memory_mb: 0,
+            ..SchedulerConfig::default().with_max_tasks(max_tasks)
+        }
+    }
+
+    fn tasks(ids: &[&str]) -> Vec<Task> {
+        ids.iter()
+            .map(|id| Task {
+                id: id.to_string(),
+                ..Task::example()
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_run_tasks_parallel_in_task_order() {
+        let slow = Arc::new(SlowExecutor::new());
+        let executor: Arc<dyn BenchmarkExecutor> = slow.clone();
+        let tasks = tasks(&["task-0", "task-1", "panic", "task-3", "task-4"]);
+        let config = OrchestratorConfig {
+            scheduler: unthrottled(3),
+            ..OrchestratorConfig::default()
+        };
+        let (tx, mut rx) = mpsc::unbounded_channel();
+
+        let completed =
+            Orchestrator::run_tasks(&executor, &tasks, "Slow", &config, &tx, &CancellationToken::new(), None).await;
+
+        let ids: Vec<&str> = completed.iter().map(|(r, _)| r[0].task_id.as_str()).collect();
+        assert_eq!(ids, vec!["task-0", "task-1", "panic", "task-3", "task-4"]);
+        assert!(completed[0].0[0].solved && !completed[1].0[0].solved);
+        assert!(completed[2].0[0].error.as_deref().unwrap().contains("panicked"));
+        assert!(slow.max_running.load(Ordering::SeqCst) > 1);
+        assert!(slow.max_running.load(Ordering::SeqCst) <= 3);
+
+        drop(tx);
+        let mut started = 0;
+        let mut completion_order = Vec::new();
+        while let Some(event) = rx.recv().await {
+            match event {
+                ProgressEvent::TaskStarted { in_flight, .. } => {
+                    started += 1;
+                    assert!((1..=3).contains(&in_flight));
+                }
+                ProgressEvent::TaskCompleted { task_index, .. } => completion_order.push(task_index),
+                _ => {}
+            }
+        }
+        assert_eq!(started, 5);
+        assert_ne!(completion_order, vec![0, 1, 2, 3, 4]);
+    }
+
+    #[tokio::test]
+    async fn test_run_tasks_sequential_without_concurrency_support() {
+        let slow = Arc::new(SlowExecutor {
+            concurrent: false,
+            ..SlowExecutor::new()
+        });
+        let executor: Arc<dyn BenchmarkExecutor> = slow.clone();
+        let tasks = tasks(&["task-0", "task-1", "task-2"]);
+        let config = OrchestratorConfig {
+            scheduler: unthrottled(3),
+            ..OrchestratorConfig::default()
+        };
+        let (tx, _rx) = mpsc::unbounded_channel();
+
+        let completed =
+            Orchestrator::run_tasks(&executor, &tasks, "Slow", &config, &tx, &CancellationToken::new(), None).await;
+
+        assert_eq!(completed.len(), 3);
+        assert_eq!(slow.max_running.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_run_tasks_cancellation_drains() {
+        let executor: Arc<dyn BenchmarkExecutor> = Arc::new(SlowExecutor::new());
+        let tasks = tasks(&["task-0", "task-1", "task-2", "task-3", "task-4"]);
+        let config = OrchestratorConfig {
+            scheduler: unthrottled(2),
+            ..OrchestratorConfig::default()
+        };
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let cancel_token = CancellationToken::new();
+
+        let canceller = cancel_token.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            canceller.cancel();
+        });
+        let completed = Orchestrator::run_tasks(&executor, &tasks, "Slow", &config, &tx, &cancel_token, None).await;
+
+        // The two running tasks finish; the rest never start
+        let ids: Vec<&str> = completed.iter().map(|(r, _)| r[0].task_id.as_str()).collect();
+        assert_eq!(ids, vec!["task-0", "task-1"]);
+    }
+
+    #[tokio::test]
+    async fn test_run_tasks_resumes_from_journal() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let storage = StorageManager::new(temp_dir.path());
+        let executor: Arc<dyn BenchmarkExecutor> = Arc::new(SlowExecutor::new());
+        let tasks = tasks(&["task-0", "task-1", "task-2", "task-3", "task-4"]);
+        let config = OrchestratorConfig {
+            scheduler: unthrottled(2),
+            ..OrchestratorConfig::default()
+        };
+        let snapshot = config.resume_snapshot().unwrap();
+        let (tx, mut rx) = mpsc::unbounded_channel();
+
+        // First attempt is cancelled after the first two tasks
+        let journal = storage.start_journal(RunKind::Benchmarks, snapshot.clone(), None).unwrap();
+        let run_id = journal.run_id().to_string();
+        let cancel_token = CancellationToken::new();
+        let canceller = cancel_token.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            canceller.cancel();
+        });
+        Orchestrator::run_tasks(&executor, &tasks, "Slow", &config, &tx, &cancel_token, Some(&journal)).await;
+        drop(journal);
+        while rx.try_recv().is_ok() {}
+
+        // Resuming runs only the other three, even with a different worker pool
+        let config = OrchestratorConfig {
+            scheduler: unthrottled(3),
+            ..config
+        };
+        let journal = storage
+            .resume_journal(&run_id, &config.resume_snapshot().unwrap())
+            .unwrap();
+        let completed =
+            Orchestrator::run_tasks(&executor, &tasks, "Slow", &config, &tx, &CancellationToken::new(), Some(&journal))
+                .await;
+
+        let ids: Vec<&str> = completed.iter().map(|(r, _)| r[0].task_id.as_str()).collect();
+        assert_eq!(ids, vec!["task-0", "task-1", "task-2", "task-3", "task-4"]);
+        assert!(completed[0].0[0].solved);
+
+        drop(tx);
+        let mut started = Vec::new();
+        while let Some(event) = rx.recv().await {
+            if let ProgressEvent::TaskStarted { task_index, .. } = event {
+                started.push(task_index);
+            }
+        }
+        started.sort_unstable();
+        assert_eq!(started, vec![2, 3, 4]);
+    }
 
     #[test]
     fn test_orchestrator_config_default() {
@@ -544,6 +990,8 @@ mod tests {
         assert_eq!(config.benchmarks, vec!["swebench-verified"]);
         assert_eq!(config.task_limit, None);
         assert_eq!(config.max_concurrent_benchmarks, 2);
+        assert!(!config.sampling.is_sampling());
+        assert_eq!(config.scheduler.max_concurrent_tasks, 1);
     }
 
     #[test]
@@ -0,0 +1,814 @@
+//! Sandboxed execution of generated programs
+//!
+//! Runs model-written Python and Rust programs for the code generation
+//! benchmarks: against stdin/stdout test cases (LiveCodeBench) or with their
+//! test harness appended (HumanEval+/MBPP).
+//!
+//! # Isolation
+//!
+//! Each [`Sandbox`] owns a fresh temporary directory that is removed when it is
+//! dropped. Programs run there with a cleared environment (`HOME` points into
+//! the sandbox), Python's isolated mode (`-I`) and stdin/stdout/stderr piped.
+//! On Unix the process also gets resource limits through `ulimit`:
+//!
+//! - **Memory**: address space capped at [`SandboxLimits::memory_limit_mb`]
+//! - **CPU time**: soft limit a second above [`SandboxLimits::time_limit`],
+//!   hard limit a second after that
+//!
+//! The wall-clock limit is enforced on every platform: the process is killed
+//! once [`SandboxLimits::time_limit`] has passed.
+//!
+//! This is process-level isolation; it keeps runaway solutions in check but is
+//! not a security boundary against hostile code.
+//!
+//! # Verdicts
+//!
+//! Every test case gets a [`Verdict`] in the usual competitive programming
+//! terms:
+//!
+//! - `AC`: output matches (ignoring trailing whitespace and blank lines)
+//! - `WA`: program finished but printed something else
+//! - `TLE`: program was killed at the wall-clock limit, or by `SIGXCPU` at the
+//!   CPU time limit
+//! - `RE`: program crashed, exited non-zero or could not be started
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use toad::benchmarks::sandbox::{IoTest, Sandbox, SandboxLimits, Verdict};
+//!
+//! # async fn example() -> anyhow::Result<()> {
+//! let sandbox = Sandbox::new(SandboxLimits::default())?;
+//! let tests = vec![IoTest::new("1 2\n", "3\n")];
+//! let verdicts = sandbox
+//!     .judge("a, b = map(int, input().split())\nprint(a + b)\n", &tests)
+//!     .await;
+//! assert_eq!(verdicts[0].verdict, Verdict::Accepted);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::benchmarks::ExecutionContext;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::time::{Duration, Instant};
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+
+/// File Python solutions are written to inside the sandbox
+const PYTHON_SOLUTION_FILE: &str = "solution.py";
+
+/// File Rust solutions are written to inside the sandbox
+const RUST_SOLUTION_FILE: &str = "solution.rs";
+
+/// Binary Rust solutions are compiled to
+const RUST_BINARY_FILE: &str = "solution";
+
+/// Variables kept from the parent environment (to find interpreters and
+/// toolchains)
+const PASSTHROUGH_ENV: [&str; 4] = ["PATH", "RUSTUP_HOME", "CARGO_HOME", "RUSTUP_TOOLCHAIN"];
+
+/// Start of the stderr of a run whose compilation failed
+const COMPILATION_FAILED: &str = "compilation failed";
+
+/// Longest stderr excerpt kept for a failed test
+const MAX_DETAIL_CHARS: usize = 500;
+
+/// Resource limits for one program run
+///
+/// Read from [`ExecutionContext::sandbox_config`] by
+/// [`SandboxLimits::from_context`]:
+///
+/// ```json
+/// {
+///     "time_limit_ms": 6000,
+///     "memory_limit_mb": 512,
+///     "python": "python3",
+///     "rustc": "rustc",
+///     "compile_time_limit_ms": 60000
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SandboxLimits {
+    /// Wall-clock limit per test case
+    #[serde(rename = "time_limit_ms", with = "duration_ms")]
+    pub time_limit: Duration,
+
+    /// Address space limit in megabytes (Unix only, 0 for none)
+    pub memory_limit_mb: u64,
+
+    /// Python interpreter to run solutions with
+    pub python: String,
+
+    /// Rust compiler to build solutions with
+    pub rustc: String,
+
+    /// Wall-clock limit for compiling a solution
+    #[serde(rename = "compile_time_limit_ms", with = "duration_ms")]
+    pub compile_time_limit: Duration,
+}
+
+impl Default for SandboxLimits {
+    fn default() -> Self {
+        Self {
+            time_limit: Duration::from_secs(6),
+            memory_limit_mb: 512,
+            python: "python3".to_string(),
+            rustc: "rustc".to_string(),
+            compile_time_limit: Duration::from_secs(60),
+        }
+    }
+}
+
+impl SandboxLimits {
+    /// Limits from the context's sandbox config, defaulting missing keys
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use toad::benchmarks::ExecutionContext;
+    /// use toad::benchmarks::sandbox::SandboxLimits;
+    /// use std::time::Duration;
+    ///
+    /// let ctx = ExecutionContext {
+    ///     sandbox_config: Some(serde_json::json!({ "time_limit_ms": 2000 })),
+    ///     ..ExecutionContext::default()
+    /// };
+    /// let limits = SandboxLimits::from_context(&ctx);
+    /// assert_eq!(limits.time_limit, Duration::from_secs(2));
+    /// assert_eq!(limits.memory_limit_mb, 512);
+    /// ```
+    pub fn from_context(ctx: &ExecutionContext) -> Self {
+        ctx.sandbox_config
+            .as_ref()
+            .and_then(|config| match serde_json::from_value(config.clone()) {
+                Ok(limits) => Some(limits),
+                Err(e) => {
+                    tracing::warn!("Invalid sandbox config, using defaults: {}", e);
+                    None
+                }
+            })
+            .unwrap_or_default()
+    }
+}
+
+/// Serialize durations as whole milliseconds
+mod duration_ms {
+    use serde::{Deserialize, Deserializer, Serializer};
+    use std::time::Duration;
+
+    pub fn serialize<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u64(duration.as_millis() as u64)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+        u64::deserialize(deserializer).map(Duration::from_millis)
+    }
+}
+
+/// A stdin/stdout test case
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IoTest {
+    /// Text fed to the program's stdin
+    pub input: String,
+    /// Expected stdout
+    pub output: String,
+}
+
+impl IoTest {
+    /// Create a test case
+    pub fn new(input: impl Into<String>, output: impl Into<String>) -> Self {
+        Self {
+            input: input.into(),
+            output: output.into(),
+        }
+    }
+}
+
+/// Outcome of one test case
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Verdict {
+    /// Output matches
+    #[serde(rename = "AC")]
+    Accepted,
+    /// Output differs
+    #[serde(rename = "WA")]
+    WrongAnswer,
+    /// Killed at the time limit
+    #[serde(rename = "TLE")]
+    TimeLimitExceeded,
+    /// Crashed or exited non-zero
+    #[serde(rename = "RE")]
+    RuntimeError,
+}
+
+impl Verdict {
+    /// All verdicts, in report order
+    pub const ALL: [Verdict; 4] = [
+        Verdict::Accepted,
+        Verdict::WrongAnswer,
+        Verdict::TimeLimitExceeded,
+        Verdict::RuntimeError,
+    ];
+
+    /// Short code (`AC`, `WA`, `TLE`, `RE`)
+    pub fn code(&self) -> &'static str {
+        match self {
+            Verdict::Accepted => "AC",
+            Verdict::WrongAnswer => "WA",
+            Verdict::TimeLimitExceeded => "TLE",
+            Verdict::RuntimeError => "RE",
+        }
+    }
+}
+
+impl std::fmt::Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+/// Verdict of one test case with its run time
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TestVerdict {
+    /// Outcome
+    pub verdict: Verdict,
+    /// Wall-clock run time in milliseconds
+    pub duration_ms: u64,
+    /// Why the test failed (stderr excerpt or exit status), if it did
+    pub detail: Option<String>,
+}
+
+/// Raw result of running a program once
+#[derive(Debug, Clone)]
+pub struct RunOutput {
+    /// Captured stdout
+    pub stdout: String,
+    /// Captured stderr
+    pub stderr: String,
+    /// Whether the process exited with status 0
+    pub success: bool,
+    /// Exit code, if the process exited normally
+    pub exit_code: Option<i32>,
+    /// Whether the process was killed for exceeding a time limit
+    pub timed_out: bool,
+    /// Wall-clock run time
+    pub duration: Duration,
+}
+
+/// Temporary directory programs run in, with their limits
+#[derive(Debug)]
+pub struct Sandbox {
+    dir: PathBuf,
+    limits: SandboxLimits,
+}
+
+impl Sandbox {
+    /// Create a sandbox in a fresh temporary directory
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if the directory cannot be created.
+    pub fn new(limits: SandboxLimits) -> Result<Self> {
+        let dir = std::env::temp_dir().join(format!("toad-sandbox-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir)
+            .with_context(|| format!("Failed to create sandbox directory {}", dir.display()))?;
+        Ok(Self { dir, limits })
+    }
+
+    /// Directory programs run in
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Limits applied to each run
+    pub fn limits(&self) -> &SandboxLimits {
+        &self.limits
+    }
+
+    /// Write a file into the sandbox directory
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if the file cannot be written.
+    pub async fn write_file(&self, name: &str, contents: &str) -> Result<PathBuf> {
+        let path = self.dir.join(name);
+        tokio::fs::write(&path, contents)
+            .await
+            .with_context(|| format!("Failed to write {}", name))?;
+        Ok(path)
+    }
+
+    /// Run a Python program once with `stdin` as input
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if the program cannot be written or the interpreter
+    /// cannot be started. Crashes and timeouts are reported in the
+    /// [`RunOutput`].
+    pub async fn run_python(&self, source: &str, stdin: &str) -> Result<RunOutput> {
+        self.write_file(PYTHON_SOLUTION_FILE, source).await?;
+        self.run(
+            &self.limits.python,
+            &["-I", PYTHON_SOLUTION_FILE],
+            stdin,
+            &self.limits,
+        )
+        .await
+    }
+
+    /// Compile a Rust program and run it once with `stdin` as input
+    ///
+    /// Compilation gets [`SandboxLimits::compile_time_limit`] and no memory
+    /// limit. A failed compilation is reported as a failed run whose stderr
+    /// starts with `compilation failed`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if the program cannot be written or the compiler or
+    /// binary cannot be started.
+    pub async fn run_rust(&self, source: &str, stdin: &str) -> Result<RunOutput> {
+        self.write_file(RUST_SOLUTION_FILE, source).await?;
+        let compile_limits = SandboxLimits {
+            time_limit: self.limits.compile_time_limit,
+            memory_limit_mb: 0,
+            ..self.limits.clone()
+        };
+        let compiled = self
+            .run(
+                &self.limits.rustc,
+                &[
+                    "--edition",
+                    "2021",
+                    "-O",
+                    "-o",
+                    RUST_BINARY_FILE,
+                    RUST_SOLUTION_FILE,
+                ],
+                "",
+                &compile_limits,
+            )
+            .await?;
+        if !compiled.success {
+            return Ok(RunOutput {
+                stderr: format!("{}\n{}", COMPILATION_FAILED, compiled.stderr),
+                ..compiled
+            });
+        }
+
+        let binary = self.dir.join(RUST_BINARY_FILE);
+        self.run(binary.as_os_str(), &[], stdin, &self.limits).await
+    }
+
+    /// Run a program in the sandbox directory with the given limits
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if the program cannot be started.
+    pub async fn run(
+        &self,
+        program: impl AsRef<OsStr>,
+        args: &[&str],
+        stdin: &str,
+        limits: &SandboxLimits,
+    ) -> Result<RunOutput> {
+        let program = program.as_ref();
+        let mut child = self
+            .command(program, args, limits)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .with_context(|| format!("Failed to start {}", program.to_string_lossy()))?;
+
+        // Feed stdin concurrently so large inputs cannot deadlock on a full pipe
+        let mut child_stdin = child.stdin.take();
+        let input = stdin.to_string();
+        let feed = tokio::spawn(async move {
+            if let Some(pipe) = child_stdin.as_mut() {
+                let _ = pipe.write_all(input.as_bytes()).await;
+                let _ = pipe.shutdown().await;
+            }
+        });
+
+        let start = Instant::now();
+        let output = tokio::time::timeout(limits.time_limit, child.wait_with_output()).await;
+        let duration = start.elapsed();
+        feed.abort();
+
+        let Ok(output) = output else {
+            // Dropping the child future killed the process
+            return Ok(RunOutput {
+                stdout: String::new(),
+                stderr: String::new(),
+                success: false,
+                exit_code: None,
+                timed_out: true,
+                duration,
+            });
+        };
+        let output = output.context("Failed to wait for program")?;
+        Ok(RunOutput {
+            stdout: String::from_utf8_lossy(&output.stdout).into_owned(),
+            stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+            success: output.status.success(),
+            exit_code: output.status.code(),
+            timed_out: killed_by_cpu_limit(&output.status),
+            duration,
+        })
+    }
+
+    /// Run a Python program against each test case
+    ///
+    /// Returns one verdict per test, in order.
+    pub async fn judge(&self, source: &str, tests: &[IoTest]) -> Vec<TestVerdict> {
+        let mut verdicts = Vec::with_capacity(tests.len());
+        for test in tests {
+            let verdict = match self.run_python(source, &test.input).await {
+                Ok(run) => verdict_for(&run, &test.output),
+                Err(e) => TestVerdict {
+                    verdict: Verdict::RuntimeError,
+                    duration_ms: 0,
+                    detail: Some(format!("{:#}", e)),
+                },
+            };
+            verdicts.push(verdict);
+        }
+        verdicts
+    }
+
+    /// Command with the sandbox's environment and limits
+    fn command(&self, program: &OsStr, args: &[&str], limits: &SandboxLimits) -> Command {
+        #[cfg(unix)]
+        let mut command = {
+            let mut script = String::new();
+            if limits.memory_limit_mb > 0 {
+                script.push_str(&format!("ulimit -v {} && ", limits.memory_limit_mb * 1024));
+            }
+            // Linux sends SIGKILL rather than SIGXCPU when the soft and hard
+            // limits match, which would be indistinguishable from an OOM kill
+            let cpu_secs = limits.time_limit.as_secs() + 1;
+            script.push_str(&format!(
+                "ulimit -St {} && ulimit -Ht {} && exec \"$0\" \"$@\"",
+                cpu_secs,
+                cpu_secs + 1
+            ));
+            let mut command = Command::new("sh");
+            command.arg("-c").arg(script).arg(program);
+            command
+        };
+        #[cfg(not(unix))]
+        let mut command = {
+            let _ = limits;
+            Command::new(program)
+        };
+
+        command
+            .args(args)
+            .current_dir(&self.dir)
+            .env_clear()
+            .env("HOME", &self.dir)
+            .env("PYTHONIOENCODING", "utf-8");
+        for name in PASSTHROUGH_ENV {
+            if let Some(value) = std::env::var_os(name) {
+                command.env(name, value);
+            }
+        }
+        // rustup shims find their toolchains through the real home directory
+        if let Some(home) = dirs::home_dir() {
+            if std::env::var_os("RUSTUP_HOME").is_none() {
+                command.env("RUSTUP_HOME", home.join(".rustup"));
+            }
+            if std::env::var_os("CARGO_HOME").is_none() {
+                command.env("CARGO_HOME", home.join(".cargo"));
+            }
+        }
+        command
+    }
+}
+
+impl Drop for Sandbox {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.dir);
+    }
+}
+
+/// Whether a process was killed for exceeding its CPU time limit
+///
+/// Only `SIGXCPU` counts: a `SIGKILL` may just as well come from the kernel's
+/// OOM killer, and a program that ignores `SIGXCPU` until the hard limit is
+/// reported as a runtime error.
+fn killed_by_cpu_limit(status: &std::process::ExitStatus) -> bool {
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::ExitStatusExt;
+        // SIGXCPU
+        status.signal() == Some(24)
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = status;
+        false
+    }
+}
+
+/// Judge one run against the expected output
+fn verdict_for(run: &RunOutput, expected: &str) -> TestVerdict {
+    let duration_ms = run.duration.as_millis() as u64;
+    let (verdict, detail) = if run.timed_out {
+        (Verdict::TimeLimitExceeded, None)
+    } else if !run.success {
+        (Verdict::RuntimeError, Some(failure_detail(run)))
+    } else if outputs_match(&run.stdout, expected) {
+        (Verdict::Accepted, None)
+    } else {
+        (Verdict::WrongAnswer, None)
+    };
+    TestVerdict {
+        verdict,
+        duration_ms,
+        detail,
+    }
+}
+
+/// Judge a run of a program with its test harness appended
+///
+/// The program passes by exiting with status 0. A failed assertion (Python's
+/// `AssertionError`, a Rust `assert!` panic) is a wrong answer; any other
+/// failure, including a failed compilation, is a runtime error.
+///
+/// # Examples
+///
+/// ```
+/// use toad::benchmarks::sandbox::{RunOutput, Verdict, harness_verdict};
+/// use std::time::Duration;
+///
+/// let run = RunOutput {
+///     stdout: String::new(),
+///     stderr: "Traceback (most recent call last):\nAssertionError\n".to_string(),
+///     success: false,
+///     exit_code: Some(1),
+///     timed_out: false,
+///     duration: Duration::from_millis(30),
+/// };
+/// assert_eq!(harness_verdict(&run).verdict, Verdict::WrongAnswer);
+/// ```
+pub fn harness_verdict(run: &RunOutput) -> TestVerdict {
+    let (verdict, detail) = if run.timed_out {
+        (Verdict::TimeLimitExceeded, None)
+    } else if run.success {
+        (Verdict::Accepted, None)
+    } else if !run.stderr.starts_with(COMPILATION_FAILED)
+        && (run.stderr.contains("AssertionError") || run.stderr.contains("assertion"))
+    {
+        (Verdict::WrongAnswer, Some(failure_detail(run)))
+    } else {
+        (Verdict::RuntimeError, Some(failure_detail(run)))
+    };
+    TestVerdict {
+        verdict,
+        duration_ms: run.duration.as_millis() as u64,
+        detail,
+    }
+}
+
+/// Exit status and stderr excerpt of a failed run
+fn failure_detail(run: &RunOutput) -> String {
+    let status = match run.exit_code {
+        Some(code) => format!("exit code {}", code),
+        None => "killed by signal".to_string(),
+    };
+    let stderr = run.stderr.trim();
+    if stderr.is_empty() {
+        status
+    } else {
+        format!("{}: {}", status, tail(stderr, MAX_DETAIL_CHARS))
+    }
+}
+
+/// Compare outputs token by token on each line, ignoring trailing blank lines
+///
+/// # Examples
+///
+/// ```
+/// use toad::benchmarks::sandbox::outputs_match;
+///
+/// assert!(outputs_match("1 2  \n3\n\n", "1 2\n3"));
+/// assert!(!outputs_match("1 2\n3\n", "1\n2 3\n"));
+/// ```
+pub fn outputs_match(actual: &str, expected: &str) -> bool {
+    fn lines(text: &str) -> Vec<Vec<&str>> {
+        let mut lines: Vec<Vec<&str>> = text
+            .lines()
+            .map(|line| line.split_whitespace().collect())
+            .collect();
+        while lines.last().is_some_and(|line| line.is_empty()) {
+            lines.pop();
+        }
+        lines
+    }
+    lines(actual) == lines(expected)
+}
+
+/// Code of the last Python block in a model reply
+///
+/// See [`extract_code_block`]; blocks tagged `python`, `python3` or `py`
+/// are preferred.
+///
+/// # Examples
+///
+/// ```
+/// use toad::benchmarks::sandbox::extract_code;
+///
+/// let reply = "Idea first.\n```python\nprint(42)\n```\nDone.";
+/// assert_eq!(extract_code(reply), "print(42)\n");
+/// assert_eq!(extract_code("print(1)"), "print(1)");
+/// ```
+pub fn extract_code(reply: &str) -> &str {
+    extract_code_block(reply, &["python", "python3", "py"])
+}
+
+/// Code of the last fenced block in a model reply
+///
+/// Prefers blocks tagged with one of `tags`, then untagged ones; a reply
+/// without such blocks is taken as code.
+///
+/// # Examples
+///
+/// ```
+/// use toad::benchmarks::sandbox::extract_code_block;
+///
+/// let reply = "```text\nplan\n```\n```rust\nfn main() {}\n```";
+/// assert_eq!(extract_code_block(reply, &["rust", "rs"]), "fn main() {}\n");
+/// ```
+pub fn extract_code_block<'a>(reply: &'a str, tags: &[&str]) -> &'a str {
+    let mut tagged = None;
+    let mut untagged = None;
+    let mut rest = reply;
+    while let Some(open) = rest.find("```") {
+        let after = &rest[open + 3..];
+        let Some(newline) = after.find('\n') else {
+            break;
+        };
+        let tag = after[..newline].trim();
+        let body = &after[newline + 1..];
+        let Some(close) = body.find("```") else {
+            break;
+        };
+        let code = &body[..close];
+        if tag.is_empty() {
+            untagged = Some(code);
+        } else if tags.contains(&tag) {
+            tagged = Some(code);
+        }
+        rest = &body[close + 3..];
+    }
+    tagged.or(untagged).unwrap_or(reply)
+}
+
+/// Last `max` characters of `text`
+pub(crate) fn tail(text: &str, max: usize) -> &str {
+    let count = text.chars().count();
+    if count <= max {
+        return text;
+    }
+    let start = text
+        .char_indices()
+        .nth(count - max)
+        .map(|(i, _)| i)
+        .unwrap_or(0);
+    &text[start..]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SUM: &str = "a, b = map(int, input().split())\nprint(a + b)\n";
+
+    fn sandbox(time_limit: Duration) -> Sandbox {
+        Sandbox::new(SandboxLimits {
+            time_limit,
+            ..SandboxLimits::default()
+        })
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_judge_verdicts() {
+        let sandbox = sandbox(Duration::from_secs(5));
+        let tests = vec![IoTest::new("1 2\n", "3\n"), IoTest::new("2 2\n", "5\n")];
+        let verdicts = sandbox.judge(SUM, &tests).await;
+        let codes: Vec<Verdict> = verdicts.iter().map(|v| v.verdict).collect();
+        assert_eq!(codes, vec![Verdict::Accepted, Verdict::WrongAnswer]);
+
+        let crash = sandbox
+            .judge("raise ValueError('boom')\n", &tests[..1])
+            .await;
+        assert_eq!(crash[0].verdict, Verdict::RuntimeError);
+        assert!(
+            crash[0]
+                .detail
+                .as_ref()
+                .unwrap()
+                .contains("ValueError: boom")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_judge_time_limit() {
+        let sandbox = sandbox(Duration::from_millis(500));
+        let tests = vec![IoTest::new("", "")];
+        let verdicts = sandbox.judge("while True:\n    pass\n", &tests).await;
+        assert_eq!(verdicts[0].verdict, Verdict::TimeLimitExceeded);
+        assert!(verdicts[0].duration_ms < 5000);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_memory_limit_and_environment() {
+        let sandbox = Sandbox::new(SandboxLimits {
+            memory_limit_mb: 256,
+            ..SandboxLimits::default()
+        })
+        .unwrap();
+        let hog = "x = bytearray(1024 * 1024 * 1024)\nprint(len(x))\n";
+        let run = sandbox.run_python(hog, "").await.unwrap();
+        assert!(!run.success);
+        assert!(run.stderr.contains("MemoryError"));
+        let verdicts = sandbox.judge(hog, &[IoTest::new("", "")]).await;
+        assert_eq!(verdicts[0].verdict, Verdict::RuntimeError);
+
+        let env = "import os\nprint(os.getcwd())\nprint(os.environ.get('ANTHROPIC_API_KEY'))\n";
+        let run = sandbox.run_python(env, "").await.unwrap();
+        let dir = sandbox.dir().canonicalize().unwrap();
+        assert_eq!(run.stdout, format!("{}\nNone\n", dir.display()));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_sigkill_is_not_cpu_limit() {
+        use std::os::unix::process::ExitStatusExt;
+        use std::process::ExitStatus;
+
+        assert!(killed_by_cpu_limit(&ExitStatus::from_raw(24)));
+        assert!(!killed_by_cpu_limit(&ExitStatus::from_raw(9)));
+    }
+
+    #[tokio::test]
+    async fn test_run_rust() {
+        let sandbox = sandbox(Duration::from_secs(5));
+        let source = "use std::io::Read;\n\
+                      fn main() {\n\
+                          let mut s = String::new();\n\
+                          std::io::stdin().read_to_string(&mut s).unwrap();\n\
+                          println!(\"{}\", s.trim().len());\n\
+                      }\n";
+        let run = sandbox.run_rust(source, "abcd\n").await.unwrap();
+        assert!(run.success, "stderr: {}", run.stderr);
+        assert_eq!(run.stdout, "4\n");
+
+        let run = sandbox
+            .run_rust("fn main() { let x: u8 = \"no\"; }", "")
+            .await
+            .unwrap();
+        assert!(!run.success);
+        assert!(run.stderr.starts_with("compilation failed"));
+    }
+
+    #[test]
+    fn test_sandbox_dir_removed_on_drop() {
+        let sandbox = sandbox(Duration::from_secs(1));
+        let dir = sandbox.dir().to_path_buf();
+        assert!(dir.is_dir());
+        drop(sandbox);
+        assert!(!dir.exists());
+    }
+
+    #[test]
+    fn test_limits_from_context() {
+        let ctx = ExecutionContext {
+            sandbox_config: Some(serde_json::json!({
+                "time_limit_ms": 1500,
+                "memory_limit_mb": 128,
+                "python": "python3.12",
+            })),
+            ..ExecutionContext::default()
+        };
+        let limits = SandboxLimits::from_context(&ctx);
+        assert_eq!(limits.time_limit, Duration::from_millis(1500));
+        assert_eq!(limits.memory_limit_mb, 128);
+        assert_eq!(limits.python, "python3.12");
+
+        let ctx = ExecutionContext {
+            sandbox_config: Some(serde_json::json!({ "time_limit_ms": "soon" })),
+            ..ExecutionContext::default()
+        };
+        assert_eq!(SandboxLimits::from_context(&ctx), SandboxLimits::default());
+    }
+}
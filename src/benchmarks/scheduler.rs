@@ -0,0 +1,279 @@
+//! Resource-aware task scheduling
+//!
+//! The [`Orchestrator`](super::Orchestrator) runs the tasks of a benchmark on a
+//! bounded worker pool. How many tasks run at once is decided by a
+//! [`ConcurrencyController`]:
+//!
+//! - **Rate limits**: a task whose error reports a provider rate limit halves
+//!   the limit and starts a cooldown; after the cooldown, every `limit`
+//!   consecutive successes raise it by one again (additive increase,
+//!   multiplicative decrease, as in TCP congestion control)
+//! - **CPU and memory**: no new task starts while the 1-minute load average
+//!   per CPU is above [`SchedulerConfig::max_load_per_cpu`] or available memory
+//!   is below [`SchedulerConfig::min_available_memory_mb`]
+//!
+//! At least one task always runs, so a busy machine slows an evaluation down
+//! but never stalls it. Tasks run one at a time by default, and always for
+//! executors that don't support concurrent tasks (see
+//! [`BenchmarkExecutor::supports_concurrent_tasks`](super::BenchmarkExecutor::supports_concurrent_tasks)). Resource usage is read from `/proc`; on other
+//! platforms only the rate limit adapts the concurrency.
+//!
+//! # Examples
+//!
+//! ```
+//! use toad::ai::evaluation::TaskResult;
+//! use toad::benchmarks::scheduler::{ConcurrencyController, SchedulerConfig};
+//!
+//! let mut controller = ConcurrencyController::new(SchedulerConfig::default().with_max_tasks(8));
+//! assert_eq!(controller.limit(), 8);
+//!
+//! let mut result = TaskResult::new("task-1".to_string());
+//! result.mark_failed("Rate limit exceeded. Retry after Some(30) seconds".to_string());
+//! controller.record(&result);
+//! assert_eq!(controller.limit(), 4);
+//! ```
+
+use crate::ai::evaluation::TaskResult;
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+/// Limits of the task-level worker pool
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SchedulerConfig {
+    /// Maximum tasks running at once within a benchmark (1 = sequential)
+    pub max_concurrent_tasks: usize,
+
+    /// Don't start tasks while the 1-minute load average per CPU exceeds this
+    pub max_load_per_cpu: f64,
+
+    /// Don't start tasks while less memory than this is available (MiB)
+    pub min_available_memory_mb: u64,
+
+    /// How long concurrency stays reduced after a rate-limited task
+    #[serde(with = "humantime_serde")]
+    pub rate_limit_cooldown: Duration,
+}
+
+impl Default for SchedulerConfig {
+    fn default() -> Self {
+        Self {
+            max_concurrent_tasks: 1,
+            max_load_per_cpu: 1.0,
+            min_available_memory_mb: 1024,
+            rate_limit_cooldown: Duration::from_secs(30),
+        }
+    }
+}
+
+impl SchedulerConfig {
+    /// Run tasks one at a time
+    pub fn sequential() -> Self {
+        Self::default().with_max_tasks(1)
+    }
+
+    /// Run at most `max` tasks at once (at least one)
+    pub fn with_max_tasks(mut self, max: usize) -> Self {
+        self.max_concurrent_tasks = max.max(1);
+        self
+    }
+}
+
+/// Snapshot of the machine's load
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SystemResources {
+    /// 1-minute load average divided by the number of CPUs
+    pub load_per_cpu: f64,
+
+    /// Memory available to new processes (MiB)
+    pub available_memory_mb: u64,
+}
+
+impl SystemResources {
+    /// Current load, or None where `/proc` is unavailable
+    pub fn sample() -> Option<Self> {
+        let loadavg = std::fs::read_to_string("/proc/loadavg").ok()?;
+        let meminfo = std::fs::read_to_string("/proc/meminfo").ok()?;
+        let cpus = std::thread::available_parallelism().map_or(1, |n| n.get());
+        Self::parse(&loadavg, &meminfo, cpus)
+    }
+
+    fn parse(loadavg: &str, meminfo: &str, cpus: usize) -> Option<Self> {
+        let load: f64 = loadavg.split_whitespace().next()?.parse().ok()?;
+        let available_kb: u64 = meminfo
+            .lines()
+            .find_map(|line| line.strip_prefix("MemAvailable:"))?
+            .split_whitespace()
+            .next()?
+            .parse()
+            .ok()?;
+        Some(Self {
+            load_per_cpu: load / cpus.max(1) as f64,
+            available_memory_mb: available_kb / 1024,
+        })
+    }
+}
+
+/// Adaptive limit on concurrently running tasks
+#[derive(Debug)]
+pub struct ConcurrencyController {
+    config: SchedulerConfig,
+    limit: usize,
+    successes: usize,
+    cooldown_until: Option<Instant>,
+}
+
+impl ConcurrencyController {
+    /// Start at the configured maximum
+    pub fn new(config: SchedulerConfig) -> Self {
+        let limit = config.max_concurrent_tasks.max(1);
+        Self {
+            config,
+            limit,
+            successes: 0,
+            cooldown_until: None,
+        }
+    }
+
+    /// Current limit on running tasks
+    pub fn limit(&self) -> usize {
+        self.limit
+    }
+
+    /// Whether another task may start while `in_flight` tasks run
+    ///
+    /// `resources` is a [`SystemResources::sample`]; None skips the CPU and
+    /// memory checks.
+    pub fn can_start(&self, in_flight: usize, resources: Option<SystemResources>) -> bool {
+        if in_flight == 0 {
+            return true;
+        }
+        if in_flight >= self.limit {
+            return false;
+        }
+        resources.is_none_or(|r| {
+            r.load_per_cpu <= self.config.max_load_per_cpu
+                && r.available_memory_mb >= self.config.min_available_memory_mb
+        })
+    }
+
+    /// Adapt the limit to a finished task
+    pub fn record(&mut self, result: &TaskResult) {
+        let now = Instant::now();
+        if is_rate_limited(result) {
+            self.limit = (self.limit / 2).max(1);
+            self.successes = 0;
+            self.cooldown_until = Some(now + self.config.rate_limit_cooldown);
+            tracing::warn!(
+                "Task {} hit a rate limit; running at most {} tasks at once",
+                result.task_id,
+                self.limit
+            );
+            return;
+        }
+        if self.cooldown_until.is_some_and(|until| now < until) {
+            return;
+        }
+        self.successes += 1;
+        if self.successes >= self.limit && self.limit < self.config.max_concurrent_tasks {
+            self.limit += 1;
+            self.successes = 0;
+        }
+    }
+}
+
+/// Whether a task failed because the provider rejected a request as over its
+/// rate limit (HTTP 429 or 529)
+fn is_rate_limited(result: &TaskResult) -> bool {
+    result.error.as_deref().is_some_and(|error| {
+        let error = error.to_lowercase();
+        ["rate limit", "rate_limit", "(429)", "(529)", "overloaded"]
+            .iter()
+            .any(|marker| error.contains(marker))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn failed(error: &str) -> TaskResult {
+        let mut result = TaskResult::new("task".to_string());
+        result.mark_failed(error.to_string());
+        result
+    }
+
+    #[test]
+    fn test_rate_limit_halves_then_recovers() {
+        let config = SchedulerConfig {
+            rate_limit_cooldown: Duration::ZERO,
+            ..SchedulerConfig::default().with_max_tasks(4)
+        };
+        let mut controller = ConcurrencyController::new(config);
+
+        controller.record(&failed("API error (429): Too Many Requests"));
+        assert_eq!(controller.limit(), 2);
+        controller.record(&failed("Overloaded"));
+        controller.record(&failed("rate_limit_error"));
+        assert_eq!(controller.limit(), 1);
+
+        // Other failures count as completions
+        controller.record(&failed("Wrong answer on test 3"));
+        assert_eq!(controller.limit(), 2);
+        controller.record(&TaskResult::new("a".to_string()));
+        controller.record(&TaskResult::new("b".to_string()));
+        assert_eq!(controller.limit(), 3);
+        for _ in 0..10 {
+            controller.record(&TaskResult::new("c".to_string()));
+        }
+        assert_eq!(controller.limit(), 4);
+    }
+
+    #[test]
+    fn test_cooldown_holds_limit() {
+        let mut controller =
+            ConcurrencyController::new(SchedulerConfig::default().with_max_tasks(4));
+        controller.record(&failed("Rate limit exceeded"));
+        for _ in 0..10 {
+            controller.record(&TaskResult::new("a".to_string()));
+        }
+        assert_eq!(controller.limit(), 2);
+    }
+
+    #[test]
+    fn test_can_start_respects_resources() {
+        let controller = ConcurrencyController::new(SchedulerConfig::default().with_max_tasks(4));
+        let idle = SystemResources {
+            load_per_cpu: 0.2,
+            available_memory_mb: 8192,
+        };
+        let busy = SystemResources {
+            load_per_cpu: 2.5,
+            ..idle
+        };
+        let low_memory = SystemResources {
+            available_memory_mb: 100,
+            ..idle
+        };
+
+        assert!(controller.can_start(1, Some(idle)));
+        assert!(controller.can_start(1, None));
+        assert!(!controller.can_start(4, Some(idle)));
+        assert!(!controller.can_start(1, Some(busy)));
+        assert!(!controller.can_start(1, Some(low_memory)));
+        // Never stalls
+        assert!(controller.can_start(0, Some(busy)));
+    }
+
+    #[test]
+    fn test_parse_proc() {
+        let resources = SystemResources::parse(
+            "3.00 2.50 2.00 2/345 6789\n",
+            "MemTotal:       16384000 kB\nMemFree:  1000 kB\nMemAvailable:    4194304 kB\n",
+            4,
+        )
+        .unwrap();
+        assert_eq!(resources.load_per_cpu, 0.75);
+        assert_eq!(resources.available_memory_mb, 4096);
+        assert!(SystemResources::parse("", "", 4).is_none());
+    }
+}
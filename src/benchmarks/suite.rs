@@ -0,0 +1,846 @@
+//! Repo-local task suites
+//!
+//! A task suite is a TOML file describing benchmark tasks taken from a team's
+//! own codebase, typically historical bugs. Each task gives the agent a prompt
+//! in a fresh workspace (a checkout of a git repository or a copy of a fixture
+//! directory), runs optional setup commands, and judges the result with
+//! verification commands.
+//!
+//! Suites run through the orchestrator like any other benchmark
+//! (`toad eval --suite path/to/suite.toml`, or the `suite:<path>` benchmark
+//! name).
+//!
+//! # Format
+//!
+//! ```toml
+//! name = "toad-regressions"
+//! version = "2026-10"              # Optional
+//! repo = "../.."                   # Default repository for tasks without one
+//! setup = ["cargo fetch"]          # Run before each task's own setup
+//! command_timeout_secs = 600       # Per setup/verify command (default: 600)
+//!
+//! [[task]]
+//! id = "issue-412"
+//! prompt = "Scrolling past the last row of the board panics. Fix it."
+//! rev = "3f2c1ab"                  # Commit to check out (repository tasks only)
+//! verify = [
+//!     "cargo test --lib board::tests::test_scroll_past_end",
+//!     { command = "cargo test --lib -- --ignored flaky", expect = "fail" },
+//!     { command = "cargo run -- --version", stdout_contains = "toad" },
+//! ]
+//!
+//! [[task]]
+//! id = "parser-fixture"
+//! prompt = "Make parse_duration accept '1h30m'."
+//! fixture = "fixtures/parser"      # Directory copied into the workspace
+//! setup = ["git init --quiet"]
+//! verify = ["python3 -m pytest -q"]
+//! ```
+//!
+//! Relative paths resolve against the directory of the suite file. A task uses
+//! its own `fixture` or `repo`, falling back to the suite's `repo`. Repositories
+//! are cloned, so uncommitted changes are not part of the workspace.
+//!
+//! A verification check passes when the command exits as expected (`pass`:
+//! status 0, `fail`: non-zero) and, if given, its stdout contains
+//! `stdout_contains`. A task is solved when every check passes.
+//!
+//! Commands run with `sh -c` in the workspace and inherit the environment. The
+//! agent's tools work in the workspace too (see
+//! [`Workdir`](crate::ai::tools::Workdir)): its commands run there and its file
+//! tools resolve relative paths against it, so the agent never touches the
+//! repository the workspace was cloned from, and tasks can run concurrently.
+
+use crate::ai::agent::Agent;
+use crate::ai::evaluation::{self, Complexity, TaskResult};
+use crate::ai::llm::LLMClient;
+use crate::ai::metrics::MetricsCollector;
+use crate::benchmarks::sandbox::{TestVerdict, Verdict};
+use crate::benchmarks::workspace::{CommandError, Workspace, run_shell};
+use crate::benchmarks::{BenchmarkExecutor, BenchmarkMetadata, ExecutionContext, Task, codegen};
+use crate::config::ToadConfig;
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Time limit of one setup or verification command, unless the suite sets one
+pub const DEFAULT_COMMAND_TIMEOUT_SECS: u64 = 600;
+
+/// Expected exit status of a verification command
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Expect {
+    /// Exit status 0
+    #[default]
+    Pass,
+    /// Non-zero exit status
+    Fail,
+}
+
+/// Verification command of a task
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "CheckSpec")]
+pub struct Check {
+    /// Shell command, run in the workspace
+    pub command: String,
+
+    /// Expected exit status
+    #[serde(default)]
+    pub expect: Expect,
+
+    /// Text the command's stdout must contain
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stdout_contains: Option<String>,
+}
+
+/// A check as written in the suite: a bare command or a table
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum CheckSpec {
+    Command(String),
+    Table {
+        command: String,
+        #[serde(default)]
+        expect: Expect,
+        #[serde(default)]
+        stdout_contains: Option<String>,
+    },
+}
+
+impl From<CheckSpec> for Check {
+    fn from(spec: CheckSpec) -> Self {
+        match spec {
+            CheckSpec::Command(command) => Check {
+                command,
+                expect: Expect::Pass,
+                stdout_contains: None,
+            },
+            CheckSpec::Table {
+                command,
+                expect,
+                stdout_contains,
+            } => Check {
+                command,
+                expect,
+                stdout_contains,
+            },
+        }
+    }
+}
+
+/// One task of a suite
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SuiteTask {
+    /// Unique task identifier
+    pub id: String,
+
+    /// Instructions for the agent
+    pub prompt: String,
+
+    /// Git repository to check out into the workspace
+    #[serde(default)]
+    pub repo: Option<PathBuf>,
+
+    /// Commit, branch or tag to check out (default: the repository's HEAD)
+    #[serde(default)]
+    pub rev: Option<String>,
+
+    /// Directory to copy into the workspace
+    #[serde(default)]
+    pub fixture: Option<PathBuf>,
+
+    /// Commands run in the workspace before the agent starts
+    #[serde(default)]
+    pub setup: Vec<String>,
+
+    /// Checks that decide whether the task is solved
+    #[serde(default)]
+    pub verify: Vec<Check>,
+}
+
+/// A suite of repo-local tasks
+///
+/// After [`load`](TaskSuite::load), every task has its source (`repo` or
+/// `fixture`) and full setup command list resolved.
+///
+/// # Examples
+///
+/// ```
+/// use toad::benchmarks::suite::TaskSuite;
+///
+/// let dir = tempfile::tempdir().unwrap();
+/// std::fs::create_dir(dir.path().join("fixture")).unwrap();
+/// let suite = TaskSuite::from_toml(
+///     r#"
+///     name = "demo"
+///
+///     [[task]]
+///     id = "add-readme"
+///     prompt = "Add a README."
+///     fixture = "fixture"
+///     verify = ["test -f README.md"]
+///     "#,
+///     dir.path(),
+/// )
+/// .unwrap();
+///
+/// assert_eq!(suite.tasks[0].fixture, Some(dir.path().join("fixture")));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TaskSuite {
+    /// Suite name, reported as the benchmark name
+    pub name: String,
+
+    /// Suite version
+    #[serde(default)]
+    pub version: Option<String>,
+
+    /// Repository of tasks that name neither a repository nor a fixture
+    #[serde(default)]
+    pub repo: Option<PathBuf>,
+
+    /// Commands run before each task's own setup commands
+    #[serde(default)]
+    pub setup: Vec<String>,
+
+    /// Time limit of one setup or verification command
+    #[serde(default)]
+    pub command_timeout_secs: Option<u64>,
+
+    /// Tasks, in suite order
+    #[serde(rename = "task", default)]
+    pub tasks: Vec<SuiteTask>,
+}
+
+impl TaskSuite {
+    /// Read and validate the suite file at `path`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file can't be read or parsed, or the suite is
+    /// invalid (see [`from_toml`](TaskSuite::from_toml)).
+    pub fn load(path: &Path) -> Result<Self> {
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read task suite {}", path.display()))?;
+        let base_dir = path.parent().unwrap_or_else(|| Path::new("."));
+        Self::from_toml(&text, base_dir)
+            .with_context(|| format!("Invalid task suite {}", path.display()))
+    }
+
+    /// Parse a suite, resolving relative paths against `base_dir`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the TOML is malformed, the suite has no tasks, a
+    /// task ID repeats, a task has no verification checks, a task has both or
+    /// neither of a repository and a fixture, `rev` is set on a fixture task,
+    /// or a repository or fixture path does not exist.
+    pub fn from_toml(text: &str, base_dir: &Path) -> Result<Self> {
+        let mut suite: TaskSuite = toml::from_str(text).context("Failed to parse task suite")?;
+        suite.resolve(base_dir)?;
+        Ok(suite)
+    }
+
+    fn resolve(&mut self, base_dir: &Path) -> Result<()> {
+        if self.tasks.is_empty() {
+            anyhow::bail!("Suite '{}' has no tasks", self.name);
+        }
+        let default_repo = self.repo.as_ref().map(|p| base_dir.join(p));
+
+        let mut ids = HashSet::new();
+        for task in &mut self.tasks {
+            if !ids.insert(task.id.clone()) {
+                anyhow::bail!("Duplicate task ID '{}'", task.id);
+            }
+            if task.verify.is_empty() {
+                anyhow::bail!("Task '{}' has no verification checks", task.id);
+            }
+
+            match (&task.repo, &task.fixture) {
+                (Some(_), Some(_)) => {
+                    anyhow::bail!("Task '{}' sets both repo and fixture", task.id)
+                }
+                (None, None) => match &default_repo {
+                    Some(repo) => task.repo = Some(repo.clone()),
+                    None => anyhow::bail!(
+                        "Task '{}' needs a repo or fixture (the suite sets no default repo)",
+                        task.id
+                    ),
+                },
+                (Some(repo), None) => task.repo = Some(base_dir.join(repo)),
+                (None, Some(fixture)) => {
+                    if task.rev.is_some() {
+                        anyhow::bail!("Task '{}' sets rev on a fixture", task.id);
+                    }
+                    task.fixture = Some(base_dir.join(fixture));
+                }
+            }
+            let source = task.repo.as_ref().or(task.fixture.as_ref());
+            if let Some(source) = source.filter(|p| !p.exists()) {
+                anyhow::bail!("Task '{}': {} does not exist", task.id, source.display());
+            }
+
+            task.setup = self.setup.iter().chain(&task.setup).cloned().collect();
+        }
+        Ok(())
+    }
+
+    /// Time limit of one setup or verification command
+    pub fn command_timeout(&self) -> Duration {
+        Duration::from_secs(
+            self.command_timeout_secs
+                .unwrap_or(DEFAULT_COMMAND_TIMEOUT_SECS),
+        )
+    }
+
+    /// Task with the given ID
+    pub fn task(&self, id: &str) -> Option<&SuiteTask> {
+        self.tasks.iter().find(|t| t.id == id)
+    }
+}
+
+/// Run a verification check in `dir`
+async fn run_check(check: &Check, dir: &Path, timeout: Duration) -> TestVerdict {
+    let start = Instant::now();
+    let output = match run_shell(&check.command, dir, timeout).await {
+        Ok(output) => output,
+        Err(CommandError::TimedOut) => {
+            return TestVerdict {
+                verdict: Verdict::TimeLimitExceeded,
+                duration_ms: start.elapsed().as_millis() as u64,
+                detail: Some(format!(
+                    "`{}` exceeded {}s",
+                    check.command,
+                    timeout.as_secs()
+                )),
+            };
+        }
+        Err(CommandError::Spawn(e)) => {
+            return TestVerdict {
+                verdict: Verdict::RuntimeError,
+                duration_ms: 0,
+                detail: Some(format!("`{}` could not start: {}", check.command, e)),
+            };
+        }
+    };
+
+    let status_ok = match check.expect {
+        Expect::Pass => output.success(),
+        Expect::Fail => !output.success(),
+    };
+    let detail = if !status_ok {
+        Some(match check.expect {
+            Expect::Pass => format!(
+                "`{}` failed with {}",
+                check.command,
+                output.failure_detail()
+            ),
+            Expect::Fail => format!("`{}` passed but was expected to fail", check.command),
+        })
+    } else {
+        check
+            .stdout_contains
+            .as_ref()
+            .filter(|text| !output.stdout.contains(text.as_str()))
+            .map(|text| format!("`{}` output lacks {:?}", check.command, text))
+    };
+
+    TestVerdict {
+        verdict: if detail.is_none() {
+            Verdict::Accepted
+        } else {
+            Verdict::WrongAnswer
+        },
+        duration_ms: output.duration.as_millis() as u64,
+        detail,
+    }
+}
+
+/// Check out or copy the source of `task` into a new workspace
+async fn checkout(task: &SuiteTask, timeout: Duration) -> Result<Workspace> {
+    let workspace = Workspace::create("suite")?;
+    if let Some(fixture) = &task.fixture {
+        workspace.copy_from(fixture)?;
+    } else if let Some(repo) = &task.repo {
+        workspace
+            .clone(&repo.display().to_string(), task.rev.as_deref(), timeout)
+            .await?;
+    }
+    Ok(workspace)
+}
+
+/// Executor for a repo-local task suite
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// let mut executor = SuiteExecutor::new("benchmarks/regressions.toml");
+/// executor.setup().await?;
+///
+/// for task in executor.tasks() {
+///     let result = executor.run_task(&task, &ExecutionContext::default()).await;
+///     println!("{}: solved={}", task.id, result.solved);
+/// }
+/// ```
+pub struct SuiteExecutor {
+    /// Benchmark metadata (name and task count set by setup())
+    metadata: BenchmarkMetadata,
+
+    /// Suite file
+    path: PathBuf,
+
+    /// Loaded suite (populated by setup())
+    suite: Option<TaskSuite>,
+
+    /// Model client (None: Anthropic client from the environment)
+    client: Option<Arc<dyn LLMClient>>,
+}
+
+impl SuiteExecutor {
+    /// Create an executor for the suite file at `path`
+    ///
+    /// The suite is read by [`setup`](BenchmarkExecutor::setup); until then
+    /// the metadata is named after the file and has no tasks.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use toad::benchmarks::BenchmarkExecutor;
+    /// use toad::benchmarks::suite::SuiteExecutor;
+    ///
+    /// let executor = SuiteExecutor::new("suites/regressions.toml");
+    /// assert_eq!(executor.get_metadata().name, "regressions");
+    /// assert_eq!(executor.get_metadata().total_tasks, 0);
+    /// ```
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+        let name = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "task-suite".to_string());
+        Self {
+            metadata: BenchmarkMetadata {
+                name,
+                version: "local".to_string(),
+                total_tasks: 0,
+                dataset_url: None,
+                license: None,
+                contamination_risk: "LOW".to_string(), // Private codebase
+            },
+            path,
+            suite: None,
+            client: None,
+        }
+    }
+
+    /// Run the agent with `client` instead of an Anthropic client
+    pub fn with_client(mut self, client: Arc<dyn LLMClient>) -> Self {
+        self.client = Some(client);
+        self
+    }
+
+    /// Loaded suite
+    pub fn suite(&self) -> Option<&TaskSuite> {
+        self.suite.as_ref()
+    }
+
+    /// Run the agent on `task` in `workspace` and judge the result
+    async fn attempt(
+        &self,
+        task: &SuiteTask,
+        timeout: Duration,
+        ctx: &ExecutionContext,
+        metrics: &mut MetricsCollector,
+    ) -> std::result::Result<Vec<TestVerdict>, String> {
+        let workspace = checkout(task, timeout)
+            .await
+            .map_err(|e| format!("Workspace setup failed: {:#}", e))?;
+        for command in &task.setup {
+            workspace
+                .run_step(command, &workspace.path, timeout)
+                .await
+                .map_err(|e| format!("Setup failed: {:#}", e))?;
+        }
+
+        let client =
+            codegen::resolve_client(self.client.as_ref(), ctx).map_err(|e| format!("{:#}", e))?;
+        let config =
+            serde_json::from_value::<ToadConfig>(ctx.system_config.clone()).unwrap_or_default();
+        let tool_registry = workspace.tool_registry(&config.features);
+        let agent =
+            Agent::new(Box::new(client), tool_registry).with_max_steps(ctx.max_steps as u32);
+
+        let agent_task = agent_task(task, &workspace.path);
+        match tokio::time::timeout(ctx.timeout, agent.execute_task(&agent_task, metrics)).await {
+            // Checks decide the outcome even when the agent gives up early
+            Ok(Ok(_)) => {}
+            Ok(Err(e)) => return Err(format!("Agent execution failed: {}", e)),
+            Err(_) => {
+                return Err(format!(
+                    "Task exceeded timeout of {}s",
+                    ctx.timeout.as_secs()
+                ));
+            }
+        }
+
+        let mut verdicts = Vec::with_capacity(task.verify.len());
+        for check in &task.verify {
+            verdicts.push(run_check(check, &workspace.path, timeout).await);
+        }
+        Ok(verdicts)
+    }
+}
+
+/// Agent task for a suite task checked out in `workspace`
+fn agent_task(task: &SuiteTask, workspace: &Path) -> evaluation::Task {
+    evaluation::Task {
+        id: task.id.clone(),
+        repo: task
+            .repo
+            .as_ref()
+            .or(task.fixture.as_ref())
+            .map(|p| p.display().to_string())
+            .unwrap_or_default(),
+        base_commit: task.rev.clone().unwrap_or_default(),
+        problem_statement: task.prompt.clone(),
+        hints: Some(format!(
+            "The code is checked out at {}, the working directory of your tools. \
+             Paths outside it are refused.",
+            workspace.display()
+        )),
+        test_patch: String::new(),
+        files_to_modify: Vec::new(),
+        solution_patch: None,
+        complexity: Complexity::Medium,
+        metadata: HashMap::new(),
+    }
+}
+
+#[async_trait]
+impl BenchmarkExecutor for SuiteExecutor {
+    /// Load and validate the suite file
+    ///
+    /// After setup(), the metadata carries the suite's name, version and task
+    /// count.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the suite file is missing or invalid.
+    async fn setup(&mut self) -> Result<()> {
+        let suite = TaskSuite::load(&self.path)?;
+        tracing::info!(
+            "Loaded task suite '{}' ({} tasks) from {}",
+            suite.name,
+            suite.tasks.len(),
+            self.path.display()
+        );
+        self.metadata.name = suite.name.clone();
+        if let Some(version) = &suite.version {
+            self.metadata.version = version.clone();
+        }
+        self.metadata.total_tasks = suite.tasks.len();
+        self.suite = Some(suite);
+        Ok(())
+    }
+
+    /// Run the agent in a fresh workspace and run the task's checks
+    ///
+    /// Each check is recorded as a verdict (`AC` when it passes, `WA` when
+    /// the exit status or output is wrong, `TLE` on timeout); the task is
+    /// solved when all checks pass. Failing to prepare the workspace or a
+    /// failing setup command fails the task without running the agent.
+    async fn run_task(&self, task: &Task, ctx: &ExecutionContext) -> TaskResult {
+        let mut result = TaskResult::new(task.id.clone());
+        let Some(suite) = self.suite.as_ref() else {
+            result.mark_failed("Task suite not loaded (call setup() first)".to_string());
+            return result;
+        };
+        let Some(suite_task) = suite.task(&task.id) else {
+            result.mark_failed(format!("Task {} not found in suite", task.id));
+            return result;
+        };
+
+        let mut metrics = MetricsCollector::new();
+        metrics.start();
+        match self
+            .attempt(suite_task, suite.command_timeout(), ctx, &mut metrics)
+            .await
+        {
+            Ok(verdicts) => codegen::record_verdicts(&mut result, &verdicts),
+            Err(error) => result.mark_failed(error),
+        }
+        codegen::apply_metrics(&mut result, &mut metrics);
+
+        tracing::info!(
+            "Task {} complete: solved={}, cost=${:.4}",
+            task.id,
+            result.solved,
+            result.cost_usd
+        );
+        result
+    }
+
+    /// Nothing to clean up (workspaces are removed after each task)
+    async fn cleanup(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    fn get_metadata(&self) -> &BenchmarkMetadata {
+        &self.metadata
+    }
+
+    fn tasks(&self) -> Vec<Task> {
+        let Some(suite) = &self.suite else {
+            return Vec::new();
+        };
+        suite
+            .tasks
+            .iter()
+            .map(|t| {
+                let mut metadata = HashMap::new();
+                if let Some(repo) = &t.repo {
+                    metadata.insert("repo".to_string(), serde_json::json!(repo));
+                }
+                if let Some(rev) = &t.rev {
+                    metadata.insert("rev".to_string(), serde_json::json!(rev));
+                }
+                if let Some(fixture) = &t.fixture {
+                    metadata.insert("fixture".to_string(), serde_json::json!(fixture));
+                }
+                metadata.insert("setup".to_string(), serde_json::json!(t.setup));
+                metadata.insert("verify".to_string(), serde_json::json!(t.verify));
+                Task {
+                    id: t.id.clone(),
+                    description: t.prompt.clone(),
+                    expected_output: Some(format!("{} verification checks pass", t.verify.len())),
+                    metadata,
+                }
+            })
+            .collect()
+    }
+
+    /// Every task works in its own workspace
+    fn supports_concurrent_tasks(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai::llm::mock::MockResponseBuilder;
+    use tempfile::TempDir;
+
+    fn write_suite(dir: &TempDir, verify: &str) -> PathBuf {
+        let fixture = dir.path().join("fixture");
+        std::fs::create_dir_all(fixture.join("src")).unwrap();
+        std::fs::write(fixture.join("src/lib.txt"), "buggy\n").unwrap();
+
+        let path = dir.path().join("regressions.toml");
+        let suite = format!(
+            r#"
+            name = "regressions"
+            version = "1"
+            setup = ["touch prepared"]
+
+            [[task]]
+            id = "fix-lib"
+            prompt = "Fix src/lib.txt"
+            fixture = "fixture"
+            verify = {}
+            "#,
+            verify
+        );
+        std::fs::write(&path, suite).unwrap();
+        path
+    }
+
+    async fn run(path: &Path) -> TaskResult {
+        run_with(path, MockResponseBuilder::new().with_text("Done.")).await
+    }
+
+    async fn run_with(path: &Path, responses: MockResponseBuilder) -> TaskResult {
+        let client = responses.build();
+        let mut executor = SuiteExecutor::new(path).with_client(Arc::new(client));
+        executor.setup().await.unwrap();
+        let task = executor.tasks().remove(0);
+        executor.run_task(&task, &ExecutionContext::default()).await
+    }
+
+    #[test]
+    fn test_parse_resolves_defaults() {
+        let dir = TempDir::new().unwrap();
+        std::fs::create_dir(dir.path().join("fixture")).unwrap();
+        let suite = TaskSuite::from_toml(
+            r#"
+            name = "demo"
+            repo = "."
+            setup = ["cargo fetch"]
+            command_timeout_secs = 30
+
+            [[task]]
+            id = "a"
+            prompt = "Fix a"
+            rev = "abc123"
+            setup = ["make"]
+            verify = ["make test", { command = "make lint", expect = "fail" }]
+
+            [[task]]
+            id = "b"
+            prompt = "Fix b"
+            fixture = "fixture"
+            verify = [{ command = "cat out", stdout_contains = "ok" }]
+            "#,
+            dir.path(),
+        )
+        .unwrap();
+
+        assert_eq!(suite.command_timeout(), Duration::from_secs(30));
+        let a = suite.task("a").unwrap();
+        assert_eq!(a.repo, Some(dir.path().join(".")));
+        assert_eq!(a.setup, vec!["cargo fetch", "make"]);
+        assert_eq!(a.verify[0].expect, Expect::Pass);
+        assert_eq!(a.verify[1].expect, Expect::Fail);
+
+        let b = suite.task("b").unwrap();
+        assert_eq!(b.repo, None);
+        assert_eq!(b.fixture, Some(dir.path().join("fixture")));
+        assert_eq!(b.verify[0].stdout_contains.as_deref(), Some("ok"));
+    }
+
+    #[test]
+    fn test_parse_rejects_invalid_suites() {
+        let dir = TempDir::new().unwrap();
+        let cases = [
+            ("name = \"x\"", "no tasks"),
+            (
+                "name = \"x\"\n[[task]]\nid = \"a\"\nprompt = \"p\"\nverify = [\"true\"]",
+                "needs a repo or fixture",
+            ),
+            (
+                "name = \"x\"\nrepo = \".\"\n[[task]]\nid = \"a\"\nprompt = \"p\"",
+                "no verification checks",
+            ),
+            (
+                "name = \"x\"\nrepo = \".\"\n[[task]]\nid = \"a\"\nprompt = \"p\"\nverify = [\"true\"]\n\
+                 [[task]]\nid = \"a\"\nprompt = \"q\"\nverify = [\"true\"]",
+                "Duplicate task ID",
+            ),
+            (
+                "name = \"x\"\n[[task]]\nid = \"a\"\nprompt = \"p\"\nfixture = \"missing\"\nverify = [\"true\"]",
+                "does not exist",
+            ),
+        ];
+        for (text, expected) in cases {
+            let err = TaskSuite::from_toml(text, dir.path()).unwrap_err();
+            assert!(
+                format!("{:#}", err).contains(expected),
+                "{}: {:#}",
+                expected,
+                err
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_run_task_passes_when_checks_pass() {
+        let dir = TempDir::new().unwrap();
+        let path = write_suite(
+            &dir,
+            r#"["test -f prepared", { command = "cat src/lib.txt", stdout_contains = "buggy" }, { command = "test -f missing", expect = "fail" }]"#,
+        );
+
+        let result = run(&path).await;
+        assert!(result.solved, "{:?}", result.error);
+        let signals = result.quality_signals.unwrap();
+        assert_eq!(signals["tests_total"], 3.0);
+        assert_eq!(signals["tests_passed"], 3.0);
+        assert_eq!(result.api_calls, 1);
+
+        // The fixture is copied, never modified in place
+        assert!(!dir.path().join("fixture/prepared").exists());
+    }
+
+    #[tokio::test]
+    async fn test_agent_tools_work_in_workspace() {
+        let dir = TempDir::new().unwrap();
+        let path = write_suite(
+            &dir,
+            r#"["grep -q fixed src/lib.txt", "grep -q ran notes.txt"]"#,
+        );
+        let responses = MockResponseBuilder::new()
+            .with_tool(
+                "write",
+                serde_json::json!({"path": "src/lib.txt", "content": "fixed\n"}),
+            )
+            .with_tool(
+                "bash",
+                serde_json::json!({"command": "echo ran > notes.txt"}),
+            )
+            .with_text("Done.");
+
+        let result = run_with(&path, responses).await;
+        assert!(result.solved, "{:?}", result.error);
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("fixture/src/lib.txt")).unwrap(),
+            "buggy\n"
+        );
+        assert!(!Path::new("src/lib.txt").exists());
+        assert!(!Path::new("notes.txt").exists());
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_tasks_keep_separate_workspaces() {
+        let dir = TempDir::new().unwrap();
+        let rendezvous = dir.path().display();
+        let path = write_suite(
+            &dir,
+            &format!(
+                r#"["grep -qx $(cat agent) src/lib.txt", "test -f {0}/started-alpha", "test -f {0}/started-beta"]"#,
+                rendezvous
+            ),
+        );
+        assert!(SuiteExecutor::new(&path).supports_concurrent_tasks());
+
+        // Each agent writes its own answer, then waits until the other agent
+        // has started, which only happens if both tasks run at once
+        let agent = |name: &str, other: &str| {
+            let responses = MockResponseBuilder::new()
+                .with_tool(
+                    "write",
+                    serde_json::json!({"path": "src/lib.txt", "content": format!("{}\n", name)}),
+                )
+                .with_tool(
+                    "bash",
+                    serde_json::json!({"command": format!(
+                        "echo {name} > agent; touch {rendezvous}/started-{name}; \
+                         for i in $(seq 100); do test -f {rendezvous}/started-{other} && break; sleep 0.05; done"
+                    )}),
+                )
+                .with_text("Done.");
+            run_with(&path, responses)
+        };
+
+        let (alpha, beta) = tokio::join!(agent("alpha", "beta"), agent("beta", "alpha"));
+        assert!(alpha.solved, "{:?}", alpha.error);
+        assert!(beta.solved, "{:?}", beta.error);
+    }
+
+    #[tokio::test]
+    async fn test_run_task_reports_failing_check() {
+        let dir = TempDir::new().unwrap();
+        let path = write_suite(&dir, r#"["true", "grep -q fixed src/lib.txt"]"#);
+
+        let result = run(&path).await;
+        assert!(!result.solved);
+        let error = result.error.unwrap();
+        assert!(error.contains("1/2 tests passed"), "{}", error);
+        assert!(error.contains("grep -q fixed src/lib.txt"), "{}", error);
+    }
+}
@@ -37,15 +37,19 @@
 //! ```
 
 use crate::ai::agent::Agent;
-use crate::ai::evaluation::{DatasetManager, DatasetSource, TaskResult};
+use crate::ai::evaluation::{self, DatasetManager, DatasetSource, TaskResult};
 use crate::ai::llm::{AnthropicClient, get_api_key};
 use crate::ai::metrics::MetricsCollector;
-use crate::ai::tools::ToolRegistry;
+use crate::benchmarks::workspace::Workspace;
 use crate::benchmarks::{BenchmarkExecutor, BenchmarkMetadata, ExecutionContext, Task};
 use crate::config::ToadConfig;
 use anyhow::{Context, Result};
 use async_trait::async_trait;
-use std::time::Instant;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+/// Time limit for cloning a task's repository and checking out its base commit
+const CHECKOUT_TIMEOUT: Duration = Duration::from_secs(600);
 
 /// SWE-bench benchmark executor
 ///
@@ -143,9 +147,6 @@ impl SweBenchExecutor {
     ///
     /// Maps SWE-bench-specific fields into the flexible `metadata` HashMap
     /// for cross-benchmark compatibility.
-    ///
-    /// Note: Currently unused but will be needed in Phase 5 for orchestrator
-    #[allow(dead_code)]
     fn convert_task(swebench_task: &crate::ai::evaluation::Task) -> Task {
         use std::collections::HashMap;
 
@@ -244,7 +245,8 @@ impl BenchmarkExecutor for SweBenchExecutor {
     ///
     /// Runs the task using the existing M0 agent infrastructure. This method:
     /// 1. Creates LLM client (Anthropic Claude)
-    /// 2. Creates tool registry with configured features
+    /// 2. Clones the task's repository at its base commit into a fresh
+    ///    workspace and creates a tool registry working there
     /// 3. Creates agent with max_steps from ExecutionContext
     /// 4. Executes task and collects metrics
     /// 5. Returns TaskResult with full metrics
@@ -308,12 +310,23 @@ impl BenchmarkExecutor for SweBenchExecutor {
             llm_client = llm_client.with_prompt_caching(true);
         }
 
-        // Create tool registry with feature flags
-        let tool_registry = if config.features.smart_test_selection {
-            ToolRegistry::m2_with_features(&config.features)
-        } else {
-            ToolRegistry::m1_with_features(&config.features)
+        // Sample at the configured temperature (pass@k mode)
+        if let Some(temperature) = ctx.sample.and_then(|s| s.temperature) {
+            llm_client = llm_client.with_temperature(temperature);
+        }
+
+        // Check out the repository in a workspace of its own and root the
+        // agent's tools there
+        let workspace = match checkout(swebench_task).await {
+            Ok(workspace) => workspace,
+            Err(e) => {
+                let mut result = TaskResult::new(task.id.clone());
+                result.mark_failed(format!("Workspace setup failed: {:#}", e));
+                return result;
+            }
         };
+        let tool_registry = workspace.tool_registry(&config.features);
+        let agent_task = in_workspace(swebench_task, &workspace.path);
 
         // Create agent with max_steps from ExecutionContext
         let agent = Agent::new(Box::new(llm_client), tool_registry)
@@ -326,7 +339,7 @@ impl BenchmarkExecutor for SweBenchExecutor {
         let start = Instant::now();
         let agent_result = match tokio::time::timeout(
             ctx.timeout,
-            agent.execute_task(swebench_task, &mut metrics_collector),
+            agent.execute_task(&agent_task, &mut metrics_collector),
         )
         .await
         {
@@ -353,6 +366,7 @@ impl BenchmarkExecutor for SweBenchExecutor {
         // Build task result from agent result and metrics
         let final_metrics = metrics_collector.finish();
         let mut result = TaskResult::new(task.id.clone());
+        result.complexity = Some(swebench_task.complexity);
 
         result.duration_ms = final_metrics.duration_ms;
         result.cost_usd = final_metrics.cost_usd;
@@ -416,6 +430,53 @@ impl BenchmarkExecutor for SweBenchExecutor {
     fn get_metadata(&self) -> &BenchmarkMetadata {
         &self.metadata
     }
+
+    /// Loaded SWE-bench tasks converted to generic tasks
+    fn tasks(&self) -> Vec<Task> {
+        self.tasks.iter().map(Self::convert_task).collect()
+    }
+
+    /// Every task works in its own checkout
+    fn supports_concurrent_tasks(&self) -> bool {
+        true
+    }
+}
+
+/// Clone the repository of `task` at its base commit into a new workspace
+async fn checkout(task: &evaluation::Task) -> Result<Workspace> {
+    let workspace = Workspace::create("swebench")?;
+    let rev = Some(task.base_commit.as_str()).filter(|rev| !rev.is_empty());
+    workspace
+        .clone(&clone_url(&task.repo), rev, CHECKOUT_TIMEOUT)
+        .await?;
+    Ok(workspace)
+}
+
+/// Clone URL of a task repository
+///
+/// SWE-bench names repositories `owner/name` on GitHub; URLs and absolute
+/// paths (local datasets) are used as given.
+fn clone_url(repo: &str) -> String {
+    if repo.contains("://") || Path::new(repo).is_absolute() {
+        repo.to_string()
+    } else {
+        format!("https://github.com/{}", repo)
+    }
+}
+
+/// `task` with a hint pointing the agent at its checkout in `workspace`
+fn in_workspace(task: &evaluation::Task, workspace: &Path) -> evaluation::Task {
+    let note = format!(
+        "The repository is checked out at {}, the working directory of your tools. \
+         Paths outside it are refused.",
+        workspace.display()
+    );
+    let mut task = task.clone();
+    task.hints = Some(match task.hints.take().filter(|hints| !hints.is_empty()) {
+        Some(hints) => format!("{}\n\n{}", hints, note),
+        None => note,
+    });
+    task
 }
 
 #[cfg(test)]
@@ -490,6 +551,75 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_clone_url() {
+        assert_eq!(
+            clone_url("django/django"),
+            "https://github.com/django/django"
+        );
+        assert_eq!(
+            clone_url("https://example.com/repo.git"),
+            "https://example.com/repo.git"
+        );
+        assert_eq!(clone_url("/srv/repos/app"), "/srv/repos/app");
+    }
+
+    #[tokio::test]
+    async fn test_checkout_gives_each_task_its_own_copy() {
+        use tempfile::TempDir;
+
+        // Local repository with two commits; tasks start from the first
+        let repo = TempDir::new().unwrap();
+        let git = |args: &[&str]| {
+            let output = std::process::Command::new("git")
+                .args(["-c", "user.name=t", "-c", "user.email=t@t"])
+                .args(args)
+                .current_dir(repo.path())
+                .output()
+                .unwrap();
+            assert!(output.status.success(), "git {:?} failed", args);
+            String::from_utf8_lossy(&output.stdout).trim().to_string()
+        };
+        git(&["init", "--quiet"]);
+        std::fs::write(repo.path().join("lib.py"), "old\n").unwrap();
+        git(&["add", "lib.py"]);
+        git(&["commit", "--quiet", "-m", "first"]);
+        let base_commit = git(&["rev-parse", "HEAD"]);
+        std::fs::write(repo.path().join("lib.py"), "new\n").unwrap();
+        git(&["commit", "--quiet", "-am", "second"]);
+
+        let task = crate::ai::evaluation::Task {
+            repo: repo.path().display().to_string(),
+            base_commit,
+            ..crate::ai::evaluation::Task::example()
+        };
+        let first = checkout(&task).await.unwrap();
+        let second = checkout(&task).await.unwrap();
+        assert_ne!(first.path, second.path);
+        assert_eq!(
+            std::fs::read_to_string(first.path.join("lib.py")).unwrap(),
+            "old\n"
+        );
+
+        // Tasks don't see each other's edits, and the source is untouched
+        std::fs::write(first.path.join("lib.py"), "edited\n").unwrap();
+        assert_eq!(
+            std::fs::read_to_string(second.path.join("lib.py")).unwrap(),
+            "old\n"
+        );
+        assert_eq!(
+            std::fs::read_to_string(repo.path().join("lib.py")).unwrap(),
+            "new\n"
+        );
+
+        let hints = in_workspace(&task, &first.path).hints.unwrap();
+        assert!(hints.contains(&first.path.display().to_string()));
+
+        let path = first.path.clone();
+        drop(first);
+        assert!(!path.exists());
+    }
+
     #[tokio::test]
     #[ignore] // Requires network access to download dataset
     async fn test_swebench_executor_setup() {
@@ -196,6 +196,7 @@ pub struct BenchmarkMetadata {
 ///         "temperature": 1.0,
 ///     }),
 ///     sandbox_config: None,
+///     sample: None,
 /// };
 /// ```
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -225,6 +226,12 @@ pub struct ExecutionContext {
     /// For benchmarks that require isolated execution environments (e.g., SWE-bench
     /// running in Docker). Format is benchmark-specific.
     pub sandbox_config: Option<Value>,
+
+    /// Which sample of the task this run is, in sampling (pass@k) mode
+    ///
+    /// Executors that create their own model client apply its temperature.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sample: Option<crate::ai::evaluation::sampling::Sample>,
 }
 
 impl Default for ExecutionContext {
@@ -234,6 +241,7 @@ impl Default for ExecutionContext {
             max_steps: 25,
             system_config: serde_json::json!({}),
             sandbox_config: None,
+            sample: None,
         }
     }
 }
@@ -247,7 +255,8 @@ impl Default for ExecutionContext {
 ///
 /// 1. `EvaluationStarted` - Orchestrator begins
 /// 2. `BenchmarkStarted` - For each benchmark
-/// 3. `TaskCompleted` - For each task (many events)
+/// 3. `TaskStarted` / `TaskCompleted` - For each task (many events, interleaved
+///    when tasks run concurrently)
 /// 4. `BenchmarkCompleted` - When benchmark finishes
 /// 5. `EvaluationCompleted` - Orchestrator done
 ///
@@ -301,7 +310,23 @@ pub enum ProgressEvent {
         total_tasks: usize,
     },
 
+    /// A task started running
+    TaskStarted {
+        /// Benchmark this task belongs to
+        benchmark_name: String,
+        /// Task identifier
+        task_id: String,
+        /// Task index (0-based)
+        task_index: usize,
+        /// Total tasks in this benchmark
+        total_tasks: usize,
+        /// Tasks of this benchmark now running, including this one
+        in_flight: usize,
+    },
+
     /// A single task completed (success or failure)
+    ///
+    /// With task-level parallelism, tasks complete out of order.
     TaskCompleted {
         /// Benchmark this task belongs to
         benchmark_name: String,
@@ -317,6 +342,9 @@ pub enum ProgressEvent {
         duration_ms: u64,
         /// API cost in USD
         cost_usd: f64,
+        /// Tasks of this benchmark still running
+        #[serde(default)]
+        in_flight: usize,
     },
 
     /// A benchmark completed all tasks
@@ -413,6 +441,7 @@ mod tests {
         assert_eq!(ctx.max_steps, 25);
         assert_eq!(ctx.system_config, serde_json::json!({}));
         assert!(ctx.sandbox_config.is_none());
+        assert!(ctx.sample.is_none());
 
         // Test custom context
         let custom_ctx = ExecutionContext {
@@ -420,6 +449,7 @@ mod tests {
             max_steps: 50,
             system_config: serde_json::json!({"model": "claude-opus-3"}),
             sandbox_config: Some(serde_json::json!({"docker_image": "python:3.11"})),
+            sample: None,
         };
 
         // Test serialization roundtrip
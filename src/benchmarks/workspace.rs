@@ -0,0 +1,185 @@
+//! Per-task workspaces for executors that run the agent
+//!
+//! Every task gets its own temporary directory, and the agent's tools are
+//! rooted there (see [`Workdir`]): its commands run in the workspace and its
+//! file tools resolve relative paths against it. Tasks therefore never share
+//! files, so agent executors can run several tasks at once.
+
+use crate::ai::tools::{ToolRegistry, Workdir};
+use crate::benchmarks::sandbox::tail;
+use crate::config::FeatureFlags;
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::time::{Duration, Instant};
+use tokio::process::Command;
+
+/// Longest command output excerpt kept in a failure message
+const MAX_DETAIL_CHARS: usize = 500;
+
+/// Output of a shell command
+#[derive(Debug)]
+pub(super) struct CommandOutput {
+    pub(super) stdout: String,
+    pub(super) stderr: String,
+    pub(super) exit_code: Option<i32>,
+    pub(super) duration: Duration,
+}
+
+impl CommandOutput {
+    pub(super) fn success(&self) -> bool {
+        self.exit_code == Some(0)
+    }
+
+    /// Exit status and output excerpt of a failed command
+    pub(super) fn failure_detail(&self) -> String {
+        let status = match self.exit_code {
+            Some(code) => format!("exit code {}", code),
+            None => "killed by signal".to_string(),
+        };
+        let output = if self.stderr.trim().is_empty() {
+            self.stdout.trim()
+        } else {
+            self.stderr.trim()
+        };
+        if output.is_empty() {
+            status
+        } else {
+            format!("{}: {}", status, tail(output, MAX_DETAIL_CHARS))
+        }
+    }
+}
+
+/// Why a shell command produced no output
+pub(super) enum CommandError {
+    TimedOut,
+    Spawn(std::io::Error),
+}
+
+/// Run `command` with `sh -c` in `dir`, killing it after `timeout`
+pub(super) async fn run_shell(
+    command: &str,
+    dir: &Path,
+    timeout: Duration,
+) -> std::result::Result<CommandOutput, CommandError> {
+    let start = Instant::now();
+    let child = Command::new("sh")
+        .arg("-c")
+        .arg(command)
+        .current_dir(dir)
+        .stdin(Stdio::null())
+        .kill_on_drop(true)
+        .output();
+    match tokio::time::timeout(timeout, child).await {
+        Ok(Ok(output)) => Ok(CommandOutput {
+            stdout: String::from_utf8_lossy(&output.stdout).into_owned(),
+            stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+            exit_code: output.status.code(),
+            duration: start.elapsed(),
+        }),
+        Ok(Err(e)) => Err(CommandError::Spawn(e)),
+        Err(_) => Err(CommandError::TimedOut),
+    }
+}
+
+/// Temporary directory a task runs in, removed on drop
+pub(super) struct Workspace {
+    pub(super) path: PathBuf,
+}
+
+impl Workspace {
+    /// Create an empty workspace named after `prefix`
+    pub(super) fn create(prefix: &str) -> Result<Self> {
+        let path = std::env::temp_dir().join(format!("toad-{}-{}", prefix, uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&path)
+            .with_context(|| format!("Failed to create workspace {}", path.display()))?;
+        Ok(Self { path })
+    }
+
+    /// Copy the contents of `fixture` into the workspace
+    pub(super) fn copy_from(&self, fixture: &Path) -> Result<()> {
+        copy_dir(fixture, &self.path)
+            .with_context(|| format!("Failed to copy fixture {}", fixture.display()))
+    }
+
+    /// Clone the git repository `repo` into the workspace, at `rev` if given
+    pub(super) async fn clone(
+        &self,
+        repo: &str,
+        rev: Option<&str>,
+        timeout: Duration,
+    ) -> Result<()> {
+        let clone = format!(
+            "git clone --quiet {} {}",
+            shell_quote(repo),
+            shell_quote(&self.path.display().to_string())
+        );
+        self.run_step(&clone, Path::new("."), timeout)
+            .await
+            .with_context(|| format!("Failed to clone {}", repo))?;
+        if let Some(rev) = rev {
+            let checkout = format!("git checkout --quiet {}", shell_quote(rev));
+            self.run_step(&checkout, &self.path, timeout)
+                .await
+                .with_context(|| format!("Failed to check out {}", rev))?;
+        }
+        Ok(())
+    }
+
+    /// Run a command that must succeed
+    pub(super) async fn run_step(
+        &self,
+        command: &str,
+        dir: &Path,
+        timeout: Duration,
+    ) -> Result<()> {
+        match run_shell(command, dir, timeout).await {
+            Ok(output) if output.success() => Ok(()),
+            Ok(output) => anyhow::bail!("`{}` failed with {}", command, output.failure_detail()),
+            Err(CommandError::TimedOut) => {
+                anyhow::bail!("`{}` exceeded {}s", command, timeout.as_secs())
+            }
+            Err(CommandError::Spawn(e)) => anyhow::bail!("`{}` could not start: {}", command, e),
+        }
+    }
+
+    /// Agent tools working in the workspace
+    pub(super) fn tool_registry(&self, features: &FeatureFlags) -> ToolRegistry {
+        let workdir = Workdir::at(&self.path);
+        if features.smart_test_selection {
+            ToolRegistry::m2_in_workdir(features, workdir)
+        } else {
+            ToolRegistry::m1_in_workdir(features, workdir)
+        }
+    }
+}
+
+impl Drop for Workspace {
+    fn drop(&mut self) {
+        if self.path.exists()
+            && let Err(e) = std::fs::remove_dir_all(&self.path)
+        {
+            tracing::warn!("Failed to remove workspace {}: {}", self.path.display(), e);
+        }
+    }
+}
+
+/// Copy the directory `from` to `to`, recursively
+fn copy_dir(from: &Path, to: &Path) -> std::io::Result<()> {
+    std::fs::create_dir_all(to)?;
+    for entry in std::fs::read_dir(from)? {
+        let entry = entry?;
+        let target = to.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_dir(&entry.path(), &target)?;
+        } else {
+            std::fs::copy(entry.path(), target)?;
+        }
+    }
+    Ok(())
+}
+
+/// Quote `text` as one `sh` word
+fn shell_quote(text: &str) -> String {
+    format!("'{}'", text.replace('\'', r"'\''"))
+}
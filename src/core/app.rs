@@ -7,9 +7,24 @@ use crate::ai::llm::{AnthropicClient, LLMClient, Message};
 use crate::config::Config;
 use crate::core::app_state::{AppScreen, EvaluationState};
 use crate::core::event::Event;
+use crate::infrastructure::automation_executor::AutomationExecutor;
+use crate::infrastructure::board_store::{BoardDatabase, BoardStore};
 use crate::infrastructure::clipboard::Clipboard;
+use crate::infrastructure::cross_window_context::CrossWindowContextManager;
+use crate::infrastructure::filtering_search::LiveFilter;
 use crate::infrastructure::history::History;
+use crate::infrastructure::multiple_views::ViewManager;
+use crate::infrastructure::window_ipc::WindowEndpoint;
 use crate::performance::PerformanceMetrics;
+use crate::ui::screens::editor::EditorScreen;
+use crate::ui::screens::calendar::CalendarScreen;
+use crate::ui::screens::kanban::KanbanScreen;
+use crate::ui::screens::mind_map::MindMapScreen;
+use crate::ui::screens::table_view::TableViewScreen;
+use crate::ui::screens::timeline::TimelineScreen;
+use crate::core::app_card_run::ActiveCardRun;
+use crate::core::app_calendar_sync::CalendarWatch;
+use crate::ui::screens::git::GitScreen;
 use crate::ui::widgets::{
     conversation::ConversationView,
     core::{dialog::ConfirmDialog, help::HelpScreen},
@@ -118,7 +133,7 @@ pub struct App {
     pub(crate) ai_processing: bool,
 
     /// Tick counter for cursor blinking (toggles every 2 ticks = 500ms)
-    tick_count: u32,
+    pub(crate) tick_count: u32,
 
     /// Command history for up/down arrow navigation
     pub(crate) command_history: History,
@@ -135,6 +150,60 @@ pub struct App {
 
     /// Tool execution status panel
     pub(crate) tool_status_panel: ToolStatusPanel,
+
+    /// Git screen state (created when the git screen is first opened)
+    pub(crate) git_screen: Option<GitScreen>,
+
+    /// Editor screen state (created when a file is first opened)
+    pub(crate) editor_screen: Option<EditorScreen>,
+
+    /// Kanban screen state (created when the board is first opened)
+    pub(crate) kanban_screen: Option<KanbanScreen>,
+
+    /// Timeline screen state (created when the timeline is first opened)
+    pub(crate) timeline_screen: Option<TimelineScreen>,
+
+    /// Calendar screen state (created when the calendar is first opened)
+    pub(crate) calendar_screen: Option<CalendarScreen>,
+
+    /// Table screen state (created when the table is first opened)
+    pub(crate) table_screen: Option<TableViewScreen>,
+
+    /// Mind map screen state (created when the mind map is first opened)
+    pub(crate) mind_map_screen: Option<MindMapScreen>,
+
+    /// Card filter shared by the board views (lent to the open view)
+    pub(crate) board_filter: LiveFilter,
+
+    /// Saved settings of each board view
+    pub(crate) board_views: ViewManager,
+
+    /// Agent run on a task card (at most one at a time)
+    pub(crate) card_run: Option<ActiveCardRun>,
+
+    /// Task board database (cards, boards, dependencies, time entries, ...)
+    pub(crate) board: BoardDatabase,
+
+    /// Repo-local store the board is loaded from (None if loading failed)
+    pub(crate) board_store: Option<BoardStore>,
+
+    /// Whether the board changed since it was last saved
+    pub(crate) board_dirty: bool,
+
+    /// Runs the board's automation rules as the board changes
+    pub(crate) automation: AutomationExecutor,
+
+    /// Watched calendar directory (created when the board links one)
+    pub(crate) calendar_watch: Option<CalendarWatch>,
+
+    /// Background read of the commit history for card activity
+    pub(crate) git_activity_task: Option<tokio::task::JoinHandle<()>>,
+
+    /// Clipboard entries and agent contexts shared with other windows
+    pub(crate) window_context: CrossWindowContextManager,
+
+    /// Connection to other TOAD windows (started by the TUI)
+    pub(crate) window_endpoint: Option<WindowEndpoint>,
 }
 
 impl std::fmt::Debug for App {
@@ -171,6 +240,23 @@ impl std::fmt::Debug for App {
             .field("conversation_view", &"<ConversationView>") // Skip for large widget
             .field("ai_processing", &self.ai_processing)
             .field("tick_count", &self.tick_count)
+            .field("git_screen", &self.git_screen.is_some())
+            .field("editor_screen", &self.editor_screen.is_some())
+            .field("kanban_screen", &self.kanban_screen.is_some())
+            .field("timeline_screen", &self.timeline_screen.is_some())
+            .field("calendar_screen", &self.calendar_screen.is_some())
+            .field("table_screen", &self.table_screen.is_some())
+            .field("mind_map_screen", &self.mind_map_screen.is_some())
+            .field("board_filter", &self.board_filter.query())
+            .field("board_views", &self.board_views)
+            .field("card_run", &self.card_run)
+            .field("board_store", &self.board_store)
+            .field("board_dirty", &self.board_dirty)
+            .field("automation", &self.automation)
+            .field("calendar_watch", &self.calendar_watch)
+            .field("git_activity_task", &self.git_activity_task.is_some())
+            .field("window_context", &self.window_context)
+            .field("window_endpoint", &self.window_endpoint)
             .finish()
     }
 }
@@ -241,7 +327,7 @@ impl Default for App {
             tabs.add_tab("Main");
         }
 
-        Self {
+        let mut app = Self {
             screen,
             should_quit: false,
             status_message: if welcome_shown {
@@ -284,7 +370,29 @@ impl Default for App {
             total_cost_usd: 0.0,
             clipboard: Clipboard::new().ok(),
             tool_status_panel: ToolStatusPanel::new(),
-        }
+            git_screen: None,
+            editor_screen: None,
+            kanban_screen: None,
+            timeline_screen: None,
+            calendar_screen: None,
+            table_screen: None,
+            mind_map_screen: None,
+            board_filter: LiveFilter::new(),
+            board_views: ViewManager::new(),
+            card_run: None,
+            board: BoardDatabase::default(),
+            board_store: None,
+            board_dirty: false,
+            automation: AutomationExecutor::new(),
+            calendar_watch: None,
+            git_activity_task: None,
+            window_context: CrossWindowContextManager::new(),
+            window_endpoint: None,
+        };
+
+        // Load the repo-local board database
+        app.load_board();
+        app
     }
 }
 
@@ -322,10 +430,22 @@ impl App {
                     }
                 }
 
+                self.check_editor_external_changes();
+                self.run_automation();
+                self.poll_calendar();
+                self.poll_git_activity();
+                self.autosave_board();
+                self.poll_windows();
+
                 // Toasts are automatically cleaned up during render
                 Ok(())
             }
-            Event::Mouse(_) => Ok(()),
+            Event::Mouse(mouse) => {
+                if self.screen == AppScreen::Kanban {
+                    self.handle_kanban_mouse(mouse);
+                }
+                Ok(())
+            }
 
             // Evaluation events
             Event::StartEvaluation(args) => {
@@ -356,6 +476,7 @@ impl App {
 
                     eval_state.results = Some(results);
                     eval_state.handle = None; // Evaluation is done
+                    eval_state.recent_runs = crate::core::app_evaluation::recent_accuracy();
 
                     self.toast_success(format!(
                         "Evaluation complete: {}/{} solved ({:.1}%)",
@@ -415,6 +536,54 @@ impl App {
                 self.handle_ai_error(error);
                 Ok(())
             }
+
+            // Git screen events
+            Event::GitRefreshed(refresh) => {
+                self.apply_git_refresh(*refresh);
+                Ok(())
+            }
+            Event::GitDiffLoaded { path, staged, diff } => {
+                self.handle_git_diff_loaded(path, staged, diff);
+                Ok(())
+            }
+            Event::GitOperationComplete(message) => {
+                self.handle_git_operation_complete(message);
+                Ok(())
+            }
+            Event::GitCommitComplete(output) => {
+                self.handle_git_commit_complete(output);
+                Ok(())
+            }
+            Event::GitCommitMessageGenerated(message) => {
+                self.handle_git_commit_message_generated(message);
+                Ok(())
+            }
+            Event::GitPullRequestGenerated(description) => {
+                self.handle_git_pull_request_generated(description);
+                Ok(())
+            }
+            Event::GitConflictProposal { path, index, lines } => {
+                self.handle_git_conflict_proposal(path, index, lines);
+                Ok(())
+            }
+            Event::GitError(error) => {
+                self.handle_git_error(error);
+                Ok(())
+            }
+            Event::GitActivityLoaded { history, announce } => {
+                self.handle_git_activity_loaded(*history, announce);
+                Ok(())
+            }
+            Event::GitActivityFailed { error, announce } => {
+                self.handle_git_activity_failed(error, announce);
+                Ok(())
+            }
+
+            // Card run events
+            Event::CardRun(event) => {
+                self.handle_card_run_event(event);
+                Ok(())
+            }
         }
     }
 
@@ -425,6 +594,13 @@ impl App {
             AppScreen::TrustDialog => self.handle_trust_dialog_key(key),
             AppScreen::Main => self.handle_main_key(key),
             AppScreen::Evaluation => self.handle_evaluation_key(key),
+            AppScreen::Git => self.handle_git_key(key),
+            AppScreen::Editor => self.handle_editor_key(key),
+            AppScreen::Kanban => self.handle_kanban_key(key),
+            AppScreen::Timeline => self.handle_timeline_key(key),
+            AppScreen::Calendar => self.handle_calendar_key(key),
+            AppScreen::Table => self.handle_table_key(key),
+            AppScreen::MindMap => self.handle_mind_map_key(key),
         }
     }
 
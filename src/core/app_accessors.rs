@@ -228,6 +228,44 @@ impl App {
         self.evaluation_state.as_mut()
     }
 
+    /// Get the git screen state
+    pub fn git_screen(&self) -> Option<&crate::ui::screens::git::GitScreen> {
+        self.git_screen.as_ref()
+    }
+
+    /// Get mutable git screen state
+    pub fn git_screen_mut(&mut self) -> Option<&mut crate::ui::screens::git::GitScreen> {
+        self.git_screen.as_mut()
+    }
+
+    /// Get the editor screen state
+    pub fn editor_screen(&self) -> Option<&crate::ui::screens::editor::EditorScreen> {
+        self.editor_screen.as_ref()
+    }
+
+    /// Get mutable editor screen state
+    pub fn editor_screen_mut(&mut self) -> Option<&mut crate::ui::screens::editor::EditorScreen> {
+        self.editor_screen.as_mut()
+    }
+
+    /// Get the kanban screen state
+    pub fn kanban_screen(&self) -> Option<&crate::ui::screens::kanban::KanbanScreen> {
+        self.kanban_screen.as_ref()
+    }
+
+    /// Whether the current screen uses the mouse
+    ///
+    /// The terminal only captures the mouse while this is true, so text
+    /// selection keeps working everywhere else.
+    pub fn wants_mouse_capture(&self) -> bool {
+        self.screen == AppScreen::Kanban
+    }
+
+    /// Get the task board database
+    pub fn board(&self) -> &crate::infrastructure::board_store::BoardDatabase {
+        &self.board
+    }
+
     /// Get reference to session state
     pub fn session(&self) -> &SessionState {
         &self.session
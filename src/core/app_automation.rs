@@ -0,0 +1,167 @@
+//! Board automation
+//!
+//! Runs the board's automation rules (see
+//! [`automation_executor`](crate::infrastructure::automation_executor)) as
+//! cards change and reports what they did through toasts and the status bar.
+
+use crate::core::app::App;
+use crate::infrastructure::automation_executor::AutomationReport;
+
+/// Ticks between automation runs (ticks are 250ms)
+const AUTOMATION_TICKS: u32 = 4;
+
+impl App {
+    /// Run automation rules every [`AUTOMATION_TICKS`] ticks
+    pub(crate) fn run_automation(&mut self) {
+        if !self.tick_count.is_multiple_of(AUTOMATION_TICKS) {
+            return;
+        }
+        let report = self.automation.run(&mut self.board);
+        self.handle_automation_report(report);
+    }
+
+    /// Surface the result of an automation run
+    fn handle_automation_report(&mut self, report: AutomationReport) {
+        if report.changed_board() {
+            self.board_dirty = true;
+            self.status_message = format!(
+                "Automation: {} actions, {} cards created",
+                report.executed.len(),
+                report.created_cards.len()
+            );
+        }
+        for notification in report.notifications {
+            self.toast_info(notification);
+        }
+        for error in report.errors {
+            self.toast_error(format!("Automation failed: {}", error));
+        }
+        if report.suppressed > 0 {
+            self.toast_warning(format!(
+                "Automation loop stopped ({} rule runs skipped)",
+                report.suppressed
+            ));
+        }
+    }
+
+    /// Show the board's automation rules in the status bar
+    pub(crate) fn list_automation_rules(&mut self) {
+        let mut rules = self.board.automation.get_rules();
+        if rules.is_empty() {
+            self.status_message = "No automation rules".to_string();
+            return;
+        }
+        rules.sort_by(|a, b| a.id.cmp(&b.id));
+        let list: Vec<String> = rules
+            .iter()
+            .map(|rule| {
+                format!(
+                    "{} {}: {} → {}{} ({} runs)",
+                    rule.id,
+                    rule.name,
+                    rule.trigger.name(),
+                    rule.action.name(),
+                    if rule.enabled { "" } else { " [off]" },
+                    rule.execution_count
+                )
+            })
+            .collect();
+        self.status_message = format!("Rules: {}", list.join("; "));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::app::App;
+    use crate::core::event::Event;
+    use crate::infrastructure::builtin_automation::{
+        AutomationAction, AutomationRule, TriggerCondition,
+    };
+    use crate::infrastructure::rich_task_cards::Priority;
+    use tempfile::TempDir;
+
+    fn app_in(dir: &TempDir) -> App {
+        let mut app = App::new();
+        app.working_directory = dir.path().to_path_buf();
+        app.load_board();
+        app
+    }
+
+    #[test]
+    fn test_rules_run_on_tick() {
+        let dir = TempDir::new().unwrap();
+        let mut app = app_in(&dir);
+        app.board_mut().automation.add_rule(AutomationRule::new(
+            "Notify on new cards",
+            TriggerCondition::TaskCreated,
+            AutomationAction::SendNotification("new card".to_string()),
+        ));
+        app.board_mut().automation.add_rule(AutomationRule::new(
+            "Tag new cards",
+            TriggerCondition::TaskCreated,
+            AutomationAction::AddTag("triage".to_string()),
+        ));
+        let card = app.board_mut().cards.create_card(
+            "Flaky test".to_string(),
+            String::new(),
+            "To Do".to_string(),
+            Priority::Medium,
+            "me".to_string(),
+        );
+        app.save_board().unwrap();
+
+        for _ in 0..4 {
+            app.update(Event::Tick).unwrap();
+        }
+
+        assert_eq!(app.board().cards.get_card(&card).unwrap().tags.len(), 1);
+        assert!(app.status_message.starts_with("Automation: 2 actions"));
+        assert_eq!(app.toasts.len(), 1);
+        assert!(!app.board_dirty, "changes are autosaved on the same tick");
+    }
+
+    #[test]
+    fn test_loaded_cards_do_not_fire_created_rules() {
+        let dir = TempDir::new().unwrap();
+        let mut app = app_in(&dir);
+        app.board_mut().cards.create_card(
+            "Old card".to_string(),
+            String::new(),
+            "To Do".to_string(),
+            Priority::Medium,
+            "me".to_string(),
+        );
+        app.board_mut().automation.add_rule(AutomationRule::new(
+            "Tag new cards",
+            TriggerCondition::TaskCreated,
+            AutomationAction::AddTag("triage".to_string()),
+        ));
+        app.save_board().unwrap();
+
+        let mut app = app_in(&dir);
+        for _ in 0..4 {
+            app.update(Event::Tick).unwrap();
+        }
+        let cards = app.board().cards.cards();
+        assert!(cards[0].tags.is_empty());
+    }
+
+    #[test]
+    fn test_board_rules_command_lists_rules() {
+        let dir = TempDir::new().unwrap();
+        let mut app = app_in(&dir);
+        app.process_command("/board rules");
+        assert_eq!(app.status_message, "No automation rules");
+
+        app.board_mut().automation.add_rule(AutomationRule::new(
+            "Archive done",
+            TriggerCondition::TaskCompleted,
+            AutomationAction::Archive,
+        ));
+        app.process_command("/board rules");
+        assert_eq!(
+            app.status_message,
+            "Rules: rule-1 Archive done: Task Completed → Archive (0 runs)"
+        );
+    }
+}
@@ -0,0 +1,278 @@
+//! Board database lifecycle
+//!
+//! Loads the repo-local `.toad/` board database at startup, saves it
+//! incrementally after changes and exposes snapshots through `/board`
+//! commands.
+
+use crate::core::app::App;
+use crate::infrastructure::board_store::{BoardDatabase, BoardStore};
+
+/// Ticks between autosaves of a changed board (ticks are 250ms)
+const AUTOSAVE_TICKS: u32 = 4;
+
+impl App {
+    /// Load the board database for the current working directory
+    ///
+    /// A database that cannot be read (corrupt file, newer schema) is left
+    /// untouched on disk: the app starts with an empty board and does not
+    /// save over it.
+    pub(crate) fn load_board(&mut self) {
+        let mut store = BoardStore::open(&self.working_directory);
+        self.board_dirty = false;
+        match store.load() {
+            Ok(board) => {
+                self.board = board;
+                self.board_store = Some(store);
+            }
+            Err(e) => {
+                tracing::warn!("Failed to load board database: {}", e);
+                self.board = BoardDatabase::default();
+                self.board_store = None;
+                self.toast_warning(format!("Board database not loaded: {}", e));
+            }
+        }
+        self.automation.observe(&self.board);
+    }
+
+    /// Get mutable access to the board, marking it for saving
+    pub fn board_mut(&mut self) -> &mut BoardDatabase {
+        self.board_dirty = true;
+        &mut self.board
+    }
+
+    /// Save the board if it changed
+    ///
+    /// Only sections that differ from what is on disk are written.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if the database cannot be written. The board stays
+    /// marked as changed so the next save retries.
+    pub fn save_board(&mut self) -> crate::Result<()> {
+        if !self.board_dirty {
+            return Ok(());
+        }
+        let Some(store) = self.board_store.as_mut() else {
+            return Ok(());
+        };
+        store.save(&self.board)?;
+        self.board_dirty = false;
+        Ok(())
+    }
+
+    /// Save a changed board every [`AUTOSAVE_TICKS`] ticks
+    pub(crate) fn autosave_board(&mut self) {
+        if !self.board_dirty || !self.tick_count.is_multiple_of(AUTOSAVE_TICKS) {
+            return;
+        }
+        if let Err(e) = self.save_board() {
+            tracing::warn!("Failed to save board database: {}", e);
+            self.toast_error(format!("Failed to save board: {}", e));
+        }
+    }
+
+    /// Handle `/board` subcommands
+    ///
+    /// - `` (empty): summary of the board database
+    /// - `snapshot [message]`: save and snapshot the board
+    /// - `snapshots`: list snapshots
+    /// - `restore <id>`: restore a snapshot
+    /// - `run <card-id>`: hand a card to the agent
+    /// - `rules`: list automation rules
+    /// - `calendar [<dir> | off | prefer card|calendar]`: sync with an
+    ///   iCalendar directory
+    /// - `git`: link commits to cards and estimate time from git history
+    /// - `timesheet [days]`: time tracked per card over the last days
+    pub(crate) fn process_board_command(&mut self, args: &str) {
+        let (sub, rest) = args.split_once(' ').unwrap_or((args, ""));
+        let rest = rest.trim();
+        match sub {
+            "" => {
+                self.status_message = format!(
+                    "Board: {} cards, {} boards, {} projects",
+                    self.board.cards.total_cards(),
+                    self.board.boards.total_boards(),
+                    self.board.projects.project_count()
+                );
+            }
+            "snapshot" => self.snapshot_board(rest),
+            "snapshots" => self.list_board_snapshots(),
+            "restore" if !rest.is_empty() => self.restore_board_snapshot(rest),
+            "run" if !rest.is_empty() => self.run_card_with_agent(rest),
+            "rules" => self.list_automation_rules(),
+            "calendar" => self.process_calendar_command(rest),
+            "git" => self.sync_git_activity_now(),
+            "timesheet" => self.show_timesheet(rest),
+            _ => {
+                self.toast_error(
+                    "Usage: /board [snapshot [message] | snapshots | restore <id> | run <card-id> | rules | calendar [<dir>] | git | timesheet [days]]",
+                );
+            }
+        }
+    }
+
+    /// Save the board and store a snapshot of it
+    fn snapshot_board(&mut self, message: &str) {
+        let message = if message.is_empty() {
+            "Manual snapshot"
+        } else {
+            message
+        };
+        let saved = self.save_board();
+        let result = match (saved, self.board_store.as_mut()) {
+            (Err(e), _) => Err(e.to_string()),
+            (Ok(()), Some(store)) => store
+                .create_snapshot(&self.board, message)
+                .map_err(|e| e.to_string()),
+            (Ok(()), None) => Err("board database not loaded".to_string()),
+        };
+        match result {
+            Ok(id) => self.toast_success(format!("Board snapshot {}", id)),
+            Err(e) => self.toast_error(format!("Snapshot failed: {}", e)),
+        }
+    }
+
+    /// Show the stored snapshots in the status bar
+    fn list_board_snapshots(&mut self) {
+        let Some(store) = self.board_store.as_ref() else {
+            self.toast_error("Board database not loaded");
+            return;
+        };
+        match store.snapshots() {
+            Ok(snapshots) if snapshots.is_empty() => {
+                self.status_message = "No board snapshots".to_string();
+            }
+            Ok(snapshots) => {
+                let list: Vec<String> = snapshots
+                    .iter()
+                    .rev()
+                    .map(|s| format!("{} ({})", s.id, s.message))
+                    .collect();
+                self.status_message = format!("Snapshots: {}", list.join(", "));
+            }
+            Err(e) => self.toast_error(format!("Cannot list snapshots: {}", e)),
+        }
+    }
+
+    /// Replace the board with a stored snapshot
+    fn restore_board_snapshot(&mut self, id: &str) {
+        let Some(store) = self.board_store.as_mut() else {
+            self.toast_error("Board database not loaded");
+            return;
+        };
+        match store.restore_snapshot(id) {
+            Ok(board) => {
+                self.board = board;
+                self.board_dirty = false;
+                self.automation.observe(&self.board);
+                self.toast_success(format!("Restored board snapshot {}", id));
+            }
+            Err(e) => self.toast_error(format!("Restore failed: {}", e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::app::App;
+    use crate::core::event::Event;
+    use crate::infrastructure::board_store::BoardStore;
+    use crate::infrastructure::rich_task_cards::Priority;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn app_in(dir: &TempDir) -> App {
+        let mut app = App::new();
+        app.working_directory = dir.path().to_path_buf();
+        app.load_board();
+        app
+    }
+
+    fn add_card(app: &mut App, title: &str) -> String {
+        app.board_mut().cards.create_card(
+            title.to_string(),
+            String::new(),
+            "todo".to_string(),
+            Priority::Medium,
+            "me".to_string(),
+        )
+    }
+
+    #[test]
+    fn test_board_loaded_at_startup() {
+        let dir = TempDir::new().unwrap();
+        let mut store = BoardStore::open(dir.path());
+        let mut db = store.load().unwrap();
+        db.cards.create_card(
+            "Persisted".to_string(),
+            String::new(),
+            "todo".to_string(),
+            Priority::Low,
+            "me".to_string(),
+        );
+        store.save(&db).unwrap();
+
+        let app = app_in(&dir);
+        assert_eq!(app.board().cards.total_cards(), 1);
+    }
+
+    #[test]
+    fn test_changes_autosaved_on_tick() {
+        let dir = TempDir::new().unwrap();
+        let mut app = app_in(&dir);
+        add_card(&mut app, "New card");
+
+        for _ in 0..4 {
+            app.update(Event::Tick).unwrap();
+        }
+        assert!(!app.board_dirty);
+
+        let reloaded = BoardStore::open(dir.path()).load().unwrap();
+        assert_eq!(reloaded.cards.total_cards(), 1);
+    }
+
+    #[test]
+    fn test_unchanged_board_is_not_written() {
+        let dir = TempDir::new().unwrap();
+        let mut app = app_in(&dir);
+        app.save_board().unwrap();
+        assert!(!dir.path().join(".toad").exists());
+    }
+
+    #[test]
+    fn test_corrupt_database_is_not_overwritten() {
+        let dir = TempDir::new().unwrap();
+        let board_dir = dir.path().join(".toad").join("board");
+        fs::create_dir_all(&board_dir).unwrap();
+        fs::write(board_dir.join("manifest.json"), "not json").unwrap();
+
+        let mut app = app_in(&dir);
+        assert!(app.board_store.is_none());
+        add_card(&mut app, "Scratch");
+        app.save_board().unwrap();
+        assert_eq!(
+            fs::read_to_string(board_dir.join("manifest.json")).unwrap(),
+            "not json"
+        );
+    }
+
+    #[test]
+    fn test_board_snapshot_and_restore_commands() {
+        let dir = TempDir::new().unwrap();
+        let mut app = app_in(&dir);
+        add_card(&mut app, "First");
+        app.process_command("/board snapshot one card");
+
+        let id = app.board_store.as_ref().unwrap().snapshots().unwrap()[0]
+            .id
+            .clone();
+        add_card(&mut app, "Second");
+        assert_eq!(app.board().cards.total_cards(), 2);
+
+        app.process_command(&format!("/board restore {}", id));
+        assert_eq!(app.board().cards.total_cards(), 1);
+
+        app.process_command("/board snapshots");
+        assert!(app.status_message.contains(&id));
+    }
+}
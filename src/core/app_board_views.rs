@@ -0,0 +1,339 @@
+//! Board view lifecycle
+//!
+//! Opens the calendar, table and mind map views of the app's
+//! [`BoardDatabase`](crate::infrastructure::board_store::BoardDatabase) and
+//! switches between all board views with `Ctrl+1`..`Ctrl+6`.
+//!
+//! The views share two pieces of state kept on the app: the live card filter,
+//! lent to whichever view is open so a query typed in one view still applies
+//! after switching, and each view's settings, kept per board in a
+//! [`ViewManager`](crate::infrastructure::multiple_views::ViewManager).
+
+use crate::core::app::App;
+use crate::core::app_state::AppScreen;
+use crate::infrastructure::multiple_views::{ViewSettings, ViewType};
+use crate::ui::screens::calendar::{CalendarOutcome, CalendarScreen};
+use crate::ui::screens::mind_map::{MindMapOutcome, MindMapScreen};
+use crate::ui::screens::table_view::{TableOutcome, TableViewScreen};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+/// Board view bound to a `Ctrl+<digit>` key, if any
+pub(crate) fn board_view_shortcut(key: &KeyEvent) -> Option<ViewType> {
+    let KeyCode::Char(digit @ '1'..='9') = key.code else {
+        return None;
+    };
+    if key.modifiers != KeyModifiers::CONTROL {
+        return None;
+    }
+    let shortcut = format!("Ctrl+{}", digit);
+    ViewType::all()
+        .into_iter()
+        .find(|view| view.shortcut() == shortcut)
+}
+
+impl App {
+    /// Board whose view settings are in use
+    fn view_board_id(&self) -> String {
+        self.kanban_screen
+            .as_ref()
+            .and_then(|screen| screen.board_id())
+            .map(str::to_string)
+            .or_else(|| self.board.boards.boards().first().map(|b| b.id.clone()))
+            .unwrap_or_else(|| "default".to_string())
+    }
+
+    /// Saved settings of a board view, creating the view on first use
+    pub(crate) fn board_view_settings(&mut self, view_type: ViewType) -> ViewSettings {
+        let board_id = self.view_board_id();
+        let view_id = match self.board_views.switch_to_type(&board_id, view_type) {
+            Some(view_id) => view_id,
+            None => {
+                let view_id = self.board_views.create_view(
+                    view_type,
+                    view_type.name().to_string(),
+                    board_id.clone(),
+                );
+                self.board_views.set_active_view(board_id, view_id.clone());
+                view_id
+            }
+        };
+        self.board_views
+            .get_view(&view_id)
+            .map(|view| view.settings.clone())
+            .unwrap_or_else(|| ViewSettings::default_for_type(view_type))
+    }
+
+    /// Remember a board view's settings
+    pub(crate) fn store_board_view_settings(
+        &mut self,
+        view_type: ViewType,
+        settings: ViewSettings,
+    ) {
+        let board_id = self.view_board_id();
+        let view_id = self
+            .board_views
+            .views_for_board(&board_id)
+            .into_iter()
+            .find(|view| view.view_type == view_type)
+            .map(|view| view.id.clone());
+        if let Some(view) = view_id.and_then(|id| self.board_views.get_view_mut(&id)) {
+            view.settings = settings;
+        }
+    }
+
+    /// Lend the shared card filter to the open board view, or take it back
+    ///
+    /// The board view screens (kanban, timeline, calendar, table and mind map)
+    /// only hold the filter while they are open; the app keeps it in between
+    /// so a query typed in one view carries over to the next. Called once
+    /// right after a board view opens and once right before it closes, so the
+    /// filter is always in exactly one place.
+    pub(crate) fn swap_board_filter(&mut self) {
+        let filter = match self.screen {
+            AppScreen::Kanban => self.kanban_screen.as_mut().map(|s| s.filter_mut()),
+            AppScreen::Timeline => self.timeline_screen.as_mut().map(|s| s.filter_mut()),
+            AppScreen::Calendar => self.calendar_screen.as_mut().map(|s| s.filter_mut()),
+            AppScreen::Table => self.table_screen.as_mut().map(|s| s.filter_mut()),
+            AppScreen::MindMap => self.mind_map_screen.as_mut().map(|s| s.filter_mut()),
+            _ => None,
+        };
+        if let Some(filter) = filter {
+            std::mem::swap(filter, &mut self.board_filter);
+        }
+    }
+
+    /// Switch from the open board view to another one
+    pub(crate) fn open_board_view(&mut self, view_type: ViewType) {
+        if view_type == ViewType::List {
+            self.toast_info("The list view is not available yet");
+            return;
+        }
+        match self.screen {
+            AppScreen::Kanban => self.close_kanban_screen(),
+            AppScreen::Timeline => self.close_timeline_screen(),
+            AppScreen::Calendar => self.close_calendar_screen(),
+            AppScreen::Table => self.close_table_screen(),
+            AppScreen::MindMap => self.close_mind_map_screen(),
+            _ => {}
+        }
+        match view_type {
+            ViewType::Kanban => self.open_kanban_screen(),
+            ViewType::Timeline => self.open_timeline_screen(),
+            ViewType::Calendar => self.open_calendar_screen(),
+            ViewType::Table => self.open_table_screen(),
+            ViewType::MindMap => self.open_mind_map_screen(),
+            ViewType::List => {}
+        }
+    }
+
+    /// Open the calendar screen, creating it on first use
+    pub(crate) fn open_calendar_screen(&mut self) {
+        if self.calendar_screen.is_none() {
+            let settings = self.board_view_settings(ViewType::Calendar);
+            self.calendar_screen = Some(CalendarScreen::from_settings(&settings));
+        }
+        self.screen = AppScreen::Calendar;
+        self.swap_board_filter();
+        self.status_message = "Opened Calendar".to_string();
+    }
+
+    /// Close the calendar screen and return to the main interface
+    pub(crate) fn close_calendar_screen(&mut self) {
+        self.swap_board_filter();
+        if let Some(settings) = self.calendar_screen.as_ref().map(|s| s.settings()) {
+            self.store_board_view_settings(ViewType::Calendar, settings);
+        }
+        self.screen = AppScreen::Main;
+        self.status_message = "Closed Calendar".to_string();
+    }
+
+    /// Act on the result of a calendar key press
+    pub(crate) fn handle_calendar_outcome(&mut self, outcome: CalendarOutcome) {
+        match outcome {
+            CalendarOutcome::None => {}
+            CalendarOutcome::Rescheduled(message) => {
+                self.board_dirty = true;
+                self.status_message = message;
+            }
+            CalendarOutcome::Message(message) => self.status_message = message,
+            CalendarOutcome::Error(message) => self.toast_error(message),
+            CalendarOutcome::Close => self.close_calendar_screen(),
+        }
+    }
+
+    /// Open the table screen, creating it on first use
+    pub(crate) fn open_table_screen(&mut self) {
+        if self.table_screen.is_none() {
+            let settings = self.board_view_settings(ViewType::Table);
+            self.table_screen = Some(TableViewScreen::from_settings(&settings));
+        }
+        self.screen = AppScreen::Table;
+        self.swap_board_filter();
+        self.status_message = "Opened Table".to_string();
+    }
+
+    /// Close the table screen and return to the main interface
+    pub(crate) fn close_table_screen(&mut self) {
+        self.swap_board_filter();
+        if let Some(settings) = self.table_screen.as_ref().map(|s| s.settings()) {
+            self.store_board_view_settings(ViewType::Table, settings);
+        }
+        self.screen = AppScreen::Main;
+        self.status_message = "Closed Table".to_string();
+    }
+
+    /// Act on the result of a table key press
+    pub(crate) fn handle_table_outcome(&mut self, outcome: TableOutcome) {
+        match outcome {
+            TableOutcome::None => {}
+            TableOutcome::Edited(message) => {
+                self.board_dirty = true;
+                self.status_message = message;
+            }
+            TableOutcome::Message(message) => self.status_message = message,
+            TableOutcome::Error(message) => self.toast_error(message),
+            TableOutcome::Close => self.close_table_screen(),
+        }
+    }
+
+    /// Open the mind map screen, creating it on first use
+    pub(crate) fn open_mind_map_screen(&mut self) {
+        if self.mind_map_screen.is_none() {
+            let settings = self.board_view_settings(ViewType::MindMap);
+            self.mind_map_screen = Some(MindMapScreen::from_settings(&settings));
+        }
+        self.screen = AppScreen::MindMap;
+        self.swap_board_filter();
+        self.status_message = "Opened Mind Map".to_string();
+    }
+
+    /// Close the mind map screen and return to the main interface
+    pub(crate) fn close_mind_map_screen(&mut self) {
+        self.swap_board_filter();
+        if let Some(settings) = self.mind_map_screen.as_ref().map(|s| s.settings()) {
+            self.store_board_view_settings(ViewType::MindMap, settings);
+        }
+        self.screen = AppScreen::Main;
+        self.status_message = "Closed Mind Map".to_string();
+    }
+
+    /// Act on the result of a mind map key press
+    pub(crate) fn handle_mind_map_outcome(&mut self, outcome: MindMapOutcome) {
+        match outcome {
+            MindMapOutcome::None => {}
+            MindMapOutcome::Message(message) => self.status_message = message,
+            MindMapOutcome::Close => self.close_mind_map_screen(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::event::Event;
+    use crate::infrastructure::multiple_views::CalendarMode;
+    use crate::infrastructure::rich_task_cards::Priority;
+    use tempfile::TempDir;
+
+    fn app_in(dir: &TempDir) -> App {
+        let mut app = App::new();
+        app.screen = AppScreen::Main;
+        app.working_directory = dir.path().to_path_buf();
+        app.load_board();
+        app
+    }
+
+    fn press(app: &mut App, code: KeyCode, modifiers: KeyModifiers) {
+        app.update(Event::Key(KeyEvent::new(code, modifiers)))
+            .unwrap();
+    }
+
+    fn type_text(app: &mut App, text: &str) {
+        for c in text.chars() {
+            press(app, KeyCode::Char(c), KeyModifiers::NONE);
+        }
+    }
+
+    #[test]
+    fn test_shortcuts_follow_view_types() {
+        let key = KeyEvent::new(KeyCode::Char('5'), KeyModifiers::CONTROL);
+        assert_eq!(board_view_shortcut(&key), Some(ViewType::Table));
+        let key = KeyEvent::new(KeyCode::Char('5'), KeyModifiers::NONE);
+        assert_eq!(board_view_shortcut(&key), None);
+        let key = KeyEvent::new(KeyCode::Char('7'), KeyModifiers::CONTROL);
+        assert_eq!(board_view_shortcut(&key), None);
+    }
+
+    #[test]
+    fn test_filter_follows_view_switches() {
+        let dir = TempDir::new().unwrap();
+        let mut app = app_in(&dir);
+        for title in ["Fix login", "Write docs"] {
+            app.board_mut().cards.create_card(
+                title.to_string(),
+                String::new(),
+                "To Do".to_string(),
+                Priority::Medium,
+                "me".to_string(),
+            );
+        }
+
+        app.process_command("/kanban");
+        press(&mut app, KeyCode::Char('/'), KeyModifiers::NONE);
+        type_text(&mut app, "login");
+        press(&mut app, KeyCode::Enter, KeyModifiers::NONE);
+
+        press(&mut app, KeyCode::Char('5'), KeyModifiers::CONTROL);
+        assert_eq!(app.screen, AppScreen::Table);
+        let table = app.table_screen.as_mut().unwrap();
+        table.handle_key(
+            KeyEvent::new(KeyCode::Null, KeyModifiers::NONE),
+            &mut app.board,
+        );
+        assert_eq!(table.row_ids().len(), 1);
+
+        press(&mut app, KeyCode::Char('6'), KeyModifiers::CONTROL);
+        assert_eq!(app.screen, AppScreen::MindMap);
+        assert_eq!(
+            app.mind_map_screen
+                .as_ref()
+                .unwrap()
+                .visible_nodes(app.board()),
+            3
+        );
+
+        press(&mut app, KeyCode::Esc, KeyModifiers::NONE);
+        assert_eq!(app.screen, AppScreen::MindMap);
+        press(&mut app, KeyCode::Esc, KeyModifiers::NONE);
+        assert_eq!(app.screen, AppScreen::Main);
+        assert!(!app.board_filter.is_active());
+    }
+
+    #[test]
+    fn test_view_settings_are_kept() {
+        let dir = TempDir::new().unwrap();
+        let mut app = app_in(&dir);
+
+        app.process_command("/calendar");
+        assert_eq!(app.screen, AppScreen::Calendar);
+        press(&mut app, KeyCode::Char('m'), KeyModifiers::NONE);
+        press(&mut app, KeyCode::Esc, KeyModifiers::NONE);
+        assert_eq!(app.screen, AppScreen::Main);
+
+        app.calendar_screen = None;
+        app.process_command("/calendar");
+        assert_eq!(
+            app.calendar_screen.as_ref().unwrap().mode(),
+            CalendarMode::Week
+        );
+    }
+
+    #[test]
+    fn test_list_view_is_not_available() {
+        let dir = TempDir::new().unwrap();
+        let mut app = app_in(&dir);
+        app.process_command("/mindmap");
+        press(&mut app, KeyCode::Char('2'), KeyModifiers::CONTROL);
+        assert_eq!(app.screen, AppScreen::MindMap);
+    }
+}
@@ -0,0 +1,255 @@
+//! Calendar sync
+//!
+//! Keeps the board in sync with a directory of `.ics` files (see
+//! [`calendar_sync`](crate::infrastructure::calendar_sync)). `/board calendar
+//! <dir>` links a directory; it is then polled every [`CALENDAR_SYNC_TICKS`]
+//! ticks and synced whenever its files or the board changed.
+
+use crate::core::app::App;
+use crate::infrastructure::calendar_sync::{
+    CalendarSource, CalendarSyncState, ConflictPolicy, SyncOptions, SyncReport, sync_calendar,
+};
+use chrono::Utc;
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+
+/// Ticks between polls of the calendar directory (ticks are 250ms)
+const CALENDAR_SYNC_TICKS: u32 = 8;
+
+/// Watched calendar directory
+#[derive(Debug)]
+pub(crate) struct CalendarWatch {
+    source: CalendarSource,
+    /// UIDs in conflict after the last sync (to report new ones only)
+    conflicts: BTreeSet<String>,
+    /// Board changed since the last sync (latched before autosave clears
+    /// the dirty flag)
+    board_changed: bool,
+}
+
+impl App {
+    /// Handle `/board calendar [<dir> | off | prefer card|calendar]`
+    pub(crate) fn process_calendar_command(&mut self, args: &str) {
+        let (sub, rest) = args.split_once(' ').unwrap_or((args, ""));
+        match (sub, rest.trim()) {
+            ("", _) => {
+                if self.board.calendar.source.is_none() {
+                    self.toast_error("No calendar linked. Usage: /board calendar <dir>");
+                } else {
+                    self.sync_calendar_now(ConflictPolicy::Report);
+                }
+            }
+            ("off", _) => {
+                self.board_mut().calendar = CalendarSyncState::default();
+                self.calendar_watch = None;
+                self.toast_info("Calendar unlinked");
+            }
+            ("prefer", "card") => self.sync_calendar_now(ConflictPolicy::PreferCard),
+            ("prefer", "calendar") => self.sync_calendar_now(ConflictPolicy::PreferCalendar),
+            ("prefer", _) => self.toast_error("Usage: /board calendar prefer card|calendar"),
+            _ => {
+                let dir = PathBuf::from(args.trim());
+                self.board_mut().calendar.source = Some(dir);
+                self.calendar_watch = None;
+                self.sync_calendar_now(ConflictPolicy::Report);
+            }
+        }
+    }
+
+    /// Sync with the linked calendar every [`CALENDAR_SYNC_TICKS`] ticks if
+    /// its directory or the board changed
+    pub(crate) fn poll_calendar(&mut self) {
+        let board_dirty = self.board_dirty;
+        let due = self.tick_count.is_multiple_of(CALENDAR_SYNC_TICKS);
+        let Some(watch) = self.calendar_watch() else {
+            return;
+        };
+        watch.board_changed |= board_dirty;
+        if due && (watch.source.has_changed() || watch.board_changed) {
+            self.run_calendar_sync(ConflictPolicy::Report, false);
+        }
+    }
+
+    /// Sync now and report the result
+    fn sync_calendar_now(&mut self, conflicts: ConflictPolicy) {
+        if self.calendar_watch().is_some() {
+            self.run_calendar_sync(conflicts, true);
+        }
+    }
+
+    /// Watch state for the board's calendar, (re)created when the linked
+    /// directory changed (e.g. after loading another board)
+    fn calendar_watch(&mut self) -> Option<&mut CalendarWatch> {
+        let Some(dir) = self.board.calendar.source.as_ref() else {
+            self.calendar_watch = None;
+            return None;
+        };
+        let dir = self.working_directory.join(dir);
+        if self
+            .calendar_watch
+            .as_ref()
+            .is_none_or(|watch| watch.source.dir() != dir)
+        {
+            self.calendar_watch = Some(CalendarWatch {
+                source: CalendarSource::new(dir),
+                conflicts: BTreeSet::new(),
+                board_changed: false,
+            });
+        }
+        self.calendar_watch.as_mut()
+    }
+
+    /// Run one sync; `announce` reports results even if nothing changed
+    fn run_calendar_sync(&mut self, conflicts: ConflictPolicy, announce: bool) {
+        let Some(watch) = self.calendar_watch.as_mut() else {
+            return;
+        };
+        let options = SyncOptions {
+            conflicts,
+            ..SyncOptions::default()
+        };
+        let result = sync_calendar(&mut self.board, &watch.source, &options, Utc::now());
+        watch.source.mark_seen();
+        watch.board_changed = false;
+
+        match result {
+            Ok(report) => self.handle_calendar_report(report, announce),
+            Err(e) => {
+                tracing::warn!("Calendar sync failed: {}", e);
+                self.toast_error(format!("Calendar sync failed: {}", e));
+            }
+        }
+    }
+
+    /// Surface the result of a sync
+    fn handle_calendar_report(&mut self, report: SyncReport, announce: bool) {
+        if report.changed_board() {
+            self.board_dirty = true;
+        }
+        if report.changed_board() || announce {
+            self.status_message = report.summary();
+        }
+
+        let conflicts: BTreeSet<String> = report.conflicts.iter().map(|c| c.uid.clone()).collect();
+        let previous = self
+            .calendar_watch
+            .as_mut()
+            .map(|watch| std::mem::replace(&mut watch.conflicts, conflicts.clone()))
+            .unwrap_or_default();
+        if !conflicts.is_empty() && (announce || !conflicts.is_subset(&previous)) {
+            let titles: Vec<&str> = report
+                .conflicts
+                .iter()
+                .map(|c| c.card.title.as_str())
+                .collect();
+            self.toast_warning(format!(
+                "Calendar conflicts: {}. Resolve with /board calendar prefer card|calendar",
+                titles.join(", ")
+            ));
+        }
+
+        for warning in report.warnings {
+            tracing::warn!("Calendar sync: {}", warning);
+            if announce {
+                self.toast_warning(warning);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::app::App;
+    use crate::core::event::Event;
+    use crate::infrastructure::rich_task_cards::Priority;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn app_in(dir: &TempDir) -> App {
+        let mut app = App::new();
+        app.working_directory = dir.path().to_path_buf();
+        app.load_board();
+        app
+    }
+
+    fn write_todo(dir: &TempDir, uid: &str, summary: &str) {
+        fs::create_dir_all(dir.path().join("cal")).unwrap();
+        fs::write(
+            dir.path().join("cal").join(format!("{}.ics", uid)),
+            format!(
+                "BEGIN:VCALENDAR\r\nBEGIN:VTODO\r\nUID:{}\r\nSUMMARY:{}\r\n\
+                 DUE:20300101T090000Z\r\nEND:VTODO\r\nEND:VCALENDAR\r\n",
+                uid, summary
+            ),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_board_calendar_command_links_and_syncs() {
+        let dir = TempDir::new().unwrap();
+        write_todo(&dir, "tax", "File taxes");
+        let mut app = app_in(&dir);
+
+        app.process_command("/board calendar cal");
+
+        assert_eq!(app.status_message, "Calendar: 1 imported");
+        assert_eq!(app.board().cards.cards()[0].title, "File taxes");
+        assert!(app.board().calendar.source.is_some());
+
+        app.save_board().unwrap();
+        let reloaded = app_in(&dir);
+        assert_eq!(reloaded.board().calendar.links.len(), 1);
+    }
+
+    #[test]
+    fn test_watched_directory_is_polled_on_tick() {
+        let dir = TempDir::new().unwrap();
+        let mut app = app_in(&dir);
+        app.process_command("/board calendar cal");
+        assert!(app.board().cards.cards().is_empty());
+
+        write_todo(&dir, "rent", "Pay rent");
+        for _ in 0..8 {
+            app.update(Event::Tick).unwrap();
+        }
+
+        assert_eq!(app.board().cards.cards()[0].title, "Pay rent");
+    }
+
+    #[test]
+    fn test_board_changes_are_pushed_on_tick() {
+        let dir = TempDir::new().unwrap();
+        let mut app = app_in(&dir);
+        app.process_command("/board calendar cal");
+
+        let card = app.board_mut().cards.create_card(
+            "Renew domain".to_string(),
+            String::new(),
+            "To Do".to_string(),
+            Priority::High,
+            "me".to_string(),
+        );
+        app.board_mut().cards.get_card_mut(&card).unwrap().due_date = Some(chrono::Utc::now());
+        for _ in 0..8 {
+            app.update(Event::Tick).unwrap();
+        }
+
+        let files: Vec<_> = fs::read_dir(dir.path().join("cal")).unwrap().collect();
+        assert_eq!(files.len(), 1);
+        assert!(app.board().calendar.uid_for_card(&card).is_some());
+    }
+
+    #[test]
+    fn test_calendar_off_and_usage() {
+        let dir = TempDir::new().unwrap();
+        let mut app = app_in(&dir);
+        app.process_command("/board calendar");
+        assert_eq!(app.toasts.len(), 1);
+
+        app.process_command("/board calendar cal");
+        app.process_command("/board calendar off");
+        assert!(app.board().calendar.source.is_none());
+        assert!(app.calendar_watch.is_none());
+    }
+}
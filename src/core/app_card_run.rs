@@ -0,0 +1,340 @@
+//! Agent runs on task cards
+//!
+//! Hands a board card to the agent (see [`card_runner`](crate::ai::card_runner))
+//! and tracks the run on the card: it moves through the board columns,
+//! commits made on the card branch are recorded as
+//! [`CardCommit`](crate::infrastructure::git_card_integration::CardCommit)s
+//! and the run's time and cost are logged to time tracking.
+
+use crate::ai::card_runner::{
+    AGENT_AUTHOR, CardRunEvent, CardRunReport, CardRunRequest, start_card_run,
+};
+use crate::core::app::App;
+use crate::infrastructure::git_card_integration::CardBranch;
+use crate::infrastructure::time_tracking::TimeEntry;
+use tokio::task::JoinHandle;
+
+/// Column a card moves to while the agent works on it
+const RUNNING_COLUMN: &str = "In Progress";
+
+/// Column a card moves to when the agent finished successfully
+const FINISHED_COLUMN: &str = "Review";
+
+/// Agent run in progress
+///
+/// Only one run at a time: runs switch the working tree's branch.
+#[derive(Debug)]
+pub(crate) struct ActiveCardRun {
+    /// Card being worked on
+    pub(crate) card_id: String,
+    /// Column the card was in before the run (restored if the run fails)
+    origin_column: Option<String>,
+    /// Background task
+    handle: JoinHandle<()>,
+}
+
+impl App {
+    /// Start an agent run for a card
+    ///
+    /// The agent works on the card's active branch, or on a new branch named
+    /// with [`CardBranch::suggest_branch_name`].
+    pub(crate) fn run_card_with_agent(&mut self, card_id: &str) {
+        if let Some(run) = self.card_run.as_ref()
+            && !run.handle.is_finished()
+        {
+            self.toast_warning(format!("Agent is already working on {}", run.card_id));
+            return;
+        }
+        let Some(client) = self.llm_client().cloned() else {
+            self.toast_error("LLM client not available");
+            return;
+        };
+        let Some(event_tx) = self.event_tx.clone() else {
+            self.toast_error("Cannot run card: event channel not initialized");
+            return;
+        };
+        let Some(card) = self.board.cards.get_card(card_id) else {
+            self.toast_error(format!("Card {} not found", card_id));
+            return;
+        };
+
+        let branch = self
+            .board
+            .git
+            .get_active_card_branches(card_id)
+            .last()
+            .map(|b| b.name.clone())
+            .unwrap_or_else(|| CardBranch::suggest_branch_name(card_id, &card.title));
+        let request = CardRunRequest::new(card, branch, self.working_directory.clone());
+        let origin_column = self
+            .board
+            .boards
+            .get_card_position(card_id)
+            .and_then(|pos| self.board.boards.get_column(&pos.column_id))
+            .map(|column| column.name.clone());
+
+        let handle = start_card_run(request, Box::new(client), event_tx);
+        self.card_run = Some(ActiveCardRun {
+            card_id: card_id.to_string(),
+            origin_column,
+            handle,
+        });
+        self.status_message = format!("Starting agent on {}...", card_id);
+    }
+
+    /// Act on progress of the running card
+    pub(crate) fn handle_card_run_event(&mut self, event: CardRunEvent) {
+        match event {
+            CardRunEvent::Started {
+                card_id,
+                branch,
+                base_branch,
+            } => {
+                let known = self
+                    .board
+                    .git
+                    .get_card_branches(&card_id)
+                    .iter()
+                    .any(|b| b.name == branch);
+                if !known && let Some(card) = self.board.cards.get_card(&card_id) {
+                    let title = card.title.clone();
+                    self.board.git.create_branch(
+                        card_id.clone(),
+                        base_branch,
+                        &title,
+                        AGENT_AUTHOR.to_string(),
+                    );
+                }
+                self.log_card_run_activity(&card_id, format!("Agent started on {}", branch));
+                self.move_run_card(&card_id, RUNNING_COLUMN);
+                self.toast_info(format!("Agent working on {} ({})", card_id, branch));
+            }
+            CardRunEvent::Progress {
+                card_id,
+                step,
+                cost_usd,
+                last_tool,
+            } => {
+                let tool = last_tool.map(|t| format!(" · {}", t)).unwrap_or_default();
+                self.status_message = format!(
+                    "Agent on {}: step {} · ${:.4}{}",
+                    card_id, step, cost_usd, tool
+                );
+            }
+            CardRunEvent::Finished(report) => self.finish_card_run(*report),
+            CardRunEvent::Failed { card_id, error } => {
+                let origin = self.take_card_run(&card_id);
+                if let Some(column) = origin {
+                    self.move_run_card(&card_id, &column);
+                }
+                self.log_card_run_activity(&card_id, format!("Agent run failed: {}", error));
+                self.toast_error(format!("Agent run on {} failed: {}", card_id, error));
+            }
+        }
+    }
+
+    /// Record commits, time and cost of a finished run and move the card on
+    fn finish_card_run(&mut self, report: CardRunReport) {
+        let card_id = report.card_id.clone();
+        let origin = self.take_card_run(&card_id);
+        let commit_count = report.commits.len();
+        for commit in report.commits {
+            // Skip commits a git activity sync already linked
+            if !self.board.git.has_commit(&commit.card_id, &commit.hash) {
+                self.board.git.add_commit(commit);
+            }
+        }
+
+        let entry = TimeEntry::manual(
+            &card_id,
+            report.started_at,
+            chrono::Duration::milliseconds(report.duration_ms as i64),
+        )
+        .description(format!(
+            "Agent run on {} ({} steps)",
+            report.branch, report.steps
+        ))
+        .cost(report.cost_usd)
+        .add_tag("agent");
+        self.board.time.add_entry(entry);
+
+        let outcome = if report.success {
+            "finished"
+        } else {
+            "stopped"
+        };
+        let summary = report.summary.lines().next().unwrap_or_default();
+        self.log_card_run_activity(
+            &card_id,
+            format!(
+                "Agent {} after {} steps (${:.4}, {} commits): {}",
+                outcome, report.steps, report.cost_usd, commit_count, summary
+            ),
+        );
+
+        if report.success {
+            self.move_run_card(&card_id, FINISHED_COLUMN);
+            self.toast_success(format!(
+                "Agent finished {}: {} commits, ${:.4}",
+                card_id, commit_count, report.cost_usd
+            ));
+        } else {
+            if let Some(column) = origin {
+                self.move_run_card(&card_id, &column);
+            }
+            self.toast_warning(format!("Agent stopped on {}: {}", card_id, summary));
+        }
+    }
+
+    /// Forget the active run for a card, returning its origin column
+    fn take_card_run(&mut self, card_id: &str) -> Option<String> {
+        match self.card_run.take() {
+            Some(run) if run.card_id == card_id => run.origin_column,
+            other => {
+                self.card_run = other;
+                None
+            }
+        }
+    }
+
+    /// Move the running card to a column by name
+    fn move_run_card(&mut self, card_id: &str, column: &str) {
+        match self.board_mut().move_card_to_named(card_id, column) {
+            Ok(Some(warning)) => self.toast_warning(warning),
+            Ok(None) => {}
+            Err(e) => tracing::warn!("Cannot move {} to {}: {}", card_id, column, e),
+        }
+    }
+
+    fn log_card_run_activity(&mut self, card_id: &str, description: String) {
+        self.board_mut().comments.log_activity(
+            card_id.to_string(),
+            "agent_run".to_string(),
+            description,
+            Some(AGENT_AUTHOR.to_string()),
+            None,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ai::llm::mock::MockResponseBuilder;
+    use crate::core::app::App;
+    use crate::core::event::Event;
+    use crate::infrastructure::rich_task_cards::Priority;
+    use std::process::Command;
+    use std::sync::Arc;
+    use std::time::Duration;
+    use tempfile::TempDir;
+
+    fn git(dir: &std::path::Path, args: &[&str]) -> String {
+        let output = Command::new("git")
+            .current_dir(dir)
+            .args(args)
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "git {:?} failed", args);
+        String::from_utf8_lossy(&output.stdout).trim().to_string()
+    }
+
+    /// App on a fresh repository with one card on the board
+    fn app_with_card(dir: &TempDir) -> (App, String) {
+        git(dir.path(), &["init", "-q", "-b", "main"]);
+        git(dir.path(), &["config", "user.email", "test@example.com"]);
+        git(dir.path(), &["config", "user.name", "Test"]);
+        std::fs::write(dir.path().join("README.md"), "hello\n").unwrap();
+        git(dir.path(), &["add", "."]);
+        git(dir.path(), &["commit", "-q", "-m", "init"]);
+
+        let mut app = App::new();
+        app.working_directory = dir.path().to_path_buf();
+        app.load_board();
+        let card = app.board_mut().cards.create_card(
+            "Tidy readme".to_string(),
+            "Fix the wording".to_string(),
+            "To Do".to_string(),
+            Priority::Medium,
+            "me".to_string(),
+        );
+        let board_id = app.board_mut().ensure_board();
+        app.board_mut().place_cards(&board_id);
+        (app, card)
+    }
+
+    /// Feed card run events into the app until the run ends
+    async fn drive(app: &mut App, rx: &mut tokio::sync::mpsc::UnboundedReceiver<Event>) {
+        loop {
+            let event = tokio::time::timeout(Duration::from_secs(10), rx.recv())
+                .await
+                .unwrap()
+                .unwrap();
+            let done = matches!(
+                &event,
+                Event::CardRun(
+                    crate::ai::card_runner::CardRunEvent::Finished(_)
+                        | crate::ai::card_runner::CardRunEvent::Failed { .. }
+                )
+            );
+            app.update(event).unwrap();
+            if done {
+                break;
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_card_run_tracks_branch_column_time_and_cost() {
+        let dir = TempDir::new().unwrap();
+        let (mut app, card) = app_with_card(&dir);
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        app.set_event_tx(tx);
+        app.llm_client = Some(Arc::new(
+            MockResponseBuilder::new()
+                .with_text("Reworded the readme")
+                .build(),
+        ));
+
+        app.run_card_with_agent(&card);
+        drive(&mut app, &mut rx).await;
+
+        let branch = git(dir.path(), &["branch", "--show-current"]);
+        assert_eq!(branch, "feature/card-1-tidy-readme");
+        let branches = app.board().git.get_card_branches(&card);
+        assert_eq!(branches.len(), 1);
+        assert_eq!(branches[0].base_branch, "main");
+
+        assert_eq!(app.board().cards.get_card(&card).unwrap().status, "Review");
+        let entries = app.board().time.entries_for_task(&card);
+        assert_eq!(entries.len(), 1);
+        assert!(entries[0].tags.contains(&"agent".to_string()));
+        assert!(app.card_run.is_none());
+        assert!(app.board_dirty);
+    }
+
+    #[tokio::test]
+    async fn test_failed_run_returns_card_to_its_column() {
+        let dir = TempDir::new().unwrap();
+        let (mut app, card) = app_with_card(&dir);
+        std::fs::write(dir.path().join("README.md"), "uncommitted\n").unwrap();
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        app.set_event_tx(tx);
+        app.llm_client = Some(Arc::new(MockResponseBuilder::new().with_text("x").build()));
+
+        app.run_card_with_agent(&card);
+        drive(&mut app, &mut rx).await;
+
+        assert_eq!(app.board().cards.get_card(&card).unwrap().status, "To Do");
+        assert!(app.board().git.get_card_branches(&card).is_empty());
+        assert!(app.card_run.is_none());
+    }
+
+    #[test]
+    fn test_run_without_llm_client_is_refused() {
+        let dir = TempDir::new().unwrap();
+        let (mut app, card) = app_with_card(&dir);
+        app.llm_client = None;
+        app.run_card_with_agent(&card);
+        assert!(app.card_run.is_none());
+    }
+}
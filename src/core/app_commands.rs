@@ -16,6 +16,14 @@ impl App {
     /// - `/help`: Show help screen
     /// - `/commands`: List available commands
     /// - `/clear`: Clear screen
+    /// - `/git`: Open the git screen
+    /// - `/edit [path]`: Open the editor (optionally on a file)
+    /// - `/board [snapshot [message] | snapshots | restore <id> | run <card-id> | rules | calendar [<dir>] | git | timesheet [days]]`: Board database
+    /// - `/kanban`: Open the kanban board
+    /// - `/timeline`: Open the Gantt timeline of the board
+    /// - `/calendar`: Open the calendar of card due dates
+    /// - `/table`: Open the card table
+    /// - `/mindmap`: Open the mind map of epics and cards
     ///
     /// # Evaluation Commands
     ///
@@ -48,12 +56,51 @@ impl App {
                 }
                 "commands" => {
                     self.status_message =
-                        "Available commands: /help, /commands, /clear, eval, compare, show-config"
+                        "Available commands: /help, /commands, /clear, /git, /edit [path], /board, /kanban, /timeline, /calendar, /table, /mindmap, /windows, /send <window> [path[:line]], eval, compare, show-config"
                             .to_string();
                 }
                 "clear" => {
                     self.status_message = "Screen cleared".to_string();
                 }
+                "git" => {
+                    self.open_git_screen();
+                }
+                "edit" => {
+                    self.open_editor_screen();
+                }
+                _ if command.starts_with("edit ") => {
+                    self.open_in_editor(command["edit ".len()..].trim());
+                }
+                "kanban" => {
+                    self.open_kanban_screen();
+                }
+                "timeline" => {
+                    self.open_timeline_screen();
+                }
+                "calendar" => {
+                    self.open_calendar_screen();
+                }
+                "table" => {
+                    self.open_table_screen();
+                }
+                "mindmap" => {
+                    self.open_mind_map_screen();
+                }
+                "board" => {
+                    self.process_board_command("");
+                }
+                _ if command.starts_with("board ") => {
+                    self.process_board_command(command["board ".len()..].trim());
+                }
+                "windows" => {
+                    self.list_windows();
+                }
+                "send" => {
+                    self.process_send_command("");
+                }
+                _ if command.starts_with("send ") => {
+                    self.process_send_command(command["send ".len()..].trim());
+                }
                 _ => {
                     self.status_message = format!("Unknown command: /{}", command);
                 }
@@ -104,7 +151,7 @@ impl App {
     /// - `split_vertical`: Split pane vertically (coming soon)
     /// - `open_file`: Open file picker (coming soon)
     /// - `search_files`: Search files (coming soon)
-    /// - `git_status`: Show git status (coming soon)
+    /// - `git_status`: Open the git screen
     /// - `recent_files`: Show recent files (coming soon)
     ///
     /// # Parameters
@@ -145,6 +192,27 @@ impl App {
                 self.screen = AppScreen::Evaluation;
                 self.status_message = "Opened Evaluation Center".to_string();
             }
+            "git_status" => {
+                self.open_git_screen();
+            }
+            "open_editor" => {
+                self.open_editor_screen();
+            }
+            "open_kanban" => {
+                self.open_kanban_screen();
+            }
+            "open_timeline" => {
+                self.open_timeline_screen();
+            }
+            "open_calendar" => {
+                self.open_calendar_screen();
+            }
+            "open_table" => {
+                self.open_table_screen();
+            }
+            "open_mind_map" => {
+                self.open_mind_map_screen();
+            }
             "quit" => {
                 self.should_quit = true;
             }
@@ -0,0 +1,210 @@
+//! Editor screen lifecycle
+//!
+//! Opens workspace files in the embedded editor, either from the file tree,
+//! the `/edit` command or the files the agent modified during an evaluation,
+//! and surfaces editor outcomes and on-disk changes as toasts.
+
+use crate::core::app::App;
+use crate::core::app_state::AppScreen;
+use crate::ui::screens::editor::{EditorOutcome, EditorScreen};
+use std::path::{Path, PathBuf};
+
+/// Ticks between checks for files changed on disk (ticks are 250ms)
+const EXTERNAL_CHECK_TICKS: u32 = 8;
+
+impl App {
+    /// Open the editor screen, creating it on first use
+    pub(crate) fn open_editor_screen(&mut self) {
+        if self.editor_screen.is_none() {
+            self.editor_screen = Some(EditorScreen::new(self.working_directory.clone()));
+        }
+        self.screen = AppScreen::Editor;
+        self.status_message = "Opened Editor".to_string();
+    }
+
+    /// Open a file in the editor (relative to the working directory)
+    pub(crate) fn open_in_editor(&mut self, path: impl AsRef<Path>) {
+        self.open_editor_screen();
+        let Some(screen) = self.editor_screen.as_mut() else {
+            return;
+        };
+        if let Err(e) = screen.open(path.as_ref()) {
+            self.toast_error(format!("Cannot open {}: {}", path.as_ref().display(), e));
+        }
+    }
+
+    /// Open every file the agent modified in the current evaluation
+    pub(crate) fn open_modified_files(&mut self) {
+        let files = self.modified_files();
+        if files.is_empty() {
+            self.toast_info("No modified files to open");
+            return;
+        }
+
+        self.open_editor_screen();
+        let Some(screen) = self.editor_screen.as_mut() else {
+            return;
+        };
+        let mut failed = Vec::new();
+        for file in &files {
+            if screen.open(file).is_err() {
+                failed.push(file.display().to_string());
+            }
+        }
+        // Show the first modified file
+        if let Some(first) = files.first() {
+            let _ = screen.open(first);
+        }
+
+        if !failed.is_empty() {
+            self.toast_warning(format!("Could not open: {}", failed.join(", ")));
+        }
+    }
+
+    /// Files modified by the agent, from live progress and final results
+    fn modified_files(&self) -> Vec<PathBuf> {
+        let Some(state) = self.evaluation_state.as_ref() else {
+            return Vec::new();
+        };
+        let mut files: Vec<PathBuf> = Vec::new();
+        let progress = state.progress.iter().flat_map(|p| p.files_modified.iter());
+        let results = state
+            .results
+            .iter()
+            .flat_map(|r| r.results.iter())
+            .flat_map(|t| t.files_modified.iter());
+        for file in progress.chain(results) {
+            if !files.contains(file) {
+                files.push(file.clone());
+            }
+        }
+        files
+    }
+
+    /// Close the editor screen and return to the main interface
+    ///
+    /// Buffers stay open for the next visit.
+    pub(crate) fn close_editor_screen(&mut self) {
+        self.screen = AppScreen::Main;
+        self.status_message = "Closed Editor".to_string();
+    }
+
+    /// Act on the result of an editor key press
+    pub(crate) fn handle_editor_outcome(&mut self, outcome: EditorOutcome) {
+        match outcome {
+            EditorOutcome::None => {}
+            EditorOutcome::Message(message) => self.status_message = message,
+            EditorOutcome::Saved(path) => {
+                let path = path.strip_prefix(&self.working_directory).unwrap_or(&path);
+                self.toast_success(format!("Saved {}", path.display()));
+            }
+            EditorOutcome::Error(message) => self.toast_error(message),
+            EditorOutcome::Copy(text) => match self.clipboard.as_mut().map(|c| c.copy(&text)) {
+                Some(Ok(())) => self.status_message = "Copied selection".to_string(),
+                Some(Err(e)) => self.toast_error(format!("Failed to copy: {}", e)),
+                None => self.toast_error("Clipboard not available"),
+            },
+            EditorOutcome::Close => self.close_editor_screen(),
+        }
+    }
+
+    /// Periodically check open buffers for changes made on disk
+    ///
+    /// Called on every tick; only does work every [`EXTERNAL_CHECK_TICKS`]
+    /// ticks while the editor screen is shown.
+    pub(crate) fn check_editor_external_changes(&mut self) {
+        if self.screen != AppScreen::Editor || !self.tick_count.is_multiple_of(EXTERNAL_CHECK_TICKS)
+        {
+            return;
+        }
+        let Some(screen) = self.editor_screen.as_mut() else {
+            return;
+        };
+        for outcome in screen.check_external_changes() {
+            match outcome {
+                EditorOutcome::Message(message) => self.toast_info(message),
+                EditorOutcome::Error(message) => self.toast_warning(message),
+                other => self.handle_editor_outcome(other),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::app::App;
+    use crate::core::app_state::{AppScreen, EvaluationState};
+    use crate::core::event::{EvaluationProgress, Event};
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn app_in(dir: &TempDir) -> App {
+        let mut app = App::new();
+        app.screen = AppScreen::Main;
+        app.working_directory = dir.path().to_path_buf();
+        app
+    }
+
+    #[test]
+    fn test_open_and_close_editor() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("a.txt"), "hello").unwrap();
+        let mut app = app_in(&dir);
+
+        app.open_in_editor("a.txt");
+        assert_eq!(app.screen, AppScreen::Editor);
+        assert_eq!(app.editor_screen().unwrap().buffers().len(), 1);
+
+        app.close_editor_screen();
+        assert_eq!(app.screen, AppScreen::Main);
+        assert!(app.editor_screen.is_some());
+    }
+
+    #[test]
+    fn test_open_missing_file_shows_error() {
+        let dir = TempDir::new().unwrap();
+        let mut app = app_in(&dir);
+        app.open_in_editor("missing.txt");
+        assert_eq!(app.screen, AppScreen::Editor);
+        assert!(app.editor_screen().unwrap().buffers().is_empty());
+    }
+
+    #[test]
+    fn test_open_modified_files_from_evaluation() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("x.rs"), "fn x() {}").unwrap();
+        fs::write(dir.path().join("y.rs"), "fn y() {}").unwrap();
+        let mut app = app_in(&dir);
+
+        let mut progress = EvaluationProgress::new(1, 1, "task".to_string());
+        progress.files_modified = vec!["x.rs".into(), "y.rs".into(), "x.rs".into()];
+        app.evaluation_state = Some(EvaluationState {
+            handle: None,
+            progress: Some(progress),
+            results: None,
+            error: None,
+            recent_runs: Vec::new(),
+        });
+
+        app.open_modified_files();
+        let screen = app.editor_screen().unwrap();
+        assert_eq!(screen.buffers().len(), 2);
+        assert_eq!(screen.active_buffer().unwrap().display_name(), "x.rs");
+    }
+
+    #[test]
+    fn test_external_change_reloaded_on_tick() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("a.txt");
+        fs::write(&path, "before").unwrap();
+        let mut app = app_in(&dir);
+        app.open_in_editor("a.txt");
+
+        fs::write(&path, "after the agent edit").unwrap();
+        for _ in 0..8 {
+            app.update(Event::Tick).unwrap();
+        }
+        let buffer = app.editor_screen().unwrap().active_buffer().unwrap();
+        assert_eq!(buffer.text(), "after the agent edit");
+    }
+}
@@ -2,6 +2,7 @@
 //!
 //! Handles starting, stopping, and managing evaluation tasks and comparisons.
 
+use crate::ai::evaluation::{ExperimentManager, HistoryFilter, Metric};
 use crate::core::app::App;
 use crate::core::app_state::{AppScreen, EvaluationState};
 
@@ -47,6 +48,7 @@ impl App {
                 progress: None,
                 results: None,
                 error: None,
+                recent_runs: Vec::new(),
             });
 
             self.screen = AppScreen::Evaluation;
@@ -102,6 +104,7 @@ impl App {
                 progress: None,
                 results: None,
                 error: None,
+                recent_runs: Vec::new(),
             });
 
             self.screen = AppScreen::Evaluation;
@@ -157,6 +160,23 @@ impl App {
     }
 }
 
+/// Runs shown in the evaluation screen's sparkline
+const RECENT_RUNS: usize = 20;
+
+/// Accuracy of the most recent runs in the experiment history, oldest first
+///
+/// Empty if the history can't be read.
+pub(crate) fn recent_accuracy() -> Vec<f64> {
+    let manager = ExperimentManager::default();
+    match manager.trend(Metric::Accuracy, &HistoryFilter::default(), RECENT_RUNS) {
+        Ok(points) => points.iter().map(|p| p.value).collect(),
+        Err(e) => {
+            tracing::warn!("Failed to read experiment history: {}", e);
+            Vec::new()
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::core::app::App;
@@ -218,6 +238,7 @@ mod tests {
             progress: None,
             results: None,
             error: None,
+            recent_runs: Vec::new(),
         });
 
         let mut progress = EvaluationProgress::new(3, 10, "task-456".to_string());
@@ -247,6 +268,7 @@ mod tests {
             progress: None,
             results: None,
             error: None,
+            recent_runs: Vec::new(),
         });
 
         let progress = EvaluationProgress::new(7, 15, "task-789".to_string());
@@ -274,6 +296,7 @@ mod tests {
             progress: None,
             results: None,
             error: None,
+            recent_runs: Vec::new(),
         });
 
         let results = EvaluationResults {
@@ -311,6 +334,7 @@ mod tests {
             progress: None,
             results: None,
             error: None,
+            recent_runs: Vec::new(),
         });
 
         let error = "Network timeout".to_string();
@@ -334,6 +358,7 @@ mod tests {
             progress: None,
             results: None,
             error: None,
+            recent_runs: Vec::new(),
         });
 
         let error_msg = "API rate limit exceeded".to_string();
@@ -355,6 +380,7 @@ mod tests {
             progress: None,
             results: None,
             error: None,
+            recent_runs: Vec::new(),
         });
 
         let event = Event::EvaluationError("Error".to_string());
@@ -0,0 +1,177 @@
+//! Calendar, table and mind map screen event handlers
+//!
+//! Forwards keys to the
+//! [`CalendarScreen`](crate::ui::screens::calendar::CalendarScreen),
+//! [`TableViewScreen`](crate::ui::screens::table_view::TableViewScreen) and
+//! [`MindMapScreen`](crate::ui::screens::mind_map::MindMapScreen), and acts
+//! on their outcomes. `Ctrl+1`..`Ctrl+6` switch to another board view from
+//! any of them.
+
+use crate::core::app::App;
+use crate::core::app_board_views::board_view_shortcut;
+use chrono::Utc;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+impl App {
+    /// Handle keys on the calendar screen
+    ///
+    /// # Behavior
+    ///
+    /// - `Ctrl+C`: Quit the application
+    /// - `Ctrl+1`..`Ctrl+6`: Switch board view
+    /// - `h`/`l`, `j`/`k` (or arrows): Previous/next day and week
+    /// - `[`/`]`: Previous/next month, week or day
+    /// - `Tab`/`Shift+Tab`: Select a card on the day
+    /// - `H`/`L`: Move the card's due date a day earlier/later
+    /// - `m`: Cycle month, week and day layouts
+    /// - `w`: Show or hide weekends
+    /// - `t`: Jump to today
+    /// - `/`: Filter cards
+    /// - `Esc`: Clear the filter, then back to the main screen
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if state transition fails (should not happen in practice).
+    pub(crate) fn handle_calendar_key(&mut self, key: KeyEvent) -> crate::Result<()> {
+        if key.code == KeyCode::Char('c') && key.modifiers == KeyModifiers::CONTROL {
+            self.should_quit = true;
+            return Ok(());
+        }
+        if let Some(view) = board_view_shortcut(&key) {
+            self.open_board_view(view);
+            return Ok(());
+        }
+
+        let Some(screen) = self.calendar_screen.as_mut() else {
+            self.close_calendar_screen();
+            return Ok(());
+        };
+        let outcome = screen.handle_key(key, &mut self.board, Utc::now().date_naive());
+        self.handle_calendar_outcome(outcome);
+        Ok(())
+    }
+
+    /// Handle keys on the table screen
+    ///
+    /// # Behavior
+    ///
+    /// - `Ctrl+C`: Quit the application
+    /// - `Ctrl+1`..`Ctrl+6`: Switch board view
+    /// - `j`/`k`, `h`/`l` (or arrows): Select row and column
+    /// - `s`: Sort by the selected column (again to reverse), `S` to unsort
+    /// - `e`/`Enter`: Edit the selected cell (`Enter` saves, `Esc` cancels)
+    /// - `#`: Show or hide row numbers
+    /// - `/`: Filter cards
+    /// - `Esc`: Clear the filter, then back to the main screen
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if state transition fails (should not happen in practice).
+    pub(crate) fn handle_table_key(&mut self, key: KeyEvent) -> crate::Result<()> {
+        if key.code == KeyCode::Char('c') && key.modifiers == KeyModifiers::CONTROL {
+            self.should_quit = true;
+            return Ok(());
+        }
+        if let Some(view) = board_view_shortcut(&key) {
+            self.open_board_view(view);
+            return Ok(());
+        }
+
+        let Some(screen) = self.table_screen.as_mut() else {
+            self.close_table_screen();
+            return Ok(());
+        };
+        let outcome = screen.handle_key(key, &mut self.board);
+        self.handle_table_outcome(outcome);
+        Ok(())
+    }
+
+    /// Handle keys on the mind map screen
+    ///
+    /// # Behavior
+    ///
+    /// - `Ctrl+C`: Quit the application
+    /// - `Ctrl+1`..`Ctrl+6`: Switch board view
+    /// - `j`/`k` (or arrows): Select node
+    /// - `l`/`h` (or arrows): Expand, or collapse and go to the parent
+    /// - `Space`: Expand or collapse
+    /// - `E`/`C`: Expand or collapse everything
+    /// - `Enter`: Follow a dependency to its card
+    /// - `d`: Show or hide card details
+    /// - `/`: Filter cards
+    /// - `Esc`: Clear the filter, then back to the main screen
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if state transition fails (should not happen in practice).
+    pub(crate) fn handle_mind_map_key(&mut self, key: KeyEvent) -> crate::Result<()> {
+        if key.code == KeyCode::Char('c') && key.modifiers == KeyModifiers::CONTROL {
+            self.should_quit = true;
+            return Ok(());
+        }
+        if let Some(view) = board_view_shortcut(&key) {
+            self.open_board_view(view);
+            return Ok(());
+        }
+
+        let Some(screen) = self.mind_map_screen.as_mut() else {
+            self.close_mind_map_screen();
+            return Ok(());
+        };
+        let outcome = screen.handle_key(key, &mut self.board);
+        self.handle_mind_map_outcome(outcome);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::app::App;
+    use crate::core::app_state::AppScreen;
+    use crate::core::event::Event;
+    use crate::infrastructure::rich_task_cards::Priority;
+    use chrono::{Duration, Utc};
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use tempfile::TempDir;
+
+    fn press(app: &mut App, code: KeyCode) {
+        app.update(Event::Key(KeyEvent::new(code, KeyModifiers::NONE)))
+            .unwrap();
+    }
+
+    #[test]
+    fn test_calendar_reschedule_marks_board_dirty() {
+        let dir = TempDir::new().unwrap();
+        let mut app = App::new();
+        app.working_directory = dir.path().to_path_buf();
+        app.load_board();
+        let card = app.board_mut().cards.create_card(
+            "Ship".to_string(),
+            String::new(),
+            "To Do".to_string(),
+            Priority::High,
+            "me".to_string(),
+        );
+        let due = Utc::now();
+        app.board_mut().cards.get_card_mut(&card).unwrap().due_date = Some(due);
+        app.save_board().unwrap();
+        app.open_calendar_screen();
+
+        press(&mut app, KeyCode::Char('L'));
+        assert!(app.board_dirty);
+        assert_eq!(
+            app.board().cards.get_card(&card).unwrap().due_date,
+            Some(due + Duration::days(1))
+        );
+    }
+
+    #[test]
+    fn test_board_view_keys_without_screen_return_to_main() {
+        let mut app = App::new();
+        for screen in [AppScreen::Calendar, AppScreen::Table, AppScreen::MindMap] {
+            app.screen = screen;
+            press(&mut app, KeyCode::Char('j'));
+            assert_eq!(app.screen, AppScreen::Main);
+        }
+    }
+}
@@ -0,0 +1,90 @@
+//! Editor screen event handlers
+//!
+//! Forwards keys to the [`EditorScreen`](crate::ui::screens::editor::EditorScreen)
+//! and acts on the outcome.
+
+use crate::core::app::App;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+impl App {
+    /// Handle keys on the editor screen
+    ///
+    /// # Behavior
+    ///
+    /// - `Ctrl+C`: Quit the application
+    /// - **File tree**: `j`/`k` move, `Enter` open file or toggle directory,
+    ///   `Tab` focus the buffer, `Esc` back to the buffer (or main screen)
+    /// - **Normal**: vim motions (`h`/`j`/`k`/`l`, `w`/`b`/`e`, `0`/`$`, `gg`/`G`,
+    ///   `f`/`t`), `i`/`a`/`A`/`I`/`o`/`O` insert, `x`/`dd` delete, `u`/`Ctrl+R`
+    ///   undo/redo, `v`/`V`/`Ctrl+V` visual, `Ctrl+N` add a cursor below,
+    ///   `m`/`'` marks, `q`/`@` macros, `]`/`[` switch buffers, `Ctrl+S` save,
+    ///   `:` command line, `Esc` back to main screen
+    /// - **Command line**: `:w`, `:w!`, `:wq`, `:q`, `:q!`, `:e!`, `:e <path>`,
+    ///   `:bd`, `:<line>`
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if state transition fails (should not happen in practice).
+    pub(crate) fn handle_editor_key(&mut self, key: KeyEvent) -> crate::Result<()> {
+        if key.code == KeyCode::Char('c') && key.modifiers == KeyModifiers::CONTROL {
+            self.should_quit = true;
+            return Ok(());
+        }
+
+        let Some(screen) = self.editor_screen.as_mut() else {
+            self.close_editor_screen();
+            return Ok(());
+        };
+        let outcome = screen.handle_key(key);
+        self.handle_editor_outcome(outcome);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::app::App;
+    use crate::core::app_state::AppScreen;
+    use crate::core::event::Event;
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn press(app: &mut App, code: KeyCode) {
+        app.update(Event::Key(KeyEvent::new(code, KeyModifiers::NONE)))
+            .unwrap();
+    }
+
+    #[test]
+    fn test_editor_edit_save_and_close() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("notes.md");
+        fs::write(&path, "world\n").unwrap();
+
+        let mut app = App::new();
+        app.working_directory = dir.path().to_path_buf();
+        app.open_in_editor("notes.md");
+
+        for c in "ihello ".chars() {
+            press(&mut app, KeyCode::Char(c));
+        }
+        press(&mut app, KeyCode::Esc);
+        app.update(Event::Key(KeyEvent::new(
+            KeyCode::Char('s'),
+            KeyModifiers::CONTROL,
+        )))
+        .unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "hello world\n");
+
+        press(&mut app, KeyCode::Esc);
+        assert_eq!(app.screen, AppScreen::Main);
+    }
+
+    #[test]
+    fn test_editor_key_without_screen_returns_to_main() {
+        let mut app = App::new();
+        app.screen = AppScreen::Editor;
+        press(&mut app, KeyCode::Char('j'));
+        assert_eq!(app.screen, AppScreen::Main);
+    }
+}
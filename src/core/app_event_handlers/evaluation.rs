@@ -13,6 +13,7 @@ impl App {
     ///
     /// - `Esc` or `Ctrl+C`: Cancel running evaluation (or return to main if complete)
     /// - `q`: Return to main screen (only if evaluation is complete)
+    /// - `e`: Open the files the agent modified in the editor
     /// - Other keys: Ignored during evaluation
     ///
     /// # State Handling
@@ -67,6 +68,9 @@ impl App {
                     self.screen = AppScreen::Main;
                 }
             }
+            (KeyCode::Char('e'), KeyModifiers::NONE) => {
+                self.open_modified_files();
+            }
             // Other keys are ignored during evaluation
             _ => {}
         }
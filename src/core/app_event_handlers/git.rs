@@ -0,0 +1,379 @@
+//! Git screen event handlers
+//!
+//! Handles keyboard events on the git screen: panel navigation, staging,
+//! commits, branch switching/creation, graph scrolling and conflict resolution.
+
+use crate::core::app::App;
+use crate::ui::screens::git::GitPanel;
+use crate::ui::widgets::git::git_branch_manager::BranchMode;
+use crate::ui::widgets::git::git_commit_dialog::CommitDialogState;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+impl App {
+    /// Handle keys on the git screen
+    ///
+    /// # Behavior
+    ///
+    /// - `Esc` / `q`: Return to main screen
+    /// - `Tab` / `Shift+Tab` / `1`-`4`: Switch panel
+    /// - `r`: Refresh, `c`: Open commit dialog, `p`: Generate a pull request description
+    /// - **Status**: `j`/`k` move, `h`/`l` switch pane, `Space` stage/unstage,
+    ///   `a` stage all, `u` unstage all, `Enter` show diff, `]`/`[` next/previous
+    ///   hunk, `s` stage/unstage hunk
+    /// - **Branches**: `j`/`k`/`g`/`G` move, `Enter` switch, `n` new branch
+    /// - **Graph**: `j`/`k` scroll
+    /// - **Conflicts**: `j`/`k` move, `o` ours, `t` theirs, `b` both, `v` view mode,
+    ///   `a` ask the agent, `y`/`n` accept/reject its proposal, `w` write resolved files
+    ///
+    /// While the pull request description, commit dialog or branch name input
+    /// is open, keys go there first. In the commit dialog `Ctrl+G` generates a
+    /// message from the staged diff.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if state transition fails (should not happen in practice).
+    pub(crate) fn handle_git_key(&mut self, key: KeyEvent) -> crate::Result<()> {
+        let Some(screen) = self.git_screen.as_ref() else {
+            self.close_git_screen();
+            return Ok(());
+        };
+        let panel = screen.panel();
+
+        if screen.pull_request().is_some() {
+            match key.code {
+                KeyCode::Char('y') => self.git_copy_pull_request(),
+                KeyCode::Esc | KeyCode::Char('q') => {
+                    if let Some(screen) = self.git_screen.as_mut() {
+                        screen.set_pull_request(None);
+                    }
+                }
+                _ => {}
+            }
+            return Ok(());
+        }
+        if screen.commit_dialog().is_visible() {
+            return self.handle_git_commit_dialog_key(key);
+        }
+        if panel == GitPanel::Branches && screen.branches().mode() == BranchMode::Create {
+            return self.handle_git_branch_input_key(key);
+        }
+
+        match (key.code, key.modifiers) {
+            (KeyCode::Char('c'), KeyModifiers::CONTROL) => {
+                self.should_quit = true;
+                return Ok(());
+            }
+            (KeyCode::Esc, _) | (KeyCode::Char('q'), KeyModifiers::NONE) => {
+                self.close_git_screen();
+                return Ok(());
+            }
+            (KeyCode::Char('r'), KeyModifiers::NONE) => {
+                self.refresh_git();
+                return Ok(());
+            }
+            (KeyCode::Char('c'), KeyModifiers::NONE) => {
+                self.git_open_commit_dialog();
+                return Ok(());
+            }
+            (KeyCode::Char('p'), KeyModifiers::NONE) => {
+                self.git_generate_pull_request();
+                return Ok(());
+            }
+            _ => {}
+        }
+
+        let Some(screen) = self.git_screen.as_mut() else {
+            return Ok(());
+        };
+        match key.code {
+            KeyCode::Tab => {
+                screen.set_panel(panel.next());
+                return Ok(());
+            }
+            KeyCode::BackTab => {
+                screen.set_panel(panel.previous());
+                return Ok(());
+            }
+            KeyCode::Char(c @ '1'..='4') => {
+                screen.set_panel(GitPanel::ALL[c as usize - '1' as usize]);
+                return Ok(());
+            }
+            _ => {}
+        }
+
+        match panel {
+            GitPanel::Status => self.handle_git_status_key(key),
+            GitPanel::Branches => self.handle_git_branches_key(key),
+            GitPanel::Graph => {
+                let graph = screen.graph_mut();
+                match key.code {
+                    KeyCode::Char('j') | KeyCode::Down => graph.scroll_down(),
+                    KeyCode::Char('k') | KeyCode::Up => graph.scroll_up(),
+                    KeyCode::Char('g') | KeyCode::Home => graph.set_scroll_offset(0),
+                    _ => {}
+                }
+                Ok(())
+            }
+            GitPanel::Conflicts => {
+                match key.code {
+                    KeyCode::Char('a') => {
+                        self.git_propose_conflict_resolution();
+                        return Ok(());
+                    }
+                    KeyCode::Char('w') => {
+                        self.git_write_resolutions();
+                        return Ok(());
+                    }
+                    _ => {}
+                }
+                let resolver = screen.conflicts_mut();
+                match key.code {
+                    KeyCode::Char('j') | KeyCode::Down => resolver.next_conflict(),
+                    KeyCode::Char('k') | KeyCode::Up => resolver.previous_conflict(),
+                    KeyCode::Char('o') => resolver.choose_ours(),
+                    KeyCode::Char('t') => resolver.choose_theirs(),
+                    KeyCode::Char('b') => resolver.choose_both(),
+                    KeyCode::Char('v') => resolver.toggle_view_mode(),
+                    KeyCode::Char('y') => {
+                        resolver.accept_proposal();
+                    }
+                    KeyCode::Char('n') => {
+                        resolver.reject_proposal();
+                    }
+                    _ => {}
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Handle keys on the status panel
+    fn handle_git_status_key(&mut self, key: KeyEvent) -> crate::Result<()> {
+        let Some(screen) = self.git_screen.as_mut() else {
+            return Ok(());
+        };
+
+        match key.code {
+            KeyCode::Char('j') | KeyCode::Down => screen.stage_mut().move_down(),
+            KeyCode::Char('k') | KeyCode::Up => screen.stage_mut().move_up(),
+            KeyCode::Char('g') | KeyCode::Home => screen.stage_mut().jump_to_top(),
+            KeyCode::Char('G') | KeyCode::End => screen.stage_mut().jump_to_bottom(),
+            KeyCode::Char('h') | KeyCode::Char('l') | KeyCode::Left | KeyCode::Right => {
+                screen.stage_mut().toggle_focus()
+            }
+            KeyCode::Char(']') => {
+                if let Some(diff) = screen.diff_mut() {
+                    diff.next_hunk();
+                }
+            }
+            KeyCode::Char('[') => {
+                if let Some(diff) = screen.diff_mut() {
+                    diff.prev_hunk();
+                }
+            }
+            KeyCode::Char(' ') => self.git_toggle_stage_selected(),
+            KeyCode::Char('a') => self.git_stage_all(),
+            KeyCode::Char('u') => self.git_unstage_all(),
+            KeyCode::Char('s') => self.git_toggle_stage_hunk(),
+            KeyCode::Enter => self.git_load_selected_diff(),
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Handle keys on the branches panel
+    fn handle_git_branches_key(&mut self, key: KeyEvent) -> crate::Result<()> {
+        let Some(screen) = self.git_screen.as_mut() else {
+            return Ok(());
+        };
+        let branches = screen.branches_mut();
+
+        match key.code {
+            KeyCode::Char('j') | KeyCode::Down => branches.move_down(),
+            KeyCode::Char('k') | KeyCode::Up => branches.move_up(),
+            KeyCode::Char('g') | KeyCode::Home => branches.jump_to_top(),
+            KeyCode::Char('G') | KeyCode::End => branches.jump_to_bottom(),
+            KeyCode::Char('n') => branches.set_mode(BranchMode::Create),
+            KeyCode::Enter => self.git_checkout_selected_branch(),
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Handle keys while typing a new branch name
+    fn handle_git_branch_input_key(&mut self, key: KeyEvent) -> crate::Result<()> {
+        let Some(screen) = self.git_screen.as_mut() else {
+            return Ok(());
+        };
+        let branches = screen.branches_mut();
+
+        match key.code {
+            KeyCode::Esc => branches.cancel(),
+            KeyCode::Enter => self.git_create_branch(),
+            KeyCode::Backspace => branches.backspace(),
+            KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                branches.insert_char(c)
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Handle keys while the commit dialog is open
+    ///
+    /// `Ctrl+S` (or `Ctrl+Enter` where the terminal reports it) commits,
+    /// `Ctrl+G` generates a message, `Enter` inserts a newline and `Esc`
+    /// closes the dialog. Only `Esc` works while a message is generated.
+    fn handle_git_commit_dialog_key(&mut self, key: KeyEvent) -> crate::Result<()> {
+        let Some(screen) = self.git_screen.as_mut() else {
+            return Ok(());
+        };
+        let dialog = screen.commit_dialog_mut();
+
+        if dialog.is_generating() {
+            if key.code == KeyCode::Esc {
+                dialog.hide();
+            }
+            return Ok(());
+        }
+
+        match dialog.state() {
+            CommitDialogState::Committing | CommitDialogState::Hidden => {}
+            CommitDialogState::Success => dialog.hide(),
+            CommitDialogState::Error => match key.code {
+                KeyCode::Esc => dialog.hide(),
+                _ => dialog.resume_editing(),
+            },
+            CommitDialogState::Editing => match (key.code, key.modifiers) {
+                (KeyCode::Char('s'), KeyModifiers::CONTROL)
+                | (KeyCode::Enter, KeyModifiers::CONTROL) => self.git_commit(),
+                (KeyCode::Char('g'), KeyModifiers::CONTROL) => self.git_generate_commit_message(),
+                (KeyCode::Esc, _) => dialog.hide(),
+                (KeyCode::Enter, _) => dialog.insert_newline(),
+                (KeyCode::Backspace, _) => dialog.backspace(),
+                (KeyCode::Delete, _) => dialog.delete(),
+                (KeyCode::Left, _) => dialog.move_cursor_left(),
+                (KeyCode::Right, _) => dialog.move_cursor_right(),
+                (KeyCode::Home, _) => dialog.move_cursor_to_line_start(),
+                (KeyCode::End, _) => dialog.move_cursor_to_line_end(),
+                (KeyCode::Char(c), modifiers) if !modifiers.contains(KeyModifiers::CONTROL) => {
+                    dialog.insert_char(c)
+                }
+                _ => {}
+            },
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::app::App;
+    use crate::core::app_state::AppScreen;
+    use crate::ui::screens::git::GitPanel;
+    use crate::ui::widgets::git::git_branch_manager::BranchMode;
+    use crate::ui::widgets::git::git_commit_dialog::CommitDialogState;
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use tempfile::TempDir;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn git_app() -> (App, TempDir) {
+        let dir = TempDir::new().unwrap();
+        std::fs::create_dir(dir.path().join(".git")).unwrap();
+        let mut app = App::new();
+        app.working_directory = dir.path().to_path_buf();
+        app.open_git_screen();
+        (app, dir)
+    }
+
+    #[test]
+    fn test_git_key_without_screen_returns_to_main() {
+        let mut app = App::new();
+        app.screen = AppScreen::Git;
+        app.handle_git_key(key(KeyCode::Char('j'))).unwrap();
+        assert_eq!(app.screen, AppScreen::Main);
+    }
+
+    #[test]
+    fn test_git_esc_returns_to_main() {
+        let (mut app, _dir) = git_app();
+        assert_eq!(app.screen, AppScreen::Git);
+
+        app.handle_git_key(key(KeyCode::Esc)).unwrap();
+        assert_eq!(app.screen, AppScreen::Main);
+    }
+
+    #[test]
+    fn test_git_panel_navigation() {
+        let (mut app, _dir) = git_app();
+        let panel = |app: &App| app.git_screen.as_ref().unwrap().panel();
+
+        assert_eq!(panel(&app), GitPanel::Status);
+        app.handle_git_key(key(KeyCode::Tab)).unwrap();
+        assert_eq!(panel(&app), GitPanel::Branches);
+        app.handle_git_key(key(KeyCode::BackTab)).unwrap();
+        assert_eq!(panel(&app), GitPanel::Status);
+        app.handle_git_key(key(KeyCode::Char('3'))).unwrap();
+        assert_eq!(panel(&app), GitPanel::Graph);
+        app.handle_git_key(key(KeyCode::Char('4'))).unwrap();
+        assert_eq!(panel(&app), GitPanel::Conflicts);
+    }
+
+    #[test]
+    fn test_git_commit_dialog_captures_typing() {
+        let (mut app, _dir) = git_app();
+
+        app.handle_git_key(key(KeyCode::Char('c'))).unwrap();
+        assert!(
+            app.git_screen
+                .as_ref()
+                .unwrap()
+                .commit_dialog()
+                .is_visible()
+        );
+
+        // 'q' is typed into the message rather than leaving the screen
+        for c in "fix: q".chars() {
+            app.handle_git_key(key(KeyCode::Char(c))).unwrap();
+        }
+        assert_eq!(app.screen, AppScreen::Git);
+        assert_eq!(
+            app.git_screen.as_ref().unwrap().commit_dialog().message(),
+            "fix: q"
+        );
+
+        app.handle_git_key(key(KeyCode::Esc)).unwrap();
+        let dialog = app.git_screen.as_ref().unwrap().commit_dialog();
+        assert_eq!(dialog.state(), CommitDialogState::Hidden);
+    }
+
+    #[test]
+    fn test_git_branch_create_mode_captures_typing() {
+        let (mut app, _dir) = git_app();
+
+        app.handle_git_key(key(KeyCode::Char('2'))).unwrap();
+        app.handle_git_key(key(KeyCode::Char('n'))).unwrap();
+        assert_eq!(
+            app.git_screen.as_ref().unwrap().branches().mode(),
+            BranchMode::Create
+        );
+
+        for c in "feat/x".chars() {
+            app.handle_git_key(key(KeyCode::Char(c))).unwrap();
+        }
+        assert_eq!(
+            app.git_screen.as_ref().unwrap().branches().input_buffer(),
+            "feat/x"
+        );
+
+        app.handle_git_key(key(KeyCode::Esc)).unwrap();
+        assert_eq!(
+            app.git_screen.as_ref().unwrap().branches().mode(),
+            BranchMode::Browse
+        );
+        assert_eq!(app.screen, AppScreen::Git);
+    }
+}
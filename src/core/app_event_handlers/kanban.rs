@@ -0,0 +1,109 @@
+//! Kanban screen event handlers
+//!
+//! Forwards keys and mouse events to the
+//! [`KanbanScreen`](crate::ui::screens::kanban::KanbanScreen) and acts on the
+//! outcome.
+
+use crate::core::app::App;
+use crate::core::app_board_views::board_view_shortcut;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseEvent};
+
+impl App {
+    /// Handle keys on the kanban screen
+    ///
+    /// # Behavior
+    ///
+    /// - `Ctrl+C`: Quit the application
+    /// - `Ctrl+1`..`Ctrl+6`: Switch board view
+    /// - `h`/`l`, `j`/`k` (or arrows): Select column and card
+    /// - `H`/`L` (or `Shift+Left`/`Shift+Right`): Move the card to the
+    ///   previous/next column
+    /// - `J`/`K` (or `Shift+Down`/`Shift+Up`): Reorder the card in its column
+    /// - `Enter`: Card details (comments, attachments, activity)
+    /// - `r`: Run the agent on the selected card
+    /// - `n`: New card in the selected column
+    /// - `/`: Filter cards (live, `filtering_search` syntax)
+    /// - `[`/`]`: Previous/next board
+    /// - `Esc`: Clear the filter, then back to the main screen
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if state transition fails (should not happen in practice).
+    pub(crate) fn handle_kanban_key(&mut self, key: KeyEvent) -> crate::Result<()> {
+        if key.code == KeyCode::Char('c') && key.modifiers == KeyModifiers::CONTROL {
+            self.should_quit = true;
+            return Ok(());
+        }
+        if let Some(view) = board_view_shortcut(&key) {
+            self.open_board_view(view);
+            return Ok(());
+        }
+
+        let Some(screen) = self.kanban_screen.as_mut() else {
+            self.close_kanban_screen();
+            return Ok(());
+        };
+        let outcome = screen.handle_key(key, &mut self.board);
+        self.handle_kanban_outcome(outcome);
+        Ok(())
+    }
+
+    /// Handle mouse events on the kanban screen (select, drag and drop)
+    pub(crate) fn handle_kanban_mouse(&mut self, mouse: MouseEvent) {
+        let Some(screen) = self.kanban_screen.as_mut() else {
+            return;
+        };
+        let outcome = screen.handle_mouse(mouse, &mut self.board);
+        self.handle_kanban_outcome(outcome);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::app::App;
+    use crate::core::app_state::AppScreen;
+    use crate::core::event::Event;
+    use crate::infrastructure::rich_task_cards::Priority;
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use tempfile::TempDir;
+
+    fn press(app: &mut App, code: KeyCode) {
+        app.update(Event::Key(KeyEvent::new(code, KeyModifiers::NONE)))
+            .unwrap();
+    }
+
+    #[test]
+    fn test_kanban_move_card_and_close() {
+        let dir = TempDir::new().unwrap();
+        let mut app = App::new();
+        app.working_directory = dir.path().to_path_buf();
+        app.load_board();
+        let card = app.board_mut().cards.create_card(
+            "Fix login".to_string(),
+            String::new(),
+            "To Do".to_string(),
+            Priority::High,
+            "me".to_string(),
+        );
+        app.open_kanban_screen();
+        app.save_board().unwrap();
+
+        press(&mut app, KeyCode::Char('L'));
+        assert!(app.board_dirty);
+        assert_eq!(
+            app.board().cards.get_card(&card).unwrap().status,
+            "In Progress"
+        );
+
+        press(&mut app, KeyCode::Esc);
+        assert_eq!(app.screen, AppScreen::Main);
+    }
+
+    #[test]
+    fn test_kanban_key_without_screen_returns_to_main() {
+        let mut app = App::new();
+        app.screen = AppScreen::Kanban;
+        press(&mut app, KeyCode::Char('j'));
+        assert_eq!(app.screen, AppScreen::Main);
+    }
+}
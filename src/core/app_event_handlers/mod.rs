@@ -9,6 +9,11 @@
 //! - [`trust_dialog`]: Trust confirmation dialog event handling
 //! - [`main_screen`]: Main TUI interface event handling (largest handler)
 //! - [`evaluation`]: Evaluation progress/results screen event handling
+//! - [`git`]: Git workflow screen event handling
+//! - [`editor`]: File editor screen event handling
+//! - [`kanban`]: Kanban board screen event handling
+//! - [`timeline`]: Timeline (Gantt) screen event handling
+//! - [`board_views`]: Calendar, table and mind map screen event handling
 //!
 //! # Architecture
 //!
@@ -25,10 +30,22 @@
 //!     TrustDialog  → handle_trust_dialog_key()
 //!     Main         → handle_main_key()
 //!     Evaluation   → handle_evaluation_key()
+//!     Git          → handle_git_key()
+//!     Editor       → handle_editor_key()
+//!     Kanban       → handle_kanban_key()
+//!     Timeline     → handle_timeline_key()
+//!     Calendar     → handle_calendar_key()
+//!     Table        → handle_table_key()
+//!     MindMap      → handle_mind_map_key()
 //! }
 //! ```
 
+mod board_views;
+mod editor;
 mod evaluation;
+mod git;
+mod kanban;
 mod main_screen;
+mod timeline;
 mod trust_dialog;
 mod welcome;
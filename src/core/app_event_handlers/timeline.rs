@@ -0,0 +1,48 @@
+//! Timeline screen event handlers
+//!
+//! Forwards keys to the
+//! [`TimelineScreen`](crate::ui::screens::timeline::TimelineScreen) and acts
+//! on the outcome.
+
+use crate::core::app::App;
+use crate::core::app_board_views::board_view_shortcut;
+use chrono::Utc;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+impl App {
+    /// Handle keys on the timeline screen
+    ///
+    /// # Behavior
+    ///
+    /// - `Ctrl+C`: Quit the application
+    /// - `Ctrl+1`..`Ctrl+6`: Switch board view
+    /// - `j`/`k` (or arrows): Select card
+    /// - `h`/`l` (or arrows): Move the card's start a day earlier/later
+    /// - `H`/`L`: Move the card's start a week earlier/later
+    /// - `u`: Unpin the card's start
+    /// - `z`: Cycle zoom (days, weeks, months, quarters)
+    /// - `c`: Show only the critical path
+    /// - `Esc`: Back to the main screen
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if state transition fails (should not happen in practice).
+    pub(crate) fn handle_timeline_key(&mut self, key: KeyEvent) -> crate::Result<()> {
+        if key.code == KeyCode::Char('c') && key.modifiers == KeyModifiers::CONTROL {
+            self.should_quit = true;
+            return Ok(());
+        }
+        if let Some(view) = board_view_shortcut(&key) {
+            self.open_board_view(view);
+            return Ok(());
+        }
+
+        let Some(screen) = self.timeline_screen.as_mut() else {
+            self.close_timeline_screen();
+            return Ok(());
+        };
+        let outcome = screen.handle_key(key, &mut self.board, Utc::now());
+        self.handle_timeline_outcome(outcome);
+        Ok(())
+    }
+}
@@ -0,0 +1,941 @@
+//! Git screen lifecycle and operations
+//!
+//! Opens the git screen, refreshes it from [`GitService`] off the UI thread,
+//! and runs staging, commit and branch operations as background tasks that
+//! report back through git [`Event`]s.
+
+use crate::ai::llm::Message;
+use crate::core::app::App;
+use crate::core::app_state::AppScreen;
+use crate::core::event::Event;
+use crate::git::merge::{parse_resolution, resolution_prompt};
+use crate::git::{CommitMessageGenerator, GitGraphService, GitService, PullRequestDescription};
+use crate::ui::screens::git::{ConflictFile, GitPanel, GitRefresh, GitScreen};
+use crate::ui::widgets::git::git_commit_dialog::CommitDialogState;
+use std::future::Future;
+use std::path::PathBuf;
+
+/// Maximum number of commits loaded into the git graph
+const GRAPH_COMMIT_LIMIT: usize = 200;
+
+impl App {
+    /// Open the git screen for the working directory
+    ///
+    /// Creates the screen on first use and triggers an asynchronous refresh.
+    /// Shows an error toast when the working directory is not a git repository.
+    pub(crate) fn open_git_screen(&mut self) {
+        if self.git_screen.is_none() {
+            match GitService::new(&self.working_directory) {
+                Ok(service) => self.git_screen = Some(GitScreen::new(service)),
+                Err(e) => {
+                    self.toast_error(format!("Git: {}", e));
+                    self.status_message = format!("Git unavailable: {}", e);
+                    return;
+                }
+            }
+        }
+
+        self.screen = AppScreen::Git;
+        self.status_message = "Opened Git".to_string();
+        self.refresh_git();
+    }
+
+    /// Close the git screen and return to the main interface
+    pub(crate) fn close_git_screen(&mut self) {
+        self.screen = AppScreen::Main;
+        self.status_message = "Closed Git".to_string();
+    }
+
+    /// Refresh status, branches, graph and conflicts in the background
+    ///
+    /// The diff shown on the status panel is reloaded as well so it reflects
+    /// hunks staged in the meantime.
+    pub(crate) fn refresh_git(&mut self) {
+        let Some(event_tx) = self.event_tx.clone() else {
+            self.status_message = "Git: event channel not initialized".to_string();
+            return;
+        };
+        let Some(screen) = self.git_screen.as_mut() else {
+            return;
+        };
+
+        screen.set_loading(true);
+        let service = screen.service().clone();
+        let diff_target = screen
+            .diff_target()
+            .map(|(path, staged)| (path.to_path_buf(), staged));
+
+        tokio::spawn(async move {
+            match fetch_git_refresh(&service).await {
+                Ok(refresh) => {
+                    let _ = event_tx.send(Event::GitRefreshed(Box::new(refresh)));
+                }
+                Err(e) => {
+                    let _ = event_tx.send(Event::GitError(e.to_string()));
+                    return;
+                }
+            }
+
+            if let Some((path, staged)) = diff_target
+                && let Ok(diff) = service.file_diff(&path, staged).await
+            {
+                let diff = diff.map(|d| d.to_patch()).unwrap_or_default();
+                let _ = event_tx.send(Event::GitDiffLoaded { path, staged, diff });
+            }
+        });
+    }
+
+    /// Apply refreshed repository data to the git screen
+    pub(crate) fn apply_git_refresh(&mut self, refresh: GitRefresh) {
+        let Some(screen) = self.git_screen.as_mut() else {
+            return;
+        };
+
+        if screen.apply_refresh(refresh) {
+            let count = screen.conflict_files().len();
+            let auto = screen.conflicts().auto_resolved_count();
+            let mut message = format!(
+                "Merge conflicts detected in {} file{}",
+                count,
+                if count == 1 { "" } else { "s" }
+            );
+            if auto > 0 {
+                message.push_str(&format!(" ({} auto-resolved)", auto));
+            }
+            self.toast_warning(message);
+            self.status_message =
+                "Resolve conflicts: o ours · t theirs · b both · a ask agent · w write".to_string();
+        }
+    }
+
+    /// Show a loaded diff on the status panel
+    pub(crate) fn handle_git_diff_loaded(&mut self, path: PathBuf, staged: bool, diff: String) {
+        let Some(screen) = self.git_screen.as_mut() else {
+            return;
+        };
+
+        if diff.trim().is_empty() {
+            if screen
+                .diff_target()
+                .is_some_and(|(p, _)| p == path.as_path())
+            {
+                screen.clear_diff();
+            }
+            self.status_message = format!("No changes in {}", path.display());
+        } else {
+            screen.set_diff(path, staged, &diff);
+        }
+    }
+
+    /// Load the diff for the file under the cursor
+    pub(crate) fn git_load_selected_diff(&mut self) {
+        let Some((path, staged)) = self.git_selected_file() else {
+            return;
+        };
+
+        self.spawn_git_event(move |service| async move {
+            match service.file_diff(&path, staged).await {
+                Ok(diff) => Event::GitDiffLoaded {
+                    path,
+                    staged,
+                    diff: diff.map(|d| d.to_patch()).unwrap_or_default(),
+                },
+                Err(e) => Event::GitError(e.to_string()),
+            }
+        });
+    }
+
+    /// Stage or unstage the file under the cursor
+    pub(crate) fn git_toggle_stage_selected(&mut self) {
+        let Some((path, staged)) = self.git_selected_file() else {
+            return;
+        };
+
+        self.spawn_git_operation(move |service| async move {
+            if staged {
+                service.unstage(&path).await?;
+                Ok(format!("Unstaged: {}", path.display()))
+            } else {
+                service.stage(&path).await?;
+                Ok(format!("Staged: {}", path.display()))
+            }
+        });
+    }
+
+    /// Stage every unstaged file
+    pub(crate) fn git_stage_all(&mut self) {
+        let Some(screen) = self.git_screen.as_ref() else {
+            return;
+        };
+        let paths: Vec<PathBuf> = screen
+            .stage()
+            .unstaged_entries()
+            .iter()
+            .map(|e| e.path.clone())
+            .collect();
+        if paths.is_empty() {
+            return;
+        }
+
+        self.spawn_git_operation(move |service| async move {
+            for path in &paths {
+                service.stage(path).await?;
+            }
+            Ok(format!("Staged {} files", paths.len()))
+        });
+    }
+
+    /// Unstage every staged file
+    pub(crate) fn git_unstage_all(&mut self) {
+        let Some(screen) = self.git_screen.as_ref() else {
+            return;
+        };
+        let paths: Vec<PathBuf> = screen
+            .stage()
+            .staged_entries()
+            .iter()
+            .map(|e| e.path.clone())
+            .collect();
+        if paths.is_empty() {
+            return;
+        }
+
+        self.spawn_git_operation(move |service| async move {
+            for path in &paths {
+                service.unstage(path).await?;
+            }
+            Ok(format!("Unstaged {} files", paths.len()))
+        });
+    }
+
+    /// Stage (or unstage, for a staged diff) the hunk selected in the diff viewer
+    pub(crate) fn git_toggle_stage_hunk(&mut self) {
+        let Some(screen) = self.git_screen.as_ref() else {
+            return;
+        };
+        let Some((path, staged)) = screen
+            .diff_target()
+            .map(|(path, staged)| (path.to_path_buf(), staged))
+        else {
+            self.status_message = "Open a diff with Enter first".to_string();
+            return;
+        };
+        let Some(hunk) = screen.diff().and_then(|d| d.selected_hunk()).cloned() else {
+            return;
+        };
+
+        self.spawn_git_operation(move |service| async move {
+            if staged {
+                service.unstage_hunk(&path, &hunk).await?;
+                Ok(format!("Unstaged hunk in {}", path.display()))
+            } else {
+                service.stage_hunk(&path, &hunk).await?;
+                Ok(format!("Staged hunk in {}", path.display()))
+            }
+        });
+    }
+
+    /// Open the commit dialog
+    pub(crate) fn git_open_commit_dialog(&mut self) {
+        if let Some(screen) = self.git_screen.as_mut() {
+            let staged = screen.snapshot().staged_count();
+            screen.commit_dialog_mut().open(staged);
+        }
+    }
+
+    /// Commit the staged changes with the dialog's message
+    pub(crate) fn git_commit(&mut self) {
+        let Some(screen) = self.git_screen.as_mut() else {
+            return;
+        };
+        let Some(message) = screen.commit_dialog_mut().begin_commit() else {
+            return;
+        };
+
+        self.spawn_git_event(move |service| async move {
+            match service.commit(&message).await {
+                Ok(output) => Event::GitCommitComplete(output),
+                Err(e) => Event::GitError(e.to_string()),
+            }
+        });
+    }
+
+    /// Switch to the branch selected in the branch manager
+    pub(crate) fn git_checkout_selected_branch(&mut self) {
+        let Some(screen) = self.git_screen.as_mut() else {
+            return;
+        };
+        let Some(branch) = screen.branches().selected_branch().cloned() else {
+            return;
+        };
+        if branch.is_current {
+            screen.branches_mut().set_message("Already on this branch");
+            return;
+        }
+
+        self.spawn_git_operation(move |service| async move {
+            service.checkout_branch(&branch.name).await?;
+            Ok(format!("Switched to branch '{}'", branch.name))
+        });
+    }
+
+    /// Create a branch from the branch manager's input and switch to it
+    pub(crate) fn git_create_branch(&mut self) {
+        let Some(screen) = self.git_screen.as_mut() else {
+            return;
+        };
+        let name = screen.branches().input_buffer().trim().to_string();
+        if name.is_empty() {
+            screen
+                .branches_mut()
+                .set_error("Branch name cannot be empty");
+            return;
+        }
+        screen.branches_mut().cancel();
+
+        self.spawn_git_operation(move |service| async move {
+            service.create_branch(&name, true).await?;
+            Ok(format!("Created and switched to branch '{}'", name))
+        });
+    }
+
+    /// Generate a commit message for the staged changes into the commit dialog
+    ///
+    /// Uses the configured LLM client, or a deterministic summary of the diff
+    /// when none is available. The result replaces the dialog's message and
+    /// stays editable.
+    pub(crate) fn git_generate_commit_message(&mut self) {
+        let generator = self.commit_message_generator();
+        let Some(screen) = self.git_screen.as_mut() else {
+            return;
+        };
+        if !screen.commit_dialog_mut().begin_generating() {
+            return;
+        }
+
+        self.status_message = "Generating commit message...".to_string();
+        self.spawn_git_event(move |service| async move {
+            match generator.staged_commit_message(&service).await {
+                Ok(message) => Event::GitCommitMessageGenerated(message.to_string()),
+                Err(e) => Event::GitError(format!("Message generation failed: {}", e)),
+            }
+        });
+    }
+
+    /// Put a generated commit message into the commit dialog
+    pub(crate) fn handle_git_commit_message_generated(&mut self, message: String) {
+        let Some(screen) = self.git_screen.as_mut() else {
+            return;
+        };
+        let dialog = screen.commit_dialog_mut();
+        if !dialog.is_generating() {
+            return;
+        }
+        dialog.finish_generating(Some(message));
+        self.status_message =
+            "Generated commit message: edit it, then Ctrl+S to commit".to_string();
+    }
+
+    /// Generate a pull request description for the current branch
+    ///
+    /// Covers the commits since the branch's upstream.
+    pub(crate) fn git_generate_pull_request(&mut self) {
+        let generator = self.commit_message_generator();
+        self.status_message = "Generating pull request description...".to_string();
+        self.spawn_git_event(move |service| async move {
+            match generator.branch_pull_request(&service, None).await {
+                Ok(description) => Event::GitPullRequestGenerated(description),
+                Err(e) => Event::GitError(format!("PR description failed: {}", e)),
+            }
+        });
+    }
+
+    /// Show a generated pull request description
+    pub(crate) fn handle_git_pull_request_generated(
+        &mut self,
+        description: PullRequestDescription,
+    ) {
+        let Some(screen) = self.git_screen.as_mut() else {
+            return;
+        };
+        screen.set_pull_request(Some(description));
+        self.status_message = "Pull request description ready: y to copy".to_string();
+    }
+
+    /// Copy the shown pull request description to the clipboard
+    pub(crate) fn git_copy_pull_request(&mut self) {
+        let Some(text) = self
+            .git_screen
+            .as_ref()
+            .and_then(|s| s.pull_request())
+            .map(|d| d.to_string())
+        else {
+            return;
+        };
+
+        match self.clipboard.as_mut().map(|c| c.copy(&text)) {
+            Some(Ok(())) => self.toast_success("Copied pull request description"),
+            Some(Err(e)) => self.toast_error(format!("Failed to copy: {}", e)),
+            None => self.toast_error("Clipboard not available"),
+        }
+    }
+
+    /// Generator backed by the LLM client, or deterministic without one
+    fn commit_message_generator(&self) -> CommitMessageGenerator {
+        match self.llm_client() {
+            Some(client) => CommitMessageGenerator::new(client.clone()),
+            None => CommitMessageGenerator::deterministic(),
+        }
+    }
+
+    /// Ask the agent to resolve the current conflict region
+    ///
+    /// The reply arrives as a proposal that is shown as a diff and must be
+    /// accepted (`y`) or rejected (`n`) before it takes effect.
+    pub(crate) fn git_propose_conflict_resolution(&mut self) {
+        let Some(client) = self.llm_client().cloned() else {
+            self.toast_error("LLM client not available");
+            return;
+        };
+        let Some(screen) = self.git_screen.as_ref() else {
+            return;
+        };
+        let resolver = screen.conflicts();
+        let index = resolver.current_index();
+        let Some(conflict) = resolver.current() else {
+            return;
+        };
+        if conflict.is_resolved() {
+            self.status_message = "Conflict already resolved".to_string();
+            return;
+        }
+
+        let path = conflict.file_path.clone();
+        let prompt = resolution_prompt(
+            &path,
+            conflict.base.as_deref(),
+            &conflict.ours,
+            &conflict.theirs,
+        );
+        self.status_message = format!("Asking agent to resolve conflict in {}...", path);
+
+        self.spawn_git_event(move |_| async move {
+            let reply = match client.send_message(vec![Message::user(prompt)], None).await {
+                Ok(response) => response.content,
+                Err(e) => return Event::GitError(format!("Agent request failed: {}", e)),
+            };
+            match parse_resolution(&reply) {
+                Some(lines) => Event::GitConflictProposal { path, index, lines },
+                None => Event::GitError("Agent reply contained no usable resolution".to_string()),
+            }
+        });
+    }
+
+    /// Attach an agent's proposal to its conflict region
+    ///
+    /// Ignored when the region was reloaded or removed in the meantime.
+    pub(crate) fn handle_git_conflict_proposal(
+        &mut self,
+        path: String,
+        index: usize,
+        lines: Vec<String>,
+    ) {
+        let Some(screen) = self.git_screen.as_mut() else {
+            return;
+        };
+        let resolver = screen.conflicts_mut();
+        if resolver
+            .conflicts()
+            .get(index)
+            .is_none_or(|c| c.file_path != path || c.is_resolved())
+        {
+            self.status_message = "Discarded stale conflict proposal".to_string();
+            return;
+        }
+
+        resolver.set_proposal(index, lines);
+        self.status_message = "Review proposal: y accept · n reject".to_string();
+    }
+
+    /// Write every fully resolved file and mark it resolved in the index
+    pub(crate) fn git_write_resolutions(&mut self) {
+        let Some(screen) = self.git_screen.as_ref() else {
+            return;
+        };
+        let files = screen.conflicts().resolved_files();
+        if files.is_empty() {
+            self.status_message = "No fully resolved files to write".to_string();
+            return;
+        }
+
+        self.spawn_git_operation(move |service| async move {
+            for (path, content) in &files {
+                service.write_resolution(path, content).await?;
+            }
+            Ok(format!(
+                "Wrote {} resolved file{}",
+                files.len(),
+                if files.len() == 1 { "" } else { "s" }
+            ))
+        });
+    }
+
+    /// Handle a successful git operation: report it and refresh
+    pub(crate) fn handle_git_operation_complete(&mut self, message: String) {
+        if let Some(screen) = self.git_screen.as_mut() {
+            match screen.panel() {
+                GitPanel::Branches => screen.branches_mut().set_message(message.clone()),
+                _ => screen.stage_mut().set_message(message.clone()),
+            }
+        }
+        self.status_message = message;
+        self.refresh_git();
+    }
+
+    /// Handle a finished commit
+    pub(crate) fn handle_git_commit_complete(&mut self, output: String) {
+        if let Some(screen) = self.git_screen.as_mut() {
+            screen.commit_dialog_mut().finish_commit(Ok(output));
+        }
+        self.toast_success("Commit created");
+        self.status_message = "Commit created".to_string();
+        self.refresh_git();
+    }
+
+    /// Handle a failed git operation
+    pub(crate) fn handle_git_error(&mut self, error: String) {
+        if let Some(screen) = self.git_screen.as_mut() {
+            screen.set_loading(false);
+
+            let dialog = screen.commit_dialog_mut();
+            if dialog.is_generating() {
+                dialog.finish_generating(None);
+                self.toast_error(error.clone());
+                self.status_message = error;
+                return;
+            }
+            if dialog.state() == CommitDialogState::Committing {
+                dialog.finish_commit(Err(error.clone()));
+                self.status_message = format!("Git error: {}", error);
+                return;
+            }
+
+            if screen.panel() == GitPanel::Branches {
+                screen.branches_mut().set_error(error.clone());
+            }
+        }
+
+        self.toast_error(format!("Git: {}", error));
+        self.status_message = format!("Git error: {}", error);
+    }
+
+    /// File under the cursor on the status panel and whether it is staged
+    fn git_selected_file(&self) -> Option<(PathBuf, bool)> {
+        self.git_screen
+            .as_ref()?
+            .stage()
+            .selected_entry()
+            .map(|entry| (entry.path.clone(), entry.is_staged))
+    }
+
+    /// Run a git operation in the background and refresh when it succeeds
+    fn spawn_git_operation<F, Fut>(&mut self, operation: F)
+    where
+        F: FnOnce(GitService) -> Fut + Send + 'static,
+        Fut: Future<Output = anyhow::Result<String>> + Send + 'static,
+    {
+        self.spawn_git_event(move |service| async move {
+            match operation(service).await {
+                Ok(message) => Event::GitOperationComplete(message),
+                Err(e) => Event::GitError(e.to_string()),
+            }
+        });
+    }
+
+    /// Run a git task in the background and deliver its resulting event
+    fn spawn_git_event<F, Fut>(&mut self, task: F)
+    where
+        F: FnOnce(GitService) -> Fut + Send + 'static,
+        Fut: Future<Output = Event> + Send + 'static,
+    {
+        let Some(event_tx) = self.event_tx.clone() else {
+            self.status_message = "Git: event channel not initialized".to_string();
+            return;
+        };
+        let Some(screen) = self.git_screen.as_ref() else {
+            return;
+        };
+        let service = screen.service().clone();
+
+        tokio::spawn(async move {
+            let event = task(service).await;
+            let _ = event_tx.send(event);
+        });
+    }
+}
+
+/// Collect everything the git screen displays
+async fn fetch_git_refresh(service: &GitService) -> anyhow::Result<GitRefresh> {
+    let snapshot = service.snapshot().await?;
+    let graph = GitGraphService::new(service.clone())
+        .fetch_graph(Some(GRAPH_COMMIT_LIMIT))
+        .await
+        .unwrap_or_default();
+
+    let mut conflicts = Vec::new();
+    for path in snapshot.conflicted_paths() {
+        let content = tokio::fs::read_to_string(service.repo_path().join(&path))
+            .await
+            .unwrap_or_default();
+        let versions = service.merge_versions(&path).await.unwrap_or_default();
+        conflicts.push(ConflictFile {
+            path,
+            content,
+            versions,
+        });
+    }
+
+    Ok(GitRefresh {
+        snapshot,
+        graph,
+        conflicts,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ai::llm::mock::MockResponseBuilder;
+    use crate::core::app::App;
+    use crate::core::app_state::AppScreen;
+    use crate::core::event::Event;
+    use crate::git::{FileChange, RepoSnapshot};
+    use crate::ui::screens::git::{ConflictFile, GitPanel, GitRefresh};
+    use crate::ui::widgets::git::git_graph::GitGraph;
+    use std::path::PathBuf;
+    use std::process::Command;
+    use std::sync::Arc;
+    use std::time::Duration;
+    use tempfile::TempDir;
+
+    fn git(dir: &std::path::Path, args: &[&str]) {
+        let status = Command::new("git")
+            .current_dir(dir)
+            .args(args)
+            .output()
+            .unwrap();
+        assert!(status.status.success(), "git {:?} failed", args);
+    }
+
+    fn init_repo() -> TempDir {
+        let dir = TempDir::new().unwrap();
+        git(dir.path(), &["init", "-q"]);
+        git(dir.path(), &["config", "user.email", "test@example.com"]);
+        git(dir.path(), &["config", "user.name", "Test User"]);
+        std::fs::write(dir.path().join("file.txt"), "one\n").unwrap();
+        git(dir.path(), &["add", "file.txt"]);
+        git(dir.path(), &["commit", "-q", "-m", "initial"]);
+        dir
+    }
+
+    async fn next_git_event(rx: &mut tokio::sync::mpsc::UnboundedReceiver<Event>) -> Event {
+        tokio::time::timeout(Duration::from_secs(10), rx.recv())
+            .await
+            .expect("timed out waiting for git event")
+            .expect("event channel closed")
+    }
+
+    #[test]
+    fn test_open_git_screen_not_a_repo() {
+        let dir = TempDir::new().unwrap();
+        let mut app = App::new();
+        app.screen = AppScreen::Main;
+        app.working_directory = dir.path().to_path_buf();
+
+        app.open_git_screen();
+
+        assert_eq!(app.screen, AppScreen::Main);
+        assert!(app.git_screen.is_none());
+        assert!(app.status_message.contains("Git"));
+    }
+
+    #[test]
+    fn test_open_and_close_git_screen() {
+        let dir = init_repo();
+        let mut app = App::new();
+        app.working_directory = dir.path().to_path_buf();
+
+        app.open_git_screen();
+        assert_eq!(app.screen, AppScreen::Git);
+        assert!(app.git_screen.is_some());
+
+        app.close_git_screen();
+        assert_eq!(app.screen, AppScreen::Main);
+        // Screen state is kept for the next visit
+        assert!(app.git_screen.is_some());
+    }
+
+    #[test]
+    fn test_apply_git_refresh_with_conflicts_warns() {
+        let dir = init_repo();
+        let mut app = App::new();
+        app.working_directory = dir.path().to_path_buf();
+        app.open_git_screen();
+
+        app.update(Event::GitRefreshed(Box::new(GitRefresh {
+            snapshot: RepoSnapshot {
+                branch: "main".to_string(),
+                changes: vec![FileChange::Conflicted(PathBuf::from("file.txt"))],
+                ..Default::default()
+            },
+            graph: GitGraph::new(),
+            conflicts: vec![ConflictFile {
+                path: PathBuf::from("file.txt"),
+                content: "<<<<<<< HEAD\na\n=======\nb\n>>>>>>> other\n".to_string(),
+                versions: Default::default(),
+            }],
+        })))
+        .unwrap();
+
+        let screen = app.git_screen.as_ref().unwrap();
+        assert_eq!(screen.panel(), GitPanel::Conflicts);
+        assert!(app.status_message.contains("conflicts"));
+    }
+
+    #[test]
+    fn test_empty_diff_clears_viewer() {
+        let dir = init_repo();
+        let mut app = App::new();
+        app.working_directory = dir.path().to_path_buf();
+        app.open_git_screen();
+
+        app.update(Event::GitDiffLoaded {
+            path: PathBuf::from("file.txt"),
+            staged: false,
+            diff: "@@ -1,1 +1,1 @@\n-one\n+two\n".to_string(),
+        })
+        .unwrap();
+        assert!(app.git_screen.as_ref().unwrap().diff().is_some());
+
+        app.update(Event::GitDiffLoaded {
+            path: PathBuf::from("file.txt"),
+            staged: false,
+            diff: String::new(),
+        })
+        .unwrap();
+        assert!(app.git_screen.as_ref().unwrap().diff().is_none());
+    }
+
+    #[test]
+    fn test_git_error_fails_pending_commit() {
+        let dir = init_repo();
+        let mut app = App::new();
+        app.working_directory = dir.path().to_path_buf();
+        app.open_git_screen();
+
+        let screen = app.git_screen.as_mut().unwrap();
+        screen.commit_dialog_mut().open(1);
+        screen.commit_dialog_mut().set_message("feat: x");
+        assert!(screen.commit_dialog_mut().begin_commit().is_some());
+
+        app.update(Event::GitError("boom".to_string())).unwrap();
+
+        let dialog = app.git_screen.as_ref().unwrap().commit_dialog();
+        assert!(dialog.error_message().unwrap().contains("boom"));
+    }
+
+    #[tokio::test]
+    async fn test_refresh_stage_and_commit_roundtrip() {
+        let dir = init_repo();
+        std::fs::write(dir.path().join("file.txt"), "one\ntwo\n").unwrap();
+
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let mut app = App::new();
+        app.working_directory = dir.path().to_path_buf();
+        app.set_event_tx(tx);
+
+        app.open_git_screen();
+        let event = next_git_event(&mut rx).await;
+        assert!(matches!(event, Event::GitRefreshed(_)));
+        app.update(event).unwrap();
+        assert_eq!(app.git_screen.as_ref().unwrap().stage().unstaged_count(), 1);
+
+        app.git_toggle_stage_selected();
+        let event = next_git_event(&mut rx).await;
+        assert!(matches!(event, Event::GitOperationComplete(_)));
+        app.update(event).unwrap();
+        let event = next_git_event(&mut rx).await;
+        app.update(event).unwrap();
+        assert_eq!(app.git_screen.as_ref().unwrap().stage().staged_count(), 1);
+
+        app.git_open_commit_dialog();
+        app.git_screen
+            .as_mut()
+            .unwrap()
+            .commit_dialog_mut()
+            .set_message("feat: add line");
+        app.git_commit();
+        let event = next_git_event(&mut rx).await;
+        assert!(matches!(event, Event::GitCommitComplete(_)));
+        app.update(event).unwrap();
+
+        let log = Command::new("git")
+            .current_dir(dir.path())
+            .args(["log", "--oneline"])
+            .output()
+            .unwrap();
+        assert!(String::from_utf8_lossy(&log.stdout).contains("feat: add line"));
+    }
+
+    #[tokio::test]
+    async fn test_agent_resolves_conflict_and_writes_file() {
+        let dir = init_repo();
+        git(dir.path(), &["checkout", "-q", "-b", "feature"]);
+        std::fs::write(dir.path().join("file.txt"), "theirs\n").unwrap();
+        git(dir.path(), &["commit", "-q", "-am", "theirs"]);
+        git(dir.path(), &["checkout", "-q", "-"]);
+        std::fs::write(dir.path().join("file.txt"), "ours\n").unwrap();
+        git(dir.path(), &["commit", "-q", "-am", "ours"]);
+        let _ = Command::new("git")
+            .current_dir(dir.path())
+            .args(["merge", "-q", "feature"])
+            .output();
+
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let mut app = App::new();
+        app.working_directory = dir.path().to_path_buf();
+        app.set_event_tx(tx);
+        app.llm_client = Some(Arc::new(
+            MockResponseBuilder::new()
+                .with_text("```\nours and theirs\n```")
+                .build(),
+        ));
+
+        app.open_git_screen();
+        let event = next_git_event(&mut rx).await;
+        app.update(event).unwrap();
+        assert_eq!(
+            app.git_screen.as_ref().unwrap().panel(),
+            GitPanel::Conflicts
+        );
+
+        // Nothing is written before the proposal is accepted
+        app.git_write_resolutions();
+        assert!(app.status_message.contains("No fully resolved"));
+
+        app.git_propose_conflict_resolution();
+        let event = next_git_event(&mut rx).await;
+        assert!(matches!(event, Event::GitConflictProposal { .. }));
+        app.update(event).unwrap();
+        assert!(
+            app.git_screen
+                .as_ref()
+                .unwrap()
+                .conflicts()
+                .current()
+                .unwrap()
+                .proposal
+                .is_some()
+        );
+
+        assert!(
+            app.git_screen
+                .as_mut()
+                .unwrap()
+                .conflicts_mut()
+                .accept_proposal()
+        );
+        app.git_write_resolutions();
+        let event = next_git_event(&mut rx).await;
+        assert!(
+            matches!(event, Event::GitOperationComplete(_)),
+            "{:?}",
+            event
+        );
+        app.update(event).unwrap();
+        let event = next_git_event(&mut rx).await;
+        app.update(event).unwrap();
+
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("file.txt")).unwrap(),
+            "ours and theirs\n"
+        );
+        let screen = app.git_screen.as_ref().unwrap();
+        assert!(screen.conflict_files().is_empty());
+        assert_eq!(screen.panel(), GitPanel::Status);
+    }
+
+    #[tokio::test]
+    async fn test_generate_commit_message_into_dialog() {
+        let dir = init_repo();
+        std::fs::write(dir.path().join("file.txt"), "one\ntwo\n").unwrap();
+        git(dir.path(), &["add", "file.txt"]);
+
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let mut app = App::new();
+        app.working_directory = dir.path().to_path_buf();
+        app.set_event_tx(tx);
+        app.llm_client = Some(Arc::new(
+            MockResponseBuilder::new()
+                .with_text("feat: add second line\n\nExtends file.txt.")
+                .build(),
+        ));
+
+        app.open_git_screen();
+        let event = next_git_event(&mut rx).await;
+        app.update(event).unwrap();
+
+        app.git_open_commit_dialog();
+        app.git_generate_commit_message();
+        assert!(
+            app.git_screen
+                .as_ref()
+                .unwrap()
+                .commit_dialog()
+                .is_generating()
+        );
+
+        let event = next_git_event(&mut rx).await;
+        assert!(matches!(event, Event::GitCommitMessageGenerated(_)));
+        app.update(event).unwrap();
+
+        let dialog = app.git_screen.as_ref().unwrap().commit_dialog();
+        assert!(!dialog.is_generating());
+        assert_eq!(
+            dialog.message(),
+            "feat: add second line\n\nExtends file.txt."
+        );
+    }
+
+    #[tokio::test]
+    async fn test_generate_pull_request_description() {
+        let dir = init_repo();
+        git(dir.path(), &["branch", "base"]);
+        git(dir.path(), &["checkout", "-q", "-b", "feature"]);
+        git(dir.path(), &["branch", "--set-upstream-to=base"]);
+        std::fs::write(dir.path().join("file.txt"), "one\ntwo\n").unwrap();
+        git(dir.path(), &["commit", "-q", "-am", "feat: add two"]);
+
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let mut app = App::new();
+        app.working_directory = dir.path().to_path_buf();
+        app.set_event_tx(tx);
+        app.llm_client = None;
+
+        app.open_git_screen();
+        let event = next_git_event(&mut rx).await;
+        app.update(event).unwrap();
+
+        app.git_generate_pull_request();
+        let event = next_git_event(&mut rx).await;
+        assert!(
+            matches!(event, Event::GitPullRequestGenerated(_)),
+            "{:?}",
+            event
+        );
+        app.update(event).unwrap();
+
+        let description = app.git_screen.as_ref().unwrap().pull_request().unwrap();
+        assert_eq!(description.title, "feat: add two");
+        assert!(description.body.contains("update file.txt (+1 -0)"));
+    }
+}
@@ -0,0 +1,328 @@
+//! Git activity on cards
+//!
+//! Reads the commit history of the working directory off the UI thread every
+//! [`GIT_ACTIVITY_TICKS`] ticks and attributes it to cards (see
+//! [`git_activity`](crate::infrastructure::git_activity)): commits are linked
+//! to the cards they name and time is estimated from their timestamps.
+//! `/board git` syncs now, `/board timesheet [days]` shows the tracked time.
+
+use crate::core::app::App;
+use crate::core::event::Event;
+use crate::git::GitService;
+use crate::infrastructure::git_activity::{
+    CardMatcher, CommitChanges, GitActivityOptions, GitHistory, sync_git_activity,
+};
+use crate::infrastructure::git_card_integration::GitCardIntegrationManager;
+use chrono::{Duration, Utc};
+use std::collections::HashMap;
+
+/// Ticks between git activity syncs (ticks are 250ms)
+const GIT_ACTIVITY_TICKS: u32 = 240;
+
+/// Commits read from the log per sync
+const GIT_ACTIVITY_LOG_LIMIT: usize = 500;
+
+/// Branches tried as the base of a card branch without a recorded base
+const DEFAULT_BASE_BRANCHES: [&str; 2] = ["main", "master"];
+
+/// Days shown by `/board timesheet` without an argument
+const DEFAULT_TIMESHEET_DAYS: i64 = 7;
+
+impl App {
+    /// Sync git activity every [`GIT_ACTIVITY_TICKS`] ticks
+    pub(crate) fn poll_git_activity(&mut self) {
+        if self.tick_count.is_multiple_of(GIT_ACTIVITY_TICKS) && self.board.cards.total_cards() > 0
+        {
+            self.load_git_activity(false);
+        }
+    }
+
+    /// Handle `/board git`: sync git activity now and report the result
+    pub(crate) fn sync_git_activity_now(&mut self) {
+        self.load_git_activity(true);
+    }
+
+    /// Read the commit history in the background; `announce` reports
+    /// results and errors even if nothing changed
+    ///
+    /// Skipped while an agent runs on a card: its commits are recorded with
+    /// the run and its time is tracked as agent time.
+    fn load_git_activity(&mut self, announce: bool) {
+        if self.card_run.is_some() {
+            if announce {
+                self.toast_warning("Git sync waits until the agent run finishes");
+            }
+            return;
+        }
+        if self
+            .git_activity_task
+            .as_ref()
+            .is_some_and(|task| !task.is_finished())
+        {
+            return;
+        }
+        let Some(event_tx) = self.event_tx.clone() else {
+            return;
+        };
+        let service = match GitService::new(&self.working_directory) {
+            Ok(service) => service,
+            Err(e) => {
+                if announce {
+                    self.toast_error(format!("Git sync failed: {}", e));
+                }
+                return;
+            }
+        };
+
+        let cards = CardMatcher::from_board(&self.board);
+        let git = self.board.git.clone();
+        let mut branch_bases = HashMap::new();
+        for card in self.board.cards.cards() {
+            for branch in self.board.git.get_card_branches(&card.id) {
+                branch_bases.insert(branch.name.clone(), branch.base_branch.clone());
+            }
+        }
+
+        self.git_activity_task = Some(tokio::spawn(async move {
+            let event = match load_git_history(&service, &cards, &git, &branch_bases).await {
+                Ok(history) => Event::GitActivityLoaded {
+                    history: Box::new(history),
+                    announce,
+                },
+                Err(e) => Event::GitActivityFailed {
+                    error: e.to_string(),
+                    announce,
+                },
+            };
+            let _ = event_tx.send(event);
+        }));
+    }
+
+    /// Attribute a loaded history to the board's cards
+    pub(crate) fn handle_git_activity_loaded(&mut self, history: GitHistory, announce: bool) {
+        // A run may have started since the history was requested
+        if self.card_run.is_some() {
+            return;
+        }
+        let report = sync_git_activity(&mut self.board, &history, &GitActivityOptions::default());
+        if report.changed_board() {
+            self.board_dirty = true;
+        }
+        if report.changed_board() || announce {
+            self.status_message = report.summary();
+        }
+    }
+
+    /// Surface a failed history load
+    pub(crate) fn handle_git_activity_failed(&mut self, error: String, announce: bool) {
+        tracing::warn!("Git activity sync failed: {}", error);
+        if announce {
+            self.toast_error(format!("Git sync failed: {}", error));
+        }
+    }
+
+    /// Handle `/board timesheet [days]`: time per card over the last days
+    pub(crate) fn show_timesheet(&mut self, args: &str) {
+        let days = if args.is_empty() {
+            DEFAULT_TIMESHEET_DAYS
+        } else {
+            match args.parse::<i64>() {
+                Ok(days) if days > 0 => days,
+                _ => {
+                    self.toast_error("Usage: /board timesheet [days]");
+                    return;
+                }
+            }
+        };
+
+        let end = Utc::now();
+        let start = (end.date_naive() - Duration::days(days - 1))
+            .and_hms_opt(0, 0, 0)
+            .map(|start| start.and_utc())
+            .unwrap_or(end);
+        let timesheet = self.board.time.timesheet(start, end);
+        if timesheet.rows.is_empty() {
+            self.status_message = format!("No time tracked in the last {}d", days);
+            return;
+        }
+
+        let rows: Vec<String> = timesheet
+            .rows
+            .iter()
+            .map(|row| {
+                if row.estimated_seconds > 0 {
+                    format!(
+                        "{} {} (git {})",
+                        row.task_id,
+                        format_hours(row.total_seconds),
+                        format_hours(row.estimated_seconds)
+                    )
+                } else {
+                    format!("{} {}", row.task_id, format_hours(row.total_seconds))
+                }
+            })
+            .collect();
+        self.status_message = format!(
+            "Timesheet ({}d, total {}, {} from git): {}",
+            days,
+            format_hours(timesheet.total_seconds()),
+            format_hours(timesheet.estimated_seconds()),
+            rows.join("; ")
+        );
+    }
+}
+
+/// Format seconds as e.g. `1h05m`
+fn format_hours(seconds: i64) -> String {
+    let minutes = seconds / 60;
+    format!("{}h{:02}m", minutes / 60, minutes % 60)
+}
+
+/// Read the history to attribute: recent commits, the commits only on the
+/// current branch if it names a card, and file changes of the commits that
+/// will be linked
+async fn load_git_history(
+    service: &GitService,
+    cards: &CardMatcher,
+    git: &GitCardIntegrationManager,
+    branch_bases: &HashMap<String, String>,
+) -> anyhow::Result<GitHistory> {
+    let mut history = GitHistory {
+        branch: service.current_branch().await?,
+        commits: service.log(Some(GIT_ACTIVITY_LOG_LIMIT)).await?,
+        ..GitHistory::default()
+    };
+
+    if !cards.cards_in(&history.branch).is_empty() {
+        let bases = branch_bases
+            .get(&history.branch)
+            .map(String::as_str)
+            .into_iter()
+            .chain(DEFAULT_BASE_BRANCHES)
+            .filter(|base| !base.is_empty() && *base != history.branch);
+        for base in bases {
+            if let Ok(commits) = service.log_range(base).await {
+                history.base_branch = base.to_string();
+                history.branch_commits = commits.into_iter().map(|c| c.full_hash).collect();
+                break;
+            }
+        }
+    }
+
+    let pending: Vec<String> = history
+        .attribute(cards)
+        .into_iter()
+        .filter(|(commit, ids)| ids.iter().any(|id| !git.has_commit(id, &commit.hash)))
+        .map(|(commit, _)| commit.full_hash.clone())
+        .collect();
+    for hash in pending {
+        if let Ok((files, additions, deletions)) = service.commit_stats(&hash).await {
+            history.changes.insert(
+                hash,
+                CommitChanges {
+                    files,
+                    additions,
+                    deletions,
+                },
+            );
+        }
+    }
+    Ok(history)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::app::App;
+    use crate::core::event::Event;
+    use crate::infrastructure::rich_task_cards::Priority;
+    use crate::infrastructure::time_tracking::TimeEntry;
+    use std::process::Command;
+    use std::time::Duration;
+    use tempfile::TempDir;
+
+    fn git(dir: &std::path::Path, args: &[&str]) {
+        let status = Command::new("git")
+            .current_dir(dir)
+            .args(args)
+            .output()
+            .unwrap();
+        assert!(status.status.success(), "git {:?} failed", args);
+    }
+
+    fn add_card(app: &mut App) -> String {
+        app.board_mut().cards.create_card(
+            "Fix login".to_string(),
+            String::new(),
+            "To Do".to_string(),
+            Priority::High,
+            "me".to_string(),
+        )
+    }
+
+    #[test]
+    fn test_board_timesheet() {
+        let dir = TempDir::new().unwrap();
+        let mut app = App::new();
+        app.working_directory = dir.path().to_path_buf();
+        app.load_board();
+
+        app.process_command("/board timesheet");
+        assert!(app.status_message.contains("No time tracked"));
+
+        let card = add_card(&mut app);
+        let start = chrono::Utc::now() - chrono::Duration::hours(2);
+        app.board_mut().time.add_entry(TimeEntry::manual(
+            &card,
+            start,
+            chrono::Duration::minutes(90),
+        ));
+        app.board_mut().time.add_entry(TimeEntry::estimated(
+            &card,
+            start,
+            chrono::Duration::minutes(30),
+        ));
+
+        app.process_command("/board timesheet 2");
+        assert!(app.status_message.contains("total 2h00m, 0h30m from git"));
+        assert!(
+            app.status_message
+                .contains(&format!("{} 2h00m (git 0h30m)", card))
+        );
+
+        let toasts = app.toasts.len();
+        app.process_command("/board timesheet soon");
+        assert_eq!(app.toasts.len(), toasts + 1);
+    }
+
+    #[tokio::test]
+    async fn test_board_git_links_commits_to_cards() {
+        let dir = TempDir::new().unwrap();
+        git(dir.path(), &["init", "-q"]);
+        git(dir.path(), &["config", "user.email", "test@example.com"]);
+        git(dir.path(), &["config", "user.name", "Test User"]);
+        std::fs::write(dir.path().join("login.rs"), "fn login() {}\n").unwrap();
+        git(dir.path(), &["add", "login.rs"]);
+        git(dir.path(), &["commit", "-q", "-m", "card-1: add login"]);
+
+        let mut app = App::new();
+        app.working_directory = dir.path().to_path_buf();
+        app.load_board();
+        let card = add_card(&mut app);
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        app.event_tx = Some(tx);
+
+        app.process_command("/board git");
+        let event = tokio::time::timeout(Duration::from_secs(10), rx.recv())
+            .await
+            .expect("timed out waiting for git activity")
+            .expect("event channel closed");
+        assert!(matches!(event, Event::GitActivityLoaded { .. }));
+        app.update(event).unwrap();
+
+        let commits = app.board.git.get_card_commits(&card);
+        assert_eq!(commits.len(), 1);
+        assert_eq!(commits[0].files_changed, vec!["login.rs".to_string()]);
+        assert_eq!(app.board.time.total_time_for_task(&card).num_minutes(), 30);
+        assert!(app.status_message.contains("1 commit link on 1 card"));
+    }
+}
@@ -0,0 +1,101 @@
+//! Kanban screen lifecycle
+//!
+//! Opens the board screen over the app's [`BoardDatabase`](crate::infrastructure::board_store::BoardDatabase)
+//! and turns kanban outcomes into saves, toasts and status messages.
+
+use crate::core::app::App;
+use crate::core::app_state::AppScreen;
+use crate::ui::screens::kanban::{KanbanOutcome, KanbanScreen};
+
+impl App {
+    /// Open the kanban screen, creating it on first use
+    ///
+    /// Creates a default board if there is none and puts cards that are not
+    /// on the board yet into the column matching their status.
+    pub(crate) fn open_kanban_screen(&mut self) {
+        let screen = self.kanban_screen.get_or_insert_with(KanbanScreen::new);
+        if screen.sync(&mut self.board) {
+            self.board_dirty = true;
+        }
+        self.screen = AppScreen::Kanban;
+        self.swap_board_filter();
+        self.status_message = "Opened Kanban Board".to_string();
+    }
+
+    /// Close the kanban screen and return to the main interface
+    pub(crate) fn close_kanban_screen(&mut self) {
+        self.swap_board_filter();
+        self.screen = AppScreen::Main;
+        self.status_message = "Closed Kanban Board".to_string();
+    }
+
+    /// Act on the result of a kanban key press or mouse event
+    pub(crate) fn handle_kanban_outcome(&mut self, outcome: KanbanOutcome) {
+        match outcome {
+            KanbanOutcome::None => {}
+            KanbanOutcome::Changed => self.board_dirty = true,
+            KanbanOutcome::WipExceeded(warning) => {
+                self.board_dirty = true;
+                self.toast_warning(warning);
+            }
+            KanbanOutcome::Message(message) => self.status_message = message,
+            KanbanOutcome::Error(message) => self.toast_error(message),
+            KanbanOutcome::RunAgent(card_id) => self.run_card_with_agent(&card_id),
+            KanbanOutcome::Close => self.close_kanban_screen(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::app::App;
+    use crate::core::app_state::AppScreen;
+    use crate::infrastructure::rich_task_cards::Priority;
+    use tempfile::TempDir;
+
+    fn app_in(dir: &TempDir) -> App {
+        let mut app = App::new();
+        app.screen = AppScreen::Main;
+        app.working_directory = dir.path().to_path_buf();
+        app.load_board();
+        app
+    }
+
+    #[test]
+    fn test_open_creates_board_and_places_cards() {
+        let dir = TempDir::new().unwrap();
+        let mut app = app_in(&dir);
+        app.board.cards.create_card(
+            "Review PR".to_string(),
+            String::new(),
+            "review".to_string(),
+            Priority::High,
+            "me".to_string(),
+        );
+
+        app.process_command("/kanban");
+        assert_eq!(app.screen, AppScreen::Kanban);
+        assert!(app.wants_mouse_capture());
+        assert!(app.board_dirty);
+
+        let board_id = app.kanban_screen().unwrap().board_id().unwrap().to_string();
+        let review = app.board().column_by_name(&board_id, "Review").unwrap();
+        assert_eq!(review.card_ids.len(), 1);
+
+        app.close_kanban_screen();
+        assert_eq!(app.screen, AppScreen::Main);
+        assert!(!app.wants_mouse_capture());
+    }
+
+    #[test]
+    fn test_reopen_keeps_board_clean() {
+        let dir = TempDir::new().unwrap();
+        let mut app = app_in(&dir);
+        app.open_kanban_screen();
+        app.save_board().unwrap();
+
+        app.close_kanban_screen();
+        app.open_kanban_screen();
+        assert!(!app.board_dirty);
+    }
+}
@@ -48,6 +48,13 @@ impl App {
             AppScreen::TrustDialog => "TrustDialog",
             AppScreen::Main => "Main",
             AppScreen::Evaluation => "Main", // Save as Main since Evaluation is transient
+            AppScreen::Git => "Main",        // Git screen is reopened on demand
+            AppScreen::Editor => "Main",     // Editor is reopened on demand
+            AppScreen::Kanban => "Main",     // Board is reopened on demand
+            AppScreen::Timeline => "Main",   // Timeline is reopened on demand
+            AppScreen::Calendar => "Main",   // Board views are reopened on demand
+            AppScreen::Table => "Main",
+            AppScreen::MindMap => "Main",
         };
         self.session.set_last_screen(screen_str.to_string());
     }
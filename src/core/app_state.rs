@@ -10,11 +10,18 @@
 
 /// Different screens/modes the application can be in
 ///
-/// The application can be in one of four states:
+/// The application can be in one of these states:
 /// - Welcome: Initial screen with logo and tips
 /// - TrustDialog: Directory trust confirmation
 /// - Main: Main application interface
 /// - Evaluation: Evaluation running screen with progress
+/// - Git: Integrated git workflow (staging, commits, branches, conflicts)
+/// - Editor: Embedded editor for workspace files
+/// - Kanban: Task board of cards
+/// - Timeline: Gantt chart of the card schedule
+/// - Calendar: Cards by due date
+/// - Table: Sortable, editable table of cards
+/// - MindMap: Tree of epics, cards, subtasks and dependencies
 ///
 /// # Examples
 ///
@@ -34,6 +41,20 @@ pub enum AppScreen {
     Main,
     /// Evaluation running screen
     Evaluation,
+    /// Integrated git workflow screen
+    Git,
+    /// Embedded file editor
+    Editor,
+    /// Kanban task board
+    Kanban,
+    /// Gantt timeline of the card schedule
+    Timeline,
+    /// Calendar of card due dates
+    Calendar,
+    /// Table of cards
+    Table,
+    /// Mind map of epics and cards
+    MindMap,
 }
 
 /// State of a running or completed evaluation
@@ -64,6 +85,7 @@ pub enum AppScreen {
 ///     progress: None,
 ///     results: None,
 ///     error: None,
+///     recent_runs: Vec::new(),
 /// };
 /// ```
 #[derive(Debug)]
@@ -79,6 +101,9 @@ pub struct EvaluationState {
 
     /// Error message (if failed)
     pub error: Option<String>,
+
+    /// Accuracy of the most recent runs in the experiment history, oldest first
+    pub recent_runs: Vec<f64>,
 }
 
 #[cfg(test)]
@@ -128,8 +153,15 @@ mod tests {
             AppScreen::TrustDialog,
             AppScreen::Main,
             AppScreen::Evaluation,
+            AppScreen::Git,
+            AppScreen::Editor,
+            AppScreen::Kanban,
+            AppScreen::Timeline,
+            AppScreen::Calendar,
+            AppScreen::Table,
+            AppScreen::MindMap,
         ];
-        assert_eq!(screens.len(), 4, "Should have 4 AppScreen variants");
+        assert_eq!(screens.len(), 11, "Should have 11 AppScreen variants");
     }
 
     #[test]
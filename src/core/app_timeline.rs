@@ -0,0 +1,115 @@
+//! Timeline screen lifecycle
+//!
+//! Opens the Gantt view of the board's card schedule and turns timeline
+//! outcomes into saves, toasts and status messages.
+
+use crate::core::app::App;
+use crate::core::app_state::AppScreen;
+use crate::infrastructure::multiple_views::ViewType;
+use crate::ui::screens::timeline::{TimelineOutcome, TimelineScreen};
+
+impl App {
+    /// Open the timeline screen, creating it on first use
+    pub(crate) fn open_timeline_screen(&mut self) {
+        if self.timeline_screen.is_none() {
+            let settings = self.board_view_settings(ViewType::Timeline);
+            self.timeline_screen = Some(TimelineScreen::from_settings(&settings));
+        }
+        self.screen = AppScreen::Timeline;
+        self.swap_board_filter();
+        self.status_message = "Opened Timeline".to_string();
+    }
+
+    /// Close the timeline screen and return to the main interface
+    pub(crate) fn close_timeline_screen(&mut self) {
+        self.swap_board_filter();
+        if let Some(settings) = self.timeline_screen.as_ref().map(|s| s.settings()) {
+            self.store_board_view_settings(ViewType::Timeline, settings);
+        }
+        self.screen = AppScreen::Main;
+        self.status_message = "Closed Timeline".to_string();
+    }
+
+    /// Act on the result of a timeline key press
+    pub(crate) fn handle_timeline_outcome(&mut self, outcome: TimelineOutcome) {
+        match outcome {
+            TimelineOutcome::None => {}
+            TimelineOutcome::Rescheduled(message) => {
+                self.board_dirty = true;
+                self.status_message = message;
+            }
+            TimelineOutcome::Changed => self.board_dirty = true,
+            TimelineOutcome::Message(message) => self.status_message = message,
+            TimelineOutcome::Error(message) => self.toast_error(message),
+            TimelineOutcome::Close => self.close_timeline_screen(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::app::App;
+    use crate::core::app_state::AppScreen;
+    use crate::core::event::Event;
+    use crate::infrastructure::rich_task_cards::Priority;
+    use crate::infrastructure::task_dependencies::DependencyType;
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use tempfile::TempDir;
+
+    fn app_in(dir: &TempDir) -> App {
+        let mut app = App::new();
+        app.screen = AppScreen::Main;
+        app.working_directory = dir.path().to_path_buf();
+        app.load_board();
+        app
+    }
+
+    fn press(app: &mut App, code: KeyCode) {
+        app.update(Event::Key(KeyEvent::new(code, KeyModifiers::NONE)))
+            .unwrap();
+    }
+
+    #[test]
+    fn test_moving_card_marks_board_dirty() {
+        let dir = TempDir::new().unwrap();
+        let mut app = app_in(&dir);
+        let mut ids = Vec::new();
+        for title in ["Design", "Build"] {
+            ids.push(app.board_mut().cards.create_card(
+                title.to_string(),
+                String::new(),
+                "To Do".to_string(),
+                Priority::Medium,
+                "me".to_string(),
+            ));
+        }
+        app.board_mut()
+            .dependencies
+            .create_dependency(
+                ids[0].clone(),
+                ids[1].clone(),
+                DependencyType::Blocks,
+                "me".to_string(),
+            )
+            .unwrap();
+        app.save_board().unwrap();
+
+        app.process_command("/timeline");
+        assert_eq!(app.screen, AppScreen::Timeline);
+
+        press(&mut app, KeyCode::Char('l'));
+        assert!(app.board_dirty);
+        assert!(app.status_message.starts_with("Design starts"));
+        assert!(
+            app.board()
+                .cards
+                .get_card(&ids[1])
+                .unwrap()
+                .due_date
+                .is_some()
+        );
+
+        press(&mut app, KeyCode::Esc);
+        assert_eq!(app.screen, AppScreen::Main);
+    }
+}
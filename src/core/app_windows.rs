@@ -0,0 +1,222 @@
+//! Other TOAD windows
+//!
+//! The TUI starts a [`WindowEndpoint`] at startup (see
+//! [`window_ipc`](crate::infrastructure::window_ipc)) and releases it on exit.
+//! The endpoint is polled every tick: clipboard entries and agent contexts
+//! from other windows go into the app's [`CrossWindowContextManager`], files
+//! they send are opened in the editor, and diffs are announced.
+//!
+//! `/windows` lists the other windows; `/send <window> [<path>[:<line>]]` asks
+//! one of them to open a file (default: the file open in the editor, at the
+//! cursor line).
+
+use crate::core::app::App;
+use crate::infrastructure::cross_window_context::CrossWindowContextManager;
+use crate::infrastructure::window_ipc::{
+    Delivery, IpcConfig, IpcEvent, IpcMessage, WindowEndpoint, new_window_id,
+};
+use std::path::PathBuf;
+
+impl App {
+    /// Listen for other windows and register in the per-user discovery file
+    ///
+    /// Failing to start only disables the cross-window features.
+    pub fn start_window_ipc(&mut self, config: &IpcConfig) {
+        match WindowEndpoint::start(new_window_id(), config) {
+            Ok(endpoint) => {
+                tracing::info!("Listening for other windows as {}", endpoint.window_id());
+                self.window_endpoint = Some(endpoint);
+            }
+            Err(e) => tracing::warn!("Cross-window IPC disabled: {}", e),
+        }
+    }
+
+    /// Close the socket and unregister from discovery
+    pub fn stop_window_ipc(&mut self) {
+        // Dropping the endpoint unregisters it
+        self.window_endpoint = None;
+    }
+
+    /// This window's ID, while IPC is running
+    pub fn window_id(&self) -> Option<&str> {
+        self.window_endpoint.as_ref().map(WindowEndpoint::window_id)
+    }
+
+    /// Clipboard entries and agent contexts shared with other windows
+    pub fn window_context(&self) -> &CrossWindowContextManager {
+        &self.window_context
+    }
+
+    /// Handle what other windows sent since the last tick
+    pub(crate) fn poll_windows(&mut self) {
+        let Some(endpoint) = self.window_endpoint.as_mut() else {
+            return;
+        };
+        for event in endpoint.poll() {
+            self.handle_window_event(event);
+        }
+    }
+
+    /// Handle `/windows`
+    pub(crate) fn list_windows(&mut self) {
+        let Some(endpoint) = self.window_endpoint.as_ref() else {
+            self.toast_error("Cross-window IPC is not running");
+            return;
+        };
+        match endpoint.peers() {
+            Ok(peers) if peers.is_empty() => {
+                self.status_message = "No other windows".to_string();
+            }
+            Ok(peers) => {
+                let names: Vec<String> = peers
+                    .iter()
+                    .map(|peer| match &peer.title {
+                        Some(title) => format!("{} ({})", peer.window_id, title),
+                        None => peer.window_id.clone(),
+                    })
+                    .collect();
+                self.status_message = format!("Other windows: {}", names.join(", "));
+            }
+            Err(e) => self.toast_error(format!("Cannot list windows: {}", e)),
+        }
+    }
+
+    /// Handle `/send <window> [<path>[:<line>]]`
+    pub(crate) fn process_send_command(&mut self, args: &str) {
+        let (window, target) = args.split_once(' ').unwrap_or((args, ""));
+        if window.is_empty() {
+            self.toast_error("Usage: /send <window> [<path>[:<line>]]");
+            return;
+        }
+        let Some((path, line)) = self.file_to_send(target.trim()) else {
+            self.toast_error("No file to send. Usage: /send <window> <path>[:<line>]");
+            return;
+        };
+        let Some(endpoint) = self.window_endpoint.as_mut() else {
+            self.toast_error("Cross-window IPC is not running");
+            return;
+        };
+
+        let message = IpcMessage::OpenFile {
+            path: path.clone(),
+            line,
+        };
+        match endpoint.send(window, message) {
+            Ok(Delivery::Sent) => {
+                self.toast_success(format!("Sent {} to window {}", path.display(), window))
+            }
+            Ok(Delivery::Queued) => self.toast_info(format!(
+                "Window {} is unreachable; sending {} when it is back",
+                window,
+                path.display()
+            )),
+            Err(e) => self.toast_error(format!("Cannot send to window {}: {}", window, e)),
+        }
+    }
+
+    /// File and 1-based line named by `target`, or the file open in the editor
+    ///
+    /// Relative paths resolve against the working directory, since the other
+    /// window may run elsewhere.
+    fn file_to_send(&self, target: &str) -> Option<(PathBuf, Option<usize>)> {
+        if target.is_empty() {
+            let buffer = self.editor_screen.as_ref()?.active_buffer()?;
+            let path = buffer.path()?.to_path_buf();
+            return Some((path, Some(buffer.cursor().line + 1)));
+        }
+        if let Some((path, line)) = target.rsplit_once(':')
+            && let Ok(line) = line.parse()
+        {
+            return Some((self.working_directory.join(path), Some(line)));
+        }
+        Some((self.working_directory.join(target), None))
+    }
+
+    /// Apply one event from the endpoint
+    fn handle_window_event(&mut self, event: IpcEvent) {
+        match event {
+            IpcEvent::Message(incoming) => {
+                if incoming.apply(&mut self.window_context) {
+                    return;
+                }
+                match incoming.message {
+                    IpcMessage::OpenFile { path, line } => {
+                        self.status_message =
+                            format!("Opened {} for window {}", path.display(), incoming.from);
+                        self.open_in_editor(&path);
+                        if let Some(line) = line
+                            && let Some(buffer) = self
+                                .editor_screen
+                                .as_mut()
+                                .and_then(|screen| screen.active_buffer_mut())
+                        {
+                            buffer.goto_line(line.saturating_sub(1));
+                        }
+                    }
+                    IpcMessage::Diff { title, .. } => {
+                        self.toast_info(format!("Window {} sent a diff: {}", incoming.from, title));
+                    }
+                    _ => {}
+                }
+            }
+            IpcEvent::Connected { window_id, version } => {
+                tracing::debug!("Window {} connected (protocol v{})", window_id, version);
+            }
+            IpcEvent::Disconnected { window_id } => {
+                tracing::debug!("Window {} disconnected", window_id);
+            }
+            IpcEvent::Dropped { to, .. } => {
+                self.toast_warning(format!("Gave up sending to window {}", to));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::app::App;
+    use crate::infrastructure::window_ipc::{IpcConfig, IpcEvent, IpcMessage, WindowEndpoint};
+    use std::time::Duration;
+
+    #[test]
+    fn test_send_command_asks_window_to_open_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = IpcConfig::in_dir(dir.path());
+        let mut app = App::new();
+        app.start_window_ipc(&config);
+        let mut other = WindowEndpoint::start("other", &config).unwrap();
+
+        app.process_command("/send other src/main.rs:42");
+        let expected = app.working_directory().join("src/main.rs");
+        loop {
+            match other.recv_timeout(Duration::from_secs(5)) {
+                Some(IpcEvent::Message(incoming)) => {
+                    assert_eq!(Some(incoming.from.as_str()), app.window_id());
+                    match incoming.message {
+                        IpcMessage::OpenFile { path, line } => {
+                            assert_eq!(path, expected);
+                            assert_eq!(line, Some(42));
+                        }
+                        other => panic!("unexpected message {:?}", other),
+                    }
+                    break;
+                }
+                Some(_) => {}
+                None => panic!("no message received"),
+            }
+        }
+
+        app.process_command("/windows");
+        assert_eq!(app.status_message(), "Other windows: other");
+    }
+
+    #[test]
+    fn test_send_command_without_ipc_or_target() {
+        let mut app = App::new();
+        app.process_command("/send");
+        app.process_command("/send other");
+        app.process_command("/send other README.md");
+        assert!(app.window_id().is_none());
+        assert_eq!(app.toasts.len(), 3);
+    }
+}
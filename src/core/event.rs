@@ -3,10 +3,15 @@
 //! This module defines the Event type which represents all possible
 //! messages/events in the Elm Architecture pattern.
 
+use crate::ai::card_runner::CardRunEvent;
 use crate::ai::eval_commands::{CompareArgs, EvalArgs};
 use crate::ai::evaluation::{EvaluationResults, TaskResult};
 use crate::ai::llm::Message;
+use crate::git::PullRequestDescription;
+use crate::infrastructure::git_activity::GitHistory;
+use crate::ui::screens::git::GitRefresh;
 use crossterm::event::{self, KeyEvent, KeyEventKind, MouseEvent};
+use std::path::PathBuf;
 use std::time::Duration;
 
 /// Events that can occur in the application (Message in Elm Architecture)
@@ -64,6 +69,57 @@ pub enum Event {
 
     /// AI error occurred
     AIError(String),
+
+    // Git screen events
+    /// Repository data refreshed for the git screen
+    GitRefreshed(Box<GitRefresh>),
+
+    /// Diff loaded for a file on the git screen
+    GitDiffLoaded {
+        path: PathBuf,
+        staged: bool,
+        diff: String,
+    },
+
+    /// Git operation finished (message describing the outcome)
+    GitOperationComplete(String),
+
+    /// Commit created from the git screen (git output)
+    GitCommitComplete(String),
+
+    /// Commit message generated from the staged diff
+    GitCommitMessageGenerated(String),
+
+    /// Pull request description generated for the current branch
+    GitPullRequestGenerated(PullRequestDescription),
+
+    /// Agent proposed a resolution for a conflict region
+    GitConflictProposal {
+        path: String,
+        index: usize,
+        lines: Vec<String>,
+    },
+
+    /// Git operation failed
+    GitError(String),
+
+    /// Commit history read for attributing git activity to cards
+    GitActivityLoaded {
+        history: Box<GitHistory>,
+        /// Report the result even if nothing changed
+        announce: bool,
+    },
+
+    /// Reading the commit history for card activity failed
+    GitActivityFailed {
+        error: String,
+        /// Report the error to the user
+        announce: bool,
+    },
+
+    // Card run events
+    /// Progress or outcome of an agent run on a task card
+    CardRun(CardRunEvent),
 }
 
 /// Tool execution details for real-time tracking
@@ -690,6 +746,30 @@ mod tests {
                 timestamp: Utc::now(),
             }),
             Event::EvaluationError("test error".to_string()),
+            Event::GitOperationComplete("Staged: a.txt".to_string()),
+            Event::GitCommitMessageGenerated("feat: add x".to_string()),
+            Event::GitPullRequestGenerated(PullRequestDescription {
+                title: "Add x".to_string(),
+                body: String::new(),
+            }),
+            Event::GitConflictProposal {
+                path: "a.txt".to_string(),
+                index: 0,
+                lines: vec!["merged".to_string()],
+            },
+            Event::GitError("test error".to_string()),
+            Event::GitActivityLoaded {
+                history: Box::default(),
+                announce: false,
+            },
+            Event::GitActivityFailed {
+                error: "test error".to_string(),
+                announce: true,
+            },
+            Event::CardRun(CardRunEvent::Failed {
+                card_id: "card-1".to_string(),
+                error: "test error".to_string(),
+            }),
         ];
 
         // Ensure all Event variants can be matched
@@ -712,6 +792,17 @@ mod tests {
                 Event::AIStreamComplete => {}
                 Event::AITokenUsage { .. } => {}
                 Event::AIError(_) => {}
+                Event::GitRefreshed(_) => {}
+                Event::GitDiffLoaded { .. } => {}
+                Event::GitOperationComplete(_) => {}
+                Event::GitCommitComplete(_) => {}
+                Event::GitCommitMessageGenerated(_) => {}
+                Event::GitPullRequestGenerated(_) => {}
+                Event::GitConflictProposal { .. } => {}
+                Event::GitError(_) => {}
+                Event::GitActivityLoaded { .. } => {}
+                Event::GitActivityFailed { .. } => {}
+                Event::CardRun(_) => {}
             }
         }
     }
@@ -10,11 +10,22 @@ pub mod app;
 pub mod app_accessors;
 pub mod app_ai;
 pub mod app_approvals;
+pub mod app_automation;
+pub mod app_board;
+pub mod app_board_views;
+pub mod app_calendar_sync;
+pub mod app_card_run;
 pub mod app_commands;
+pub mod app_editor;
+pub mod app_kanban;
 pub mod app_evaluation;
 pub mod app_event_handlers;
+pub mod app_git;
+pub mod app_git_activity;
 pub mod app_session;
 pub mod app_state;
+pub mod app_timeline;
+pub mod app_windows;
 pub mod event;
 pub mod tui;
 pub mod ui;
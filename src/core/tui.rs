@@ -5,6 +5,7 @@
 
 use crate::Result;
 use crossterm::{
+    event::{DisableMouseCapture, EnableMouseCapture},
     execute,
     terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
 };
@@ -14,6 +15,8 @@ use std::io::{self, Stdout};
 /// A wrapper around the Ratatui terminal that handles setup and cleanup
 pub struct Tui {
     terminal: Terminal<CrosstermBackend<Stdout>>,
+    /// Whether mouse events are currently captured
+    mouse_captured: bool,
 }
 
 impl Tui {
@@ -33,7 +36,10 @@ impl Tui {
         let backend = CrosstermBackend::new(stdout);
         let terminal = Terminal::new(backend)?;
 
-        Ok(Self { terminal })
+        Ok(Self {
+            terminal,
+            mouse_captured: false,
+        })
     }
 
     /// Get a mutable reference to the terminal for drawing
@@ -41,6 +47,24 @@ impl Tui {
         &mut self.terminal
     }
 
+    /// Turn mouse capture on or off
+    ///
+    /// Capturing the mouse disables the terminal's own text selection, so
+    /// it is only enabled while a screen needs mouse events. Does nothing
+    /// if the capture state is unchanged.
+    pub fn set_mouse_capture(&mut self, enabled: bool) -> Result<()> {
+        if enabled == self.mouse_captured {
+            return Ok(());
+        }
+        if enabled {
+            execute!(self.terminal.backend_mut(), EnableMouseCapture)?;
+        } else {
+            execute!(self.terminal.backend_mut(), DisableMouseCapture)?;
+        }
+        self.mouse_captured = enabled;
+        Ok(())
+    }
+
     /// Draw the UI using the provided closure
     ///
     /// This is a convenience method that wraps the terminal's draw method
@@ -55,10 +79,12 @@ impl Tui {
     /// Restore the terminal to its original state
     ///
     /// This will:
+    /// - Release mouse capture
     /// - Leave alternate screen
     /// - Disable raw mode
     /// - Show cursor
     fn restore(&mut self) -> Result<()> {
+        self.set_mouse_capture(false)?;
         disable_raw_mode()?;
         execute!(self.terminal.backend_mut(), LeaveAlternateScreen)?;
         self.terminal.show_cursor()?;
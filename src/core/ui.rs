@@ -6,6 +6,7 @@
 use crate::core::app::App;
 use crate::core::app_state::AppScreen;
 use crate::ui::theme::{ToadTheme, ResolvedThemeColors};
+use crate::ui::widgets::charts::sparkline::Sparkline;
 use crate::ui::widgets::core::welcome_screen::WelcomeScreen;
 use crate::ui::widgets::layout::tabbar::TabBar;
 use ratatui::{
@@ -36,6 +37,27 @@ pub fn render(app: &mut App, frame: &mut Frame) {
         AppScreen::Evaluation => {
             render_evaluation(app, frame, area);
         }
+        AppScreen::Git => {
+            render_git(app, frame, area);
+        }
+        AppScreen::Editor => {
+            render_editor(app, frame, area);
+        }
+        AppScreen::Kanban => {
+            render_kanban(app, frame, area);
+        }
+        AppScreen::Timeline => {
+            render_timeline(app, frame, area);
+        }
+        AppScreen::Calendar => {
+            render_calendar(app, frame, area);
+        }
+        AppScreen::Table => {
+            render_table(app, frame, area);
+        }
+        AppScreen::MindMap => {
+            render_mind_map(app, frame, area);
+        }
     }
 }
 
@@ -259,6 +281,88 @@ fn render_shortcuts_bar(frame: &mut Frame, area: Rect, colors: &ResolvedThemeCol
     frame.render_widget(shortcuts_paragraph, area);
 }
 
+/// Render the git screen
+fn render_git(app: &mut App, frame: &mut Frame, area: Rect) {
+    if let Some(screen) = app.git_screen_mut() {
+        screen.render(frame, area);
+    } else {
+        let colors = ResolvedThemeColors::from_manager(app.theme_manager_mut());
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(colors.accent()))
+            .title("Git");
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let msg = Paragraph::new("Not a git repository")
+            .style(Style::default().fg(colors.gray()));
+        frame.render_widget(msg, inner);
+    }
+
+    // Render toasts on top
+    app.toasts_mut().render(frame, area);
+}
+
+/// Render the editor screen
+fn render_editor(app: &mut App, frame: &mut Frame, area: Rect) {
+    if let Some(screen) = app.editor_screen_mut() {
+        screen.render(frame, area);
+    }
+
+    // Render toasts on top
+    app.toasts_mut().render(frame, area);
+}
+
+/// Render the kanban board screen
+fn render_kanban(app: &mut App, frame: &mut Frame, area: Rect) {
+    if let Some(screen) = app.kanban_screen.as_mut() {
+        screen.render(frame, area, &app.board);
+    }
+
+    // Render toasts on top
+    app.toasts_mut().render(frame, area);
+}
+
+/// Render the timeline (Gantt) screen
+fn render_timeline(app: &mut App, frame: &mut Frame, area: Rect) {
+    if let Some(screen) = app.timeline_screen.as_mut() {
+        screen.render(frame, area, &app.board, chrono::Utc::now());
+    }
+
+    // Render toasts on top
+    app.toasts_mut().render(frame, area);
+}
+
+/// Render the calendar screen
+fn render_calendar(app: &mut App, frame: &mut Frame, area: Rect) {
+    if let Some(screen) = app.calendar_screen.as_mut() {
+        screen.render(frame, area, &app.board, chrono::Utc::now().date_naive());
+    }
+
+    // Render toasts on top
+    app.toasts_mut().render(frame, area);
+}
+
+/// Render the table screen
+fn render_table(app: &mut App, frame: &mut Frame, area: Rect) {
+    if let Some(screen) = app.table_screen.as_mut() {
+        screen.render(frame, area, &app.board);
+    }
+
+    // Render toasts on top
+    app.toasts_mut().render(frame, area);
+}
+
+/// Render the mind map screen
+fn render_mind_map(app: &mut App, frame: &mut Frame, area: Rect) {
+    if let Some(screen) = app.mind_map_screen.as_mut() {
+        screen.render(frame, area, &app.board);
+    }
+
+    // Render toasts on top
+    app.toasts_mut().render(frame, area);
+}
+
 /// Render the evaluation screen with comprehensive real-time visibility
 fn render_evaluation(app: &mut App, frame: &mut Frame, area: Rect) {
     // Create colors from theme
@@ -523,7 +627,7 @@ fn render_files_panel(frame: &mut Frame, area: Rect, progress: &crate::core::eve
     let block = Block::default()
         .borders(Borders::ALL)
         .border_style(Style::default().fg(colors.gray()))
-        .title(format!(" Files Modified ({}) ", progress.files_modified.len()))
+        .title(format!(" Files Modified ({}) · e: open in editor ", progress.files_modified.len()))
         .title_style(Style::default().fg(colors.gray()));
 
     let inner = block.inner(area);
@@ -723,6 +827,17 @@ fn render_evaluation_complete(frame: &mut Frame, area: Rect, state: &crate::core
                 Style::default().fg(colors.info()),
             ),
         ]));
+        if state.recent_runs.len() > 1 {
+            let label = "Recent runs: ";
+            let width = (inner.width as usize).saturating_sub(label.len());
+            lines.push(Line::from(vec![
+                Span::styled(label, Style::default().fg(colors.gray())),
+                Span::styled(
+                    Sparkline::new(state.recent_runs.clone()).to_string(width),
+                    Style::default().fg(colors.accent()),
+                ),
+            ]));
+        }
         lines.push(Line::from(""));
         lines.push(Line::from(vec![
             Span::styled("Press ", Style::default().fg(colors.gray())),
@@ -0,0 +1,1301 @@
+//! File-backed text buffer
+//!
+//! A [`Buffer`] holds the contents of one workspace file in a [`Rope`] and
+//! composes the other editor primitives around it: cursors are a
+//! [`MultiCursor`], word motions come from [`VimMotions`], edits are recorded
+//! on an [`UndoStack`], selections are [`SelectionRange`]s and marks live in a
+//! [`MarksManager`].
+//!
+//! # Disk state
+//!
+//! The buffer remembers the modification time, size and content hash of the
+//! file as last loaded or saved. [`Buffer::check_external_change`] compares
+//! them with the file on disk so edits made outside TOAD (for example by the
+//! agent) are noticed, and [`Buffer::save`] refuses to overwrite them. Saves are
+//! atomic: the content is written to a temporary sibling file which is then
+//! renamed over the original.
+//!
+//! # Examples
+//!
+//! ```
+//! use toad::editor::Buffer;
+//!
+//! let mut buffer = Buffer::from_text("fn main() {}\n");
+//! buffer.insert_str("// entry point\n");
+//! assert!(buffer.is_dirty());
+//! assert_eq!(buffer.line(0), Some("// entry point"));
+//!
+//! buffer.undo();
+//! assert!(!buffer.is_dirty());
+//! ```
+
+use crate::editor::macros::MacroAction;
+use crate::editor::marks::MarksManager;
+use crate::editor::multicursor::MultiCursor;
+use crate::editor::rope::Rope;
+use crate::editor::undo::{Action, UndoStack};
+use crate::editor::vim_motions::{Motion, VimMotions};
+use crate::editor::visual_selection::{Position, SelectionMode, SelectionRange};
+use crate::infrastructure::fs_util::atomic_write;
+use crate::ui::syntax::{HighlightedSpan, Language, SyntaxHighlighter};
+use std::collections::hash_map::DefaultHasher;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// Errors raised by buffer file operations
+#[derive(Debug, thiserror::Error)]
+pub enum BufferError {
+    /// Reading or writing the file failed
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// The file is not valid UTF-8 text
+    #[error("{0} is not a UTF-8 text file")]
+    NotText(PathBuf),
+
+    /// The buffer has no file to save to
+    #[error("Buffer has no file path")]
+    NoPath,
+
+    /// The file changed on disk since it was loaded or saved
+    #[error("{0} was modified outside the editor")]
+    ExternallyModified(PathBuf),
+}
+
+/// Line ending style of a file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LineEnding {
+    /// Unix `\n`
+    #[default]
+    Lf,
+    /// Windows `\r\n`
+    CrLf,
+}
+
+impl LineEnding {
+    /// The separator string
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LineEnding::Lf => "\n",
+            LineEnding::CrLf => "\r\n",
+        }
+    }
+}
+
+/// How the file on disk differs from the buffer's last known state
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExternalChange {
+    /// The file content changed
+    Modified,
+    /// The file no longer exists
+    Deleted,
+}
+
+/// A single replacement applied to the text
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextEdit {
+    /// Where the replacement starts
+    pub start: Position,
+    /// Text that was removed from `start`
+    pub removed: String,
+    /// Text that was inserted at `start`
+    pub inserted: String,
+}
+
+/// One undoable change, possibly spanning several cursors
+///
+/// The buffer applies the text changes itself; the undo stack only keeps
+/// the history, so `execute` and `undo` are no-ops.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BufferEdit {
+    /// Unique id used to tell whether the buffer is back at its saved state
+    pub id: u64,
+    /// Replacements in the order they were applied
+    pub edits: Vec<TextEdit>,
+    /// Cursors before the change
+    pub cursors_before: Vec<Position>,
+    /// Cursors after the change
+    pub cursors_after: Vec<Position>,
+}
+
+impl Action for BufferEdit {
+    fn execute(&self) -> Result<(), String> {
+        Ok(())
+    }
+
+    fn undo(&self) -> Result<(), String> {
+        Ok(())
+    }
+
+    fn description(&self) -> String {
+        let inserted: usize = self.edits.iter().map(|e| e.inserted.chars().count()).sum();
+        let removed: usize = self.edits.iter().map(|e| e.removed.chars().count()).sum();
+        format!("Edit +{} -{}", inserted, removed)
+    }
+}
+
+/// Last known state of the file on disk
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct DiskState {
+    modified: Option<SystemTime>,
+    len: u64,
+    hash: u64,
+}
+
+impl DiskState {
+    fn read(path: &Path, bytes: &[u8]) -> Self {
+        let modified = fs::metadata(path).and_then(|m| m.modified()).ok();
+        Self {
+            modified,
+            len: bytes.len() as u64,
+            hash: content_hash(bytes),
+        }
+    }
+}
+
+/// An editable, optionally file-backed text buffer
+#[derive(Debug, Clone)]
+pub struct Buffer {
+    /// File the buffer is backed by
+    path: Option<PathBuf>,
+    /// Text storage
+    rope: Rope,
+    /// Line ending used when saving
+    line_ending: LineEnding,
+    /// Whether the file ends with a line ending
+    trailing_newline: bool,
+    /// All cursors; always holds at least the primary cursor
+    cursors: MultiCursor,
+    /// Edit history
+    history: UndoStack<BufferEdit>,
+    /// Edits collected while an undo group is open
+    group: Option<BufferEdit>,
+    /// Id given to the next edit
+    next_edit_id: u64,
+    /// Id of the newest history entry when the buffer was last saved
+    saved_edit: Option<u64>,
+    /// File state as last loaded or saved
+    disk: Option<DiskState>,
+    /// Incremented on every text change (for render caches)
+    revision: u64,
+}
+
+impl Buffer {
+    /// Create an unsaved buffer holding `text`
+    pub fn from_text(text: &str) -> Self {
+        let (rope, line_ending, trailing_newline) = parse_text(text);
+        Self {
+            path: None,
+            rope,
+            line_ending,
+            trailing_newline,
+            cursors: MultiCursor::with_cursor(0, 0),
+            history: UndoStack::new(),
+            group: None,
+            next_edit_id: 1,
+            saved_edit: None,
+            disk: None,
+            revision: 0,
+        }
+    }
+
+    /// Open a file
+    ///
+    /// # Errors
+    ///
+    /// Fails when the file cannot be read or is not UTF-8 text.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, BufferError> {
+        let path = path.as_ref().to_path_buf();
+        let bytes = fs::read(&path)?;
+        let text = std::str::from_utf8(&bytes).map_err(|_| BufferError::NotText(path.clone()))?;
+
+        let mut buffer = Self::from_text(text);
+        buffer.disk = Some(DiskState::read(&path, &bytes));
+        buffer.path = Some(path);
+        Ok(buffer)
+    }
+
+    /// Path of the backing file
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    /// Short name for tabs and titles
+    pub fn display_name(&self) -> String {
+        self.path
+            .as_ref()
+            .and_then(|p| p.file_name())
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| "[scratch]".to_string())
+    }
+
+    /// Language used for syntax highlighting, from the file extension
+    pub fn language(&self) -> Language {
+        self.path
+            .as_ref()
+            .and_then(|p| p.extension())
+            .map(|ext| Language::from_extension(&ext.to_string_lossy()))
+            .unwrap_or(Language::PlainText)
+    }
+
+    /// Line ending used when saving
+    pub fn line_ending(&self) -> LineEnding {
+        self.line_ending
+    }
+
+    /// Revision counter, bumped on every text change
+    pub fn revision(&self) -> u64 {
+        self.revision
+    }
+
+    /// Number of lines
+    pub fn line_count(&self) -> usize {
+        self.rope.line_count()
+    }
+
+    /// Get a line (without its line ending)
+    pub fn line(&self, index: usize) -> Option<&str> {
+        self.rope.line(index)
+    }
+
+    /// Length of a line in characters
+    pub fn line_len(&self, index: usize) -> usize {
+        self.rope.line_len(index)
+    }
+
+    /// Iterate over lines starting at `start`
+    pub fn lines_from(&self, start: usize) -> impl Iterator<Item = &str> {
+        self.rope.lines_from(start)
+    }
+
+    /// Buffer text joined with `\n`
+    pub fn text(&self) -> String {
+        self.rope.to_text()
+    }
+
+    /// File content as it would be saved (original line endings)
+    pub fn contents(&self) -> String {
+        let mut out = self.rope.to_text();
+        if self.line_ending == LineEnding::CrLf {
+            out = out.replace('\n', "\r\n");
+        }
+        if self.trailing_newline {
+            out.push_str(self.line_ending.as_str());
+        }
+        out
+    }
+
+    // ---- cursors ----------------------------------------------------------
+
+    /// Primary cursor
+    pub fn cursor(&self) -> Position {
+        self.cursors
+            .primary_position()
+            .map_or(Position::new(0, 0), |c| Position::new(c.line, c.col))
+    }
+
+    /// All cursors in document order
+    pub fn cursors(&self) -> Vec<Position> {
+        self.cursors
+            .positions()
+            .into_iter()
+            .map(|c| Position::new(c.line, c.col))
+            .collect()
+    }
+
+    /// Number of cursors
+    pub fn cursor_count(&self) -> usize {
+        self.cursors.cursor_count()
+    }
+
+    /// Move the primary cursor, dropping any secondary cursors
+    pub fn set_cursor(&mut self, pos: Position) {
+        let pos = self.rope.clamp(pos);
+        self.cursors = MultiCursor::with_cursor(pos.line, pos.col);
+    }
+
+    /// Add a secondary cursor; it becomes the primary cursor
+    pub fn add_cursor(&mut self, pos: Position) -> bool {
+        let pos = self.rope.clamp(pos);
+        self.cursors.add_cursor(pos.line, pos.col)
+    }
+
+    /// Add a cursor on the line below the lowest cursor
+    pub fn add_cursor_below(&mut self) -> bool {
+        let Some(last) = self.cursors().into_iter().max() else {
+            return false;
+        };
+        if last.line + 1 >= self.line_count() {
+            return false;
+        }
+        self.add_cursor(Position::new(last.line + 1, last.col))
+    }
+
+    /// Keep only the primary cursor
+    pub fn clear_secondary_cursors(&mut self) {
+        let primary = self.cursor();
+        self.cursors = MultiCursor::with_cursor(primary.line, primary.col);
+    }
+
+    /// Replace all cursors, keeping the one at `primary` (an index into
+    /// `positions`) as the primary cursor
+    fn set_cursors(&mut self, positions: &[Position], primary: usize) {
+        let mut cursors = MultiCursor::new();
+        for pos in positions {
+            let pos = self.rope.clamp(*pos);
+            cursors.add_cursor(pos.line, pos.col);
+        }
+        if let Some(pos) = positions.get(primary) {
+            let pos = self.rope.clamp(*pos);
+            cursors.set_primary(pos.line, pos.col);
+        }
+        if cursors.is_empty() {
+            cursors.add_cursor(0, 0);
+        }
+        self.cursors = cursors;
+    }
+
+    /// Index of the primary cursor within [`Buffer::cursors`]
+    fn primary_index(&self, cursors: &[Position]) -> usize {
+        let primary = self.cursor();
+        cursors.iter().position(|c| *c == primary).unwrap_or(0)
+    }
+
+    /// Move every cursor with `f`
+    fn move_cursors(&mut self, f: impl Fn(&Rope, Position) -> Position) {
+        let cursors = self.cursors();
+        let primary = self.primary_index(&cursors);
+        let moved: Vec<Position> = cursors.iter().map(|c| f(&self.rope, *c)).collect();
+        self.set_cursors(&moved, primary);
+    }
+
+    /// Move cursors left, wrapping to the end of the previous line
+    pub fn move_left(&mut self, n: usize) {
+        self.move_cursors(|rope, mut pos| {
+            for _ in 0..n {
+                if pos.col > 0 {
+                    pos.col -= 1;
+                } else if pos.line > 0 {
+                    pos.line -= 1;
+                    pos.col = rope.line_len(pos.line);
+                }
+            }
+            pos
+        });
+    }
+
+    /// Move cursors right, wrapping to the start of the next line
+    pub fn move_right(&mut self, n: usize) {
+        self.move_cursors(|rope, mut pos| {
+            for _ in 0..n {
+                if pos.col < rope.line_len(pos.line) {
+                    pos.col += 1;
+                } else if pos.line + 1 < rope.line_count() {
+                    pos.line += 1;
+                    pos.col = 0;
+                }
+            }
+            pos
+        });
+    }
+
+    /// Move cursors one column left without leaving their line
+    pub fn move_left_in_line(&mut self) {
+        self.move_cursors(|_, pos| Position::new(pos.line, pos.col.saturating_sub(1)));
+    }
+
+    /// Move cursors one column right without leaving their line
+    pub fn move_right_in_line(&mut self) {
+        self.move_cursors(|rope, pos| rope.clamp(Position::new(pos.line, pos.col + 1)));
+    }
+
+    /// Move cursors up
+    pub fn move_up(&mut self, n: usize) {
+        self.move_cursors(|rope, pos| {
+            rope.clamp(Position::new(pos.line.saturating_sub(n), pos.col))
+        });
+    }
+
+    /// Move cursors down
+    pub fn move_down(&mut self, n: usize) {
+        self.move_cursors(|rope, pos| rope.clamp(Position::new(pos.line + n, pos.col)));
+    }
+
+    /// Move cursors to the start of their line
+    pub fn move_line_start(&mut self) {
+        self.move_cursors(|_, pos| Position::new(pos.line, 0));
+    }
+
+    /// Move cursors to the end of their line
+    pub fn move_line_end(&mut self) {
+        self.move_cursors(|rope, pos| Position::new(pos.line, rope.line_len(pos.line)));
+    }
+
+    /// Jump to a line, dropping secondary cursors
+    pub fn goto_line(&mut self, line: usize) {
+        self.set_cursor(Position::new(line, 0));
+    }
+
+    /// Apply a vim motion to every cursor
+    ///
+    /// Motions operate within the cursor's line; word motions continue onto
+    /// the next or previous line when the current one has no further words.
+    pub fn apply_motion(&mut self, motion: Motion, count: usize) {
+        self.move_cursors(|rope, pos| {
+            let mut pos = pos;
+            for _ in 0..count.max(1) {
+                pos = motion_step(rope, pos, motion);
+            }
+            pos
+        });
+    }
+
+    // ---- editing ----------------------------------------------------------
+
+    /// Insert text at every cursor
+    pub fn insert_str(&mut self, text: &str) -> bool {
+        self.edit_at_cursors(|_, pos| Some((pos, pos, text.to_string())))
+    }
+
+    /// Insert a character at every cursor
+    pub fn insert_char(&mut self, ch: char) -> bool {
+        self.insert_str(ch.encode_utf8(&mut [0; 4]))
+    }
+
+    /// Split the line at every cursor
+    pub fn insert_newline(&mut self) -> bool {
+        self.insert_str("\n")
+    }
+
+    /// Delete the character before every cursor (joins lines at column 0)
+    pub fn delete_backward(&mut self) -> bool {
+        self.edit_at_cursors(|rope, pos| {
+            if pos.col > 0 {
+                Some((Position::new(pos.line, pos.col - 1), pos, String::new()))
+            } else if pos.line > 0 {
+                let prev = Position::new(pos.line - 1, rope.line_len(pos.line - 1));
+                Some((prev, pos, String::new()))
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Delete the character under every cursor (joins lines at line end)
+    pub fn delete_forward(&mut self) -> bool {
+        self.edit_at_cursors(|rope, pos| {
+            if pos.col < rope.line_len(pos.line) {
+                Some((pos, Position::new(pos.line, pos.col + 1), String::new()))
+            } else if pos.line + 1 < rope.line_count() {
+                Some((pos, Position::new(pos.line + 1, 0), String::new()))
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Delete the whole line of every cursor
+    pub fn delete_line(&mut self) -> bool {
+        self.edit_at_cursors(|rope, pos| {
+            let (start, end) = line_span(rope, pos.line, pos.line);
+            Some((start, end, String::new()))
+        })
+    }
+
+    /// Text covered by a visual selection
+    pub fn selection_text(&self, range: &SelectionRange) -> String {
+        match range.mode {
+            SelectionMode::Block => {
+                let mut lines = Vec::new();
+                for line in range.start.line..=range.end.line.min(self.line_count() - 1) {
+                    let (from, to) = range.col_range_for_line(line).unwrap_or((0, 0));
+                    let to = (to + 1).min(self.line_len(line));
+                    lines.push(
+                        self.rope
+                            .slice(Position::new(line, from), Position::new(line, to)),
+                    );
+                }
+                lines.join("\n")
+            }
+            _ => {
+                let (start, end) = self.selection_span(range);
+                self.rope.slice(start, end)
+            }
+        }
+    }
+
+    /// Delete a visual selection, returning the removed text
+    ///
+    /// Afterwards there is a single cursor at the start of the selection.
+    pub fn delete_selection(&mut self, range: &SelectionRange) -> String {
+        let removed = self.selection_text(range);
+        let cursors_before = self.cursors();
+
+        let spans: Vec<(Position, Position)> = match range.mode {
+            SelectionMode::Block => (range.start.line..=range.end.line.min(self.line_count() - 1))
+                .rev()
+                .filter_map(|line| {
+                    let (from, to) = range.col_range_for_line(line)?;
+                    let to = (to + 1).min(self.line_len(line));
+                    (from < to).then(|| (Position::new(line, from), Position::new(line, to)))
+                })
+                .collect(),
+            _ => vec![self.selection_span(range)],
+        };
+
+        let edits: Vec<TextEdit> = spans
+            .into_iter()
+            .filter(|(start, end)| start != end)
+            .map(|(start, end)| TextEdit {
+                start,
+                removed: self.rope.remove(start, end),
+                inserted: String::new(),
+            })
+            .collect();
+
+        let cursor = self
+            .rope
+            .clamp(Position::new(range.start.line, range.start.col));
+        let cursor = if range.mode == SelectionMode::Line {
+            Position::new(cursor.line, 0)
+        } else {
+            cursor
+        };
+        self.set_cursor(cursor);
+        self.record(edits, cursors_before);
+        removed
+    }
+
+    /// Start and end (exclusive) of a character or line selection
+    fn selection_span(&self, range: &SelectionRange) -> (Position, Position) {
+        match range.mode {
+            SelectionMode::Line => line_span(&self.rope, range.start.line, range.end.line),
+            _ => {
+                let start = self.rope.clamp(range.start);
+                let end = self.rope.clamp(range.end);
+                // Character selections include the character under the end
+                let end = if end.col < self.line_len(end.line) {
+                    Position::new(end.line, end.col + 1)
+                } else if end.line + 1 < self.line_count() {
+                    Position::new(end.line + 1, 0)
+                } else {
+                    end
+                };
+                (start, end)
+            }
+        }
+    }
+
+    /// Apply an edit at every cursor
+    ///
+    /// `op` returns the span to replace and the replacement for a cursor, or
+    /// `None` to leave it alone. Cursors are processed top to bottom and the
+    /// remaining ones are shifted by each edit. Every cursor ends up after its
+    /// replacement text.
+    fn edit_at_cursors(
+        &mut self,
+        mut op: impl FnMut(&Rope, Position) -> Option<(Position, Position, String)>,
+    ) -> bool {
+        let cursors_before = self.cursors();
+        let primary = self.primary_index(&cursors_before);
+        let mut pending = cursors_before.clone();
+        let mut edits = Vec::new();
+
+        for i in 0..pending.len() {
+            let Some((start, end, text)) = op(&self.rope, pending[i]) else {
+                continue;
+            };
+            let (start, end) = (self.rope.clamp(start), self.rope.clamp(end));
+            let removed = self.rope.remove(start, end);
+            let new_end = self.rope.insert(start, &text);
+
+            for (j, pos) in pending.iter_mut().enumerate() {
+                *pos = if j == i {
+                    new_end
+                } else {
+                    shift_position(*pos, start, end, new_end)
+                };
+            }
+            edits.push(TextEdit {
+                start,
+                removed,
+                inserted: text,
+            });
+        }
+
+        if edits.is_empty() {
+            return false;
+        }
+        self.set_cursors(&pending, primary);
+        self.record(edits, cursors_before);
+        true
+    }
+
+    /// Record applied edits in the history
+    fn record(&mut self, edits: Vec<TextEdit>, cursors_before: Vec<Position>) {
+        if edits.is_empty() {
+            return;
+        }
+        self.revision += 1;
+        let cursors_after = self.cursors();
+
+        if let Some(group) = self.group.as_mut() {
+            if group.edits.is_empty() {
+                group.cursors_before = cursors_before;
+            }
+            group.edits.extend(edits);
+            group.cursors_after = cursors_after;
+            return;
+        }
+
+        let edit = BufferEdit {
+            id: self.take_edit_id(),
+            edits,
+            cursors_before,
+            cursors_after,
+        };
+        let _ = self.history.execute(edit);
+    }
+
+    fn take_edit_id(&mut self) -> u64 {
+        let id = self.next_edit_id;
+        self.next_edit_id += 1;
+        id
+    }
+
+    /// Start collecting edits into one undo step (e.g. an insert session)
+    pub fn begin_undo_group(&mut self) {
+        if self.group.is_none() {
+            self.group = Some(BufferEdit {
+                id: 0,
+                edits: Vec::new(),
+                cursors_before: Vec::new(),
+                cursors_after: Vec::new(),
+            });
+        }
+    }
+
+    /// Close the current undo group
+    pub fn end_undo_group(&mut self) {
+        if let Some(mut group) = self.group.take()
+            && !group.edits.is_empty()
+        {
+            group.id = self.take_edit_id();
+            let _ = self.history.execute(group);
+        }
+    }
+
+    /// Undo the last change
+    pub fn undo(&mut self) -> bool {
+        self.end_undo_group();
+        let Some(edit) = self.history.peek_undo().cloned() else {
+            return false;
+        };
+        for e in edit.edits.iter().rev() {
+            let end = advance(e.start, &e.inserted);
+            self.rope.remove(e.start, end);
+            self.rope.insert(e.start, &e.removed);
+        }
+        let _ = self.history.undo();
+        self.revision += 1;
+        let primary = edit.cursors_before.len().saturating_sub(1);
+        self.set_cursors(&edit.cursors_before, primary);
+        true
+    }
+
+    /// Redo the last undone change
+    pub fn redo(&mut self) -> bool {
+        self.end_undo_group();
+        let Some(edit) = self.history.peek_redo().cloned() else {
+            return false;
+        };
+        for e in &edit.edits {
+            let end = advance(e.start, &e.removed);
+            self.rope.remove(e.start, end);
+            self.rope.insert(e.start, &e.inserted);
+        }
+        let _ = self.history.redo();
+        self.revision += 1;
+        let primary = edit.cursors_after.len().saturating_sub(1);
+        self.set_cursors(&edit.cursors_after, primary);
+        true
+    }
+
+    /// Whether the text differs from the last saved or loaded state
+    pub fn is_dirty(&self) -> bool {
+        if self.group.as_ref().is_some_and(|g| !g.edits.is_empty()) {
+            return true;
+        }
+        self.history.peek_undo().map(|e| e.id) != self.saved_edit
+    }
+
+    /// Apply a recorded macro action
+    ///
+    /// Text insertion, deletion and relative cursor moves are applied;
+    /// mode changes and commands are left to the caller.
+    pub fn apply_macro_action(&mut self, action: &MacroAction) -> bool {
+        match action {
+            MacroAction::InsertText(text) => self.insert_str(text),
+            MacroAction::DeleteText(count) => {
+                let mut changed = false;
+                for _ in 0..*count {
+                    changed |= self.delete_backward();
+                }
+                changed
+            }
+            MacroAction::MoveCursor { line, col } => {
+                self.move_cursors(|rope, pos| {
+                    let line = pos.line.saturating_add_signed(*line);
+                    let col = pos.col.saturating_add_signed(*col);
+                    rope.clamp(Position::new(line, col))
+                });
+                true
+            }
+            _ => false,
+        }
+    }
+
+    // ---- marks ------------------------------------------------------------
+
+    /// Key under which marks for this buffer are stored
+    fn mark_file(&self) -> String {
+        self.path
+            .as_ref()
+            .map(|p| p.to_string_lossy().to_string())
+            .unwrap_or_default()
+    }
+
+    /// Set a mark at the primary cursor
+    pub fn set_mark(&self, marks: &mut MarksManager, ch: char) -> bool {
+        let cursor = self.cursor();
+        marks.set_mark(ch, self.mark_file(), cursor.line, cursor.col)
+    }
+
+    /// Jump to a mark if it belongs to this buffer
+    pub fn jump_to_mark(&mut self, marks: &MarksManager, ch: char) -> bool {
+        match marks.get_mark(ch) {
+            Some(mark) if mark.is_in_file(&self.mark_file()) => {
+                self.set_cursor(Position::new(mark.line, mark.col));
+                true
+            }
+            _ => false,
+        }
+    }
+
+    // ---- highlighting -----------------------------------------------------
+
+    /// Syntax-highlight `count` lines starting at `start`
+    ///
+    /// Only the requested window is parsed, which keeps rendering cheap for
+    /// large files. Span offsets are byte offsets within each line.
+    pub fn highlight_lines(
+        &self,
+        highlighter: &SyntaxHighlighter,
+        start: usize,
+        count: usize,
+    ) -> Vec<Vec<HighlightedSpan>> {
+        let lines: Vec<&str> = self.rope.lines_from(start).take(count).collect();
+        let source = lines.join("\n");
+        let mut out: Vec<Vec<HighlightedSpan>> = vec![Vec::new(); lines.len()];
+        if lines.is_empty() {
+            return out;
+        }
+
+        let mut line = 0;
+        let mut line_start = 0;
+        for span in highlighter.highlight(&source, self.language()) {
+            let mut offset = span.start;
+            for (i, piece) in span.text.split('\n').enumerate() {
+                if i > 0 {
+                    line += 1;
+                    offset += 1;
+                    line_start = offset;
+                }
+                if !piece.is_empty() && line < out.len() {
+                    out[line].push(HighlightedSpan {
+                        text: piece.to_string(),
+                        style: span.style,
+                        start: offset - line_start,
+                        end: offset - line_start + piece.len(),
+                    });
+                }
+                offset += piece.len();
+            }
+        }
+        out
+    }
+
+    // ---- disk -------------------------------------------------------------
+
+    /// Compare the file on disk with the last loaded or saved state
+    ///
+    /// A touched file whose content is unchanged is not reported; its new
+    /// modification time is remembered instead.
+    pub fn check_external_change(&mut self) -> Option<ExternalChange> {
+        let (path, disk) = (self.path.as_ref()?, self.disk?);
+        let metadata = match fs::metadata(path) {
+            Ok(metadata) => metadata,
+            Err(_) => return Some(ExternalChange::Deleted),
+        };
+        let modified = metadata.modified().ok();
+        if modified == disk.modified && metadata.len() == disk.len {
+            return None;
+        }
+
+        let bytes = fs::read(path).ok()?;
+        if content_hash(&bytes) == disk.hash {
+            self.disk = Some(DiskState { modified, ..disk });
+            None
+        } else {
+            Some(ExternalChange::Modified)
+        }
+    }
+
+    /// Replace the buffer with the file on disk, discarding history
+    ///
+    /// # Errors
+    ///
+    /// Fails when the buffer has no path or the file cannot be read.
+    pub fn reload(&mut self) -> Result<(), BufferError> {
+        let path = self.path.clone().ok_or(BufferError::NoPath)?;
+        let cursors = self.cursors();
+        let primary = self.primary_index(&cursors);
+
+        let mut fresh = Self::open(&path)?;
+        fresh.next_edit_id = self.next_edit_id;
+        fresh.revision = self.revision + 1;
+        *self = fresh;
+        self.set_cursors(&cursors, primary);
+        Ok(())
+    }
+
+    /// Save to the backing file
+    ///
+    /// # Errors
+    ///
+    /// Refuses to overwrite a file that changed on disk since it was loaded
+    /// (use [`Buffer::force_save`]), and fails on I/O errors.
+    pub fn save(&mut self) -> Result<(), BufferError> {
+        let path = self.path.clone().ok_or(BufferError::NoPath)?;
+        if self.check_external_change() == Some(ExternalChange::Modified) {
+            return Err(BufferError::ExternallyModified(path));
+        }
+        self.write_to(&path)
+    }
+
+    /// Save even if the file changed on disk
+    pub fn force_save(&mut self) -> Result<(), BufferError> {
+        let path = self.path.clone().ok_or(BufferError::NoPath)?;
+        self.write_to(&path)
+    }
+
+    /// Save to a new path, which becomes the backing file
+    pub fn save_as(&mut self, path: impl AsRef<Path>) -> Result<(), BufferError> {
+        let path = path.as_ref().to_path_buf();
+        self.write_to(&path)?;
+        self.path = Some(path);
+        Ok(())
+    }
+
+    /// Atomically write the contents to `path` and mark the buffer clean
+    fn write_to(&mut self, path: &Path) -> Result<(), BufferError> {
+        self.end_undo_group();
+        let contents = self.contents();
+        atomic_write(path, contents.as_bytes())?;
+
+        self.disk = Some(DiskState::read(path, contents.as_bytes()));
+        self.saved_edit = self.history.peek_undo().map(|e| e.id);
+        Ok(())
+    }
+}
+
+/// Split text into lines, detecting the line ending and trailing newline
+fn parse_text(text: &str) -> (Rope, LineEnding, bool) {
+    let line_ending = match text.find('\n') {
+        Some(i) if i > 0 && text.as_bytes()[i - 1] == b'\r' => LineEnding::CrLf,
+        _ => LineEnding::Lf,
+    };
+    let trailing_newline = text.ends_with('\n');
+    let body = text.strip_suffix('\n').unwrap_or(text);
+    let body = if line_ending == LineEnding::CrLf {
+        body.strip_suffix('\r').unwrap_or(body)
+    } else {
+        body
+    };
+
+    let lines: Vec<String> = body
+        .split('\n')
+        .map(|line| match line_ending {
+            LineEnding::CrLf => line.strip_suffix('\r').unwrap_or(line).to_string(),
+            LineEnding::Lf => line.to_string(),
+        })
+        .collect();
+    (Rope::from_lines(lines), line_ending, trailing_newline)
+}
+
+/// Hash of file content used for change detection
+fn content_hash(bytes: &[u8]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    bytes.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Position after `text` when inserted at `start`
+fn advance(start: Position, text: &str) -> Position {
+    let mut parts = text.split('\n');
+    let first = parts.next().unwrap_or("");
+    match parts.enumerate().last() {
+        Some((i, last)) => Position::new(start.line + i + 1, last.chars().count()),
+        None => Position::new(start.line, start.col + first.chars().count()),
+    }
+}
+
+/// Map a position across a replacement of `start..end` that now ends at `new_end`
+fn shift_position(pos: Position, start: Position, end: Position, new_end: Position) -> Position {
+    if pos < start {
+        pos
+    } else if pos < end {
+        start
+    } else if pos.line == end.line {
+        Position::new(new_end.line, new_end.col + (pos.col - end.col))
+    } else {
+        Position::new(pos.line - end.line + new_end.line, pos.col)
+    }
+}
+
+/// Span covering whole lines `first..=last`, including one line break
+fn line_span(rope: &Rope, first: usize, last: usize) -> (Position, Position) {
+    let last = last.min(rope.line_count() - 1);
+    if last + 1 < rope.line_count() {
+        (Position::new(first, 0), Position::new(last + 1, 0))
+    } else if first > 0 {
+        (
+            Position::new(first - 1, rope.line_len(first - 1)),
+            Position::new(last, rope.line_len(last)),
+        )
+    } else {
+        (
+            Position::new(0, 0),
+            Position::new(last, rope.line_len(last)),
+        )
+    }
+}
+
+/// One step of a motion, crossing lines for word motions
+fn motion_step(rope: &Rope, pos: Position, motion: Motion) -> Position {
+    let line = rope.line(pos.line).unwrap_or("");
+    if let Some(col) = VimMotions::new(line).execute(pos.col, motion) {
+        return Position::new(pos.line, col);
+    }
+
+    let forward = matches!(
+        motion,
+        Motion::WordForward | Motion::WORDForward | Motion::EndOfWord | Motion::EndOfWORD
+    );
+    let backward = matches!(motion, Motion::WordBackward | Motion::WORDBackward);
+
+    if forward && pos.line + 1 < rope.line_count() {
+        let next = rope.line(pos.line + 1).unwrap_or("");
+        let col = next.chars().take_while(|c| c.is_whitespace()).count();
+        Position::new(pos.line + 1, col)
+    } else if backward && pos.line > 0 {
+        let prev = rope.line(pos.line - 1).unwrap_or("");
+        let len = prev.chars().count();
+        let col = VimMotions::new(prev).execute(len, motion).unwrap_or(0);
+        Position::new(pos.line - 1, col)
+    } else {
+        pos
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_buffer_open_and_contents_roundtrip() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("a.rs");
+        fs::write(&path, "fn a() {}\r\nfn b() {}\r\n").unwrap();
+
+        let buffer = Buffer::open(&path).unwrap();
+        assert_eq!(buffer.line_count(), 2);
+        assert_eq!(buffer.line(1), Some("fn b() {}"));
+        assert_eq!(buffer.line_ending(), LineEnding::CrLf);
+        assert_eq!(buffer.language(), Language::Rust);
+        assert_eq!(buffer.contents(), "fn a() {}\r\nfn b() {}\r\n");
+        assert!(!buffer.is_dirty());
+    }
+
+    #[test]
+    fn test_buffer_open_binary_rejected() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("bin");
+        fs::write(&path, [0xff, 0xfe, 0x00]).unwrap();
+        assert!(matches!(Buffer::open(&path), Err(BufferError::NotText(_))));
+    }
+
+    #[test]
+    fn test_buffer_insert_and_delete() {
+        let mut buffer = Buffer::from_text("hello");
+        buffer.set_cursor(Position::new(0, 5));
+        buffer.insert_str(" world");
+        buffer.insert_newline();
+        buffer.insert_char('!');
+        assert_eq!(buffer.text(), "hello world\n!");
+        assert_eq!(buffer.cursor(), Position::new(1, 1));
+
+        buffer.delete_backward();
+        buffer.delete_backward();
+        assert_eq!(buffer.text(), "hello world");
+        assert_eq!(buffer.cursor(), Position::new(0, 11));
+
+        buffer.set_cursor(Position::new(0, 0));
+        buffer.delete_forward();
+        assert_eq!(buffer.text(), "ello world");
+    }
+
+    #[test]
+    fn test_buffer_undo_redo_and_dirty() {
+        let mut buffer = Buffer::from_text("abc");
+        assert!(!buffer.is_dirty());
+
+        buffer.insert_str("x");
+        assert!(buffer.is_dirty());
+        assert!(buffer.undo());
+        assert_eq!(buffer.text(), "abc");
+        assert!(!buffer.is_dirty());
+
+        assert!(buffer.redo());
+        assert_eq!(buffer.text(), "xabc");
+        assert_eq!(buffer.cursor(), Position::new(0, 1));
+        assert!(buffer.is_dirty());
+        assert!(!buffer.redo());
+    }
+
+    #[test]
+    fn test_buffer_undo_group() {
+        let mut buffer = Buffer::from_text("");
+        buffer.begin_undo_group();
+        for ch in "abc".chars() {
+            buffer.insert_char(ch);
+        }
+        assert!(buffer.is_dirty());
+        buffer.end_undo_group();
+
+        buffer.undo();
+        assert_eq!(buffer.text(), "");
+        assert!(!buffer.undo());
+    }
+
+    #[test]
+    fn test_buffer_multicursor_insert() {
+        let mut buffer = Buffer::from_text("a\nb\nc");
+        buffer.add_cursor_below();
+        buffer.add_cursor_below();
+        assert_eq!(buffer.cursor_count(), 3);
+
+        buffer.insert_str("- ");
+        assert_eq!(buffer.text(), "- a\n- b\n- c");
+        assert_eq!(
+            buffer.cursors(),
+            vec![
+                Position::new(0, 2),
+                Position::new(1, 2),
+                Position::new(2, 2)
+            ]
+        );
+
+        buffer.undo();
+        assert_eq!(buffer.text(), "a\nb\nc");
+        assert_eq!(buffer.cursor_count(), 3);
+    }
+
+    #[test]
+    fn test_buffer_multicursor_same_line() {
+        let mut buffer = Buffer::from_text("ab");
+        buffer.add_cursor(Position::new(0, 1));
+        buffer.add_cursor(Position::new(0, 2));
+        buffer.insert_newline();
+        assert_eq!(buffer.text(), "\na\nb\n");
+        assert_eq!(buffer.cursors().len(), 3);
+    }
+
+    #[test]
+    fn test_buffer_motions_cross_lines() {
+        let mut buffer = Buffer::from_text("one two\nthree");
+        buffer.apply_motion(Motion::WordForward, 1);
+        assert_eq!(buffer.cursor(), Position::new(0, 4));
+        buffer.apply_motion(Motion::WordForward, 1);
+        assert_eq!(buffer.cursor(), Position::new(1, 0));
+        buffer.apply_motion(Motion::WordBackward, 1);
+        assert_eq!(buffer.cursor(), Position::new(0, 4));
+    }
+
+    #[test]
+    fn test_buffer_delete_selection_modes() {
+        let mut buffer = Buffer::from_text("abcd\nefgh\nijkl");
+        let range = SelectionRange::new(
+            SelectionMode::Character,
+            Position::new(0, 2),
+            Position::new(1, 1),
+        );
+        assert_eq!(buffer.delete_selection(&range), "cd\nef");
+        assert_eq!(buffer.text(), "abgh\nijkl");
+
+        let range = SelectionRange::new(
+            SelectionMode::Line,
+            Position::new(1, 0),
+            Position::new(1, 0),
+        );
+        buffer.delete_selection(&range);
+        assert_eq!(buffer.text(), "abgh");
+
+        let mut buffer = Buffer::from_text("abcd\nefgh");
+        let range = SelectionRange::new(
+            SelectionMode::Block,
+            Position::new(0, 1),
+            Position::new(1, 2),
+        );
+        assert_eq!(buffer.delete_selection(&range), "bc\nfg");
+        assert_eq!(buffer.text(), "ad\neh");
+        buffer.undo();
+        assert_eq!(buffer.text(), "abcd\nefgh");
+    }
+
+    #[test]
+    fn test_buffer_delete_line() {
+        let mut buffer = Buffer::from_text("a\nb\nc");
+        buffer.goto_line(2);
+        buffer.delete_line();
+        assert_eq!(buffer.text(), "a\nb");
+        buffer.goto_line(0);
+        buffer.delete_line();
+        assert_eq!(buffer.text(), "b");
+    }
+
+    #[test]
+    fn test_buffer_save_and_external_change() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("notes.txt");
+        fs::write(&path, "one\n").unwrap();
+
+        let mut buffer = Buffer::open(&path).unwrap();
+        buffer.insert_str("zero\n");
+        buffer.save().unwrap();
+        assert!(!buffer.is_dirty());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "zero\none\n");
+        assert_eq!(buffer.check_external_change(), None);
+
+        // Same content rewritten is not a change
+        fs::write(&path, "zero\none\n").unwrap();
+        assert_eq!(buffer.check_external_change(), None);
+
+        fs::write(&path, "changed elsewhere\n").unwrap();
+        assert_eq!(
+            buffer.check_external_change(),
+            Some(ExternalChange::Modified)
+        );
+        buffer.insert_str("mine ");
+        assert!(matches!(
+            buffer.save(),
+            Err(BufferError::ExternallyModified(_))
+        ));
+
+        buffer.reload().unwrap();
+        assert_eq!(buffer.text(), "changed elsewhere");
+        assert!(!buffer.is_dirty());
+
+        fs::remove_file(&path).unwrap();
+        assert_eq!(
+            buffer.check_external_change(),
+            Some(ExternalChange::Deleted)
+        );
+        buffer.force_save().unwrap();
+        assert!(path.exists());
+    }
+
+    #[test]
+    fn test_buffer_save_is_atomic() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("f.txt");
+        let mut buffer = Buffer::from_text("text");
+        assert!(matches!(buffer.save(), Err(BufferError::NoPath)));
+        buffer.save_as(&path).unwrap();
+
+        let names: Vec<String> = fs::read_dir(dir.path())
+            .unwrap()
+            .map(|e| e.unwrap().file_name().to_string_lossy().to_string())
+            .collect();
+        assert_eq!(names, vec!["f.txt".to_string()]);
+        assert_eq!(buffer.display_name(), "f.txt");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_buffer_save_through_symlink() {
+        let dir = tempdir().unwrap();
+        let target = dir.path().join("real.txt");
+        let link = dir.path().join("link.txt");
+        fs::write(&target, "one\n").unwrap();
+        std::os::unix::fs::symlink(&target, &link).unwrap();
+
+        let mut buffer = Buffer::open(&link).unwrap();
+        buffer.insert_str("zero\n");
+        buffer.save().unwrap();
+
+        assert!(
+            fs::symlink_metadata(&link)
+                .unwrap()
+                .file_type()
+                .is_symlink()
+        );
+        assert_eq!(fs::read_to_string(&target).unwrap(), "zero\none\n");
+        assert_eq!(buffer.check_external_change(), None);
+    }
+
+    #[test]
+    fn test_buffer_marks() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("m.txt");
+        fs::write(&path, "a\nb\nc").unwrap();
+        let mut buffer = Buffer::open(&path).unwrap();
+        let mut marks = MarksManager::new();
+
+        buffer.goto_line(2);
+        assert!(buffer.set_mark(&mut marks, 'a'));
+        buffer.goto_line(0);
+        assert!(buffer.jump_to_mark(&marks, 'a'));
+        assert_eq!(buffer.cursor(), Position::new(2, 0));
+
+        let mut other = Buffer::from_text("x");
+        assert!(!other.jump_to_mark(&marks, 'a'));
+    }
+
+    #[test]
+    fn test_buffer_macro_actions() {
+        let mut buffer = Buffer::from_text("ab");
+        buffer.apply_macro_action(&MacroAction::MoveCursor { line: 0, col: 1 });
+        buffer.apply_macro_action(&MacroAction::InsertText("xy".to_string()));
+        buffer.apply_macro_action(&MacroAction::DeleteText(1));
+        assert_eq!(buffer.text(), "axb");
+        assert!(!buffer.apply_macro_action(&MacroAction::EnterMode("insert".to_string())));
+    }
+
+    #[test]
+    fn test_buffer_highlight_lines_split_per_line() {
+        let buffer = Buffer::from_text("first\nsecond\nthird");
+        let highlighter = SyntaxHighlighter::new();
+        let lines = buffer.highlight_lines(&highlighter, 1, 5);
+        assert_eq!(lines.len(), 2);
+        let text: Vec<String> = lines
+            .iter()
+            .map(|spans| spans.iter().map(|s| s.text.as_str()).collect())
+            .collect();
+        assert_eq!(text, vec!["second".to_string(), "third".to_string()]);
+        assert_eq!(lines[1][0].start, 0);
+    }
+}
@@ -1,16 +1,20 @@
 //! Editor domain
 //!
 //! Contains editor-specific functionality like vim motions, undo/redo,
-//! multi-cursor editing, visual selection, and external editor integration.
+//! multi-cursor editing, visual selection, and external editor integration,
+//! plus the file-backed [`Buffer`] that composes them.
 
+pub mod buffer;
 pub mod external;
 pub mod macros;
 pub mod marks;
 pub mod multicursor;
+pub mod rope;
 pub mod undo;
 pub mod vim_motions;
 pub mod visual_selection;
 
+pub use buffer::{Buffer, BufferError, ExternalChange, LineEnding};
 pub use external::{edit_with_external_editor, EditorError};
 pub use macros::{Macro, MacroAction, MacroManager};
 pub use marks::{Mark, MarkType, MarksManager};
@@ -0,0 +1,361 @@
+//! Chunked line storage for editor buffers
+//!
+//! A [`Rope`] keeps text as a sequence of line chunks so that edits in large
+//! files only touch the chunk around the cursor instead of shifting the whole
+//! file. Lines never contain the `\n` separator; columns are counted in
+//! characters.
+//!
+//! # Examples
+//!
+//! ```
+//! use toad::editor::rope::Rope;
+//! use toad::editor::Position;
+//!
+//! let mut rope = Rope::from_text("hello\nworld");
+//! rope.insert(Position::new(0, 5), ",\nbig");
+//! assert_eq!(rope.line_count(), 3);
+//! assert_eq!(rope.to_text(), "hello,\nbig\nworld");
+//! ```
+
+use crate::editor::visual_selection::Position;
+
+/// Preferred number of lines per chunk
+const CHUNK_LINES: usize = 256;
+
+/// Line-chunked text storage
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rope {
+    /// Line chunks, never empty as a whole (a rope has at least one line)
+    chunks: Vec<Vec<String>>,
+    /// Total number of lines
+    len_lines: usize,
+}
+
+impl Default for Rope {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Rope {
+    /// Create a rope holding a single empty line
+    pub fn new() -> Self {
+        Self {
+            chunks: vec![vec![String::new()]],
+            len_lines: 1,
+        }
+    }
+
+    /// Build a rope from text, splitting on `\n`
+    pub fn from_text(text: &str) -> Self {
+        let lines: Vec<String> = text.split('\n').map(str::to_string).collect();
+        Self::from_lines(lines)
+    }
+
+    /// Build a rope from already split lines
+    ///
+    /// An empty list yields a single empty line.
+    pub fn from_lines(lines: Vec<String>) -> Self {
+        if lines.is_empty() {
+            return Self::new();
+        }
+        let len_lines = lines.len();
+        let chunks = lines
+            .chunks(CHUNK_LINES)
+            .map(|chunk| chunk.to_vec())
+            .collect();
+        Self { chunks, len_lines }
+    }
+
+    /// Number of lines (always at least one)
+    pub fn line_count(&self) -> usize {
+        self.len_lines
+    }
+
+    /// Number of chunks currently backing the rope
+    pub fn chunk_count(&self) -> usize {
+        self.chunks.len()
+    }
+
+    /// Get a line by index
+    pub fn line(&self, index: usize) -> Option<&str> {
+        if index >= self.len_lines {
+            return None;
+        }
+        let (chunk, offset) = self.locate(index);
+        Some(self.chunks[chunk][offset].as_str())
+    }
+
+    /// Length of a line in characters (0 for lines past the end)
+    pub fn line_len(&self, index: usize) -> usize {
+        self.line(index).map_or(0, |line| line.chars().count())
+    }
+
+    /// Iterate over lines starting at `start`
+    pub fn lines_from(&self, start: usize) -> impl Iterator<Item = &str> {
+        self.chunks.iter().flatten().skip(start).map(String::as_str)
+    }
+
+    /// Join all lines with `\n`
+    pub fn to_text(&self) -> String {
+        let mut out = String::new();
+        for (i, line) in self.lines_from(0).enumerate() {
+            if i > 0 {
+                out.push('\n');
+            }
+            out.push_str(line);
+        }
+        out
+    }
+
+    /// Clamp a position to the nearest valid position in the rope
+    pub fn clamp(&self, pos: Position) -> Position {
+        let line = pos.line.min(self.len_lines - 1);
+        Position::new(line, pos.col.min(self.line_len(line)))
+    }
+
+    /// Text between two positions (`start` inclusive, `end` exclusive)
+    pub fn slice(&self, start: Position, end: Position) -> String {
+        let (start, end) = self.ordered(start, end);
+        if start.line == end.line {
+            let line = self.line(start.line).unwrap_or("");
+            return line[byte_index(line, start.col)..byte_index(line, end.col)].to_string();
+        }
+
+        let mut out = String::new();
+        for (i, line) in self
+            .lines_from(start.line)
+            .take(end.line - start.line + 1)
+            .enumerate()
+        {
+            if i == 0 {
+                out.push_str(&line[byte_index(line, start.col)..]);
+            } else {
+                out.push('\n');
+                if start.line + i == end.line {
+                    out.push_str(&line[..byte_index(line, end.col)]);
+                } else {
+                    out.push_str(line);
+                }
+            }
+        }
+        out
+    }
+
+    /// Insert text at a position, returning the position just after it
+    pub fn insert(&mut self, pos: Position, text: &str) -> Position {
+        let pos = self.clamp(pos);
+        let line = self.line(pos.line).unwrap_or("");
+        let split = byte_index(line, pos.col);
+        let (prefix, suffix) = (line[..split].to_string(), line[split..].to_string());
+
+        let parts: Vec<&str> = text.split('\n').collect();
+        let last = parts.len() - 1;
+        let end = if last == 0 {
+            Position::new(pos.line, pos.col + parts[0].chars().count())
+        } else {
+            Position::new(pos.line + last, parts[last].chars().count())
+        };
+
+        let replacement: Vec<String> = parts
+            .iter()
+            .enumerate()
+            .map(|(i, part)| {
+                let mut s = String::new();
+                if i == 0 {
+                    s.push_str(&prefix);
+                }
+                s.push_str(part);
+                if i == last {
+                    s.push_str(&suffix);
+                }
+                s
+            })
+            .collect();
+
+        self.splice_lines(pos.line, pos.line + 1, replacement);
+        end
+    }
+
+    /// Remove the text between two positions, returning it
+    pub fn remove(&mut self, start: Position, end: Position) -> String {
+        let (start, end) = self.ordered(start, end);
+        if start == end {
+            return String::new();
+        }
+        let removed = self.slice(start, end);
+
+        let first = self.line(start.line).unwrap_or("");
+        let prefix = first[..byte_index(first, start.col)].to_string();
+        let last = self.line(end.line).unwrap_or("");
+        let suffix = last[byte_index(last, end.col)..].to_string();
+
+        self.splice_lines(start.line, end.line + 1, vec![prefix + &suffix]);
+        removed
+    }
+
+    /// Clamp and order two positions
+    fn ordered(&self, a: Position, b: Position) -> (Position, Position) {
+        let (a, b) = (self.clamp(a), self.clamp(b));
+        if b.is_before(&a) { (b, a) } else { (a, b) }
+    }
+
+    /// Chunk index and offset of a line
+    ///
+    /// `index == line_count()` maps to the end of the last chunk.
+    fn locate(&self, index: usize) -> (usize, usize) {
+        let mut remaining = index;
+        for (i, chunk) in self.chunks.iter().enumerate() {
+            if remaining < chunk.len() {
+                return (i, remaining);
+            }
+            remaining -= chunk.len();
+        }
+        let last = self.chunks.len() - 1;
+        (last, self.chunks[last].len())
+    }
+
+    /// Replace lines `start..end` with `replacement`
+    fn splice_lines(&mut self, start: usize, end: usize, replacement: Vec<String>) {
+        let (chunk, offset) = self.locate(start);
+        let mut remaining = end - start;
+        let (mut c, mut o) = (chunk, offset);
+        while remaining > 0 && c < self.chunks.len() {
+            let take = (self.chunks[c].len() - o).min(remaining);
+            self.chunks[c].drain(o..o + take);
+            remaining -= take;
+            c += 1;
+            o = 0;
+        }
+
+        self.len_lines = self.len_lines - (end - start) + replacement.len();
+        self.chunks[chunk].splice(offset..offset, replacement);
+        self.rebalance(chunk);
+    }
+
+    /// Split oversized chunks and drop or merge small ones near `around`
+    fn rebalance(&mut self, around: usize) {
+        self.chunks.retain(|chunk| !chunk.is_empty());
+        if self.chunks.is_empty() {
+            self.chunks.push(vec![String::new()]);
+            self.len_lines = 1;
+            return;
+        }
+
+        let around = around.min(self.chunks.len() - 1);
+        if self.chunks[around].len() > CHUNK_LINES * 2 {
+            let chunk = self.chunks.remove(around);
+            for (i, piece) in chunk.chunks(CHUNK_LINES).enumerate() {
+                self.chunks.insert(around + i, piece.to_vec());
+            }
+        } else if around + 1 < self.chunks.len()
+            && self.chunks[around].len() + self.chunks[around + 1].len() <= CHUNK_LINES
+        {
+            let next = self.chunks.remove(around + 1);
+            self.chunks[around].extend(next);
+        }
+    }
+}
+
+/// Byte index of a character column within a line (clamped to its length)
+pub fn byte_index(line: &str, col: usize) -> usize {
+    line.char_indices()
+        .nth(col)
+        .map_or(line.len(), |(index, _)| index)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rope_from_text() {
+        let rope = Rope::from_text("a\nb\nc");
+        assert_eq!(rope.line_count(), 3);
+        assert_eq!(rope.line(1), Some("b"));
+        assert_eq!(rope.line(3), None);
+        assert_eq!(rope.to_text(), "a\nb\nc");
+    }
+
+    #[test]
+    fn test_rope_empty() {
+        let rope = Rope::from_text("");
+        assert_eq!(rope.line_count(), 1);
+        assert_eq!(rope.to_text(), "");
+    }
+
+    #[test]
+    fn test_rope_insert_single_line() {
+        let mut rope = Rope::from_text("hello world");
+        let end = rope.insert(Position::new(0, 5), ",");
+        assert_eq!(end, Position::new(0, 6));
+        assert_eq!(rope.to_text(), "hello, world");
+    }
+
+    #[test]
+    fn test_rope_insert_multiline() {
+        let mut rope = Rope::from_text("ab\ncd");
+        let end = rope.insert(Position::new(0, 1), "1\n2\n3");
+        assert_eq!(end, Position::new(2, 1));
+        assert_eq!(rope.to_text(), "a1\n2\n3b\ncd");
+    }
+
+    #[test]
+    fn test_rope_remove_across_lines() {
+        let mut rope = Rope::from_text("one\ntwo\nthree");
+        let removed = rope.remove(Position::new(0, 2), Position::new(2, 2));
+        assert_eq!(removed, "e\ntwo\nth");
+        assert_eq!(rope.to_text(), "onree");
+        assert_eq!(rope.line_count(), 1);
+    }
+
+    #[test]
+    fn test_rope_remove_reversed_positions() {
+        let mut rope = Rope::from_text("abcdef");
+        let removed = rope.remove(Position::new(0, 4), Position::new(0, 1));
+        assert_eq!(removed, "bcd");
+        assert_eq!(rope.to_text(), "aef");
+    }
+
+    #[test]
+    fn test_rope_unicode_columns() {
+        let mut rope = Rope::from_text("héllo");
+        assert_eq!(rope.line_len(0), 5);
+        rope.insert(Position::new(0, 2), "X");
+        assert_eq!(rope.to_text(), "héXllo");
+        assert_eq!(rope.slice(Position::new(0, 1), Position::new(0, 3)), "éX");
+    }
+
+    #[test]
+    fn test_rope_large_file_chunking() {
+        let text: Vec<String> = (0..2000).map(|i| format!("line {}", i)).collect();
+        let mut rope = Rope::from_text(&text.join("\n"));
+        assert!(rope.chunk_count() > 1);
+        assert_eq!(rope.line(1500), Some("line 1500"));
+
+        // Edits spanning a chunk boundary
+        let removed = rope.remove(Position::new(250, 0), Position::new(260, 0));
+        assert_eq!(removed.lines().count(), 10);
+        assert_eq!(rope.line_count(), 1990);
+        assert_eq!(rope.line(250), Some("line 260"));
+
+        let many = vec!["x"; 1000].join("\n");
+        rope.insert(Position::new(10, 0), &many);
+        assert_eq!(rope.line_count(), 2989);
+        assert_eq!(rope.line(1009), Some("xline 10"));
+        assert_eq!(rope.line(2988), Some("line 1999"));
+    }
+
+    #[test]
+    fn test_rope_clamp() {
+        let rope = Rope::from_text("ab\nc");
+        assert_eq!(rope.clamp(Position::new(5, 5)), Position::new(1, 1));
+        assert_eq!(rope.clamp(Position::new(0, 9)), Position::new(0, 2));
+    }
+
+    #[test]
+    fn test_byte_index() {
+        assert_eq!(byte_index("héllo", 2), 3);
+        assert_eq!(byte_index("abc", 10), 3);
+    }
+}
@@ -0,0 +1,780 @@
+//! Commit message and pull request description generation
+//!
+//! Summarizes staged changes into a conventional-commit message and a
+//! branch's commits into a pull request title and body. Text is generated
+//! through an [`LLMClient`]; diffs larger than the chunk budget are split at
+//! file (and, if needed, hunk) boundaries, summarized chunk by chunk and the
+//! summaries combined in a final request. The deterministic mode derives
+//! messages from the diff alone, for tests and when no client is configured.
+//!
+//! # Examples
+//!
+//! ```
+//! use toad::git::commit_message::CommitMessageGenerator;
+//!
+//! # async fn example() -> anyhow::Result<()> {
+//! let diff = "\
+//! diff --git a/src/lib.rs b/src/lib.rs
+//! --- a/src/lib.rs
+//! +++ b/src/lib.rs
+//! @@ -1 +1,2 @@
+//!  fn a() {}
+//! +fn b() {}
+//! ";
+//! let generator = CommitMessageGenerator::deterministic();
+//! let message = generator.commit_message(diff).await?;
+//! assert_eq!(message.subject, "chore(src): update lib.rs");
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::ai::llm::{LLMClient, Message};
+use crate::git::patch::{FileDiff, FileDiffKind, parse_diff};
+use crate::git::{CommitInfo, GitService};
+use anyhow::{Context, Result, bail};
+use std::fmt;
+use std::path::Path;
+use std::sync::Arc;
+
+/// Default maximum size of a diff chunk sent in one request, in bytes
+pub const DEFAULT_CHUNK_SIZE: usize = 12_000;
+
+/// Marker appended to diff pieces cut to fit a chunk
+const TRUNCATION_NOTE: &str = "\n... (truncated)\n";
+
+/// Maximum length of a commit subject line
+const MAX_SUBJECT_LEN: usize = 72;
+
+/// Conventional commit types accepted in generated subjects
+const COMMIT_TYPES: &[&str] = &[
+    "feat", "fix", "docs", "style", "refactor", "perf", "test", "build", "ci", "chore", "revert",
+];
+
+/// A generated commit message
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommitMessage {
+    /// Conventional-commit subject line (`type(scope): description`)
+    pub subject: String,
+    /// Optional body, separated from the subject by a blank line
+    pub body: Option<String>,
+}
+
+impl CommitMessage {
+    /// Normalize model output into a commit message
+    ///
+    /// Strips code fences, forces a conventional-commit subject (falling back
+    /// to `chore:`) and truncates it to 72 characters.
+    pub fn parse(text: &str) -> Option<Self> {
+        let text = strip_fences(text);
+        let mut lines = text.lines().skip_while(|l| l.trim().is_empty());
+        let subject = lines.next()?.trim();
+        let subject = subject.strip_prefix("Subject:").unwrap_or(subject).trim();
+        if subject.is_empty() {
+            return None;
+        }
+
+        let subject = if is_conventional(subject) {
+            subject.to_string()
+        } else {
+            format!("chore: {}", lowercase_first(subject))
+        };
+        let body = lines.collect::<Vec<_>>().join("\n").trim().to_string();
+
+        Some(Self {
+            subject: truncate(&subject, MAX_SUBJECT_LEN),
+            body: (!body.is_empty()).then_some(body),
+        })
+    }
+}
+
+impl fmt::Display for CommitMessage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.body {
+            Some(body) => write!(f, "{}\n\n{}", self.subject, body),
+            None => write!(f, "{}", self.subject),
+        }
+    }
+}
+
+/// A generated pull request description
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PullRequestDescription {
+    /// Pull request title
+    pub title: String,
+    /// Markdown body
+    pub body: String,
+}
+
+impl PullRequestDescription {
+    /// Normalize model output: first line is the title, the rest the body
+    pub fn parse(text: &str) -> Option<Self> {
+        let text = strip_fences(text);
+        let mut lines = text.lines().skip_while(|l| l.trim().is_empty());
+        let title = lines.next()?.trim();
+        let title = title.trim_start_matches('#').trim();
+        let title = title.strip_prefix("Title:").unwrap_or(title).trim();
+        if title.is_empty() {
+            return None;
+        }
+
+        Some(Self {
+            title: title.to_string(),
+            body: lines.collect::<Vec<_>>().join("\n").trim().to_string(),
+        })
+    }
+}
+
+impl fmt::Display for PullRequestDescription {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}\n\n{}", self.title, self.body)
+    }
+}
+
+/// Generates commit messages and pull request descriptions
+#[derive(Clone)]
+pub struct CommitMessageGenerator {
+    /// Client used for generation; `None` in deterministic mode
+    client: Option<Arc<dyn LLMClient>>,
+    /// Maximum diff chunk size in bytes
+    chunk_size: usize,
+}
+
+impl fmt::Debug for CommitMessageGenerator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CommitMessageGenerator")
+            .field("deterministic", &self.client.is_none())
+            .field("chunk_size", &self.chunk_size)
+            .finish()
+    }
+}
+
+impl CommitMessageGenerator {
+    /// Create a generator backed by an LLM client
+    pub fn new(client: Arc<dyn LLMClient>) -> Self {
+        Self {
+            client: Some(client),
+            chunk_size: DEFAULT_CHUNK_SIZE,
+        }
+    }
+
+    /// Create a generator that derives text from the diff without a model
+    ///
+    /// Output depends only on the input, which makes it suitable for tests
+    /// and as an offline fallback.
+    pub fn deterministic() -> Self {
+        Self {
+            client: None,
+            chunk_size: DEFAULT_CHUNK_SIZE,
+        }
+    }
+
+    /// Set the maximum diff chunk size in bytes
+    pub fn with_chunk_size(mut self, chunk_size: usize) -> Self {
+        self.chunk_size = chunk_size.max(1);
+        self
+    }
+
+    /// Whether the generator runs without a model
+    pub fn is_deterministic(&self) -> bool {
+        self.client.is_none()
+    }
+
+    /// Generate a commit message for a diff
+    pub async fn commit_message(&self, diff: &str) -> Result<CommitMessage> {
+        if diff.trim().is_empty() {
+            bail!("No staged changes to describe");
+        }
+        let Some(client) = &self.client else {
+            return Ok(deterministic_commit_message(&parse_diff(diff)));
+        };
+
+        let chunks = split_diff(diff, self.chunk_size);
+        let prompt = if let [chunk] = chunks.as_slice() {
+            commit_prompt(&format!("Diff:\n```diff\n{}```", chunk))
+        } else {
+            let summaries = self.summarize_chunks(client, &chunks).await?;
+            commit_prompt(&format!(
+                "The diff was too large to show at once. Summaries of its parts:\n\n{}",
+                summaries.join("\n\n")
+            ))
+        };
+
+        let reply = ask(client, prompt).await?;
+        CommitMessage::parse(&reply).context("Model returned an empty commit message")
+    }
+
+    /// Generate a commit message for the staged changes of a repository
+    pub async fn staged_commit_message(&self, service: &GitService) -> Result<CommitMessage> {
+        let diff = service.diff(None, true).await?;
+        self.commit_message(&diff).await
+    }
+
+    /// Generate a pull request description from a branch's commits and diff
+    pub async fn pull_request(
+        &self,
+        branch: &str,
+        commits: &[CommitInfo],
+        diff: &str,
+    ) -> Result<PullRequestDescription> {
+        if commits.is_empty() {
+            bail!("Branch '{}' has no commits to describe", branch);
+        }
+        let Some(client) = &self.client else {
+            return Ok(deterministic_pull_request(
+                branch,
+                commits,
+                &parse_diff(diff),
+            ));
+        };
+
+        let log: Vec<String> = commits
+            .iter()
+            .map(|c| format!("- {} {}", c.hash, c.message))
+            .collect();
+        let chunks = split_diff(diff, self.chunk_size);
+        let changes = if chunks.len() > 1 {
+            self.summarize_chunks(client, &chunks).await?.join("\n\n")
+        } else {
+            format!("```diff\n{}```", chunks.concat())
+        };
+
+        let prompt = format!(
+            "Write a pull request description for branch '{}'.\n\n\
+             Reply with the title on the first line, a blank line, then a Markdown body \
+             with a '## Summary' section (bullet points) and a '## Changes' section. \
+             Do not wrap the reply in a code block.\n\n\
+             Commits (oldest first):\n{}\n\nChanges:\n{}",
+            branch,
+            log.join("\n"),
+            changes
+        );
+
+        let reply = ask(client, prompt).await?;
+        PullRequestDescription::parse(&reply).context("Model returned an empty description")
+    }
+
+    /// Generate a pull request description for the current branch
+    ///
+    /// Covers the commits between `base` (the branch's upstream when `None`)
+    /// and `HEAD`.
+    pub async fn branch_pull_request(
+        &self,
+        service: &GitService,
+        base: Option<&str>,
+    ) -> Result<PullRequestDescription> {
+        let base = match base {
+            Some(base) => base.to_string(),
+            None => service
+                .upstream_branch()
+                .await
+                .context("Current branch has no upstream to compare against")?,
+        };
+        let branch = service.current_branch().await?;
+        let mut commits = service.log_range(&base).await?;
+        commits.reverse();
+        let diff = service.range_diff(&base).await?;
+
+        self.pull_request(&branch, &commits, &diff).await
+    }
+
+    /// Summarize each chunk of a large diff
+    async fn summarize_chunks(
+        &self,
+        client: &Arc<dyn LLMClient>,
+        chunks: &[String],
+    ) -> Result<Vec<String>> {
+        let mut summaries = Vec::with_capacity(chunks.len());
+        for (i, chunk) in chunks.iter().enumerate() {
+            let prompt = format!(
+                "Summarize the following part {} of {} of a diff as short bullet points \
+                 describing what changed and why. Reply with the bullet points only.\n\n\
+                 ```diff\n{}```",
+                i + 1,
+                chunks.len(),
+                chunk
+            );
+            summaries.push(ask(client, prompt).await?.trim().to_string());
+        }
+        Ok(summaries)
+    }
+}
+
+/// Split a diff into chunks of at most `max` bytes
+///
+/// Splits between files first and between hunks of a file that is too large
+/// on its own; a single hunk larger than `max` is truncated.
+pub fn split_diff(diff: &str, max: usize) -> Vec<String> {
+    let mut chunks = Vec::new();
+    let mut current = String::new();
+
+    for piece in split_before(diff, "diff --git ")
+        .into_iter()
+        .flat_map(|file| split_file(file, max))
+    {
+        if !current.is_empty() && current.len() + piece.len() > max {
+            chunks.push(std::mem::take(&mut current));
+        }
+        current.push_str(&piece);
+    }
+    if !current.is_empty() {
+        chunks.push(current);
+    }
+    chunks
+}
+
+/// Split a single file's diff into pieces no larger than `max` bytes
+///
+/// Every piece after the first repeats the file header so it stays readable.
+fn split_file(file: &str, max: usize) -> Vec<String> {
+    if file.len() <= max {
+        return vec![file.to_string()];
+    }
+
+    let mut parts = split_before(file, "@@ ").into_iter();
+    let header = parts.next().unwrap_or_default();
+    let pieces: Vec<String> = parts
+        .map(|hunk| truncate_bytes(&format!("{}{}", header, hunk), max))
+        .collect();
+    if pieces.is_empty() {
+        vec![truncate_bytes(file, max)]
+    } else {
+        pieces
+    }
+}
+
+/// Cut text to about `max` bytes, marking the cut
+fn truncate_bytes(text: &str, max: usize) -> String {
+    if text.len() <= max {
+        return text.to_string();
+    }
+    let mut cut = max.saturating_sub(TRUNCATION_NOTE.len());
+    while !text.is_char_boundary(cut) {
+        cut -= 1;
+    }
+    format!("{}{}", &text[..cut], TRUNCATION_NOTE)
+}
+
+/// Split text into pieces that each start with a line beginning with `marker`
+///
+/// Text before the first marker line forms its own piece.
+fn split_before<'a>(text: &'a str, marker: &str) -> Vec<&'a str> {
+    let mut pieces = Vec::new();
+    let mut start = 0;
+    let mut offset = 0;
+
+    for line in text.split_inclusive('\n') {
+        if line.starts_with(marker) && offset > start {
+            pieces.push(&text[start..offset]);
+            start = offset;
+        }
+        offset += line.len();
+    }
+    if start < text.len() {
+        pieces.push(&text[start..]);
+    }
+    pieces
+}
+
+/// Instructions for a commit message request
+fn commit_prompt(changes: &str) -> String {
+    format!(
+        "Write a git commit message for the following staged changes.\n\n\
+         Use the Conventional Commits format: a subject line `type(scope): description` \
+         of at most 72 characters in the imperative mood, where type is one of {}; \
+         then a blank line and a short body explaining what changed and why. \
+         Reply with the commit message only.\n\n{}",
+        COMMIT_TYPES.join(", "),
+        changes
+    )
+}
+
+/// Send a single-message request and return the reply text
+async fn ask(client: &Arc<dyn LLMClient>, prompt: String) -> Result<String> {
+    let response = client
+        .send_message(vec![Message::user(prompt)], None)
+        .await?;
+    Ok(response.content)
+}
+
+/// Commit message derived from the diff alone
+fn deterministic_commit_message(files: &[FileDiff]) -> CommitMessage {
+    let commit_type = infer_type(files);
+    let scope = infer_scope(files);
+    let description = match files {
+        [file] => {
+            let name = file_name(file.path());
+            match file.kind {
+                FileDiffKind::Added => format!("add {}", name),
+                FileDiffKind::Deleted => format!("remove {}", name),
+                FileDiffKind::Renamed => format!(
+                    "rename {} to {}",
+                    file_name(&file.old_path),
+                    file_name(&file.new_path)
+                ),
+                FileDiffKind::Modified => format!("update {}", name),
+            }
+        }
+        _ => format!("update {} files", files.len()),
+    };
+
+    let subject = match scope {
+        Some(scope) => format!("{}({}): {}", commit_type, scope, description),
+        None => format!("{}: {}", commit_type, description),
+    };
+    let body = (files.len() > 1).then(|| {
+        files
+            .iter()
+            .map(describe_file)
+            .collect::<Vec<_>>()
+            .join("\n")
+    });
+
+    CommitMessage {
+        subject: truncate(&subject, MAX_SUBJECT_LEN),
+        body,
+    }
+}
+
+/// Pull request description derived from the commits and diff alone
+fn deterministic_pull_request(
+    branch: &str,
+    commits: &[CommitInfo],
+    files: &[FileDiff],
+) -> PullRequestDescription {
+    let title = match commits {
+        [commit] => commit.message.clone(),
+        _ => format!("{}: {} commits", branch, commits.len()),
+    };
+
+    let mut body = String::from("## Summary\n");
+    for commit in commits {
+        body.push_str(&format!("- {}\n", commit.message));
+    }
+    if !files.is_empty() {
+        body.push_str("\n## Changes\n");
+        for file in files {
+            body.push_str(&describe_file(file));
+            body.push('\n');
+        }
+    }
+
+    PullRequestDescription {
+        title,
+        body: body.trim_end().to_string(),
+    }
+}
+
+/// Bullet describing a file's change with its line counts
+fn describe_file(file: &FileDiff) -> String {
+    let (added, removed) = file.line_counts();
+    let verb = match file.kind {
+        FileDiffKind::Added => "add",
+        FileDiffKind::Deleted => "remove",
+        FileDiffKind::Renamed => "rename",
+        FileDiffKind::Modified => "update",
+    };
+    format!(
+        "- {} {} (+{} -{})",
+        verb,
+        file.path().display(),
+        added,
+        removed
+    )
+}
+
+/// Conventional commit type from the kinds and locations of changed files
+fn infer_type(files: &[FileDiff]) -> &'static str {
+    let all = |pred: fn(&Path) -> bool| !files.is_empty() && files.iter().all(|f| pred(f.path()));
+
+    if all(is_doc) {
+        "docs"
+    } else if all(is_test) {
+        "test"
+    } else if files.iter().any(|f| f.kind == FileDiffKind::Added) {
+        "feat"
+    } else {
+        "chore"
+    }
+}
+
+/// Directory shared by all changed files, if any
+fn infer_scope(files: &[FileDiff]) -> Option<String> {
+    let mut parents = files.iter().map(|f| f.path().parent());
+    let first = parents.next()??;
+    if !parents.all(|p| p == Some(first)) {
+        return None;
+    }
+    first
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+}
+
+fn is_doc(path: &Path) -> bool {
+    path.extension().is_some_and(|e| e == "md" || e == "txt") || path.starts_with("docs")
+}
+
+fn is_test(path: &Path) -> bool {
+    path.starts_with("tests")
+        || path.file_stem().is_some_and(|s| {
+            s.to_string_lossy().ends_with("_test") || s.to_string_lossy().starts_with("test_")
+        })
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| path.display().to_string())
+}
+
+/// Whether a subject follows `type(scope)!: description`
+fn is_conventional(subject: &str) -> bool {
+    let Some((prefix, description)) = subject.split_once(": ") else {
+        return false;
+    };
+    let prefix = prefix.strip_suffix('!').unwrap_or(prefix);
+    let commit_type = match prefix.split_once('(') {
+        Some((commit_type, scope)) => {
+            if !scope.ends_with(')') || scope.len() < 2 {
+                return false;
+            }
+            commit_type
+        }
+        None => prefix,
+    };
+    COMMIT_TYPES.contains(&commit_type) && !description.trim().is_empty()
+}
+
+/// Content of the first fenced code block, or the whole text
+fn strip_fences(text: &str) -> &str {
+    let Some(start) = text.find("```") else {
+        return text.trim();
+    };
+    let after = &text[start + 3..];
+    let body = after.find('\n').map_or("", |nl| &after[nl + 1..]);
+    match body.find("```") {
+        Some(end) => body[..end].trim(),
+        None => body.trim(),
+    }
+}
+
+fn lowercase_first(text: &str) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_lowercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Truncate to at most `max` characters
+fn truncate(text: &str, max: usize) -> String {
+    match text.char_indices().nth(max) {
+        Some((idx, _)) => text[..idx].trim_end().to_string(),
+        None => text.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai::llm::mock::MockResponseBuilder;
+
+    fn file_diff(path: &str, added: usize) -> String {
+        let mut diff = format!(
+            "diff --git a/{p} b/{p}\n--- a/{p}\n+++ b/{p}\n@@ -1 +1,{} @@\n keep\n",
+            added + 1,
+            p = path
+        );
+        for i in 0..added {
+            diff.push_str(&format!("+line {}\n", i));
+        }
+        diff
+    }
+
+    fn commit(hash: &str, message: &str) -> CommitInfo {
+        CommitInfo {
+            hash: hash.to_string(),
+            full_hash: hash.to_string(),
+            message: message.to_string(),
+            author: "Test".to_string(),
+            email: "test@example.com".to_string(),
+            timestamp: 0,
+            parents: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_parse_commit_message() {
+        let message =
+            CommitMessage::parse("```\nfeat(git): add generator\n\nBody text.\n```").unwrap();
+        assert_eq!(message.subject, "feat(git): add generator");
+        assert_eq!(message.body.as_deref(), Some("Body text."));
+        assert_eq!(
+            message.to_string(),
+            "feat(git): add generator\n\nBody text."
+        );
+    }
+
+    #[test]
+    fn test_parse_commit_message_forces_conventional_subject() {
+        let message = CommitMessage::parse("Add a generator").unwrap();
+        assert_eq!(message.subject, "chore: add a generator");
+        assert!(message.body.is_none());
+
+        let long = format!("fix: {}", "x".repeat(100));
+        assert_eq!(CommitMessage::parse(&long).unwrap().subject.len(), 72);
+        assert!(CommitMessage::parse("  \n").is_none());
+    }
+
+    #[test]
+    fn test_is_conventional() {
+        assert!(is_conventional("feat: x"));
+        assert!(is_conventional("fix(ui)!: x"));
+        assert!(!is_conventional("feature: x"));
+        assert!(!is_conventional("fix(: x"));
+        assert!(!is_conventional("fix x"));
+    }
+
+    #[test]
+    fn test_parse_pull_request() {
+        let pr = PullRequestDescription::parse("# Title: Add sync\n\n## Summary\n- a").unwrap();
+        assert_eq!(pr.title, "Add sync");
+        assert_eq!(pr.body, "## Summary\n- a");
+    }
+
+    #[test]
+    fn test_split_diff_by_file() {
+        let diff = format!(
+            "{}{}{}",
+            file_diff("a.rs", 3),
+            file_diff("b.rs", 3),
+            file_diff("c.rs", 3)
+        );
+        let one = file_diff("a.rs", 3).len();
+
+        let chunks = split_diff(&diff, one * 2);
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks.concat(), diff);
+        assert!(split_diff(&diff, diff.len()).len() == 1);
+    }
+
+    #[test]
+    fn test_split_diff_large_file_by_hunk() {
+        let header = "diff --git a/a.rs b/a.rs\n--- a/a.rs\n+++ b/a.rs\n";
+        let hunk = |n: usize| {
+            format!(
+                "@@ -{n} +{n} @@\n-{}\n+{}\n",
+                "o".repeat(40),
+                "n".repeat(40)
+            )
+        };
+        let diff = format!("{}{}{}", header, hunk(1), hunk(10));
+
+        let chunks = split_diff(&diff, header.len() + hunk(1).len() + 5);
+        assert_eq!(chunks.len(), 2);
+        assert!(chunks.iter().all(|c| c.starts_with(header)));
+        assert!(chunks[1].contains("@@ -10 +10 @@"));
+    }
+
+    #[test]
+    fn test_split_diff_truncates_huge_hunk() {
+        let diff = file_diff("a.rs", 500);
+        let chunks = split_diff(&diff, 200);
+        assert_eq!(chunks.len(), 1);
+        assert!(chunks[0].len() <= 200);
+        assert!(chunks[0].ends_with("... (truncated)\n"));
+    }
+
+    #[tokio::test]
+    async fn test_deterministic_commit_message() {
+        let generator = CommitMessageGenerator::deterministic();
+        let diff = format!(
+            "{}{}",
+            file_diff("src/git/a.rs", 1),
+            file_diff("src/git/b.rs", 2)
+        );
+
+        let message = generator.commit_message(&diff).await.unwrap();
+        assert_eq!(message.subject, "chore(git): update 2 files");
+        assert_eq!(
+            message.body.as_deref(),
+            Some("- update src/git/a.rs (+1 -0)\n- update src/git/b.rs (+2 -0)")
+        );
+        assert!(generator.commit_message("").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_deterministic_commit_message_for_new_docs() {
+        let diff = "diff --git a/README.md b/README.md\nnew file mode 100644\n--- /dev/null\n+++ b/README.md\n@@ -0,0 +1 @@\n+# Hi\n";
+        let message = CommitMessageGenerator::deterministic()
+            .commit_message(diff)
+            .await
+            .unwrap();
+        assert_eq!(message.subject, "docs: add README.md");
+    }
+
+    #[tokio::test]
+    async fn test_llm_commit_message_single_request() {
+        let client = MockResponseBuilder::new()
+            .with_text("feat(git): add commit message generator\n\nSummarizes staged diffs.")
+            .build();
+        let generator = CommitMessageGenerator::new(Arc::new(client));
+
+        let message = generator
+            .commit_message(&file_diff("a.rs", 2))
+            .await
+            .unwrap();
+        assert_eq!(message.subject, "feat(git): add commit message generator");
+        assert_eq!(message.body.as_deref(), Some("Summarizes staged diffs."));
+    }
+
+    #[tokio::test]
+    async fn test_llm_commit_message_chunks_large_diff() {
+        let client = Arc::new(
+            MockResponseBuilder::new()
+                .with_text("- changed a")
+                .with_text("- changed b")
+                .with_text("refactor: split modules")
+                .build(),
+        );
+        let diff = format!("{}{}", file_diff("a.rs", 20), file_diff("b.rs", 20));
+        let generator = CommitMessageGenerator::new(client.clone())
+            .with_chunk_size(file_diff("a.rs", 20).len());
+
+        let message = generator.commit_message(&diff).await.unwrap();
+        assert_eq!(message.subject, "refactor: split modules");
+        assert_eq!(client.call_count(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_pull_request_description() {
+        let commits = vec![commit("abc", "feat: a"), commit("def", "fix: b")];
+        let diff = file_diff("src/a.rs", 1);
+
+        let pr = CommitMessageGenerator::deterministic()
+            .pull_request("feature", &commits, &diff)
+            .await
+            .unwrap();
+        assert_eq!(pr.title, "feature: 2 commits");
+        assert_eq!(
+            pr.body,
+            "## Summary\n- feat: a\n- fix: b\n\n## Changes\n- update src/a.rs (+1 -0)"
+        );
+
+        let client = MockResponseBuilder::new()
+            .with_text("Add a and fix b\n\n## Summary\n- a\n- b")
+            .build();
+        let pr = CommitMessageGenerator::new(Arc::new(client))
+            .pull_request("feature", &commits, &diff)
+            .await
+            .unwrap();
+        assert_eq!(pr.title, "Add a and fix b");
+        assert!(pr.body.starts_with("## Summary"));
+
+        assert!(
+            CommitMessageGenerator::deterministic()
+                .pull_request("feature", &[], "")
+                .await
+                .is_err()
+        );
+    }
+}
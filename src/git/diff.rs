@@ -0,0 +1,109 @@
+//! Diff model shared by the git service and the diff widgets
+//!
+//! A diff is a list of [`DiffHunk`]s, each made of [`DiffLine`]s tagged with
+//! their [`ChangeType`].
+
+/// Line change type
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeType {
+    /// Line added
+    Added,
+    /// Line removed
+    Removed,
+    /// Line modified (removed + added)
+    Modified,
+    /// Context line (unchanged)
+    Context,
+}
+
+impl ChangeType {
+    /// Get marker symbol
+    pub fn marker(&self) -> &'static str {
+        match self {
+            ChangeType::Added => "+",
+            ChangeType::Removed => "-",
+            ChangeType::Modified => "~",
+            ChangeType::Context => " ",
+        }
+    }
+}
+
+/// A single diff line
+#[derive(Debug, Clone)]
+pub struct DiffLine {
+    /// Line content
+    pub content: String,
+    /// Change type
+    pub change_type: ChangeType,
+    /// Old line number (if applicable)
+    pub old_line_num: Option<usize>,
+    /// New line number (if applicable)
+    pub new_line_num: Option<usize>,
+}
+
+impl DiffLine {
+    /// Create a new diff line
+    pub fn new(
+        content: String,
+        change_type: ChangeType,
+        old_line_num: Option<usize>,
+        new_line_num: Option<usize>,
+    ) -> Self {
+        Self {
+            content,
+            change_type,
+            old_line_num,
+            new_line_num,
+        }
+    }
+}
+
+/// A diff hunk (contiguous block of changes)
+#[derive(Debug, Clone)]
+pub struct DiffHunk {
+    /// Hunk header (e.g., "@@ -1,3 +1,4 @@")
+    pub header: String,
+    /// Lines in this hunk
+    pub lines: Vec<DiffLine>,
+    /// Old start line
+    pub old_start: usize,
+    /// Old line count
+    pub old_count: usize,
+    /// New start line
+    pub new_start: usize,
+    /// New line count
+    pub new_count: usize,
+    /// Whether this hunk is selected for application
+    pub selected: bool,
+}
+
+impl DiffHunk {
+    /// Create a new diff hunk
+    pub fn new(
+        header: String,
+        old_start: usize,
+        old_count: usize,
+        new_start: usize,
+        new_count: usize,
+    ) -> Self {
+        Self {
+            header,
+            lines: Vec::new(),
+            old_start,
+            old_count,
+            new_start,
+            new_count,
+            selected: true, // Selected by default
+        }
+    }
+
+    /// Add a line to the hunk
+    pub fn add_line(&mut self, line: DiffLine) {
+        self.lines.push(line);
+    }
+
+    /// Get number of lines
+    pub fn line_count(&self) -> usize {
+        self.lines.len()
+    }
+}
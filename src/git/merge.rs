@@ -0,0 +1,603 @@
+//! Three-way merge engine
+//!
+//! Line-based diff3 merge of a common ancestor ("base") with two descendants
+//! ("ours" and "theirs"). Regions changed on only one side, or changed the
+//! same way on both, merge cleanly; overlapping different changes become
+//! conflicts carrying all three versions.
+//!
+//! # Examples
+//!
+//! ```
+//! use toad::git::merge::merge3;
+//!
+//! let base = "a\nb\nc\n";
+//! let ours = "a\nB\nc\n";
+//! let theirs = "a\nb\nC\n";
+//!
+//! let merge = merge3(base, ours, theirs);
+//! assert!(merge.is_clean());
+//! assert_eq!(merge.to_text("HEAD", "feature"), "a\nB\nC\n");
+//! ```
+
+/// Conflict marker opening the "ours" section
+pub const OURS_MARKER: &str = "<<<<<<<";
+/// Conflict marker opening the base section (diff3 style)
+pub const BASE_MARKER: &str = "|||||||";
+/// Conflict marker separating "ours" from "theirs"
+pub const SEPARATOR_MARKER: &str = "=======";
+/// Conflict marker closing the "theirs" section
+pub const THEIRS_MARKER: &str = ">>>>>>>";
+
+/// The three versions of a conflicted file, as stored in the index
+///
+/// Any version may be missing, e.g. for add/add or modify/delete conflicts.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MergeVersions {
+    /// Common ancestor (stage 1)
+    pub base: Option<String>,
+    /// Current branch (stage 2)
+    pub ours: Option<String>,
+    /// Incoming branch (stage 3)
+    pub theirs: Option<String>,
+}
+
+impl MergeVersions {
+    /// Merge the three versions, if all of them exist
+    pub fn merge(&self) -> Option<Merge> {
+        Some(merge3(
+            self.base.as_deref()?,
+            self.ours.as_deref()?,
+            self.theirs.as_deref()?,
+        ))
+    }
+}
+
+/// A run of merged lines
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MergeChunk {
+    /// Lines both sides agree on
+    Clean(Vec<String>),
+    /// Overlapping changes that need a decision
+    Conflict {
+        /// Lines in the common ancestor
+        base: Vec<String>,
+        /// Lines on the current branch
+        ours: Vec<String>,
+        /// Lines on the incoming branch
+        theirs: Vec<String>,
+    },
+}
+
+/// Result of a three-way merge
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Merge {
+    /// Merged chunks in file order
+    pub chunks: Vec<MergeChunk>,
+    /// Whether the merged text ends with a newline
+    trailing_newline: bool,
+}
+
+impl Merge {
+    /// Whether the merge produced no conflicts
+    pub fn is_clean(&self) -> bool {
+        self.conflict_count() == 0
+    }
+
+    /// Number of conflicting chunks
+    pub fn conflict_count(&self) -> usize {
+        self.chunks
+            .iter()
+            .filter(|c| matches!(c, MergeChunk::Conflict { .. }))
+            .count()
+    }
+
+    /// Merged lines, or `None` if conflicts remain
+    pub fn resolved_lines(&self) -> Option<Vec<String>> {
+        let mut lines = Vec::new();
+        for chunk in &self.chunks {
+            match chunk {
+                MergeChunk::Clean(clean) => lines.extend(clean.iter().cloned()),
+                MergeChunk::Conflict { .. } => return None,
+            }
+        }
+        Some(lines)
+    }
+
+    /// Merged text with diff3-style markers around conflicts
+    pub fn to_text(&self, our_label: &str, their_label: &str) -> String {
+        let mut lines = Vec::new();
+        for chunk in &self.chunks {
+            match chunk {
+                MergeChunk::Clean(clean) => lines.extend(clean.iter().cloned()),
+                MergeChunk::Conflict { base, ours, theirs } => {
+                    lines.push(format!("{} {}", OURS_MARKER, our_label));
+                    lines.extend(ours.iter().cloned());
+                    lines.push(format!("{} base", BASE_MARKER));
+                    lines.extend(base.iter().cloned());
+                    lines.push(SEPARATOR_MARKER.to_string());
+                    lines.extend(theirs.iter().cloned());
+                    lines.push(format!("{} {}", THEIRS_MARKER, their_label));
+                }
+            }
+        }
+
+        let mut text = lines.join("\n");
+        if self.trailing_newline && !lines.is_empty() {
+            text.push('\n');
+        }
+        text
+    }
+}
+
+/// One line of a line diff
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LineChange {
+    /// Line present in both
+    Same(String),
+    /// Line only in the old text
+    Removed(String),
+    /// Line only in the new text
+    Added(String),
+}
+
+/// Three-way merge of text
+///
+/// Lines are split on `\n`, so carriage returns are kept with their line.
+pub fn merge3(base: &str, ours: &str, theirs: &str) -> Merge {
+    let trailing_newline = if ours.is_empty() {
+        theirs.ends_with('\n') || theirs.is_empty()
+    } else {
+        ours.ends_with('\n')
+    };
+
+    let base = split_lines(base);
+    let ours = split_lines(ours);
+    let theirs = split_lines(theirs);
+
+    Merge {
+        chunks: merge_lines(&base, &ours, &theirs),
+        trailing_newline,
+    }
+}
+
+/// Three-way merge of line slices
+pub fn merge_lines<S: AsRef<str>>(base: &[S], ours: &[S], theirs: &[S]) -> Vec<MergeChunk> {
+    let base: Vec<&str> = base.iter().map(AsRef::as_ref).collect();
+    let ours: Vec<&str> = ours.iter().map(AsRef::as_ref).collect();
+    let theirs: Vec<&str> = theirs.iter().map(AsRef::as_ref).collect();
+
+    let ours_of_base = match_map(&base, &ours);
+    let theirs_of_base = match_map(&base, &theirs);
+
+    let mut chunks = Vec::new();
+    let (mut o, mut a, mut b) = (0, 0, 0);
+
+    while o < base.len() || a < ours.len() || b < theirs.len() {
+        // Lines unchanged on both sides
+        let mut stable = Vec::new();
+        while o < base.len() && ours_of_base[o] == Some(a) && theirs_of_base[o] == Some(b) {
+            stable.push(base[o].to_string());
+            o += 1;
+            a += 1;
+            b += 1;
+        }
+        if !stable.is_empty() {
+            push_clean(&mut chunks, stable);
+            continue;
+        }
+
+        // Next base line both sides still share
+        let sync = (o..base.len()).find(|&j| {
+            matches!(ours_of_base[j], Some(x) if x >= a)
+                && matches!(theirs_of_base[j], Some(y) if y >= b)
+        });
+        let (end_o, end_a, end_b) = match sync {
+            Some(j) => (
+                j,
+                ours_of_base[j].unwrap_or(a),
+                theirs_of_base[j].unwrap_or(b),
+            ),
+            None => (base.len(), ours.len(), theirs.len()),
+        };
+
+        let base_part = &base[o..end_o];
+        let ours_part = &ours[a..end_a];
+        let theirs_part = &theirs[b..end_b];
+        let owned = |lines: &[&str]| lines.iter().map(|l| l.to_string()).collect::<Vec<_>>();
+
+        if ours_part == base_part {
+            push_clean(&mut chunks, owned(theirs_part));
+        } else if theirs_part == base_part || ours_part == theirs_part {
+            push_clean(&mut chunks, owned(ours_part));
+        } else if let Some(lines) = merge_line_by_line(base_part, ours_part, theirs_part) {
+            push_clean(&mut chunks, lines);
+        } else {
+            chunks.push(MergeChunk::Conflict {
+                base: owned(base_part),
+                ours: owned(ours_part),
+                theirs: owned(theirs_part),
+            });
+        }
+
+        o = end_o;
+        a = end_a;
+        b = end_b;
+    }
+
+    chunks
+}
+
+/// Merge same-length regions where each line changed on at most one side
+///
+/// Plain diff3 treats adjacent edits as a conflict; when the region lines up
+/// one-to-one they can still be combined line by line.
+fn merge_line_by_line(base: &[&str], ours: &[&str], theirs: &[&str]) -> Option<Vec<String>> {
+    if base.len() != ours.len() || base.len() != theirs.len() {
+        return None;
+    }
+
+    base.iter()
+        .zip(ours)
+        .zip(theirs)
+        .map(|((b, o), t)| {
+            if o == b || o == t {
+                Some(t.to_string())
+            } else if t == b {
+                Some(o.to_string())
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+/// Resolve a conflict region whose changes do not actually overlap
+///
+/// Returns the merged lines when a line-level merge of the region is clean.
+pub fn resolve_region<S: AsRef<str>>(base: &[S], ours: &[S], theirs: &[S]) -> Option<Vec<String>> {
+    let mut lines = Vec::new();
+    for chunk in merge_lines(base, ours, theirs) {
+        match chunk {
+            MergeChunk::Clean(clean) => lines.extend(clean),
+            MergeChunk::Conflict { .. } => return None,
+        }
+    }
+    Some(lines)
+}
+
+/// Line diff between two versions
+pub fn line_diff<S: AsRef<str>>(old: &[S], new: &[S]) -> Vec<LineChange> {
+    let old: Vec<&str> = old.iter().map(AsRef::as_ref).collect();
+    let new: Vec<&str> = new.iter().map(AsRef::as_ref).collect();
+
+    let mut changes = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    for (x, y) in matching_pairs(&old, &new) {
+        changes.extend(old[i..x].iter().map(|l| LineChange::Removed(l.to_string())));
+        changes.extend(new[j..y].iter().map(|l| LineChange::Added(l.to_string())));
+        changes.push(LineChange::Same(old[x].to_string()));
+        i = x + 1;
+        j = y + 1;
+    }
+    changes.extend(old[i..].iter().map(|l| LineChange::Removed(l.to_string())));
+    changes.extend(new[j..].iter().map(|l| LineChange::Added(l.to_string())));
+    changes
+}
+
+/// Whether text still contains conflict markers
+pub fn has_conflict_markers(text: &str) -> bool {
+    text.lines().any(|line| {
+        line.starts_with(&format!("{} ", OURS_MARKER))
+            || line.starts_with(&format!("{} ", THEIRS_MARKER))
+            || line.trim_end() == SEPARATOR_MARKER
+    })
+}
+
+/// Prompt asking an agent to resolve one conflict region
+pub fn resolution_prompt<S: AsRef<str>>(
+    file_path: &str,
+    base: Option<&[S]>,
+    ours: &[S],
+    theirs: &[S],
+) -> String {
+    let block = |lines: &[S]| {
+        lines
+            .iter()
+            .map(AsRef::as_ref)
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+
+    let mut prompt = format!(
+        "Resolve this merge conflict in `{}`. Combine the intent of both sides.\n\n",
+        file_path
+    );
+    if let Some(base) = base {
+        prompt.push_str(&format!("Common ancestor:\n```\n{}\n```\n\n", block(base)));
+    }
+    prompt.push_str(&format!(
+        "Ours (current branch):\n```\n{}\n```\n\n",
+        block(ours)
+    ));
+    prompt.push_str(&format!(
+        "Theirs (incoming branch):\n```\n{}\n```\n\n",
+        block(theirs)
+    ));
+    prompt.push_str(
+        "Reply with only the resolved lines in a single fenced code block, \
+         without conflict markers.",
+    );
+    prompt
+}
+
+/// Extract the resolved lines from an agent reply
+///
+/// Uses the first fenced code block (or the whole reply when there is none)
+/// and rejects replies that still contain conflict markers.
+pub fn parse_resolution(reply: &str) -> Option<Vec<String>> {
+    let body = match reply.find("```") {
+        Some(start) => {
+            let after = &reply[start + 3..];
+            // Skip the info string (e.g. a language tag)
+            let after = after.split_once('\n').map(|(_, rest)| rest).unwrap_or("");
+            let end = after.find("```")?;
+            &after[..end]
+        }
+        None => reply.trim(),
+    };
+
+    if has_conflict_markers(body) {
+        return None;
+    }
+    Some(split_lines(body).into_iter().map(String::from).collect())
+}
+
+fn push_clean(chunks: &mut Vec<MergeChunk>, lines: Vec<String>) {
+    if lines.is_empty() {
+        return;
+    }
+    if let Some(MergeChunk::Clean(previous)) = chunks.last_mut() {
+        previous.extend(lines);
+    } else {
+        chunks.push(MergeChunk::Clean(lines));
+    }
+}
+
+fn split_lines(text: &str) -> Vec<&str> {
+    let mut lines: Vec<&str> = text.split('\n').collect();
+    if lines.last() == Some(&"") {
+        lines.pop();
+    }
+    lines
+}
+
+/// For every line of `a`, the index of the matching line of `b` (if any)
+fn match_map(a: &[&str], b: &[&str]) -> Vec<Option<usize>> {
+    let mut map = vec![None; a.len()];
+    for (x, y) in matching_pairs(a, b) {
+        map[x] = Some(y);
+    }
+    map
+}
+
+/// Matching line pairs of a longest common subsequence (Myers' algorithm)
+fn matching_pairs(a: &[&str], b: &[&str]) -> Vec<(usize, usize)> {
+    // Common prefix and suffix are matched directly
+    let prefix = a.iter().zip(b).take_while(|(x, y)| x == y).count();
+    let suffix = a[prefix..]
+        .iter()
+        .rev()
+        .zip(b[prefix..].iter().rev())
+        .take_while(|(x, y)| x == y)
+        .count();
+
+    let mut pairs: Vec<(usize, usize)> = (0..prefix).map(|i| (i, i)).collect();
+    let inner = myers(&a[prefix..a.len() - suffix], &b[prefix..b.len() - suffix]);
+    pairs.extend(inner.into_iter().map(|(x, y)| (x + prefix, y + prefix)));
+    pairs.extend((0..suffix).map(|i| (a.len() - suffix + i, b.len() - suffix + i)));
+    pairs
+}
+
+fn myers(a: &[&str], b: &[&str]) -> Vec<(usize, usize)> {
+    let (n, m) = (a.len() as isize, b.len() as isize);
+    if n == 0 || m == 0 {
+        return Vec::new();
+    }
+
+    let max = n + m;
+    let offset = max + 1;
+    let mut v = vec![0isize; (2 * max + 3) as usize];
+    // Snapshot of v for k in [-d-1, d+1] before each round d
+    let mut trace: Vec<Vec<isize>> = Vec::new();
+
+    'search: for d in 0..=max {
+        trace.push(v[(offset - d - 1) as usize..=(offset + d + 1) as usize].to_vec());
+        let mut k = -d;
+        while k <= d {
+            let idx = (k + offset) as usize;
+            let mut x = if k == -d || (k != d && v[idx - 1] < v[idx + 1]) {
+                v[idx + 1]
+            } else {
+                v[idx - 1] + 1
+            };
+            let mut y = x - k;
+            while x < n && y < m && a[x as usize] == b[y as usize] {
+                x += 1;
+                y += 1;
+            }
+            v[idx] = x;
+            if x >= n && y >= m {
+                break 'search;
+            }
+            k += 2;
+        }
+    }
+
+    let mut pairs = Vec::new();
+    let (mut x, mut y) = (n, m);
+    for (d, snapshot) in trace.iter().enumerate().rev() {
+        let d = d as isize;
+        let at = |k: isize| snapshot[(k + d + 1) as usize];
+        let k = x - y;
+        let prev_k = if k == -d || (k != d && at(k - 1) < at(k + 1)) {
+            k + 1
+        } else {
+            k - 1
+        };
+        let prev_x = at(prev_k);
+        let prev_y = prev_x - prev_k;
+
+        while x > prev_x && y > prev_y {
+            x -= 1;
+            y -= 1;
+            pairs.push((x as usize, y as usize));
+        }
+        if d > 0 {
+            x = prev_x;
+            y = prev_y;
+        }
+    }
+
+    pairs.reverse();
+    pairs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge_non_overlapping_changes() {
+        let merge = merge3("a\nb\nc\nd\n", "A\nb\nc\nd\n", "a\nb\nc\nD\n");
+        assert!(merge.is_clean());
+        assert_eq!(merge.to_text("ours", "theirs"), "A\nb\nc\nD\n");
+    }
+
+    #[test]
+    fn test_merge_identical_changes() {
+        let merge = merge3("a\nb\n", "a\nx\n", "a\nx\n");
+        assert!(merge.is_clean());
+        assert_eq!(merge.resolved_lines().unwrap(), vec!["a", "x"]);
+    }
+
+    #[test]
+    fn test_merge_overlapping_conflict() {
+        let merge = merge3("a\nb\nc\n", "a\nours\nc\n", "a\ntheirs\nc\n");
+        assert_eq!(merge.conflict_count(), 1);
+        assert!(merge.resolved_lines().is_none());
+
+        match &merge.chunks[1] {
+            MergeChunk::Conflict { base, ours, theirs } => {
+                assert_eq!(base, &vec!["b".to_string()]);
+                assert_eq!(ours, &vec!["ours".to_string()]);
+                assert_eq!(theirs, &vec!["theirs".to_string()]);
+            }
+            other => panic!("expected conflict, got {:?}", other),
+        }
+
+        let text = merge.to_text("HEAD", "feature");
+        assert_eq!(
+            text,
+            "a\n<<<<<<< HEAD\nours\n||||||| base\nb\n=======\ntheirs\n>>>>>>> feature\nc\n"
+        );
+        assert!(has_conflict_markers(&text));
+    }
+
+    #[test]
+    fn test_merge_both_insert_at_same_place() {
+        let merge = merge3("a\nb\n", "a\nx\nb\n", "a\ny\nb\n");
+        assert_eq!(merge.conflict_count(), 1);
+    }
+
+    #[test]
+    fn test_merge_deletion_and_edit_elsewhere() {
+        let merge = merge3("a\nb\nc\nd\ne\n", "a\nc\nd\ne\n", "a\nb\nc\nd\nE\n");
+        assert!(merge.is_clean());
+        assert_eq!(merge.to_text("o", "t"), "a\nc\nd\nE\n");
+    }
+
+    #[test]
+    fn test_merge_preserves_missing_trailing_newline() {
+        let merge = merge3("a\nb", "A\nb", "a\nB");
+        assert_eq!(merge.to_text("o", "t"), "A\nB");
+    }
+
+    #[test]
+    fn test_merge_versions_requires_all_stages() {
+        let versions = MergeVersions {
+            base: None,
+            ours: Some("a\n".to_string()),
+            theirs: Some("b\n".to_string()),
+        };
+        assert!(versions.merge().is_none());
+    }
+
+    #[test]
+    fn test_resolve_region() {
+        // git reports adjacent edits as one region; line level they are independent
+        let base = ["x", "y"];
+        let ours = ["X", "y"];
+        let theirs = ["x", "Y"];
+        assert_eq!(
+            resolve_region(&base, &ours, &theirs).unwrap(),
+            vec!["X", "Y"]
+        );
+
+        assert!(resolve_region(&["x"], &["1"], &["2"]).is_none());
+    }
+
+    #[test]
+    fn test_line_diff() {
+        let changes = line_diff(&["a", "b", "c"], &["a", "B", "c", "d"]);
+        assert_eq!(
+            changes,
+            vec![
+                LineChange::Same("a".to_string()),
+                LineChange::Removed("b".to_string()),
+                LineChange::Added("B".to_string()),
+                LineChange::Same("c".to_string()),
+                LineChange::Added("d".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_myers_finds_longest_common_subsequence() {
+        let a = ["a", "b", "c", "a", "b", "b", "a"];
+        let b = ["c", "b", "a", "b", "a", "c"];
+        let pairs = matching_pairs(&a, &b);
+        assert_eq!(pairs.len(), 4);
+        for window in pairs.windows(2) {
+            assert!(window[0].0 < window[1].0 && window[0].1 < window[1].1);
+        }
+        for (x, y) in pairs {
+            assert_eq!(a[x], b[y]);
+        }
+    }
+
+    #[test]
+    fn test_parse_resolution() {
+        let reply = "Here you go:\n```rust\nfn a() {}\nfn b() {}\n```\nDone.";
+        assert_eq!(
+            parse_resolution(reply).unwrap(),
+            vec!["fn a() {}", "fn b() {}"]
+        );
+        assert_eq!(parse_resolution("x\ny").unwrap(), vec!["x", "y"]);
+        assert!(parse_resolution("```\n<<<<<<< HEAD\na\n=======\nb\n>>>>>>> x\n```").is_none());
+    }
+
+    #[test]
+    fn test_resolution_prompt_includes_all_sides() {
+        let prompt = resolution_prompt("src/lib.rs", Some(&["base"]), &["ours"], &["theirs"]);
+        assert!(prompt.contains("src/lib.rs"));
+        assert!(prompt.contains("```\nbase\n```"));
+        assert!(prompt.contains("```\nours\n```"));
+        assert!(prompt.contains("```\ntheirs\n```"));
+    }
+
+    #[test]
+    fn test_has_conflict_markers_ignores_lookalikes() {
+        assert!(!has_conflict_markers("a\n======= heading\n"));
+        assert!(!has_conflict_markers("plain text\n"));
+    }
+}
@@ -1,13 +1,23 @@
 //! Git integration module
 //!
 //! Provides services for interacting with git repositories, including
-//! status checking, commit history, branch management, staging operations,
-//! and auto-commit functionality for AI-assisted changes.
+//! status checking, commit history, branch management, staging operations
+//! (whole files, hunks or line ranges), three-way merging of conflicts,
+//! commit message and pull request description generation, and auto-commit
+//! functionality for AI-assisted changes.
 
 pub mod auto_commit;
+pub mod commit_message;
+pub mod diff;
 pub mod graph_service;
+pub mod merge;
+pub mod patch;
 pub mod service;
 
 pub use auto_commit::AutoCommitManager;
+pub use commit_message::{CommitMessage, CommitMessageGenerator, PullRequestDescription};
+pub use diff::{ChangeType, DiffHunk, DiffLine};
 pub use graph_service::GitGraphService;
-pub use service::{BranchInfo, CommitInfo, FileChange, GitService};
+pub use merge::{Merge, MergeChunk, MergeVersions};
+pub use patch::{FileDiff, FileDiffKind};
+pub use service::{BranchInfo, CommitInfo, FileChange, GitService, RepoSnapshot};
@@ -0,0 +1,820 @@
+//! Unified diff parsing and partial patch synthesis
+//!
+//! Parses `git diff` output into per-file [`FileDiff`]s made of [`DiffHunk`]s
+//! and builds patches for a subset of a file's changes, suitable for
+//! `git apply --cached` (staging) or `git apply --cached --reverse`
+//! (unstaging). New, deleted, renamed and binary files are recognised from
+//! the extended diff headers.
+//!
+//! # Examples
+//!
+//! ```
+//! use toad::git::patch::parse_diff;
+//!
+//! let diff = "\
+//! diff --git a/src/lib.rs b/src/lib.rs
+//! --- a/src/lib.rs
+//! +++ b/src/lib.rs
+//! @@ -1,2 +1,3 @@
+//!  fn a() {}
+//! +fn b() {}
+//! +fn c() {}
+//!  fn d() {}
+//! ";
+//! let files = parse_diff(diff);
+//! assert_eq!(files.len(), 1);
+//!
+//! // Stage only `fn b() {}` (line 1 of hunk 0)
+//! let patch = files[0].lines_patch(0, 1..=1, false).unwrap();
+//! assert!(patch.contains("@@ -1,2 +1,3 @@"));
+//! assert!(patch.contains("+fn b() {}"));
+//! assert!(!patch.contains("fn c() {}"));
+//! ```
+
+use crate::git::diff::{ChangeType, DiffHunk, DiffLine};
+use anyhow::{Result, bail};
+use std::collections::HashSet;
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
+
+/// Marker git emits after a line without a trailing newline
+const NO_NEWLINE_MARKER: &str = "\\ No newline at end of file";
+
+/// Kind of change a file diff describes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileDiffKind {
+    /// Content or mode change of an existing file
+    Modified,
+    /// File created
+    Added,
+    /// File removed
+    Deleted,
+    /// File moved (possibly with content changes)
+    Renamed,
+}
+
+/// Diff of a single file
+#[derive(Debug, Clone)]
+pub struct FileDiff {
+    /// Path before the change (same as `new_path` unless renamed)
+    pub old_path: PathBuf,
+    /// Path after the change (same as `old_path` unless renamed)
+    pub new_path: PathBuf,
+    /// Kind of change
+    pub kind: FileDiffKind,
+    /// Whether git reported the file as binary
+    pub binary: bool,
+    /// Text hunks (empty for binary, pure rename or mode-only changes)
+    pub hunks: Vec<DiffHunk>,
+    /// Header lines as emitted by git, up to the first hunk
+    header: Vec<String>,
+    /// File mode from `new file mode` / `deleted file mode` headers
+    mode: Option<String>,
+    /// (hunk, line) pairs followed by a "No newline at end of file" marker
+    no_newline: HashSet<(usize, usize)>,
+}
+
+impl FileDiff {
+    /// Number of added and removed lines
+    pub fn line_counts(&self) -> (usize, usize) {
+        self.hunks
+            .iter()
+            .flat_map(|h| &h.lines)
+            .fold((0, 0), |(added, removed), line| match line.change_type {
+                ChangeType::Added => (added + 1, removed),
+                ChangeType::Removed => (added, removed + 1),
+                _ => (added, removed),
+            })
+    }
+
+    /// Path of the file in the working tree
+    pub fn path(&self) -> &Path {
+        match self.kind {
+            FileDiffKind::Deleted => &self.old_path,
+            _ => &self.new_path,
+        }
+    }
+
+    /// Find the hunk with the same header and lines as `hunk`
+    ///
+    /// Used to check that a hunk picked from an earlier diff still applies.
+    pub fn find_hunk(&self, hunk: &DiffHunk) -> Option<usize> {
+        self.hunks.iter().position(|candidate| {
+            candidate.header == hunk.header
+                && candidate.lines.len() == hunk.lines.len()
+                && candidate
+                    .lines
+                    .iter()
+                    .zip(&hunk.lines)
+                    .all(|(a, b)| a.change_type == b.change_type && a.content == b.content)
+        })
+    }
+
+    /// The whole file diff as a patch
+    pub fn to_patch(&self) -> String {
+        let mut patch = self.header.join("\n");
+        patch.push('\n');
+        for (hunk_idx, hunk) in self.hunks.iter().enumerate() {
+            patch.push_str(&hunk.header);
+            patch.push('\n');
+            for (line_idx, line) in hunk.lines.iter().enumerate() {
+                let marker = match line.change_type {
+                    ChangeType::Added => '+',
+                    ChangeType::Removed => '-',
+                    ChangeType::Modified | ChangeType::Context => ' ',
+                };
+                self.push_line(&mut patch, marker, &line.content, hunk_idx, line_idx);
+            }
+        }
+        patch
+    }
+
+    /// Patch containing a single hunk
+    ///
+    /// With `reverse` the patch is built for `git apply --reverse` against
+    /// the new side of the diff, which is how staged hunks are unstaged.
+    pub fn hunk_patch(&self, hunk: usize, reverse: bool) -> Result<String> {
+        self.check_hunk(hunk)?;
+        self.build_patch(reverse, |h, _| h == hunk)
+    }
+
+    /// Patch containing only the given lines (indices into the hunk's lines)
+    ///
+    /// Unselected removals are kept as context and unselected additions are
+    /// dropped (the other way around with `reverse`), so the patch applies
+    /// cleanly on its own.
+    pub fn lines_patch(
+        &self,
+        hunk: usize,
+        lines: RangeInclusive<usize>,
+        reverse: bool,
+    ) -> Result<String> {
+        self.check_hunk(hunk)?;
+        self.build_patch(reverse, |h, l| h == hunk && lines.contains(&l))
+    }
+
+    fn check_hunk(&self, hunk: usize) -> Result<()> {
+        if self.binary {
+            bail!(
+                "{} is a binary file and can only be staged as a whole",
+                self.path().display()
+            );
+        }
+        if hunk >= self.hunks.len() {
+            bail!(
+                "Hunk {} out of range ({} has {} hunks)",
+                hunk,
+                self.path().display(),
+                self.hunks.len()
+            );
+        }
+        Ok(())
+    }
+
+    /// Build a patch from the changed lines accepted by `selected`
+    fn build_patch(
+        &self,
+        reverse: bool,
+        selected: impl Fn(usize, usize) -> bool,
+    ) -> Result<String> {
+        let complete = self.hunks.iter().enumerate().all(|(h, hunk)| {
+            hunk.lines
+                .iter()
+                .enumerate()
+                .all(|(l, line)| !is_change(line) || selected(h, l))
+        });
+
+        let mut body = String::new();
+        // Net line count change (new - old) of the hunks emitted so far
+        let mut delta: isize = 0;
+
+        for (hunk_idx, hunk) in self.hunks.iter().enumerate() {
+            let mut lines = Vec::new();
+            let mut has_change = false;
+
+            for (line_idx, line) in hunk.lines.iter().enumerate() {
+                let chosen = selected(hunk_idx, line_idx);
+                let marker = match (line.change_type, chosen, reverse) {
+                    (ChangeType::Context | ChangeType::Modified, _, _) => Some(' '),
+                    (ChangeType::Added, true, _) => Some('+'),
+                    (ChangeType::Removed, true, _) => Some('-'),
+                    // Unselected lines keep the side the patch is applied to intact
+                    (ChangeType::Added, false, false) | (ChangeType::Removed, false, true) => None,
+                    (ChangeType::Added, false, true) | (ChangeType::Removed, false, false) => {
+                        Some(' ')
+                    }
+                };
+                if let Some(marker) = marker {
+                    has_change |= marker != ' ';
+                    lines.push((marker, line_idx));
+                }
+            }
+
+            if !has_change {
+                continue;
+            }
+
+            let old_count = lines.iter().filter(|(m, _)| *m != '+').count();
+            let new_count = lines.iter().filter(|(m, _)| *m != '-').count();
+
+            // The side the patch is applied to keeps its original position
+            let (old_start, new_start) = if reverse {
+                let new_pos = range_position(hunk.new_start, hunk.new_count);
+                let old_pos = (new_pos as isize - delta).max(1) as usize;
+                (range_start(old_pos, old_count), hunk.new_start)
+            } else {
+                let old_pos = range_position(hunk.old_start, hunk.old_count);
+                let new_pos = (old_pos as isize + delta).max(1) as usize;
+                (hunk.old_start, range_start(new_pos, new_count))
+            };
+            delta += new_count as isize - old_count as isize;
+
+            body.push_str(&format!(
+                "@@ -{},{} +{},{} @@{}\n",
+                old_start,
+                old_count,
+                new_start,
+                new_count,
+                hunk_context(&hunk.header)
+            ));
+            for (marker, line_idx) in lines {
+                let content = &hunk.lines[line_idx].content;
+                self.push_line(&mut body, marker, content, hunk_idx, line_idx);
+            }
+        }
+
+        if body.is_empty() {
+            bail!("No changes selected in {}", self.path().display());
+        }
+
+        // Content selections never revert a rename; unstage the path for that
+        let header = if complete && self.kind != FileDiffKind::Renamed {
+            self.header.clone()
+        } else {
+            self.partial_header(reverse)
+        };
+
+        let mut patch = header.join("\n");
+        patch.push('\n');
+        patch.push_str(&body);
+        Ok(patch)
+    }
+
+    /// Header for a patch carrying only part of the file's changes
+    ///
+    /// A partial new file is still created and a partial deletion still
+    /// removes content, but a partially reverted creation (or partially
+    /// applied deletion) leaves the file in place, so those become plain
+    /// modifications of the path present on the side being patched.
+    fn partial_header(&self, reverse: bool) -> Vec<String> {
+        let old = git_path(&self.old_path);
+        let new = git_path(&self.new_path);
+        let mode = self.mode.clone().unwrap_or_else(|| "100644".to_string());
+
+        match (self.kind, reverse) {
+            (FileDiffKind::Added, false) => vec![
+                format!("diff --git a/{new} b/{new}"),
+                format!("new file mode {mode}"),
+                "--- /dev/null".to_string(),
+                format!("+++ b/{new}"),
+            ],
+            (FileDiffKind::Deleted, true) => vec![
+                format!("diff --git a/{old} b/{old}"),
+                format!("deleted file mode {mode}"),
+                format!("--- a/{old}"),
+                "+++ /dev/null".to_string(),
+            ],
+            (FileDiffKind::Renamed, false) => vec![
+                format!("diff --git a/{old} b/{new}"),
+                format!("rename from {old}"),
+                format!("rename to {new}"),
+                format!("--- a/{old}"),
+                format!("+++ b/{new}"),
+            ],
+            (FileDiffKind::Deleted, false) => modification_header(&old),
+            _ => modification_header(&new),
+        }
+    }
+
+    fn push_line(&self, out: &mut String, marker: char, content: &str, hunk: usize, line: usize) {
+        out.push(marker);
+        out.push_str(content);
+        out.push('\n');
+        if self.no_newline.contains(&(hunk, line)) {
+            out.push_str(NO_NEWLINE_MARKER);
+            out.push('\n');
+        }
+    }
+}
+
+/// Parse `git diff` output into per-file diffs
+///
+/// Text before the first `diff --git` line is ignored. Content lines are split
+/// on `\n` only, so carriage returns in CRLF files are preserved.
+pub fn parse_diff(text: &str) -> Vec<FileDiff> {
+    let mut files = Vec::new();
+    let mut current: Option<FileDiff> = None;
+    let mut old_remaining = 0usize;
+    let mut new_remaining = 0usize;
+    let mut old_line = 0usize;
+    let mut new_line = 0usize;
+
+    let mut lines: Vec<&str> = text.split('\n').collect();
+    if lines.last() == Some(&"") {
+        lines.pop();
+    }
+
+    for line in lines {
+        // Hunk body, bounded by the counts from the hunk header
+        if let Some(file) = current.as_mut()
+            && let Some(hunk_idx) = file.hunks.len().checked_sub(1)
+        {
+            let hunk = &mut file.hunks[hunk_idx];
+            if line.starts_with('\\') {
+                if let Some(line_idx) = hunk.lines.len().checked_sub(1) {
+                    file.no_newline.insert((hunk_idx, line_idx));
+                }
+                continue;
+            }
+
+            if old_remaining > 0 || new_remaining > 0 {
+                let (change_type, content) = match line.chars().next() {
+                    Some('+') => (ChangeType::Added, &line[1..]),
+                    Some('-') => (ChangeType::Removed, &line[1..]),
+                    Some(' ') => (ChangeType::Context, &line[1..]),
+                    // Some tools strip the space from empty context lines
+                    None => (ChangeType::Context, ""),
+                    _ => (ChangeType::Context, line),
+                };
+                let (old_num, new_num) = match change_type {
+                    ChangeType::Added => {
+                        new_remaining = new_remaining.saturating_sub(1);
+                        new_line += 1;
+                        (None, Some(new_line - 1))
+                    }
+                    ChangeType::Removed => {
+                        old_remaining = old_remaining.saturating_sub(1);
+                        old_line += 1;
+                        (Some(old_line - 1), None)
+                    }
+                    _ => {
+                        old_remaining = old_remaining.saturating_sub(1);
+                        new_remaining = new_remaining.saturating_sub(1);
+                        old_line += 1;
+                        new_line += 1;
+                        (Some(old_line - 1), Some(new_line - 1))
+                    }
+                };
+                hunk.add_line(DiffLine::new(
+                    content.to_string(),
+                    change_type,
+                    old_num,
+                    new_num,
+                ));
+                continue;
+            }
+        }
+
+        if let Some(rest) = line.strip_prefix("diff --git ") {
+            if let Some(file) = current.take() {
+                files.push(file);
+            }
+            let (old, new) = split_diff_git_paths(rest);
+            current = Some(FileDiff {
+                old_path: PathBuf::from(&old),
+                new_path: PathBuf::from(&new),
+                kind: FileDiffKind::Modified,
+                binary: false,
+                hunks: Vec::new(),
+                header: vec![line.to_string()],
+                mode: None,
+                no_newline: HashSet::new(),
+            });
+            continue;
+        }
+
+        let Some(file) = current.as_mut() else {
+            continue;
+        };
+
+        if line.starts_with("@@") {
+            if let Some(((old_start, old_count), (new_start, new_count))) = parse_hunk_header(line)
+            {
+                file.hunks.push(DiffHunk::new(
+                    line.to_string(),
+                    old_start,
+                    old_count,
+                    new_start,
+                    new_count,
+                ));
+                old_remaining = old_count;
+                new_remaining = new_count;
+                old_line = old_start;
+                new_line = new_start;
+            }
+            continue;
+        }
+
+        file.header.push(line.to_string());
+        if let Some(mode) = line.strip_prefix("new file mode ") {
+            file.kind = FileDiffKind::Added;
+            file.mode = Some(mode.trim().to_string());
+        } else if let Some(mode) = line.strip_prefix("deleted file mode ") {
+            file.kind = FileDiffKind::Deleted;
+            file.mode = Some(mode.trim().to_string());
+        } else if let Some(path) = line.strip_prefix("rename from ") {
+            file.kind = FileDiffKind::Renamed;
+            file.old_path = PathBuf::from(unquote(path));
+        } else if let Some(path) = line.strip_prefix("rename to ") {
+            file.kind = FileDiffKind::Renamed;
+            file.new_path = PathBuf::from(unquote(path));
+        } else if let Some(path) = line.strip_prefix("--- ") {
+            if let Some(path) = strip_side_prefix(path, "a/") {
+                file.old_path = PathBuf::from(path);
+            }
+        } else if let Some(path) = line.strip_prefix("+++ ") {
+            if let Some(path) = strip_side_prefix(path, "b/") {
+                file.new_path = PathBuf::from(path);
+            }
+        } else if line.starts_with("Binary files ") || line == "GIT binary patch" {
+            file.binary = true;
+        }
+    }
+
+    if let Some(file) = current {
+        files.push(file);
+    }
+
+    // Keep both paths meaningful for creations and deletions
+    for file in &mut files {
+        match file.kind {
+            FileDiffKind::Added => file.old_path = file.new_path.clone(),
+            FileDiffKind::Deleted => file.new_path = file.old_path.clone(),
+            _ => {}
+        }
+    }
+
+    files
+}
+
+/// Whether a line is an addition or removal
+fn is_change(line: &DiffLine) -> bool {
+    matches!(line.change_type, ChangeType::Added | ChangeType::Removed)
+}
+
+/// First line covered by a hunk range (git uses `start - 1` for empty ranges)
+fn range_position(start: usize, count: usize) -> usize {
+    if count == 0 { start + 1 } else { start }
+}
+
+/// Inverse of [`range_position`]
+fn range_start(position: usize, count: usize) -> usize {
+    if count == 0 {
+        position.saturating_sub(1)
+    } else {
+        position
+    }
+}
+
+/// Function context git appends after the closing `@@`
+fn hunk_context(header: &str) -> &str {
+    header
+        .strip_prefix("@@")
+        .and_then(|rest| rest.find("@@").map(|idx| &rest[idx + 2..]))
+        .unwrap_or("")
+}
+
+/// Parse `@@ -old_start,old_count +new_start,new_count @@`
+fn parse_hunk_header(header: &str) -> Option<((usize, usize), (usize, usize))> {
+    let mut parts = header.split_whitespace().skip(1);
+    let old = parts.next()?.strip_prefix('-')?;
+    let new = parts.next()?.strip_prefix('+')?;
+
+    let parse_range = |range: &str| -> Option<(usize, usize)> {
+        match range.split_once(',') {
+            Some((start, count)) => Some((start.parse().ok()?, count.parse().ok()?)),
+            None => Some((range.parse().ok()?, 1)),
+        }
+    };
+
+    Some((parse_range(old)?, parse_range(new)?))
+}
+
+/// Split the `a/<old> b/<new>` part of a `diff --git` line
+fn split_diff_git_paths(rest: &str) -> (String, String) {
+    if rest.starts_with('"') {
+        let mut parts = rest.splitn(2, "\" ");
+        let old = parts.next().unwrap_or_default();
+        let new = parts.next().unwrap_or_default();
+        let old = unquote(&format!("{old}\""));
+        let new = unquote(new);
+        return (
+            old.strip_prefix("a/").unwrap_or(&old).to_string(),
+            new.strip_prefix("b/").unwrap_or(&new).to_string(),
+        );
+    }
+
+    // Without renames both halves are the same path, so split in the middle
+    let half = rest.len() / 2;
+    if rest.len() % 2 == 1
+        && rest.is_char_boundary(half)
+        && rest.as_bytes().get(half) == Some(&b' ')
+        && rest[..half].strip_prefix("a/") == rest[half + 1..].strip_prefix("b/")
+    {
+        let path = rest[2..half].to_string();
+        return (path.clone(), path);
+    }
+
+    match rest.split_once(" b/") {
+        Some((old, new)) => (
+            old.strip_prefix("a/").unwrap_or(old).to_string(),
+            new.to_string(),
+        ),
+        None => (rest.to_string(), rest.to_string()),
+    }
+}
+
+/// Strip `a/` or `b/` from a `---`/`+++` path, `None` for `/dev/null`
+fn strip_side_prefix(path: &str, prefix: &str) -> Option<String> {
+    let path = unquote(path.trim_end_matches('\t'));
+    if path == "/dev/null" {
+        return None;
+    }
+    Some(path.strip_prefix(prefix).unwrap_or(&path).to_string())
+}
+
+/// Remove C-style quoting git applies to unusual paths
+fn unquote(path: &str) -> String {
+    let Some(inner) = path.strip_prefix('"').and_then(|p| p.strip_suffix('"')) else {
+        return path.to_string();
+    };
+
+    let mut out = Vec::new();
+    let mut bytes = inner.bytes();
+    while let Some(b) = bytes.next() {
+        if b != b'\\' {
+            out.push(b);
+            continue;
+        }
+        match bytes.next() {
+            Some(b'n') => out.push(b'\n'),
+            Some(b't') => out.push(b'\t'),
+            Some(d @ b'0'..=b'7') => {
+                let mut value = (d - b'0') as u32;
+                for _ in 0..2 {
+                    if let Some(d @ b'0'..=b'7') = bytes.clone().next() {
+                        bytes.next();
+                        value = value * 8 + (d - b'0') as u32;
+                    }
+                }
+                out.push(value as u8);
+            }
+            Some(other) => out.push(other),
+            None => {}
+        }
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+/// Path as written in patch headers (always `/`-separated)
+fn git_path(path: &Path) -> String {
+    path.to_string_lossy().replace('\\', "/")
+}
+
+fn modification_header(path: &str) -> Vec<String> {
+    vec![
+        format!("diff --git a/{path} b/{path}"),
+        format!("--- a/{path}"),
+        format!("+++ b/{path}"),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TWO_HUNKS: &str = "\
+diff --git a/src/main.rs b/src/main.rs
+index 1111111..2222222 100644
+--- a/src/main.rs
++++ b/src/main.rs
+@@ -1,4 +1,5 @@ mod a;
+ line 1
+-line 2
++line 2 changed
++line 2b
+ line 3
+ line 4
+@@ -10,3 +11,2 @@ fn tail() {
+ line 10
+-line 11
+ line 12
+";
+
+    #[test]
+    fn test_parse_modified_file() {
+        let files = parse_diff(TWO_HUNKS);
+        assert_eq!(files.len(), 1);
+
+        let file = &files[0];
+        assert_eq!(file.kind, FileDiffKind::Modified);
+        assert_eq!(file.path(), Path::new("src/main.rs"));
+        assert_eq!(file.hunks.len(), 2);
+        assert_eq!(file.hunks[0].lines.len(), 6);
+        assert_eq!(file.hunks[1].old_start, 10);
+        assert_eq!(file.hunks[1].lines[1].old_line_num, Some(11));
+    }
+
+    #[test]
+    fn test_to_patch_roundtrip() {
+        let files = parse_diff(TWO_HUNKS);
+        assert_eq!(files[0].to_patch(), TWO_HUNKS);
+    }
+
+    #[test]
+    fn test_parse_removed_line_that_looks_like_header() {
+        let diff = "\
+diff --git a/notes.md b/notes.md
+--- a/notes.md
++++ b/notes.md
+@@ -1,2 +1,1 @@
+--- not a header
+ keep
+";
+        let files = parse_diff(diff);
+        let hunk = &files[0].hunks[0];
+        assert_eq!(hunk.lines.len(), 2);
+        assert_eq!(hunk.lines[0].change_type, ChangeType::Removed);
+        assert_eq!(hunk.lines[0].content, "-- not a header");
+    }
+
+    #[test]
+    fn test_second_hunk_patch_shifts_nothing_on_old_side() {
+        let files = parse_diff(TWO_HUNKS);
+        let patch = files[0].hunk_patch(1, false).unwrap();
+
+        // Only the second hunk, positioned against the unmodified old side
+        assert!(patch.contains("@@ -10,3 +10,2 @@ fn tail() {"));
+        assert!(!patch.contains("line 2 changed"));
+        assert!(patch.starts_with("diff --git a/src/main.rs b/src/main.rs\n--- a/src/main.rs"));
+    }
+
+    #[test]
+    fn test_lines_patch_forward() {
+        let files = parse_diff(TWO_HUNKS);
+        // Select only "+line 2b" (index 3)
+        let patch = files[0].lines_patch(0, 3..=3, false).unwrap();
+
+        assert!(patch.contains("@@ -1,4 +1,5 @@ mod a;"));
+        // Unselected removal becomes context, unselected addition is dropped
+        assert!(patch.contains("\n line 2\n+line 2b\n"));
+        assert!(!patch.contains("line 2 changed"));
+    }
+
+    #[test]
+    fn test_lines_patch_reverse() {
+        let files = parse_diff(TWO_HUNKS);
+        // Unstage only "-line 2" (index 1)
+        let patch = files[0].lines_patch(0, 1..=1, true).unwrap();
+
+        // Unselected additions stay as context on the new side
+        assert!(patch.contains("@@ -1,6 +1,5 @@"));
+        assert!(patch.contains("\n-line 2\n line 2 changed\n line 2b\n"));
+    }
+
+    #[test]
+    fn test_lines_patch_without_changes_fails() {
+        let files = parse_diff(TWO_HUNKS);
+        // Index 0 is a context line
+        assert!(files[0].lines_patch(0, 0..=0, false).is_err());
+        assert!(files[0].hunk_patch(5, false).is_err());
+    }
+
+    #[test]
+    fn test_new_file_partial_keeps_creation_forward() {
+        let diff = "\
+diff --git a/new.txt b/new.txt
+new file mode 100755
+index 0000000..3333333
+--- /dev/null
++++ b/new.txt
+@@ -0,0 +1,3 @@
++a
++b
++c
+";
+        let file = &parse_diff(diff)[0];
+        assert_eq!(file.kind, FileDiffKind::Added);
+        assert_eq!(file.old_path, PathBuf::from("new.txt"));
+
+        let forward = file.lines_patch(0, 0..=1, false).unwrap();
+        assert!(forward.contains("new file mode 100755\n--- /dev/null\n+++ b/new.txt"));
+        assert!(forward.contains("@@ -0,0 +1,2 @@\n+a\n+b\n"));
+
+        // Partially unstaging a new file leaves it in place
+        let reverse = file.lines_patch(0, 2..=2, true).unwrap();
+        assert!(!reverse.contains("new file mode"));
+        assert!(reverse.contains("--- a/new.txt\n+++ b/new.txt"));
+        assert!(reverse.contains("@@ -1,2 +1,3 @@\n a\n b\n+c\n"));
+
+        // The whole file keeps git's own header
+        let whole = file.hunk_patch(0, false).unwrap();
+        assert!(whole.contains("index 0000000..3333333"));
+    }
+
+    #[test]
+    fn test_deleted_file_partial_forward_is_modification() {
+        let diff = "\
+diff --git a/old.txt b/old.txt
+deleted file mode 100644
+index 3333333..0000000
+--- a/old.txt
++++ /dev/null
+@@ -1,2 +0,0 @@
+-a
+-b
+";
+        let file = &parse_diff(diff)[0];
+        assert_eq!(file.kind, FileDiffKind::Deleted);
+        assert_eq!(file.path(), Path::new("old.txt"));
+
+        let forward = file.lines_patch(0, 0..=0, false).unwrap();
+        assert!(!forward.contains("deleted file mode"));
+        assert!(forward.contains("@@ -1,2 +1,1 @@\n-a\n b\n"));
+    }
+
+    #[test]
+    fn test_rename_and_binary_headers() {
+        let diff = "\
+diff --git a/a.txt b/b.txt
+similarity index 90%
+rename from a.txt
+rename to b.txt
+index 1111111..2222222 100644
+--- a/a.txt
++++ b/b.txt
+@@ -1 +1 @@
+-x
++y
+diff --git a/img.png b/img.png
+index 1111111..2222222 100644
+Binary files a/img.png and b/img.png differ
+";
+        let files = parse_diff(diff);
+        assert_eq!(files.len(), 2);
+
+        assert_eq!(files[0].kind, FileDiffKind::Renamed);
+        assert_eq!(files[0].old_path, PathBuf::from("a.txt"));
+        assert_eq!(files[0].new_path, PathBuf::from("b.txt"));
+        assert_eq!(files[0].hunks[0].old_count, 1);
+
+        assert!(files[1].binary);
+        assert!(files[1].hunks.is_empty());
+        assert!(files[1].hunk_patch(0, false).is_err());
+    }
+
+    #[test]
+    fn test_no_newline_marker_preserved() {
+        let diff = "\
+diff --git a/f b/f
+--- a/f
++++ b/f
+@@ -1 +1 @@
+-old
+\\ No newline at end of file
++new
+\\ No newline at end of file
+";
+        let file = &parse_diff(diff)[0];
+        assert_eq!(file.hunks[0].lines.len(), 2);
+        assert_eq!(file.to_patch(), diff);
+    }
+
+    #[test]
+    fn test_crlf_content_preserved() {
+        let diff = "diff --git a/w b/w\n--- a/w\n+++ b/w\n@@ -1 +1 @@\n-a\r\n+b\r\n";
+        let file = &parse_diff(diff)[0];
+        assert_eq!(file.hunks[0].lines[1].content, "b\r");
+    }
+
+    #[test]
+    fn test_quoted_paths() {
+        let diff = "\
+diff --git \"a/dir/na\\303\\257ve.txt\" \"b/dir/na\\303\\257ve.txt\"
+--- \"a/dir/na\\303\\257ve.txt\"
++++ \"b/dir/na\\303\\257ve.txt\"
+@@ -1 +1 @@
+-a
++b
+";
+        let file = &parse_diff(diff)[0];
+        assert_eq!(file.path(), Path::new("dir/naïve.txt"));
+    }
+
+    #[test]
+    fn test_paths_with_spaces() {
+        let diff = "diff --git a/my file.txt b/my file.txt\n";
+        let file = &parse_diff(diff)[0];
+        assert_eq!(file.path(), Path::new("my file.txt"));
+    }
+}
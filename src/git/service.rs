@@ -1,13 +1,20 @@
 //! Git service for interacting with repositories
 //!
 //! Provides async methods for common git operations like status checking,
-//! commit history, branch management, and file staging.
+//! commit history, branch management, and file, hunk and line staging.
 
+use crate::git::diff::DiffHunk;
+use crate::git::merge::{MergeVersions, has_conflict_markers};
+use crate::git::patch::{FileDiff, parse_diff};
 use anyhow::{Context, Result};
+use std::ops::RangeInclusive;
 use std::path::{Path, PathBuf};
 use std::process::Stdio;
 use tokio::process::Command;
 
+/// `git log` format parsed by [`parse_log`]
+const LOG_FORMAT: &str = "--pretty=format:%H|%h|%s|%an|%ae|%ct|%P";
+
 /// Git service for repository operations
 #[derive(Debug, Clone)]
 pub struct GitService {
@@ -59,6 +66,40 @@ pub struct BranchInfo {
     pub behind: usize,
 }
 
+/// Point-in-time view of a repository used to populate the git UI
+#[derive(Debug, Clone, Default)]
+pub struct RepoSnapshot {
+    /// Current branch name
+    pub branch: String,
+    /// Commits ahead of upstream
+    pub ahead: usize,
+    /// Commits behind upstream
+    pub behind: usize,
+    /// Working tree and index changes
+    pub changes: Vec<FileChange>,
+    /// Local branches
+    pub branches: Vec<BranchInfo>,
+}
+
+impl RepoSnapshot {
+    /// Paths of files with unresolved merge conflicts
+    pub fn conflicted_paths(&self) -> Vec<PathBuf> {
+        self.changes
+            .iter()
+            .filter(|c| matches!(c, FileChange::Conflicted(_)))
+            .map(|c| c.path().to_path_buf())
+            .collect()
+    }
+
+    /// Number of files with staged changes
+    pub fn staged_count(&self) -> usize {
+        self.changes
+            .iter()
+            .filter(|c| matches!(c, FileChange::Staged(_)))
+            .count()
+    }
+}
+
 /// Commit information
 #[derive(Debug, Clone)]
 pub struct CommitInfo {
@@ -149,15 +190,28 @@ impl GitService {
             let work_status = &line[1..2];
             let path = &line[3..];
 
+            // Files with changes in both the index and the working tree
+            // (e.g. after staging a single hunk) appear in both lists
+            if matches!(index_status, "M" | "A") && matches!(work_status, "M" | "D") {
+                changes.push(FileChange::Staged(PathBuf::from(path)));
+                changes.push(if work_status == "D" {
+                    FileChange::Deleted(PathBuf::from(path))
+                } else {
+                    FileChange::Modified(PathBuf::from(path))
+                });
+                continue;
+            }
+
             // Parse status codes
             let change = match (index_status, work_status) {
                 ("M", " ") => FileChange::Staged(PathBuf::from(path)),
                 ("A", " ") => FileChange::Staged(PathBuf::from(path)),
                 (" ", "M") => FileChange::Modified(PathBuf::from(path)),
-                ("M", "M") => FileChange::Modified(PathBuf::from(path)),
                 ("?", "?") => FileChange::Untracked(PathBuf::from(path)),
                 ("D", " ") | (" ", "D") => FileChange::Deleted(PathBuf::from(path)),
-                ("U", "U") | ("A", "A") | ("D", "D") => FileChange::Conflicted(PathBuf::from(path)),
+                ("U", _) | (_, "U") | ("A", "A") | ("D", "D") => {
+                    FileChange::Conflicted(PathBuf::from(path))
+                }
                 ("R", " ") => {
                     // Renamed files are shown as "old -> new"
                     if let Some((old, new)) = path.split_once(" -> ") {
@@ -283,7 +337,7 @@ impl GitService {
     ///
     /// * `max_count` - Maximum number of commits to fetch (None for all)
     pub async fn log(&self, max_count: Option<usize>) -> Result<Vec<CommitInfo>> {
-        let mut args = vec!["log", "--pretty=format:%H|%h|%s|%an|%ae|%ct|%P"];
+        let mut args = vec!["log", LOG_FORMAT];
 
         let max_str;
         if let Some(max) = max_count {
@@ -305,33 +359,7 @@ impl GitService {
             anyhow::bail!("git log failed: {}", stderr);
         }
 
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        let mut commits = Vec::new();
-
-        for line in stdout.lines() {
-            let parts: Vec<&str> = line.split('|').collect();
-            if parts.len() < 6 {
-                continue;
-            }
-
-            let parents = if parts.len() > 6 && !parts[6].is_empty() {
-                parts[6].split_whitespace().map(|s| s.to_string()).collect()
-            } else {
-                Vec::new()
-            };
-
-            commits.push(CommitInfo {
-                full_hash: parts[0].to_string(),
-                hash: parts[1].to_string(),
-                message: parts[2].to_string(),
-                author: parts[3].to_string(),
-                email: parts[4].to_string(),
-                timestamp: parts[5].parse().unwrap_or(0),
-                parents,
-            });
-        }
-
-        Ok(commits)
+        Ok(parse_log(&String::from_utf8_lossy(&output.stdout)))
     }
 
     /// Stage a file
@@ -434,6 +462,380 @@ impl GitService {
         let stdout = String::from_utf8_lossy(&output.stdout).to_string();
         Ok(stdout)
     }
+
+    /// Upstream of the current branch (e.g. `origin/main`), `None` if unset
+    pub async fn upstream_branch(&self) -> Option<String> {
+        let args = [
+            "rev-parse",
+            "--abbrev-ref",
+            "--symbolic-full-name",
+            "@{upstream}",
+        ];
+        let name = self.run_git(&args, "git rev-parse").await.ok()?;
+        let name = name.trim();
+        (!name.is_empty()).then(|| name.to_string())
+    }
+
+    /// Commits reachable from `HEAD` but not from `base`, newest first
+    pub async fn log_range(&self, base: &str) -> Result<Vec<CommitInfo>> {
+        let range = format!("{}..HEAD", base);
+        let stdout = self
+            .run_git(&["log", LOG_FORMAT, &range], "git log")
+            .await?;
+        Ok(parse_log(&stdout))
+    }
+
+    /// Files changed by a commit with its total additions and deletions
+    ///
+    /// Binary files count as changed with no additions or deletions.
+    pub async fn commit_stats(&self, rev: &str) -> Result<(Vec<String>, usize, usize)> {
+        let stdout = self
+            .run_git(&["show", "--numstat", "--format=", rev], "git show")
+            .await?;
+        let mut files = Vec::new();
+        let (mut additions, mut deletions) = (0, 0);
+        for line in stdout.lines() {
+            let mut parts = line.splitn(3, '\t');
+            let (Some(added), Some(removed), Some(path)) =
+                (parts.next(), parts.next(), parts.next())
+            else {
+                continue;
+            };
+            additions += added.parse::<usize>().unwrap_or(0);
+            deletions += removed.parse::<usize>().unwrap_or(0);
+            files.push(path.to_string());
+        }
+        Ok((files, additions, deletions))
+    }
+
+    /// Diff of `HEAD` against its merge base with `base`
+    pub async fn range_diff(&self, base: &str) -> Result<String> {
+        let range = format!("{}...HEAD", base);
+        self.run_git(&["diff", &range], "git diff").await
+    }
+
+    /// Collect branch, status and branch list in one call
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use toad::git::GitService;
+    /// # use std::path::Path;
+    /// #
+    /// # async fn example() -> anyhow::Result<()> {
+    /// let service = GitService::new(Path::new("."))?;
+    /// let snapshot = service.snapshot().await?;
+    /// println!("{} changes on {}", snapshot.changes.len(), snapshot.branch);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn snapshot(&self) -> Result<RepoSnapshot> {
+        let changes = self.status().await?;
+        let branch = self.current_branch().await.unwrap_or_default();
+        let (ahead, behind) = self.ahead_behind().await.unwrap_or((0, 0));
+        let branches = self.list_branches().await.unwrap_or_default();
+
+        Ok(RepoSnapshot {
+            branch,
+            ahead,
+            behind,
+            changes,
+            branches,
+        })
+    }
+
+    /// Switch to an existing branch
+    pub async fn checkout_branch(&self, name: &str) -> Result<()> {
+        self.run_git(&["checkout", name], "git checkout").await?;
+        Ok(())
+    }
+
+    /// Create a new branch, optionally switching to it
+    pub async fn create_branch(&self, name: &str, checkout: bool) -> Result<()> {
+        if checkout {
+            self.run_git(&["checkout", "-b", name], "git checkout -b")
+                .await?;
+        } else {
+            self.run_git(&["branch", name], "git branch").await?;
+        }
+        Ok(())
+    }
+
+    /// Apply a unified diff patch to the index (`cached`) or working tree
+    ///
+    /// With `reverse` the patch is applied in reverse, which is how staged
+    /// hunks are removed from the index again.
+    pub async fn apply_patch(&self, patch: &str, cached: bool, reverse: bool) -> Result<()> {
+        use tokio::io::AsyncWriteExt;
+
+        let mut args = vec!["apply", "--whitespace=nowarn"];
+        if cached {
+            args.push("--cached");
+        }
+        if reverse {
+            args.push("--reverse");
+        }
+        args.push("-");
+
+        let mut child = Command::new("git")
+            .current_dir(&self.repo_path)
+            .args(&args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .context("Failed to run git apply")?;
+
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin
+                .write_all(patch.as_bytes())
+                .await
+                .context("Failed to write patch to git apply")?;
+        }
+
+        let output = child
+            .wait_with_output()
+            .await
+            .context("Failed to run git apply")?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            anyhow::bail!("git apply failed: {}", stderr);
+        }
+
+        Ok(())
+    }
+
+    /// Parse the diff of a single file
+    ///
+    /// `staged` selects the index-vs-HEAD diff (with rename detection, so a
+    /// renamed file is found by either path); otherwise the working tree diff
+    /// is used, falling back to a creation diff for untracked files. Returns
+    /// `None` when the file has no changes.
+    pub async fn file_diff(
+        &self,
+        path: impl AsRef<Path>,
+        staged: bool,
+    ) -> Result<Option<FileDiff>> {
+        let path = path.as_ref();
+
+        if staged {
+            let text = self
+                .run_git(
+                    &["diff", "--cached", "-M", "--no-color"],
+                    "git diff --cached",
+                )
+                .await?;
+            return Ok(parse_diff(&text)
+                .into_iter()
+                .find(|file| file.new_path == path || file.old_path == path));
+        }
+
+        let path_str = path.to_str().unwrap_or("");
+        let text = self
+            .run_git(&["diff", "--no-color", "--", path_str], "git diff")
+            .await?;
+        if !text.trim().is_empty() {
+            return Ok(parse_diff(&text).into_iter().next());
+        }
+
+        let untracked = self
+            .run_git(
+                &["ls-files", "--others", "--exclude-standard", "--", path_str],
+                "git ls-files",
+            )
+            .await?;
+        if untracked.trim().is_empty() {
+            return Ok(None);
+        }
+
+        // `--no-index` exits with 1 when the files differ
+        let output = Command::new("git")
+            .current_dir(&self.repo_path)
+            .args([
+                "diff",
+                "--no-color",
+                "--no-index",
+                "--",
+                "/dev/null",
+                path_str,
+            ])
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .output()
+            .await
+            .context("Failed to run git diff --no-index")?;
+        if output.status.code().is_none_or(|code| code > 1) {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            anyhow::bail!("git diff --no-index failed: {}", stderr);
+        }
+
+        Ok(parse_diff(&String::from_utf8_lossy(&output.stdout))
+            .into_iter()
+            .next())
+    }
+
+    /// Stage a single hunk of a file's working tree diff
+    ///
+    /// The hunk is matched against a fresh diff, so a hunk taken from an
+    /// outdated diff is rejected instead of being applied in the wrong place.
+    pub async fn stage_hunk(&self, path: impl AsRef<Path>, hunk: &DiffHunk) -> Result<()> {
+        self.apply_selection(path.as_ref(), hunk, None, false).await
+    }
+
+    /// Remove a single hunk of a file's staged diff from the index
+    pub async fn unstage_hunk(&self, path: impl AsRef<Path>, hunk: &DiffHunk) -> Result<()> {
+        self.apply_selection(path.as_ref(), hunk, None, true).await
+    }
+
+    /// Stage some lines of a working tree hunk (indices into `hunk.lines`)
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use toad::git::GitService;
+    /// # use std::path::Path;
+    /// #
+    /// # async fn example() -> anyhow::Result<()> {
+    /// let service = GitService::new(Path::new("."))?;
+    /// if let Some(diff) = service.file_diff("src/main.rs", false).await? {
+    ///     // Stage the first three lines of the first hunk
+    ///     service.stage_lines("src/main.rs", &diff.hunks[0], 0..=2).await?;
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn stage_lines(
+        &self,
+        path: impl AsRef<Path>,
+        hunk: &DiffHunk,
+        lines: RangeInclusive<usize>,
+    ) -> Result<()> {
+        self.apply_selection(path.as_ref(), hunk, Some(lines), false)
+            .await
+    }
+
+    /// Unstage some lines of a staged hunk (indices into `hunk.lines`)
+    pub async fn unstage_lines(
+        &self,
+        path: impl AsRef<Path>,
+        hunk: &DiffHunk,
+        lines: RangeInclusive<usize>,
+    ) -> Result<()> {
+        self.apply_selection(path.as_ref(), hunk, Some(lines), true)
+            .await
+    }
+
+    /// Apply part of a file's diff to the index
+    async fn apply_selection(
+        &self,
+        path: &Path,
+        hunk: &DiffHunk,
+        lines: Option<RangeInclusive<usize>>,
+        staged: bool,
+    ) -> Result<()> {
+        let diff = self
+            .file_diff(path, staged)
+            .await?
+            .with_context(|| format!("No changes in {}", path.display()))?;
+        let index = diff
+            .find_hunk(hunk)
+            .context("Hunk no longer matches the current diff; refresh and try again")?;
+
+        let patch = match lines {
+            Some(lines) => diff.lines_patch(index, lines, staged)?,
+            None => diff.hunk_patch(index, staged)?,
+        };
+        self.apply_patch(&patch, true, staged).await
+    }
+
+    /// Load the base, ours and theirs versions of a conflicted file
+    ///
+    /// Reads index stages 1, 2 and 3 (`git show :1:<path>` etc.); stages that
+    /// do not exist, as in add/add or modify/delete conflicts, are `None`.
+    pub async fn merge_versions(&self, path: impl AsRef<Path>) -> Result<MergeVersions> {
+        let path = path.as_ref();
+        Ok(MergeVersions {
+            base: self.show_stage(path, 1).await,
+            ours: self.show_stage(path, 2).await,
+            theirs: self.show_stage(path, 3).await,
+        })
+    }
+
+    /// Mark a conflicted file as resolved
+    pub async fn mark_resolved(&self, path: impl AsRef<Path>) -> Result<()> {
+        self.stage(path).await
+    }
+
+    /// Write resolved content for a conflicted file and mark it resolved
+    ///
+    /// Refuses content that still contains conflict markers.
+    pub async fn write_resolution(&self, path: impl AsRef<Path>, content: &str) -> Result<()> {
+        let path = path.as_ref();
+        if has_conflict_markers(content) {
+            anyhow::bail!("{} still contains conflict markers", path.display());
+        }
+
+        tokio::fs::write(self.repo_path.join(path), content)
+            .await
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        self.mark_resolved(path).await
+    }
+
+    /// Content of a file at an index stage, `None` if the stage is absent
+    async fn show_stage(&self, path: &Path, stage: u8) -> Option<String> {
+        let spec = format!(":{}:{}", stage, path.to_string_lossy());
+        self.run_git(&["show", &spec], "git show").await.ok()
+    }
+
+    /// Run a git command and return its stdout
+    async fn run_git(&self, args: &[&str], label: &str) -> Result<String> {
+        let output = Command::new("git")
+            .current_dir(&self.repo_path)
+            .args(args)
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .output()
+            .await
+            .with_context(|| format!("Failed to run {}", label))?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            anyhow::bail!("{} failed: {}", label, stderr);
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    }
+}
+
+/// Parse `git log` output produced with [`LOG_FORMAT`]
+fn parse_log(stdout: &str) -> Vec<CommitInfo> {
+    let mut commits = Vec::new();
+
+    for line in stdout.lines() {
+        let parts: Vec<&str> = line.split('|').collect();
+        if parts.len() < 6 {
+            continue;
+        }
+
+        let parents = if parts.len() > 6 && !parts[6].is_empty() {
+            parts[6].split_whitespace().map(|s| s.to_string()).collect()
+        } else {
+            Vec::new()
+        };
+
+        commits.push(CommitInfo {
+            full_hash: parts[0].to_string(),
+            hash: parts[1].to_string(),
+            message: parts[2].to_string(),
+            author: parts[3].to_string(),
+            email: parts[4].to_string(),
+            timestamp: parts[5].parse().unwrap_or(0),
+            parents,
+        });
+    }
+
+    commits
 }
 
 #[cfg(test)]
@@ -567,4 +969,386 @@ mod tests {
         assert!(test_status.is_some());
         assert!(matches!(test_status.unwrap(), FileChange::Untracked(_)));
     }
+
+    async fn commit_file(path: &Path, name: &str, content: &str) {
+        fs::write(path.join(name), content).await.unwrap();
+        for args in [vec!["add", name], vec!["commit", "-q", "-m", name]] {
+            Command::new("git")
+                .current_dir(path)
+                .args(&args)
+                .output()
+                .await
+                .unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn test_git_service_status_partially_staged() {
+        let temp_dir = TempDir::new().unwrap();
+        init_git_repo(temp_dir.path()).await;
+        commit_file(temp_dir.path(), "a.txt", "one\n").await;
+
+        let service = GitService::new(temp_dir.path()).unwrap();
+        fs::write(temp_dir.path().join("a.txt"), "one\ntwo\n")
+            .await
+            .unwrap();
+        service.stage("a.txt").await.unwrap();
+        fs::write(temp_dir.path().join("a.txt"), "one\ntwo\nthree\n")
+            .await
+            .unwrap();
+
+        let status = service.status().await.unwrap();
+        assert!(status.iter().any(|c| matches!(c, FileChange::Staged(_))));
+        assert!(status.iter().any(|c| matches!(c, FileChange::Modified(_))));
+    }
+
+    #[tokio::test]
+    async fn test_git_service_snapshot_and_branches() {
+        let temp_dir = TempDir::new().unwrap();
+        init_git_repo(temp_dir.path()).await;
+        commit_file(temp_dir.path(), "a.txt", "one\n").await;
+
+        let service = GitService::new(temp_dir.path()).unwrap();
+        service.create_branch("feature", true).await.unwrap();
+
+        let snapshot = service.snapshot().await.unwrap();
+        assert_eq!(snapshot.branch, "feature");
+        assert_eq!(snapshot.branches.len(), 2);
+        assert!(snapshot.changes.is_empty());
+
+        let other = snapshot
+            .branches
+            .iter()
+            .find(|b| !b.is_current)
+            .unwrap()
+            .name
+            .clone();
+        service.checkout_branch(&other).await.unwrap();
+        assert_eq!(service.current_branch().await.unwrap(), other);
+    }
+
+    #[tokio::test]
+    async fn test_git_service_stage_and_unstage_hunk() {
+        let temp_dir = TempDir::new().unwrap();
+        init_git_repo(temp_dir.path()).await;
+        let lines: Vec<String> = (1..=20).map(|i| format!("line {}", i)).collect();
+        commit_file(temp_dir.path(), "a.txt", &(lines.join("\n") + "\n")).await;
+
+        // Two separate hunks: one near the top, one near the bottom
+        let mut changed = lines.clone();
+        changed[1] = "changed 2".to_string();
+        changed[18] = "changed 19".to_string();
+        fs::write(temp_dir.path().join("a.txt"), changed.join("\n") + "\n")
+            .await
+            .unwrap();
+
+        let service = GitService::new(temp_dir.path()).unwrap();
+        let diff = service.diff(Some(Path::new("a.txt")), false).await.unwrap();
+        let viewer = crate::ui::widgets::git::DiffViewer::new(&diff);
+        assert_eq!(viewer.hunk_count(), 2);
+
+        let hunk = viewer.selected_hunk().unwrap().clone();
+        service.stage_hunk("a.txt", &hunk).await.unwrap();
+
+        let staged = service.diff(Some(Path::new("a.txt")), true).await.unwrap();
+        assert!(staged.contains("+changed 2"));
+        assert!(!staged.contains("+changed 19"));
+
+        service.unstage_hunk("a.txt", &hunk).await.unwrap();
+        let staged = service.diff(Some(Path::new("a.txt")), true).await.unwrap();
+        assert!(staged.is_empty());
+    }
+
+    async fn git(path: &Path, args: &[&str]) -> String {
+        let output = Command::new("git")
+            .current_dir(path)
+            .args(args)
+            .output()
+            .await
+            .unwrap();
+        String::from_utf8_lossy(&output.stdout).to_string()
+    }
+
+    #[tokio::test]
+    async fn test_git_service_stage_lines() {
+        let temp_dir = TempDir::new().unwrap();
+        init_git_repo(temp_dir.path()).await;
+        commit_file(temp_dir.path(), "a.txt", "one\ntwo\nthree\n").await;
+        fs::write(temp_dir.path().join("a.txt"), "one\nTWO\nthree\nfour\n")
+            .await
+            .unwrap();
+
+        let service = GitService::new(temp_dir.path()).unwrap();
+        let diff = service.file_diff("a.txt", false).await.unwrap().unwrap();
+        let hunk = diff.hunks[0].clone();
+        // Lines: " one", "-two", "+TWO", " three", "+four"; stage only "+four"
+        service.stage_lines("a.txt", &hunk, 4..=4).await.unwrap();
+
+        assert_eq!(
+            git(temp_dir.path(), &["show", ":a.txt"]).await,
+            "one\ntwo\nthree\nfour\n"
+        );
+
+        // Unstage it again line by line
+        let staged = service.file_diff("a.txt", true).await.unwrap().unwrap();
+        let staged_hunk = staged.hunks[0].clone();
+        let added = staged_hunk
+            .lines
+            .iter()
+            .position(|l| l.content == "four")
+            .unwrap();
+        service
+            .unstage_lines("a.txt", &staged_hunk, added..=added)
+            .await
+            .unwrap();
+        assert!(service.file_diff("a.txt", true).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_git_service_stage_lines_of_untracked_file() {
+        let temp_dir = TempDir::new().unwrap();
+        init_git_repo(temp_dir.path()).await;
+        commit_file(temp_dir.path(), "a.txt", "one\n").await;
+        fs::write(temp_dir.path().join("new.txt"), "a\nb\nc\n")
+            .await
+            .unwrap();
+
+        let service = GitService::new(temp_dir.path()).unwrap();
+        let diff = service.file_diff("new.txt", false).await.unwrap().unwrap();
+        assert_eq!(diff.kind, crate::git::FileDiffKind::Added);
+
+        service
+            .stage_lines("new.txt", &diff.hunks[0], 0..=1)
+            .await
+            .unwrap();
+        assert_eq!(git(temp_dir.path(), &["show", ":new.txt"]).await, "a\nb\n");
+
+        // Unstaging part of a new file keeps it in the index
+        let staged = service.file_diff("new.txt", true).await.unwrap().unwrap();
+        service
+            .unstage_lines("new.txt", &staged.hunks[0], 1..=1)
+            .await
+            .unwrap();
+        assert_eq!(git(temp_dir.path(), &["show", ":new.txt"]).await, "a\n");
+
+        // Unstaging the rest removes it from the index entirely
+        let staged = service.file_diff("new.txt", true).await.unwrap().unwrap();
+        service
+            .unstage_hunk("new.txt", &staged.hunks[0])
+            .await
+            .unwrap();
+        assert!(
+            git(temp_dir.path(), &["ls-files", "new.txt"])
+                .await
+                .is_empty()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_git_service_stage_part_of_deletion() {
+        let temp_dir = TempDir::new().unwrap();
+        init_git_repo(temp_dir.path()).await;
+        commit_file(temp_dir.path(), "gone.txt", "a\nb\n").await;
+        fs::remove_file(temp_dir.path().join("gone.txt"))
+            .await
+            .unwrap();
+
+        let service = GitService::new(temp_dir.path()).unwrap();
+        let diff = service.file_diff("gone.txt", false).await.unwrap().unwrap();
+        assert_eq!(diff.kind, crate::git::FileDiffKind::Deleted);
+
+        service
+            .stage_lines("gone.txt", &diff.hunks[0], 0..=0)
+            .await
+            .unwrap();
+        assert_eq!(git(temp_dir.path(), &["show", ":gone.txt"]).await, "b\n");
+
+        let diff = service.file_diff("gone.txt", false).await.unwrap().unwrap();
+        service
+            .stage_hunk("gone.txt", &diff.hunks[0])
+            .await
+            .unwrap();
+        assert!(
+            git(temp_dir.path(), &["ls-files", "gone.txt"])
+                .await
+                .is_empty()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_git_service_unstage_lines_of_rename() {
+        let temp_dir = TempDir::new().unwrap();
+        init_git_repo(temp_dir.path()).await;
+        let content: String = (1..=10).map(|i| format!("line {}\n", i)).collect();
+        commit_file(temp_dir.path(), "old.txt", &content).await;
+
+        git(temp_dir.path(), &["mv", "old.txt", "new.txt"]).await;
+        let changed = content.replace("line 5\n", "line five\n");
+        fs::write(temp_dir.path().join("new.txt"), &changed)
+            .await
+            .unwrap();
+        git(temp_dir.path(), &["add", "new.txt"]).await;
+
+        let service = GitService::new(temp_dir.path()).unwrap();
+        let staged = service.file_diff("new.txt", true).await.unwrap().unwrap();
+        assert_eq!(staged.kind, crate::git::FileDiffKind::Renamed);
+        assert_eq!(staged.old_path, PathBuf::from("old.txt"));
+
+        // Drop the content change from the index but keep the rename
+        let hunk = staged.hunks[0].clone();
+        let added = hunk
+            .lines
+            .iter()
+            .position(|l| l.content == "line five")
+            .unwrap();
+        let removed = hunk
+            .lines
+            .iter()
+            .position(|l| l.content == "line 5")
+            .unwrap();
+        let range = removed.min(added)..=removed.max(added);
+        service
+            .unstage_lines("new.txt", &hunk, range)
+            .await
+            .unwrap();
+
+        assert_eq!(git(temp_dir.path(), &["show", ":new.txt"]).await, content);
+        assert!(
+            git(temp_dir.path(), &["ls-files", "old.txt"])
+                .await
+                .is_empty()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_git_service_binary_file_cannot_be_split() {
+        let temp_dir = TempDir::new().unwrap();
+        init_git_repo(temp_dir.path()).await;
+        fs::write(temp_dir.path().join("img.bin"), [0u8, 1, 2, 0, 3])
+            .await
+            .unwrap();
+        git(temp_dir.path(), &["add", "img.bin"]).await;
+        git(temp_dir.path(), &["commit", "-q", "-m", "bin"]).await;
+        fs::write(temp_dir.path().join("img.bin"), [0u8, 9, 9, 0, 3])
+            .await
+            .unwrap();
+
+        let service = GitService::new(temp_dir.path()).unwrap();
+        let diff = service.file_diff("img.bin", false).await.unwrap().unwrap();
+        assert!(diff.binary);
+        assert!(diff.hunk_patch(0, false).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_git_service_stage_hunk_without_trailing_newline() {
+        let temp_dir = TempDir::new().unwrap();
+        init_git_repo(temp_dir.path()).await;
+        commit_file(temp_dir.path(), "a.txt", "one\ntwo").await;
+        fs::write(temp_dir.path().join("a.txt"), "one\nthree")
+            .await
+            .unwrap();
+
+        let service = GitService::new(temp_dir.path()).unwrap();
+        let diff = service.file_diff("a.txt", false).await.unwrap().unwrap();
+        service.stage_hunk("a.txt", &diff.hunks[0]).await.unwrap();
+
+        assert_eq!(
+            git(temp_dir.path(), &["show", ":a.txt"]).await,
+            "one\nthree"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_git_service_stale_hunk_rejected() {
+        let temp_dir = TempDir::new().unwrap();
+        init_git_repo(temp_dir.path()).await;
+        commit_file(temp_dir.path(), "a.txt", "one\n").await;
+        fs::write(temp_dir.path().join("a.txt"), "two\n")
+            .await
+            .unwrap();
+
+        let service = GitService::new(temp_dir.path()).unwrap();
+        let diff = service.file_diff("a.txt", false).await.unwrap().unwrap();
+        fs::write(temp_dir.path().join("a.txt"), "three\n")
+            .await
+            .unwrap();
+
+        assert!(service.stage_hunk("a.txt", &diff.hunks[0]).await.is_err());
+    }
+
+    /// Create a repository with a conflicted `a.txt` after merging `feature`
+    async fn conflicted_repo() -> TempDir {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path();
+        init_git_repo(path).await;
+        commit_file(path, "a.txt", "one\ntwo\nthree\n").await;
+        git(path, &["checkout", "-q", "-b", "feature"]).await;
+        commit_file(path, "a.txt", "one\ntheirs\nthree\n").await;
+        git(path, &["checkout", "-q", "-"]).await;
+        commit_file(path, "a.txt", "one\nours\nthree\n").await;
+        git(path, &["merge", "-q", "feature"]).await;
+        temp_dir
+    }
+
+    #[tokio::test]
+    async fn test_git_service_merge_versions() {
+        let temp_dir = conflicted_repo().await;
+        let service = GitService::new(temp_dir.path()).unwrap();
+
+        let versions = service.merge_versions("a.txt").await.unwrap();
+        assert_eq!(versions.base.as_deref(), Some("one\ntwo\nthree\n"));
+        assert_eq!(versions.ours.as_deref(), Some("one\nours\nthree\n"));
+        assert_eq!(versions.theirs.as_deref(), Some("one\ntheirs\nthree\n"));
+        assert_eq!(versions.merge().unwrap().conflict_count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_git_service_write_resolution() {
+        let temp_dir = conflicted_repo().await;
+        let service = GitService::new(temp_dir.path()).unwrap();
+        assert_eq!(
+            service.snapshot().await.unwrap().conflicted_paths().len(),
+            1
+        );
+
+        let markers = "<<<<<<< HEAD\nours\n=======\ntheirs\n>>>>>>> feature\n";
+        assert!(service.write_resolution("a.txt", markers).await.is_err());
+
+        service
+            .write_resolution("a.txt", "one\nboth\nthree\n")
+            .await
+            .unwrap();
+
+        let snapshot = service.snapshot().await.unwrap();
+        assert!(snapshot.conflicted_paths().is_empty());
+        assert_eq!(
+            git(temp_dir.path(), &["show", ":a.txt"]).await,
+            "one\nboth\nthree\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_git_service_branch_range() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path();
+        init_git_repo(path).await;
+        commit_file(path, "a.txt", "one\n").await;
+        git(path, &["branch", "base"]).await;
+        git(path, &["checkout", "-q", "-b", "feature"]).await;
+        commit_file(path, "a.txt", "one\ntwo\n").await;
+        commit_file(path, "b.txt", "b\n").await;
+
+        let service = GitService::new(path).unwrap();
+        assert_eq!(service.upstream_branch().await, None);
+        git(path, &["branch", "--set-upstream-to=base"]).await;
+        assert_eq!(service.upstream_branch().await.as_deref(), Some("base"));
+
+        let commits = service.log_range("base").await.unwrap();
+        assert_eq!(commits.len(), 2);
+        assert_eq!(commits[0].message, "b.txt");
+
+        let diff = service.range_diff("base").await.unwrap();
+        assert!(diff.contains("+two"));
+        assert!(diff.contains("b/b.txt"));
+    }
 }
@@ -2,10 +2,64 @@
 //!
 //! Smart task prioritization, auto-categorization, effort estimation,
 //! bottleneck detection, and burndown forecasting powered by AI/ML.
-
+//!
+//! Suggestions are computed from the [`BoardDatabase`]: priorities from due
+//! dates, dependencies and wording, effort from time tracked on similar
+//! cards, bottlenecks from column dwell times in the activity log and
+//! burndown forecasts by Monte Carlo simulation over past throughput. An
+//! [`LLMClient`] can suggest priorities and categories instead.
+
+use crate::ai::llm::{LLMClient, Message};
+use crate::infrastructure::board_store::BoardDatabase;
+use crate::infrastructure::card_comments::ActivityLogEntry;
+use crate::infrastructure::rich_task_cards::RichTaskCard;
+use crate::infrastructure::visual_kanban_board::KanbanColumn;
 use chrono::{DateTime, Utc};
+use rand::Rng;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+
+/// Similar cards an effort estimate is based on
+const MAX_SIMILAR_TASKS: usize = 5;
+
+/// Minimum similarity (0.0 to 1.0) for a card to count as similar
+const MIN_SIMILARITY: f32 = 0.2;
+
+/// Dwell time, relative to the median column, that makes a column a bottleneck
+const BOTTLENECK_DWELL_RATIO: f32 = 1.5;
+
+/// Simulated futures per burndown forecast
+pub const MONTE_CARLO_RUNS: usize = 2000;
+
+/// Days after which a simulated future is given up
+const MAX_FORECAST_DAYS: i64 = 365;
+
+/// Words that mark urgent work
+const URGENT_WORDS: &[&str] = &[
+    "urgent",
+    "critical",
+    "outage",
+    "security",
+    "vulnerability",
+    "crash",
+    "hotfix",
+    "production",
+    "asap",
+];
+
+/// Words that mark defects
+const DEFECT_WORDS: &[&str] = &["bug", "broken", "regression", "error", "fails", "failing"];
+
+/// Words that mark work that can wait
+const MINOR_WORDS: &[&str] = &[
+    "docs",
+    "documentation",
+    "typo",
+    "cleanup",
+    "refactor",
+    "chore",
+    "cosmetic",
+];
 
 /// Task priority suggestion
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -40,6 +94,13 @@ impl SuggestedPriority {
             SuggestedPriority::Low => 1,
         }
     }
+
+    /// Parse a display name (case-insensitive)
+    pub fn from_name(name: &str) -> Option<Self> {
+        [Self::Critical, Self::High, Self::Medium, Self::Low]
+            .into_iter()
+            .find(|priority| priority.name().eq_ignore_ascii_case(name.trim()))
+    }
 }
 
 /// Priority suggestion with confidence
@@ -314,8 +375,364 @@ impl AITaskIntelligence {
         }
     }
 
-    /// Suggest priority for a task
+    /// Suggest a priority for a card from its due date, dependents and wording
+    ///
+    /// Each signal (due soon or overdue, cards it blocks, urgent, defect or
+    /// minor-work words in its title, description and tags) moves an urgency
+    /// score and adds a reason; more signals give more confidence. Returns
+    /// `None` if the card does not exist.
     pub fn suggest_priority(
+        &mut self,
+        db: &BoardDatabase,
+        card_id: &str,
+        now: DateTime<Utc>,
+    ) -> Option<&PrioritySuggestion> {
+        let card = db.cards.get_card(card_id)?;
+        let mut score = 0;
+        let mut reasoning = Vec::new();
+
+        if let Some(due) = card.due_date {
+            let hours_left = due.signed_duration_since(now).num_hours();
+            if hours_left < 0 {
+                score += 3;
+                reasoning.push(format!("Overdue since {}", due.format("%Y-%m-%d")));
+            } else if hours_left <= 48 {
+                score += 2;
+                reasoning.push("Due within 2 days".to_string());
+            } else if hours_left <= 7 * 24 {
+                score += 1;
+                reasoning.push("Due within a week".to_string());
+            }
+        }
+
+        let blocked = db.dependencies.get_blocked(card_id).len();
+        if blocked > 0 {
+            score += blocked.min(2) as i32;
+            reasoning.push(format!(
+                "Blocks {} other card{}",
+                blocked,
+                if blocked == 1 { "" } else { "s" }
+            ));
+        }
+
+        let mut words = tokens(&card.title);
+        words.extend(tokens(&card.description));
+        words.extend(tag_set(card));
+        if let Some(word) = find_word(&words, URGENT_WORDS) {
+            score += 2;
+            reasoning.push(format!("Mentions \"{}\"", word));
+        }
+        if let Some(word) = find_word(&words, DEFECT_WORDS) {
+            score += 1;
+            reasoning.push(format!("Looks like a defect (\"{}\")", word));
+        }
+        if let Some(word) = find_word(&words, MINOR_WORDS) {
+            score -= 1;
+            reasoning.push(format!("Looks like minor work (\"{}\")", word));
+        }
+
+        let priority = match score {
+            4.. => SuggestedPriority::Critical,
+            2..=3 => SuggestedPriority::High,
+            0..=1 => SuggestedPriority::Medium,
+            _ => SuggestedPriority::Low,
+        };
+        let confidence = if reasoning.is_empty() {
+            reasoning.push("No urgency signals".to_string());
+            0.3
+        } else {
+            (0.4 + 0.15 * reasoning.len() as f32).min(0.95)
+        };
+
+        self.record_priority(card_id.to_string(), priority, confidence, reasoning);
+        self.priority_suggestions.get(card_id)
+    }
+
+    /// Estimate a card's effort from the time tracked on similar cards
+    ///
+    /// Similarity combines title words (70%) and tag names (30%), each as
+    /// Jaccard overlap. The estimate is the similarity-weighted mean of the
+    /// hours tracked on the closest cards, with an interval of one weighted
+    /// standard deviation (at least 20%). Returns `None` if the card does not
+    /// exist or no similar card has tracked time.
+    pub fn estimate_effort(
+        &mut self,
+        db: &BoardDatabase,
+        card_id: &str,
+    ) -> Option<&EffortEstimation> {
+        let card = db.cards.get_card(card_id)?;
+        let title = tokens(&card.title);
+        let tags = tag_set(card);
+
+        let mut similar: Vec<(f32, f32, &str)> = db
+            .cards
+            .cards()
+            .into_iter()
+            .filter(|other| other.id != card.id)
+            .filter_map(|other| {
+                let hours = db.time.total_time_for_task(&other.id).num_seconds() as f32 / 3600.0;
+                let similarity = 0.7 * jaccard(&title, &tokens(&other.title))
+                    + 0.3 * jaccard(&tags, &tag_set(other));
+                (hours > 0.0 && similarity >= MIN_SIMILARITY).then_some((
+                    similarity,
+                    hours,
+                    other.id.as_str(),
+                ))
+            })
+            .collect();
+        if similar.is_empty() {
+            return None;
+        }
+        similar.sort_by(|a, b| b.0.total_cmp(&a.0).then_with(|| a.2.cmp(b.2)));
+        similar.truncate(MAX_SIMILAR_TASKS);
+
+        let weight: f32 = similar.iter().map(|(w, _, _)| w).sum();
+        let mean = similar.iter().map(|(w, h, _)| w * h).sum::<f32>() / weight;
+        let variance = similar
+            .iter()
+            .map(|(w, h, _)| w * (h - mean).powi(2))
+            .sum::<f32>()
+            / weight;
+        let spread = variance.sqrt().max(mean * 0.2);
+        let confidence = (weight / similar.len() as f32) * (similar.len().min(3) as f32 / 3.0);
+
+        let mut estimation = EffortEstimation::new(card_id.to_string(), mean, confidence)
+            .with_interval((mean - spread).max(0.0), mean + spread);
+        for (_, _, task_id) in &similar {
+            estimation.add_similar_task(task_id.to_string());
+        }
+        self.effort_estimations
+            .insert(card_id.to_string(), estimation);
+        self.effort_estimations.get(card_id)
+    }
+
+    /// Detect bottlenecks on a board from how long cards stay in each column
+    ///
+    /// Dwell times come from the cards' `card_moved` activity (up to `now`
+    /// for the column a card is in). A column is a bottleneck if cards stay
+    /// there 1.5 times longer than the median column, or if it is over its
+    /// WIP limit. The last column is where work ends and is never one.
+    /// Replaces earlier results for the board's columns; returns the
+    /// bottlenecks, most severe first.
+    pub fn detect_bottlenecks(
+        &mut self,
+        db: &BoardDatabase,
+        board_id: &str,
+        now: DateTime<Utc>,
+    ) -> Vec<&Bottleneck> {
+        let columns = db.boards.columns_for_board(board_id);
+        let Some((_, active)) = columns.split_last() else {
+            return Vec::new();
+        };
+        let dwell = column_dwell_hours(db, &columns, now);
+        let averages: HashMap<&str, f32> = active
+            .iter()
+            .filter_map(|column| {
+                let hours = dwell.get(&column.id)?;
+                Some((
+                    column.id.as_str(),
+                    hours.iter().sum::<f32>() / hours.len() as f32,
+                ))
+            })
+            .collect();
+        let typical = median(averages.values().copied().collect());
+
+        for column in active {
+            self.bottlenecks.remove(&column.id);
+            let avg_hours = averages.get(column.id.as_str()).copied().unwrap_or(0.0);
+            let ratio = if typical > 0.0 {
+                avg_hours / typical
+            } else {
+                0.0
+            };
+            let slow = ratio >= BOTTLENECK_DWELL_RATIO;
+            let over_wip = column.is_over_wip_limit();
+            if !slow && !over_wip {
+                continue;
+            }
+
+            let mut bottleneck = Bottleneck::new(column.id.clone(), column.card_count(), avg_hours);
+            bottleneck.wip_limit = column.wip_limit;
+            let mut severity = 0.0_f32;
+            if slow {
+                severity = ((ratio - 1.0) / 3.0).clamp(0.1, 1.0);
+                bottleneck.add_suggestion(format!(
+                    "Cards spend {:.1}h in {} on average, {:.1}x the typical column",
+                    avg_hours, column.name, ratio
+                ));
+            }
+            match column.wip_limit {
+                Some(limit) if over_wip => {
+                    let overload = column.card_count() as f32 / limit.max(1) as f32 - 1.0;
+                    severity = severity.max((0.5 + overload).min(1.0));
+                    bottleneck.add_suggestion(format!(
+                        "{} holds {} cards over its WIP limit of {}: finish work before starting more",
+                        column.name,
+                        column.card_count(),
+                        limit
+                    ));
+                }
+                Some(_) => {}
+                None => bottleneck.add_suggestion(format!(
+                    "Set a WIP limit on {} to stop work piling up",
+                    column.name
+                )),
+            }
+            bottleneck.severity = severity;
+            self.bottlenecks.insert(column.id.clone(), bottleneck);
+        }
+
+        let mut found: Vec<&Bottleneck> = active
+            .iter()
+            .filter_map(|column| self.bottlenecks.get(&column.id))
+            .collect();
+        found.sort_by(|a, b| b.severity.total_cmp(&a.severity));
+        found
+    }
+
+    /// Cards finished per day on a board over the last `days` days
+    ///
+    /// A card counts as finished on the day it moved into the board's last
+    /// column. The result is oldest day first, ending with today.
+    pub fn daily_throughput(
+        db: &BoardDatabase,
+        board_id: &str,
+        days: usize,
+        now: DateTime<Utc>,
+    ) -> Vec<f32> {
+        let mut throughput = vec![0.0; days];
+        let columns = db.boards.columns_for_board(board_id);
+        let Some(done) = columns.last() else {
+            return throughput;
+        };
+        for card_id in columns.iter().flat_map(|column| &column.card_ids) {
+            for activity in moves_of(db, card_id) {
+                if activity_column(activity, &columns) != Some(done.id.as_str()) {
+                    continue;
+                }
+                let days_ago = now.signed_duration_since(activity.created_at).num_days();
+                if (0..days as i64).contains(&days_ago) {
+                    throughput[days - 1 - days_ago as usize] += 1.0;
+                }
+            }
+        }
+        throughput
+    }
+
+    /// Forecast when remaining work is done by Monte Carlo over past throughput
+    ///
+    /// Simulates [`MONTE_CARLO_RUNS`] futures, each drawing every day's
+    /// throughput from `daily_throughput` (e.g. [`Self::daily_throughput`]).
+    /// The forecasted date is the median outcome and the confidence is the
+    /// share of runs finishing by `target_date`. Without any throughput the
+    /// forecast falls back to [`BurndownForecast::new`].
+    pub fn forecast_burndown(
+        &mut self,
+        sprint_id: String,
+        target_date: DateTime<Utc>,
+        remaining_work: f32,
+        daily_throughput: &[f32],
+        now: DateTime<Utc>,
+    ) -> &BurndownForecast {
+        self.forecast_burndown_with_rng(
+            sprint_id,
+            target_date,
+            remaining_work,
+            daily_throughput,
+            now,
+            &mut rand::rng(),
+        )
+    }
+
+    /// [`Self::forecast_burndown`] with a caller-provided random number generator
+    pub fn forecast_burndown_with_rng<R: Rng + ?Sized>(
+        &mut self,
+        sprint_id: String,
+        target_date: DateTime<Utc>,
+        remaining_work: f32,
+        daily_throughput: &[f32],
+        now: DateTime<Utc>,
+        rng: &mut R,
+    ) -> &BurndownForecast {
+        let velocity = if daily_throughput.is_empty() {
+            0.0
+        } else {
+            daily_throughput.iter().sum::<f32>() / daily_throughput.len() as f32
+        };
+        let mut forecast =
+            BurndownForecast::new(sprint_id.clone(), target_date, remaining_work, velocity);
+
+        if velocity > 0.0 {
+            let mut outcomes: Vec<i64> = (0..MONTE_CARLO_RUNS)
+                .map(|_| {
+                    let mut left = remaining_work;
+                    let mut days = 0;
+                    while left > 0.0 && days < MAX_FORECAST_DAYS {
+                        left -= daily_throughput[rng.random_range(0..daily_throughput.len())];
+                        days += 1;
+                    }
+                    days
+                })
+                .collect();
+            outcomes.sort_unstable();
+
+            let days_available =
+                target_date.signed_duration_since(now).num_seconds() as f64 / (24.0 * 3600.0);
+            let in_time = outcomes
+                .iter()
+                .filter(|&&days| days as f64 <= days_available)
+                .count();
+            forecast.forecasted_date = now + chrono::Duration::days(outcomes[outcomes.len() / 2]);
+            forecast.confidence = in_time as f32 / outcomes.len() as f32;
+            forecast.days_delta = target_date
+                .signed_duration_since(forecast.forecasted_date)
+                .num_days() as i32;
+            forecast.on_track = forecast.forecasted_date <= target_date;
+        }
+
+        self.forecasts.insert(sprint_id.clone(), forecast);
+        &self.forecasts[&sprint_id]
+    }
+
+    /// Ask an LLM for a card's priority and categories, with reasons
+    ///
+    /// The model replies with a JSON object (`priority`, `confidence`,
+    /// `reasons`, `tags`, `project`, `epic`); both suggestions are recorded
+    /// and returned.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails or the reply is not a valid
+    /// suggestion.
+    pub async fn suggest_with_llm(
+        &mut self,
+        card: &RichTaskCard,
+        client: &dyn LLMClient,
+    ) -> anyhow::Result<(PrioritySuggestion, CategorySuggestion)> {
+        let response = client
+            .send_message(vec![Message::user(llm_prompt(card))], None)
+            .await?;
+        let reply = parse_llm_reply(&response.content)?;
+        let priority = SuggestedPriority::from_name(&reply.priority)
+            .ok_or_else(|| anyhow::anyhow!("Unknown priority \"{}\"", reply.priority))?;
+        let confidence = reply.confidence.clamp(0.0, 1.0);
+
+        self.record_priority(card.id.clone(), priority, confidence, reply.reasons);
+        self.record_categories(
+            card.id.clone(),
+            reply.tags,
+            reply.project,
+            reply.epic,
+            confidence,
+        );
+        Ok((
+            self.priority_suggestions[&card.id].clone(),
+            self.category_suggestions[&card.id].clone(),
+        ))
+    }
+
+    /// Record a priority suggestion made elsewhere (e.g. by a reviewer)
+    pub fn record_priority(
         &mut self,
         task_id: String,
         priority: SuggestedPriority,
@@ -350,8 +767,8 @@ impl AITaskIntelligence {
             .collect()
     }
 
-    /// Suggest categories for a task
-    pub fn suggest_categories(
+    /// Record a category suggestion made elsewhere
+    pub fn record_categories(
         &mut self,
         task_id: String,
         tags: Vec<String>,
@@ -376,8 +793,8 @@ impl AITaskIntelligence {
         self.category_suggestions.get(task_id)
     }
 
-    /// Estimate effort for a task
-    pub fn estimate_effort(
+    /// Record an effort estimation made elsewhere
+    pub fn record_effort(
         &mut self,
         task_id: String,
         hours: f32,
@@ -403,8 +820,8 @@ impl AITaskIntelligence {
         self.effort_estimations.values().collect()
     }
 
-    /// Detect a bottleneck
-    pub fn detect_bottleneck(
+    /// Record a bottleneck detected elsewhere
+    pub fn record_bottleneck(
         &mut self,
         column_id: String,
         task_count: usize,
@@ -440,8 +857,8 @@ impl AITaskIntelligence {
             .collect()
     }
 
-    /// Create a burndown forecast
-    pub fn forecast_burndown(
+    /// Record a burndown forecast from a known velocity
+    pub fn record_forecast(
         &mut self,
         sprint_id: String,
         target_date: DateTime<Utc>,
@@ -511,9 +928,171 @@ impl Default for AITaskIntelligence {
     }
 }
 
+/// Lowercase words of at least three characters
+fn tokens(text: &str) -> HashSet<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| word.chars().count() >= 3)
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// Lowercase tag names of a card
+fn tag_set(card: &RichTaskCard) -> HashSet<String> {
+    card.tags
+        .iter()
+        .map(|tag| tag.name.to_lowercase())
+        .collect()
+}
+
+/// Jaccard overlap of two word sets (0.0 if both are empty)
+fn jaccard(a: &HashSet<String>, b: &HashSet<String>) -> f32 {
+    let union = a.union(b).count();
+    if union == 0 {
+        return 0.0;
+    }
+    a.intersection(b).count() as f32 / union as f32
+}
+
+/// First of `candidates` contained in `words`
+fn find_word<'a>(words: &HashSet<String>, candidates: &[&'a str]) -> Option<&'a str> {
+    candidates
+        .iter()
+        .find(|word| words.contains(**word))
+        .copied()
+}
+
+/// Median of values (0.0 if there are none)
+fn median(mut values: Vec<f32>) -> f32 {
+    if values.is_empty() {
+        return 0.0;
+    }
+    values.sort_by(f32::total_cmp);
+    let mid = values.len() / 2;
+    if values.len().is_multiple_of(2) {
+        (values[mid - 1] + values[mid]) / 2.0
+    } else {
+        values[mid]
+    }
+}
+
+/// Column moves of a card, oldest first
+pub(crate) fn moves_of<'a>(db: &'a BoardDatabase, card_id: &str) -> Vec<&'a ActivityLogEntry> {
+    let mut moves: Vec<_> = db
+        .comments
+        .activities_for_card(card_id)
+        .into_iter()
+        .filter(|activity| activity.activity_type == "card_moved")
+        .collect();
+    moves.sort_by_key(|activity| activity.created_at);
+    moves
+}
+
+/// Column a move went to: its `column_id` metadata, or the column named in
+/// the description for moves logged without it
+pub(crate) fn activity_column<'a>(
+    activity: &ActivityLogEntry,
+    columns: &[&'a KanbanColumn],
+) -> Option<&'a str> {
+    let column = match activity.metadata.get("column_id") {
+        Some(id) => columns.iter().find(|column| column.id == *id),
+        None => {
+            let name = activity.description.strip_prefix("Moved to ")?;
+            columns.iter().find(|column| column.name == name)
+        }
+    };
+    column.map(|column| column.id.as_str())
+}
+
+/// Hours each stay of a card in a column lasted, by column ID
+///
+/// Covers every card on the given columns; the current stay lasts until `now`.
+fn column_dwell_hours(
+    db: &BoardDatabase,
+    columns: &[&KanbanColumn],
+    now: DateTime<Utc>,
+) -> HashMap<String, Vec<f32>> {
+    let mut dwell: HashMap<String, Vec<f32>> = HashMap::new();
+    for card_id in columns.iter().flat_map(|column| &column.card_ids) {
+        let moves = moves_of(db, card_id);
+        for (i, activity) in moves.iter().enumerate() {
+            let Some(column_id) = activity_column(activity, columns) else {
+                continue;
+            };
+            let left_at = moves.get(i + 1).map_or(now, |next| next.created_at);
+            let hours = left_at
+                .signed_duration_since(activity.created_at)
+                .num_seconds() as f32
+                / 3600.0;
+            dwell
+                .entry(column_id.to_string())
+                .or_default()
+                .push(hours.max(0.0));
+        }
+    }
+    dwell
+}
+
+/// Reply expected from the LLM by [`AITaskIntelligence::suggest_with_llm`]
+#[derive(Debug, Deserialize)]
+struct LlmSuggestion {
+    priority: String,
+    #[serde(default = "default_llm_confidence")]
+    confidence: f32,
+    #[serde(default)]
+    reasons: Vec<String>,
+    #[serde(default)]
+    tags: Vec<String>,
+    #[serde(default)]
+    project: Option<String>,
+    #[serde(default)]
+    epic: Option<String>,
+}
+
+fn default_llm_confidence() -> f32 {
+    0.5
+}
+
+/// Instructions for a priority and category request
+fn llm_prompt(card: &RichTaskCard) -> String {
+    let tags: Vec<&str> = card.tags.iter().map(|tag| tag.name.as_str()).collect();
+    let due = card.due_date.map_or_else(
+        || "none".to_string(),
+        |due| due.format("%Y-%m-%d").to_string(),
+    );
+    format!(
+        "Suggest a priority and categories for this task card.\n\n\
+         Title: {}\nDescription: {}\nTags: {}\nDue: {}\n\n\
+         Reply with a JSON object only: {{\"priority\": one of \"Critical\", \"High\", \
+         \"Medium\", \"Low\"; \"confidence\": 0.0 to 1.0; \"reasons\": short strings \
+         explaining the priority; \"tags\": suggested tags; \"project\" and \"epic\": \
+         names or null}}.",
+        card.title,
+        card.description,
+        tags.join(", "),
+        due
+    )
+}
+
+/// Parse the JSON object in an LLM reply (ignoring text or fences around it)
+fn parse_llm_reply(reply: &str) -> anyhow::Result<LlmSuggestion> {
+    let start = reply.find('{');
+    let end = reply.rfind('}');
+    let json = match (start, end) {
+        (Some(start), Some(end)) if start < end => &reply[start..=end],
+        _ => anyhow::bail!("No JSON object in reply"),
+    };
+    Ok(serde_json::from_str(json)?)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::ai::llm::mock::MockResponseBuilder;
+    use crate::infrastructure::rich_task_cards::Priority;
+    use crate::infrastructure::task_dependencies::DependencyType;
+    use crate::infrastructure::time_tracking::TimeEntry;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
 
     #[test]
     fn test_suggested_priority_name() {
@@ -647,10 +1226,10 @@ mod tests {
     }
 
     #[test]
-    fn test_ai_suggest_priority() {
+    fn test_ai_record_priority() {
         let mut ai = AITaskIntelligence::new();
 
-        ai.suggest_priority(
+        ai.record_priority(
             "task-1".to_string(),
             SuggestedPriority::High,
             0.85,
@@ -666,19 +1245,19 @@ mod tests {
     fn test_ai_high_confidence_priorities() {
         let mut ai = AITaskIntelligence::new();
 
-        ai.suggest_priority("task-1".to_string(), SuggestedPriority::High, 0.9, vec![]);
-        ai.suggest_priority("task-2".to_string(), SuggestedPriority::Medium, 0.5, vec![]);
-        ai.suggest_priority("task-3".to_string(), SuggestedPriority::Low, 0.8, vec![]);
+        ai.record_priority("task-1".to_string(), SuggestedPriority::High, 0.9, vec![]);
+        ai.record_priority("task-2".to_string(), SuggestedPriority::Medium, 0.5, vec![]);
+        ai.record_priority("task-3".to_string(), SuggestedPriority::Low, 0.8, vec![]);
 
         let high_conf = ai.get_high_confidence_priorities(0.75);
         assert_eq!(high_conf.len(), 2);
     }
 
     #[test]
-    fn test_ai_suggest_categories() {
+    fn test_ai_record_categories() {
         let mut ai = AITaskIntelligence::new();
 
-        ai.suggest_categories(
+        ai.record_categories(
             "task-1".to_string(),
             vec!["backend".to_string(), "api".to_string()],
             Some("project-1".to_string()),
@@ -692,10 +1271,10 @@ mod tests {
     }
 
     #[test]
-    fn test_ai_estimate_effort() {
+    fn test_ai_record_effort() {
         let mut ai = AITaskIntelligence::new();
 
-        ai.estimate_effort(
+        ai.record_effort(
             "task-1".to_string(),
             8.0,
             0.7,
@@ -708,10 +1287,10 @@ mod tests {
     }
 
     #[test]
-    fn test_ai_detect_bottleneck() {
+    fn test_ai_record_bottleneck() {
         let mut ai = AITaskIntelligence::new();
 
-        ai.detect_bottleneck(
+        ai.record_bottleneck(
             "in-progress".to_string(),
             15,
             72.0,
@@ -729,20 +1308,20 @@ mod tests {
     fn test_ai_severe_bottlenecks() {
         let mut ai = AITaskIntelligence::new();
 
-        ai.detect_bottleneck("col-1".to_string(), 12, 48.0, None, vec![]);
-        ai.detect_bottleneck("col-2".to_string(), 3, 12.0, None, vec![]);
-        ai.detect_bottleneck("col-3".to_string(), 8, 36.0, None, vec![]);
+        ai.record_bottleneck("col-1".to_string(), 12, 48.0, None, vec![]);
+        ai.record_bottleneck("col-2".to_string(), 3, 12.0, None, vec![]);
+        ai.record_bottleneck("col-3".to_string(), 8, 36.0, None, vec![]);
 
         let severe = ai.get_severe_bottlenecks(0.5);
         assert_eq!(severe.len(), 2); // col-1 and col-3
     }
 
     #[test]
-    fn test_ai_forecast_burndown() {
+    fn test_ai_record_forecast() {
         let mut ai = AITaskIntelligence::new();
         let target = Utc::now() + chrono::Duration::days(10);
 
-        ai.forecast_burndown("sprint-1".to_string(), target, 20.0, 3.0);
+        ai.record_forecast("sprint-1".to_string(), target, 20.0, 3.0);
 
         let forecast = ai.get_forecast("sprint-1").unwrap();
         assert_eq!(forecast.remaining_work, 20.0);
@@ -757,8 +1336,8 @@ mod tests {
         let target1 = Utc::now() + chrono::Duration::days(20);
         let target2 = Utc::now() + chrono::Duration::days(5);
 
-        ai.forecast_burndown("sprint-1".to_string(), target1, 20.0, 2.0);
-        ai.forecast_burndown("sprint-2".to_string(), target2, 30.0, 1.0);
+        ai.record_forecast("sprint-1".to_string(), target1, 20.0, 2.0);
+        ai.record_forecast("sprint-2".to_string(), target2, 30.0, 1.0);
 
         let at_risk = ai.get_at_risk_sprints();
         assert_eq!(at_risk.len(), 1); // Only sprint-2 is at risk
@@ -768,17 +1347,17 @@ mod tests {
     fn test_ai_counts() {
         let mut ai = AITaskIntelligence::new();
 
-        ai.suggest_priority("task-1".to_string(), SuggestedPriority::High, 0.8, vec![]);
-        ai.suggest_categories("task-2".to_string(), vec![], None, None, 0.7);
-        ai.estimate_effort("task-3".to_string(), 4.0, 0.6, vec![]);
+        ai.record_priority("task-1".to_string(), SuggestedPriority::High, 0.8, vec![]);
+        ai.record_categories("task-2".to_string(), vec![], None, None, 0.7);
+        ai.record_effort("task-3".to_string(), 4.0, 0.6, vec![]);
 
         assert_eq!(ai.suggestion_count(), 3);
 
-        ai.detect_bottleneck("col-1".to_string(), 10, 24.0, None, vec![]);
+        ai.record_bottleneck("col-1".to_string(), 10, 24.0, None, vec![]);
         assert_eq!(ai.bottleneck_count(), 1);
 
         let target = Utc::now() + chrono::Duration::days(10);
-        ai.forecast_burndown("sprint-1".to_string(), target, 15.0, 2.0);
+        ai.record_forecast("sprint-1".to_string(), target, 15.0, 2.0);
         assert_eq!(ai.forecast_count(), 1);
     }
 
@@ -786,8 +1365,8 @@ mod tests {
     fn test_ai_clear_old_suggestions() {
         let mut ai = AITaskIntelligence::new();
 
-        ai.suggest_priority("task-1".to_string(), SuggestedPriority::High, 0.8, vec![]);
-        ai.suggest_categories("task-2".to_string(), vec![], None, None, 0.7);
+        ai.record_priority("task-1".to_string(), SuggestedPriority::High, 0.8, vec![]);
+        ai.record_categories("task-2".to_string(), vec![], None, None, 0.7);
 
         // Clear suggestions older than 0 days (should clear all since they were just created)
         // But since they were created "now", they won't be cleared with days=0
@@ -797,4 +1376,296 @@ mod tests {
         ai.clear_old_suggestions(1);
         assert_eq!(ai.suggestion_count(), 2);
     }
+
+    fn db_with_card(title: &str, description: &str) -> (BoardDatabase, String) {
+        let mut db = BoardDatabase::default();
+        let card_id = db.cards.create_card(
+            title.to_string(),
+            description.to_string(),
+            "To Do".to_string(),
+            Priority::Medium,
+            "me".to_string(),
+        );
+        (db, card_id)
+    }
+
+    fn tracked_card(db: &mut BoardDatabase, title: &str, tags: &[&str], hours: i64) -> String {
+        let card_id = db.cards.create_card(
+            title.to_string(),
+            String::new(),
+            "Done".to_string(),
+            Priority::Medium,
+            "me".to_string(),
+        );
+        for name in tags {
+            let tag = db.cards.create_tag(name.to_string(), "#fff".to_string());
+            db.cards.get_card_mut(&card_id).unwrap().add_tag(tag);
+        }
+        db.time.add_entry(TimeEntry::manual(
+            &card_id,
+            Utc::now() - chrono::Duration::days(1),
+            chrono::Duration::hours(hours),
+        ));
+        card_id
+    }
+
+    /// Move a card and backdate the logged move
+    fn move_at(db: &mut BoardDatabase, card_id: &str, column: &str, at: DateTime<Utc>) {
+        db.move_card_to_named(card_id, column).unwrap();
+        let activity_id = db
+            .comments
+            .activities_for_card(card_id)
+            .last()
+            .unwrap()
+            .id
+            .clone();
+        db.comments
+            .get_activity_mut(&activity_id)
+            .unwrap()
+            .created_at = at;
+    }
+
+    #[test]
+    fn test_suggested_priority_from_name() {
+        assert_eq!(
+            SuggestedPriority::from_name(" high "),
+            Some(SuggestedPriority::High)
+        );
+        assert_eq!(SuggestedPriority::from_name("P0"), None);
+    }
+
+    #[test]
+    fn test_suggest_priority_from_due_date_and_wording() {
+        let now = Utc::now();
+        let (mut db, card_id) = db_with_card("Login crash", "Users see an error page");
+        db.cards.get_card_mut(&card_id).unwrap().due_date = Some(now - chrono::Duration::days(2));
+        let minor = db.cards.create_card(
+            "Fix typo in docs".to_string(),
+            String::new(),
+            "To Do".to_string(),
+            Priority::Medium,
+            "me".to_string(),
+        );
+
+        let mut ai = AITaskIntelligence::new();
+        let suggestion = ai.suggest_priority(&db, &card_id, now).unwrap();
+        assert_eq!(suggestion.priority, SuggestedPriority::Critical);
+        assert_eq!(suggestion.reasoning.len(), 3);
+        assert!(suggestion.reasoning[0].starts_with("Overdue since"));
+        assert!(suggestion.confidence > 0.8);
+
+        let suggestion = ai.suggest_priority(&db, &minor, now).unwrap();
+        assert_eq!(suggestion.priority, SuggestedPriority::Low);
+        assert!(ai.suggest_priority(&db, "missing", now).is_none());
+    }
+
+    #[test]
+    fn test_suggest_priority_counts_blocked_cards() {
+        let (mut db, card_id) = db_with_card("Set up schema", "");
+        let other = db.cards.create_card(
+            "Write queries".to_string(),
+            String::new(),
+            "To Do".to_string(),
+            Priority::Medium,
+            "me".to_string(),
+        );
+        db.dependencies
+            .create_dependency(
+                card_id.clone(),
+                other,
+                DependencyType::Blocks,
+                "me".to_string(),
+            )
+            .unwrap();
+
+        let mut ai = AITaskIntelligence::new();
+        let suggestion = ai.suggest_priority(&db, &card_id, Utc::now()).unwrap();
+        assert_eq!(suggestion.priority, SuggestedPriority::Medium);
+        assert_eq!(
+            suggestion.reasoning,
+            vec!["Blocks 1 other card".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_estimate_effort_from_similar_cards() {
+        let (mut db, card_id) = db_with_card("Add settings page", "");
+        let tag = db
+            .cards
+            .create_tag("frontend".to_string(), "#fff".to_string());
+        db.cards.get_card_mut(&card_id).unwrap().add_tag(tag);
+        let login = tracked_card(&mut db, "Add login page", &["frontend"], 4);
+        let signup = tracked_card(&mut db, "Add signup page", &["frontend"], 6);
+        tracked_card(&mut db, "Migrate database", &["backend"], 20);
+
+        let mut ai = AITaskIntelligence::new();
+        let estimation = ai.estimate_effort(&db, &card_id).unwrap();
+        assert!((estimation.estimated_hours - 5.0).abs() < 0.01);
+        let (lower, upper) = estimation.confidence_interval;
+        assert!(lower < 5.0 && upper > 5.0);
+        assert_eq!(estimation.similar_tasks.len(), 2);
+        assert!(estimation.similar_tasks.contains(&login));
+        assert!(estimation.similar_tasks.contains(&signup));
+        assert!(estimation.confidence > 0.0 && estimation.confidence < 1.0);
+    }
+
+    #[test]
+    fn test_estimate_effort_without_history() {
+        let (mut db, card_id) = db_with_card("Add settings page", "");
+        tracked_card(&mut db, "Migrate database", &[], 20);
+
+        let mut ai = AITaskIntelligence::new();
+        assert!(ai.estimate_effort(&db, &card_id).is_none());
+        assert!(ai.get_effort_estimation(&card_id).is_none());
+    }
+
+    #[test]
+    fn test_detect_bottlenecks_from_dwell_time() {
+        let now = Utc::now();
+        let hours_ago = |h: i64| now - chrono::Duration::hours(h);
+        let mut db = BoardDatabase::default();
+        let board_id = db.ensure_board();
+        for i in 0..3 {
+            let card_id = db.cards.create_card(
+                format!("Card {}", i),
+                String::new(),
+                "To Do".to_string(),
+                Priority::Medium,
+                "me".to_string(),
+            );
+            move_at(&mut db, &card_id, "To Do", hours_ago(100));
+            move_at(&mut db, &card_id, "In Progress", hours_ago(98));
+            move_at(&mut db, &card_id, "Review", hours_ago(96));
+            move_at(&mut db, &card_id, "Done", hours_ago(60));
+        }
+
+        let mut ai = AITaskIntelligence::new();
+        let bottlenecks = ai.detect_bottlenecks(&db, &board_id, now);
+        assert_eq!(bottlenecks.len(), 1);
+        let review = db.column_by_name(&board_id, "Review").unwrap();
+        assert_eq!(bottlenecks[0].column_id, review.id);
+        assert!((bottlenecks[0].avg_time_in_column - 36.0).abs() < 0.1);
+        assert_eq!(bottlenecks[0].severity, 1.0);
+        assert!(bottlenecks[0].suggested_actions[0].contains("Review"));
+    }
+
+    #[test]
+    fn test_detect_bottlenecks_over_wip_limit() {
+        let mut db = BoardDatabase::default();
+        let board_id = db.ensure_board();
+        let in_progress = db
+            .column_by_name(&board_id, "In Progress")
+            .unwrap()
+            .id
+            .clone();
+        db.boards.set_wip_limit(&in_progress, Some(1));
+        for i in 0..2 {
+            let card_id = db.cards.create_card(
+                format!("Card {}", i),
+                String::new(),
+                "In Progress".to_string(),
+                Priority::Medium,
+                "me".to_string(),
+            );
+            db.move_card_to_named(&card_id, "In Progress").unwrap();
+        }
+
+        let mut ai = AITaskIntelligence::new();
+        let bottlenecks = ai.detect_bottlenecks(&db, &board_id, Utc::now());
+        assert_eq!(bottlenecks.len(), 1);
+        assert!(bottlenecks[0].exceeds_wip_limit());
+        assert_eq!(bottlenecks[0].severity, 1.0);
+        assert!(ai.detect_bottlenecks(&db, "missing", Utc::now()).is_empty());
+    }
+
+    #[test]
+    fn test_daily_throughput_counts_cards_reaching_last_column() {
+        let now = Utc::now();
+        let mut db = BoardDatabase::default();
+        let board_id = db.ensure_board();
+        for days_ago in [0, 0, 2] {
+            let card_id = db.cards.create_card(
+                "Card".to_string(),
+                String::new(),
+                "To Do".to_string(),
+                Priority::Medium,
+                "me".to_string(),
+            );
+            move_at(&mut db, &card_id, "To Do", now - chrono::Duration::days(10));
+            move_at(
+                &mut db,
+                &card_id,
+                "Done",
+                now - chrono::Duration::days(days_ago),
+            );
+        }
+
+        let throughput = AITaskIntelligence::daily_throughput(&db, &board_id, 4, now);
+        assert_eq!(throughput, vec![0.0, 1.0, 0.0, 2.0]);
+    }
+
+    #[test]
+    fn test_forecast_burndown_monte_carlo() {
+        let now = Utc::now();
+        let mut rng = StdRng::seed_from_u64(7);
+        let mut ai = AITaskIntelligence::new();
+
+        let forecast = ai.forecast_burndown_with_rng(
+            "steady".to_string(),
+            now + chrono::Duration::days(10),
+            10.0,
+            &[2.0, 2.0, 2.0],
+            now,
+            &mut rng,
+        );
+        assert_eq!(forecast.forecasted_date, now + chrono::Duration::days(5));
+        assert_eq!(forecast.confidence, 1.0);
+        assert_eq!(forecast.days_delta, 5);
+        assert!(forecast.on_track);
+
+        let forecast = ai.forecast_burndown_with_rng(
+            "uneven".to_string(),
+            now + chrono::Duration::days(4),
+            10.0,
+            &[0.0, 5.0],
+            now,
+            &mut rng,
+        );
+        assert!(forecast.confidence > 0.0 && forecast.confidence < 1.0);
+        assert_eq!(forecast.current_velocity, 2.5);
+
+        let forecast = ai.forecast_burndown(
+            "stalled".to_string(),
+            now + chrono::Duration::days(4),
+            10.0,
+            &[],
+            now,
+        );
+        assert!(!forecast.on_track);
+        assert_eq!(ai.forecast_count(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_suggest_with_llm() {
+        let (db, card_id) = db_with_card("Payments time out", "Checkout fails under load");
+        let card = db.cards.get_card(&card_id).unwrap();
+        let client = MockResponseBuilder::new()
+            .with_text(
+                "```json\n{\"priority\": \"high\", \"confidence\": 0.8, \
+                 \"reasons\": [\"Blocks checkout\"], \"tags\": [\"payments\"], \
+                 \"project\": \"Billing\"}\n```",
+            )
+            .with_text("{\"priority\": \"someday\"}")
+            .build();
+
+        let mut ai = AITaskIntelligence::new();
+        let (priority, categories) = ai.suggest_with_llm(card, &client).await.unwrap();
+        assert_eq!(priority.priority, SuggestedPriority::High);
+        assert_eq!(priority.reasoning, vec!["Blocks checkout".to_string()]);
+        assert_eq!(categories.suggested_tags, vec!["payments".to_string()]);
+        assert_eq!(categories.suggested_project, Some("Billing".to_string()));
+        assert_eq!(ai.suggestion_count(), 2);
+
+        assert!(ai.suggest_with_llm(card, &client).await.is_err());
+    }
 }
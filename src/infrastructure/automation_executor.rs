@@ -0,0 +1,1133 @@
+//! Automation rule execution
+//!
+//! Runs the [`builtin_automation`](crate::infrastructure::builtin_automation)
+//! rules stored in a [`BoardDatabase`]. The executor compares the board with
+//! the card state it saw last and turns the differences into [`BoardEvent`]s
+//! (card created, moved, assigned, tagged, ...). Rules whose trigger matches
+//! an event run their action at once or, with a delay, through the
+//! executor's schedule. Due-date triggers and recurring tasks are checked
+//! against a [`Clock`] on every run, so tests can drive time with a
+//! [`FakeClock`].
+//!
+//! Every action is written to the card's activity feed as an `automation`
+//! entry (with the rule ID as `rule_id` metadata).
+//!
+//! # Loop protection
+//!
+//! Actions change the board and cause further events. Within one run a rule
+//! acts at most once per card, and chains of rules triggering rules stop
+//! after [`MAX_CHAIN_DEPTH`] steps (delayed actions keep their step count),
+//! so rules that undo each other cannot loop.
+//!
+//! # Examples
+//!
+//! ```
+//! use toad::infrastructure::automation_executor::AutomationExecutor;
+//! use toad::infrastructure::board_store::BoardDatabase;
+//! use toad::infrastructure::builtin_automation::{
+//!     AutomationAction, AutomationRule, TriggerCondition,
+//! };
+//! use toad::infrastructure::Priority;
+//!
+//! let mut db = BoardDatabase::default();
+//! db.automation.add_rule(AutomationRule::new(
+//!     "Tag new cards",
+//!     TriggerCondition::TaskCreated,
+//!     AutomationAction::AddTag("triage".to_string()),
+//! ));
+//!
+//! let mut executor = AutomationExecutor::new();
+//! executor.observe(&db);
+//! let card_id = db.cards.create_card(
+//!     "Crash on start".to_string(),
+//!     String::new(),
+//!     "To Do".to_string(),
+//!     Priority::High,
+//!     "me".to_string(),
+//! );
+//!
+//! let report = executor.run(&mut db);
+//! assert_eq!(report.executed.len(), 1);
+//! assert_eq!(db.cards.get_card(&card_id).unwrap().tags[0].name, "triage");
+//! ```
+
+use crate::infrastructure::board_store::BoardDatabase;
+use crate::infrastructure::builtin_automation::{
+    AutomationAction, AutomationRule, RecurringTask, TriggerCondition,
+};
+use crate::infrastructure::rich_task_cards::{Assignee, Priority, RichTaskCard, Tag};
+use chrono::{DateTime, Duration, Utc};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+/// Rule steps in one chain of events before the chain is stopped
+pub const MAX_CHAIN_DEPTH: usize = 8;
+
+/// Activity type of the audit entries
+pub const AUTOMATION_ACTIVITY: &str = "automation";
+
+/// User the automation acts as
+const AUTOMATION_USER: &str = "automation";
+
+/// Color of tags created by rules
+const TAG_COLOR: &str = "#808080";
+
+/// Source of the current time
+pub trait Clock: fmt::Debug + Send + Sync {
+    /// Current time
+    fn now(&self) -> DateTime<Utc>;
+}
+
+/// Wall clock time
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+/// Manually advanced clock for tests
+///
+/// Clones share the same time, so a test can keep one and hand another to
+/// the executor.
+#[derive(Debug, Clone)]
+pub struct FakeClock {
+    now: Arc<Mutex<DateTime<Utc>>>,
+}
+
+impl FakeClock {
+    /// Create a clock stopped at `start`
+    pub fn new(start: DateTime<Utc>) -> Self {
+        Self {
+            now: Arc::new(Mutex::new(start)),
+        }
+    }
+
+    /// Move the clock forward
+    pub fn advance(&self, by: Duration) {
+        let mut now = self.now.lock().unwrap_or_else(|e| e.into_inner());
+        *now += by;
+    }
+
+    /// Set the clock to a time
+    pub fn set(&self, at: DateTime<Utc>) {
+        *self.now.lock().unwrap_or_else(|e| e.into_inner()) = at;
+    }
+}
+
+impl Clock for FakeClock {
+    fn now(&self) -> DateTime<Utc> {
+        *self.now.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Change to a card detected on the board
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BoardEvent {
+    /// Card was created
+    CardCreated { card_id: String },
+    /// Card changed status (column)
+    CardMoved {
+        card_id: String,
+        from: String,
+        to: String,
+    },
+    /// Card priority changed
+    PriorityChanged { card_id: String, priority: Priority },
+    /// User was assigned to the card
+    CardAssigned { card_id: String, user_id: String },
+    /// User was removed from the card
+    CardUnassigned { card_id: String, user_id: String },
+    /// Card due date was set or changed
+    DueDateSet { card_id: String },
+    /// Tag was added to the card
+    TagAdded { card_id: String, tag: String },
+    /// Tag was removed from the card
+    TagRemoved { card_id: String, tag: String },
+}
+
+impl BoardEvent {
+    /// Card the event is about
+    pub fn card_id(&self) -> &str {
+        match self {
+            BoardEvent::CardCreated { card_id }
+            | BoardEvent::CardMoved { card_id, .. }
+            | BoardEvent::PriorityChanged { card_id, .. }
+            | BoardEvent::CardAssigned { card_id, .. }
+            | BoardEvent::CardUnassigned { card_id, .. }
+            | BoardEvent::DueDateSet { card_id }
+            | BoardEvent::TagAdded { card_id, .. }
+            | BoardEvent::TagRemoved { card_id, .. } => card_id,
+        }
+    }
+
+    /// Whether a rule trigger fires for this event
+    ///
+    /// Status and tag names match ignoring case and spaces; priorities also
+    /// match as `P0`..`P3`. Moving a card to a `Done` column completes it.
+    /// Time-based triggers never match events.
+    pub fn matches(&self, trigger: &TriggerCondition) -> bool {
+        match (self, trigger) {
+            (BoardEvent::CardCreated { .. }, TriggerCondition::TaskCreated) => true,
+            (BoardEvent::CardMoved { to, .. }, TriggerCondition::TaskMovedTo(status)) => {
+                name_key(to) == name_key(status)
+            }
+            (BoardEvent::CardMoved { from, .. }, TriggerCondition::TaskMovedFrom(status)) => {
+                name_key(from) == name_key(status)
+            }
+            (BoardEvent::CardMoved { to, .. }, TriggerCondition::TaskCompleted) => is_done(to),
+            (
+                BoardEvent::PriorityChanged { priority, .. },
+                TriggerCondition::PriorityChangedTo(name),
+            ) => parse_priority(name) == Some(*priority),
+            (BoardEvent::CardAssigned { .. }, TriggerCondition::TaskAssigned) => true,
+            (BoardEvent::CardUnassigned { .. }, TriggerCondition::TaskUnassigned) => true,
+            (BoardEvent::DueDateSet { .. }, TriggerCondition::DueDateSet) => true,
+            (BoardEvent::TagAdded { tag, .. }, TriggerCondition::TagAdded(name)) => {
+                name_key(tag) == name_key(name)
+            }
+            (BoardEvent::TagRemoved { tag, .. }, TriggerCondition::TagRemoved(name)) => {
+                name_key(tag) == name_key(name)
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Action run by a rule
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExecutedAction {
+    /// Rule that ran
+    pub rule_id: String,
+    /// Rule name
+    pub rule_name: String,
+    /// Card the action was applied to
+    pub card_id: String,
+    /// Action applied
+    pub action: AutomationAction,
+    /// When it ran
+    pub executed_at: DateTime<Utc>,
+}
+
+/// What one [`AutomationExecutor::run`] did
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AutomationReport {
+    /// Actions applied, in order
+    pub executed: Vec<ExecutedAction>,
+    /// Cards created from recurring tasks
+    pub created_cards: Vec<String>,
+    /// Messages of `SendNotification` actions and warnings (e.g. WIP limits)
+    pub notifications: Vec<String>,
+    /// Rule runs skipped by the loop protection
+    pub suppressed: usize,
+    /// Actions that failed
+    pub errors: Vec<String>,
+}
+
+impl AutomationReport {
+    /// Whether the run changed the board
+    pub fn changed_board(&self) -> bool {
+        !self.executed.is_empty() || !self.created_cards.is_empty()
+    }
+
+    /// Whether nothing happened
+    pub fn is_empty(&self) -> bool {
+        !self.changed_board()
+            && self.notifications.is_empty()
+            && self.suppressed == 0
+            && self.errors.is_empty()
+    }
+}
+
+/// Card fields that rules react to
+#[derive(Debug, Clone, PartialEq)]
+struct CardState {
+    status: String,
+    priority: Priority,
+    assignees: Vec<String>,
+    tags: Vec<String>,
+    due_date: Option<DateTime<Utc>>,
+}
+
+impl CardState {
+    fn of(card: &RichTaskCard) -> Self {
+        Self {
+            status: card.status.clone(),
+            priority: card.priority,
+            assignees: card.assignees.iter().map(|a| a.user_id.clone()).collect(),
+            tags: card.tags.iter().map(|t| t.name.clone()).collect(),
+            due_date: card.due_date,
+        }
+    }
+}
+
+/// Delayed rule action waiting for its time
+#[derive(Debug, Clone)]
+struct ScheduledAction {
+    due_at: DateTime<Utc>,
+    rule_id: String,
+    card_id: String,
+    depth: usize,
+}
+
+/// Rule action ready to run
+#[derive(Debug, Clone)]
+struct PendingAction {
+    rule: AutomationRule,
+    card_id: String,
+    depth: usize,
+}
+
+/// Event-driven executor for automation rules
+///
+/// Delayed actions are kept in memory; they are lost when the app exits.
+#[derive(Debug)]
+pub struct AutomationExecutor {
+    clock: Box<dyn Clock>,
+    /// Card state at the last run, by card ID (`None` before the first look)
+    seen: Option<HashMap<String, CardState>>,
+    scheduled: Vec<ScheduledAction>,
+}
+
+impl AutomationExecutor {
+    /// Create an executor on the system clock
+    pub fn new() -> Self {
+        Self::with_clock(SystemClock)
+    }
+
+    /// Create an executor on a given clock
+    pub fn with_clock(clock: impl Clock + 'static) -> Self {
+        Self {
+            clock: Box::new(clock),
+            seen: None,
+            scheduled: Vec::new(),
+        }
+    }
+
+    /// Take the board's current state as the baseline without firing rules
+    ///
+    /// Call after loading a board, so its existing cards do not count as
+    /// created. [`run`](Self::run) does this itself the first time.
+    pub fn observe(&mut self, db: &BoardDatabase) {
+        self.seen = Some(snapshot(db));
+    }
+
+    /// Delayed actions waiting to run
+    pub fn scheduled_count(&self) -> usize {
+        self.scheduled.len()
+    }
+
+    /// Detect changes since the last run and execute matching rules
+    ///
+    /// In order: creates due recurring tasks, fires due-date triggers, runs
+    /// delayed actions whose time has come and then the rules matching the
+    /// board changes, repeating for changes made by the rules themselves.
+    pub fn run(&mut self, db: &mut BoardDatabase) -> AutomationReport {
+        let now = self.clock.now();
+        let mut report = AutomationReport::default();
+        if self.seen.is_none() {
+            self.observe(db);
+        }
+
+        self.create_recurring_cards(db, now, &mut report);
+        let mut pending = self.due_date_actions(db, now);
+        pending.extend(self.due_scheduled_actions(db, now));
+
+        let mut fired: HashSet<(String, String)> = HashSet::new();
+        let mut events = self.changes(db);
+        let mut depth = 0;
+        loop {
+            for event in &events {
+                for rule in matching_rules(db, event) {
+                    let card_id = event.card_id().to_string();
+                    match rule.delay_seconds {
+                        Some(delay) if delay > 0 => self.scheduled.push(ScheduledAction {
+                            due_at: now + Duration::seconds(delay as i64),
+                            rule_id: rule.id.clone(),
+                            card_id,
+                            depth,
+                        }),
+                        _ => pending.push(PendingAction {
+                            rule,
+                            card_id,
+                            depth,
+                        }),
+                    }
+                }
+            }
+            if pending.is_empty() {
+                break;
+            }
+
+            let mut next_depth = depth + 1;
+            for action in pending.drain(..) {
+                let key = (action.rule.id.clone(), action.card_id.clone());
+                if action.depth >= MAX_CHAIN_DEPTH || !fired.insert(key) {
+                    report.suppressed += 1;
+                    log(
+                        db,
+                        &action.card_id,
+                        &action.rule.id,
+                        format!(
+                            "Rule '{}' skipped to stop an automation loop",
+                            action.rule.name
+                        ),
+                    );
+                    continue;
+                }
+                self.execute(db, &action.rule, &action.card_id, now, &mut report);
+                next_depth = next_depth.max(action.depth + 1);
+            }
+            events = self.changes(db);
+            depth = next_depth;
+        }
+        report
+    }
+
+    /// Events for the differences between the board and the last run
+    fn changes(&mut self, db: &BoardDatabase) -> Vec<BoardEvent> {
+        let current = snapshot(db);
+        let previous = self.seen.replace(current.clone()).unwrap_or_default();
+        let mut events = Vec::new();
+        for card in db.cards.cards() {
+            let state = &current[&card.id];
+            let card_id = card.id.clone();
+            let Some(old) = previous.get(&card.id) else {
+                events.push(BoardEvent::CardCreated { card_id });
+                continue;
+            };
+            if old.status != state.status {
+                events.push(BoardEvent::CardMoved {
+                    card_id: card_id.clone(),
+                    from: old.status.clone(),
+                    to: state.status.clone(),
+                });
+            }
+            if old.priority != state.priority {
+                events.push(BoardEvent::PriorityChanged {
+                    card_id: card_id.clone(),
+                    priority: state.priority,
+                });
+            }
+            for user_id in added(&old.assignees, &state.assignees) {
+                events.push(BoardEvent::CardAssigned {
+                    card_id: card_id.clone(),
+                    user_id,
+                });
+            }
+            for user_id in added(&state.assignees, &old.assignees) {
+                events.push(BoardEvent::CardUnassigned {
+                    card_id: card_id.clone(),
+                    user_id,
+                });
+            }
+            if state.due_date.is_some() && old.due_date != state.due_date {
+                events.push(BoardEvent::DueDateSet {
+                    card_id: card_id.clone(),
+                });
+            }
+            for tag in added(&old.tags, &state.tags) {
+                events.push(BoardEvent::TagAdded {
+                    card_id: card_id.clone(),
+                    tag,
+                });
+            }
+            for tag in added(&state.tags, &old.tags) {
+                events.push(BoardEvent::TagRemoved {
+                    card_id: card_id.clone(),
+                    tag,
+                });
+            }
+        }
+        events
+    }
+
+    /// Create cards for recurring tasks that are due
+    fn create_recurring_cards(
+        &mut self,
+        db: &mut BoardDatabase,
+        now: DateTime<Utc>,
+        report: &mut AutomationReport,
+    ) {
+        let mut due: Vec<RecurringTask> = db
+            .automation
+            .get_due_recurring_tasks_at(now)
+            .into_iter()
+            .cloned()
+            .collect();
+        due.sort_by(|a, b| a.id.cmp(&b.id));
+
+        for task in due {
+            let card_id = db.cards.create_card(
+                task.title.clone(),
+                task.description.clone(),
+                task.target_status.clone(),
+                Priority::Medium,
+                AUTOMATION_USER.to_string(),
+            );
+            for tag in &task.tags {
+                let tag = find_or_create_tag(db, tag);
+                if let Some(card) = db.cards.get_card_mut(&card_id) {
+                    card.add_tag(tag);
+                }
+            }
+            if let Some(user_id) = &task.assignee
+                && let Some(card) = db.cards.get_card_mut(&card_id)
+            {
+                card.add_assignee(assignee(user_id, now));
+            }
+            if !db.boards.boards().is_empty() {
+                let _ = db.move_card_to_named(&card_id, &task.target_status);
+            }
+            db.automation.mark_recurring_task_created_at(&task.id, now);
+            log(
+                db,
+                &card_id,
+                &task.id,
+                format!(
+                    "Created from recurring task '{}' ({})",
+                    task.title,
+                    task.pattern.name()
+                ),
+            );
+            report.created_cards.push(card_id);
+        }
+    }
+
+    /// Due-date rules that fire now
+    ///
+    /// Each rule fires once per card and due date; the audit log records
+    /// which ones already did.
+    fn due_date_actions(&self, db: &BoardDatabase, now: DateTime<Utc>) -> Vec<PendingAction> {
+        let mut rules: Vec<&AutomationRule> = db
+            .automation
+            .get_enabled_rules()
+            .into_iter()
+            .filter(|rule| is_due_date_trigger(&rule.trigger))
+            .collect();
+        rules.sort_by(|a, b| a.id.cmp(&b.id));
+
+        let mut actions = Vec::new();
+        for card in db.cards.cards() {
+            let Some(due) = card.due_date else {
+                continue;
+            };
+            if is_done(&card.status) {
+                continue;
+            }
+            for rule in &rules {
+                let fires = match rule.trigger {
+                    TriggerCondition::DueDateApproaching(days) => {
+                        due > now && due - now <= Duration::days(days as i64)
+                    }
+                    _ => due <= now,
+                };
+                if fires && !fired_for_due_date(db, &card.id, &rule.id, due) {
+                    actions.push(PendingAction {
+                        rule: (*rule).clone(),
+                        card_id: card.id.clone(),
+                        depth: 0,
+                    });
+                }
+            }
+        }
+        actions
+    }
+
+    /// Delayed actions whose time has come, oldest first
+    fn due_scheduled_actions(
+        &mut self,
+        db: &BoardDatabase,
+        now: DateTime<Utc>,
+    ) -> Vec<PendingAction> {
+        let (mut due, waiting): (Vec<_>, Vec<_>) = self
+            .scheduled
+            .drain(..)
+            .partition(|action| action.due_at <= now);
+        self.scheduled = waiting;
+        due.sort_by_key(|action| action.due_at);
+        due.into_iter()
+            .filter_map(|action| {
+                let rule = db.automation.get_rule(&action.rule_id)?;
+                rule.enabled.then(|| PendingAction {
+                    rule: rule.clone(),
+                    card_id: action.card_id,
+                    depth: action.depth,
+                })
+            })
+            .collect()
+    }
+
+    /// Apply a rule's action and record it
+    fn execute(
+        &mut self,
+        db: &mut BoardDatabase,
+        rule: &AutomationRule,
+        card_id: &str,
+        now: DateTime<Utc>,
+        report: &mut AutomationReport,
+    ) {
+        let due_date = db.cards.get_card(card_id).and_then(|card| card.due_date);
+        match apply_action(db, &rule.action, card_id, now) {
+            Ok(notification) => {
+                report.notifications.extend(notification);
+                let activity_id = log(
+                    db,
+                    card_id,
+                    &rule.id,
+                    format!("Rule '{}': {}", rule.name, rule.action.name()),
+                );
+                if is_due_date_trigger(&rule.trigger)
+                    && let Some(due) = due_date
+                    && let Some(activity) = db.comments.get_activity_mut(&activity_id)
+                {
+                    activity.add_metadata("due_date".to_string(), due.to_rfc3339());
+                }
+                if let Some(stored) = db.automation.get_rule_mut(&rule.id) {
+                    stored.record_execution_at(now);
+                }
+                report.executed.push(ExecutedAction {
+                    rule_id: rule.id.clone(),
+                    rule_name: rule.name.clone(),
+                    card_id: card_id.to_string(),
+                    action: rule.action.clone(),
+                    executed_at: now,
+                });
+            }
+            Err(e) => {
+                log(
+                    db,
+                    card_id,
+                    &rule.id,
+                    format!("Rule '{}' failed: {}", rule.name, e),
+                );
+                report.errors.push(format!("{}: {}", rule.name, e));
+            }
+        }
+    }
+}
+
+impl Default for AutomationExecutor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Rules triggered by an event, in rule ID order
+fn matching_rules(db: &BoardDatabase, event: &BoardEvent) -> Vec<AutomationRule> {
+    let mut rules: Vec<AutomationRule> = db
+        .automation
+        .get_enabled_rules()
+        .into_iter()
+        .filter(|rule| event.matches(&rule.trigger))
+        .cloned()
+        .collect();
+    rules.sort_by(|a, b| a.id.cmp(&b.id));
+    rules
+}
+
+/// Apply an action to a card
+///
+/// Returns a message to show (notifications and WIP warnings).
+fn apply_action(
+    db: &mut BoardDatabase,
+    action: &AutomationAction,
+    card_id: &str,
+    now: DateTime<Utc>,
+) -> Result<Option<String>, String> {
+    let title = db
+        .cards
+        .get_card(card_id)
+        .map(|card| card.title.clone())
+        .ok_or_else(|| format!("Card {} not found", card_id))?;
+
+    match action {
+        AutomationAction::MoveToStatus(status) => {
+            if db.board_of_card(card_id).is_some() {
+                return db.move_card_to_named(card_id, status);
+            }
+            card_mut(db, card_id).status = status.clone();
+        }
+        AutomationAction::AssignTo(user_id) => {
+            card_mut(db, card_id).add_assignee(assignee(user_id, now));
+        }
+        AutomationAction::Unassign => card_mut(db, card_id).assignees.clear(),
+        AutomationAction::AddTag(name) => {
+            let has_tag = card_mut(db, card_id)
+                .tags
+                .iter()
+                .any(|tag| name_key(&tag.name) == name_key(name));
+            if !has_tag {
+                let tag = find_or_create_tag(db, name);
+                card_mut(db, card_id).add_tag(tag);
+            }
+        }
+        AutomationAction::RemoveTag(name) => {
+            card_mut(db, card_id)
+                .tags
+                .retain(|tag| name_key(&tag.name) != name_key(name));
+        }
+        AutomationAction::SetPriority(name) => {
+            card_mut(db, card_id).priority =
+                parse_priority(name).ok_or_else(|| format!("Unknown priority '{}'", name))?;
+        }
+        AutomationAction::SetDueDateDaysFromNow(days) => {
+            card_mut(db, card_id).due_date = Some(now + Duration::days(*days as i64));
+        }
+        AutomationAction::Archive => {
+            db.boards.remove_card(card_id);
+            card_mut(db, card_id).status = "Archived".to_string();
+        }
+        AutomationAction::Delete => {
+            db.boards.remove_card(card_id);
+            db.cards.delete_card(card_id);
+        }
+        AutomationAction::SendNotification(message) => {
+            return Ok(Some(format!("{}: {}", title, message)));
+        }
+        AutomationAction::AddComment(text) => {
+            db.comments.create_comment(
+                card_id.to_string(),
+                None,
+                text.clone(),
+                AUTOMATION_USER.to_string(),
+                "Automation".to_string(),
+            );
+        }
+    }
+    if let Some(card) = db.cards.get_card_mut(card_id) {
+        card.updated_at = now;
+    }
+    Ok(None)
+}
+
+/// Card that `apply_action` checked exists
+fn card_mut<'a>(db: &'a mut BoardDatabase, card_id: &str) -> &'a mut RichTaskCard {
+    db.cards
+        .get_card_mut(card_id)
+        .expect("card existence checked before applying the action")
+}
+
+/// Write an audit entry to a card's activity feed
+fn log(db: &mut BoardDatabase, card_id: &str, source_id: &str, description: String) -> String {
+    let activity_id = db.comments.log_activity(
+        card_id.to_string(),
+        AUTOMATION_ACTIVITY.to_string(),
+        description,
+        Some(AUTOMATION_USER.to_string()),
+        Some("Automation".to_string()),
+    );
+    if let Some(activity) = db.comments.get_activity_mut(&activity_id) {
+        activity.add_metadata("rule_id".to_string(), source_id.to_string());
+    }
+    activity_id
+}
+
+/// Whether a trigger fires on time passing rather than on a board change
+fn is_due_date_trigger(trigger: &TriggerCondition) -> bool {
+    matches!(
+        trigger,
+        TriggerCondition::DueDateApproaching(_) | TriggerCondition::TaskOverdue
+    )
+}
+
+/// Whether a due-date rule already ran for a card's current due date
+fn fired_for_due_date(
+    db: &BoardDatabase,
+    card_id: &str,
+    rule_id: &str,
+    due: DateTime<Utc>,
+) -> bool {
+    let due = due.to_rfc3339();
+    db.comments
+        .activities_for_card(card_id)
+        .iter()
+        .any(|activity| {
+            activity.activity_type == AUTOMATION_ACTIVITY
+                && activity
+                    .metadata
+                    .get("rule_id")
+                    .is_some_and(|id| id == rule_id)
+                && activity.metadata.get("due_date") == Some(&due)
+        })
+}
+
+/// Card state of every card, by ID
+fn snapshot(db: &BoardDatabase) -> HashMap<String, CardState> {
+    db.cards
+        .cards()
+        .into_iter()
+        .map(|card| (card.id.clone(), CardState::of(card)))
+        .collect()
+}
+
+/// Items of `new` not in `old`
+fn added(old: &[String], new: &[String]) -> Vec<String> {
+    new.iter()
+        .filter(|item| !old.contains(item))
+        .cloned()
+        .collect()
+}
+
+/// A tag with this name already used on the board, or a new one
+fn find_or_create_tag(db: &mut BoardDatabase, name: &str) -> Tag {
+    let existing = db
+        .cards
+        .cards()
+        .into_iter()
+        .flat_map(|card| &card.tags)
+        .find(|tag| name_key(&tag.name) == name_key(name))
+        .cloned();
+    existing.unwrap_or_else(|| db.cards.create_tag(name.to_string(), TAG_COLOR.to_string()))
+}
+
+fn assignee(user_id: &str, now: DateTime<Utc>) -> Assignee {
+    Assignee {
+        user_id: user_id.to_string(),
+        display_name: user_id.to_string(),
+        avatar: None,
+        assigned_at: now,
+    }
+}
+
+/// Priority by name (`Critical`..`Low`) or level (`P0`..`P3`)
+fn parse_priority(name: &str) -> Option<Priority> {
+    match name_key(name).as_str() {
+        "critical" | "p0" => Some(Priority::Critical),
+        "high" | "p1" => Some(Priority::High),
+        "medium" | "p2" => Some(Priority::Medium),
+        "low" | "p3" => Some(Priority::Low),
+        _ => None,
+    }
+}
+
+/// Normalized status or tag name for matching
+fn name_key(name: &str) -> String {
+    name.trim()
+        .to_lowercase()
+        .chars()
+        .filter(|c| !matches!(c, ' ' | '_' | '-'))
+        .collect()
+}
+
+/// Whether a status means the card is finished
+fn is_done(status: &str) -> bool {
+    name_key(status) == "done"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::builtin_automation::RecurrencePattern;
+
+    fn start() -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339("2026-03-02T09:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    /// Board with one card in "To Do", and an executor that has seen it
+    fn setup() -> (BoardDatabase, String, AutomationExecutor, FakeClock) {
+        let mut db = BoardDatabase::default();
+        let board_id = db.ensure_board();
+        let card_id = db.cards.create_card(
+            "Write release notes".to_string(),
+            String::new(),
+            "To Do".to_string(),
+            Priority::Medium,
+            "me".to_string(),
+        );
+        db.place_cards(&board_id);
+        let clock = FakeClock::new(start());
+        let mut executor = AutomationExecutor::with_clock(clock.clone());
+        executor.observe(&db);
+        (db, card_id, executor, clock)
+    }
+
+    fn rule(db: &mut BoardDatabase, trigger: TriggerCondition, action: AutomationAction) -> String {
+        db.automation
+            .add_rule(AutomationRule::new("test rule", trigger, action))
+    }
+
+    fn automation_log(db: &BoardDatabase, card_id: &str) -> Vec<String> {
+        db.comments
+            .activities_for_card(card_id)
+            .into_iter()
+            .filter(|a| a.activity_type == AUTOMATION_ACTIVITY)
+            .map(|a| a.description.clone())
+            .collect()
+    }
+
+    #[test]
+    fn test_event_matching() {
+        let moved = BoardEvent::CardMoved {
+            card_id: "card-1".to_string(),
+            from: "In Progress".to_string(),
+            to: "Done".to_string(),
+        };
+        assert!(moved.matches(&TriggerCondition::TaskMovedTo("done".to_string())));
+        assert!(moved.matches(&TriggerCondition::TaskMovedFrom("in_progress".to_string())));
+        assert!(moved.matches(&TriggerCondition::TaskCompleted));
+        assert!(!moved.matches(&TriggerCondition::TaskCreated));
+
+        let priority = BoardEvent::PriorityChanged {
+            card_id: "card-1".to_string(),
+            priority: Priority::Critical,
+        };
+        assert!(priority.matches(&TriggerCondition::PriorityChangedTo("P0".to_string())));
+        assert!(!priority.matches(&TriggerCondition::PriorityChangedTo("High".to_string())));
+    }
+
+    #[test]
+    fn test_board_changes_fire_rules_and_are_audited() {
+        let (mut db, card_id, mut executor, _) = setup();
+        let rule_id = rule(
+            &mut db,
+            TriggerCondition::PriorityChangedTo("P0".to_string()),
+            AutomationAction::AssignTo("lead".to_string()),
+        );
+        rule(
+            &mut db,
+            TriggerCondition::TaskAssigned,
+            AutomationAction::MoveToStatus("In Progress".to_string()),
+        );
+
+        assert!(executor.run(&mut db).is_empty());
+        db.cards.get_card_mut(&card_id).unwrap().priority = Priority::Critical;
+        let report = executor.run(&mut db);
+
+        assert_eq!(report.executed.len(), 2);
+        let card = db.cards.get_card(&card_id).unwrap();
+        assert_eq!(card.assignees[0].user_id, "lead");
+        assert_eq!(card.status, "In Progress");
+        assert_eq!(
+            automation_log(&db, &card_id),
+            vec![
+                "Rule 'test rule': Assign To lead".to_string(),
+                "Rule 'test rule': Move To In Progress".to_string(),
+            ]
+        );
+        let rule = db.automation.get_rule(&rule_id).unwrap();
+        assert_eq!(rule.execution_count, 1);
+        assert_eq!(rule.last_executed, Some(start()));
+    }
+
+    #[test]
+    fn test_delayed_action_runs_on_schedule() {
+        let (mut db, card_id, mut executor, clock) = setup();
+        db.automation.add_rule(
+            AutomationRule::new(
+                "Archive finished cards",
+                TriggerCondition::TaskCompleted,
+                AutomationAction::Archive,
+            )
+            .with_delay(7 * 24 * 3600),
+        );
+
+        db.move_card_to_named(&card_id, "Done").unwrap();
+        let report = executor.run(&mut db);
+        assert!(report.executed.is_empty());
+        assert_eq!(executor.scheduled_count(), 1);
+
+        clock.advance(Duration::days(6));
+        assert!(executor.run(&mut db).executed.is_empty());
+
+        clock.advance(Duration::days(1));
+        let report = executor.run(&mut db);
+        assert_eq!(report.executed.len(), 1);
+        assert_eq!(report.executed[0].executed_at, start() + Duration::days(7));
+        assert_eq!(db.cards.get_card(&card_id).unwrap().status, "Archived");
+        assert!(db.boards.get_card_position(&card_id).is_none());
+        assert_eq!(executor.scheduled_count(), 0);
+    }
+
+    #[test]
+    fn test_disabled_rule_drops_scheduled_action() {
+        let (mut db, card_id, mut executor, clock) = setup();
+        let rule_id = db.automation.add_rule(
+            AutomationRule::new(
+                "Nudge",
+                TriggerCondition::TaskMovedTo("Review".to_string()),
+                AutomationAction::AddComment("Please review".to_string()),
+            )
+            .with_delay(60),
+        );
+
+        db.move_card_to_named(&card_id, "Review").unwrap();
+        executor.run(&mut db);
+        db.automation
+            .get_rule_mut(&rule_id)
+            .unwrap()
+            .set_enabled(false);
+        clock.advance(Duration::minutes(2));
+
+        assert!(executor.run(&mut db).executed.is_empty());
+        assert_eq!(db.comments.comments_for_card(&card_id).len(), 0);
+    }
+
+    #[test]
+    fn test_rules_undoing_each_other_do_not_loop() {
+        let (mut db, card_id, mut executor, _) = setup();
+        rule(
+            &mut db,
+            TriggerCondition::TaskMovedTo("Review".to_string()),
+            AutomationAction::MoveToStatus("In Progress".to_string()),
+        );
+        rule(
+            &mut db,
+            TriggerCondition::TaskMovedTo("In Progress".to_string()),
+            AutomationAction::MoveToStatus("Review".to_string()),
+        );
+
+        db.move_card_to_named(&card_id, "Review").unwrap();
+        let report = executor.run(&mut db);
+
+        assert_eq!(report.executed.len(), 2);
+        assert_eq!(report.suppressed, 1);
+        assert_eq!(db.cards.get_card(&card_id).unwrap().status, "Review");
+        assert!(
+            automation_log(&db, &card_id)
+                .last()
+                .unwrap()
+                .contains("skipped to stop an automation loop")
+        );
+        assert!(executor.run(&mut db).is_empty());
+    }
+
+    #[test]
+    fn test_delayed_rules_undoing_each_other_stop_at_chain_depth() {
+        let (mut db, card_id, mut executor, clock) = setup();
+        for (from, to) in [("Review", "In Progress"), ("In Progress", "Review")] {
+            db.automation.add_rule(
+                AutomationRule::new(
+                    "bounce",
+                    TriggerCondition::TaskMovedTo(from.to_string()),
+                    AutomationAction::MoveToStatus(to.to_string()),
+                )
+                .with_delay(1),
+            );
+        }
+
+        db.move_card_to_named(&card_id, "Review").unwrap();
+        let mut executed = 0;
+        let mut suppressed = 0;
+        for _ in 0..20 {
+            let report = executor.run(&mut db);
+            executed += report.executed.len();
+            suppressed += report.suppressed;
+            clock.advance(Duration::seconds(1));
+        }
+        assert_eq!(executed, MAX_CHAIN_DEPTH);
+        assert_eq!(suppressed, 1);
+        assert_eq!(executor.scheduled_count(), 0);
+    }
+
+    #[test]
+    fn test_due_date_triggers_fire_once_per_due_date() {
+        let (mut db, card_id, mut executor, clock) = setup();
+        rule(
+            &mut db,
+            TriggerCondition::DueDateApproaching(2),
+            AutomationAction::AddTag("due-soon".to_string()),
+        );
+        rule(
+            &mut db,
+            TriggerCondition::TaskOverdue,
+            AutomationAction::SendNotification("overdue".to_string()),
+        );
+        db.cards.get_card_mut(&card_id).unwrap().due_date = Some(start() + Duration::days(3));
+
+        assert!(executor.run(&mut db).executed.is_empty());
+        clock.advance(Duration::days(2));
+        assert_eq!(executor.run(&mut db).executed.len(), 1);
+        assert!(executor.run(&mut db).executed.is_empty());
+        assert_eq!(
+            db.cards.get_card(&card_id).unwrap().tags[0].name,
+            "due-soon"
+        );
+
+        clock.advance(Duration::days(2));
+        let report = executor.run(&mut db);
+        assert_eq!(
+            report.notifications,
+            vec!["Write release notes: overdue".to_string()]
+        );
+        assert!(executor.run(&mut db).notifications.is_empty());
+
+        db.cards.get_card_mut(&card_id).unwrap().due_date = Some(clock.now() - Duration::hours(1));
+        assert_eq!(executor.run(&mut db).notifications.len(), 1);
+    }
+
+    #[test]
+    fn test_recurring_tasks_materialize_on_schedule() {
+        let (mut db, _, mut executor, clock) = setup();
+        let mut task = RecurringTask::new("Standup", "Daily sync", RecurrencePattern::Daily)
+            .with_tag("meeting")
+            .with_assignee("alice");
+        task.next_occurrence = start();
+        db.automation.add_recurring_task(task);
+        rule(
+            &mut db,
+            TriggerCondition::TaskCreated,
+            AutomationAction::SetDueDateDaysFromNow(1),
+        );
+
+        let report = executor.run(&mut db);
+        assert_eq!(report.created_cards.len(), 1);
+        let card = db.cards.get_card(&report.created_cards[0]).unwrap();
+        assert_eq!(card.title, "Standup");
+        assert_eq!(card.tags[0].name, "meeting");
+        assert_eq!(card.assignees[0].user_id, "alice");
+        assert_eq!(card.due_date, Some(start() + Duration::days(1)));
+        assert!(db.boards.get_card_position(&card.id).is_some());
+
+        assert!(executor.run(&mut db).created_cards.is_empty());
+        clock.advance(Duration::days(1));
+        assert_eq!(executor.run(&mut db).created_cards.len(), 1);
+        assert_eq!(db.cards.total_cards(), 3);
+    }
+
+    #[test]
+    fn test_failed_action_is_reported() {
+        let (mut db, card_id, mut executor, _) = setup();
+        rule(
+            &mut db,
+            TriggerCondition::TagAdded("urgent".to_string()),
+            AutomationAction::SetPriority("someday".to_string()),
+        );
+        let tag = db
+            .cards
+            .create_tag("Urgent".to_string(), "#f00".to_string());
+        db.cards.get_card_mut(&card_id).unwrap().add_tag(tag);
+
+        let report = executor.run(&mut db);
+        assert_eq!(
+            report.errors,
+            vec!["test rule: Unknown priority 'someday'".to_string()]
+        );
+        assert!(!report.changed_board());
+    }
+
+    #[test]
+    fn test_first_run_takes_baseline() {
+        let mut db = BoardDatabase::default();
+        rule(
+            &mut db,
+            TriggerCondition::TaskCreated,
+            AutomationAction::AddTag("new".to_string()),
+        );
+        db.cards.create_card(
+            "Existing".to_string(),
+            String::new(),
+            "To Do".to_string(),
+            Priority::Low,
+            "me".to_string(),
+        );
+
+        let mut executor = AutomationExecutor::with_clock(FakeClock::new(start()));
+        assert!(executor.run(&mut db).is_empty());
+    }
+}
@@ -0,0 +1,839 @@
+//! Persistent board database
+//!
+//! Stores the in-memory task managers (cards, boards, dependencies, comments,
+//! attachments, time entries, projects, git links, automation rules and
+//! calendar sync links) in a repo-local `.toad/` directory so boards survive
+//! restarts.
+//!
+//! # Layout
+//!
+//! ```text
+//! .toad/
+//! ├── board/
+//! │   ├── manifest.json      schema version and save time
+//! │   ├── cards.json         one file per section
+//! │   └── ...
+//! └── snapshots/
+//!     └── <id>.json          full copies of the database
+//! ```
+//!
+//! Every file is written atomically (temp file plus rename). Saves are
+//! incremental: a section file is only rewritten when its content changed
+//! since the last load or save. Older schema versions are upgraded through
+//! [`MIGRATIONS`] when loaded.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use toad::infrastructure::board_store::{BoardDatabase, BoardStore};
+//! use toad::infrastructure::Priority;
+//!
+//! let mut store = BoardStore::open(".");
+//! let mut db = store.load().unwrap();
+//! db.cards.create_card(
+//!     "Fix login".to_string(),
+//!     String::new(),
+//!     "todo".to_string(),
+//!     Priority::High,
+//!     "me".to_string(),
+//! );
+//! store.save(&db).unwrap();
+//! let id = store.create_snapshot(&db, "Before refactor").unwrap();
+//! let restored: BoardDatabase = store.restore_snapshot(&id).unwrap();
+//! ```
+
+use crate::infrastructure::builtin_automation::AutomationManager;
+use crate::infrastructure::calendar_sync::CalendarSyncState;
+use crate::infrastructure::card_comments::CommentManager;
+use crate::infrastructure::file_attachments::AttachmentManager;
+use crate::infrastructure::fs_util::atomic_write;
+use crate::infrastructure::git_card_integration::GitCardIntegrationManager;
+use crate::infrastructure::project_management::ProjectManager;
+use crate::infrastructure::rich_task_cards::RichTaskCardManager;
+use crate::infrastructure::task_dependencies::DependencyManager;
+use crate::infrastructure::time_tracking::TimeTracker;
+use crate::infrastructure::visual_kanban_board::{BoardManager, KanbanColumn};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::collections::hash_map::DefaultHasher;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::io;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// Current schema version of the on-disk database
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// Directory (relative to the workspace) holding the database
+pub const STORE_DIR: &str = ".toad";
+
+/// Schema migrations, applied in order of their `from` version
+pub const MIGRATIONS: &[Migration] = &[];
+
+/// Errors from the board store
+#[derive(Debug, Error)]
+pub enum StoreError {
+    /// Reading or writing a file failed
+    #[error("I/O error: {0}")]
+    Io(#[from] io::Error),
+    /// A file could not be (de)serialized
+    #[error("invalid board data: {0}")]
+    Json(#[from] serde_json::Error),
+    /// The database was written by a newer version of toad
+    #[error("board schema version {found} is newer than supported version {supported}")]
+    UnsupportedVersion {
+        /// Version found on disk
+        found: u32,
+        /// Latest version this build understands
+        supported: u32,
+    },
+    /// A migration step failed or is missing
+    #[error("cannot migrate board schema from version {from}: {message}")]
+    Migration {
+        /// Version the failed step started from
+        from: u32,
+        /// Failure reason
+        message: String,
+    },
+    /// No snapshot with the given ID exists
+    #[error("snapshot not found: {0}")]
+    SnapshotNotFound(String),
+}
+
+/// One schema upgrade step
+///
+/// Operates on the raw sections (section name to JSON value) so it can
+/// rename fields or sections before they are deserialized.
+#[derive(Debug, Clone, Copy)]
+pub struct Migration {
+    /// Version this step upgrades from (to `from + 1`)
+    pub from: u32,
+    /// Human readable summary of the change
+    pub description: &'static str,
+    /// Transformation applied to the sections
+    pub apply: fn(&mut Map<String, Value>) -> Result<(), String>,
+}
+
+/// Upgrade raw sections from `from` to `to` using `migrations`
+///
+/// Returns the number of steps applied.
+pub fn migrate(
+    sections: &mut Map<String, Value>,
+    from: u32,
+    to: u32,
+    migrations: &[Migration],
+) -> Result<usize, StoreError> {
+    if from > to {
+        return Err(StoreError::UnsupportedVersion {
+            found: from,
+            supported: to,
+        });
+    }
+    for version in from..to {
+        let step = migrations
+            .iter()
+            .find(|m| m.from == version)
+            .ok_or_else(|| StoreError::Migration {
+                from: version,
+                message: "no migration step registered".to_string(),
+            })?;
+        (step.apply)(sections).map_err(|message| StoreError::Migration {
+            from: version,
+            message,
+        })?;
+    }
+    Ok((to - from) as usize)
+}
+
+/// All persisted task managers
+///
+/// Each field is stored as its own section file. Sections missing on disk
+/// (e.g. added in a later release) load as empty managers.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BoardDatabase {
+    /// Rich task cards
+    pub cards: RichTaskCardManager,
+    /// Kanban boards, columns, swimlanes and card positions
+    pub boards: BoardManager,
+    /// Task dependencies
+    pub dependencies: DependencyManager,
+    /// Card comments and activity log
+    pub comments: CommentManager,
+    /// Files attached to cards
+    pub attachments: AttachmentManager,
+    /// Time entries and the active timer
+    pub time: TimeTracker,
+    /// Projects and workspaces
+    pub projects: ProjectManager,
+    /// Branches, commits and reviews linked to cards
+    pub git: GitCardIntegrationManager,
+    /// Automation rules, recurring tasks and templates
+    pub automation: AutomationManager,
+    /// Calendar sync source and item links
+    pub calendar: CalendarSyncState,
+}
+
+impl BoardDatabase {
+    /// Serialize into sections (section name to JSON value)
+    ///
+    /// Objects in the result are key-sorted, so equal databases always
+    /// produce identical bytes.
+    pub fn to_sections(&self) -> Result<Map<String, Value>, StoreError> {
+        match serde_json::to_value(self)? {
+            Value::Object(sections) => Ok(sections),
+            _ => unreachable!("BoardDatabase serializes to an object"),
+        }
+    }
+
+    /// Deserialize from sections
+    pub fn from_sections(sections: Map<String, Value>) -> Result<Self, StoreError> {
+        Ok(serde_json::from_value(Value::Object(sections))?)
+    }
+}
+
+/// Columns of the board created by [`BoardDatabase::ensure_board`]
+pub const DEFAULT_COLUMNS: &[&str] = &["To Do", "In Progress", "Review", "Done"];
+
+impl BoardDatabase {
+    /// ID of the first board, creating a default board if there is none
+    pub fn ensure_board(&mut self) -> String {
+        if let Some(board) = self.boards.boards().first() {
+            return board.id.clone();
+        }
+        let board_id = self.boards.create_board(
+            "Agent Work".to_string(),
+            "Work items for this repository".to_string(),
+            "me".to_string(),
+        );
+        for name in DEFAULT_COLUMNS {
+            self.boards.create_column(board_id.clone(), name.to_string());
+        }
+        board_id
+    }
+
+    /// Find a board column by name (ignoring case, spaces and underscores)
+    pub fn column_by_name(&self, board_id: &str, name: &str) -> Option<&KanbanColumn> {
+        let key = column_key(name);
+        self.boards
+            .columns_for_board(board_id)
+            .into_iter()
+            .find(|column| column_key(&column.name) == key)
+    }
+
+    /// Put cards that are not on any board into a board's columns
+    ///
+    /// Each card goes to the column named after its status, or the first
+    /// column. Returns the number of cards placed.
+    pub fn place_cards(&mut self, board_id: &str) -> usize {
+        let Some(first) = self.boards.columns_for_board(board_id).first().map(|c| c.id.clone())
+        else {
+            return 0;
+        };
+        let mut unplaced: Vec<(String, String)> = self
+            .cards
+            .cards()
+            .into_iter()
+            .filter(|card| self.boards.get_card_position(&card.id).is_none())
+            .map(|card| (card.id.clone(), card.status.clone()))
+            .collect();
+        unplaced.sort();
+
+        for (card_id, status) in &unplaced {
+            let column_id = self
+                .column_by_name(board_id, status)
+                .map_or_else(|| first.clone(), |c| c.id.clone());
+            let _ = self.move_card(card_id, &column_id, usize::MAX);
+        }
+        unplaced.len()
+    }
+
+    /// Move a card to a column, keeping its status in sync
+    ///
+    /// `position` is clamped to the end of the column. Moving into a column
+    /// that is already at its WIP limit still succeeds but returns a warning.
+    /// Changing columns is recorded in the card's activity log, with the
+    /// column ID as `column_id` metadata.
+    pub fn move_card(
+        &mut self,
+        card_id: &str,
+        column_id: &str,
+        position: usize,
+    ) -> Result<Option<String>, String> {
+        let column = self
+            .boards
+            .get_column(column_id)
+            .ok_or_else(|| format!("Column {} not found", column_id))?;
+        let column_name = column.name.clone();
+        let changes_column = self
+            .boards
+            .get_card_position(card_id)
+            .is_none_or(|pos| pos.column_id != column_id);
+        let warning = (changes_column && column.would_violate_wip_limit()).then(|| {
+            format!(
+                "WIP limit exceeded in {} ({}/{})",
+                column_name,
+                column.card_count() + 1,
+                column.wip_limit.unwrap_or_default()
+            )
+        });
+
+        self.boards
+            .move_card_to_column(card_id.to_string(), column_id.to_string(), position)?;
+        if changes_column {
+            if let Some(card) = self.cards.get_card_mut(card_id) {
+                card.status = column_name.clone();
+                card.updated_at = Utc::now();
+            }
+            let activity_id = self.comments.log_activity(
+                card_id.to_string(),
+                "card_moved".to_string(),
+                format!("Moved to {}", column_name),
+                None,
+                None,
+            );
+            if let Some(activity) = self.comments.get_activity_mut(&activity_id) {
+                activity.add_metadata("column_id".to_string(), column_id.to_string());
+            }
+        }
+        Ok(warning)
+    }
+
+    /// ID of the board a card is placed on
+    pub fn board_of_card(&self, card_id: &str) -> Option<String> {
+        let column_id = &self.boards.get_card_position(card_id)?.column_id;
+        self.boards
+            .boards()
+            .into_iter()
+            .find(|board| {
+                self.boards
+                    .columns_for_board(&board.id)
+                    .iter()
+                    .any(|c| c.id == *column_id)
+            })
+            .map(|board| board.id.clone())
+    }
+
+    /// Move a card to the end of the column with the given name on its board
+    ///
+    /// A card that is not on a board yet is placed on the first board
+    /// (created if needed) before moving.
+    pub fn move_card_to_named(
+        &mut self,
+        card_id: &str,
+        column_name: &str,
+    ) -> Result<Option<String>, String> {
+        let board_id = match self.board_of_card(card_id) {
+            Some(board_id) => board_id,
+            None => {
+                let board_id = self.ensure_board();
+                self.place_cards(&board_id);
+                board_id
+            }
+        };
+        let column_id = self
+            .column_by_name(&board_id, column_name)
+            .map(|c| c.id.clone())
+            .ok_or_else(|| format!("Board has no {} column", column_name))?;
+        self.move_card(card_id, &column_id, usize::MAX)
+    }
+}
+
+/// Normalized column name for matching statuses to columns
+fn column_key(name: &str) -> String {
+    name.trim()
+        .to_lowercase()
+        .chars()
+        .filter(|c| !matches!(c, ' ' | '_' | '-'))
+        .collect()
+}
+
+/// Contents of `board/manifest.json`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Manifest {
+    schema_version: u32,
+    saved_at: DateTime<Utc>,
+}
+
+/// Metadata of a stored snapshot
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SnapshotInfo {
+    /// Snapshot ID
+    pub id: String,
+    /// Snapshot timestamp
+    pub timestamp: DateTime<Utc>,
+    /// Snapshot message/description
+    pub message: String,
+    /// Parent snapshot ID (for history)
+    pub parent: Option<String>,
+    /// Schema version the snapshot was written with
+    pub schema_version: u32,
+}
+
+/// Contents of a `snapshots/<id>.json` file
+#[derive(Debug, Serialize, Deserialize)]
+struct SnapshotFile {
+    info: SnapshotInfo,
+    sections: Map<String, Value>,
+}
+
+/// Repo-local board database
+#[derive(Debug, Clone)]
+pub struct BoardStore {
+    /// The `.toad/` directory
+    root: PathBuf,
+    /// Content hash of each section as last loaded or saved
+    saved: HashMap<String, u64>,
+}
+
+impl BoardStore {
+    /// Open the store for a workspace (nothing is read until [`load`](Self::load))
+    pub fn open(workspace: impl AsRef<Path>) -> Self {
+        Self {
+            root: workspace.as_ref().join(STORE_DIR),
+            saved: HashMap::new(),
+        }
+    }
+
+    /// The `.toad/` directory
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Whether a database has been saved in this workspace
+    pub fn exists(&self) -> bool {
+        self.manifest_path().exists()
+    }
+
+    fn board_dir(&self) -> PathBuf {
+        self.root.join("board")
+    }
+
+    fn snapshot_dir(&self) -> PathBuf {
+        self.root.join("snapshots")
+    }
+
+    fn manifest_path(&self) -> PathBuf {
+        self.board_dir().join("manifest.json")
+    }
+
+    fn section_path(&self, name: &str) -> PathBuf {
+        self.board_dir().join(format!("{}.json", name))
+    }
+
+    fn snapshot_path(&self, id: &str) -> PathBuf {
+        self.snapshot_dir().join(format!("{}.json", id))
+    }
+
+    /// Load the database, migrating older schemas
+    ///
+    /// Returns an empty database when nothing has been saved yet. After a
+    /// migration every section is considered changed, so the next
+    /// [`save`](Self::save) rewrites the database in the current schema.
+    pub fn load(&mut self) -> Result<BoardDatabase, StoreError> {
+        self.saved.clear();
+        if !self.exists() {
+            return Ok(BoardDatabase::default());
+        }
+
+        let manifest: Manifest = serde_json::from_slice(&fs::read(self.manifest_path())?)?;
+        let mut sections = Map::new();
+        for entry in fs::read_dir(self.board_dir())? {
+            let path = entry?.path();
+            let Some(name) = path.file_stem().and_then(|s| s.to_str()) else {
+                continue;
+            };
+            if name == "manifest" || path.extension().is_none_or(|ext| ext != "json") {
+                continue;
+            }
+            let value: Value = serde_json::from_slice(&fs::read(&path)?)?;
+            sections.insert(name.to_string(), value);
+        }
+
+        let migrated = migrate(
+            &mut sections,
+            manifest.schema_version,
+            SCHEMA_VERSION,
+            MIGRATIONS,
+        )? > 0;
+        let db = BoardDatabase::from_sections(sections)?;
+        if !migrated {
+            self.saved = section_hashes(&db.to_sections()?)?;
+        }
+        Ok(db)
+    }
+
+    /// Save sections that changed since the last load or save
+    ///
+    /// Returns the names of the sections written.
+    pub fn save(&mut self, db: &BoardDatabase) -> Result<Vec<String>, StoreError> {
+        let sections = db.to_sections()?;
+        let hashes = section_hashes(&sections)?;
+        let changed: Vec<String> = sections
+            .keys()
+            .filter(|name| self.saved.get(*name) != hashes.get(*name))
+            .cloned()
+            .collect();
+        if changed.is_empty() && self.exists() {
+            return Ok(changed);
+        }
+
+        fs::create_dir_all(self.board_dir())?;
+        for name in &changed {
+            atomic_write(
+                &self.section_path(name),
+                &serde_json::to_vec_pretty(&sections[name])?,
+            )?;
+            self.saved.insert(name.clone(), hashes[name]);
+        }
+        let manifest = Manifest {
+            schema_version: SCHEMA_VERSION,
+            saved_at: Utc::now(),
+        };
+        atomic_write(
+            &self.manifest_path(),
+            &serde_json::to_vec_pretty(&manifest)?,
+        )?;
+        Ok(changed)
+    }
+
+    /// Store a full copy of the database as a snapshot
+    ///
+    /// The newest existing snapshot becomes the parent. Returns the new ID.
+    pub fn create_snapshot(
+        &mut self,
+        db: &BoardDatabase,
+        message: impl Into<String>,
+    ) -> Result<String, StoreError> {
+        let parent = self.snapshots()?.pop().map(|info| info.id);
+        let timestamp = Utc::now();
+        let id = format!(
+            "{}-{}",
+            timestamp.format("%Y%m%d%H%M%S"),
+            &uuid::Uuid::new_v4().simple().to_string()[..8]
+        );
+        let file = SnapshotFile {
+            info: SnapshotInfo {
+                id: id.clone(),
+                timestamp,
+                message: message.into(),
+                parent,
+                schema_version: SCHEMA_VERSION,
+            },
+            sections: db.to_sections()?,
+        };
+
+        fs::create_dir_all(self.snapshot_dir())?;
+        atomic_write(&self.snapshot_path(&id), &serde_json::to_vec_pretty(&file)?)?;
+        Ok(id)
+    }
+
+    /// List snapshots, oldest first
+    pub fn snapshots(&self) -> Result<Vec<SnapshotInfo>, StoreError> {
+        let dir = self.snapshot_dir();
+        if !dir.exists() {
+            return Ok(Vec::new());
+        }
+        let mut infos = Vec::new();
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.extension().is_none_or(|ext| ext != "json") {
+                continue;
+            }
+            let file: SnapshotFile = serde_json::from_slice(&fs::read(&path)?)?;
+            infos.push(file.info);
+        }
+        infos.sort_by(|a, b| a.timestamp.cmp(&b.timestamp).then(a.id.cmp(&b.id)));
+        Ok(infos)
+    }
+
+    /// Restore a snapshot and make it the current database
+    ///
+    /// The restored database is saved immediately (incrementally, so only
+    /// sections that differ from the current state are rewritten).
+    pub fn restore_snapshot(&mut self, id: &str) -> Result<BoardDatabase, StoreError> {
+        let path = self.snapshot_path(id);
+        if !path.exists() {
+            return Err(StoreError::SnapshotNotFound(id.to_string()));
+        }
+        let mut file: SnapshotFile = serde_json::from_slice(&fs::read(&path)?)?;
+        migrate(
+            &mut file.sections,
+            file.info.schema_version,
+            SCHEMA_VERSION,
+            MIGRATIONS,
+        )?;
+        let db = BoardDatabase::from_sections(file.sections)?;
+        self.save(&db)?;
+        Ok(db)
+    }
+
+    /// Delete a snapshot
+    pub fn delete_snapshot(&mut self, id: &str) -> Result<(), StoreError> {
+        let path = self.snapshot_path(id);
+        if !path.exists() {
+            return Err(StoreError::SnapshotNotFound(id.to_string()));
+        }
+        fs::remove_file(path)?;
+        Ok(())
+    }
+}
+
+/// Content hash of each section's canonical JSON
+fn section_hashes(sections: &Map<String, Value>) -> Result<HashMap<String, u64>, StoreError> {
+    let mut hashes = HashMap::new();
+    for (name, value) in sections {
+        let mut hasher = DefaultHasher::new();
+        serde_json::to_vec(value)?.hash(&mut hasher);
+        hashes.insert(name.clone(), hasher.finish());
+    }
+    Ok(hashes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::rich_task_cards::Priority;
+    use tempfile::TempDir;
+
+    fn sample_db() -> BoardDatabase {
+        let mut db = BoardDatabase::default();
+        db.cards.create_card(
+            "Fix login".to_string(),
+            "Users cannot log in".to_string(),
+            "todo".to_string(),
+            Priority::High,
+            "alice".to_string(),
+        );
+        db.boards.create_board(
+            "Sprint 1".to_string(),
+            "First sprint".to_string(),
+            "alice".to_string(),
+        );
+        db
+    }
+
+    #[test]
+    fn test_load_missing_store_is_empty() {
+        let dir = TempDir::new().unwrap();
+        let mut store = BoardStore::open(dir.path());
+        assert!(!store.exists());
+        let db = store.load().unwrap();
+        assert_eq!(db.cards.total_cards(), 0);
+        assert!(!dir.path().join(STORE_DIR).exists());
+    }
+
+    #[test]
+    fn test_save_and_reload_roundtrip() {
+        let dir = TempDir::new().unwrap();
+        let mut store = BoardStore::open(dir.path());
+        store.save(&sample_db()).unwrap();
+        assert!(store.exists());
+
+        let mut reopened = BoardStore::open(dir.path());
+        let db = reopened.load().unwrap();
+        assert_eq!(db.cards.total_cards(), 1);
+        assert_eq!(db.boards.total_boards(), 1);
+    }
+
+    #[test]
+    fn test_incremental_save_only_writes_changed_sections() {
+        let dir = TempDir::new().unwrap();
+        let mut store = BoardStore::open(dir.path());
+        let mut db = sample_db();
+
+        let first = store.save(&db).unwrap();
+        assert!(first.contains(&"cards".to_string()));
+        assert!(first.contains(&"boards".to_string()));
+        assert!(store.save(&db).unwrap().is_empty());
+
+        assert!(db.time.start_timer("card-1"));
+        assert_eq!(store.save(&db).unwrap(), vec!["time".to_string()]);
+
+        // A freshly loaded store knows what is already on disk
+        let mut reopened = BoardStore::open(dir.path());
+        let db = reopened.load().unwrap();
+        assert!(reopened.save(&db).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_no_temp_files_left_behind() {
+        let dir = TempDir::new().unwrap();
+        let mut store = BoardStore::open(dir.path());
+        store.save(&sample_db()).unwrap();
+        let leftovers = fs::read_dir(store.root().join("board"))
+            .unwrap()
+            .filter(|e| {
+                e.as_ref()
+                    .unwrap()
+                    .file_name()
+                    .to_string_lossy()
+                    .ends_with(".toad-tmp")
+            })
+            .count();
+        assert_eq!(leftovers, 0);
+    }
+
+    #[test]
+    fn test_snapshot_and_restore() {
+        let dir = TempDir::new().unwrap();
+        let mut store = BoardStore::open(dir.path());
+        let mut db = sample_db();
+        store.save(&db).unwrap();
+        let first = store.create_snapshot(&db, "one card").unwrap();
+
+        db.cards.create_card(
+            "Second".to_string(),
+            String::new(),
+            "todo".to_string(),
+            Priority::Low,
+            "bob".to_string(),
+        );
+        store.save(&db).unwrap();
+        let second = store.create_snapshot(&db, "two cards").unwrap();
+
+        let snapshots = store.snapshots().unwrap();
+        assert_eq!(snapshots.len(), 2);
+        assert_eq!(snapshots[1].parent.as_deref(), Some(first.as_str()));
+        assert_eq!(snapshots[1].id, second);
+
+        let restored = store.restore_snapshot(&first).unwrap();
+        assert_eq!(restored.cards.total_cards(), 1);
+        let reloaded = BoardStore::open(dir.path()).load().unwrap();
+        assert_eq!(reloaded.cards.total_cards(), 1);
+
+        store.delete_snapshot(&first).unwrap();
+        assert!(matches!(
+            store.restore_snapshot(&first),
+            Err(StoreError::SnapshotNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_ensure_board_creates_default_columns_once() {
+        let mut db = BoardDatabase::default();
+        let board_id = db.ensure_board();
+        assert_eq!(db.ensure_board(), board_id);
+        let names: Vec<&str> = db
+            .boards
+            .columns_for_board(&board_id)
+            .iter()
+            .map(|c| c.name.as_str())
+            .collect();
+        assert_eq!(names, DEFAULT_COLUMNS);
+    }
+
+    #[test]
+    fn test_place_cards_by_status() {
+        let mut db = sample_db();
+        db.boards = BoardManager::new();
+        let board_id = db.ensure_board();
+        let review = db.cards.create_card(
+            "Review me".to_string(),
+            String::new(),
+            "review".to_string(),
+            Priority::Low,
+            "bob".to_string(),
+        );
+
+        assert_eq!(db.place_cards(&board_id), 2);
+        assert_eq!(db.place_cards(&board_id), 0);
+        let column = db.column_by_name(&board_id, "Review").unwrap();
+        assert_eq!(column.card_ids, vec![review]);
+        let todo = db.column_by_name(&board_id, "to_do").unwrap();
+        assert_eq!(todo.card_count(), 1);
+    }
+
+    #[test]
+    fn test_move_card_syncs_status_and_warns_on_wip() {
+        let mut db = sample_db();
+        db.boards = BoardManager::new();
+        let board_id = db.ensure_board();
+        db.place_cards(&board_id);
+        let doing = db.column_by_name(&board_id, "In Progress").unwrap().id.clone();
+        db.boards.set_wip_limit(&doing, Some(0));
+
+        let warning = db.move_card("card-1", &doing, 0).unwrap();
+        assert!(warning.unwrap().contains("In Progress"));
+        assert_eq!(db.cards.get_card("card-1").unwrap().status, "In Progress");
+        assert_eq!(db.comments.activities_for_card("card-1").len(), 2);
+
+        // Reordering within the same column never warns
+        assert_eq!(db.move_card("card-1", &doing, 0).unwrap(), None);
+        assert!(db.move_card("card-1", "column-99", 0).is_err());
+    }
+
+    #[test]
+    fn test_move_card_to_named_column() {
+        let mut db = sample_db();
+        db.boards = BoardManager::new();
+
+        db.move_card_to_named("card-1", "review").unwrap();
+        let board_id = db.board_of_card("card-1").unwrap();
+        let review = db.column_by_name(&board_id, "Review").unwrap();
+        assert_eq!(review.card_ids, vec!["card-1".to_string()]);
+
+        assert!(db.move_card_to_named("card-1", "Archive").is_err());
+    }
+
+    #[test]
+    fn test_newer_schema_is_rejected() {
+        let dir = TempDir::new().unwrap();
+        let mut store = BoardStore::open(dir.path());
+        store.save(&sample_db()).unwrap();
+        let manifest = serde_json::json!({
+            "schema_version": SCHEMA_VERSION + 1,
+            "saved_at": Utc::now(),
+        });
+        fs::write(store.manifest_path(), manifest.to_string()).unwrap();
+
+        assert!(matches!(
+            store.load(),
+            Err(StoreError::UnsupportedVersion { .. })
+        ));
+    }
+
+    #[test]
+    fn test_migrate_applies_steps_in_order() {
+        fn rename_tasks(sections: &mut Map<String, Value>) -> Result<(), String> {
+            let tasks = sections.remove("tasks").ok_or("missing tasks section")?;
+            sections.insert("cards".to_string(), tasks);
+            Ok(())
+        }
+        fn add_marker(sections: &mut Map<String, Value>) -> Result<(), String> {
+            sections.insert("marker".to_string(), Value::Bool(true));
+            Ok(())
+        }
+        let migrations = [
+            Migration {
+                from: 1,
+                description: "add marker",
+                apply: add_marker,
+            },
+            Migration {
+                from: 0,
+                description: "rename tasks to cards",
+                apply: rename_tasks,
+            },
+        ];
+
+        let mut sections = Map::new();
+        sections.insert("tasks".to_string(), Value::Null);
+        assert_eq!(migrate(&mut sections, 0, 2, &migrations).unwrap(), 2);
+        assert!(sections.contains_key("cards"));
+        assert!(sections.contains_key("marker"));
+
+        let mut broken = Map::new();
+        assert!(matches!(
+            migrate(&mut broken, 0, 1, &migrations),
+            Err(StoreError::Migration { from: 0, .. })
+        ));
+        assert!(matches!(
+            migrate(&mut Map::new(), 0, 3, &migrations),
+            Err(StoreError::Migration { .. })
+        ));
+    }
+}
@@ -168,7 +168,12 @@ impl AutomationRule {
 
     /// Record execution
     pub fn record_execution(&mut self) {
-        self.last_executed = Some(Utc::now());
+        self.record_execution_at(Utc::now());
+    }
+
+    /// Record execution at a given time
+    pub fn record_execution_at(&mut self, at: DateTime<Utc>) {
+        self.last_executed = Some(at);
         self.execution_count += 1;
     }
 
@@ -285,19 +290,29 @@ impl RecurringTask {
 
     /// Check if task should be created now
     pub fn should_create(&self) -> bool {
-        self.enabled && Utc::now() >= self.next_occurrence
+        self.should_create_at(Utc::now())
+    }
+
+    /// Check if task should be created at a given time
+    pub fn should_create_at(&self, now: DateTime<Utc>) -> bool {
+        self.enabled && now >= self.next_occurrence
     }
 
     /// Calculate next occurrence
     pub fn calculate_next_occurrence(&mut self) {
-        self.last_created = Some(Utc::now());
+        self.calculate_next_occurrence_at(Utc::now());
+    }
+
+    /// Calculate next occurrence after a task was created at `now`
+    pub fn calculate_next_occurrence_at(&mut self, now: DateTime<Utc>) {
+        self.last_created = Some(now);
 
         self.next_occurrence = match self.pattern {
-            RecurrencePattern::Daily => Utc::now() + Duration::days(1),
-            RecurrencePattern::EveryNDays(n) => Utc::now() + Duration::days(n as i64),
-            RecurrencePattern::Weekly(_) => Utc::now() + Duration::weeks(1),
-            RecurrencePattern::Monthly(_) => Utc::now() + Duration::days(30),
-            RecurrencePattern::Yearly(_, _) => Utc::now() + Duration::days(365),
+            RecurrencePattern::Daily => now + Duration::days(1),
+            RecurrencePattern::EveryNDays(n) => now + Duration::days(n as i64),
+            RecurrencePattern::Weekly(_) => now + Duration::weeks(1),
+            RecurrencePattern::Monthly(_) => now + Duration::days(30),
+            RecurrencePattern::Yearly(_, _) => now + Duration::days(365),
         };
     }
 }
@@ -434,7 +449,8 @@ impl BulkActionResult {
 }
 
 /// Automation manager
-#[derive(Debug)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct AutomationManager {
     /// Automation rules
     rules: HashMap<String, AutomationRule>,
@@ -525,16 +541,26 @@ impl AutomationManager {
 
     /// Get recurring tasks that should be created now
     pub fn get_due_recurring_tasks(&self) -> Vec<&RecurringTask> {
+        self.get_due_recurring_tasks_at(Utc::now())
+    }
+
+    /// Get recurring tasks that should be created at a given time
+    pub fn get_due_recurring_tasks_at(&self, now: DateTime<Utc>) -> Vec<&RecurringTask> {
         self.recurring_tasks
             .values()
-            .filter(|t| t.should_create())
+            .filter(|t| t.should_create_at(now))
             .collect()
     }
 
     /// Mark recurring task as created
     pub fn mark_recurring_task_created(&mut self, task_id: &str) {
+        self.mark_recurring_task_created_at(task_id, Utc::now());
+    }
+
+    /// Mark recurring task as created at a given time
+    pub fn mark_recurring_task_created_at(&mut self, task_id: &str, now: DateTime<Utc>) {
         if let Some(task) = self.recurring_tasks.get_mut(task_id) {
-            task.calculate_next_occurrence();
+            task.calculate_next_occurrence_at(now);
         }
     }
 
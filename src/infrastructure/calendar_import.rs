@@ -0,0 +1,1476 @@
+//! iCalendar (.ics) import
+//!
+//! Parses `VEVENT` and `VTODO` components of RFC 5545 calendars into
+//! [`CalendarEvent`]s, the same type [`CalendarExporter`] writes, so a file
+//! exported by toad parses back into equivalent events.
+//!
+//! # Architecture
+//!
+//! - Content lines are unfolded and split into a property name, parameters
+//!   and a value, then nested into components by `BEGIN`/`END`
+//! - `VTIMEZONE` components become [`TimeZoneDef`]s; date-times carrying a
+//!   `TZID` are resolved to UTC through them (unknown zones fall back to UTC
+//!   with a warning)
+//! - `RRULE`s parse into [`RecurrenceRule`], which expands occurrences in
+//!   local wall-clock time so recurring items keep their hour across DST
+//!   changes
+//! - Problems with single components are collected as warnings instead of
+//!   failing the whole file
+//!
+//! # Examples
+//!
+//! ```
+//! use toad::infrastructure::calendar_import::parse_ics;
+//!
+//! let ics = "BEGIN:VCALENDAR\r\n\
+//!            BEGIN:VTODO\r\n\
+//!            UID:rent@example.com\r\n\
+//!            SUMMARY:Pay rent\r\n\
+//!            DUE;VALUE=DATE:20250301\r\n\
+//!            RRULE:FREQ=MONTHLY\r\n\
+//!            END:VTODO\r\n\
+//!            END:VCALENDAR\r\n";
+//!
+//! let calendar = parse_ics(ics).unwrap();
+//! assert_eq!(calendar.events[0].summary, "Pay rent");
+//! assert!(calendar.events[0].all_day);
+//! ```
+//!
+//! [`CalendarExporter`]: crate::infrastructure::calendar_integration::CalendarExporter
+
+use crate::infrastructure::calendar_integration::{
+    CalendarComponent, CalendarEvent, EventPriority, EventStatus, Recurrence,
+};
+use chrono::{
+    DateTime, Datelike, Days, Duration, Months, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc,
+    Weekday,
+};
+use std::collections::{HashMap, VecDeque};
+use thiserror::Error;
+
+/// Upper bound on recurrence periods expanded for a single rule
+const MAX_RECURRENCE_PERIODS: u32 = 50_000;
+
+/// Time zone identifiers that always mean UTC
+const UTC_ZONE_IDS: &[&str] = &["UTC", "Etc/UTC", "GMT", "Etc/GMT", "Z", "Universal"];
+
+/// Errors that make an `.ics` file unreadable as a whole
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum IcsError {
+    /// Malformed content line or unbalanced `BEGIN`/`END`
+    #[error("line {line}: {message}")]
+    Syntax {
+        /// Line number (1-based) of the offending content line
+        line: usize,
+        /// Failure reason
+        message: String,
+    },
+    /// The input has no `VCALENDAR` component
+    #[error("no VCALENDAR component found")]
+    NotACalendar,
+}
+
+/// A parsed calendar file
+#[derive(Debug, Clone, Default)]
+pub struct IcsCalendar {
+    /// Calendar name (`X-WR-CALNAME`), if any
+    pub name: Option<String>,
+    /// Events and to-dos in file order
+    pub events: Vec<CalendarEvent>,
+    /// Time zones defined in the file, by TZID
+    pub time_zones: HashMap<String, TimeZoneDef>,
+    /// Non-fatal problems found while parsing
+    pub warnings: Vec<String>,
+    /// Recurrence details per UID that do not fit on [`CalendarEvent`]
+    series: HashMap<String, SeriesInfo>,
+}
+
+/// Time zone and exceptions of a (possibly recurring) item
+#[derive(Debug, Clone, Default)]
+struct SeriesInfo {
+    /// TZID the item's scheduled time was written in
+    tzid: Option<String>,
+    /// Excluded occurrences (`EXDATE`)
+    exdates: Vec<DateTime<Utc>>,
+}
+
+impl IcsCalendar {
+    /// First occurrence of an item's scheduled time strictly after `after`
+    ///
+    /// The scheduled time is `DUE` for to-dos and `DTSTART` for events.
+    /// Non-recurring items have a single occurrence; recurring ones are
+    /// expanded in the time zone they were written in, skipping `EXDATE`s.
+    pub fn occurrence_after(
+        &self,
+        event: &CalendarEvent,
+        after: DateTime<Utc>,
+    ) -> Option<DateTime<Utc>> {
+        let scheduled = event.scheduled_at();
+        let rule = event
+            .recurrence
+            .to_rrule()
+            .and_then(|rule| RecurrenceRule::parse(&rule).ok());
+        let Some(rule) = rule else {
+            return (scheduled > after).then_some(scheduled);
+        };
+
+        let series = self.series.get(&event.uid);
+        let zone = series
+            .and_then(|info| info.tzid.as_deref())
+            .and_then(|tzid| self.time_zones.get(tzid));
+        let exdates = series.map(|info| info.exdates.as_slice()).unwrap_or(&[]);
+        let to_utc = |local: NaiveDateTime| match zone {
+            Some(zone) => zone.to_utc(local),
+            None => Utc.from_utc_datetime(&local),
+        };
+        let until = rule.until.map(|until| match until {
+            Until::Utc(utc) => match zone {
+                Some(zone) => zone.to_local(utc),
+                None => utc.naive_utc(),
+            },
+            Until::Local(local) => local,
+        });
+        let start = match zone {
+            Some(zone) => zone.to_local(scheduled),
+            None => scheduled.naive_utc(),
+        };
+
+        rule.occurrences(start, until)
+            .map(to_utc)
+            .filter(|occurrence| !exdates.contains(occurrence))
+            .find(|occurrence| *occurrence > after)
+    }
+
+    /// TZID an item's scheduled time was written in, if any
+    pub fn time_zone_of(&self, uid: &str) -> Option<&str> {
+        self.series.get(uid).and_then(|info| info.tzid.as_deref())
+    }
+}
+
+/// A `VTIMEZONE` definition
+///
+/// Holds the `STANDARD`/`DAYLIGHT` observances of the zone; the offset in
+/// effect at a local time is the one of the observance with the latest
+/// onset at or before it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TimeZoneDef {
+    /// Zone identifier (TZID)
+    pub id: String,
+    observances: Vec<Observance>,
+}
+
+/// One `STANDARD` or `DAYLIGHT` block of a time zone
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Observance {
+    /// First onset (local time)
+    start: NaiveDateTime,
+    /// UTC offset before the onset, in seconds
+    offset_from: i32,
+    /// UTC offset from the onset on, in seconds
+    offset_to: i32,
+    /// Rule for later onsets
+    rule: Option<RecurrenceRule>,
+    /// Additional onsets (`RDATE`)
+    rdates: Vec<NaiveDateTime>,
+}
+
+impl Observance {
+    /// Latest onset at or before a local time
+    fn last_onset_before(&self, local: NaiveDateTime) -> Option<NaiveDateTime> {
+        if self.start > local {
+            return None;
+        }
+        let mut latest = self.start;
+        if let Some(rule) = &self.rule {
+            let until = rule.until.map(|until| match until {
+                Until::Utc(utc) => utc.naive_utc() + Duration::seconds(self.offset_from.into()),
+                Until::Local(local) => local,
+            });
+            for onset in rule.occurrences(self.start, until) {
+                if onset > local {
+                    break;
+                }
+                latest = onset;
+            }
+        }
+        for rdate in &self.rdates {
+            if *rdate <= local && *rdate > latest {
+                latest = *rdate;
+            }
+        }
+        Some(latest)
+    }
+}
+
+impl TimeZoneDef {
+    /// Create a zone with a constant UTC offset (in seconds)
+    pub fn fixed(id: impl Into<String>, offset: i32) -> Self {
+        Self {
+            id: id.into(),
+            observances: vec![Observance {
+                start: NaiveDate::MIN.and_time(NaiveTime::MIN),
+                offset_from: offset,
+                offset_to: offset,
+                rule: None,
+                rdates: Vec::new(),
+            }],
+        }
+    }
+
+    /// UTC offset (in seconds) in effect at a local wall-clock time
+    pub fn offset_at(&self, local: NaiveDateTime) -> i32 {
+        self.observances
+            .iter()
+            .filter_map(|obs| {
+                obs.last_onset_before(local)
+                    .map(|onset| (onset, obs.offset_to))
+            })
+            .max_by_key(|(onset, _)| *onset)
+            .map(|(_, offset)| offset)
+            .or_else(|| {
+                self.observances
+                    .iter()
+                    .min_by_key(|obs| obs.start)
+                    .map(|obs| obs.offset_from)
+            })
+            .unwrap_or(0)
+    }
+
+    /// Convert a local wall-clock time in this zone to UTC
+    pub fn to_utc(&self, local: NaiveDateTime) -> DateTime<Utc> {
+        let offset = self.offset_at(local);
+        Utc.from_utc_datetime(&(local - Duration::seconds(offset.into())))
+    }
+
+    /// Convert a UTC time to local wall-clock time in this zone
+    pub fn to_local(&self, utc: DateTime<Utc>) -> NaiveDateTime {
+        let naive = utc.naive_utc();
+        self.observances
+            .iter()
+            .map(|obs| naive + Duration::seconds(obs.offset_to.into()))
+            .find(|local| self.to_utc(*local) == utc)
+            .unwrap_or_else(|| naive + Duration::seconds(self.offset_at(naive).into()))
+    }
+}
+
+/// Recurrence frequency (`FREQ`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Frequency {
+    /// Every day
+    Daily,
+    /// Every week
+    Weekly,
+    /// Every month
+    Monthly,
+    /// Every year
+    Yearly,
+}
+
+/// End of a recurrence (`UNTIL`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Until {
+    /// An absolute instant (`UNTIL` with a `Z` suffix)
+    Utc(DateTime<Utc>),
+    /// A local time or the end of a local date
+    Local(NaiveDateTime),
+}
+
+/// A parsed `RRULE`
+///
+/// Supports `FREQ` (daily to yearly), `INTERVAL`, `COUNT`, `UNTIL`, `BYDAY`
+/// (with ordinals such as `-1SU`), `BYMONTHDAY` and `BYMONTH`. Rules using
+/// other `BY*` parts are rejected rather than expanded incorrectly.
+///
+/// # Examples
+///
+/// ```
+/// use chrono::{Datelike, NaiveDate};
+/// use toad::infrastructure::calendar_import::RecurrenceRule;
+///
+/// let rule = RecurrenceRule::parse("FREQ=MONTHLY;BYDAY=-1FR;COUNT=3").unwrap();
+/// let start = NaiveDate::from_ymd_opt(2025, 1, 31).unwrap().and_hms_opt(9, 0, 0).unwrap();
+/// let days: Vec<u32> = rule.occurrences(start, None).map(|d| d.day()).collect();
+/// assert_eq!(days, vec![31, 28, 28]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecurrenceRule {
+    /// Frequency
+    pub freq: Frequency,
+    /// Interval between periods (at least 1)
+    pub interval: u32,
+    /// Total number of occurrences, including the first
+    pub count: Option<u32>,
+    /// Last possible occurrence
+    pub until: Option<Until>,
+    /// Weekdays, with an optional ordinal within the month or year
+    pub by_day: Vec<(Option<i32>, Weekday)>,
+    /// Days of the month (negative counts from the end)
+    pub by_month_day: Vec<i32>,
+    /// Months (1-12)
+    pub by_month: Vec<u32>,
+}
+
+impl RecurrenceRule {
+    /// Parse an `RRULE` value such as `FREQ=WEEKLY;BYDAY=MO,WE`
+    pub fn parse(rule: &str) -> Result<Self, String> {
+        let mut freq = None;
+        let mut parsed = Self {
+            freq: Frequency::Daily,
+            interval: 1,
+            count: None,
+            until: None,
+            by_day: Vec::new(),
+            by_month_day: Vec::new(),
+            by_month: Vec::new(),
+        };
+
+        for part in rule.trim().split(';').filter(|part| !part.is_empty()) {
+            let (key, value) = part
+                .split_once('=')
+                .ok_or_else(|| format!("malformed rule part '{}'", part))?;
+            let value = value.trim();
+            match key.trim().to_ascii_uppercase().as_str() {
+                "FREQ" => {
+                    freq = Some(match value.to_ascii_uppercase().as_str() {
+                        "DAILY" => Frequency::Daily,
+                        "WEEKLY" => Frequency::Weekly,
+                        "MONTHLY" => Frequency::Monthly,
+                        "YEARLY" => Frequency::Yearly,
+                        other => return Err(format!("unsupported frequency {}", other)),
+                    })
+                }
+                "INTERVAL" => {
+                    parsed.interval = value
+                        .parse()
+                        .ok()
+                        .filter(|interval| *interval > 0)
+                        .ok_or_else(|| format!("invalid interval '{}'", value))?
+                }
+                "COUNT" => {
+                    parsed.count = Some(
+                        value
+                            .parse()
+                            .map_err(|_| format!("invalid count '{}'", value))?,
+                    )
+                }
+                "UNTIL" => parsed.until = Some(parse_until(value)?),
+                "BYDAY" => {
+                    parsed.by_day = value
+                        .split(',')
+                        .map(parse_by_day)
+                        .collect::<Result<_, _>>()?
+                }
+                "BYMONTHDAY" => {
+                    parsed.by_month_day = parse_numbers(value, -31..=31)?
+                        .into_iter()
+                        .filter(|day| *day != 0)
+                        .collect()
+                }
+                "BYMONTH" => {
+                    parsed.by_month = parse_numbers(value, 1..=12)?
+                        .into_iter()
+                        .map(|month| month as u32)
+                        .collect()
+                }
+                "WKST" => {}
+                other => return Err(format!("unsupported rule part {}", other)),
+            }
+        }
+
+        parsed.freq = freq.ok_or("rule has no FREQ")?;
+        Ok(parsed)
+    }
+
+    /// Occurrences in local wall-clock time, starting with `start` itself
+    ///
+    /// `until` overrides the rule's own `UNTIL`, which callers resolve into
+    /// the start's time zone first.
+    pub fn occurrences(
+        &self,
+        start: NaiveDateTime,
+        until: Option<NaiveDateTime>,
+    ) -> Occurrences<'_> {
+        let until = until.or(match self.until {
+            Some(Until::Local(local)) => Some(local),
+            Some(Until::Utc(utc)) => Some(utc.naive_utc()),
+            None => None,
+        });
+        Occurrences {
+            rule: self,
+            start,
+            until,
+            period: 0,
+            pending: VecDeque::from([start]),
+            emitted: 0,
+            done: false,
+        }
+    }
+
+    /// Candidate dates of the `period`-th period after `start`
+    fn period_dates(&self, start: NaiveDate, period: u32) -> Option<Vec<NaiveDate>> {
+        let steps = period.checked_mul(self.interval)?;
+        let dates = match self.freq {
+            Frequency::Daily => {
+                let day = start.checked_add_days(Days::new(steps.into()))?;
+                let weekday_ok = self.by_day.is_empty()
+                    || self.by_day.iter().any(|(_, wd)| *wd == day.weekday());
+                let month_day_ok = self.by_month_day.is_empty()
+                    || self
+                        .by_month_day
+                        .iter()
+                        .any(|n| resolve_month_day(day.year(), day.month(), *n) == Some(day));
+                if weekday_ok && month_day_ok {
+                    vec![day]
+                } else {
+                    Vec::new()
+                }
+            }
+            Frequency::Weekly => {
+                let offset = u64::from(steps) * 7;
+                if self.by_day.is_empty() {
+                    vec![start.checked_add_days(Days::new(offset))?]
+                } else {
+                    let monday = start
+                        .checked_sub_days(Days::new(start.weekday().num_days_from_monday().into()))?
+                        .checked_add_days(Days::new(offset))?;
+                    self.by_day
+                        .iter()
+                        .filter_map(|(_, wd)| {
+                            monday.checked_add_days(Days::new(wd.num_days_from_monday().into()))
+                        })
+                        .collect()
+                }
+            }
+            Frequency::Monthly => {
+                let month = start.with_day(1)?.checked_add_months(Months::new(steps))?;
+                self.month_dates(month.year(), month.month(), start.day())
+            }
+            Frequency::Yearly => {
+                let year = start.year().checked_add(i32::try_from(steps).ok()?)?;
+                if !self.by_month.is_empty() {
+                    self.by_month
+                        .iter()
+                        .flat_map(|month| self.month_dates(year, *month, start.day()))
+                        .collect()
+                } else if !self.by_day.is_empty() && self.by_month_day.is_empty() {
+                    let first = NaiveDate::from_ymd_opt(year, 1, 1)?;
+                    let last = NaiveDate::from_ymd_opt(year, 12, 31)?;
+                    select_weekdays(first.iter_days().take_while(|d| *d <= last), &self.by_day)
+                } else {
+                    self.month_dates(year, start.month(), start.day())
+                }
+            }
+        };
+
+        Some(
+            dates
+                .into_iter()
+                .filter(|date| self.by_month.is_empty() || self.by_month.contains(&date.month()))
+                .collect(),
+        )
+    }
+
+    /// Dates within one month selected by `BYMONTHDAY`/`BYDAY`
+    fn month_dates(&self, year: i32, month: u32, default_day: u32) -> Vec<NaiveDate> {
+        if !self.by_month_day.is_empty() {
+            return self
+                .by_month_day
+                .iter()
+                .filter_map(|n| resolve_month_day(year, month, *n))
+                .filter(|date| {
+                    self.by_day.is_empty()
+                        || self.by_day.iter().any(|(_, wd)| *wd == date.weekday())
+                })
+                .collect();
+        }
+        if !self.by_day.is_empty() {
+            let Some(first) = NaiveDate::from_ymd_opt(year, month, 1) else {
+                return Vec::new();
+            };
+            return select_weekdays(
+                first.iter_days().take_while(|d| d.month() == month),
+                &self.by_day,
+            );
+        }
+        NaiveDate::from_ymd_opt(year, month, default_day)
+            .into_iter()
+            .collect()
+    }
+}
+
+/// Iterator over the occurrences of a [`RecurrenceRule`]
+#[derive(Debug)]
+pub struct Occurrences<'a> {
+    rule: &'a RecurrenceRule,
+    start: NaiveDateTime,
+    until: Option<NaiveDateTime>,
+    period: u32,
+    pending: VecDeque<NaiveDateTime>,
+    emitted: u32,
+    done: bool,
+}
+
+impl Iterator for Occurrences<'_> {
+    type Item = NaiveDateTime;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.done {
+            if let Some(next) = self.pending.pop_front() {
+                let count_reached = self.rule.count.is_some_and(|count| self.emitted >= count);
+                let past_until = self.until.is_some_and(|until| next > until);
+                if count_reached || past_until {
+                    self.done = true;
+                    return None;
+                }
+                self.emitted += 1;
+                return Some(next);
+            }
+
+            if self.period >= MAX_RECURRENCE_PERIODS {
+                self.done = true;
+                break;
+            }
+            let Some(dates) = self.rule.period_dates(self.start.date(), self.period) else {
+                self.done = true;
+                break;
+            };
+            self.period += 1;
+
+            let mut candidates: Vec<NaiveDateTime> = dates
+                .into_iter()
+                .map(|date| date.and_time(self.start.time()))
+                .filter(|candidate| *candidate > self.start)
+                .collect();
+            candidates.sort();
+            candidates.dedup();
+            self.pending.extend(candidates);
+        }
+        None
+    }
+}
+
+/// Parse an iCalendar document
+///
+/// Fails only when the text is not structurally a calendar; problems with
+/// individual components end up in [`IcsCalendar::warnings`].
+pub fn parse_ics(input: &str) -> Result<IcsCalendar, IcsError> {
+    let roots = parse_components(input)?;
+    let calendars: Vec<&Component> = roots.iter().filter(|c| c.name == "VCALENDAR").collect();
+    if calendars.is_empty() {
+        return Err(IcsError::NotACalendar);
+    }
+
+    let mut result = IcsCalendar::default();
+    for calendar in calendars {
+        if result.name.is_none() {
+            result.name = calendar
+                .property("X-WR-CALNAME")
+                .map(|p| unescape_text(&p.value));
+        }
+        for zone in calendar.children.iter().filter(|c| c.name == "VTIMEZONE") {
+            match parse_time_zone(zone) {
+                Ok(def) => {
+                    result.time_zones.insert(def.id.clone(), def);
+                }
+                Err(message) => result.warnings.push(format!("VTIMEZONE: {}", message)),
+            }
+        }
+        for item in &calendar.children {
+            let kind = match item.name.as_str() {
+                "VEVENT" => CalendarComponent::Event,
+                "VTODO" => CalendarComponent::Todo,
+                _ => continue,
+            };
+            match parse_item(item, kind, &result.time_zones, &mut result.warnings) {
+                Ok(Some((event, series))) => {
+                    if result.series.contains_key(&event.uid) {
+                        result
+                            .warnings
+                            .push(format!("{}: duplicate UID ignored", event.uid));
+                        continue;
+                    }
+                    result.series.insert(event.uid.clone(), series);
+                    result.events.push(event);
+                }
+                Ok(None) => {}
+                Err(message) => result.warnings.push(format!("{}: {}", item.name, message)),
+            }
+        }
+    }
+    Ok(result)
+}
+
+/// A content line: `NAME;PARAM=VALUE:value`
+#[derive(Debug, Clone)]
+struct Property {
+    name: String,
+    params: Vec<(String, String)>,
+    value: String,
+}
+
+impl Property {
+    fn param(&self, name: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// A `BEGIN:NAME` ... `END:NAME` block
+#[derive(Debug, Clone, Default)]
+struct Component {
+    name: String,
+    properties: Vec<Property>,
+    children: Vec<Component>,
+}
+
+impl Component {
+    fn property(&self, name: &str) -> Option<&Property> {
+        self.properties.iter().find(|p| p.name == name)
+    }
+
+    fn properties<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Property> {
+        self.properties.iter().filter(move |p| p.name == name)
+    }
+}
+
+/// Unfold content lines, keeping the line number each one starts on
+pub(crate) fn unfold_lines(input: &str) -> Vec<(usize, String)> {
+    let mut lines: Vec<(usize, String)> = Vec::new();
+    for (index, raw) in input.split('\n').enumerate() {
+        let raw = raw.strip_suffix('\r').unwrap_or(raw);
+        if let Some(continuation) = raw.strip_prefix([' ', '\t'])
+            && let Some((_, last)) = lines.last_mut()
+        {
+            last.push_str(continuation);
+            continue;
+        }
+        if !raw.trim().is_empty() {
+            lines.push((index + 1, raw.to_string()));
+        }
+    }
+    lines
+}
+
+/// Nest unfolded content lines into components
+fn parse_components(input: &str) -> Result<Vec<Component>, IcsError> {
+    let lines = unfold_lines(input);
+
+    let mut roots = Vec::new();
+    let mut stack: Vec<Component> = Vec::new();
+    for (line, text) in lines {
+        let property =
+            parse_property(&text).map_err(|message| IcsError::Syntax { line, message })?;
+        match property.name.as_str() {
+            "BEGIN" => stack.push(Component {
+                name: property.value.trim().to_ascii_uppercase(),
+                ..Component::default()
+            }),
+            "END" => {
+                let name = property.value.trim().to_ascii_uppercase();
+                let component =
+                    stack
+                        .pop()
+                        .filter(|c| c.name == name)
+                        .ok_or_else(|| IcsError::Syntax {
+                            line,
+                            message: format!("unexpected END:{}", name),
+                        })?;
+                match stack.last_mut() {
+                    Some(parent) => parent.children.push(component),
+                    None => roots.push(component),
+                }
+            }
+            _ => match stack.last_mut() {
+                Some(component) => component.properties.push(property),
+                None => {
+                    return Err(IcsError::Syntax {
+                        line,
+                        message: format!("property {} outside of a component", property.name),
+                    });
+                }
+            },
+        }
+    }
+
+    if let Some(open) = stack.last() {
+        return Err(IcsError::Syntax {
+            line: input.lines().count(),
+            message: format!("missing END:{}", open.name),
+        });
+    }
+    Ok(roots)
+}
+
+/// Split a content line into name, parameters and value
+fn parse_property(line: &str) -> Result<Property, String> {
+    let mut in_quotes = false;
+    let mut segments = Vec::new();
+    let mut current = String::new();
+    let mut value = None;
+    for (index, ch) in line.char_indices() {
+        match ch {
+            '"' => {
+                in_quotes = !in_quotes;
+                current.push(ch);
+            }
+            ';' if !in_quotes => segments.push(std::mem::take(&mut current)),
+            ':' if !in_quotes => {
+                segments.push(std::mem::take(&mut current));
+                value = Some(line[index + 1..].to_string());
+                break;
+            }
+            _ => current.push(ch),
+        }
+    }
+    let value = value.ok_or_else(|| format!("missing ':' in '{}'", line))?;
+
+    let mut segments = segments.into_iter();
+    let name = segments
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_uppercase();
+    if name.is_empty() {
+        return Err(format!("missing property name in '{}'", line));
+    }
+    let params = segments
+        .map(|param| {
+            let (key, value) = param.split_once('=').unwrap_or((param.as_str(), ""));
+            (
+                key.trim().to_ascii_uppercase(),
+                value.trim().trim_matches('"').to_string(),
+            )
+        })
+        .collect();
+
+    Ok(Property {
+        name,
+        params,
+        value,
+    })
+}
+
+/// Build a time zone from a `VTIMEZONE` component
+fn parse_time_zone(zone: &Component) -> Result<TimeZoneDef, String> {
+    let id = zone
+        .property("TZID")
+        .map(|p| p.value.trim().to_string())
+        .ok_or("missing TZID")?;
+
+    let mut observances = Vec::new();
+    for block in zone
+        .children
+        .iter()
+        .filter(|c| c.name == "STANDARD" || c.name == "DAYLIGHT")
+    {
+        let offset = |name: &str| {
+            block
+                .property(name)
+                .and_then(|p| parse_utc_offset(&p.value))
+                .ok_or_else(|| format!("{} {}: missing or invalid {}", id, block.name, name))
+        };
+        let start = block
+            .property("DTSTART")
+            .and_then(|p| parse_naive(&p.value))
+            .ok_or_else(|| format!("{} {}: missing or invalid DTSTART", id, block.name))?;
+        let rule = match block.property("RRULE") {
+            Some(p) => Some(
+                RecurrenceRule::parse(&p.value)
+                    .map_err(|message| format!("{} {}: {}", id, block.name, message))?,
+            ),
+            None => None,
+        };
+        let rdates = block
+            .properties("RDATE")
+            .flat_map(|p| {
+                p.value
+                    .split(',')
+                    .filter_map(parse_naive)
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        observances.push(Observance {
+            start,
+            offset_from: offset("TZOFFSETFROM")?,
+            offset_to: offset("TZOFFSETTO")?,
+            rule,
+            rdates,
+        });
+    }
+
+    if observances.is_empty() {
+        return Err(format!("{}: no STANDARD or DAYLIGHT observance", id));
+    }
+    Ok(TimeZoneDef { id, observances })
+}
+
+/// A date-time property resolved to UTC
+struct ResolvedTime {
+    utc: DateTime<Utc>,
+    all_day: bool,
+    tzid: Option<String>,
+}
+
+/// Resolve a date or date-time property, honouring `VALUE=DATE` and `TZID`
+fn resolve_time(
+    property: &Property,
+    zones: &HashMap<String, TimeZoneDef>,
+    warnings: &mut Vec<String>,
+) -> Result<ResolvedTime, String> {
+    let value = property.value.trim();
+    resolve_value(value, property, zones, warnings)
+        .ok_or_else(|| format!("invalid {} value '{}'", property.name, value))
+}
+
+fn resolve_value(
+    value: &str,
+    property: &Property,
+    zones: &HashMap<String, TimeZoneDef>,
+    warnings: &mut Vec<String>,
+) -> Option<ResolvedTime> {
+    if property.param("VALUE") == Some("DATE") || value.len() == 8 {
+        let date = NaiveDate::parse_from_str(value, "%Y%m%d").ok()?;
+        return Some(ResolvedTime {
+            utc: Utc.from_utc_datetime(&date.and_time(NaiveTime::MIN)),
+            all_day: true,
+            tzid: None,
+        });
+    }
+
+    if let Some(utc) = value.strip_suffix(['Z', 'z']) {
+        let naive = parse_naive(utc)?;
+        return Some(ResolvedTime {
+            utc: Utc.from_utc_datetime(&naive),
+            all_day: false,
+            tzid: None,
+        });
+    }
+
+    let naive = parse_naive(value)?;
+    let tzid = property
+        .param("TZID")
+        .map(|tzid| tzid.trim_start_matches('/').to_string());
+    let utc = match tzid.as_deref() {
+        Some(id) if UTC_ZONE_IDS.iter().any(|utc| utc.eq_ignore_ascii_case(id)) => {
+            Utc.from_utc_datetime(&naive)
+        }
+        Some(id) => match zones.get(id) {
+            Some(zone) => zone.to_utc(naive),
+            None => {
+                let warning = format!("unknown time zone '{}', treating times as UTC", id);
+                if !warnings.contains(&warning) {
+                    warnings.push(warning);
+                }
+                Utc.from_utc_datetime(&naive)
+            }
+        },
+        // Floating times have no zone; read them as UTC
+        None => Utc.from_utc_datetime(&naive),
+    };
+    Some(ResolvedTime {
+        utc,
+        all_day: false,
+        tzid: tzid.filter(|id| zones.contains_key(id)),
+    })
+}
+
+/// Convert a `VEVENT`/`VTODO` into an event
+///
+/// Returns `Ok(None)` for components that are deliberately skipped.
+fn parse_item(
+    item: &Component,
+    kind: CalendarComponent,
+    zones: &HashMap<String, TimeZoneDef>,
+    warnings: &mut Vec<String>,
+) -> Result<Option<(CalendarEvent, SeriesInfo)>, String> {
+    let uid = item
+        .property("UID")
+        .map(|p| p.value.trim().to_string())
+        .filter(|uid| !uid.is_empty())
+        .ok_or("missing UID")?;
+    if item.property("RECURRENCE-ID").is_some() {
+        warnings.push(format!(
+            "{}: modified instance of a recurring item ignored",
+            uid
+        ));
+        return Ok(None);
+    }
+
+    let time = |name: &str, warnings: &mut Vec<String>| {
+        item.property(name)
+            .map(|p| resolve_time(p, zones, warnings))
+            .transpose()
+    };
+    let dtstart = time("DTSTART", warnings)?;
+    let due = time("DUE", warnings)?;
+    let dtend = time("DTEND", warnings)?;
+    let duration = match item.property("DURATION") {
+        Some(p) => Some(
+            parse_duration(&p.value).ok_or_else(|| format!("invalid DURATION '{}'", p.value))?,
+        ),
+        None => None,
+    };
+    let stamp = |name: &str| {
+        item.property(name)
+            .and_then(|p| p.value.trim().strip_suffix('Z').and_then(parse_naive))
+            .map(|naive| Utc.from_utc_datetime(&naive))
+    };
+    let created = stamp("CREATED")
+        .or_else(|| stamp("DTSTAMP"))
+        .unwrap_or_else(Utc::now);
+    let last_modified = stamp("LAST-MODIFIED")
+        .or_else(|| stamp("DTSTAMP"))
+        .unwrap_or(created);
+
+    let text = |name: &str| item.property(name).map(|p| unescape_text(&p.value));
+    let mut event = CalendarEvent::new(text("SUMMARY").unwrap_or_default(), created);
+    event.uid = uid.clone();
+    event.description = text("DESCRIPTION");
+    event.location = text("LOCATION");
+    event.component = kind;
+    event.created = created;
+    event.last_modified = last_modified;
+
+    let scheduled = match kind {
+        CalendarComponent::Event => {
+            let start = dtstart.ok_or("missing DTSTART")?;
+            event.start = start.utc;
+            event.end = dtend
+                .map(|end| end.utc)
+                .or_else(|| duration.map(|d| start.utc + d));
+            start
+        }
+        CalendarComponent::Todo => {
+            let due = due.or_else(|| {
+                let start = dtstart.as_ref()?;
+                Some(ResolvedTime {
+                    utc: start.utc + duration?,
+                    all_day: start.all_day,
+                    tzid: start.tzid.clone(),
+                })
+            });
+            event.start = dtstart.as_ref().or(due.as_ref()).map_or(created, |t| t.utc);
+            event.due = due.as_ref().map(|due| due.utc);
+            match due.or(dtstart) {
+                Some(time) => time,
+                None => ResolvedTime {
+                    utc: created,
+                    all_day: false,
+                    tzid: None,
+                },
+            }
+        }
+    };
+    event.all_day = scheduled.all_day;
+
+    if let Some(priority) = item.property("PRIORITY") {
+        let value = priority
+            .value
+            .trim()
+            .parse()
+            .map_err(|_| format!("invalid PRIORITY '{}'", priority.value))?;
+        event.priority = EventPriority::from_ical(value);
+    }
+
+    event.status = match item.property("STATUS") {
+        Some(p) => EventStatus::from_ical(&p.value)
+            .ok_or_else(|| format!("invalid STATUS '{}'", p.value))?,
+        None => match kind {
+            CalendarComponent::Event => EventStatus::Confirmed,
+            CalendarComponent::Todo => EventStatus::NeedsAction,
+        },
+    };
+    if kind == CalendarComponent::Todo && item.property("COMPLETED").is_some() {
+        event.status = EventStatus::Completed;
+    }
+
+    event.categories = item
+        .properties("CATEGORIES")
+        .flat_map(|p| split_unescaped(&p.value))
+        .map(|category| category.trim().to_string())
+        .filter(|category| !category.is_empty())
+        .collect();
+
+    if let Some(rrule) = item.property("RRULE") {
+        if let Err(message) = RecurrenceRule::parse(&rrule.value) {
+            warnings.push(format!("{}: recurrence not expanded ({})", uid, message));
+        }
+        event.recurrence = Recurrence::from_rrule(&rrule.value);
+    }
+
+    let mut exdates = Vec::new();
+    for property in item.properties("EXDATE") {
+        for value in property.value.split(',') {
+            let time = resolve_value(value.trim(), property, zones, warnings)
+                .ok_or_else(|| format!("invalid EXDATE value '{}'", value))?;
+            exdates.push(time.utc);
+        }
+    }
+
+    Ok(Some((
+        event,
+        SeriesInfo {
+            tzid: scheduled.tzid,
+            exdates,
+        },
+    )))
+}
+
+/// Parse `YYYYMMDDTHHMMSS` (or a bare date, as midnight)
+fn parse_naive(value: &str) -> Option<NaiveDateTime> {
+    let value = value.trim();
+    NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S")
+        .ok()
+        .or_else(|| {
+            NaiveDate::parse_from_str(value, "%Y%m%d")
+                .ok()
+                .map(|date| date.and_time(NaiveTime::MIN))
+        })
+}
+
+/// Parse an `UNTIL` value
+fn parse_until(value: &str) -> Result<Until, String> {
+    let invalid = || format!("invalid UNTIL '{}'", value);
+    if let Some(utc) = value.strip_suffix(['Z', 'z']) {
+        let naive = parse_naive(utc).ok_or_else(invalid)?;
+        return Ok(Until::Utc(Utc.from_utc_datetime(&naive)));
+    }
+    if value.len() == 8 {
+        let date = NaiveDate::parse_from_str(value, "%Y%m%d").map_err(|_| invalid())?;
+        let end_of_day = NaiveTime::from_hms_opt(23, 59, 59).unwrap_or(NaiveTime::MIN);
+        return Ok(Until::Local(date.and_time(end_of_day)));
+    }
+    parse_naive(value).map(Until::Local).ok_or_else(invalid)
+}
+
+/// Parse one `BYDAY` entry such as `MO`, `2TU` or `-1SU`
+fn parse_by_day(entry: &str) -> Result<(Option<i32>, Weekday), String> {
+    let entry = entry.trim().to_ascii_uppercase();
+    let split = entry.len().saturating_sub(2);
+    let (ordinal, day) = entry.split_at(split);
+    let weekday = match day {
+        "MO" => Weekday::Mon,
+        "TU" => Weekday::Tue,
+        "WE" => Weekday::Wed,
+        "TH" => Weekday::Thu,
+        "FR" => Weekday::Fri,
+        "SA" => Weekday::Sat,
+        "SU" => Weekday::Sun,
+        _ => return Err(format!("invalid BYDAY '{}'", entry)),
+    };
+    let ordinal = match ordinal.trim_start_matches('+') {
+        "" => None,
+        n => Some(
+            n.parse::<i32>()
+                .ok()
+                .filter(|n| *n != 0 && (-53..=53).contains(n))
+                .ok_or_else(|| format!("invalid BYDAY '{}'", entry))?,
+        ),
+    };
+    Ok((ordinal, weekday))
+}
+
+/// Parse a comma-separated list of integers within a range
+fn parse_numbers(value: &str, range: std::ops::RangeInclusive<i32>) -> Result<Vec<i32>, String> {
+    value
+        .split(',')
+        .map(|n| {
+            n.trim()
+                .parse()
+                .ok()
+                .filter(|n| range.contains(n))
+                .ok_or_else(|| format!("invalid number '{}'", n))
+        })
+        .collect()
+}
+
+/// Resolve a (possibly negative) day of the month
+fn resolve_month_day(year: i32, month: u32, day: i32) -> Option<NaiveDate> {
+    if day > 0 {
+        return NaiveDate::from_ymd_opt(year, month, day as u32);
+    }
+    let first_of_next =
+        NaiveDate::from_ymd_opt(year, month, 1)?.checked_add_months(Months::new(1))?;
+    first_of_next.checked_sub_days(Days::new(u64::from(day.unsigned_abs())))
+}
+
+/// Pick the days matching `BYDAY` entries from a span of consecutive days
+fn select_weekdays(
+    span: impl Iterator<Item = NaiveDate>,
+    by_day: &[(Option<i32>, Weekday)],
+) -> Vec<NaiveDate> {
+    let span: Vec<NaiveDate> = span.collect();
+    let mut selected = Vec::new();
+    for (ordinal, weekday) in by_day {
+        let matching: Vec<NaiveDate> = span
+            .iter()
+            .copied()
+            .filter(|date| date.weekday() == *weekday)
+            .collect();
+        match ordinal {
+            None => selected.extend(matching),
+            Some(n) if *n > 0 => selected.extend(matching.get(*n as usize - 1)),
+            Some(n) => selected.extend(
+                matching
+                    .len()
+                    .checked_sub(n.unsigned_abs() as usize)
+                    .and_then(|index| matching.get(index)),
+            ),
+        }
+    }
+    selected
+}
+
+/// Parse a UTC offset such as `+0200`, `-0500` or `+053000` into seconds
+fn parse_utc_offset(value: &str) -> Option<i32> {
+    let value = value.trim();
+    let (sign, digits) = match value.as_bytes().first()? {
+        b'+' => (1, &value[1..]),
+        b'-' => (-1, &value[1..]),
+        _ => return None,
+    };
+    if !(digits.len() == 4 || digits.len() == 6) || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let hours: i32 = digits[0..2].parse().ok()?;
+    let minutes: i32 = digits[2..4].parse().ok()?;
+    let seconds: i32 = digits.get(4..6).map_or(Some(0), |s| s.parse().ok())?;
+    Some(sign * (hours * 3600 + minutes * 60 + seconds))
+}
+
+/// Parse an RFC 5545 duration such as `PT1H30M`, `P2D` or `-P1W`
+fn parse_duration(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    let (sign, rest) = match value.strip_prefix('-') {
+        Some(rest) => (-1, rest),
+        None => (1, value.strip_prefix('+').unwrap_or(value)),
+    };
+    let rest = rest.strip_prefix('P')?;
+
+    let mut total = Duration::zero();
+    let mut number = String::new();
+    let mut in_time = false;
+    let mut parts = 0;
+    for ch in rest.chars() {
+        match ch {
+            '0'..='9' => number.push(ch),
+            'T' if !in_time && number.is_empty() => in_time = true,
+            unit => {
+                let n: i64 = number.parse().ok()?;
+                number.clear();
+                total += match (unit, in_time) {
+                    ('W', false) => Duration::weeks(n),
+                    ('D', false) => Duration::days(n),
+                    ('H', true) => Duration::hours(n),
+                    ('M', true) => Duration::minutes(n),
+                    ('S', true) => Duration::seconds(n),
+                    _ => return None,
+                };
+                parts += 1;
+            }
+        }
+    }
+    (number.is_empty() && parts > 0).then_some(total * sign)
+}
+
+/// Undo RFC 5545 text escaping
+fn unescape_text(value: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(ch) = chars.next() {
+        if ch != '\\' {
+            result.push(ch);
+            continue;
+        }
+        match chars.next() {
+            Some('n' | 'N') => result.push('\n'),
+            Some(other) => result.push(other),
+            None => result.push('\\'),
+        }
+    }
+    result
+}
+
+/// Split a text list on commas that are not escaped, unescaping each item
+fn split_unescaped(value: &str) -> Vec<String> {
+    let mut items = Vec::new();
+    let mut current = String::new();
+    let mut escaped = false;
+    for ch in value.chars() {
+        if escaped {
+            current.push('\\');
+            current.push(ch);
+            escaped = false;
+        } else if ch == '\\' {
+            escaped = true;
+        } else if ch == ',' {
+            items.push(unescape_text(&std::mem::take(&mut current)));
+        } else {
+            current.push(ch);
+        }
+    }
+    items.push(unescape_text(&current));
+    items
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::calendar_integration::CalendarExporter;
+
+    const NEW_YORK: &str = "BEGIN:VTIMEZONE\r\n\
+TZID:America/New_York\r\n\
+BEGIN:DAYLIGHT\r\n\
+TZOFFSETFROM:-0500\r\n\
+TZOFFSETTO:-0400\r\n\
+DTSTART:20070311T020000\r\n\
+RRULE:FREQ=YEARLY;BYMONTH=3;BYDAY=2SU\r\n\
+END:DAYLIGHT\r\n\
+BEGIN:STANDARD\r\n\
+TZOFFSETFROM:-0400\r\n\
+TZOFFSETTO:-0500\r\n\
+DTSTART:20071104T020000\r\n\
+RRULE:FREQ=YEARLY;BYMONTH=11;BYDAY=1SU\r\n\
+END:STANDARD\r\n\
+END:VTIMEZONE\r\n";
+
+    fn calendar(body: &str) -> String {
+        format!(
+            "BEGIN:VCALENDAR\r\nVERSION:2.0\r\n{}END:VCALENDAR\r\n",
+            body
+        )
+    }
+
+    fn utc(y: i32, m: u32, d: u32, h: u32, min: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, m, d, h, min, 0).unwrap()
+    }
+
+    fn local(y: i32, m: u32, d: u32, h: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(y, m, d)
+            .unwrap()
+            .and_hms_opt(h, 0, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_parse_event_and_todo() {
+        let ics = calendar(
+            "BEGIN:VEVENT\r\n\
+UID:standup@example.com\r\n\
+DTSTAMP:20250101T000000Z\r\n\
+SUMMARY:Standup\\, daily\r\n\
+DESCRIPTION:Line one\\nLine two\r\n\
+DTSTART:20250303T090000Z\r\n\
+DURATION:PT15M\r\n\
+PRIORITY:1\r\n\
+CATEGORIES:team,meeting\r\n\
+BEGIN:VALARM\r\n\
+ACTION:DISPLAY\r\n\
+TRIGGER:-PT5M\r\n\
+END:VALARM\r\n\
+END:VEVENT\r\n\
+BEGIN:VTODO\r\n\
+UID:report@example.com\r\n\
+SUMMARY:Quarterly report\r\n\
+DUE;VALUE=DATE:20250331\r\n\
+STATUS:COMPLETED\r\n\
+END:VTODO\r\n",
+        );
+
+        let parsed = parse_ics(&ics).unwrap();
+        assert!(parsed.warnings.is_empty(), "{:?}", parsed.warnings);
+        assert_eq!(parsed.events.len(), 2);
+
+        let event = &parsed.events[0];
+        assert_eq!(event.summary, "Standup, daily");
+        assert_eq!(event.description.as_deref(), Some("Line one\nLine two"));
+        assert_eq!(event.start, utc(2025, 3, 3, 9, 0));
+        assert_eq!(event.end, Some(utc(2025, 3, 3, 9, 15)));
+        assert_eq!(event.priority, EventPriority::Critical);
+        assert_eq!(event.categories, vec!["team", "meeting"]);
+        assert_eq!(event.component, CalendarComponent::Event);
+
+        let todo = &parsed.events[1];
+        assert_eq!(todo.component, CalendarComponent::Todo);
+        assert_eq!(todo.due, Some(utc(2025, 3, 31, 0, 0)));
+        assert!(todo.all_day);
+        assert_eq!(todo.status, EventStatus::Completed);
+    }
+
+    #[test]
+    fn test_folded_lines_and_quoted_params() {
+        let ics = calendar(
+            "BEGIN:VEVENT\r\n\
+UID:folded\r\n\
+SUMMARY:A very long sum\r\n mary that was folded\r\n\
+ATTENDEE;CN=\"Doe; Jane: QA\":mailto:jane@example.com\r\n\
+DTSTART:20250101T100000Z\r\n\
+END:VEVENT\r\n",
+        );
+
+        let parsed = parse_ics(&ics).unwrap();
+        assert_eq!(
+            parsed.events[0].summary,
+            "A very long summary that was folded"
+        );
+    }
+
+    #[test]
+    fn test_time_zone_resolution_across_dst() {
+        let ics = calendar(&format!(
+            "{}BEGIN:VEVENT\r\n\
+UID:winter\r\n\
+DTSTART;TZID=America/New_York:20250115T090000\r\n\
+END:VEVENT\r\n\
+BEGIN:VEVENT\r\n\
+UID:summer\r\n\
+DTSTART;TZID=America/New_York:20250715T090000\r\n\
+END:VEVENT\r\n",
+            NEW_YORK
+        ));
+
+        let parsed = parse_ics(&ics).unwrap();
+        assert_eq!(parsed.events[0].start, utc(2025, 1, 15, 14, 0));
+        assert_eq!(parsed.events[1].start, utc(2025, 7, 15, 13, 0));
+
+        let zone = &parsed.time_zones["America/New_York"];
+        assert_eq!(zone.offset_at(local(2025, 3, 9, 1)), -5 * 3600);
+        assert_eq!(zone.offset_at(local(2025, 3, 9, 3)), -4 * 3600);
+        assert_eq!(
+            zone.to_local(utc(2025, 7, 15, 13, 0)),
+            local(2025, 7, 15, 9)
+        );
+    }
+
+    #[test]
+    fn test_unknown_time_zone_falls_back_to_utc() {
+        let ics = calendar(
+            "BEGIN:VEVENT\r\n\
+UID:x\r\n\
+DTSTART;TZID=Mars/Olympus:20250101T100000\r\n\
+END:VEVENT\r\n",
+        );
+
+        let parsed = parse_ics(&ics).unwrap();
+        assert_eq!(parsed.events[0].start, utc(2025, 1, 1, 10, 0));
+        assert!(parsed.warnings[0].contains("Mars/Olympus"));
+    }
+
+    #[test]
+    fn test_recurring_occurrence_keeps_local_hour() {
+        let ics = calendar(&format!(
+            "{}BEGIN:VEVENT\r\n\
+UID:weekly\r\n\
+DTSTART;TZID=America/New_York:20250303T090000\r\n\
+RRULE:FREQ=WEEKLY;BYDAY=MO\r\n\
+EXDATE;TZID=America/New_York:20250310T090000\r\n\
+END:VEVENT\r\n",
+            NEW_YORK
+        ));
+
+        let parsed = parse_ics(&ics).unwrap();
+        let event = &parsed.events[0];
+        assert_eq!(
+            event.recurrence,
+            Recurrence::Custom("FREQ=WEEKLY;BYDAY=MO".to_string())
+        );
+        assert_eq!(parsed.time_zone_of("weekly"), Some("America/New_York"));
+
+        // March 10 is excluded; March 17 is after the DST switch (UTC-4)
+        let next = parsed.occurrence_after(event, utc(2025, 3, 4, 0, 0));
+        assert_eq!(next, Some(utc(2025, 3, 17, 13, 0)));
+    }
+
+    #[test]
+    fn test_rrule_expansion() {
+        let start = local(2025, 1, 31, 9);
+        let monthly = RecurrenceRule::parse("FREQ=MONTHLY").unwrap();
+        let months: Vec<u32> = monthly
+            .occurrences(start, None)
+            .take(3)
+            .map(|d| d.month())
+            .collect();
+        // Months without a 31st are skipped
+        assert_eq!(months, vec![1, 3, 5]);
+
+        let weekly = RecurrenceRule::parse("FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,FR;COUNT=4").unwrap();
+        let days: Vec<NaiveDateTime> = weekly.occurrences(local(2025, 3, 3, 9), None).collect();
+        assert_eq!(
+            days,
+            vec![
+                local(2025, 3, 3, 9),
+                local(2025, 3, 7, 9),
+                local(2025, 3, 17, 9),
+                local(2025, 3, 21, 9)
+            ]
+        );
+
+        let until = RecurrenceRule::parse("FREQ=DAILY;UNTIL=20250305").unwrap();
+        assert_eq!(until.occurrences(local(2025, 3, 3, 9), None).count(), 3);
+
+        assert!(RecurrenceRule::parse("FREQ=HOURLY").is_err());
+        assert!(RecurrenceRule::parse("FREQ=MONTHLY;BYSETPOS=-1").is_err());
+        assert!(RecurrenceRule::parse("INTERVAL=2").is_err());
+    }
+
+    #[test]
+    fn test_exported_calendar_parses_back() {
+        let due = utc(2025, 6, 1, 17, 0);
+        let events = vec![
+            CalendarEvent::new("Launch", due)
+                .description("Ship it; then celebrate")
+                .priority(EventPriority::High)
+                .recurrence(Recurrence::Weekly),
+            CalendarEvent::new("File taxes", due).todo(due),
+        ];
+        let ics = CalendarExporter::new("Tasks").export_events(&events);
+
+        let parsed = parse_ics(&ics).unwrap();
+        assert_eq!(parsed.name.as_deref(), Some("Tasks"));
+        assert_eq!(parsed.events.len(), 2);
+        assert_eq!(parsed.events[0].uid, events[0].uid);
+        assert_eq!(parsed.events[0].description, events[0].description);
+        assert_eq!(parsed.events[0].priority, EventPriority::High);
+        assert_eq!(parsed.events[0].recurrence, Recurrence::Weekly);
+        assert_eq!(parsed.events[1].component, CalendarComponent::Todo);
+        assert_eq!(parsed.events[1].due, Some(due));
+        assert_eq!(parsed.events[1].status, EventStatus::NeedsAction);
+    }
+
+    #[test]
+    fn test_structural_errors() {
+        assert_eq!(
+            parse_ics("BEGIN:VEVENT\nEND:VEVENT\n").unwrap_err(),
+            IcsError::NotACalendar
+        );
+        assert!(matches!(
+            parse_ics("BEGIN:VCALENDAR\nBEGIN:VEVENT\nEND:VCALENDAR\n"),
+            Err(IcsError::Syntax { line: 3, .. })
+        ));
+        assert!(matches!(
+            parse_ics("BEGIN:VCALENDAR\nnot a property\nEND:VCALENDAR\n"),
+            Err(IcsError::Syntax { line: 2, .. })
+        ));
+
+        let parsed = parse_ics(&calendar(
+            "BEGIN:VEVENT\r\nSUMMARY:No uid\r\nEND:VEVENT\r\n",
+        ))
+        .unwrap();
+        assert!(parsed.events.is_empty());
+        assert!(parsed.warnings[0].contains("missing UID"));
+    }
+
+    #[test]
+    fn test_parse_duration_and_offsets() {
+        assert_eq!(parse_duration("PT1H30M"), Some(Duration::minutes(90)));
+        assert_eq!(parse_duration("-P1W"), Some(Duration::weeks(-1)));
+        assert_eq!(parse_duration("P1DT2H"), Some(Duration::hours(26)));
+        assert_eq!(parse_duration("P"), None);
+        assert_eq!(parse_duration("PT5"), None);
+        assert_eq!(parse_utc_offset("+0530"), Some(19_800));
+        assert_eq!(parse_utc_offset("-0500"), Some(-18_000));
+        assert_eq!(parse_utc_offset("0500"), None);
+    }
+}
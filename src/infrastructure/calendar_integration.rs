@@ -2,6 +2,9 @@
 //!
 //! Provides iCal (.ics) export functionality for tasks with due dates,
 //! priority-based color coding, and Google Calendar compatibility.
+//! Tasks can be exported as `VEVENT`s or as `VTODO`s carrying a `DUE`
+//! date; parsing `.ics` files back into events lives in
+//! [`calendar_import`](super::calendar_import).
 //!
 //! # Examples
 //!
@@ -50,6 +53,18 @@ impl EventPriority {
             EventPriority::Low => 7,
         }
     }
+
+    /// Map an iCal PRIORITY value back to a priority level
+    ///
+    /// `0` means "undefined" in RFC 5545 and maps to [`EventPriority::Medium`].
+    pub fn from_ical(priority: u8) -> Self {
+        match priority {
+            1..=2 => EventPriority::Critical,
+            3..=4 => EventPriority::High,
+            6..=9 => EventPriority::Low,
+            _ => EventPriority::Medium,
+        }
+    }
 }
 
 /// Recurrence pattern for recurring events
@@ -81,6 +96,45 @@ impl Recurrence {
             Recurrence::Custom(rule) => Some(rule.clone()),
         }
     }
+
+    /// Parse an iCal RRULE value
+    ///
+    /// Plain `FREQ=...` rules map to the simple variants; anything with
+    /// further parts (INTERVAL, BYDAY, COUNT, ...) is kept as
+    /// [`Recurrence::Custom`].
+    pub fn from_rrule(rule: &str) -> Self {
+        let rule = rule.trim();
+        if rule.is_empty() {
+            return Recurrence::None;
+        }
+        match rule.to_ascii_uppercase().as_str() {
+            "FREQ=DAILY" => Recurrence::Daily,
+            "FREQ=WEEKLY" => Recurrence::Weekly,
+            "FREQ=MONTHLY" => Recurrence::Monthly,
+            "FREQ=YEARLY" => Recurrence::Yearly,
+            _ => Recurrence::Custom(rule.to_string()),
+        }
+    }
+}
+
+/// Kind of calendar component an event is stored as
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CalendarComponent {
+    /// A `VEVENT` (scheduled by DTSTART/DTEND)
+    #[default]
+    Event,
+    /// A `VTODO` (scheduled by DUE)
+    Todo,
+}
+
+impl CalendarComponent {
+    /// iCal component name
+    pub fn to_ical(&self) -> &'static str {
+        match self {
+            CalendarComponent::Event => "VEVENT",
+            CalendarComponent::Todo => "VTODO",
+        }
+    }
 }
 
 /// Calendar event for export
@@ -102,11 +156,15 @@ pub struct CalendarEvent {
     pub priority: EventPriority,
     /// Recurrence pattern
     pub recurrence: Recurrence,
+    /// Component kind (VEVENT or VTODO)
+    pub component: CalendarComponent,
+    /// Due time (VTODO only)
+    pub due: Option<DateTime<Utc>>,
     /// Categories/tags
     pub categories: Vec<String>,
     /// Whether event is all-day
     pub all_day: bool,
-    /// Event status (TENTATIVE, CONFIRMED, CANCELLED)
+    /// Event status (TENTATIVE, CONFIRMED, CANCELLED, or a VTODO status)
     pub status: EventStatus,
     /// Created timestamp
     pub created: DateTime<Utc>,
@@ -123,6 +181,12 @@ pub enum EventStatus {
     Confirmed,
     /// Cancelled
     Cancelled,
+    /// To-do not started yet (VTODO only)
+    NeedsAction,
+    /// To-do in progress (VTODO only)
+    InProcess,
+    /// To-do completed (VTODO only)
+    Completed,
 }
 
 impl EventStatus {
@@ -132,6 +196,22 @@ impl EventStatus {
             EventStatus::Tentative => "TENTATIVE",
             EventStatus::Confirmed => "CONFIRMED",
             EventStatus::Cancelled => "CANCELLED",
+            EventStatus::NeedsAction => "NEEDS-ACTION",
+            EventStatus::InProcess => "IN-PROCESS",
+            EventStatus::Completed => "COMPLETED",
+        }
+    }
+
+    /// Parse an iCal STATUS value (case-insensitive)
+    pub fn from_ical(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_uppercase().as_str() {
+            "TENTATIVE" => Some(EventStatus::Tentative),
+            "CONFIRMED" => Some(EventStatus::Confirmed),
+            "CANCELLED" => Some(EventStatus::Cancelled),
+            "NEEDS-ACTION" => Some(EventStatus::NeedsAction),
+            "IN-PROCESS" => Some(EventStatus::InProcess),
+            "COMPLETED" => Some(EventStatus::Completed),
+            _ => None,
         }
     }
 }
@@ -165,6 +245,8 @@ impl CalendarEvent {
             end: None,
             priority: EventPriority::Medium,
             recurrence: Recurrence::None,
+            component: CalendarComponent::Event,
+            due: None,
             categories: Vec::new(),
             all_day: false,
             status: EventStatus::Confirmed,
@@ -227,6 +309,24 @@ impl CalendarEvent {
         self
     }
 
+    /// Store the event as a `VTODO` due at the given time
+    pub fn todo(mut self, due: DateTime<Utc>) -> Self {
+        self.component = CalendarComponent::Todo;
+        self.due = Some(due);
+        if self.status == EventStatus::Confirmed {
+            self.status = EventStatus::NeedsAction;
+        }
+        self
+    }
+
+    /// Time the item is scheduled for: DUE for to-dos, DTSTART otherwise
+    pub fn scheduled_at(&self) -> DateTime<Utc> {
+        match self.component {
+            CalendarComponent::Todo => self.due.unwrap_or(self.start),
+            CalendarComponent::Event => self.start,
+        }
+    }
+
     /// Get event end time (default: start + 1 hour)
     pub fn get_end(&self) -> DateTime<Utc> {
         self.end.unwrap_or_else(|| self.start + Duration::hours(1))
@@ -327,6 +427,11 @@ impl CalendarExporter {
 
     /// Write a single event to iCal format
     fn write_event(&self, ical: &mut String, event: &CalendarEvent) {
+        if event.component == CalendarComponent::Todo {
+            self.write_todo(ical, event);
+            return;
+        }
+
         writeln!(ical, "BEGIN:VEVENT").unwrap();
 
         // UID (required)
@@ -399,8 +504,58 @@ impl CalendarExporter {
         writeln!(ical, "END:VEVENT").unwrap();
     }
 
+    /// Write a single to-do to iCal format
+    fn write_todo(&self, ical: &mut String, event: &CalendarEvent) {
+        writeln!(ical, "BEGIN:VTODO").unwrap();
+        writeln!(ical, "UID:{}", event.uid).unwrap();
+        writeln!(ical, "DTSTAMP:{}", Self::format_datetime(&Utc::now())).unwrap();
+        writeln!(ical, "SUMMARY:{}", Self::escape_text(&event.summary)).unwrap();
+
+        let due = event.scheduled_at();
+        if event.all_day {
+            writeln!(ical, "DUE;VALUE=DATE:{}", Self::format_date(&due)).unwrap();
+        } else {
+            writeln!(ical, "DUE:{}", Self::format_datetime(&due)).unwrap();
+        }
+
+        if let Some(ref desc) = event.description {
+            writeln!(ical, "DESCRIPTION:{}", Self::escape_text(desc)).unwrap();
+        }
+        if let Some(ref loc) = event.location {
+            writeln!(ical, "LOCATION:{}", Self::escape_text(loc)).unwrap();
+        }
+
+        writeln!(ical, "PRIORITY:{}", event.priority.ical_priority()).unwrap();
+        writeln!(ical, "STATUS:{}", event.status.to_ical()).unwrap();
+        if event.status == EventStatus::Completed {
+            writeln!(
+                ical,
+                "COMPLETED:{}",
+                Self::format_datetime(&event.last_modified)
+            )
+            .unwrap();
+        }
+
+        if !event.categories.is_empty() {
+            writeln!(ical, "CATEGORIES:{}", event.categories.join(",")).unwrap();
+        }
+        if let Some(rrule) = event.recurrence.to_rrule() {
+            writeln!(ical, "RRULE:{}", rrule).unwrap();
+        }
+
+        writeln!(ical, "CREATED:{}", Self::format_datetime(&event.created)).unwrap();
+        writeln!(
+            ical,
+            "LAST-MODIFIED:{}",
+            Self::format_datetime(&event.last_modified)
+        )
+        .unwrap();
+
+        writeln!(ical, "END:VTODO").unwrap();
+    }
+
     /// Format datetime in iCal format (UTC)
-    fn format_datetime(dt: &DateTime<Utc>) -> String {
+    pub(crate) fn format_datetime(dt: &DateTime<Utc>) -> String {
         dt.format("%Y%m%dT%H%M%SZ").to_string()
     }
 
@@ -410,7 +565,7 @@ impl CalendarExporter {
     }
 
     /// Escape text for iCal (RFC 5545)
-    fn escape_text(text: &str) -> String {
+    pub(crate) fn escape_text(text: &str) -> String {
         text.replace('\\', "\\\\")
             .replace(',', "\\,")
             .replace(';', "\\;")
@@ -678,4 +833,53 @@ mod tests {
 
         assert_eq!(event.get_end(), end);
     }
+
+    #[test]
+    fn test_priority_and_status_round_trip() {
+        for priority in [
+            EventPriority::Low,
+            EventPriority::Medium,
+            EventPriority::High,
+            EventPriority::Critical,
+        ] {
+            assert_eq!(EventPriority::from_ical(priority.ical_priority()), priority);
+        }
+        assert_eq!(EventPriority::from_ical(0), EventPriority::Medium);
+        assert_eq!(
+            EventStatus::from_ical("needs-action"),
+            Some(EventStatus::NeedsAction)
+        );
+        assert_eq!(
+            EventStatus::from_ical("COMPLETED"),
+            Some(EventStatus::Completed)
+        );
+        assert_eq!(EventStatus::from_ical("bogus"), None);
+    }
+
+    #[test]
+    fn test_recurrence_from_rrule() {
+        assert_eq!(Recurrence::from_rrule("FREQ=WEEKLY"), Recurrence::Weekly);
+        assert_eq!(Recurrence::from_rrule(""), Recurrence::None);
+        assert_eq!(
+            Recurrence::from_rrule("FREQ=WEEKLY;BYDAY=MO,WE"),
+            Recurrence::Custom("FREQ=WEEKLY;BYDAY=MO,WE".to_string())
+        );
+    }
+
+    #[test]
+    fn test_export_todo() {
+        let due = make_datetime(2025, 11, 9, 17, 0);
+        let event = CalendarEvent::new("Ship release", due)
+            .todo(due)
+            .status(EventStatus::Completed);
+
+        let ical = CalendarExporter::new("Tasks").export_events(&[event]);
+
+        assert!(ical.contains("BEGIN:VTODO"));
+        assert!(ical.contains("DUE:20251109T170000Z"));
+        assert!(ical.contains("STATUS:COMPLETED"));
+        assert!(ical.contains("COMPLETED:"));
+        assert!(!ical.contains("BEGIN:VEVENT"));
+        assert!(!ical.contains("DTEND"));
+    }
 }
@@ -0,0 +1,1451 @@
+//! Two-way sync between board cards and an iCalendar directory
+//!
+//! A [`CalendarSource`] is a directory of `.ics` files, the layout CalDAV
+//! sync tools (vdirsyncer, DAVx5 exports, ...) keep locally. Calendar items
+//! are matched to cards by UID through the links in [`CalendarSyncState`],
+//! which is stored in the board database.
+//!
+//! # Reconciliation
+//!
+//! Every link remembers the [`SyncedFields`] (title, description, due date,
+//! priority, done) both sides agreed on at the last sync. Comparing each
+//! side with that base tells who changed:
+//!
+//! - only the card changed: the item's properties are rewritten in place,
+//!   keeping everything toad does not manage (alarms, attendees, time zones)
+//! - only the item changed: the card is updated
+//! - both changed differently: a [`SyncConflict`], resolved according to
+//!   [`ConflictPolicy`] (reported and left alone by default)
+//! - new items become cards; new cards with a due date are written as
+//!   `VTODO`s to `<uid>.ics`
+//! - an item deleted from the calendar unlinks its card (the card is kept and
+//!   not exported again); a deleted card deletes its unchanged item
+//!
+//! The due date of a card linked to a recurring item is the next occurrence
+//! of the series. Completing such a card moves it on to the following
+//! occurrence instead of completing the series; the `RRULE` itself is owned
+//! by the calendar.
+//!
+//! # Examples
+//!
+//! ```
+//! use chrono::Utc;
+//! use toad::infrastructure::board_store::BoardDatabase;
+//! use toad::infrastructure::calendar_sync::{CalendarSource, SyncOptions, sync_calendar};
+//!
+//! let dir = tempfile::tempdir().unwrap();
+//! std::fs::write(
+//!     dir.path().join("dentist.ics"),
+//!     "BEGIN:VCALENDAR\r\nBEGIN:VTODO\r\nUID:dentist\r\nSUMMARY:Book dentist\r\n\
+//!      DUE;VALUE=DATE:20250301\r\nEND:VTODO\r\nEND:VCALENDAR\r\n",
+//! )
+//! .unwrap();
+//!
+//! let mut db = BoardDatabase::default();
+//! let source = CalendarSource::new(dir.path());
+//! let report = sync_calendar(&mut db, &source, &SyncOptions::default(), Utc::now()).unwrap();
+//!
+//! let card = db.cards.get_card(&report.imported[0]).unwrap();
+//! assert_eq!(card.title, "Book dentist");
+//! assert!(card.due_date.is_some());
+//! ```
+
+use crate::infrastructure::board_store::BoardDatabase;
+use crate::infrastructure::calendar_import::{
+    Frequency, IcsCalendar, RecurrenceRule, TimeZoneDef, parse_ics, unfold_lines,
+};
+use crate::infrastructure::calendar_integration::{
+    CalendarComponent, CalendarEvent, CalendarExporter, EventPriority, EventStatus, Recurrence,
+};
+use crate::infrastructure::fs_util::atomic_write;
+use crate::infrastructure::rich_task_cards::{Priority, RichTaskCard};
+use chrono::{DateTime, Duration, NaiveTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use uuid::Uuid;
+
+/// Activity type logged on cards changed by a sync
+pub const CALENDAR_SYNC_ACTIVITY: &str = "calendar_sync";
+
+/// Creator of cards imported from a calendar
+const SYNC_USER: &str = "calendar";
+
+/// Name of the calendar written into exported files
+const EXPORT_CALENDAR_NAME: &str = "TOAD Tasks";
+
+/// Longest content line (in octets) before it is folded
+const MAX_LINE_OCTETS: usize = 75;
+
+/// Card fields kept in sync with calendar items
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SyncedFields {
+    /// Card title / `SUMMARY`
+    pub title: String,
+    /// Card description / `DESCRIPTION`
+    pub description: String,
+    /// Due date / `DUE` (to-dos) or `DTSTART` (events)
+    pub due: Option<DateTime<Utc>>,
+    /// Priority / `PRIORITY`
+    pub priority: Priority,
+    /// Card is done / to-do is `COMPLETED` (always false for events)
+    pub done: bool,
+}
+
+/// Link between a calendar item and a card
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SyncLink {
+    /// Linked card
+    pub card_id: String,
+    /// File (within the source directory) holding the item
+    pub file: String,
+    /// Field values both sides had after the last sync
+    pub fields: SyncedFields,
+    /// Occurrence written to the card's due date (recurring items only)
+    pub projected_due: Option<DateTime<Utc>>,
+}
+
+/// Persistent calendar sync state (the `calendar` board section)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CalendarSyncState {
+    /// Directory synced with the board, if any
+    pub source: Option<PathBuf>,
+    /// Links by item UID
+    pub links: BTreeMap<String, SyncLink>,
+    /// Cards whose item was deleted from the calendar (never exported again)
+    pub detached: BTreeSet<String>,
+}
+
+impl CalendarSyncState {
+    /// UID of the item linked to a card
+    pub fn uid_for_card(&self, card_id: &str) -> Option<&str> {
+        self.links
+            .iter()
+            .find(|(_, link)| link.card_id == card_id)
+            .map(|(uid, _)| uid.as_str())
+    }
+}
+
+/// How to resolve items changed on both sides since the last sync
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ConflictPolicy {
+    /// Report the conflict and change neither side
+    #[default]
+    Report,
+    /// Overwrite the calendar item with the card
+    PreferCard,
+    /// Overwrite the card with the calendar item
+    PreferCalendar,
+}
+
+/// Options for [`sync_calendar`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SyncOptions {
+    /// Conflict resolution
+    pub conflicts: ConflictPolicy,
+    /// Write unlinked cards with a due date to the calendar
+    pub export_cards: bool,
+}
+
+impl Default for SyncOptions {
+    fn default() -> Self {
+        Self {
+            conflicts: ConflictPolicy::Report,
+            export_cards: true,
+        }
+    }
+}
+
+/// An item changed both in the calendar and on the board
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyncConflict {
+    /// Item UID
+    pub uid: String,
+    /// Card ID
+    pub card_id: String,
+    /// Fields on the card
+    pub card: SyncedFields,
+    /// Fields in the calendar
+    pub calendar: SyncedFields,
+}
+
+/// Result of a sync
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SyncReport {
+    /// Cards created from new calendar items
+    pub imported: Vec<String>,
+    /// Cards updated from their items
+    pub updated_cards: Vec<String>,
+    /// UIDs of items created from cards
+    pub exported: Vec<String>,
+    /// UIDs of items updated from their cards
+    pub updated_items: Vec<String>,
+    /// UIDs of items deleted because their card was deleted
+    pub deleted_items: Vec<String>,
+    /// Cards unlinked because their item was deleted
+    pub detached: Vec<String>,
+    /// Items left alone because both sides changed
+    pub conflicts: Vec<SyncConflict>,
+    /// Unreadable files and items
+    pub warnings: Vec<String>,
+}
+
+impl SyncReport {
+    /// Whether the sync changed the board or its sync state
+    pub fn changed_board(&self) -> bool {
+        !(self.imported.is_empty()
+            && self.updated_cards.is_empty()
+            && self.exported.is_empty()
+            && self.updated_items.is_empty()
+            && self.deleted_items.is_empty()
+            && self.detached.is_empty())
+    }
+
+    /// One-line summary for the status bar
+    pub fn summary(&self) -> String {
+        let counts = [
+            (self.imported.len(), "imported"),
+            (self.updated_cards.len(), "cards updated"),
+            (self.exported.len(), "exported"),
+            (self.updated_items.len(), "items updated"),
+            (self.deleted_items.len(), "items deleted"),
+            (self.detached.len(), "unlinked"),
+            (self.conflicts.len(), "conflicts"),
+        ];
+        let parts: Vec<String> = counts
+            .iter()
+            .filter(|(count, _)| *count > 0)
+            .map(|(count, label)| format!("{} {}", count, label))
+            .collect();
+        if parts.is_empty() {
+            "Calendar in sync".to_string()
+        } else {
+            format!("Calendar: {}", parts.join(", "))
+        }
+    }
+}
+
+/// A directory of `.ics` files used as a calendar
+///
+/// [`has_changed`](Self::has_changed) polls file names, sizes and
+/// modification times, so the directory can be watched without a file
+/// system notification backend.
+#[derive(Debug, Clone)]
+pub struct CalendarSource {
+    dir: PathBuf,
+    seen: Option<BTreeMap<String, (SystemTime, u64)>>,
+}
+
+impl CalendarSource {
+    /// Use a directory as calendar (it is created on the first write)
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            seen: None,
+        }
+    }
+
+    /// The calendar directory
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Whether any `.ics` file was added, removed or modified since the
+    /// last call (always true on the first call)
+    pub fn has_changed(&mut self) -> bool {
+        let listing = self.listing();
+        let changed = self.seen.as_ref() != Some(&listing);
+        self.seen = Some(listing);
+        changed
+    }
+
+    /// Treat the directory's current contents as seen (e.g. after a sync
+    /// wrote to it)
+    pub fn mark_seen(&mut self) {
+        self.seen = Some(self.listing());
+    }
+
+    /// Names, modification times and sizes of the `.ics` files
+    fn listing(&self) -> BTreeMap<String, (SystemTime, u64)> {
+        self.file_names()
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|name| {
+                let meta = fs::metadata(self.dir.join(&name)).ok()?;
+                Some((name, (meta.modified().ok()?, meta.len())))
+            })
+            .collect()
+    }
+
+    /// Sorted names of the `.ics` files (none if the directory is missing)
+    fn file_names(&self) -> io::Result<Vec<String>> {
+        let entries = match fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e),
+        };
+        let mut names = Vec::new();
+        for entry in entries {
+            let path = entry?.path();
+            let is_ics = path
+                .extension()
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("ics"));
+            let name = path.file_name().map(|n| n.to_string_lossy().to_string());
+            if let Some(name) = name.filter(|name| is_ics && !name.starts_with('.')) {
+                names.push(name);
+            }
+        }
+        names.sort();
+        Ok(names)
+    }
+
+    /// Read and parse every file
+    fn read(&self) -> io::Result<Snapshot> {
+        let mut snapshot = Snapshot::default();
+        for name in self.file_names()? {
+            let raw = fs::read_to_string(self.dir.join(&name))?;
+            let calendar = match parse_ics(&raw) {
+                Ok(calendar) => calendar,
+                Err(e) => {
+                    snapshot.warnings.push(format!("{}: {}", name, e));
+                    snapshot.files.insert(
+                        name,
+                        SourceFile {
+                            raw,
+                            calendar: None,
+                        },
+                    );
+                    continue;
+                }
+            };
+            for warning in &calendar.warnings {
+                snapshot.warnings.push(format!("{}: {}", name, warning));
+            }
+            for event in &calendar.events {
+                if snapshot.items.contains_key(&event.uid) {
+                    snapshot
+                        .warnings
+                        .push(format!("{}: duplicate UID {} ignored", name, event.uid));
+                } else {
+                    snapshot.items.insert(event.uid.clone(), name.clone());
+                }
+            }
+            snapshot.files.insert(
+                name,
+                SourceFile {
+                    raw,
+                    calendar: Some(calendar),
+                },
+            );
+        }
+        Ok(snapshot)
+    }
+
+    /// Write a file atomically
+    fn write(&self, name: &str, contents: &str) -> io::Result<()> {
+        fs::create_dir_all(&self.dir)?;
+        atomic_write(&self.dir.join(name), contents.as_bytes())
+    }
+
+    /// Delete a file
+    fn remove(&self, name: &str) -> io::Result<()> {
+        match fs::remove_file(self.dir.join(name)) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Contents of the source directory at the start of a sync
+#[derive(Debug, Default)]
+struct Snapshot {
+    /// Files by name
+    files: BTreeMap<String, SourceFile>,
+    /// File of each item UID
+    items: BTreeMap<String, String>,
+    warnings: Vec<String>,
+}
+
+/// One `.ics` file
+#[derive(Debug)]
+struct SourceFile {
+    raw: String,
+    /// `None` if the file could not be parsed
+    calendar: Option<IcsCalendar>,
+}
+
+impl Snapshot {
+    /// File, calendar and event of an item
+    fn item(&self, uid: &str) -> Option<(&str, &IcsCalendar, &CalendarEvent)> {
+        let file = self.items.get(uid)?;
+        let calendar = self.files.get(file)?.calendar.as_ref()?;
+        let event = calendar.events.iter().find(|event| event.uid == uid)?;
+        Some((file, calendar, event))
+    }
+
+    /// Whether a file exists but could not be parsed
+    fn is_unreadable(&self, file: &str) -> bool {
+        self.files
+            .get(file)
+            .is_some_and(|file| file.calendar.is_none())
+    }
+}
+
+/// Synchronize the board with a calendar directory
+///
+/// Links are read from and written back to `db.calendar`. `now` decides
+/// which occurrence of a recurring item a card is due on.
+///
+/// # Errors
+///
+/// Returns `Err` if the directory cannot be read or written. Links are
+/// kept consistent with the files written before the failure.
+pub fn sync_calendar(
+    db: &mut BoardDatabase,
+    source: &CalendarSource,
+    options: &SyncOptions,
+    now: DateTime<Utc>,
+) -> io::Result<SyncReport> {
+    let snapshot = source.read()?;
+    let mut sync = Reconciler {
+        links: std::mem::take(&mut db.calendar.links),
+        detached: std::mem::take(&mut db.calendar.detached),
+        db,
+        source,
+        snapshot: &snapshot,
+        options,
+        now,
+        written: HashMap::new(),
+        report: SyncReport {
+            warnings: snapshot.warnings.clone(),
+            ..SyncReport::default()
+        },
+    };
+    let result = sync.run();
+    let Reconciler {
+        links,
+        detached,
+        report,
+        ..
+    } = sync;
+    db.calendar.links = links;
+    db.calendar.detached = detached;
+    result.map(|()| report)
+}
+
+/// State of one sync run
+struct Reconciler<'a> {
+    db: &'a mut BoardDatabase,
+    source: &'a CalendarSource,
+    snapshot: &'a Snapshot,
+    options: &'a SyncOptions,
+    now: DateTime<Utc>,
+    links: BTreeMap<String, SyncLink>,
+    detached: BTreeSet<String>,
+    /// Files rewritten during this run (`None` if deleted)
+    written: HashMap<String, Option<String>>,
+    report: SyncReport,
+}
+
+impl Reconciler<'_> {
+    fn run(&mut self) -> io::Result<()> {
+        let uids: Vec<String> = self.links.keys().cloned().collect();
+        for uid in &uids {
+            self.sync_link(uid)?;
+        }
+        self.import_new_items(&uids.into_iter().collect());
+        if self.options.export_cards {
+            self.export_new_cards()?;
+        }
+        Ok(())
+    }
+
+    /// Reconcile one linked item with its card
+    fn sync_link(&mut self, uid: &str) -> io::Result<()> {
+        let Some(mut link) = self.links.get(uid).cloned() else {
+            return Ok(());
+        };
+        let snapshot = self.snapshot;
+
+        let Some((file, calendar, event)) = snapshot.item(uid) else {
+            if snapshot.is_unreadable(&link.file) {
+                return Ok(());
+            }
+            self.links.remove(uid);
+            if self.db.cards.get_card(&link.card_id).is_some() {
+                self.log(
+                    &link.card_id,
+                    "Removed from the calendar; card unlinked".to_string(),
+                );
+                self.detached.insert(link.card_id.clone());
+                self.report.detached.push(link.card_id);
+            }
+            return Ok(());
+        };
+        link.file = file.to_string();
+        let remote = item_fields(event);
+
+        let Some(card) = self.db.cards.get_card(&link.card_id) else {
+            self.links.remove(uid);
+            if remote == link.fields {
+                self.delete_item(file, uid)?;
+                self.report.deleted_items.push(uid.to_string());
+            } else {
+                // Edited in the calendar after the card was deleted: keep the edit
+                self.import_item(file, calendar, event);
+            }
+            return Ok(());
+        };
+
+        if let Some(projected) = link.projected_due
+            && is_done(card)
+            && !remote.done
+            && let Some(next) = calendar.occurrence_after(event, projected)
+        {
+            let card_id = link.card_id.clone();
+            self.set_done(&card_id, false);
+            if let Some(card) = self.db.cards.get_card_mut(&card_id) {
+                card.due_date = Some(next);
+                card.updated_at = Utc::now();
+            }
+            self.log(
+                &card_id,
+                format!(
+                    "Occurrence done; next due {}",
+                    next.format("%Y-%m-%d %H:%M")
+                ),
+            );
+            link.projected_due = Some(next);
+            self.links.insert(uid.to_string(), link);
+            self.report.updated_cards.push(card_id);
+            return Ok(());
+        }
+
+        let local = card_fields(card, Some(&link), event.component);
+        let push = if local == remote {
+            link.fields = local;
+            self.links.insert(uid.to_string(), link);
+            return Ok(());
+        } else if local == link.fields {
+            false
+        } else if remote == link.fields {
+            true
+        } else {
+            match self.options.conflicts {
+                ConflictPolicy::PreferCard => true,
+                ConflictPolicy::PreferCalendar => false,
+                ConflictPolicy::Report => {
+                    self.report.conflicts.push(SyncConflict {
+                        uid: uid.to_string(),
+                        card_id: link.card_id.clone(),
+                        card: local,
+                        calendar: remote,
+                    });
+                    self.links.insert(uid.to_string(), link);
+                    return Ok(());
+                }
+            }
+        };
+
+        if push {
+            self.update_item(file, calendar, event, &remote, &local)?;
+            if link.projected_due.is_some() {
+                link.projected_due = self
+                    .db
+                    .cards
+                    .get_card(&link.card_id)
+                    .and_then(|card| card.due_date);
+            }
+            link.fields = local;
+            self.report.updated_items.push(uid.to_string());
+        } else {
+            link.projected_due = self.apply_item(&link.card_id, calendar, event, &remote);
+            link.fields = remote;
+            self.log(&link.card_id, "Updated from the calendar".to_string());
+            self.report.updated_cards.push(link.card_id.clone());
+        }
+        self.links.insert(uid.to_string(), link);
+        Ok(())
+    }
+
+    /// Create cards for items that were not linked before this run
+    fn import_new_items(&mut self, linked: &HashSet<String>) {
+        let snapshot = self.snapshot;
+        for uid in snapshot.items.keys() {
+            if linked.contains(uid) || self.links.contains_key(uid) {
+                continue;
+            }
+            let Some((file, calendar, event)) = snapshot.item(uid) else {
+                continue;
+            };
+            if event.status == EventStatus::Cancelled {
+                continue;
+            }
+            self.import_item(file, calendar, event);
+        }
+    }
+
+    /// Create and link a card for an item
+    fn import_item(&mut self, file: &str, calendar: &IcsCalendar, event: &CalendarEvent) {
+        let fields = item_fields(event);
+        let column = if fields.done { "Done" } else { "To Do" };
+        let card_id = self.db.cards.create_card(
+            fields.title.clone(),
+            fields.description.clone(),
+            column.to_string(),
+            fields.priority,
+            SYNC_USER.to_string(),
+        );
+        let tags: Vec<_> = event
+            .categories
+            .iter()
+            .map(|name| self.db.cards.create_tag(name.clone(), "blue".to_string()))
+            .collect();
+        let projected = self.projected_due(calendar, event);
+        if let Some(card) = self.db.cards.get_card_mut(&card_id) {
+            card.due_date = projected.or(fields.due);
+            card.recurrence = recurrence_label(&event.recurrence);
+            for tag in tags {
+                card.add_tag(tag);
+            }
+        }
+        self.move_to(&card_id, column);
+        self.log(&card_id, format!("Imported from calendar file {}", file));
+        self.links.insert(
+            event.uid.clone(),
+            SyncLink {
+                card_id: card_id.clone(),
+                file: file.to_string(),
+                fields,
+                projected_due: projected,
+            },
+        );
+        self.report.imported.push(card_id);
+    }
+
+    /// Write unlinked, open cards with a due date as new to-dos
+    fn export_new_cards(&mut self) -> io::Result<()> {
+        let linked: HashSet<&str> = self.links.values().map(|l| l.card_id.as_str()).collect();
+        let mut cards: Vec<&RichTaskCard> = self
+            .db
+            .cards
+            .cards()
+            .into_iter()
+            .filter(|card| {
+                card.due_date.is_some()
+                    && !is_done(card)
+                    && !card.status.eq_ignore_ascii_case("archived")
+                    && !linked.contains(card.id.as_str())
+                    && !self.detached.contains(&card.id)
+            })
+            .collect();
+        cards.sort_by(|a, b| a.id.cmp(&b.id));
+
+        let mut new_links = Vec::new();
+        for card in cards {
+            let Some(due) = card.due_date else {
+                continue;
+            };
+            let uid = format!("{}-{}@toad", card.id, Uuid::new_v4().simple());
+            let mut event = CalendarEvent::new(card.title.clone(), due)
+                .todo(due)
+                .priority(to_event_priority(card.priority))
+                .recurrence(recurrence_from_label(card.recurrence.as_deref()))
+                .all_day(due.time() == NaiveTime::MIN);
+            event.uid = uid.clone();
+            if !card.description.is_empty() {
+                event.description = Some(card.description.clone());
+            }
+            for tag in &card.tags {
+                event.categories.push(tag.name.clone());
+            }
+
+            let file = file_name_for(&uid);
+            let ics = CalendarExporter::new(EXPORT_CALENDAR_NAME).export_events(&[event]);
+            self.source.write(&file, &ics)?;
+            let recurring = card.recurrence.is_some();
+            new_links.push((
+                uid,
+                SyncLink {
+                    card_id: card.id.clone(),
+                    file,
+                    fields: card_fields(card, None, CalendarComponent::Todo),
+                    projected_due: recurring.then_some(due),
+                },
+            ));
+        }
+
+        for (uid, link) in new_links {
+            self.log(
+                &link.card_id,
+                format!("Exported to calendar file {}", link.file),
+            );
+            self.report.exported.push(uid.clone());
+            self.links.insert(uid, link);
+        }
+        Ok(())
+    }
+
+    /// Copy an item's fields onto its card, returning the projected due
+    /// date of recurring items
+    fn apply_item(
+        &mut self,
+        card_id: &str,
+        calendar: &IcsCalendar,
+        event: &CalendarEvent,
+        fields: &SyncedFields,
+    ) -> Option<DateTime<Utc>> {
+        let projected = self.projected_due(calendar, event);
+        let was_done = self.db.cards.get_card(card_id).is_some_and(is_done);
+        if let Some(card) = self.db.cards.get_card_mut(card_id) {
+            card.title = fields.title.clone();
+            card.description = fields.description.clone();
+            card.priority = fields.priority;
+            card.due_date = projected.or(fields.due);
+            card.recurrence = recurrence_label(&event.recurrence);
+            card.updated_at = Utc::now();
+        }
+        if event.component == CalendarComponent::Todo && fields.done != was_done {
+            self.set_done(card_id, fields.done);
+        }
+        projected
+    }
+
+    /// Due date for the card of a recurring item: its next occurrence from
+    /// the start of today
+    fn projected_due(
+        &self,
+        calendar: &IcsCalendar,
+        event: &CalendarEvent,
+    ) -> Option<DateTime<Utc>> {
+        if event.recurrence == Recurrence::None {
+            return None;
+        }
+        let today = self.now.date_naive().and_time(NaiveTime::MIN).and_utc();
+        Some(
+            calendar
+                .occurrence_after(event, today - Duration::seconds(1))
+                .unwrap_or_else(|| event.scheduled_at()),
+        )
+    }
+
+    /// Current contents of a file, including writes made in this run
+    fn file_contents(&self, file: &str) -> Option<String> {
+        match self.written.get(file) {
+            Some(contents) => contents.clone(),
+            None => self.snapshot.files.get(file).map(|f| f.raw.clone()),
+        }
+    }
+
+    /// Rewrite the properties of an item that differ from the card
+    fn update_item(
+        &mut self,
+        file: &str,
+        calendar: &IcsCalendar,
+        event: &CalendarEvent,
+        old: &SyncedFields,
+        new: &SyncedFields,
+    ) -> io::Result<()> {
+        let Some(raw) = self.file_contents(file) else {
+            return Ok(());
+        };
+        let zone = calendar
+            .time_zone_of(&event.uid)
+            .and_then(|id| calendar.time_zones.get(id));
+        let Some(patched) = patch_item(&raw, event, zone, old, new, self.now) else {
+            self.report
+                .warnings
+                .push(format!("{}: item {} not found for update", file, event.uid));
+            return Ok(());
+        };
+        self.source.write(file, &patched)?;
+        self.written.insert(file.to_string(), Some(patched));
+        Ok(())
+    }
+
+    /// Remove an item, deleting its file if nothing else is left in it
+    fn delete_item(&mut self, file: &str, uid: &str) -> io::Result<()> {
+        let Some(raw) = self.file_contents(file) else {
+            return Ok(());
+        };
+        match remove_item(&raw, uid) {
+            Some((_, 0)) => {
+                self.source.remove(file)?;
+                self.written.insert(file.to_string(), None);
+            }
+            Some((remaining, _)) => {
+                self.source.write(file, &remaining)?;
+                self.written.insert(file.to_string(), Some(remaining));
+            }
+            None => {}
+        }
+        Ok(())
+    }
+
+    /// Mark a card done or not done by moving it between columns
+    fn set_done(&mut self, card_id: &str, done: bool) {
+        self.move_to(card_id, if done { "Done" } else { "To Do" });
+    }
+
+    /// Move a card to a column, falling back to just setting its status
+    fn move_to(&mut self, card_id: &str, column: &str) {
+        match self.db.move_card_to_named(card_id, column) {
+            Ok(Some(warning)) => self.report.warnings.push(warning),
+            Ok(None) => {}
+            Err(_) => {
+                if let Some(card) = self.db.cards.get_card_mut(card_id) {
+                    card.status = column.to_string();
+                }
+            }
+        }
+    }
+
+    fn log(&mut self, card_id: &str, description: String) {
+        self.db.comments.log_activity(
+            card_id.to_string(),
+            CALENDAR_SYNC_ACTIVITY.to_string(),
+            description,
+            None,
+            None,
+        );
+    }
+}
+
+/// Synced fields of a calendar item
+fn item_fields(event: &CalendarEvent) -> SyncedFields {
+    let is_todo = event.component == CalendarComponent::Todo;
+    SyncedFields {
+        title: event.summary.clone(),
+        description: event.description.clone().unwrap_or_default(),
+        due: if is_todo {
+            event.due
+        } else {
+            Some(event.start)
+        },
+        priority: to_card_priority(event.priority),
+        done: is_todo && event.status == EventStatus::Completed,
+    }
+}
+
+/// Synced fields of a card
+///
+/// A recurring card still due on the occurrence the last sync projected
+/// reports the series' own due date, so rolling occurrences do not count as
+/// local edits.
+fn card_fields(
+    card: &RichTaskCard,
+    link: Option<&SyncLink>,
+    component: CalendarComponent,
+) -> SyncedFields {
+    let due = match link {
+        Some(link) if link.projected_due.is_some() && card.due_date == link.projected_due => {
+            link.fields.due
+        }
+        _ => card.due_date,
+    };
+    SyncedFields {
+        title: card.title.clone(),
+        description: card.description.clone(),
+        due,
+        priority: card.priority,
+        done: component == CalendarComponent::Todo && is_done(card),
+    }
+}
+
+fn is_done(card: &RichTaskCard) -> bool {
+    card.status.trim().eq_ignore_ascii_case("done")
+}
+
+fn to_card_priority(priority: EventPriority) -> Priority {
+    match priority {
+        EventPriority::Critical => Priority::Critical,
+        EventPriority::High => Priority::High,
+        EventPriority::Medium => Priority::Medium,
+        EventPriority::Low => Priority::Low,
+    }
+}
+
+fn to_event_priority(priority: Priority) -> EventPriority {
+    match priority {
+        Priority::Critical => EventPriority::Critical,
+        Priority::High => EventPriority::High,
+        Priority::Medium => EventPriority::Medium,
+        Priority::Low => EventPriority::Low,
+    }
+}
+
+/// Card recurrence label ("daily", "weekly", ...) for an item's recurrence
+fn recurrence_label(recurrence: &Recurrence) -> Option<String> {
+    let freq = match recurrence {
+        Recurrence::None => return None,
+        Recurrence::Daily => Frequency::Daily,
+        Recurrence::Weekly => Frequency::Weekly,
+        Recurrence::Monthly => Frequency::Monthly,
+        Recurrence::Yearly => Frequency::Yearly,
+        Recurrence::Custom(rule) => RecurrenceRule::parse(rule).ok()?.freq,
+    };
+    let label = match freq {
+        Frequency::Daily => "daily",
+        Frequency::Weekly => "weekly",
+        Frequency::Monthly => "monthly",
+        Frequency::Yearly => "yearly",
+    };
+    Some(label.to_string())
+}
+
+/// Item recurrence for a card recurrence label
+fn recurrence_from_label(label: Option<&str>) -> Recurrence {
+    match label.map(|l| l.trim().to_ascii_lowercase()).as_deref() {
+        Some("daily") => Recurrence::Daily,
+        Some("weekly") => Recurrence::Weekly,
+        Some("monthly") => Recurrence::Monthly,
+        Some("yearly") => Recurrence::Yearly,
+        _ => Recurrence::None,
+    }
+}
+
+/// File name for an exported item
+fn file_name_for(uid: &str) -> String {
+    let stem: String = uid
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    format!("{}.ics", stem)
+}
+
+/// Property name of a content line
+fn property_name(line: &str) -> String {
+    line.split([';', ':'])
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_uppercase()
+}
+
+/// Line range (BEGIN to END, inclusive) of a calendar item
+fn find_item(lines: &[String], uid: &str) -> Option<(usize, usize)> {
+    let mut depth = 0usize;
+    let mut start = None;
+    for (index, line) in lines.iter().enumerate() {
+        match property_name(line).as_str() {
+            "BEGIN" => {
+                depth += 1;
+                let name = line[6..].trim().to_ascii_uppercase();
+                if depth == 2 && (name == "VEVENT" || name == "VTODO") {
+                    start = Some(index);
+                }
+            }
+            "END" => {
+                if depth == 2
+                    && let Some(begin) = start.take()
+                    && top_level_lines(&lines[begin..=index])
+                        .any(|line| property_name(line) == "UID" && property_value(line) == uid)
+                {
+                    return Some((begin, index));
+                }
+                depth = depth.saturating_sub(1);
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Property lines directly inside a component (not in nested ones)
+fn top_level_lines(block: &[String]) -> impl Iterator<Item = &String> {
+    let mut depth = 0usize;
+    block
+        .iter()
+        .filter(move |line| match property_name(line).as_str() {
+            "BEGIN" => {
+                depth += 1;
+                false
+            }
+            "END" => {
+                depth = depth.saturating_sub(1);
+                false
+            }
+            _ => depth == 1,
+        })
+}
+
+/// Value of a content line (after the first unquoted ':')
+fn property_value(line: &str) -> &str {
+    let mut in_quotes = false;
+    for (index, ch) in line.char_indices() {
+        match ch {
+            '"' => in_quotes = !in_quotes,
+            ':' if !in_quotes => return line[index + 1..].trim(),
+            _ => {}
+        }
+    }
+    ""
+}
+
+/// Format a date-time property, keeping date-only values and time zones
+fn format_time(name: &str, at: DateTime<Utc>, all_day: bool, zone: Option<&TimeZoneDef>) -> String {
+    if all_day && at.time() == NaiveTime::MIN {
+        return format!("{};VALUE=DATE:{}", name, at.format("%Y%m%d"));
+    }
+    match zone {
+        Some(zone) => {
+            let tzid = if zone.id.contains([';', ':', ',']) {
+                format!("\"{}\"", zone.id)
+            } else {
+                zone.id.clone()
+            };
+            format!(
+                "{};TZID={}:{}",
+                name,
+                tzid,
+                zone.to_local(at).format("%Y%m%dT%H%M%S")
+            )
+        }
+        None => format!("{}:{}", name, CalendarExporter::format_datetime(&at)),
+    }
+}
+
+/// Rewrite the managed properties of one item that differ between `old`
+/// and `new`, leaving every other line untouched
+fn patch_item(
+    raw: &str,
+    event: &CalendarEvent,
+    zone: Option<&TimeZoneDef>,
+    old: &SyncedFields,
+    new: &SyncedFields,
+    now: DateTime<Utc>,
+) -> Option<String> {
+    let lines: Vec<String> = unfold_lines(raw).into_iter().map(|(_, l)| l).collect();
+    let (begin, end) = find_item(&lines, &event.uid)?;
+    let block = &lines[begin..=end];
+
+    let mut replaced: Vec<&str> = vec!["DTSTAMP", "LAST-MODIFIED", "SEQUENCE"];
+    let mut added = Vec::new();
+    if new.title != old.title {
+        replaced.push("SUMMARY");
+        added.push(format!(
+            "SUMMARY:{}",
+            CalendarExporter::escape_text(&new.title)
+        ));
+    }
+    if new.description != old.description {
+        replaced.push("DESCRIPTION");
+        if !new.description.is_empty() {
+            added.push(format!(
+                "DESCRIPTION:{}",
+                CalendarExporter::escape_text(&new.description)
+            ));
+        }
+    }
+    if new.priority != old.priority {
+        replaced.push("PRIORITY");
+        added.push(format!(
+            "PRIORITY:{}",
+            to_event_priority(new.priority).ical_priority()
+        ));
+    }
+    if new.due != old.due {
+        match (event.component, new.due) {
+            (CalendarComponent::Todo, due) => {
+                replaced.extend(["DUE", "DURATION"]);
+                if let Some(due) = due {
+                    added.push(format_time("DUE", due, event.all_day, zone));
+                }
+            }
+            (CalendarComponent::Event, Some(start)) => {
+                replaced.push("DTSTART");
+                added.push(format_time("DTSTART", start, event.all_day, zone));
+                if let Some(end) = event.end {
+                    replaced.push("DTEND");
+                    let moved = end + (start - event.start);
+                    added.push(format_time("DTEND", moved, event.all_day, zone));
+                }
+            }
+            // Events cannot lose their start; keep it
+            (CalendarComponent::Event, None) => {}
+        }
+    }
+    if new.done != old.done && event.component == CalendarComponent::Todo {
+        replaced.extend(["STATUS", "COMPLETED", "PERCENT-COMPLETE"]);
+        if new.done {
+            added.push(format!("STATUS:{}", EventStatus::Completed.to_ical()));
+            added.push(format!(
+                "COMPLETED:{}",
+                CalendarExporter::format_datetime(&now)
+            ));
+            added.push("PERCENT-COMPLETE:100".to_string());
+        } else {
+            added.push(format!("STATUS:{}", EventStatus::NeedsAction.to_ical()));
+        }
+    }
+    let sequence = top_level_lines(block)
+        .find(|line| property_name(line) == "SEQUENCE")
+        .and_then(|line| property_value(line).parse::<u32>().ok())
+        .map_or(1, |sequence| sequence + 1);
+    added.push(format!("SEQUENCE:{}", sequence));
+    added.push(format!(
+        "LAST-MODIFIED:{}",
+        CalendarExporter::format_datetime(&now)
+    ));
+    added.push(format!(
+        "DTSTAMP:{}",
+        CalendarExporter::format_datetime(&now)
+    ));
+
+    let mut depth = 0usize;
+    let mut kept = Vec::new();
+    for line in &block[1..] {
+        let name = property_name(line);
+        match name.as_str() {
+            "BEGIN" => depth += 1,
+            "END" => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+        if depth == 0 && name != "END" && replaced.contains(&name.as_str()) {
+            continue;
+        }
+        kept.push(line.clone());
+    }
+
+    let mut patched = lines[..=begin].to_vec();
+    patched.extend(added);
+    patched.extend(kept);
+    patched.extend_from_slice(&lines[end + 1..]);
+    Some(join_lines(&patched, raw))
+}
+
+/// Remove one item from a file, returning the new contents and the number
+/// of items left
+fn remove_item(raw: &str, uid: &str) -> Option<(String, usize)> {
+    let mut lines: Vec<String> = unfold_lines(raw).into_iter().map(|(_, l)| l).collect();
+    let (begin, end) = find_item(&lines, uid)?;
+    lines.drain(begin..=end);
+    let remaining = lines
+        .iter()
+        .filter(|line| {
+            property_name(line) == "BEGIN" && {
+                let name = line[6..].trim().to_ascii_uppercase();
+                name == "VEVENT" || name == "VTODO"
+            }
+        })
+        .count();
+    Some((join_lines(&lines, raw), remaining))
+}
+
+/// Fold and join content lines with the line ending `original` used
+fn join_lines(lines: &[String], original: &str) -> String {
+    let eol = if original.contains("\r\n") {
+        "\r\n"
+    } else {
+        "\n"
+    };
+    let mut out = String::new();
+    for line in lines {
+        let mut rest = line.as_str();
+        let mut limit = MAX_LINE_OCTETS;
+        while rest.len() > limit {
+            let mut cut = limit;
+            while !rest.is_char_boundary(cut) {
+                cut -= 1;
+            }
+            out.push_str(&rest[..cut]);
+            out.push_str(eol);
+            out.push(' ');
+            rest = &rest[cut..];
+            limit = MAX_LINE_OCTETS - 1;
+        }
+        out.push_str(rest);
+        out.push_str(eol);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use tempfile::TempDir;
+
+    fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 3, 5, 12, 0, 0).unwrap()
+    }
+
+    fn write(dir: &TempDir, name: &str, body: &str) {
+        let ics = format!(
+            "BEGIN:VCALENDAR\r\nVERSION:2.0\r\n{}END:VCALENDAR\r\n",
+            body
+        );
+        fs::write(dir.path().join(name), ics).unwrap();
+    }
+
+    fn todo(uid: &str, summary: &str, due: &str) -> String {
+        format!(
+            "BEGIN:VTODO\r\nUID:{}\r\nSUMMARY:{}\r\nDUE:{}\r\nBEGIN:VALARM\r\n\
+             ACTION:DISPLAY\r\nTRIGGER:-PT1H\r\nEND:VALARM\r\nEND:VTODO\r\n",
+            uid, summary, due
+        )
+    }
+
+    fn sync(db: &mut BoardDatabase, dir: &TempDir, options: SyncOptions) -> SyncReport {
+        sync_calendar(db, &CalendarSource::new(dir.path()), &options, now()).unwrap()
+    }
+
+    #[test]
+    fn test_import_creates_linked_cards() {
+        let dir = TempDir::new().unwrap();
+        write(
+            &dir,
+            "a.ics",
+            &todo("a@x", "Renew passport", "20250310T090000Z"),
+        );
+        let mut db = BoardDatabase::default();
+
+        let report = sync(&mut db, &dir, SyncOptions::default());
+
+        assert_eq!(report.imported.len(), 1);
+        let card = db.cards.get_card(&report.imported[0]).unwrap();
+        assert_eq!(card.title, "Renew passport");
+        assert_eq!(
+            card.due_date,
+            Some(Utc.with_ymd_and_hms(2025, 3, 10, 9, 0, 0).unwrap())
+        );
+        assert!(db.board_of_card(&card.id).is_some());
+        assert_eq!(db.calendar.uid_for_card(&card.id), Some("a@x"));
+
+        let again = sync(&mut db, &dir, SyncOptions::default());
+        assert!(!again.changed_board(), "{:?}", again);
+        assert_eq!(again.summary(), "Calendar in sync");
+    }
+
+    #[test]
+    fn test_card_edit_is_written_back_in_place() {
+        let dir = TempDir::new().unwrap();
+        write(
+            &dir,
+            "a.ics",
+            &todo("a@x", "Renew passport", "20250310T090000Z"),
+        );
+        let mut db = BoardDatabase::default();
+        let card_id = sync(&mut db, &dir, SyncOptions::default()).imported[0].clone();
+
+        let card = db.cards.get_card_mut(&card_id).unwrap();
+        card.title = "Renew passport; book photo".to_string();
+        card.priority = Priority::High;
+        let report = sync(&mut db, &dir, SyncOptions::default());
+
+        assert_eq!(report.updated_items, vec!["a@x".to_string()]);
+        let raw = fs::read_to_string(dir.path().join("a.ics")).unwrap();
+        assert!(raw.contains("SUMMARY:Renew passport\\; book photo"));
+        assert!(raw.contains("PRIORITY:3"));
+        assert!(raw.contains("SEQUENCE:1"));
+        assert!(raw.contains("TRIGGER:-PT1H"), "alarm is kept");
+        assert!(!sync(&mut db, &dir, SyncOptions::default()).changed_board());
+    }
+
+    #[test]
+    fn test_calendar_edit_updates_card() {
+        let dir = TempDir::new().unwrap();
+        write(
+            &dir,
+            "a.ics",
+            &todo("a@x", "Renew passport", "20250310T090000Z"),
+        );
+        let mut db = BoardDatabase::default();
+        let card_id = sync(&mut db, &dir, SyncOptions::default()).imported[0].clone();
+
+        write(
+            &dir,
+            "a.ics",
+            "BEGIN:VTODO\r\nUID:a@x\r\nSUMMARY:Renew passport\r\nDUE:20250312T090000Z\r\n\
+             STATUS:COMPLETED\r\nEND:VTODO\r\n",
+        );
+        let report = sync(&mut db, &dir, SyncOptions::default());
+
+        assert_eq!(report.updated_cards, vec![card_id.clone()]);
+        let card = db.cards.get_card(&card_id).unwrap();
+        assert_eq!(card.due_date.unwrap().format("%d").to_string(), "12");
+        assert_eq!(card.status, "Done");
+    }
+
+    #[test]
+    fn test_conflicts_follow_policy() {
+        let dir = TempDir::new().unwrap();
+        write(&dir, "a.ics", &todo("a@x", "Original", "20250310T090000Z"));
+        let mut db = BoardDatabase::default();
+        let card_id = sync(&mut db, &dir, SyncOptions::default()).imported[0].clone();
+
+        db.cards.get_card_mut(&card_id).unwrap().title = "Board title".to_string();
+        write(
+            &dir,
+            "a.ics",
+            &todo("a@x", "Calendar title", "20250310T090000Z"),
+        );
+
+        let report = sync(&mut db, &dir, SyncOptions::default());
+        assert_eq!(report.conflicts.len(), 1);
+        assert_eq!(report.conflicts[0].calendar.title, "Calendar title");
+        assert_eq!(db.cards.get_card(&card_id).unwrap().title, "Board title");
+
+        let options = SyncOptions {
+            conflicts: ConflictPolicy::PreferCalendar,
+            ..SyncOptions::default()
+        };
+        let report = sync(&mut db, &dir, options);
+        assert!(report.conflicts.is_empty());
+        assert_eq!(db.cards.get_card(&card_id).unwrap().title, "Calendar title");
+    }
+
+    #[test]
+    fn test_new_cards_are_exported() {
+        let dir = TempDir::new().unwrap();
+        let mut db = BoardDatabase::default();
+        let card_id = db.cards.create_card(
+            "Write release notes".to_string(),
+            "For 1.2".to_string(),
+            "To Do".to_string(),
+            Priority::Critical,
+            "me".to_string(),
+        );
+        db.cards.get_card_mut(&card_id).unwrap().due_date =
+            Some(Utc.with_ymd_and_hms(2025, 3, 20, 0, 0, 0).unwrap());
+        db.cards.create_card(
+            "No due date".to_string(),
+            String::new(),
+            "To Do".to_string(),
+            Priority::Low,
+            "me".to_string(),
+        );
+
+        let report = sync(&mut db, &dir, SyncOptions::default());
+
+        assert_eq!(report.exported.len(), 1);
+        let uid = &report.exported[0];
+        let raw = fs::read_to_string(dir.path().join(file_name_for(uid))).unwrap();
+        assert!(raw.contains("BEGIN:VTODO"));
+        assert!(raw.contains("DUE;VALUE=DATE:20250320"));
+        assert!(!sync(&mut db, &dir, SyncOptions::default()).changed_board());
+    }
+
+    #[test]
+    fn test_deletions_on_either_side() {
+        let dir = TempDir::new().unwrap();
+        write(
+            &dir,
+            "both.ics",
+            &format!(
+                "{}{}",
+                todo("a@x", "First", "20250310T090000Z"),
+                todo("b@x", "Second", "20250311T090000Z")
+            ),
+        );
+        let mut db = BoardDatabase::default();
+        let imported = sync(&mut db, &dir, SyncOptions::default()).imported;
+        let first = db.calendar.links["a@x"].card_id.clone();
+        assert_eq!(imported.len(), 2);
+
+        db.cards.delete_card(&first);
+        db.boards.remove_card(&first);
+        let report = sync(&mut db, &dir, SyncOptions::default());
+        assert_eq!(report.deleted_items, vec!["a@x".to_string()]);
+        let raw = fs::read_to_string(dir.path().join("both.ics")).unwrap();
+        assert!(!raw.contains("UID:a@x") && raw.contains("UID:b@x"));
+
+        fs::remove_file(dir.path().join("both.ics")).unwrap();
+        let report = sync(&mut db, &dir, SyncOptions::default());
+        assert_eq!(report.detached.len(), 1);
+        assert!(db.calendar.links.is_empty());
+        let again = sync(&mut db, &dir, SyncOptions::default());
+        assert!(
+            again.exported.is_empty(),
+            "detached cards are not re-exported"
+        );
+    }
+
+    #[test]
+    fn test_recurring_item_rolls_to_next_occurrence() {
+        let dir = TempDir::new().unwrap();
+        write(
+            &dir,
+            "r.ics",
+            "BEGIN:VTODO\r\nUID:r@x\r\nSUMMARY:Water plants\r\nDUE:20250303T080000Z\r\n\
+             RRULE:FREQ=WEEKLY\r\nEND:VTODO\r\n",
+        );
+        let mut db = BoardDatabase::default();
+        let card_id = sync(&mut db, &dir, SyncOptions::default()).imported[0].clone();
+
+        let card = db.cards.get_card(&card_id).unwrap();
+        assert_eq!(
+            card.due_date,
+            Some(Utc.with_ymd_and_hms(2025, 3, 10, 8, 0, 0).unwrap())
+        );
+        assert_eq!(card.recurrence.as_deref(), Some("weekly"));
+        assert!(!sync(&mut db, &dir, SyncOptions::default()).changed_board());
+
+        db.move_card_to_named(&card_id, "Done").unwrap();
+        let report = sync(&mut db, &dir, SyncOptions::default());
+        assert_eq!(report.updated_cards, vec![card_id.clone()]);
+        assert!(report.updated_items.is_empty(), "the series stays open");
+        let card = db.cards.get_card(&card_id).unwrap();
+        assert_eq!(card.status, "To Do");
+        assert_eq!(
+            card.due_date,
+            Some(Utc.with_ymd_and_hms(2025, 3, 17, 8, 0, 0).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_unreadable_file_keeps_links() {
+        let dir = TempDir::new().unwrap();
+        write(&dir, "a.ics", &todo("a@x", "First", "20250310T090000Z"));
+        let mut db = BoardDatabase::default();
+        sync(&mut db, &dir, SyncOptions::default());
+
+        fs::write(
+            dir.path().join("a.ics"),
+            "BEGIN:VCALENDAR\r\nBEGIN:VTODO\r\n",
+        )
+        .unwrap();
+        let report = sync(&mut db, &dir, SyncOptions::default());
+
+        assert!(report.detached.is_empty());
+        assert!(db.calendar.links.contains_key("a@x"));
+        assert!(report.warnings[0].starts_with("a.ics"));
+    }
+
+    #[test]
+    fn test_source_detects_changes() {
+        let dir = TempDir::new().unwrap();
+        let mut source = CalendarSource::new(dir.path().join("cal"));
+        assert!(source.has_changed());
+        assert!(!source.has_changed());
+
+        fs::create_dir(dir.path().join("cal")).unwrap();
+        fs::write(
+            dir.path().join("cal/x.ics"),
+            "BEGIN:VCALENDAR\nEND:VCALENDAR\n",
+        )
+        .unwrap();
+        fs::write(dir.path().join("cal/notes.txt"), "ignored").unwrap();
+        assert!(source.has_changed());
+        assert!(!source.has_changed());
+    }
+
+    #[test]
+    fn test_long_lines_are_folded() {
+        let line = "DESCRIPTION:".to_string() + &"é".repeat(60);
+        let joined = join_lines(&[line.clone()], "\r\n");
+        assert!(joined.split("\r\n").all(|l| l.len() <= MAX_LINE_OCTETS));
+        let unfolded: Vec<String> = unfold_lines(&joined).into_iter().map(|(_, l)| l).collect();
+        assert_eq!(unfolded, vec![line]);
+    }
+}
@@ -273,7 +273,7 @@ impl ActivityLogEntry {
 }
 
 /// Comment thread manager
-#[derive(Debug)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CommentManager {
     comments: HashMap<String, Comment>,
     activities: Vec<ActivityLogEntry>,
@@ -440,6 +440,11 @@ impl CommentManager {
         id
     }
 
+    /// Gets a logged activity for adding metadata
+    pub fn get_activity_mut(&mut self, activity_id: &str) -> Option<&mut ActivityLogEntry> {
+        self.activities.iter_mut().find(|a| a.id == activity_id)
+    }
+
     /// Gets activity log for a card
     pub fn activities_for_card(&self, card_id: &str) -> Vec<&ActivityLogEntry> {
         self.activities
@@ -0,0 +1,340 @@
+//! Dated schedules for board cards
+//!
+//! Runs the critical path pass from [`task_dependencies`] over the cards in a
+//! [`BoardDatabase`] and turns the resulting day offsets into calendar dates.
+//! Card durations come from their effort estimates; a card's `start_date`
+//! pins it so it does not start earlier.
+//!
+//! # Examples
+//!
+//! ```
+//! use chrono::Utc;
+//! use toad::infrastructure::board_store::BoardDatabase;
+//! use toad::infrastructure::card_schedule::CardSchedule;
+//! use toad::infrastructure::{DependencyType, EffortEstimate, Priority};
+//!
+//! let mut db = BoardDatabase::default();
+//! let design = db.cards.create_card(
+//!     "Design".to_string(),
+//!     String::new(),
+//!     "To Do".to_string(),
+//!     Priority::High,
+//!     "me".to_string(),
+//! );
+//! let build = db.cards.create_card(
+//!     "Build".to_string(),
+//!     String::new(),
+//!     "To Do".to_string(),
+//!     Priority::High,
+//!     "me".to_string(),
+//! );
+//! db.cards.get_card_mut(&design).unwrap().effort_estimate = Some(EffortEstimate::Days(2.0));
+//! db.dependencies
+//!     .create_dependency(design.clone(), build.clone(), DependencyType::Blocks, "me".to_string())
+//!     .unwrap();
+//!
+//! let schedule = CardSchedule::build(&db, Utc::now()).unwrap();
+//! assert_eq!(schedule.get(&build).unwrap().node.earliest_start, 2.0);
+//! assert!(schedule.get(&build).unwrap().node.is_critical);
+//! ```
+//!
+//! [`task_dependencies`]: crate::infrastructure::task_dependencies
+
+use crate::infrastructure::board_store::BoardDatabase;
+use crate::infrastructure::rich_task_cards::RichTaskCard;
+use crate::infrastructure::task_dependencies::CriticalPathNode;
+use chrono::{DateTime, Duration, NaiveTime, Utc};
+use std::collections::HashMap;
+
+/// Duration of a card without an effort estimate (in days)
+pub const DEFAULT_DURATION_DAYS: f32 = 1.0;
+
+/// Working hours in a scheduled day
+pub const HOURS_PER_DAY: f32 = 8.0;
+
+/// Activity type logged when a card is rescheduled
+pub const RESCHEDULE_ACTIVITY: &str = "rescheduled";
+
+/// A card placed on the schedule
+#[derive(Debug, Clone)]
+pub struct ScheduledCard {
+    /// Card ID
+    pub card_id: String,
+    /// Card title
+    pub title: String,
+    /// Critical path timings (in days from the schedule origin)
+    pub node: CriticalPathNode,
+    /// Whether the card's start is pinned by its `start_date`
+    pub pinned: bool,
+    /// Earliest start date
+    pub start: DateTime<Utc>,
+    /// Earliest finish date
+    pub finish: DateTime<Utc>,
+    /// Latest finish date that does not delay the project
+    pub latest_finish: DateTime<Utc>,
+}
+
+/// Schedule of every card on a board
+#[derive(Debug, Clone)]
+pub struct CardSchedule {
+    /// Date of day zero (midnight UTC)
+    pub origin: DateTime<Utc>,
+    /// Scheduled cards in dependency order
+    pub cards: Vec<ScheduledCard>,
+}
+
+impl CardSchedule {
+    /// Schedule all cards in `db`
+    ///
+    /// Day zero is the start of `today`, or the earliest pinned start if that
+    /// is earlier.
+    ///
+    /// # Errors
+    ///
+    /// Returns the IDs of the cards caught in a dependency cycle.
+    pub fn build(db: &BoardDatabase, today: DateTime<Utc>) -> Result<Self, Vec<String>> {
+        let cards = db.cards.cards();
+        let origin = cards
+            .iter()
+            .filter_map(|card| card.start_date)
+            .fold(today, DateTime::min)
+            .with_time(NaiveTime::MIN)
+            .single()
+            .unwrap_or(today);
+
+        let durations: HashMap<String, f32> = cards
+            .iter()
+            .map(|card| (card.id.clone(), duration_days(card)))
+            .collect();
+        let constraints: HashMap<String, f32> = cards
+            .iter()
+            .filter_map(|card| Some((card.id.clone(), days_between(origin, card.start_date?))))
+            .collect();
+
+        let nodes = db.dependencies.schedule(&durations, &constraints)?;
+        let cards = nodes
+            .into_iter()
+            .filter_map(|node| {
+                let card = db.cards.get_card(&node.task_id)?;
+                Some(ScheduledCard {
+                    card_id: card.id.clone(),
+                    title: card.title.clone(),
+                    pinned: card.start_date.is_some(),
+                    start: date_at(origin, node.earliest_start),
+                    finish: date_at(origin, node.earliest_finish()),
+                    latest_finish: date_at(origin, node.latest_finish()),
+                    node,
+                })
+            })
+            .collect();
+        Ok(Self { origin, cards })
+    }
+
+    /// Get a card's place on the schedule
+    pub fn get(&self, card_id: &str) -> Option<&ScheduledCard> {
+        self.cards.iter().find(|card| card.card_id == card_id)
+    }
+
+    /// Cards on the critical path, in dependency order
+    pub fn critical_path(&self) -> Vec<&ScheduledCard> {
+        self.cards
+            .iter()
+            .filter(|card| card.node.is_critical)
+            .collect()
+    }
+
+    /// Length of the schedule in days
+    pub fn total_days(&self) -> f32 {
+        self.cards
+            .iter()
+            .map(|card| card.node.earliest_finish())
+            .fold(0.0, f32::max)
+    }
+
+    /// Date the last card finishes
+    pub fn finish(&self) -> DateTime<Utc> {
+        date_at(self.origin, self.total_days())
+    }
+}
+
+/// Scheduled duration of a card (in days)
+pub fn duration_days(card: &RichTaskCard) -> f32 {
+    card.effort_estimate
+        .as_ref()
+        .map(|estimate| estimate.to_hours() / HOURS_PER_DAY)
+        .unwrap_or(DEFAULT_DURATION_DAYS)
+}
+
+/// Move a card's start and reschedule everything that depends on it
+///
+/// Pins the card to `start`, then sets each dependent's due date to its new
+/// scheduled finish and logs the change in its activity. Returns the IDs of
+/// the dependents whose due date changed.
+///
+/// # Errors
+///
+/// Fails if the card doesn't exist or the dependencies contain a cycle.
+pub fn reschedule_card(
+    db: &mut BoardDatabase,
+    card_id: &str,
+    start: DateTime<Utc>,
+    today: DateTime<Utc>,
+) -> Result<Vec<String>, String> {
+    let card = db
+        .cards
+        .get_card_mut(card_id)
+        .ok_or_else(|| format!("Card {} not found", card_id))?;
+    card.start_date = Some(start);
+    card.updated_at = Utc::now();
+    let title = card.title.clone();
+
+    let schedule = CardSchedule::build(db, today)
+        .map_err(|cycle| format!("Dependency cycle between {}", cycle.join(", ")))?;
+
+    let mut changed = Vec::new();
+    for dependent in db.dependencies.dependents(card_id) {
+        let Some(finish) = schedule.get(&dependent).map(|scheduled| scheduled.finish) else {
+            continue;
+        };
+        let Some(card) = db.cards.get_card_mut(&dependent) else {
+            continue;
+        };
+        if card.due_date == Some(finish) {
+            continue;
+        }
+        card.due_date = Some(finish);
+        card.updated_at = Utc::now();
+        db.comments.log_activity(
+            dependent.clone(),
+            RESCHEDULE_ACTIVITY.to_string(),
+            format!(
+                "Due {} after {} moved",
+                finish.format("%Y-%m-%d %H:%M"),
+                title
+            ),
+            None,
+            None,
+        );
+        changed.push(dependent);
+    }
+    Ok(changed)
+}
+
+/// Date `days` after `origin`
+pub fn date_at(origin: DateTime<Utc>, days: f32) -> DateTime<Utc> {
+    origin + Duration::seconds((days * 86_400.0).round() as i64)
+}
+
+/// Days from `origin` to `date`
+pub fn days_between(origin: DateTime<Utc>, date: DateTime<Utc>) -> f32 {
+    (date - origin).num_seconds() as f32 / 86_400.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::rich_task_cards::{EffortEstimate, Priority};
+    use crate::infrastructure::task_dependencies::DependencyType;
+    use chrono::TimeZone;
+
+    fn today() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 3, 10, 14, 30, 0).unwrap()
+    }
+
+    fn add_card(db: &mut BoardDatabase, title: &str, days: f32) -> String {
+        let id = db.cards.create_card(
+            title.to_string(),
+            String::new(),
+            "To Do".to_string(),
+            Priority::Medium,
+            "me".to_string(),
+        );
+        db.cards.get_card_mut(&id).unwrap().effort_estimate = Some(EffortEstimate::Days(days));
+        id
+    }
+
+    fn block(db: &mut BoardDatabase, from: &str, to: &str) -> String {
+        db.dependencies
+            .create_dependency(
+                from.to_string(),
+                to.to_string(),
+                DependencyType::Blocks,
+                "me".to_string(),
+            )
+            .unwrap()
+    }
+
+    #[test]
+    fn test_schedule_dates_from_start_of_today() {
+        let mut db = BoardDatabase::default();
+        let design = add_card(&mut db, "Design", 2.0);
+        let build = add_card(&mut db, "Build", 3.0);
+        block(&mut db, &design, &build);
+
+        let schedule = CardSchedule::build(&db, today()).unwrap();
+        let origin = Utc.with_ymd_and_hms(2025, 3, 10, 0, 0, 0).unwrap();
+        assert_eq!(schedule.origin, origin);
+        let build = schedule.get(&build).unwrap();
+        assert_eq!(build.start, origin + Duration::days(2));
+        assert_eq!(build.finish, origin + Duration::days(5));
+        assert_eq!(schedule.total_days(), 5.0);
+        assert_eq!(schedule.critical_path().len(), 2);
+    }
+
+    #[test]
+    fn test_cards_without_estimate_take_a_day() {
+        let mut db = BoardDatabase::default();
+        let id = add_card(&mut db, "Quick fix", 1.0);
+        db.cards.get_card_mut(&id).unwrap().effort_estimate = None;
+        assert_eq!(duration_days(db.cards.get_card(&id).unwrap()), 1.0);
+        db.cards.get_card_mut(&id).unwrap().effort_estimate = Some(EffortEstimate::Hours(4.0));
+        assert_eq!(duration_days(db.cards.get_card(&id).unwrap()), 0.5);
+    }
+
+    #[test]
+    fn test_pinned_start_delays_card() {
+        let mut db = BoardDatabase::default();
+        let id = add_card(&mut db, "Launch", 1.0);
+        db.cards.get_card_mut(&id).unwrap().start_date = Some(today() + Duration::days(3));
+
+        let schedule = CardSchedule::build(&db, today()).unwrap();
+        let launch = schedule.get(&id).unwrap();
+        assert!(launch.pinned);
+        assert_eq!(launch.start, today() + Duration::days(3));
+    }
+
+    #[test]
+    fn test_reschedule_moves_dependents() {
+        let mut db = BoardDatabase::default();
+        let design = add_card(&mut db, "Design", 2.0);
+        let build = add_card(&mut db, "Build", 1.0);
+        let ship = add_card(&mut db, "Ship", 1.0);
+        let other = add_card(&mut db, "Unrelated", 1.0);
+        block(&mut db, &design, &build);
+        block(&mut db, &build, &ship);
+
+        let start = Utc.with_ymd_and_hms(2025, 3, 12, 0, 0, 0).unwrap();
+        let changed = reschedule_card(&mut db, &design, start, today()).unwrap();
+
+        assert_eq!(changed, vec![build.clone(), ship.clone()]);
+        assert_eq!(
+            db.cards.get_card(&ship).unwrap().due_date,
+            Some(start + Duration::days(4))
+        );
+        assert!(db.cards.get_card(&other).unwrap().due_date.is_none());
+        assert_eq!(
+            db.comments.activities_for_card(&ship)[0].activity_type,
+            RESCHEDULE_ACTIVITY
+        );
+
+        // Same start again changes nothing
+        let changed = reschedule_card(&mut db, &design, start, today()).unwrap();
+        assert!(changed.is_empty());
+    }
+
+    #[test]
+    fn test_reschedule_unknown_card_fails() {
+        let mut db = BoardDatabase::default();
+        assert!(reschedule_card(&mut db, "card-9", today(), today()).is_err());
+    }
+}
@@ -286,6 +286,20 @@ impl CrossWindowContextManager {
         id
     }
 
+    /// Add an entry received from another window
+    ///
+    /// The entry keeps its source window and timestamp but gets a local ID.
+    pub fn receive_clipboard_entry(&mut self, mut entry: ClipboardEntry) -> String {
+        let id = format!("clip-{}", self.next_clipboard_id);
+        self.next_clipboard_id += 1;
+
+        entry.id = id.clone();
+        self.clipboard.insert(0, entry);
+        self.trim_clipboard();
+
+        id
+    }
+
     /// Get the most recent clipboard entry
     pub fn get_latest_clipboard(&self) -> Option<&ClipboardEntry> {
         self.clipboard.first()
@@ -436,6 +450,18 @@ impl CrossWindowContextManager {
         id
     }
 
+    /// Insert or replace an agent context received from another window
+    ///
+    /// Older copies than the one already known are ignored.
+    pub fn upsert_agent_context(&mut self, context: SharedAgentContext) {
+        match self.agent_contexts.get(&context.id) {
+            Some(known) if known.updated_at > context.updated_at => {}
+            _ => {
+                self.agent_contexts.insert(context.id.clone(), context);
+            }
+        }
+    }
+
     /// Get a shared agent context
     pub fn get_agent_context(&self, context_id: &str) -> Option<&SharedAgentContext> {
         self.agent_contexts.get(context_id)
@@ -941,4 +967,46 @@ mod tests {
         assert_eq!(manager.drag_drop_operations.len(), 1);
         assert!(manager.get_drag_drop_operation(&id2).is_some());
     }
+
+    #[test]
+    fn test_receive_clipboard_entry_assigns_local_id() {
+        let mut manager = CrossWindowContextManager::new();
+        manager.add_to_clipboard(
+            "window-1".to_string(),
+            ClipboardContentType::Text,
+            "local".to_string(),
+            None,
+        );
+
+        let remote = ClipboardEntry::new(
+            "clip-1".to_string(),
+            "window-2".to_string(),
+            ClipboardContentType::Text,
+            "remote".to_string(),
+        );
+        let id = manager.receive_clipboard_entry(remote);
+
+        assert_eq!(id, "clip-2");
+        let latest = manager.get_latest_clipboard().unwrap();
+        assert_eq!(latest.content, "remote");
+        assert_eq!(latest.source_window_id, "window-2");
+    }
+
+    #[test]
+    fn test_upsert_agent_context_keeps_newest() {
+        let mut manager = CrossWindowContextManager::new();
+        let mut context = SharedAgentContext::new(
+            "window-2".to_string(),
+            "coder".to_string(),
+            "{\"step\":2}".to_string(),
+        );
+        manager.upsert_agent_context(context.clone());
+
+        context.context_data = "{\"step\":1}".to_string();
+        context.updated_at -= chrono::Duration::seconds(5);
+        manager.upsert_agent_context(context.clone());
+
+        let known = manager.get_agent_context(&context.id).unwrap();
+        assert_eq!(known.context_data, "{\"step\":2}");
+    }
 }
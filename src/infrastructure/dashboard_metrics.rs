@@ -16,6 +16,8 @@
 // - **Blocked Tasks Report**: List of tasks waiting on dependencies
 // - **Team Performance**: Individual contributor metrics
 
+use crate::infrastructure::ai_task_intelligence::{activity_column, moves_of};
+use crate::infrastructure::board_store::BoardDatabase;
 use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -169,6 +171,67 @@ impl CycleTimeMetric {
     pub fn cycle_time_duration(&self) -> Duration {
         self.completion_time.signed_duration_since(self.start_time)
     }
+
+    /// Cycle times of the cards in a board's last (done) column
+    ///
+    /// Work on a card starts at the earliest of its first move to a column
+    /// between the first and the last, its first time entry (timers, agent runs and estimates
+    /// from commit history) and its first linked commit. It completes at the
+    /// card's last move into the last column. Cards without a recorded start
+    /// or completion are skipped.
+    pub fn from_board(db: &BoardDatabase, board_id: &str) -> Vec<Self> {
+        let columns = db.boards.columns_for_board(board_id);
+        let (Some(first), Some(done)) = (columns.first(), columns.last()) else {
+            return Vec::new();
+        };
+
+        let mut metrics = Vec::new();
+        for card_id in &done.card_ids {
+            let Some(card) = db.cards.get_card(card_id) else {
+                continue;
+            };
+            let moves = moves_of(db, card_id);
+            let Some(completed) = moves
+                .iter()
+                .rev()
+                .find(|activity| activity_column(activity, &columns) == Some(done.id.as_str()))
+                .map(|activity| activity.created_at)
+            else {
+                continue;
+            };
+
+            let started_moving = moves
+                .iter()
+                .find(|activity| {
+                    let column = activity_column(activity, &columns);
+                    column != Some(first.id.as_str()) && column != Some(done.id.as_str())
+                })
+                .map(|activity| activity.created_at);
+            let first_entry = db
+                .time
+                .entries_for_task(card_id)
+                .iter()
+                .map(|entry| entry.start)
+                .min();
+            let first_commit = db
+                .git
+                .get_card_commits(card_id)
+                .iter()
+                .map(|commit| commit.committed_at)
+                .min();
+            let Some(started) = [started_moving, first_entry, first_commit]
+                .into_iter()
+                .flatten()
+                .filter(|start| *start <= completed)
+                .min()
+            else {
+                continue;
+            };
+
+            metrics.push(Self::new(card_id, &card.title, started, completed));
+        }
+        metrics
+    }
 }
 
 /// Lead time metric (time from creation to completion)
@@ -596,6 +659,18 @@ impl DashboardMetrics {
         self.cycle_times.push(metric);
     }
 
+    /// Record the cycle times of a board's done cards (see
+    /// [`CycleTimeMetric::from_board`]), replacing earlier ones for the same
+    /// cards; returns how many were recorded
+    pub fn record_board_cycle_times(&mut self, db: &BoardDatabase, board_id: &str) -> usize {
+        let metrics = CycleTimeMetric::from_board(db, board_id);
+        self.cycle_times
+            .retain(|known| !metrics.iter().any(|m| m.task_id == known.task_id));
+        let count = metrics.len();
+        self.cycle_times.extend(metrics);
+        count
+    }
+
     /// Record lead time
     pub fn record_lead_time(&mut self, metric: LeadTimeMetric) {
         self.lead_times.push(metric);
@@ -922,4 +997,50 @@ mod tests {
         assert_eq!(dashboard.team_member_count(), 1);
         assert_eq!(dashboard.team_metrics().len(), 1);
     }
+
+    #[test]
+    fn test_cycle_times_from_board_start_at_first_work() {
+        use crate::infrastructure::rich_task_cards::Priority;
+        use crate::infrastructure::time_tracking::TimeEntry;
+
+        let mut db = BoardDatabase::default();
+        let board_id = db.ensure_board();
+        let now = Utc::now();
+        let add_card = |db: &mut BoardDatabase, title: &str| {
+            let card_id = db.cards.create_card(
+                title.to_string(),
+                String::new(),
+                "To Do".to_string(),
+                Priority::Medium,
+                "me".to_string(),
+            );
+            db.move_card_to_named(&card_id, "Done").unwrap();
+            let activity = db
+                .comments
+                .activities_for_card(&card_id)
+                .last()
+                .unwrap()
+                .id
+                .clone();
+            db.comments.get_activity_mut(&activity).unwrap().created_at = now;
+            card_id
+        };
+        let estimated = add_card(&mut db, "Estimated from commits");
+        db.time.add_entry(TimeEntry::estimated(
+            &estimated,
+            now - Duration::hours(30),
+            Duration::hours(1),
+        ));
+        add_card(&mut db, "Never started");
+
+        let metrics = CycleTimeMetric::from_board(&db, &board_id);
+        assert_eq!(metrics.len(), 1);
+        assert_eq!(metrics[0].task_id, estimated);
+        assert!((metrics[0].cycle_time_hours - 30.0).abs() < 1e-6);
+
+        let mut dashboard = DashboardMetrics::new();
+        assert_eq!(dashboard.record_board_cycle_times(&db, &board_id), 1);
+        assert_eq!(dashboard.record_board_cycle_times(&db, &board_id), 1);
+        assert!((dashboard.avg_cycle_time() - 30.0).abs() < 1e-6);
+    }
 }
@@ -226,7 +226,7 @@ impl Attachment {
 }
 
 /// Attachment manager
-#[derive(Debug)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AttachmentManager {
     attachments: HashMap<String, Attachment>,
     card_attachments: HashMap<String, Vec<String>>, // card_id -> attachment_ids
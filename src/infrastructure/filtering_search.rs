@@ -11,7 +11,8 @@
 // - **Filter by Everything**: Tags, assignee, date range, priority, status, custom fields
 // - **Search Syntax**: Power user queries like `assignee:me priority:P0 -tag:blocked`
 
-use chrono::{DateTime, Utc};
+use crate::infrastructure::rich_task_cards::{CustomFieldType, RichTaskCard};
+use chrono::{DateTime, Duration, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -102,6 +103,139 @@ impl FilterCondition {
     pub fn not_contains(field: FilterField, value: impl Into<String>) -> Self {
         Self::new(field, FilterOperator::NotContains, value)
     }
+
+    /// Check whether a card satisfies this condition
+    ///
+    /// Comparisons are case-insensitive. Text fields (title, description,
+    /// full text) match substrings for both `Equals` and `Contains`; tags and
+    /// assignees match when any entry matches, and `me` stands for
+    /// `current_user`. Priorities accept `P0`-`P3` or the level name; dates
+    /// accept `now`, `today`, `week` (seven days from now) or `YYYY-MM-DD`.
+    pub fn matches(&self, card: &RichTaskCard, current_user: &str) -> bool {
+        let (operator, negated) = match self.operator {
+            FilterOperator::NotEquals => (FilterOperator::Equals, true),
+            FilterOperator::NotContains => (FilterOperator::Contains, true),
+            FilterOperator::NotIn => (FilterOperator::In, true),
+            operator => (operator, false),
+        };
+        let value = self.value.to_lowercase();
+
+        let matched = match &self.field {
+            FilterField::Title => text_matches(operator, &card.title, &value),
+            FilterField::Description => text_matches(operator, &card.description, &value),
+            FilterField::FullText => {
+                let tags: Vec<&str> = card.tags.iter().map(|t| t.name.as_str()).collect();
+                let text = format!(
+                    "{} {} {} {}",
+                    card.title,
+                    card.description,
+                    card.status,
+                    tags.join(" ")
+                );
+                text_matches(operator, &text, &value)
+            }
+            FilterField::Tags => card
+                .tags
+                .iter()
+                .any(|tag| value_matches(operator, &tag.name, &value)),
+            FilterField::Assignee => {
+                let value = if value == "me" {
+                    current_user.to_lowercase()
+                } else {
+                    value
+                };
+                if value.is_empty() {
+                    card.assignees.is_empty()
+                } else {
+                    card.assignees.iter().any(|a| {
+                        value_matches(operator, &a.user_id, &value)
+                            || value_matches(operator, &a.display_name, &value)
+                    })
+                }
+            }
+            FilterField::Priority => {
+                let level = card.priority.to_value();
+                let name = format!("{:?}", card.priority);
+                value_matches(operator, &format!("p{}", level), &value)
+                    || value_matches(operator, &name, &value)
+            }
+            FilterField::Status => value_matches(
+                operator,
+                &normalize_status(&card.status),
+                &normalize_status(&value),
+            ),
+            FilterField::DueDate => date_matches(operator, card.due_date, &value),
+            FilterField::CreatedDate => date_matches(operator, Some(card.created_at), &value),
+            FilterField::ModifiedDate => date_matches(operator, Some(card.updated_at), &value),
+            FilterField::CustomField(name) => card.custom_fields.get(name).is_some_and(|field| {
+                let actual = match &field.value {
+                    CustomFieldType::Text(text) => text.clone(),
+                    CustomFieldType::Number(number) => number.to_string(),
+                    CustomFieldType::Dropdown(selected, _) => selected.clone(),
+                    CustomFieldType::Date(date) => date.format("%Y-%m-%d").to_string(),
+                };
+                value_matches(operator, &actual, &value)
+            }),
+        };
+        matched != negated
+    }
+}
+
+/// Substring match for free text (`Equals` behaves like `Contains`)
+fn text_matches(operator: FilterOperator, actual: &str, value: &str) -> bool {
+    match operator {
+        FilterOperator::Equals => actual.to_lowercase().contains(value),
+        _ => value_matches(operator, actual, value),
+    }
+}
+
+/// Case-insensitive comparison of a single value (`value` is lowercase)
+fn value_matches(operator: FilterOperator, actual: &str, value: &str) -> bool {
+    let actual = actual.to_lowercase();
+    match operator {
+        FilterOperator::Equals => actual == value,
+        FilterOperator::Contains => actual.contains(value),
+        FilterOperator::StartsWith => actual.starts_with(value),
+        FilterOperator::EndsWith => actual.ends_with(value),
+        FilterOperator::In => value.split(',').any(|v| actual == v.trim()),
+        FilterOperator::GreaterThan => actual.as_str() > value,
+        FilterOperator::LessThan => actual.as_str() < value,
+        FilterOperator::NotEquals | FilterOperator::NotContains | FilterOperator::NotIn => {
+            unreachable!("negated operators are resolved by the caller")
+        }
+    }
+}
+
+/// Status names compare equal regardless of case, spaces and dashes
+fn normalize_status(status: &str) -> String {
+    status.trim().to_lowercase().replace([' ', '-'], "_")
+}
+
+/// Compare a date against `now`, `today`, `week` or `YYYY-MM-DD`
+///
+/// Cards without the date never match.
+fn date_matches(operator: FilterOperator, actual: Option<DateTime<Utc>>, value: &str) -> bool {
+    let Some(actual) = actual else {
+        return false;
+    };
+    let now = Utc::now();
+    let target = match value {
+        "now" => now,
+        "today" => now,
+        "week" => now + Duration::days(7),
+        other => match NaiveDate::parse_from_str(other, "%Y-%m-%d") {
+            Ok(date) => date.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc(),
+            Err(_) => return false,
+        },
+    };
+    match operator {
+        FilterOperator::Equals | FilterOperator::Contains => {
+            actual.date_naive() == target.date_naive()
+        }
+        FilterOperator::GreaterThan => actual > target,
+        FilterOperator::LessThan => actual < target,
+        _ => false,
+    }
 }
 
 /// Logical operator for combining filters
@@ -163,6 +297,23 @@ impl Filter {
     pub fn is_empty(&self) -> bool {
         self.conditions.is_empty()
     }
+
+    /// Check whether a card passes this filter (empty filters match everything)
+    pub fn matches(&self, card: &RichTaskCard, current_user: &str) -> bool {
+        if self.conditions.is_empty() {
+            return true;
+        }
+        match self.logic {
+            LogicalOperator::And => self
+                .conditions
+                .iter()
+                .all(|c| c.matches(card, current_user)),
+            LogicalOperator::Or => self
+                .conditions
+                .iter()
+                .any(|c| c.matches(card, current_user)),
+        }
+    }
 }
 
 /// Quick filter templates
@@ -443,6 +594,13 @@ impl FilterManager {
         self.active_filter.as_ref()
     }
 
+    /// Check whether a card passes the active filter (true when none is set)
+    pub fn matches(&self, card: &RichTaskCard) -> bool {
+        self.active_filter
+            .as_ref()
+            .is_none_or(|filter| filter.matches(card, &self.current_user))
+    }
+
     /// Clear active filter
     pub fn clear_filter(&mut self) {
         self.active_filter = None;
@@ -490,6 +648,84 @@ impl Default for FilterManager {
     }
 }
 
+/// Filter typed live into a query bar
+///
+/// Keeps the query text next to its [`FilterManager`] so several views can
+/// share one filter. The query is re-parsed on every edit and only recorded
+/// in the search history once committed.
+#[derive(Debug, Default)]
+pub struct LiveFilter {
+    /// Query as typed
+    query: String,
+    /// Manager holding the parsed filter and search history
+    manager: FilterManager,
+}
+
+impl LiveFilter {
+    /// Create an empty filter
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Query as typed
+    pub fn query(&self) -> &str {
+        &self.query
+    }
+
+    /// Whether a non-blank query is set
+    pub fn is_active(&self) -> bool {
+        !self.query.trim().is_empty()
+    }
+
+    /// Replace the query
+    pub fn set_query(&mut self, query: impl Into<String>) {
+        self.query = query.into();
+        self.apply();
+    }
+
+    /// Append a typed character
+    pub fn push(&mut self, c: char) {
+        self.query.push(c);
+        self.apply();
+    }
+
+    /// Remove the last character
+    pub fn pop(&mut self) {
+        self.query.pop();
+        self.apply();
+    }
+
+    /// Clear the query
+    pub fn clear(&mut self) {
+        self.set_query(String::new());
+    }
+
+    /// Record the query in the search history
+    pub fn commit(&mut self) {
+        if self.is_active() {
+            self.manager.search(self.query.clone());
+        }
+    }
+
+    /// Check whether a card passes the filter
+    pub fn matches(&self, card: &RichTaskCard) -> bool {
+        self.manager.matches(card)
+    }
+
+    /// Underlying filter manager
+    pub fn manager(&self) -> &FilterManager {
+        &self.manager
+    }
+
+    fn apply(&mut self) {
+        if self.is_active() {
+            self.manager.apply_filter(SearchParser::parse(&self.query));
+        } else {
+            self.manager.clear_filter();
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -717,4 +953,111 @@ mod tests {
         manager.apply_filter(filter);
         assert!(manager.active_filter.is_some());
     }
+
+    fn sample_card() -> RichTaskCard {
+        use crate::infrastructure::rich_task_cards::{Assignee, Priority, Tag};
+        let mut card = RichTaskCard::new(
+            "card-1".to_string(),
+            "Fix login flow".to_string(),
+            "Users cannot sign in".to_string(),
+            "In Progress".to_string(),
+            Priority::High,
+            "alice".to_string(),
+        );
+        card.add_tag(Tag {
+            id: "tag-1".to_string(),
+            name: "backend".to_string(),
+            color: "#fff".to_string(),
+            created_at: Utc::now(),
+        });
+        card.add_assignee(Assignee {
+            user_id: "alice".to_string(),
+            display_name: "Alice".to_string(),
+            avatar: None,
+            assigned_at: Utc::now(),
+        });
+        card.due_date = Some(Utc::now() + Duration::days(2));
+        card
+    }
+
+    #[test]
+    fn test_filter_matches_search_syntax() {
+        let card = sample_card();
+        let matches = |query: &str| SearchParser::parse(query).matches(&card, "alice");
+
+        assert!(matches(""));
+        assert!(matches("login"));
+        assert!(matches("LOGIN tag:backend"));
+        assert!(matches("assignee:me priority:P1"));
+        assert!(matches("priority:high status:in_progress"));
+        assert!(matches("-tag:frontend"));
+        assert!(!matches("-tag:backend"));
+        assert!(!matches("signup"));
+        assert!(!matches("assignee:bob"));
+    }
+
+    #[test]
+    fn test_filter_matches_quick_filters() {
+        let card = sample_card();
+        let matches = |quick: QuickFilter| quick.to_filter("alice").matches(&card, "alice");
+
+        assert!(matches(QuickFilter::All));
+        assert!(matches(QuickFilter::MyTasks));
+        assert!(matches(QuickFilter::HighPriority));
+        assert!(matches(QuickFilter::DueThisWeek));
+        assert!(matches(QuickFilter::InProgress));
+        assert!(!matches(QuickFilter::Overdue));
+        assert!(!matches(QuickFilter::Unassigned));
+    }
+
+    #[test]
+    fn test_filter_manager_matches_active_filter() {
+        let card = sample_card();
+        let mut manager = FilterManager::new("bob");
+        assert!(manager.matches(&card));
+
+        manager.apply_quick_filter(QuickFilter::MyTasks);
+        assert!(!manager.matches(&card));
+
+        manager.apply_filter(SearchParser::parse("fix"));
+        assert!(manager.matches(&card));
+    }
+
+    #[test]
+    fn test_live_filter_applies_while_typing() {
+        let mut card = RichTaskCard::new(
+            "card-1".to_string(),
+            "Fix login".to_string(),
+            String::new(),
+            "todo".to_string(),
+            crate::infrastructure::rich_task_cards::Priority::High,
+            "me".to_string(),
+        );
+        let mut filter = LiveFilter::new();
+        assert!(filter.matches(&card));
+
+        for c in "signup".chars() {
+            filter.push(c);
+        }
+        assert!(!filter.matches(&card));
+        card.title = "Fix signup".to_string();
+        assert!(filter.matches(&card));
+
+        filter.clear();
+        assert!(!filter.is_active());
+        assert!(filter.manager().active_filter().is_none());
+    }
+
+    #[test]
+    fn test_live_filter_commit_records_history() {
+        let mut filter = LiveFilter::new();
+        filter.set_query("priority:P0");
+        assert!(filter.manager().search_history().is_empty());
+        filter.commit();
+        assert_eq!(filter.manager().search_history(), ["priority:P0"]);
+
+        filter.set_query("  ");
+        filter.commit();
+        assert_eq!(filter.manager().search_history().len(), 1);
+    }
 }
@@ -0,0 +1,92 @@
+//! Small filesystem helpers shared by the persistence code
+//!
+//! [`atomic_write`] is used wherever TOAD saves a file others may read at the
+//! same time: editor buffers, the board database, calendar sync files and the
+//! window IPC registry.
+
+use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
+
+/// Write a file via a temporary sibling and rename, so readers never see a
+/// partially written file
+///
+/// An existing file keeps its permissions. Writing through a symlink
+/// replaces the file it points to and leaves the link in place.
+pub fn atomic_write(path: &Path, bytes: &[u8]) -> io::Result<()> {
+    // Renaming onto a symlink would replace the link itself
+    let path = &fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let tmp = path.with_file_name(format!(".{}.toad-tmp", name));
+
+    let result = (|| {
+        let mut file = fs::File::create(&tmp)?;
+        file.write_all(bytes)?;
+        file.sync_all()?;
+        if let Ok(metadata) = fs::metadata(path) {
+            fs::set_permissions(&tmp, metadata.permissions())?;
+        }
+        fs::rename(&tmp, path)
+    })();
+    if result.is_err() {
+        let _ = fs::remove_file(&tmp);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_atomic_write_replaces_contents() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("file.txt");
+
+        atomic_write(&path, b"one").unwrap();
+        atomic_write(&path, b"two").unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "two");
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_atomic_write_through_symlink() {
+        let dir = TempDir::new().unwrap();
+        let target = dir.path().join("target.txt");
+        let link = dir.path().join("link.txt");
+        fs::write(&target, "old").unwrap();
+        std::os::unix::fs::symlink(&target, &link).unwrap();
+
+        atomic_write(&link, b"new").unwrap();
+
+        assert!(
+            fs::symlink_metadata(&link)
+                .unwrap()
+                .file_type()
+                .is_symlink()
+        );
+        assert_eq!(fs::read_to_string(&target).unwrap(), "new");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_atomic_write_keeps_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("script.sh");
+        fs::write(&path, "#!/bin/sh\n").unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o750)).unwrap();
+
+        atomic_write(&path, b"#!/bin/sh\necho hi\n").unwrap();
+
+        let mode = fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o750);
+    }
+}
@@ -0,0 +1,562 @@
+//! Card activity derived from git history
+//!
+//! Attributes commits to cards and estimates the time spent on them, so work
+//! done outside toad shows up on cards, in timesheets and in cycle times
+//! without manual timers.
+//!
+//! # Attribution
+//!
+//! A commit belongs to the cards whose IDs appear in its message, in any
+//! case and punctuation (`card-12: fix login`, `[CARD-12]`, `#card-12`).
+//! A commit without card IDs that is only on the current branch belongs to
+//! the cards named in the branch, e.g. `feature/card-12-fix-login` from
+//! [`CardBranch::suggest_branch_name`](crate::infrastructure::git_card_integration::CardBranch::suggest_branch_name).
+//! Only IDs of existing cards count. Each newly attributed commit is linked
+//! as a [`CardCommit`] and logged as [`GIT_COMMIT_ACTIVITY`] on the card.
+//!
+//! # Time estimates
+//!
+//! Commits are grouped by author email and ordered by time. A commit made
+//! within [`GitActivityOptions::session_gap`] of the author's previous commit
+//! is credited with the time since that commit; the first commit of a
+//! session gets [`GitActivityOptions::first_commit`]. Time already covered
+//! by agent runs on the card (time entries tagged `agent`) is subtracted,
+//! and the rest is split evenly between the commit's cards as
+//! [`TimeEntryType::Git`](crate::infrastructure::time_tracking::TimeEntryType::Git)
+//! entries tagged [`GIT_TIME_TAG`].
+//!
+//! # Examples
+//!
+//! ```
+//! use toad::git::CommitInfo;
+//! use toad::infrastructure::board_store::BoardDatabase;
+//! use toad::infrastructure::git_activity::{GitActivityOptions, GitHistory, sync_git_activity};
+//! use toad::infrastructure::rich_task_cards::Priority;
+//!
+//! let mut db = BoardDatabase::default();
+//! let card = db.cards.create_card(
+//!     "Fix login".to_string(),
+//!     String::new(),
+//!     "To Do".to_string(),
+//!     Priority::High,
+//!     "me".to_string(),
+//! );
+//!
+//! let commit = |hash: &str, message: &str, timestamp: i64| CommitInfo {
+//!     hash: hash.to_string(),
+//!     full_hash: format!("{}0000", hash),
+//!     message: message.to_string(),
+//!     author: "Ada".to_string(),
+//!     email: "ada@example.com".to_string(),
+//!     timestamp,
+//!     parents: Vec::new(),
+//! };
+//! let history = GitHistory {
+//!     branch: "main".to_string(),
+//!     commits: vec![
+//!         commit("b2", "card-1: handle expired sessions", 1_700_003_600),
+//!         commit("a1", "card-1: validate password", 1_700_000_000),
+//!     ],
+//!     ..GitHistory::default()
+//! };
+//!
+//! let report = sync_git_activity(&mut db, &history, &GitActivityOptions::default());
+//! assert_eq!(report.linked.len(), 2);
+//! assert_eq!(db.git.get_card_commits(&card).len(), 2);
+//! // 30 minutes for the first commit of the session, 1 hour for the second
+//! assert_eq!(db.time.total_time_for_task(&card).num_minutes(), 90);
+//! ```
+
+use crate::git::CommitInfo;
+use crate::infrastructure::board_store::BoardDatabase;
+use crate::infrastructure::git_card_integration::CardCommit;
+use crate::infrastructure::time_tracking::TimeEntry;
+use chrono::{DateTime, Duration, Utc};
+use std::collections::{HashMap, HashSet};
+
+/// Activity type logged on a card when a commit is linked to it
+pub const GIT_COMMIT_ACTIVITY: &str = "git_commit";
+
+/// Tag of time entries estimated from commits
+pub const GIT_TIME_TAG: &str = "git";
+
+/// Tag of time entries logged for agent runs
+const AGENT_TIME_TAG: &str = "agent";
+
+/// Prefix of card IDs
+const CARD_ID_PREFIX: &str = "card-";
+
+/// File changes of a commit
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CommitChanges {
+    /// Changed files
+    pub files: Vec<String>,
+    /// Added lines
+    pub additions: usize,
+    /// Deleted lines
+    pub deletions: usize,
+}
+
+/// Commit history to attribute to cards
+#[derive(Debug, Clone, Default)]
+pub struct GitHistory {
+    /// Current branch
+    pub branch: String,
+    /// Branch the current one was started from (recorded as the branch of
+    /// commits not only on the current branch; empty means the current one)
+    pub base_branch: String,
+    /// Recent commits reachable from `HEAD` ([`GitService::log`](crate::git::GitService::log))
+    pub commits: Vec<CommitInfo>,
+    /// Full hashes of the commits only on the current branch
+    /// ([`GitService::log_range`](crate::git::GitService::log_range) against
+    /// the base)
+    pub branch_commits: HashSet<String>,
+    /// File changes by full hash, where known
+    pub changes: HashMap<String, CommitChanges>,
+}
+
+impl GitHistory {
+    /// Commits that belong to cards, with their card IDs, in history order
+    pub fn attribute(&self, cards: &CardMatcher) -> Vec<(&CommitInfo, Vec<String>)> {
+        let branch_cards = cards.cards_in(&self.branch);
+        self.commits
+            .iter()
+            .filter_map(|commit| {
+                let mut ids = cards.cards_in(&commit.message);
+                if ids.is_empty() && self.branch_commits.contains(&commit.full_hash) {
+                    ids = branch_cards.clone();
+                }
+                (!ids.is_empty()).then_some((commit, ids))
+            })
+            .collect()
+    }
+
+    /// Branch a commit is recorded on
+    fn branch_of(&self, commit: &CommitInfo) -> &str {
+        if self.base_branch.is_empty() || self.branch_commits.contains(&commit.full_hash) {
+            &self.branch
+        } else {
+            &self.base_branch
+        }
+    }
+}
+
+/// Finds IDs of existing cards in commit messages and branch names
+#[derive(Debug, Clone, Default)]
+pub struct CardMatcher {
+    known: HashSet<String>,
+}
+
+impl CardMatcher {
+    /// Match the given card IDs
+    pub fn new(card_ids: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            known: card_ids.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    /// Match the cards of a board
+    pub fn from_board(db: &BoardDatabase) -> Self {
+        Self::new(db.cards.cards().into_iter().map(|card| card.id.clone()))
+    }
+
+    /// IDs of known cards mentioned in `text`, in order of first mention
+    pub fn cards_in(&self, text: &str) -> Vec<String> {
+        card_ids_in(text)
+            .into_iter()
+            .filter(|id| self.known.contains(id))
+            .collect()
+    }
+}
+
+/// Card IDs (`card-<n>`, any case) mentioned in `text`, lowercased and in
+/// order of first mention
+///
+/// An ID must not be glued to other letters or digits, so `card-12` is found
+/// in `[CARD-12]` and `feature/card-12-login` but not in `postcard-12` or
+/// `card-12b`.
+pub fn card_ids_in(text: &str) -> Vec<String> {
+    let lower = text.to_ascii_lowercase();
+    let bytes = lower.as_bytes();
+    let mut ids: Vec<String> = Vec::new();
+
+    for (start, _) in lower.match_indices(CARD_ID_PREFIX) {
+        if start > 0 && bytes[start - 1].is_ascii_alphanumeric() {
+            continue;
+        }
+        let digits_start = start + CARD_ID_PREFIX.len();
+        let digits = bytes[digits_start..]
+            .iter()
+            .take_while(|b| b.is_ascii_digit())
+            .count();
+        let end = digits_start + digits;
+        if digits == 0 || bytes.get(end).is_some_and(|b| b.is_ascii_alphanumeric()) {
+            continue;
+        }
+        let id = &lower[start..end];
+        if !ids.iter().any(|known| known == id) {
+            ids.push(id.to_string());
+        }
+    }
+    ids
+}
+
+/// How time is estimated from commits
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GitActivityOptions {
+    /// Longest pause between two commits of a work session
+    pub session_gap: Duration,
+    /// Time credited to the first commit of a session
+    pub first_commit: Duration,
+}
+
+impl Default for GitActivityOptions {
+    fn default() -> Self {
+        Self {
+            session_gap: Duration::hours(2),
+            first_commit: Duration::minutes(30),
+        }
+    }
+}
+
+/// A commit newly linked to a card
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LinkedCommit {
+    /// Card the commit was linked to
+    pub card_id: String,
+    /// Abbreviated commit hash
+    pub hash: String,
+}
+
+/// Result of [`sync_git_activity`]
+#[derive(Debug, Clone, Default)]
+pub struct GitActivityReport {
+    /// Commits linked to cards
+    pub linked: Vec<LinkedCommit>,
+    /// Time entries added
+    pub time_entries: usize,
+    /// Time estimated for the linked commits (seconds)
+    pub estimated_seconds: i64,
+}
+
+impl GitActivityReport {
+    /// Whether the sync changed the board
+    pub fn changed_board(&self) -> bool {
+        !self.linked.is_empty() || self.time_entries > 0
+    }
+
+    /// One-line summary for the status bar
+    pub fn summary(&self) -> String {
+        if self.linked.is_empty() {
+            return "Git: no new commits for cards".to_string();
+        }
+        let cards: HashSet<&str> = self.linked.iter().map(|l| l.card_id.as_str()).collect();
+        let minutes = self.estimated_seconds / 60;
+        format!(
+            "Git: {} commit link{} on {} card{}, {}h{:02}m estimated",
+            self.linked.len(),
+            if self.linked.len() == 1 { "" } else { "s" },
+            cards.len(),
+            if cards.len() == 1 { "" } else { "s" },
+            minutes / 60,
+            minutes % 60
+        )
+    }
+}
+
+/// Link new commits to cards and log the time estimated for them
+///
+/// Commits already linked to a card (e.g. by an agent run) are left alone,
+/// so syncing the same history again changes nothing.
+pub fn sync_git_activity(
+    db: &mut BoardDatabase,
+    history: &GitHistory,
+    options: &GitActivityOptions,
+) -> GitActivityReport {
+    let matcher = CardMatcher::from_board(db);
+    let intervals = estimate_intervals(&history.commits, options);
+    let mut report = GitActivityReport::default();
+
+    for (commit, card_ids) in history.attribute(&matcher) {
+        let new_cards: Vec<String> = card_ids
+            .into_iter()
+            .filter(|card_id| !db.git.has_commit(card_id, &commit.full_hash))
+            .collect();
+        let Some(committed_at) = DateTime::from_timestamp(commit.timestamp, 0) else {
+            continue;
+        };
+        let share = intervals
+            .get(commit.full_hash.as_str())
+            .map(|(start, end)| (*end - *start) / new_cards.len().max(1) as i32);
+
+        for card_id in new_cards {
+            link_commit(db, history, commit, &card_id, committed_at);
+            report.linked.push(LinkedCommit {
+                card_id: card_id.clone(),
+                hash: commit.hash.clone(),
+            });
+
+            let Some(share) = share else {
+                continue;
+            };
+            let worked = share - agent_overlap(db, &card_id, committed_at - share, committed_at);
+            let entry_id = format!("git-{}-{}", commit.hash, card_id);
+            if worked <= Duration::zero() || db.time.has_entry(&entry_id) {
+                continue;
+            }
+            let mut entry = TimeEntry::estimated(&card_id, committed_at - worked, worked)
+                .description(format!("Commit {}: {}", commit.hash, commit.message))
+                .add_tag(GIT_TIME_TAG);
+            entry.id = entry_id;
+            db.time.add_entry(entry);
+            report.time_entries += 1;
+            report.estimated_seconds += worked.num_seconds();
+        }
+    }
+    report
+}
+
+/// Record a commit on a card and log it in the card's activity
+fn link_commit(
+    db: &mut BoardDatabase,
+    history: &GitHistory,
+    commit: &CommitInfo,
+    card_id: &str,
+    committed_at: DateTime<Utc>,
+) {
+    let mut card_commit = CardCommit::new(
+        commit.hash.clone(),
+        card_id.to_string(),
+        commit.message.clone(),
+        commit.author.clone(),
+        committed_at,
+        history.branch_of(commit).to_string(),
+    );
+    if let Some(changes) = history.changes.get(&commit.full_hash) {
+        card_commit =
+            card_commit.with_changes(changes.files.clone(), changes.additions, changes.deletions);
+    }
+    db.git.add_commit(card_commit);
+    db.comments.log_activity(
+        card_id.to_string(),
+        GIT_COMMIT_ACTIVITY.to_string(),
+        format!("Commit {}: {}", commit.hash, commit.message),
+        Some(commit.email.clone()),
+        Some(commit.author.clone()),
+    );
+}
+
+/// Working interval credited to each commit, by full hash
+fn estimate_intervals<'a>(
+    commits: &'a [CommitInfo],
+    options: &GitActivityOptions,
+) -> HashMap<&'a str, (DateTime<Utc>, DateTime<Utc>)> {
+    let mut by_author: HashMap<&str, Vec<(DateTime<Utc>, &str)>> = HashMap::new();
+    for commit in commits {
+        if let Some(at) = DateTime::from_timestamp(commit.timestamp, 0) {
+            by_author
+                .entry(commit.email.as_str())
+                .or_default()
+                .push((at, commit.full_hash.as_str()));
+        }
+    }
+
+    let mut intervals = HashMap::new();
+    for mut authored in by_author.into_values() {
+        authored.sort();
+        let mut previous: Option<DateTime<Utc>> = None;
+        for (at, hash) in authored {
+            let credit = match previous {
+                Some(prev) if at - prev <= options.session_gap => at - prev,
+                _ => options.first_commit,
+            };
+            intervals.insert(hash, (at - credit, at));
+            previous = Some(at);
+        }
+    }
+    intervals
+}
+
+/// Time between `start` and `end` covered by agent runs on a card
+fn agent_overlap(
+    db: &BoardDatabase,
+    card_id: &str,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+) -> Duration {
+    db.time
+        .entries_for_task(card_id)
+        .iter()
+        .filter(|entry| entry.tags.iter().any(|tag| tag == AGENT_TIME_TAG))
+        .map(|entry| {
+            let run_end = entry.end.unwrap_or(entry.start + entry.duration());
+            let overlap = run_end.min(end) - entry.start.max(start);
+            overlap.max(Duration::zero())
+        })
+        .fold(Duration::zero(), |total, overlap| total + overlap)
+        .min(end - start)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::rich_task_cards::Priority;
+    use crate::infrastructure::time_tracking::TimeEntryType;
+
+    const T0: i64 = 1_700_000_000;
+
+    fn commit(hash: &str, message: &str, email: &str, timestamp: i64) -> CommitInfo {
+        CommitInfo {
+            hash: hash.to_string(),
+            full_hash: format!("{}ffff", hash),
+            message: message.to_string(),
+            author: email.split('@').next().unwrap().to_string(),
+            email: email.to_string(),
+            timestamp,
+            parents: Vec::new(),
+        }
+    }
+
+    fn board_with_cards(count: usize) -> BoardDatabase {
+        let mut db = BoardDatabase::default();
+        for i in 0..count {
+            db.cards.create_card(
+                format!("Card {}", i + 1),
+                String::new(),
+                "To Do".to_string(),
+                Priority::Medium,
+                "me".to_string(),
+            );
+        }
+        db
+    }
+
+    #[test]
+    fn test_card_ids_in_text() {
+        assert_eq!(
+            card_ids_in("[CARD-12] fix login, see #card-3 and card-12"),
+            ["card-12", "card-3"]
+        );
+        assert_eq!(card_ids_in("feature/card-7-login-form"), ["card-7"]);
+        assert!(card_ids_in("postcard-1 card- card-2b cards-3").is_empty());
+        assert_eq!(card_ids_in("Fixes card-4."), ["card-4"]);
+    }
+
+    #[test]
+    fn test_commits_without_ids_are_attributed_to_the_branch_card() {
+        let history = GitHistory {
+            branch: "feature/card-2-search".to_string(),
+            base_branch: "main".to_string(),
+            commits: vec![
+                commit("c3", "Add search index", "ada@x", T0 + 200),
+                commit("c2", "card-1: unrelated fix", "ada@x", T0 + 100),
+                commit("c1", "Initial commit", "ada@x", T0),
+            ],
+            branch_commits: ["c3ffff", "c2ffff"].map(String::from).into(),
+            changes: HashMap::new(),
+        };
+        let matcher = CardMatcher::new(["card-1", "card-2"]);
+
+        let attributed: Vec<(&str, Vec<String>)> = history
+            .attribute(&matcher)
+            .into_iter()
+            .map(|(commit, ids)| (commit.hash.as_str(), ids))
+            .collect();
+
+        assert_eq!(
+            attributed,
+            [
+                ("c3", vec!["card-2".to_string()]),
+                ("c2", vec!["card-1".to_string()])
+            ]
+        );
+        assert_eq!(history.branch_of(&history.commits[2]), "main");
+    }
+
+    #[test]
+    fn test_sync_links_commits_and_estimates_sessions_per_author() {
+        let mut db = board_with_cards(2);
+        db.git.add_commit(CardCommit::new(
+            "a0".to_string(),
+            "card-2".to_string(),
+            "card-2: agent work".to_string(),
+            "ada".to_string(),
+            Utc::now(),
+            "main".to_string(),
+        ));
+        let history = GitHistory {
+            branch: "main".to_string(),
+            commits: vec![
+                // Next day: a new session
+                commit("d4", "card-1: polish", "ada@x", T0 + 86_400),
+                // Both cards share the 40 minutes since ada's last commit
+                commit("c3", "card-1 card-2: shared helper", "ada@x", T0 + 3_600),
+                commit("b2", "card-1: tests", "bob@x", T0 + 1_800),
+                commit("a1", "card-1: start", "ada@x", T0 + 1_200),
+                commit("a0", "card-2: agent work", "ada@x", T0),
+            ],
+            changes: [(
+                "b2ffff".to_string(),
+                CommitChanges {
+                    files: vec!["src/lib.rs".to_string()],
+                    additions: 10,
+                    deletions: 2,
+                },
+            )]
+            .into(),
+            ..GitHistory::default()
+        };
+
+        let report = sync_git_activity(&mut db, &history, &GitActivityOptions::default());
+
+        assert_eq!(report.linked.len(), 5);
+        assert_eq!(db.git.get_card_commits("card-1").len(), 4);
+        assert_eq!(db.git.get_card_commits("card-2").len(), 2);
+        assert_eq!(db.git.get_card_total_changes("card-1"), (10, 2));
+        // a1: 20m, b2: 30m (bob's first), c3: 20m of 40m, d4: 30m
+        assert_eq!(db.time.total_time_for_task("card-1").num_minutes(), 100);
+        assert_eq!(db.time.total_time_for_task("card-2").num_minutes(), 20);
+        assert!(
+            db.time
+                .entries()
+                .iter()
+                .all(|e| e.entry_type == TimeEntryType::Git && e.tags == [GIT_TIME_TAG])
+        );
+        let activity = db.comments.activities_for_card("card-1");
+        assert_eq!(activity.len(), 4);
+        assert_eq!(activity[0].activity_type, GIT_COMMIT_ACTIVITY);
+
+        let again = sync_git_activity(&mut db, &history, &GitActivityOptions::default());
+        assert!(!again.changed_board());
+    }
+
+    #[test]
+    fn test_agent_runs_are_not_counted_twice() {
+        let mut db = board_with_cards(1);
+        let run_start = DateTime::from_timestamp(T0 + 600, 0).unwrap();
+        db.time.add_entry(
+            TimeEntry::manual("card-1", run_start, Duration::minutes(20)).add_tag(AGENT_TIME_TAG),
+        );
+        let history = GitHistory {
+            branch: "main".to_string(),
+            commits: vec![
+                commit("b2", "card-1: after the agent", "ada@x", T0 + 3_600),
+                commit("a1", "card-1: before the agent", "ada@x", T0),
+            ],
+            ..GitHistory::default()
+        };
+
+        let report = sync_git_activity(&mut db, &history, &GitActivityOptions::default());
+
+        // b2 spans the hour since a1, 20 minutes of which the agent worked
+        assert_eq!(report.estimated_seconds, (30 + 40) * 60);
+        assert_eq!(
+            db.time.total_time_for_task("card-1").num_minutes(),
+            20 + 30 + 40
+        );
+        assert_eq!(
+            report.summary(),
+            "Git: 2 commit links on 1 card, 1h10m estimated"
+        );
+    }
+}
@@ -352,7 +352,7 @@ impl CardReviewWorkflow {
 }
 
 /// Manager for git card integration
-#[derive(Debug)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GitCardIntegrationManager {
     /// All git-card links
     links: HashMap<String, GitCardLink>,
@@ -511,7 +511,16 @@ impl GitCardIntegrationManager {
     }
 
     /// Add a commit to a card
+    ///
+    /// Also counts the commit on the card's branch of the same name.
     pub fn add_commit(&mut self, commit: CardCommit) {
+        if let Some(branch) = self
+            .card_branches
+            .get_mut(&commit.card_id)
+            .and_then(|branches| branches.iter_mut().find(|b| b.name == commit.branch))
+        {
+            branch.add_commit();
+        }
         let card_id = commit.card_id.clone();
         self.card_commits.entry(card_id).or_default().push(commit);
     }
@@ -524,6 +533,24 @@ impl GitCardIntegrationManager {
             .unwrap_or_default()
     }
 
+    /// Whether a commit is linked to a card
+    ///
+    /// `hash` may be full or abbreviated; linked hashes are compared as
+    /// prefixes of each other.
+    pub fn has_commit(&self, card_id: &str, hash: &str) -> bool {
+        self.get_card_commits(card_id)
+            .iter()
+            .any(|commit| same_commit(&commit.hash, hash))
+    }
+
+    /// Whether a commit is linked to any card
+    pub fn is_commit_linked(&self, hash: &str) -> bool {
+        self.card_commits
+            .values()
+            .flatten()
+            .any(|commit| same_commit(&commit.hash, hash))
+    }
+
     /// Get commits for a card on a specific branch
     pub fn get_card_commits_on_branch(&self, card_id: &str, branch: &str) -> Vec<&CardCommit> {
         self.get_card_commits(card_id)
@@ -600,6 +627,11 @@ impl GitCardIntegrationManager {
     }
 }
 
+/// Whether two (possibly abbreviated) hashes name the same commit
+fn same_commit(a: &str, b: &str) -> bool {
+    !a.is_empty() && !b.is_empty() && (a.starts_with(b) || b.starts_with(a))
+}
+
 impl Default for GitCardIntegrationManager {
     fn default() -> Self {
         Self::new()
@@ -938,6 +970,48 @@ mod tests {
         assert_eq!(commits.len(), 1);
     }
 
+    #[test]
+    fn test_manager_add_commit_counts_on_branch() {
+        let mut manager = GitCardIntegrationManager::new();
+        let branch = manager.create_branch(
+            "card-1".to_string(),
+            "main".to_string(),
+            "Fix login",
+            "user".to_string(),
+        );
+
+        manager.add_commit(CardCommit::new(
+            "abc123".to_string(),
+            "card-1".to_string(),
+            "Fix login".to_string(),
+            "agent".to_string(),
+            Utc::now(),
+            branch.name.clone(),
+        ));
+
+        assert_eq!(manager.get_card_branches("card-1")[0].commit_count, 1);
+    }
+
+    #[test]
+    fn test_manager_has_commit_matches_abbreviated_hashes() {
+        let mut manager = GitCardIntegrationManager::new();
+        manager.add_commit(CardCommit::new(
+            "abc1234".to_string(),
+            "card-1".to_string(),
+            "Fix login".to_string(),
+            "user".to_string(),
+            Utc::now(),
+            "main".to_string(),
+        ));
+
+        assert!(manager.has_commit("card-1", "abc1234def5678"));
+        assert!(manager.has_commit("card-1", "abc12"));
+        assert!(!manager.has_commit("card-2", "abc1234"));
+        assert!(!manager.has_commit("card-1", ""));
+        assert!(manager.is_commit_linked("abc1234def"));
+        assert!(!manager.is_commit_linked("def456"));
+    }
+
     #[test]
     fn test_manager_get_card_total_changes() {
         let mut manager = GitCardIntegrationManager::new();
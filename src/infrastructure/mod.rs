@@ -7,11 +7,16 @@ pub mod achievements;
 pub mod advanced_mouse;
 pub mod ai_task_intelligence;
 pub mod async_ops;
+pub mod automation_executor;
 pub mod background_tasks;
+pub mod board_store;
 pub mod batch_ops;
 pub mod builtin_automation;
+pub mod calendar_import;
 pub mod calendar_integration;
+pub mod calendar_sync;
 pub mod card_comments;
+pub mod card_schedule;
 pub mod clipboard;
 pub mod communication_integrations;
 pub mod cross_window_context;
@@ -25,6 +30,8 @@ pub mod fallback_mode;
 pub mod file_attachments;
 pub mod file_ops;
 pub mod filtering_search;
+pub mod fs_util;
+pub mod git_activity;
 pub mod git_card_integration;
 pub mod history;
 pub mod import_export;
@@ -44,6 +51,8 @@ pub mod text_truncation;
 pub mod time_tracking;
 pub mod validation;
 pub mod visual_kanban_board;
+#[cfg(unix)]
+pub mod window_ipc;
 
 pub use achievements::{
     Achievement, AchievementSystem, AchievementTier, AchievementType, LeaderboardEntry, Streak,
@@ -55,20 +64,33 @@ pub use ai_task_intelligence::{
     PrioritySuggestion, SuggestedPriority,
 };
 pub use async_ops::{AsyncOperation, AsyncOperationManager, OperationId, OperationStatus};
+pub use automation_executor::{
+    AutomationExecutor, AutomationReport, BoardEvent, Clock, ExecutedAction, FakeClock,
+    SystemClock,
+};
 pub use background_tasks::{BackgroundTask, BackgroundTaskManager, TaskId, TaskStatus};
 pub use batch_ops::{
     BatchHandler, BatchManager, BatchOperation, BatchResult, BatchStats, OpResult,
 };
+pub use board_store::{
+    BoardDatabase, BoardStore, Migration, SCHEMA_VERSION, SnapshotInfo, StoreError,
+};
 pub use builtin_automation::{
     AutomationAction, AutomationManager, AutomationRule, BulkActionResult, BulkActionType,
     RecurrencePattern, RecurringTask, TaskTemplate, TriggerCondition,
 };
+pub use calendar_import::{IcsCalendar, IcsError, RecurrenceRule, TimeZoneDef, parse_ics};
 pub use calendar_integration::{
-    CalendarEvent, CalendarExporter, EventPriority, EventStatus, Recurrence,
+    CalendarComponent, CalendarEvent, CalendarExporter, EventPriority, EventStatus, Recurrence,
+};
+pub use calendar_sync::{
+    CalendarSource, CalendarSyncState, ConflictPolicy, SyncConflict, SyncOptions, SyncReport,
+    sync_calendar,
 };
 pub use card_comments::{
     ActivityLogEntry, Comment, CommentManager, EditHistory, Reaction, ReactionEntry,
 };
+pub use card_schedule::{CardSchedule, ScheduledCard, reschedule_card};
 pub use clipboard::Clipboard;
 pub use communication_integrations::{
     DiscordMessage, EmailConfig, EmailMessage, EventType, IntegrationManager, IntegrationPlatform,
@@ -95,8 +117,11 @@ pub use fallback_mode::{BoxChars, FallbackMode};
 pub use file_attachments::{Attachment, AttachmentManager, AttachmentType, AttachmentVersion};
 pub use file_ops::{FileOpResult, FileOps};
 pub use filtering_search::{
-    Filter, FilterCondition, FilterField, FilterManager, FilterOperator, LogicalOperator,
-    QuickFilter, SearchParser,
+    Filter, FilterCondition, FilterField, FilterManager, FilterOperator, LiveFilter,
+    LogicalOperator, QuickFilter, SearchParser,
+};
+pub use git_activity::{
+    CardMatcher, GitActivityOptions, GitActivityReport, GitHistory, sync_git_activity,
 };
 pub use git_card_integration::{
     CardBranch, CardCommit, CardReviewWorkflow, GitCardIntegrationManager, GitCardLink,
@@ -132,7 +157,8 @@ pub use team_collaboration::{
 pub use terminal_capabilities::{ColorSupport, FeatureLevel, TerminalCapabilities};
 pub use text_truncation::{SmartTruncate, TruncationStrategy};
 pub use time_tracking::{
-    ActiveTimer, Billability, TimeEntry, TimeEntryType, TimeStats, TimeTracker,
+    ActiveTimer, Billability, TimeEntry, TimeEntryType, TimeStats, TimeTracker, Timesheet,
+    TimesheetRow,
 };
 pub use validation::{
     CompositeValidator, InputValidator, LengthValidator, NotEmptyValidator, RegexValidator,
@@ -141,3 +167,8 @@ pub use validation::{
 pub use visual_kanban_board::{
     BoardManager, CardPosition, KanbanBoard, KanbanColumn, Swimlane, SwimlaneGrouping,
 };
+#[cfg(unix)]
+pub use window_ipc::{
+    Delivery, Discovery, IncomingMessage, IpcConfig, IpcError, IpcEvent, IpcMessage,
+    WindowEndpoint, WindowInfo,
+};
@@ -302,7 +302,7 @@ impl Workspace {
 /// Project manager
 ///
 /// Manages projects, workspaces, templates, and cross-project references.
-#[derive(Debug)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProjectManager {
     /// All projects
     projects: HashMap<String, Project>,
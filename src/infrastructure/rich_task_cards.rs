@@ -150,6 +150,9 @@ pub struct RichTaskCard {
     pub tags: Vec<Tag>,
     /// Due date with time
     pub due_date: Option<DateTime<Utc>>,
+    /// Planned start (the card is not scheduled to start before this)
+    #[serde(default)]
+    pub start_date: Option<DateTime<Utc>>,
     /// Recurrence pattern (if recurring)
     pub recurrence: Option<String>, // "daily", "weekly", "monthly"
     /// Effort estimate
@@ -190,6 +193,7 @@ impl RichTaskCard {
             assignees: Vec::new(),
             tags: Vec::new(),
             due_date: None,
+            start_date: None,
             recurrence: None,
             effort_estimate: None,
             progress: 0,
@@ -323,7 +327,7 @@ impl RichTaskCard {
 }
 
 /// Manager for rich task cards
-#[derive(Debug)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RichTaskCardManager {
     cards: HashMap<String, RichTaskCard>,
     next_card_id: usize,
@@ -364,6 +368,13 @@ impl RichTaskCardManager {
         self.cards.get(card_id)
     }
 
+    /// Gets all task cards (oldest first)
+    pub fn cards(&self) -> Vec<&RichTaskCard> {
+        let mut cards: Vec<&RichTaskCard> = self.cards.values().collect();
+        cards.sort_by(|a, b| a.created_at.cmp(&b.created_at).then(a.id.cmp(&b.id)));
+        cards
+    }
+
     /// Gets a mutable task card by ID
     pub fn get_card_mut(&mut self, card_id: &str) -> Option<&mut RichTaskCard> {
         self.cards.get_mut(card_id)
@@ -2,10 +2,24 @@
 //!
 //! This module provides comprehensive dependency management for tasks,
 //! including dependency types, circular detection, and critical path calculation.
+//!
+//! # Scheduling
+//!
+//! [`DependencyManager::schedule`] runs the forward and backward passes of the
+//! critical path method. Each dependency type maps onto the schedule as:
+//!
+//! - `Blocks`: the target starts after the source finishes
+//! - `BlockedBy`: the source starts after the target finishes
+//! - `Duplicates`: the duplicate takes no time of its own and closes when the
+//!   original finishes
+//! - `RelatesTo`: no scheduling effect
+//!
+//! Every dependency may carry a lag (in days) added between the predecessor's
+//! finish and the successor's start.
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, HashSet, VecDeque};
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
 
 /// Dependency type
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -41,7 +55,7 @@ impl DependencyType {
         }
     }
 
-    /// Returns whether this dependency type affects scheduling
+    /// Returns whether this dependency type can create a blocking cycle
     pub fn affects_scheduling(&self) -> bool {
         matches!(self, DependencyType::Blocks | DependencyType::BlockedBy)
     }
@@ -62,6 +76,9 @@ pub struct Dependency {
     pub created_at: DateTime<Utc>,
     /// Created by user ID
     pub created_by: String,
+    /// Days to wait after the predecessor finishes (may be negative for lead)
+    #[serde(default)]
+    pub lag_days: f32,
 }
 
 impl Dependency {
@@ -80,6 +97,18 @@ impl Dependency {
             dependency_type,
             created_at: Utc::now(),
             created_by,
+            lag_days: 0.0,
+        }
+    }
+
+    /// Returns the scheduling edge as `(predecessor, successor)`, if any
+    pub fn schedule_edge(&self) -> Option<(&str, &str)> {
+        match self.dependency_type {
+            DependencyType::Blocks => Some((&self.from_task, &self.to_task)),
+            DependencyType::BlockedBy | DependencyType::Duplicates => {
+                Some((&self.to_task, &self.from_task))
+            }
+            DependencyType::RelatesTo => None,
         }
     }
 }
@@ -132,7 +161,7 @@ impl CriticalPathNode {
 }
 
 /// Dependency manager
-#[derive(Debug)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DependencyManager {
     dependencies: HashMap<String, Dependency>,
     task_dependencies: HashMap<String, Vec<String>>, // task_id -> dependency_ids
@@ -245,10 +274,11 @@ impl DependencyManager {
         if let Some(dep_ids) = self.task_dependencies.get(task_id) {
             for dep_id in dep_ids {
                 if let Some(dep) = self.dependencies.get(dep_id)
-                    && dep.from_task == task_id
-                    && dep.dependency_type == DependencyType::Blocks
+                    && dep.dependency_type.affects_scheduling()
+                    && let Some((before, after)) = dep.schedule_edge()
+                    && before == task_id
                 {
-                    blocked.push(dep.to_task.clone());
+                    blocked.push(after.to_string());
                 }
             }
         }
@@ -261,6 +291,17 @@ impl DependencyManager {
         self.dependencies.get(dep_id)
     }
 
+    /// Sets the lag (in days) of a dependency, returning false if it doesn't exist
+    pub fn set_lag(&mut self, dep_id: &str, lag_days: f32) -> bool {
+        match self.dependencies.get_mut(dep_id) {
+            Some(dep) => {
+                dep.lag_days = lag_days;
+                true
+            }
+            None => false,
+        }
+    }
+
     /// Deletes a dependency
     pub fn delete_dependency(&mut self, dep_id: &str) -> Option<Dependency> {
         let dep = self.dependencies.remove(dep_id)?;
@@ -311,126 +352,172 @@ impl DependencyManager {
             .collect()
     }
 
+    /// Gets every task that is scheduled after this one, directly or transitively
+    pub fn dependents(&self, task_id: &str) -> Vec<String> {
+        let mut found: Vec<String> = Vec::new();
+        let mut queue = VecDeque::from([task_id.to_string()]);
+        while let Some(current) = queue.pop_front() {
+            for dep in self.dependencies_for_task(&current) {
+                if let Some((before, after)) = dep.schedule_edge()
+                    && before == current
+                    && after != task_id
+                    && !found.iter().any(|task| task == after)
+                {
+                    found.push(after.to_string());
+                    queue.push_back(after.to_string());
+                }
+            }
+        }
+        found
+    }
+
     /// Calculates critical path for a set of tasks
+    ///
+    /// Returns an empty list when the dependencies between the tasks form a
+    /// cycle; use [`schedule`](Self::schedule) to find out which tasks.
     pub fn calculate_critical_path(
         &self,
         task_durations: HashMap<String, f32>,
     ) -> Vec<CriticalPathNode> {
-        let mut nodes: HashMap<String, CriticalPathNode> = HashMap::new();
+        self.schedule(&task_durations, &HashMap::new())
+            .unwrap_or_default()
+    }
 
-        // Initialize nodes
-        for (task_id, duration) in &task_durations {
-            nodes.insert(
-                task_id.clone(),
-                CriticalPathNode::new(task_id.clone(), *duration),
-            );
+    /// Schedules tasks with a forward and backward pass
+    ///
+    /// `start_constraints` holds "start no earlier than" days for tasks that are
+    /// pinned. Nodes are returned in topological order (ties by task ID).
+    /// Dependencies on tasks missing from `task_durations` are ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns the tasks caught in (or behind) a dependency cycle.
+    pub fn schedule(
+        &self,
+        task_durations: &HashMap<String, f32>,
+        start_constraints: &HashMap<String, f32>,
+    ) -> Result<Vec<CriticalPathNode>, Vec<String>> {
+        let sorted_tasks = self.topological_sort(task_durations);
+        if sorted_tasks.len() < task_durations.len() {
+            let sorted: HashSet<&String> = sorted_tasks.iter().collect();
+            let mut stuck: Vec<String> = task_durations
+                .keys()
+                .filter(|task| !sorted.contains(task))
+                .cloned()
+                .collect();
+            stuck.sort();
+            return Err(stuck);
         }
 
-        // Forward pass: Calculate earliest start times
-        let sorted_tasks = self.topological_sort(&task_durations);
+        let edges = self.schedule_edges(task_durations);
+        let duplicates: HashSet<&str> = self
+            .dependencies
+            .values()
+            .filter(|dep| dep.dependency_type == DependencyType::Duplicates)
+            .map(|dep| dep.from_task.as_str())
+            .collect();
+
+        let mut nodes: HashMap<&str, CriticalPathNode> = task_durations
+            .iter()
+            .map(|(task_id, duration)| {
+                let duration = if duplicates.contains(task_id.as_str()) {
+                    0.0
+                } else {
+                    duration.max(0.0)
+                };
+                (
+                    task_id.as_str(),
+                    CriticalPathNode::new(task_id.clone(), duration),
+                )
+            })
+            .collect();
+
+        // Forward pass: earliest start is the latest predecessor finish plus lag
         for task_id in &sorted_tasks {
-            // Calculate max_finish before getting mutable reference
-            let blockers = self.get_blockers(task_id);
-            let max_finish = blockers
+            let constraint = start_constraints.get(task_id).copied().unwrap_or(0.0);
+            let earliest_start = edges
                 .iter()
-                .filter_map(|dep| {
-                    let blocker_id = if dep.from_task.as_str() == task_id {
-                        &dep.to_task
-                    } else {
-                        &dep.from_task
-                    };
-                    nodes.get(blocker_id).map(|n| n.earliest_finish())
-                })
-                .fold(0.0, f32::max);
-
-            // Now get mutable reference and update
-            if let Some(node) = nodes.get_mut(task_id) {
-                node.earliest_start = max_finish;
+                .filter(|(_, after, _)| after == task_id)
+                .filter_map(|(before, _, lag)| nodes.get(before).map(|n| n.earliest_finish() + lag))
+                .fold(constraint, f32::max);
+            if let Some(node) = nodes.get_mut(task_id.as_str()) {
+                node.earliest_start = earliest_start;
             }
         }
 
-        // Find project completion time
         let project_duration = nodes
             .values()
             .map(|n| n.earliest_finish())
             .fold(0.0, f32::max);
 
-        // Backward pass: Calculate latest start times
+        // Backward pass: latest finish is the earliest successor start minus lag
         for task_id in sorted_tasks.iter().rev() {
-            // Calculate latest_start before getting mutable reference
-            let blocked = self.get_blocked(task_id);
-            let duration = nodes.get(task_id).map(|n| n.duration).unwrap_or(0.0);
-
-            let latest_start = if blocked.is_empty() {
-                // No successors, can finish at project end
-                project_duration - duration
-            } else {
-                let min_start = blocked
-                    .iter()
-                    .filter_map(|dep| {
-                        let blocked_id = if dep.from_task.as_str() == task_id {
-                            &dep.to_task
-                        } else {
-                            &dep.from_task
-                        };
-                        nodes.get(blocked_id).map(|n| n.latest_start)
-                    })
-                    .fold(f32::INFINITY, f32::min);
-
-                min_start - duration
-            };
-
-            // Now get mutable reference and update
-            if let Some(node) = nodes.get_mut(task_id) {
-                node.latest_start = latest_start;
+            let latest_finish = edges
+                .iter()
+                .filter(|(before, _, _)| before == task_id)
+                .filter_map(|(_, after, lag)| nodes.get(after).map(|n| n.latest_start - lag))
+                .fold(project_duration, f32::min);
+            if let Some(node) = nodes.get_mut(task_id.as_str()) {
+                node.latest_start = latest_finish - node.duration;
                 node.update_slack();
             }
         }
 
-        nodes.into_values().collect()
+        Ok(sorted_tasks
+            .iter()
+            .filter_map(|task_id| nodes.remove(task_id.as_str()))
+            .collect())
+    }
+
+    /// Scheduling edges `(predecessor, successor, lag)` between the given tasks
+    fn schedule_edges<'a>(
+        &'a self,
+        task_durations: &HashMap<String, f32>,
+    ) -> Vec<(&'a str, &'a str, f32)> {
+        self.dependencies
+            .values()
+            .filter_map(|dep| {
+                dep.schedule_edge()
+                    .map(|(before, after)| (before, after, dep.lag_days))
+            })
+            .filter(|(before, after, _)| {
+                task_durations.contains_key(*before) && task_durations.contains_key(*after)
+            })
+            .collect()
     }
 
     /// Performs topological sort of tasks based on dependencies
+    ///
+    /// Tasks on a cycle are left out of the result.
     fn topological_sort(&self, task_durations: &HashMap<String, f32>) -> Vec<String> {
-        let mut in_degree: HashMap<String, usize> = HashMap::new();
-        let mut adj_list: HashMap<String, Vec<String>> = HashMap::new();
-
-        // Initialize
-        for task_id in task_durations.keys() {
-            in_degree.insert(task_id.clone(), 0);
-            adj_list.insert(task_id.clone(), Vec::new());
-        }
+        let mut in_degree: HashMap<&str, usize> = task_durations
+            .keys()
+            .map(|task| (task.as_str(), 0))
+            .collect();
+        let mut adj_list: HashMap<&str, Vec<&str>> = HashMap::new();
 
-        // Build adjacency list and in-degrees
-        for dep in self.dependencies.values() {
-            if dep.dependency_type == DependencyType::Blocks {
-                *in_degree.entry(dep.to_task.clone()).or_insert(0) += 1;
-                adj_list
-                    .entry(dep.from_task.clone())
-                    .or_default()
-                    .push(dep.to_task.clone());
-            }
+        for (before, after, _) in self.schedule_edges(task_durations) {
+            *in_degree.entry(after).or_insert(0) += 1;
+            adj_list.entry(before).or_default().push(after);
         }
 
-        // Kahn's algorithm
-        let mut queue: VecDeque<String> = in_degree
+        // Kahn's algorithm, taking ready tasks in ID order for a stable result
+        let mut ready: BTreeSet<&str> = in_degree
             .iter()
             .filter(|(_, degree)| **degree == 0)
-            .map(|(task, _)| task.clone())
+            .map(|(task, _)| *task)
             .collect();
 
         let mut result = Vec::new();
 
-        while let Some(task) = queue.pop_front() {
-            result.push(task.clone());
+        while let Some(task) = ready.pop_first() {
+            result.push(task.to_string());
 
-            if let Some(neighbors) = adj_list.get(&task) {
-                for neighbor in neighbors {
-                    if let Some(degree) = in_degree.get_mut(neighbor) {
-                        *degree -= 1;
-                        if *degree == 0 {
-                            queue.push_back(neighbor.clone());
-                        }
+            for neighbor in adj_list.get(task).into_iter().flatten() {
+                if let Some(degree) = in_degree.get_mut(neighbor) {
+                    *degree -= 1;
+                    if *degree == 0 {
+                        ready.insert(neighbor);
                     }
                 }
             }
@@ -922,4 +1009,193 @@ mod tests {
         let task1_node = nodes.iter().find(|n| n.task_id == "task-1").unwrap();
         assert!(task1_node.is_critical);
     }
+
+    fn durations(tasks: &[(&str, f32)]) -> HashMap<String, f32> {
+        tasks
+            .iter()
+            .map(|(task, days)| (task.to_string(), *days))
+            .collect()
+    }
+
+    fn node<'a>(nodes: &'a [CriticalPathNode], task_id: &str) -> &'a CriticalPathNode {
+        nodes.iter().find(|n| n.task_id == task_id).unwrap()
+    }
+
+    #[test]
+    fn test_schedule_honors_blocked_by() {
+        let mut manager = DependencyManager::new();
+        // B is blocked by A
+        manager
+            .create_dependency(
+                "B".to_string(),
+                "A".to_string(),
+                DependencyType::BlockedBy,
+                "user-1".to_string(),
+            )
+            .unwrap();
+
+        let nodes = manager
+            .schedule(&durations(&[("A", 2.0), ("B", 1.0)]), &HashMap::new())
+            .unwrap();
+
+        assert_eq!(nodes[0].task_id, "A");
+        assert_eq!(node(&nodes, "B").earliest_start, 2.0);
+        assert!(node(&nodes, "A").is_critical && node(&nodes, "B").is_critical);
+    }
+
+    #[test]
+    fn test_schedule_with_lag() {
+        let mut manager = DependencyManager::new();
+        let dep = manager
+            .create_dependency(
+                "A".to_string(),
+                "B".to_string(),
+                DependencyType::Blocks,
+                "user-1".to_string(),
+            )
+            .unwrap();
+        assert!(manager.set_lag(&dep, 1.5));
+        assert!(!manager.set_lag("dep-9", 1.0));
+
+        let nodes = manager
+            .schedule(&durations(&[("A", 2.0), ("B", 1.0)]), &HashMap::new())
+            .unwrap();
+
+        assert_eq!(node(&nodes, "B").earliest_start, 3.5);
+        assert_eq!(node(&nodes, "A").latest_start, 0.0);
+    }
+
+    #[test]
+    fn test_schedule_start_constraint_creates_slack() {
+        let mut manager = DependencyManager::new();
+        manager
+            .create_dependency(
+                "A".to_string(),
+                "C".to_string(),
+                DependencyType::Blocks,
+                "user-1".to_string(),
+            )
+            .unwrap();
+        manager
+            .create_dependency(
+                "B".to_string(),
+                "C".to_string(),
+                DependencyType::Blocks,
+                "user-1".to_string(),
+            )
+            .unwrap();
+
+        let constraints = durations(&[("B", 3.0)]);
+        let nodes = manager
+            .schedule(
+                &durations(&[("A", 1.0), ("B", 1.0), ("C", 1.0)]),
+                &constraints,
+            )
+            .unwrap();
+
+        assert_eq!(node(&nodes, "C").earliest_start, 4.0);
+        assert_eq!(node(&nodes, "A").slack, 3.0);
+        assert!(node(&nodes, "B").is_critical);
+    }
+
+    #[test]
+    fn test_schedule_duplicates_close_with_original() {
+        let mut manager = DependencyManager::new();
+        manager
+            .create_dependency(
+                "copy".to_string(),
+                "original".to_string(),
+                DependencyType::Duplicates,
+                "user-1".to_string(),
+            )
+            .unwrap();
+        manager
+            .create_dependency(
+                "other".to_string(),
+                "original".to_string(),
+                DependencyType::RelatesTo,
+                "user-1".to_string(),
+            )
+            .unwrap();
+
+        let nodes = manager
+            .schedule(
+                &durations(&[("original", 2.0), ("copy", 5.0), ("other", 1.0)]),
+                &HashMap::new(),
+            )
+            .unwrap();
+
+        assert_eq!(node(&nodes, "copy").duration, 0.0);
+        assert_eq!(node(&nodes, "copy").earliest_start, 2.0);
+        assert_eq!(node(&nodes, "other").earliest_start, 0.0);
+    }
+
+    #[test]
+    fn test_schedule_reports_cycle() {
+        let mut manager = DependencyManager::new();
+        manager
+            .create_dependency(
+                "A".to_string(),
+                "B".to_string(),
+                DependencyType::Blocks,
+                "user-1".to_string(),
+            )
+            .unwrap();
+        // A blocked by B closes the loop
+        let result = manager.create_dependency(
+            "A".to_string(),
+            "B".to_string(),
+            DependencyType::BlockedBy,
+            "user-1".to_string(),
+        );
+        assert!(result.is_err());
+
+        manager
+            .create_dependency(
+                "A".to_string(),
+                "B".to_string(),
+                DependencyType::Duplicates,
+                "user-1".to_string(),
+            )
+            .unwrap();
+        let cycle = manager
+            .schedule(
+                &durations(&[("A", 1.0), ("B", 1.0), ("C", 1.0)]),
+                &HashMap::new(),
+            )
+            .unwrap_err();
+        assert_eq!(cycle, vec!["A".to_string(), "B".to_string()]);
+        assert!(
+            manager
+                .calculate_critical_path(durations(&[("A", 1.0), ("B", 1.0)]))
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_dependents_are_transitive() {
+        let mut manager = DependencyManager::new();
+        manager
+            .create_dependency(
+                "A".to_string(),
+                "B".to_string(),
+                DependencyType::Blocks,
+                "user-1".to_string(),
+            )
+            .unwrap();
+        manager
+            .create_dependency(
+                "C".to_string(),
+                "B".to_string(),
+                DependencyType::BlockedBy,
+                "user-1".to_string(),
+            )
+            .unwrap();
+
+        assert_eq!(
+            manager.dependents("A"),
+            vec!["B".to_string(), "C".to_string()]
+        );
+        assert!(manager.dependents("C").is_empty());
+    }
 }
@@ -15,9 +15,9 @@
 //! tracker.stop_timer();
 //! ```
 
-use chrono::{DateTime, Duration, Utc};
+use chrono::{DateTime, Duration, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 /// Time entry type
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -26,6 +26,8 @@ pub enum TimeEntryType {
     Timer,
     /// Manual time entry
     Manual,
+    /// Estimated from commit history
+    Git,
 }
 
 /// Billability status
@@ -60,6 +62,9 @@ pub struct TimeEntry {
     pub project: Option<String>,
     /// Tags for categorization
     pub tags: Vec<String>,
+    /// Spend attributed to this entry (e.g. LLM cost of an agent run)
+    #[serde(default)]
+    pub cost_usd: f64,
 }
 
 impl TimeEntry {
@@ -79,6 +84,7 @@ impl TimeEntry {
             billability: Billability::NonBillable,
             project: None,
             tags: Vec::new(),
+            cost_usd: 0.0,
         }
     }
 
@@ -99,9 +105,24 @@ impl TimeEntry {
             billability: Billability::NonBillable,
             project: None,
             tags: Vec::new(),
+            cost_usd: 0.0,
         }
     }
 
+    /// Create an entry estimated from commit history
+    pub fn estimated(task_id: impl Into<String>, start: DateTime<Utc>, duration: Duration) -> Self {
+        Self {
+            entry_type: TimeEntryType::Git,
+            ..Self::manual(task_id, start, duration)
+        }
+    }
+
+    /// Set the cost in USD
+    pub fn cost(mut self, cost_usd: f64) -> Self {
+        self.cost_usd = cost_usd;
+        self
+    }
+
     /// Set description
     pub fn description(mut self, description: impl Into<String>) -> Self {
         self.description = Some(description.into());
@@ -156,7 +177,7 @@ impl TimeEntry {
 }
 
 /// Active timer state
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ActiveTimer {
     /// Time entry being tracked
     pub entry: TimeEntry,
@@ -264,10 +285,55 @@ impl TimeStats {
     }
 }
 
+/// One task's line in a [`Timesheet`]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TimesheetRow {
+    /// Task/card ID
+    pub task_id: String,
+    /// Seconds per day (UTC)
+    pub daily_seconds: BTreeMap<NaiveDate, i64>,
+    /// Total seconds
+    pub total_seconds: i64,
+    /// Seconds estimated from commit history (part of the total)
+    pub estimated_seconds: i64,
+    /// Spend of the entries (e.g. agent runs)
+    pub cost_usd: f64,
+}
+
+/// Time per task and day over a date range
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Timesheet {
+    /// Rows by task ID
+    pub rows: Vec<TimesheetRow>,
+}
+
+impl Timesheet {
+    /// Total seconds of all rows
+    pub fn total_seconds(&self) -> i64 {
+        self.rows.iter().map(|row| row.total_seconds).sum()
+    }
+
+    /// Seconds estimated from commit history across all rows
+    pub fn estimated_seconds(&self) -> i64 {
+        self.rows.iter().map(|row| row.estimated_seconds).sum()
+    }
+
+    /// Total seconds per day across all rows
+    pub fn daily_totals(&self) -> BTreeMap<NaiveDate, i64> {
+        let mut totals = BTreeMap::new();
+        for row in &self.rows {
+            for (day, seconds) in &row.daily_seconds {
+                *totals.entry(*day).or_default() += seconds;
+            }
+        }
+        totals
+    }
+}
+
 /// Time tracker manager
 ///
 /// Manages timers, time entries, and provides timesheet views.
-#[derive(Debug)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TimeTracker {
     /// All time entries
     entries: Vec<TimeEntry>,
@@ -390,6 +456,14 @@ impl TimeTracker {
         Duration::seconds(seconds)
     }
 
+    /// Get total cost for a task
+    pub fn total_cost_for_task(&self, task_id: &str) -> f64 {
+        self.entries_for_task(task_id)
+            .iter()
+            .map(|e| e.cost_usd)
+            .sum()
+    }
+
     /// Get statistics for all entries
     pub fn stats(&self) -> TimeStats {
         TimeStats::from_entries(&self.entries)
@@ -409,6 +483,36 @@ impl TimeTracker {
         TimeStats::from_entries(&owned_entries)
     }
 
+    /// Timesheet of entries starting within a date range
+    ///
+    /// Entries count on the day they started; git estimates are also
+    /// reported separately per row.
+    pub fn timesheet(&self, start: DateTime<Utc>, end: DateTime<Utc>) -> Timesheet {
+        let mut rows: BTreeMap<&str, TimesheetRow> = BTreeMap::new();
+        for entry in self.entries_in_range(start, end) {
+            let row = rows.entry(&entry.task_id).or_insert_with(|| TimesheetRow {
+                task_id: entry.task_id.clone(),
+                ..TimesheetRow::default()
+            });
+            *row.daily_seconds
+                .entry(entry.start.date_naive())
+                .or_default() += entry.duration_seconds;
+            row.total_seconds += entry.duration_seconds;
+            if entry.entry_type == TimeEntryType::Git {
+                row.estimated_seconds += entry.duration_seconds;
+            }
+            row.cost_usd += entry.cost_usd;
+        }
+        Timesheet {
+            rows: rows.into_values().collect(),
+        }
+    }
+
+    /// Whether an entry with the given ID exists
+    pub fn has_entry(&self, id: &str) -> bool {
+        self.entries.iter().any(|entry| entry.id == id)
+    }
+
     /// Clear all entries
     pub fn clear(&mut self) {
         self.entries.clear();
@@ -698,9 +802,52 @@ mod tests {
         assert_eq!(tracker.entries()[0].task_id, "task-456");
     }
 
+    #[test]
+    fn test_tracker_total_cost_for_task() {
+        let mut tracker = TimeTracker::new();
+        let start = Utc::now();
+        tracker.add_entry(TimeEntry::manual("task-1", start, Duration::minutes(5)).cost(0.25));
+        tracker.add_entry(TimeEntry::manual("task-1", start, Duration::minutes(3)).cost(0.5));
+        tracker.add_entry(TimeEntry::manual("task-2", start, Duration::minutes(1)).cost(1.0));
+
+        assert!((tracker.total_cost_for_task("task-1") - 0.75).abs() < f64::EPSILON);
+        assert_eq!(tracker.total_cost_for_task("task-3"), 0.0);
+    }
+
     #[test]
     fn test_default_tracker() {
         let tracker = TimeTracker::default();
         assert_eq!(tracker.entries().len(), 0);
     }
+
+    #[test]
+    fn test_timesheet_groups_by_task_and_day() {
+        let mut tracker = TimeTracker::new();
+        let monday = make_datetime(2025, 11, 10, 9, 0);
+        let tuesday = make_datetime(2025, 11, 11, 9, 0);
+        tracker.add_manual_time("task-1", monday, Duration::hours(2));
+        tracker.add_entry(TimeEntry::estimated(
+            "task-1",
+            tuesday,
+            Duration::minutes(30),
+        ));
+        tracker.add_entry(TimeEntry::manual("task-2", tuesday, Duration::hours(1)).cost(0.5));
+        tracker.add_manual_time(
+            "task-2",
+            make_datetime(2025, 11, 20, 9, 0),
+            Duration::hours(1),
+        );
+
+        let sheet = tracker.timesheet(monday, make_datetime(2025, 11, 12, 0, 0));
+
+        assert_eq!(sheet.rows.len(), 2);
+        let first = &sheet.rows[0];
+        assert_eq!(first.task_id, "task-1");
+        assert_eq!(first.daily_seconds.len(), 2);
+        assert_eq!(first.total_seconds, 9000);
+        assert_eq!(first.estimated_seconds, 1800);
+        assert_eq!(sheet.rows[1].cost_usd, 0.5);
+        assert_eq!(sheet.total_seconds(), 12600);
+        assert_eq!(sheet.daily_totals()[&tuesday.date_naive()], 5400);
+    }
 }
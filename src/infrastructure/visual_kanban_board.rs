@@ -173,7 +173,7 @@ impl KanbanBoard {
 }
 
 /// Board manager
-#[derive(Debug)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BoardManager {
     boards: HashMap<String, KanbanBoard>,
     columns: HashMap<String, KanbanColumn>, // column_id -> column
@@ -219,6 +219,13 @@ impl BoardManager {
         self.boards.get(board_id)
     }
 
+    /// Gets all boards (oldest first)
+    pub fn boards(&self) -> Vec<&KanbanBoard> {
+        let mut boards: Vec<&KanbanBoard> = self.boards.values().collect();
+        boards.sort_by(|a, b| a.created_at.cmp(&b.created_at).then(a.id.cmp(&b.id)));
+        boards
+    }
+
     /// Gets a mutable board by ID
     pub fn get_board_mut(&mut self, board_id: &str) -> Option<&mut KanbanBoard> {
         self.boards.get_mut(board_id)
@@ -493,6 +500,16 @@ mod tests {
         assert_eq!(board.owner_id, "user-1");
     }
 
+    #[test]
+    fn test_boards_oldest_first() {
+        let mut manager = BoardManager::new();
+        let first = manager.create_board("A".to_string(), String::new(), "u".to_string());
+        let second = manager.create_board("B".to_string(), String::new(), "u".to_string());
+
+        let ids: Vec<&str> = manager.boards().iter().map(|b| b.id.as_str()).collect();
+        assert_eq!(ids, vec![first.as_str(), second.as_str()]);
+    }
+
     #[test]
     fn test_create_column() {
         let mut manager = BoardManager::new();
@@ -0,0 +1,1365 @@
+//! Local IPC between TOAD instances
+//!
+//! [`CrossWindowContextManager`] keeps clipboard entries and agent contexts
+//! for the windows of one process. A [`WindowEndpoint`] connects it to the
+//! other TOAD instances of the same user over Unix domain sockets, so they
+//! can share clipboard entries and agent context and hand each other files
+//! and diffs.
+//!
+//! # Discovery
+//!
+//! Every instance listens on `<dir>/<window-id>.sock` and registers itself in
+//! the per-user discovery file `<dir>/windows.json` (see [`Discovery`]).
+//! `dir` is `$XDG_RUNTIME_DIR/toad`, or `toad-<user>` in the temp directory,
+//! created with mode `0700`. Entries whose socket no longer accepts
+//! connections are pruned when the window list is read.
+//!
+//! # Protocol
+//!
+//! Frames are newline-delimited JSON ([`Frame`]). A connection carries
+//! messages one way: the connecting window sends `hello` with the range of
+//! protocol versions it speaks, the listener answers `welcome` with the
+//! highest common version (or `reject`), and from then on the client only
+//! sends `message` frames, each an [`Envelope`] stamped with the negotiated
+//! version.
+//!
+//! Message types a peer does not know decode as [`IpcMessage::Unknown`] and
+//! are skipped, as are unknown fields, so adding messages or optional fields
+//! does not need a new version. Changing the meaning of existing ones does:
+//! bump [`PROTOCOL_VERSION`], and raise [`MIN_PROTOCOL_VERSION`] once the old
+//! format is no longer produced.
+//!
+//! # Reconnects
+//!
+//! Connections to peers are opened on first use and cached. When a write
+//! fails, the peer is looked up again (it may have restarted) and
+//! reconnected once. If it is still unreachable, the message is queued and
+//! retried with exponential backoff by [`WindowEndpoint::poll`] until it
+//! expires, keeping the order of messages per peer.
+//!
+//! # Examples
+//!
+//! ```
+//! use std::time::Duration;
+//! use toad::infrastructure::cross_window_context::CrossWindowContextManager;
+//! use toad::infrastructure::window_ipc::{IpcConfig, IpcEvent, IpcMessage, WindowEndpoint};
+//!
+//! let dir = tempfile::tempdir().unwrap();
+//! let config = IpcConfig::in_dir(dir.path());
+//! let mut left = WindowEndpoint::start("left", &config).unwrap();
+//! let mut right = WindowEndpoint::start("right", &config).unwrap();
+//!
+//! left.send(
+//!     "right",
+//!     IpcMessage::OpenFile {
+//!         path: "src/main.rs".into(),
+//!         line: Some(42),
+//!     },
+//! )
+//! .unwrap();
+//!
+//! let mut manager = CrossWindowContextManager::new();
+//! while let Some(event) = right.recv_timeout(Duration::from_secs(5)) {
+//!     if let IpcEvent::Message(incoming) = event {
+//!         assert_eq!(incoming.from, "left");
+//!         // Files and diffs are for the application to handle
+//!         assert!(!incoming.apply(&mut manager));
+//!         break;
+//!     }
+//! }
+//! ```
+
+use crate::infrastructure::cross_window_context::{
+    ClipboardEntry, CrossWindowContextManager, SharedAgentContext,
+};
+use crate::infrastructure::fs_util::atomic_write;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::fs;
+use std::io::{self, ErrorKind, Read, Write};
+use std::os::unix::fs::DirBuilderExt;
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+use thiserror::Error;
+
+/// Newest protocol version this build speaks
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/// Oldest protocol version this build still accepts
+pub const MIN_PROTOCOL_VERSION: u32 = 1;
+
+/// Name of the discovery file in the IPC directory
+const DISCOVERY_FILE: &str = "windows.json";
+
+/// Largest frame accepted from a peer
+const MAX_FRAME_BYTES: usize = 16 * 1024 * 1024;
+
+/// Timeout for the handshake and for writes to a peer
+const IO_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// How often the listener and connection threads check for shutdown
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Messages kept for unreachable peers before the oldest are dropped
+const MAX_QUEUED: usize = 256;
+
+/// Delay before the first retry of a queued message (doubled per attempt)
+const RETRY_BASE: Duration = Duration::from_millis(100);
+
+/// Longest delay between retries
+const RETRY_MAX: Duration = Duration::from_secs(5);
+
+/// How long a queued message is retried before it is dropped
+const QUEUE_TTL: Duration = Duration::from_secs(60);
+
+/// Age after which a discovery lock is considered abandoned
+const LOCK_STALE: Duration = Duration::from_secs(5);
+
+/// Errors from the IPC transport
+#[derive(Debug, Error)]
+pub enum IpcError {
+    /// Socket or discovery file I/O failed
+    #[error("IPC I/O error: {0}")]
+    Io(#[from] io::Error),
+
+    /// A frame or the discovery file could not be (de)serialized
+    #[error("Malformed IPC data: {0}")]
+    Json(#[from] serde_json::Error),
+
+    /// Window ID is empty or not usable in a socket name
+    #[error("Invalid window ID '{0}' (use letters, digits, '-' and '_')")]
+    InvalidWindowId(String),
+
+    /// Another running instance already uses the window ID
+    #[error("Window '{0}' is already running")]
+    WindowIdInUse(String),
+
+    /// No running window has the ID
+    #[error("Window '{0}' is not running")]
+    UnknownWindow(String),
+
+    /// The peer refused the connection (e.g. no common protocol version)
+    #[error("Window '{window_id}' refused the connection: {reason}")]
+    Rejected {
+        /// Window that refused
+        window_id: String,
+        /// Reason given by the peer
+        reason: String,
+    },
+
+    /// The peer did not complete the handshake
+    #[error("Handshake with window '{window_id}' failed: {message}")]
+    Handshake {
+        /// Window being connected to
+        window_id: String,
+        /// What went wrong
+        message: String,
+    },
+}
+
+/// Where the IPC sockets and discovery file live
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IpcConfig {
+    /// Directory holding the sockets and the discovery file
+    pub dir: PathBuf,
+    /// Title shown to other windows (e.g. the project name)
+    pub title: Option<String>,
+}
+
+impl IpcConfig {
+    /// Per-user directory: `$XDG_RUNTIME_DIR/toad`, or `toad-<user>` in the
+    /// temp directory
+    pub fn for_current_user() -> Self {
+        let dir = match std::env::var_os("XDG_RUNTIME_DIR") {
+            Some(runtime) if !runtime.is_empty() => PathBuf::from(runtime).join("toad"),
+            _ => {
+                let user = std::env::var("USER")
+                    .or_else(|_| std::env::var("LOGNAME"))
+                    .unwrap_or_else(|_| "default".to_string());
+                std::env::temp_dir().join(format!("toad-{}", user))
+            }
+        };
+        Self::in_dir(dir)
+    }
+
+    /// Use the given directory
+    pub fn in_dir(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            title: None,
+        }
+    }
+
+    /// Set the title shown to other windows
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+}
+
+/// A running window as listed in the discovery file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WindowInfo {
+    /// Window ID
+    pub window_id: String,
+    /// Socket the window listens on
+    pub socket: PathBuf,
+    /// Process ID of the instance
+    pub pid: u32,
+    /// Title shown to other windows
+    #[serde(default)]
+    pub title: Option<String>,
+    /// When the window started listening
+    pub started_at: DateTime<Utc>,
+    /// Oldest protocol version the window accepts
+    pub min_version: u32,
+    /// Newest protocol version the window speaks
+    pub max_version: u32,
+}
+
+/// Contents of the discovery file
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct DiscoveryFile {
+    /// Running windows by ID
+    #[serde(default)]
+    windows: BTreeMap<String, WindowInfo>,
+}
+
+/// The per-user discovery file listing running windows
+///
+/// Updates are serialized with a lock file and written atomically, so
+/// windows starting at the same time do not drop each other's entries.
+#[derive(Debug, Clone)]
+pub struct Discovery {
+    dir: PathBuf,
+}
+
+impl Discovery {
+    /// Discovery file in the given IPC directory
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Path of the discovery file
+    pub fn path(&self) -> PathBuf {
+        self.dir.join(DISCOVERY_FILE)
+    }
+
+    /// Running windows, pruning entries whose socket is gone
+    pub fn windows(&self) -> Result<Vec<WindowInfo>, IpcError> {
+        let (live, stale): (Vec<_>, Vec<_>) = self
+            .read()
+            .windows
+            .into_values()
+            .partition(|info| UnixStream::connect(&info.socket).is_ok());
+
+        if !stale.is_empty() {
+            self.update(|file| {
+                for info in &stale {
+                    if file.windows.get(&info.window_id) == Some(info) {
+                        file.windows.remove(&info.window_id);
+                    }
+                }
+            })?;
+        }
+        Ok(live)
+    }
+
+    /// Look up a window without checking that it is alive
+    pub fn find(&self, window_id: &str) -> Option<WindowInfo> {
+        self.read().windows.remove(window_id)
+    }
+
+    /// Add or replace a window's entry
+    pub fn register(&self, info: WindowInfo) -> Result<(), IpcError> {
+        self.update(|file| {
+            file.windows.insert(info.window_id.clone(), info);
+        })
+    }
+
+    /// Remove a window's entry
+    pub fn unregister(&self, window_id: &str) -> Result<(), IpcError> {
+        self.update(|file| {
+            file.windows.remove(window_id);
+        })
+    }
+
+    /// Read the file; a missing or unreadable file lists no windows
+    fn read(&self) -> DiscoveryFile {
+        fs::read(self.path())
+            .ok()
+            .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+            .unwrap_or_default()
+    }
+
+    /// Read-modify-write the file under the lock
+    fn update(&self, change: impl FnOnce(&mut DiscoveryFile)) -> Result<(), IpcError> {
+        let _lock = DiscoveryLock::acquire(&self.dir.join(format!("{}.lock", DISCOVERY_FILE)))?;
+        let mut file = self.read();
+        change(&mut file);
+        atomic_write(&self.path(), &serde_json::to_vec_pretty(&file)?)?;
+        Ok(())
+    }
+}
+
+/// Lock file held while the discovery file is updated
+struct DiscoveryLock {
+    path: PathBuf,
+}
+
+impl DiscoveryLock {
+    /// Create the lock file, waiting for other holders and breaking locks
+    /// left behind by crashed instances
+    fn acquire(path: &Path) -> io::Result<Self> {
+        let deadline = Instant::now() + IO_TIMEOUT;
+        loop {
+            match fs::OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(path)
+            {
+                Ok(_) => {
+                    return Ok(Self {
+                        path: path.to_path_buf(),
+                    });
+                }
+                Err(e) if e.kind() == ErrorKind::AlreadyExists => {
+                    let abandoned = fs::metadata(path)
+                        .and_then(|meta| meta.modified())
+                        .ok()
+                        .and_then(|modified| modified.elapsed().ok())
+                        .is_some_and(|age| age > LOCK_STALE);
+                    if abandoned {
+                        let _ = fs::remove_file(path);
+                    } else if Instant::now() > deadline {
+                        return Err(io::Error::new(
+                            ErrorKind::TimedOut,
+                            format!("{} is locked", path.display()),
+                        ));
+                    } else {
+                        thread::sleep(Duration::from_millis(10));
+                    }
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
+
+impl Drop for DiscoveryLock {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+/// A frame on the wire, one JSON object per line
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Frame {
+    /// First frame from the connecting window
+    Hello {
+        /// Connecting window
+        window_id: String,
+        /// Oldest version it accepts
+        min_version: u32,
+        /// Newest version it speaks
+        max_version: u32,
+    },
+    /// Handshake accepted
+    Welcome {
+        /// Listening window
+        window_id: String,
+        /// Version used on this connection
+        version: u32,
+    },
+    /// Handshake refused; the connection is closed
+    Reject {
+        /// Why
+        reason: String,
+    },
+    /// A message, after the handshake
+    Message(Envelope),
+}
+
+/// A message with its routing information
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Envelope {
+    /// Protocol version negotiated for the connection
+    pub version: u32,
+    /// Sender's sequence number (increasing per sending window)
+    pub seq: u64,
+    /// Sending window
+    pub from: String,
+    /// When the message was sent (first attempt)
+    pub sent_at: DateTime<Utc>,
+    /// The message
+    pub message: IpcMessage,
+}
+
+/// Messages exchanged between windows
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum IpcMessage {
+    /// A clipboard entry copied in the sending window
+    Clipboard {
+        /// The entry
+        entry: ClipboardEntry,
+    },
+    /// Current state of an agent context owned by the sender
+    AgentContext {
+        /// The context
+        context: SharedAgentContext,
+    },
+    /// Subscribe the sender to an agent context of the receiver
+    Subscribe {
+        /// Context to follow
+        context_id: String,
+    },
+    /// Stop following an agent context
+    Unsubscribe {
+        /// Context to stop following
+        context_id: String,
+    },
+    /// Open a file in the receiving window
+    OpenFile {
+        /// File to open
+        path: PathBuf,
+        /// Line to jump to (1-based)
+        #[serde(default)]
+        line: Option<usize>,
+    },
+    /// Show a diff in the receiving window
+    Diff {
+        /// Short description (e.g. the file or change it came from)
+        title: String,
+        /// Unified diff
+        diff: String,
+    },
+    /// A message type from a newer version; ignored
+    #[serde(other)]
+    Unknown,
+}
+
+/// A message received from another window
+#[derive(Debug, Clone)]
+pub struct IncomingMessage {
+    /// Sending window
+    pub from: String,
+    /// Sender's sequence number
+    pub seq: u64,
+    /// When it was sent
+    pub sent_at: DateTime<Utc>,
+    /// The message
+    pub message: IpcMessage,
+}
+
+impl IncomingMessage {
+    /// Fold the message into a context manager
+    ///
+    /// Returns false if the manager has nothing to do with it: files, diffs
+    /// and unknown messages are for the application, and subscriptions to
+    /// unknown contexts are ignored.
+    pub fn apply(&self, manager: &mut CrossWindowContextManager) -> bool {
+        match &self.message {
+            IpcMessage::Clipboard { entry } => {
+                manager.receive_clipboard_entry(entry.clone());
+                true
+            }
+            IpcMessage::AgentContext { context } => {
+                manager.upsert_agent_context(context.clone());
+                true
+            }
+            IpcMessage::Subscribe { context_id } => manager
+                .subscribe_to_agent_context(context_id, self.from.clone())
+                .is_ok(),
+            IpcMessage::Unsubscribe { context_id } => manager
+                .unsubscribe_from_agent_context(context_id, &self.from)
+                .is_ok(),
+            IpcMessage::OpenFile { .. } | IpcMessage::Diff { .. } | IpcMessage::Unknown => false,
+        }
+    }
+}
+
+/// Something that happened on an endpoint
+#[derive(Debug, Clone)]
+pub enum IpcEvent {
+    /// A window connected to this one
+    Connected {
+        /// The window
+        window_id: String,
+        /// Negotiated protocol version
+        version: u32,
+    },
+    /// A message arrived
+    Message(IncomingMessage),
+    /// A connected window went away
+    Disconnected {
+        /// The window
+        window_id: String,
+    },
+    /// A message could not be delivered and was given up
+    Dropped {
+        /// Intended recipient
+        to: String,
+        /// The message
+        message: IpcMessage,
+    },
+}
+
+/// Outcome of [`WindowEndpoint::send`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Delivery {
+    /// Written to the peer's socket
+    Sent,
+    /// Peer unreachable; will be retried by [`WindowEndpoint::poll`]
+    Queued,
+}
+
+/// Highest protocol version both sides speak, if any
+pub fn negotiate_version(min_version: u32, max_version: u32) -> Option<u32> {
+    let version = max_version.min(PROTOCOL_VERSION);
+    (version >= min_version.max(MIN_PROTOCOL_VERSION)).then_some(version)
+}
+
+/// Generate a window ID unique to this process
+pub fn new_window_id() -> String {
+    let suffix = uuid::Uuid::new_v4().simple().to_string();
+    format!("{}-{}", std::process::id(), &suffix[..8])
+}
+
+/// Connection to a peer's socket
+#[derive(Debug)]
+struct PeerLink {
+    stream: UnixStream,
+    version: u32,
+}
+
+/// A message waiting for its peer to come back
+#[derive(Debug)]
+struct Pending {
+    to: String,
+    envelope: Envelope,
+    attempts: u32,
+    queued_at: Instant,
+    next_attempt: Instant,
+}
+
+/// Accepted connections, so shutdown can close them
+type Connections = Arc<Mutex<Vec<(UnixStream, JoinHandle<()>)>>>;
+
+/// This instance's end of the IPC transport
+///
+/// Listens for other windows on a background thread and sends to them on
+/// demand. Received messages are collected with [`poll`](Self::poll) or
+/// [`recv_timeout`](Self::recv_timeout). Dropping the endpoint closes the
+/// socket and removes the window from discovery.
+#[derive(Debug)]
+pub struct WindowEndpoint {
+    window_id: String,
+    socket: PathBuf,
+    discovery: Discovery,
+    events: Receiver<IpcEvent>,
+    local_events: VecDeque<IpcEvent>,
+    peers: HashMap<String, PeerLink>,
+    /// Peers this window has been connected to (queued for when missing)
+    known_peers: HashSet<String>,
+    outbox: VecDeque<Pending>,
+    next_seq: u64,
+    stop: Arc<AtomicBool>,
+    listener: Option<JoinHandle<()>>,
+    connections: Connections,
+}
+
+impl WindowEndpoint {
+    /// Listen as `window_id` and register in discovery
+    pub fn start(window_id: impl Into<String>, config: &IpcConfig) -> Result<Self, IpcError> {
+        let window_id = window_id.into();
+        let valid_id = !window_id.is_empty()
+            && window_id.len() <= 64
+            && window_id
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        if !valid_id {
+            return Err(IpcError::InvalidWindowId(window_id));
+        }
+
+        fs::DirBuilder::new()
+            .recursive(true)
+            .mode(0o700)
+            .create(&config.dir)?;
+        let socket = config.dir.join(format!("{}.sock", window_id));
+        if socket.exists() {
+            if UnixStream::connect(&socket).is_ok() {
+                return Err(IpcError::WindowIdInUse(window_id));
+            }
+            fs::remove_file(&socket)?;
+        }
+        let listener = UnixListener::bind(&socket)?;
+        listener.set_nonblocking(true)?;
+
+        let (events_tx, events) = mpsc::channel();
+        let stop = Arc::new(AtomicBool::new(false));
+        let connections = Connections::default();
+        let listener = {
+            let window_id = window_id.clone();
+            let stop = Arc::clone(&stop);
+            let connections = Arc::clone(&connections);
+            thread::Builder::new()
+                .name("toad-ipc-listener".to_string())
+                .spawn(move || accept_loop(listener, window_id, events_tx, stop, connections))?
+        };
+
+        let discovery = Discovery::new(&config.dir);
+        let endpoint = Self {
+            window_id: window_id.clone(),
+            socket: socket.clone(),
+            discovery,
+            events,
+            local_events: VecDeque::new(),
+            peers: HashMap::new(),
+            known_peers: HashSet::new(),
+            outbox: VecDeque::new(),
+            next_seq: 1,
+            stop,
+            listener: Some(listener),
+            connections,
+        };
+        endpoint.discovery.register(WindowInfo {
+            window_id,
+            socket,
+            pid: std::process::id(),
+            title: config.title.clone(),
+            started_at: Utc::now(),
+            min_version: MIN_PROTOCOL_VERSION,
+            max_version: PROTOCOL_VERSION,
+        })?;
+        Ok(endpoint)
+    }
+
+    /// This window's ID
+    pub fn window_id(&self) -> &str {
+        &self.window_id
+    }
+
+    /// Other running windows
+    pub fn peers(&self) -> Result<Vec<WindowInfo>, IpcError> {
+        Ok(self
+            .discovery
+            .windows()?
+            .into_iter()
+            .filter(|info| info.window_id != self.window_id)
+            .collect())
+    }
+
+    /// Send a message to one window
+    ///
+    /// Fails if the window is not running (and was never connected) or
+    /// refuses the connection; a window that went away after connecting gets
+    /// the message queued instead.
+    pub fn send(&mut self, to: &str, message: IpcMessage) -> Result<Delivery, IpcError> {
+        let envelope = Envelope {
+            version: PROTOCOL_VERSION,
+            seq: self.next_seq,
+            from: self.window_id.clone(),
+            sent_at: Utc::now(),
+            message,
+        };
+        self.next_seq += 1;
+
+        // Keep the order of messages to a peer that is being retried
+        if self.outbox.iter().any(|pending| pending.to == to) {
+            self.enqueue(to, envelope);
+            return Ok(Delivery::Queued);
+        }
+        match self.write_to_peer(to, &envelope) {
+            Ok(()) => Ok(Delivery::Sent),
+            Err(e) if self.is_retryable(to, &e) => {
+                tracing::debug!("Queueing message for window {}: {}", to, e);
+                self.enqueue(to, envelope);
+                Ok(Delivery::Queued)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Send a message to every other running window, returning how many it
+    /// was sent or queued to
+    pub fn broadcast(&mut self, message: IpcMessage) -> Result<usize, IpcError> {
+        let mut reached = 0;
+        for peer in self.peers()? {
+            match self.send(&peer.window_id, message.clone()) {
+                Ok(_) => reached += 1,
+                Err(e) => tracing::warn!("Broadcast to window {} failed: {}", peer.window_id, e),
+            }
+        }
+        Ok(reached)
+    }
+
+    /// Push an agent context to the windows subscribed to it, returning how
+    /// many it was sent or queued to
+    pub fn publish_agent_context(&mut self, context: &SharedAgentContext) -> usize {
+        let mut reached = 0;
+        for subscriber in &context.subscribers {
+            if *subscriber == self.window_id {
+                continue;
+            }
+            let message = IpcMessage::AgentContext {
+                context: context.clone(),
+            };
+            match self.send(subscriber, message) {
+                Ok(_) => reached += 1,
+                Err(e) => {
+                    tracing::warn!("Publishing context to window {} failed: {}", subscriber, e)
+                }
+            }
+        }
+        reached
+    }
+
+    /// Retry queued messages and collect what happened since the last call
+    pub fn poll(&mut self) -> Vec<IpcEvent> {
+        self.retry_queued();
+        let mut events: Vec<IpcEvent> = self.local_events.drain(..).collect();
+        events.extend(self.events.try_iter());
+        events
+    }
+
+    /// Wait up to `timeout` for the next event, retrying queued messages
+    /// while waiting
+    pub fn recv_timeout(&mut self, timeout: Duration) -> Option<IpcEvent> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            self.retry_queued();
+            if let Some(event) = self.local_events.pop_front() {
+                return Some(event);
+            }
+            let wait = deadline
+                .saturating_duration_since(Instant::now())
+                .min(POLL_INTERVAL);
+            match self.events.recv_timeout(wait) {
+                Ok(event) => return Some(event),
+                Err(RecvTimeoutError::Timeout) if Instant::now() < deadline => {}
+                Err(_) => return None,
+            }
+        }
+    }
+
+    /// Number of messages waiting for their peer
+    pub fn queued(&self) -> usize {
+        self.outbox.len()
+    }
+
+    /// Write a message on the cached connection, reconnecting once if it
+    /// broke
+    fn write_to_peer(&mut self, to: &str, envelope: &Envelope) -> Result<(), IpcError> {
+        if let Some(link) = self.peers.get_mut(to) {
+            match write_message(link, envelope) {
+                Ok(()) => return Ok(()),
+                Err(e) => {
+                    tracing::debug!("Connection to window {} broke: {}", to, e);
+                    self.peers.remove(to);
+                }
+            }
+        }
+
+        let info = self
+            .discovery
+            .find(to)
+            .ok_or_else(|| IpcError::UnknownWindow(to.to_string()))?;
+        let mut link = connect(&self.window_id, &info)?;
+        write_message(&mut link, envelope)?;
+        self.peers.insert(to.to_string(), link);
+        self.known_peers.insert(to.to_string());
+        Ok(())
+    }
+
+    /// Whether a failed send should be queued: the peer is temporarily
+    /// unreachable rather than incompatible
+    fn is_retryable(&self, to: &str, error: &IpcError) -> bool {
+        match error {
+            IpcError::Io(_) => true,
+            IpcError::UnknownWindow(_) => self.known_peers.contains(to),
+            _ => false,
+        }
+    }
+
+    /// Queue a message, dropping the oldest if the queue is full
+    fn enqueue(&mut self, to: &str, envelope: Envelope) {
+        if self.outbox.len() >= MAX_QUEUED
+            && let Some(oldest) = self.outbox.pop_front()
+        {
+            self.drop_pending(oldest);
+        }
+        let now = Instant::now();
+        self.outbox.push_back(Pending {
+            to: to.to_string(),
+            envelope,
+            attempts: 0,
+            queued_at: now,
+            next_attempt: now + RETRY_BASE,
+        });
+    }
+
+    /// Retry queued messages that are due, in order per peer
+    fn retry_queued(&mut self) {
+        if self.outbox.is_empty() {
+            return;
+        }
+        let now = Instant::now();
+        let mut blocked: HashSet<String> = HashSet::new();
+        let mut still_queued = VecDeque::new();
+
+        for mut pending in std::mem::take(&mut self.outbox) {
+            if now.duration_since(pending.queued_at) > QUEUE_TTL {
+                self.drop_pending(pending);
+                continue;
+            }
+            if blocked.contains(&pending.to) || now < pending.next_attempt {
+                blocked.insert(pending.to.clone());
+                still_queued.push_back(pending);
+                continue;
+            }
+            match self.write_to_peer(&pending.to, &pending.envelope) {
+                Ok(()) => {}
+                Err(e) if self.is_retryable(&pending.to, &e) => {
+                    pending.attempts += 1;
+                    let backoff = RETRY_BASE
+                        .saturating_mul(2u32.saturating_pow(pending.attempts))
+                        .min(RETRY_MAX);
+                    pending.next_attempt = now + backoff;
+                    blocked.insert(pending.to.clone());
+                    still_queued.push_back(pending);
+                }
+                Err(e) => {
+                    tracing::warn!("Giving up on message for window {}: {}", pending.to, e);
+                    self.drop_pending(pending);
+                }
+            }
+        }
+        self.outbox = still_queued;
+    }
+
+    /// Report a message that will not be delivered
+    fn drop_pending(&mut self, pending: Pending) {
+        self.local_events.push_back(IpcEvent::Dropped {
+            to: pending.to,
+            message: pending.envelope.message,
+        });
+    }
+}
+
+impl Drop for WindowEndpoint {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(listener) = self.listener.take() {
+            let _ = listener.join();
+        }
+        // Close incoming connections so peers notice at their next write
+        let connections = std::mem::take(
+            &mut *self
+                .connections
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner()),
+        );
+        for (stream, handle) in connections {
+            let _ = stream.shutdown(std::net::Shutdown::Both);
+            let _ = handle.join();
+        }
+        let _ = fs::remove_file(&self.socket);
+        if let Err(e) = self.discovery.unregister(&self.window_id) {
+            tracing::warn!("Failed to unregister window {}: {}", self.window_id, e);
+        }
+    }
+}
+
+/// Open a connection to a peer and perform the handshake
+fn connect(window_id: &str, info: &WindowInfo) -> Result<PeerLink, IpcError> {
+    let handshake_error = |message: String| IpcError::Handshake {
+        window_id: info.window_id.clone(),
+        message,
+    };
+
+    let mut stream = UnixStream::connect(&info.socket)?;
+    stream.set_read_timeout(Some(IO_TIMEOUT))?;
+    stream.set_write_timeout(Some(IO_TIMEOUT))?;
+    write_frame(
+        &mut stream,
+        &Frame::Hello {
+            window_id: window_id.to_string(),
+            min_version: MIN_PROTOCOL_VERSION,
+            max_version: PROTOCOL_VERSION,
+        },
+    )?;
+
+    let line = FrameReader::default()
+        .next_line(&mut stream)?
+        .ok_or_else(|| handshake_error("connection closed".to_string()))?;
+    match serde_json::from_slice(&line)? {
+        Frame::Welcome { version, .. }
+            if (MIN_PROTOCOL_VERSION..=PROTOCOL_VERSION).contains(&version) =>
+        {
+            Ok(PeerLink { stream, version })
+        }
+        Frame::Welcome { version, .. } => Err(handshake_error(format!(
+            "unsupported protocol version {}",
+            version
+        ))),
+        Frame::Reject { reason } => Err(IpcError::Rejected {
+            window_id: info.window_id.clone(),
+            reason,
+        }),
+        _ => Err(handshake_error("expected welcome".to_string())),
+    }
+}
+
+/// Write a message frame at the connection's version
+fn write_message(link: &mut PeerLink, envelope: &Envelope) -> Result<(), IpcError> {
+    let mut envelope = envelope.clone();
+    envelope.version = link.version;
+    write_frame(&mut link.stream, &Frame::Message(envelope))
+}
+
+/// Write one frame followed by a newline
+fn write_frame(stream: &mut UnixStream, frame: &Frame) -> Result<(), IpcError> {
+    let mut bytes = serde_json::to_vec(frame)?;
+    bytes.push(b'\n');
+    stream.write_all(&bytes)?;
+    stream.flush()?;
+    Ok(())
+}
+
+/// Splits a byte stream into lines, across read timeouts
+#[derive(Debug, Default)]
+struct FrameReader {
+    buf: Vec<u8>,
+    /// Bytes of `buf` already searched for a newline
+    scanned: usize,
+}
+
+impl FrameReader {
+    /// Next complete line, or `None` at end of stream
+    ///
+    /// Read timeouts are returned as errors without losing buffered data,
+    /// so the caller can check for shutdown and call again.
+    fn next_line(&mut self, stream: &mut impl Read) -> io::Result<Option<Vec<u8>>> {
+        loop {
+            if let Some(pos) = self.buf[self.scanned..].iter().position(|&b| b == b'\n') {
+                let end = self.scanned + pos + 1;
+                let line = self.buf.drain(..end).collect();
+                self.scanned = 0;
+                return Ok(Some(line));
+            }
+            self.scanned = self.buf.len();
+            if self.buf.len() > MAX_FRAME_BYTES {
+                return Err(io::Error::new(ErrorKind::InvalidData, "frame too large"));
+            }
+
+            let mut chunk = [0u8; 8192];
+            let read = stream.read(&mut chunk)?;
+            if read == 0 {
+                return Ok(None);
+            }
+            self.buf.extend_from_slice(&chunk[..read]);
+        }
+    }
+}
+
+/// Whether an error is a read timeout
+fn is_timeout(error: &io::Error) -> bool {
+    matches!(error.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut)
+}
+
+/// Accept connections until shutdown
+fn accept_loop(
+    listener: UnixListener,
+    window_id: String,
+    events: Sender<IpcEvent>,
+    stop: Arc<AtomicBool>,
+    connections: Connections,
+) {
+    while !stop.load(Ordering::Relaxed) {
+        match listener.accept() {
+            Ok((stream, _)) => {
+                let accepted = stream.try_clone().and_then(|clone| {
+                    let window_id = window_id.clone();
+                    let events = events.clone();
+                    let stop = Arc::clone(&stop);
+                    let handle = thread::Builder::new()
+                        .name("toad-ipc-connection".to_string())
+                        .spawn(move || serve_connection(stream, &window_id, &events, &stop))?;
+                    Ok((clone, handle))
+                });
+                match accepted {
+                    Ok(connection) => {
+                        let mut connections = connections
+                            .lock()
+                            .unwrap_or_else(|poisoned| poisoned.into_inner());
+                        connections.retain(|(_, handle)| !handle.is_finished());
+                        connections.push(connection);
+                    }
+                    Err(e) => tracing::warn!("Failed to serve IPC connection: {}", e),
+                }
+            }
+            Err(e) if e.kind() == ErrorKind::WouldBlock => thread::sleep(POLL_INTERVAL),
+            Err(e) => {
+                tracing::warn!("IPC accept failed: {}", e);
+                thread::sleep(POLL_INTERVAL);
+            }
+        }
+    }
+}
+
+/// Handshake with a connecting window, then forward its messages
+fn serve_connection(
+    mut stream: UnixStream,
+    window_id: &str,
+    events: &Sender<IpcEvent>,
+    stop: &AtomicBool,
+) {
+    // Accepted sockets may inherit the listener's non-blocking mode
+    let setup = stream
+        .set_nonblocking(false)
+        .and_then(|()| stream.set_read_timeout(Some(POLL_INTERVAL)))
+        .and_then(|()| stream.set_write_timeout(Some(IO_TIMEOUT)));
+    if let Err(e) = setup {
+        tracing::warn!("Failed to set up IPC connection: {}", e);
+        return;
+    }
+    let mut reader = FrameReader::default();
+
+    let deadline = Instant::now() + IO_TIMEOUT;
+    let hello = loop {
+        match reader.next_line(&mut stream) {
+            Ok(Some(line)) => break serde_json::from_slice::<Frame>(&line),
+            Err(e)
+                if is_timeout(&e) && Instant::now() < deadline && !stop.load(Ordering::Relaxed) => {
+            }
+            // Closed without a handshake (e.g. a liveness check)
+            _ => return,
+        }
+    };
+    let (peer, min_version, max_version) = match hello {
+        Ok(Frame::Hello {
+            window_id,
+            min_version,
+            max_version,
+        }) => (window_id, min_version, max_version),
+        _ => {
+            let reason = "expected hello".to_string();
+            let _ = write_frame(&mut stream, &Frame::Reject { reason });
+            return;
+        }
+    };
+    let Some(version) = negotiate_version(min_version, max_version) else {
+        let reason = format!(
+            "protocol versions {}-{} not supported (this window speaks {}-{})",
+            min_version, max_version, MIN_PROTOCOL_VERSION, PROTOCOL_VERSION
+        );
+        let _ = write_frame(&mut stream, &Frame::Reject { reason });
+        return;
+    };
+    let welcome = Frame::Welcome {
+        window_id: window_id.to_string(),
+        version,
+    };
+    if write_frame(&mut stream, &welcome).is_err() {
+        return;
+    }
+    let _ = events.send(IpcEvent::Connected {
+        window_id: peer.clone(),
+        version,
+    });
+
+    loop {
+        match reader.next_line(&mut stream) {
+            Ok(Some(line)) => match serde_json::from_slice::<Frame>(&line) {
+                Ok(Frame::Message(envelope)) if envelope.version == version => {
+                    let _ = events.send(IpcEvent::Message(IncomingMessage {
+                        from: peer.clone(),
+                        seq: envelope.seq,
+                        sent_at: envelope.sent_at,
+                        message: envelope.message,
+                    }));
+                }
+                Ok(Frame::Message(envelope)) => tracing::warn!(
+                    "Window {} sent protocol version {} on a version {} connection",
+                    peer,
+                    envelope.version,
+                    version
+                ),
+                Ok(_) => tracing::warn!("Unexpected frame from window {}", peer),
+                Err(e) => tracing::warn!("Malformed frame from window {}: {}", peer, e),
+            },
+            Ok(None) => break,
+            Err(e) if is_timeout(&e) => {
+                if stop.load(Ordering::Relaxed) {
+                    break;
+                }
+            }
+            Err(e) => {
+                tracing::warn!("IPC connection from window {} failed: {}", peer, e);
+                break;
+            }
+        }
+    }
+    let _ = events.send(IpcEvent::Disconnected { window_id: peer });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::cross_window_context::ClipboardContentType;
+    use tempfile::TempDir;
+
+    const WAIT: Duration = Duration::from_secs(5);
+
+    /// Wait for the next message on `to`, polling `from` so its queue is
+    /// retried meanwhile
+    fn next_message(to: &mut WindowEndpoint, from: &mut WindowEndpoint) -> Option<IncomingMessage> {
+        let deadline = Instant::now() + WAIT;
+        while Instant::now() < deadline {
+            from.poll();
+            if let Some(IpcEvent::Message(message)) = to.recv_timeout(POLL_INTERVAL) {
+                return Some(message);
+            }
+        }
+        None
+    }
+
+    fn clipboard(window_id: &str, content: &str) -> IpcMessage {
+        IpcMessage::Clipboard {
+            entry: ClipboardEntry::new(
+                "clip-1".to_string(),
+                window_id.to_string(),
+                ClipboardContentType::Text,
+                content.to_string(),
+            ),
+        }
+    }
+
+    #[test]
+    fn test_negotiate_version() {
+        assert_eq!(
+            negotiate_version(1, PROTOCOL_VERSION),
+            Some(PROTOCOL_VERSION)
+        );
+        assert_eq!(
+            negotiate_version(1, PROTOCOL_VERSION + 5),
+            Some(PROTOCOL_VERSION)
+        );
+        assert_eq!(
+            negotiate_version(PROTOCOL_VERSION + 1, PROTOCOL_VERSION + 2),
+            None
+        );
+        assert_eq!(negotiate_version(0, 0), None);
+    }
+
+    #[test]
+    fn test_unknown_messages_and_fields_are_tolerated() {
+        let line = r#"{"kind":"message","version":1,"seq":7,"from":"w2",
+            "sent_at":"2026-01-01T00:00:00Z","priority":"high",
+            "message":{"type":"open_terminal","cwd":"/tmp"}}"#;
+        let Frame::Message(envelope) = serde_json::from_str(line).unwrap() else {
+            panic!("expected a message frame");
+        };
+        assert_eq!(envelope.seq, 7);
+        assert!(matches!(envelope.message, IpcMessage::Unknown));
+
+        let line = r#"{"kind":"message","version":1,"seq":8,"from":"w2",
+            "sent_at":"2026-01-01T00:00:00Z",
+            "message":{"type":"open_file","path":"a.rs","column":3}}"#;
+        let Frame::Message(envelope) = serde_json::from_str(line).unwrap() else {
+            panic!("expected a message frame");
+        };
+        assert!(matches!(
+            envelope.message,
+            IpcMessage::OpenFile { line: None, .. }
+        ));
+    }
+
+    #[test]
+    fn test_discovery_prunes_dead_windows() {
+        let dir = TempDir::new().unwrap();
+        let discovery = Discovery::new(dir.path());
+        discovery
+            .register(WindowInfo {
+                window_id: "crashed".to_string(),
+                socket: dir.path().join("crashed.sock"),
+                pid: 1,
+                title: None,
+                started_at: Utc::now(),
+                min_version: 1,
+                max_version: 1,
+            })
+            .unwrap();
+        assert!(discovery.find("crashed").is_some());
+
+        let live = WindowEndpoint::start("live", &IpcConfig::in_dir(dir.path())).unwrap();
+        let windows = discovery.windows().unwrap();
+
+        assert_eq!(windows.len(), 1);
+        assert_eq!(windows[0].window_id, live.window_id());
+        assert!(discovery.find("crashed").is_none());
+
+        drop(live);
+        assert!(discovery.find("live").is_none());
+        assert!(!dir.path().join("live.sock").exists());
+    }
+
+    #[test]
+    fn test_messages_update_the_receiving_manager() {
+        let dir = TempDir::new().unwrap();
+        let config = IpcConfig::in_dir(dir.path());
+        let mut left = WindowEndpoint::start("left", &config).unwrap();
+        let mut right = WindowEndpoint::start("right", &config).unwrap();
+        let mut left_manager = CrossWindowContextManager::new();
+        let mut right_manager = CrossWindowContextManager::new();
+
+        let delivery = left.send("right", clipboard("left", "copied")).unwrap();
+        assert_eq!(delivery, Delivery::Sent);
+        let incoming = next_message(&mut right, &mut left).unwrap();
+        assert_eq!(incoming.from, "left");
+        assert!(incoming.apply(&mut right_manager));
+        let latest = right_manager.get_latest_clipboard().unwrap();
+        assert_eq!(latest.content, "copied");
+        assert_eq!(latest.source_window_id, "left");
+
+        // right follows an agent context owned by left
+        let context_id = left_manager.create_shared_agent_context(
+            "left".to_string(),
+            "coder".to_string(),
+            "{}".to_string(),
+        );
+        right
+            .send(
+                "left",
+                IpcMessage::Subscribe {
+                    context_id: context_id.clone(),
+                },
+            )
+            .unwrap();
+        let incoming = next_message(&mut left, &mut right).unwrap();
+        assert!(incoming.apply(&mut left_manager));
+
+        left_manager
+            .update_agent_context(&context_id, "{\"file\":\"main.rs\"}".to_string())
+            .unwrap();
+        let context = left_manager.get_agent_context(&context_id).unwrap().clone();
+        assert_eq!(left.publish_agent_context(&context), 1);
+        let incoming = next_message(&mut right, &mut left).unwrap();
+        assert!(incoming.apply(&mut right_manager));
+        assert_eq!(
+            right_manager
+                .get_agent_context(&context_id)
+                .unwrap()
+                .context_data,
+            "{\"file\":\"main.rs\"}"
+        );
+    }
+
+    #[test]
+    fn test_broadcast_reaches_every_other_window() {
+        let dir = TempDir::new().unwrap();
+        let config = IpcConfig::in_dir(dir.path());
+        let mut sender = WindowEndpoint::start("sender", &config).unwrap();
+        let mut first = WindowEndpoint::start("first", &config.clone().with_title("api")).unwrap();
+        let mut second = WindowEndpoint::start("second", &config).unwrap();
+
+        let diff = IpcMessage::Diff {
+            title: "src/lib.rs".to_string(),
+            diff: "@@ -1 +1 @@\n-a\n+b\n".to_string(),
+        };
+        assert_eq!(sender.broadcast(diff).unwrap(), 2);
+
+        for receiver in [&mut first, &mut second] {
+            let incoming = next_message(receiver, &mut sender).unwrap();
+            assert!(matches!(incoming.message, IpcMessage::Diff { .. }));
+        }
+        let peers = sender.peers().unwrap();
+        assert!(peers.iter().any(|p| p.title.as_deref() == Some("api")));
+    }
+
+    #[test]
+    fn test_messages_are_queued_until_peer_restarts() {
+        let dir = TempDir::new().unwrap();
+        let config = IpcConfig::in_dir(dir.path());
+        let mut left = WindowEndpoint::start("left", &config).unwrap();
+        let mut right = WindowEndpoint::start("right", &config).unwrap();
+
+        left.send("right", clipboard("left", "one")).unwrap();
+        assert!(next_message(&mut right, &mut left).is_some());
+        drop(right);
+
+        assert_eq!(
+            left.send("right", clipboard("left", "two")).unwrap(),
+            Delivery::Queued
+        );
+        assert_eq!(
+            left.send("right", clipboard("left", "three")).unwrap(),
+            Delivery::Queued
+        );
+        assert_eq!(left.queued(), 2);
+
+        let mut right = WindowEndpoint::start("right", &config).unwrap();
+        let mut received = Vec::new();
+        for _ in 0..2 {
+            let incoming = next_message(&mut right, &mut left).unwrap();
+            if let IpcMessage::Clipboard { entry } = incoming.message {
+                received.push(entry.content);
+            }
+        }
+        assert_eq!(received, ["two", "three"]);
+        assert_eq!(left.queued(), 0);
+    }
+
+    #[test]
+    fn test_unknown_window_and_duplicate_id_are_errors() {
+        let dir = TempDir::new().unwrap();
+        let config = IpcConfig::in_dir(dir.path());
+        let mut window = WindowEndpoint::start("main", &config).unwrap();
+
+        assert!(matches!(
+            window.send("nowhere", clipboard("main", "x")),
+            Err(IpcError::UnknownWindow(_))
+        ));
+        assert!(matches!(
+            WindowEndpoint::start("main", &config),
+            Err(IpcError::WindowIdInUse(_))
+        ));
+        assert!(matches!(
+            WindowEndpoint::start("../main", &config),
+            Err(IpcError::InvalidWindowId(_))
+        ));
+    }
+
+    #[test]
+    fn test_incompatible_peer_is_rejected() {
+        let dir = TempDir::new().unwrap();
+        let window = WindowEndpoint::start("main", &IpcConfig::in_dir(dir.path())).unwrap();
+
+        let mut stream = UnixStream::connect(dir.path().join("main.sock")).unwrap();
+        stream.set_read_timeout(Some(WAIT)).unwrap();
+        let hello = Frame::Hello {
+            window_id: "future".to_string(),
+            min_version: PROTOCOL_VERSION + 1,
+            max_version: PROTOCOL_VERSION + 1,
+        };
+        write_frame(&mut stream, &hello).unwrap();
+        let line = FrameReader::default()
+            .next_line(&mut stream)
+            .unwrap()
+            .unwrap();
+
+        match serde_json::from_slice(&line).unwrap() {
+            Frame::Reject { reason } => assert!(reason.contains("not supported")),
+            other => panic!("expected reject, got {:?}", other),
+        }
+        drop(window);
+    }
+}
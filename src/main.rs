@@ -4,6 +4,11 @@ use anyhow::Result;
 use clap::{Parser, Subcommand};
 use std::path::PathBuf;
 use std::time::Duration;
+use toad::ai::evaluation::storage::StorageManager;
+use toad::ai::evaluation::{
+    EvaluationReport, EvaluationResults, ExperimentManager, HistoryFilter, Metric, ReportFormat,
+    RunJournal, RunKind, SamplingConfig,
+};
 use toad::ai::{ComparisonResult, EvaluationHarness};
 use toad::config::{FeatureFlags, ToadConfig};
 use toad::core::{App, Tui};
@@ -46,10 +51,36 @@ enum Commands {
         output: PathBuf,
 
         /// Use multi-benchmark orchestrator with specified benchmarks (comma-separated)
-        /// Example: --benchmarks swebench-verified,livecodebench
+        /// Example: --benchmarks swebench-verified,livecodebench,humaneval,mbpp
         /// When specified, uses new orchestrator v2 path instead of legacy evaluation
         #[arg(long, value_name = "BENCHMARKS")]
         benchmarks: Option<String>,
+
+        /// Run a repo-local task suite (TOML) through the orchestrator
+        /// Combined with --benchmarks when both are given
+        #[arg(long, value_name = "PATH")]
+        suite: Option<PathBuf>,
+
+        /// Maximum tasks to run at once per benchmark (orchestrator v2 only)
+        #[arg(long, default_value = "1")]
+        max_concurrent_tasks: usize,
+
+        /// Attempts per task; above 1 reports pass@k with confidence intervals
+        #[arg(long, default_value = "1")]
+        samples: usize,
+
+        /// Sampling temperature for every attempt
+        #[arg(long)]
+        temperature: Option<f32>,
+
+        /// Seed of the first attempt; attempt i uses seed + i (GitHub and
+        /// Ollama models; defaults to 0 with --samples above 1)
+        #[arg(long)]
+        seed: Option<u64>,
+
+        /// Continue an interrupted run, skipping the tasks it finished
+        #[arg(long, value_name = "RUN_ID")]
+        resume: Option<String>,
     },
 
     /// Compare two configurations (A/B test)
@@ -77,6 +108,52 @@ enum Commands {
         /// Output directory for results
         #[arg(short, long, default_value = "./results")]
         output: PathBuf,
+
+        /// Attempts per task; above 1 reports pass@k with confidence intervals
+        #[arg(long, default_value = "1")]
+        samples: usize,
+
+        /// Sampling temperature for every attempt
+        #[arg(long)]
+        temperature: Option<f32>,
+
+        /// Seed of the first attempt; attempt i uses seed + i (GitHub and
+        /// Ollama models; defaults to 0 with --samples above 1)
+        #[arg(long)]
+        seed: Option<u64>,
+
+        /// Continue an interrupted run, skipping the tasks it finished
+        #[arg(long, value_name = "RUN_ID")]
+        resume: Option<String>,
+    },
+
+    /// Render result files into an HTML or Markdown report
+    Report {
+        /// Result files: evaluation runs, per-configuration results or comparisons
+        #[arg(required = true)]
+        inputs: Vec<PathBuf>,
+
+        /// Output file (default: ./results/report.html, or .md for markdown)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+
+        /// Report format: html or markdown (default: from the output extension)
+        #[arg(long)]
+        format: Option<String>,
+
+        /// Directory of per-task trajectory files to link to
+        #[arg(long, value_name = "DIR")]
+        trajectories: Option<PathBuf>,
+
+        /// Report title
+        #[arg(long, default_value = "TOAD Evaluation Report")]
+        title: String,
+    },
+
+    /// Track metrics across recorded runs and check them for regressions
+    Experiments {
+        #[command(subcommand)]
+        command: ExperimentsCommand,
     },
 
     /// Show feature flags for a configuration
@@ -101,6 +178,41 @@ enum Commands {
     Tui,
 }
 
+#[derive(Subcommand)]
+enum ExperimentsCommand {
+    /// Show a metric over the most recent runs
+    Trend {
+        /// Metric: accuracy, cost, latency or tokens
+        #[arg(short, long, default_value = "accuracy")]
+        metric: String,
+
+        /// Only runs of this dataset or benchmark
+        #[arg(short, long)]
+        dataset: Option<String>,
+
+        /// Only runs of this configuration (name or hash prefix)
+        #[arg(short, long)]
+        config: Option<String>,
+
+        /// Number of runs to show
+        #[arg(short = 'n', long, default_value = "20")]
+        limit: usize,
+    },
+
+    /// Test recent runs against the pinned baselines (exits 1 on regression)
+    Regressions {
+        /// Only check this dataset or benchmark
+        #[arg(short, long)]
+        dataset: Option<String>,
+    },
+
+    /// Pin a recorded run as the baseline of its dataset
+    Pin {
+        /// History ID of the run (see `toad experiments trend`)
+        run_id: String,
+    },
+}
+
 #[tokio::main]
 async fn main() -> Result<()> {
     // Load .env file if it exists
@@ -129,8 +241,23 @@ async fn main() -> Result<()> {
             milestone,
             output,
             benchmarks,
+            suite,
+            max_concurrent_tasks,
+            samples,
+            temperature,
+            seed,
+            resume,
         }) => {
-            run_eval(dataset, swebench, count, milestone, output, benchmarks).await?;
+            let options = RunOptions {
+                sampling: sampling_config(samples, temperature, seed),
+                resume,
+                max_concurrent_tasks,
+            };
+            let benchmarks = with_suite(benchmarks, suite);
+            run_eval(
+                dataset, swebench, count, milestone, output, benchmarks, options,
+            )
+            .await?;
         }
 
         Some(Commands::Compare {
@@ -140,8 +267,31 @@ async fn main() -> Result<()> {
             baseline,
             test,
             output,
+            samples,
+            temperature,
+            seed,
+            resume,
+        }) => {
+            let options = RunOptions {
+                sampling: sampling_config(samples, temperature, seed),
+                resume,
+                max_concurrent_tasks: 1,
+            };
+            run_compare(dataset, swebench, count, baseline, test, output, options).await?;
+        }
+
+        Some(Commands::Report {
+            inputs,
+            output,
+            format,
+            trajectories,
+            title,
         }) => {
-            run_compare(dataset, swebench, count, baseline, test, output).await?;
+            run_report(inputs, output, format, trajectories, title)?;
+        }
+
+        Some(Commands::Experiments { command }) => {
+            run_experiments(command)?;
         }
 
         Some(Commands::ShowConfig { milestone }) => {
@@ -161,6 +311,30 @@ async fn main() -> Result<()> {
     Ok(())
 }
 
+/// Add a `--suite` file to the `--benchmarks` list
+fn with_suite(benchmarks: Option<String>, suite: Option<PathBuf>) -> Option<String> {
+    let Some(suite) = suite else {
+        return benchmarks;
+    };
+    let suite = format!("suite:{}", suite.display());
+    Some(match benchmarks {
+        Some(list) if !list.trim().is_empty() => format!("{},{}", list, suite),
+        _ => suite,
+    })
+}
+
+/// How a run executes, from the flags shared by `eval` and `compare`
+struct RunOptions {
+    /// Attempts per task (pass@k mode when above 1)
+    sampling: SamplingConfig,
+
+    /// ID of an interrupted run to continue
+    resume: Option<String>,
+
+    /// Upper bound on tasks running at once per benchmark (orchestrator v2 only)
+    max_concurrent_tasks: usize,
+}
+
 async fn run_eval(
     dataset_path: Option<PathBuf>,
     swebench_variant: Option<String>,
@@ -168,17 +342,22 @@ async fn run_eval(
     milestone: Option<u8>,
     output: PathBuf,
     benchmarks: Option<String>,
+    options: RunOptions,
 ) -> Result<()> {
     // Route to v2 (orchestrator) if --benchmarks flag is specified
     if let Some(benchmark_str) = benchmarks {
         info!("Using orchestrator v2 with benchmarks: {}", benchmark_str);
-        return run_evaluation_v2(benchmark_str, count, milestone, output).await;
+        return run_evaluation_v2(benchmark_str, count, milestone, output, options).await;
     }
+    let RunOptions {
+        sampling, resume, ..
+    } = options;
 
     // Legacy v1 path (backward compatible)
     info!("Running evaluation (legacy v1 path)...");
 
     // Load tasks with validation
+    let dataset = dataset_name(dataset_path.as_deref(), swebench_variant.as_deref());
     let tasks = load_tasks_with_validation(dataset_path, swebench_variant, count).await?;
 
     info!("Loaded {} tasks (requested: {})", tasks.len(), count);
@@ -201,13 +380,26 @@ async fn run_eval(
 
     info!("Feature flags: {}", config.features.description());
 
-    // Run evaluation
-    let harness = EvaluationHarness::new(tasks, output.clone());
+    // Run evaluation, journaling each result so an interrupted run can resume
+    let harness = EvaluationHarness::new(tasks, output.clone()).with_sampling(sampling);
+    let storage = StorageManager::new(&output);
+    let journal = open_journal(
+        &storage,
+        RunKind::Eval,
+        resume,
+        harness.run_snapshot(&[&config])?,
+        Some(harness.planned_results()),
+    )?;
+    let run_id = journal.run_id().to_string();
+    let harness = harness.with_journal(journal);
     let results = harness.evaluate(&config).await?;
 
     // Print and save results
     results.print_summary();
     harness.save_results(&results)?;
+    drop(harness);
+    storage.finish_journal(&run_id)?;
+    record_history(&dataset, &config, &results);
 
     info!("Results saved to: {:?}", output);
 
@@ -226,18 +418,26 @@ async fn run_eval(
 /// * `count` - Number of tasks per benchmark
 /// * `milestone` - Optional milestone configuration
 /// * `output` - Output directory for results
+/// * `options` - Sampling, resume and task concurrency settings
 async fn run_evaluation_v2(
     benchmark_str: String,
     count: usize,
     milestone: Option<u8>,
     output: PathBuf,
+    options: RunOptions,
 ) -> Result<()> {
     use toad::benchmarks::{Orchestrator, OrchestratorConfig, ExecutionContext};
-    use toad::ai::evaluation::storage::StorageManager;
+    use toad::benchmarks::scheduler::SchedulerConfig;
     use tokio_util::sync::CancellationToken;
+    use std::sync::Arc;
     use std::time::Duration;
 
     info!("Running evaluation with orchestrator v2");
+    let RunOptions {
+        sampling,
+        resume,
+        max_concurrent_tasks,
+    } = options;
 
     // Parse benchmarks from comma-separated string
     let benchmarks: Vec<String> = benchmark_str
@@ -270,6 +470,7 @@ async fn run_evaluation_v2(
         max_steps: 25,
         system_config: serde_json::to_value(&toad_config)?,
         sandbox_config: None,
+        sample: None,
     };
 
     // Create orchestrator config
@@ -278,13 +479,25 @@ async fn run_evaluation_v2(
         task_limit: Some(count),
         max_concurrent_benchmarks: 2,
         execution_context,
+        sampling: sampling.clone(),
+        scheduler: SchedulerConfig::default().with_max_tasks(max_concurrent_tasks),
     };
 
     // Create cancellation token (future: wire to Ctrl+C handler)
     let cancel_token = CancellationToken::new();
 
+    // Journal finished tasks so an interrupted run can resume
+    let storage = StorageManager::new(&output);
+    let journal = Arc::new(open_journal(
+        &storage,
+        RunKind::Benchmarks,
+        resume,
+        orchestrator_config.resume_snapshot()?,
+        None,
+    )?);
+
     // Create orchestrator
-    let orchestrator = Orchestrator::new(orchestrator_config, cancel_token);
+    let orchestrator = Orchestrator::new(orchestrator_config, cancel_token).with_journal(journal);
 
     // Run evaluation
     info!("Starting evaluation...");
@@ -301,9 +514,12 @@ async fn run_evaluation_v2(
                 ProgressEvent::BenchmarkStarted { benchmark_name, total_tasks } => {
                     info!("🚀 Starting {}: {} tasks", benchmark_name, total_tasks);
                 }
-                ProgressEvent::TaskCompleted { task_id, solved, duration_ms, cost_usd, .. } => {
+                ProgressEvent::TaskStarted { task_id, in_flight, .. } => {
+                    tracing::debug!("  ▶ {} ({} running)", task_id, in_flight);
+                }
+                ProgressEvent::TaskCompleted { task_id, solved, duration_ms, cost_usd, in_flight, .. } => {
                     let status = if solved { "✅" } else { "❌" };
-                    info!("  {} {} ({}ms, ${:.4})", status, task_id, duration_ms, cost_usd);
+                    info!("  {} {} ({}ms, ${:.4}, {} running)", status, task_id, duration_ms, cost_usd, in_flight);
                 }
                 ProgressEvent::BenchmarkCompleted { benchmark_name, tasks_solved, total_tasks, duration_ms, cost_usd } => {
                     info!("✨ Completed {}: {}/{} solved ({}ms, ${:.2})",
@@ -329,10 +545,31 @@ async fn run_evaluation_v2(
     info!("Accuracy: {:.2}%", evaluation_run.aggregate_metrics.mean_accuracy * 100.0);
     info!("Median latency: {:.0}ms", evaluation_run.aggregate_metrics.median_latency_ms);
     info!("Total cost: ${:.2}", evaluation_run.aggregate_metrics.total_cost_usd);
+    if sampling.is_sampling() {
+        for result in &evaluation_run.benchmark_results {
+            for k in [1, sampling.samples] {
+                if let Some(p) = result.pass_at_k(k) {
+                    info!(
+                        "{} pass@{}: {:.2}% [{:.2}%, {:.2}%]",
+                        result.benchmark_metadata.name,
+                        k,
+                        p.value * 100.0,
+                        p.ci_lower * 100.0,
+                        p.ci_upper * 100.0
+                    );
+                }
+            }
+        }
+    }
 
     // Save results
-    let storage = StorageManager::new(&output);
     storage.save_evaluation_run(&evaluation_run).await?;
+    storage.finish_journal(&evaluation_run.run_id)?;
+    let config_name = format!("{} features", toad_config.features.enabled_count());
+    for result in &evaluation_run.benchmark_results {
+        let results = EvaluationResults::from_results(config_name.clone(), result.task_results.clone());
+        record_history(&result.benchmark_metadata.name, &toad_config, &results);
+    }
 
     info!("Results saved to: {:?}", output);
 
@@ -346,10 +583,15 @@ async fn run_compare(
     baseline_ms: u8,
     test_ms: u8,
     output: PathBuf,
+    options: RunOptions,
 ) -> Result<()> {
     info!("Running A/B comparison...");
+    let RunOptions {
+        sampling, resume, ..
+    } = options;
 
     // Load tasks with validation
+    let dataset = dataset_name(dataset_path.as_deref(), swebench_variant.as_deref());
     let tasks = load_tasks_with_validation(dataset_path, swebench_variant, count).await?;
 
     info!("Loaded {} tasks (requested: {})", tasks.len(), count);
@@ -376,14 +618,27 @@ async fn run_compare(
         config_b.features.description()
     );
 
-    // Run comparison
-    let harness = EvaluationHarness::new(tasks, output.clone());
+    // Run comparison, journaling each result so an interrupted run can resume
+    let harness = EvaluationHarness::new(tasks, output.clone()).with_sampling(sampling.clone());
+    let storage = StorageManager::new(&output);
+    let journal = open_journal(
+        &storage,
+        RunKind::Compare,
+        resume,
+        harness.run_snapshot(&[&config_a, &config_b])?,
+        Some(2 * harness.planned_results()),
+    )?;
+    let run_id = journal.run_id().to_string();
+    let harness = harness.with_journal(journal);
 
     info!("Running baseline (M{})...", baseline_ms);
     let (results_a, results_b) = harness.compare(&config_a, &config_b).await?;
 
     // Analyze comparison
-    let comparison = ComparisonResult::compare(&results_a, &results_b);
+    let mut comparison = ComparisonResult::compare(&results_a, &results_b);
+    if sampling.is_sampling() {
+        comparison = comparison.with_pass_at_k(&results_a, &results_b, &[1, sampling.samples]);
+    }
 
     // Print results
     results_a.print_summary();
@@ -393,11 +648,181 @@ async fn run_compare(
     // Save results
     harness.save_results(&results_a)?;
     harness.save_results(&results_b)?;
+    harness.save_comparison(&comparison)?;
+    drop(harness);
+    storage.finish_journal(&run_id)?;
+    record_history(&dataset, &config_a, &results_a);
+    record_history(&dataset, &config_b, &results_b);
 
     info!("Results saved to: {:?}", output);
     Ok(())
 }
 
+fn run_report(
+    inputs: Vec<PathBuf>,
+    output: Option<PathBuf>,
+    format: Option<String>,
+    trajectories: Option<PathBuf>,
+    title: String,
+) -> Result<()> {
+    let format = match (&format, &output) {
+        (Some(name), _) => ReportFormat::parse(name)?,
+        (None, Some(path)) => ReportFormat::from_path(path),
+        (None, None) => ReportFormat::Html,
+    };
+    let output = output.unwrap_or_else(|| {
+        PathBuf::from("./results").join(format!("report.{}", format.extension()))
+    });
+
+    let mut report = EvaluationReport::new(title);
+    if let Some(dir) = trajectories {
+        report = report.with_trajectories(dir);
+    }
+    for input in &inputs {
+        report.add_file(input)?;
+    }
+    report.write(&output, format)?;
+
+    println!("Report written to {}", output.display());
+    Ok(())
+}
+
+fn run_experiments(command: ExperimentsCommand) -> Result<()> {
+    let manager = ExperimentManager::default();
+
+    match command {
+        ExperimentsCommand::Trend {
+            metric,
+            dataset,
+            config,
+            limit,
+        } => {
+            let metric = Metric::parse(&metric)?;
+            let filter = HistoryFilter { dataset, config };
+            let points = manager.trend(metric, &filter, limit)?;
+            if points.is_empty() {
+                println!("No recorded runs");
+                return Ok(());
+            }
+
+            println!("{} over the last {} runs:", metric.name(), points.len());
+            for point in &points {
+                println!(
+                    "  {}  {:>10}  {}  {}  {}",
+                    point.run_id,
+                    metric.format(point.value),
+                    point.recorded_at.format("%Y-%m-%d %H:%M"),
+                    point.toad_commit.as_deref().unwrap_or("-"),
+                    point.config_name
+                );
+            }
+        }
+
+        ExperimentsCommand::Regressions { dataset } => {
+            let checks = manager.regressions(dataset.as_deref())?;
+            if checks.is_empty() {
+                println!("Nothing to check: pin a baseline with `toad experiments pin <RUN_ID>`");
+                return Ok(());
+            }
+
+            let mut regressed = false;
+            for check in &checks {
+                let status = if check.is_regression() { "REGRESSED" } else { "ok" };
+                println!(
+                    "{} {} ({}) vs baseline {}: {}",
+                    check.candidate.dataset,
+                    check.candidate.run_id,
+                    check.candidate.config_name,
+                    check.baseline.run_id,
+                    status
+                );
+                for regression in &check.regressions {
+                    println!(
+                        "  {}: {} -> {} (p={:.4})",
+                        regression.metric.name(),
+                        regression.metric.format(regression.baseline),
+                        regression.metric.format(regression.candidate),
+                        regression.p_value
+                    );
+                }
+                regressed |= check.is_regression();
+            }
+            if regressed {
+                std::process::exit(1);
+            }
+        }
+
+        ExperimentsCommand::Pin { run_id } => {
+            let record = manager.pin_baseline(&run_id)?;
+            println!("Pinned {} as the baseline of {}", record.run_id, record.dataset);
+        }
+    }
+
+    Ok(())
+}
+
+/// Dataset name of a `--dataset`/`--swebench` choice in the experiment history
+fn dataset_name(dataset_path: Option<&std::path::Path>, swebench_variant: Option<&str>) -> String {
+    match (swebench_variant, dataset_path) {
+        (Some(variant), _) => format!("swebench-{}", variant.to_lowercase()),
+        (None, Some(path)) => path.display().to_string(),
+        (None, None) => "synthetic".to_string(),
+    }
+}
+
+/// Add a finished run to the experiment history
+///
+/// Failures are logged; they don't fail the evaluation.
+fn record_history(dataset: &str, config: &ToadConfig, results: &EvaluationResults) {
+    let recorded = serde_json::to_value(config)
+        .map_err(anyhow::Error::from)
+        .and_then(|config| ExperimentManager::default().record_run(dataset, &config, results));
+    match recorded {
+        Ok(record) => info!("Recorded run {} in the experiment history", record.run_id),
+        Err(e) => tracing::warn!("Failed to record run in experiment history: {}", e),
+    }
+}
+
+/// Sampling settings from the `--samples`, `--temperature` and `--seed` flags
+fn sampling_config(samples: usize, temperature: Option<f32>, seed: Option<u64>) -> SamplingConfig {
+    let mut sampling = SamplingConfig::new(samples);
+    if let Some(t) = temperature {
+        sampling = sampling.with_temperature(t);
+    }
+    if let Some(seed) = seed {
+        sampling = sampling.with_seed(seed);
+    }
+    sampling
+}
+
+/// Start a journal for a new run, or reopen the one of `resume`
+fn open_journal(
+    storage: &StorageManager,
+    kind: RunKind,
+    resume: Option<String>,
+    snapshot: serde_json::Value,
+    planned_results: Option<usize>,
+) -> Result<RunJournal> {
+    let journal = match resume {
+        Some(run_id) => {
+            let journal = storage.resume_journal(&run_id, &snapshot)?;
+            info!(
+                "Resuming run {} ({} results already recorded)",
+                run_id,
+                journal.restored_count()
+            );
+            journal
+        }
+        None => storage.start_journal(kind, snapshot, planned_results)?,
+    };
+    info!(
+        "Run ID: {} (if interrupted, continue with --resume {})",
+        journal.run_id(),
+        journal.run_id()
+    );
+    Ok(journal)
+}
+
 /// Load tasks with validation and smart dataset handling
 async fn load_tasks_with_validation(
     dataset_path: Option<PathBuf>,
@@ -486,6 +911,9 @@ async fn run_tui() -> Result<()> {
     let mut tui = Tui::new().map_err(|e| anyhow::anyhow!("{}", e))?;
     let mut app = App::new();
 
+    // Let other TOAD windows reach this one
+    app.start_window_ipc(&toad::infrastructure::window_ipc::IpcConfig::for_current_user());
+
     // Create event channel for async operations
     let (event_tx, mut event_rx) = tokio::sync::mpsc::unbounded_channel();
     app.set_event_tx(event_tx);
@@ -494,6 +922,10 @@ async fn run_tui() -> Result<()> {
 
     // Main event loop
     while !app.should_quit() {
+        // Capture the mouse only on screens that handle it
+        tui.set_mouse_capture(app.wants_mouse_capture())
+            .map_err(|e| anyhow::anyhow!("{}", e))?;
+
         // View: Render the current state
         tui.draw(|frame| {
             toad::core::ui::render(&mut app, frame);
@@ -530,6 +962,14 @@ async fn run_tui() -> Result<()> {
         info!("Session state saved successfully");
     }
 
+    // Flush pending board changes
+    if let Err(e) = app.save_board() {
+        tracing::warn!("Failed to save board database: {}", e);
+    }
+
+    // Leave the discovery file before the terminal is restored
+    app.stop_window_ipc();
+
     Ok(())
 }
 
@@ -0,0 +1,767 @@
+//! Calendar screen - Cards plotted by due date
+//!
+//! Shows the cards of the app's [`BoardDatabase`] on a month grid, a week of
+//! day columns, or a single day's agenda. Moving the cursor selects a day;
+//! the selected card on that day can be rescheduled a day at a time.
+//!
+//! # Architecture
+//!
+//! Like the other board views, the screen only holds view state (cursor,
+//! display mode and the live filter the app lends it) and takes the board
+//! and today's date in every call. Its settings round-trip through
+//! [`ViewSettings::Calendar`] so the app can keep them in its view manager.
+//!
+//! # Examples
+//!
+//! ```
+//! use chrono::NaiveDate;
+//! use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+//! use toad::infrastructure::board_store::BoardDatabase;
+//! use toad::infrastructure::CalendarMode;
+//! use toad::ui::screens::calendar::CalendarScreen;
+//!
+//! let today = NaiveDate::from_ymd_opt(2025, 3, 10).unwrap();
+//! let mut db = BoardDatabase::default();
+//! let mut screen = CalendarScreen::new();
+//! screen.handle_key(KeyEvent::new(KeyCode::Char('l'), KeyModifiers::NONE), &mut db, today);
+//! assert_eq!(screen.cursor(today), NaiveDate::from_ymd_opt(2025, 3, 11).unwrap());
+//! screen.handle_key(KeyEvent::new(KeyCode::Char('m'), KeyModifiers::NONE), &mut db, today);
+//! assert_eq!(screen.mode(), CalendarMode::Week);
+//! ```
+
+use crate::infrastructure::board_store::BoardDatabase;
+use crate::infrastructure::card_schedule::RESCHEDULE_ACTIVITY;
+use crate::infrastructure::filtering_search::LiveFilter;
+use crate::infrastructure::multiple_views::{CalendarMode, ViewSettings};
+use crate::infrastructure::rich_task_cards::RichTaskCard;
+use crate::ui::screens::kanban::{priority_color, priority_label};
+use chrono::{Datelike, Duration, Months, NaiveDate, Utc, Weekday};
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+};
+
+/// Result of handling an input event, for the app to act on
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CalendarOutcome {
+    /// Nothing for the app to do
+    None,
+    /// A card's due date moved and the board should be saved
+    Rescheduled(String),
+    /// Informational message
+    Message(String),
+    /// An operation failed
+    Error(String),
+    /// Leave the calendar screen
+    Close,
+}
+
+/// Calendar screen
+#[derive(Debug)]
+pub struct CalendarScreen {
+    /// Selected day (today until the cursor first moves)
+    cursor: Option<NaiveDate>,
+    /// Selected card among the selected day's cards
+    card: usize,
+    /// Month, week or day layout
+    mode: CalendarMode,
+    /// Show Saturday and Sunday
+    show_weekends: bool,
+    /// Allow moving due dates
+    allow_reschedule: bool,
+    /// Typing a filter query
+    filtering: bool,
+    /// Live filter applied to cards
+    filter: LiveFilter,
+}
+
+impl Default for CalendarScreen {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CalendarScreen {
+    /// Create a month calendar on today
+    pub fn new() -> Self {
+        Self {
+            cursor: None,
+            card: 0,
+            mode: CalendarMode::Month,
+            show_weekends: true,
+            allow_reschedule: true,
+            filtering: false,
+            filter: LiveFilter::new(),
+        }
+    }
+
+    /// Create a screen from saved calendar view settings
+    pub fn from_settings(settings: &ViewSettings) -> Self {
+        let mut screen = Self::new();
+        if let ViewSettings::Calendar {
+            mode,
+            show_weekends,
+            allow_reschedule,
+        } = settings
+        {
+            screen.mode = *mode;
+            screen.show_weekends = *show_weekends;
+            screen.allow_reschedule = *allow_reschedule;
+        }
+        screen
+    }
+
+    /// Current view settings
+    pub fn settings(&self) -> ViewSettings {
+        ViewSettings::Calendar {
+            mode: self.mode,
+            show_weekends: self.show_weekends,
+            allow_reschedule: self.allow_reschedule,
+        }
+    }
+
+    /// Card filter
+    pub fn filter_mut(&mut self) -> &mut LiveFilter {
+        &mut self.filter
+    }
+
+    /// Current display mode
+    pub fn mode(&self) -> CalendarMode {
+        self.mode
+    }
+
+    /// Selected day
+    pub fn cursor(&self, today: NaiveDate) -> NaiveDate {
+        self.cursor.unwrap_or(today)
+    }
+
+    /// ID of the selected card
+    pub fn selected_card_id(&self, db: &BoardDatabase, today: NaiveDate) -> Option<String> {
+        self.cards_on(db, self.cursor(today))
+            .get(self.card)
+            .map(|card| card.id.clone())
+    }
+
+    /// Cards due on a day that pass the filter, by due time then priority
+    fn cards_on<'a>(&self, db: &'a BoardDatabase, date: NaiveDate) -> Vec<&'a RichTaskCard> {
+        let mut cards: Vec<&RichTaskCard> = db
+            .cards
+            .cards()
+            .into_iter()
+            .filter(|card| card.due_date.is_some_and(|due| due.date_naive() == date))
+            .filter(|card| self.filter.matches(card))
+            .collect();
+        cards.sort_by_key(|card| (card.due_date, card.priority.to_value(), card.id.clone()));
+        cards
+    }
+
+    /// Cards without a due date that pass the filter
+    fn unscheduled(&self, db: &BoardDatabase) -> usize {
+        db.cards
+            .cards()
+            .into_iter()
+            .filter(|card| card.due_date.is_none() && self.filter.matches(card))
+            .count()
+    }
+
+    /// Days shown as columns
+    fn weekdays(&self) -> Vec<Weekday> {
+        let days = [
+            Weekday::Mon,
+            Weekday::Tue,
+            Weekday::Wed,
+            Weekday::Thu,
+            Weekday::Fri,
+            Weekday::Sat,
+            Weekday::Sun,
+        ];
+        let count = if self.show_weekends { 7 } else { 5 };
+        days[..count].to_vec()
+    }
+
+    /// Move the cursor by `days`, skipping hidden weekends
+    fn move_cursor(&mut self, today: NaiveDate, days: i64) {
+        let mut date = self.cursor(today) + Duration::days(days);
+        while !self.show_weekends && is_weekend(date) {
+            date += Duration::days(days.signum().max(1) * if days < 0 { -1 } else { 1 });
+        }
+        self.cursor = Some(date);
+        self.card = 0;
+    }
+
+    // ---- input ------------------------------------------------------------
+
+    /// Handle a key press
+    pub fn handle_key(
+        &mut self,
+        key: KeyEvent,
+        db: &mut BoardDatabase,
+        today: NaiveDate,
+    ) -> CalendarOutcome {
+        if self.filtering {
+            match key.code {
+                KeyCode::Enter => {
+                    self.filter.commit();
+                    self.filtering = false;
+                }
+                KeyCode::Esc => {
+                    self.filter.clear();
+                    self.filtering = false;
+                }
+                KeyCode::Backspace => self.filter.pop(),
+                KeyCode::Char(c) => self.filter.push(c),
+                _ => {}
+            }
+            self.card = 0;
+            return CalendarOutcome::None;
+        }
+
+        match key.code {
+            KeyCode::Char('h') | KeyCode::Left => self.move_cursor(today, -1),
+            KeyCode::Char('l') | KeyCode::Right => self.move_cursor(today, 1),
+            KeyCode::Char('j') | KeyCode::Down => self.move_cursor(today, 7),
+            KeyCode::Char('k') | KeyCode::Up => self.move_cursor(today, -7),
+            KeyCode::Char(']') => self.shift_period(today, 1),
+            KeyCode::Char('[') => self.shift_period(today, -1),
+            KeyCode::Char('t') => {
+                self.cursor = Some(today);
+                self.card = 0;
+            }
+            KeyCode::Tab => {
+                let count = self.cards_on(db, self.cursor(today)).len();
+                if count > 0 {
+                    self.card = (self.card + 1) % count;
+                }
+            }
+            KeyCode::BackTab => {
+                let count = self.cards_on(db, self.cursor(today)).len();
+                if count > 0 {
+                    self.card = (self.card + count - 1) % count;
+                }
+            }
+            KeyCode::Char('H') => return self.reschedule_selected(db, today, -1),
+            KeyCode::Char('L') => return self.reschedule_selected(db, today, 1),
+            KeyCode::Char('m') => {
+                self.mode = match self.mode {
+                    CalendarMode::Month => CalendarMode::Week,
+                    CalendarMode::Week => CalendarMode::Day,
+                    CalendarMode::Day => CalendarMode::Month,
+                };
+            }
+            KeyCode::Char('w') => {
+                self.show_weekends = !self.show_weekends;
+                if !self.show_weekends && is_weekend(self.cursor(today)) {
+                    self.move_cursor(today, 1);
+                }
+            }
+            KeyCode::Char('/') => self.filtering = true,
+            KeyCode::Esc if self.filter.is_active() => {
+                self.filter.clear();
+                return CalendarOutcome::Message("Filter cleared".to_string());
+            }
+            KeyCode::Esc | KeyCode::Char('q') => return CalendarOutcome::Close,
+            _ => {}
+        }
+        CalendarOutcome::None
+    }
+
+    /// Move the cursor to the previous or next month, week or day
+    fn shift_period(&mut self, today: NaiveDate, delta: i32) {
+        let cursor = self.cursor(today);
+        match self.mode {
+            CalendarMode::Month => {
+                let months = Months::new(delta.unsigned_abs());
+                let date = if delta < 0 {
+                    cursor.checked_sub_months(months)
+                } else {
+                    cursor.checked_add_months(months)
+                };
+                self.cursor = date.or(Some(cursor));
+                self.card = 0;
+            }
+            CalendarMode::Week => self.move_cursor(today, 7 * delta as i64),
+            CalendarMode::Day => self.move_cursor(today, delta as i64),
+        }
+    }
+
+    /// Move the selected card's due date by `days`, keeping its time of day
+    fn reschedule_selected(
+        &mut self,
+        db: &mut BoardDatabase,
+        today: NaiveDate,
+        days: i64,
+    ) -> CalendarOutcome {
+        if !self.allow_reschedule {
+            return CalendarOutcome::Message("Rescheduling is off for this view".to_string());
+        }
+        let Some(card_id) = self.selected_card_id(db, today) else {
+            return CalendarOutcome::None;
+        };
+        let Some(card) = db.cards.get_card_mut(&card_id) else {
+            return CalendarOutcome::Error(format!("Card {} not found", card_id));
+        };
+        let Some(due) = card.due_date.map(|due| due + Duration::days(days)) else {
+            return CalendarOutcome::None;
+        };
+        card.due_date = Some(due);
+        card.updated_at = Utc::now();
+        let title = card.title.clone();
+        db.comments.log_activity(
+            card_id.clone(),
+            RESCHEDULE_ACTIVITY.to_string(),
+            format!("Due {}", due.format("%Y-%m-%d %H:%M")),
+            None,
+            None,
+        );
+
+        self.cursor = Some(due.date_naive());
+        self.card = self
+            .cards_on(db, due.date_naive())
+            .iter()
+            .position(|card| card.id == card_id)
+            .unwrap_or(0);
+        CalendarOutcome::Rescheduled(format!("{} due {}", title, due.format("%a %Y-%m-%d")))
+    }
+
+    // ---- rendering --------------------------------------------------------
+
+    /// Render the calendar
+    pub fn render(&mut self, frame: &mut Frame, area: Rect, db: &BoardDatabase, today: NaiveDate) {
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(1), // Period and filter
+                Constraint::Min(0),    // Days
+                Constraint::Length(1), // Key hints
+            ])
+            .split(area);
+
+        let count = self.cards_on(db, self.cursor(today)).len();
+        self.card = self.card.min(count.saturating_sub(1));
+
+        frame.render_widget(Paragraph::new(self.header_line(db, today)), rows[0]);
+        match self.mode {
+            CalendarMode::Month => self.render_month(frame, rows[1], db, today),
+            CalendarMode::Week => self.render_week(frame, rows[1], db, today),
+            CalendarMode::Day => self.render_day(frame, rows[1], db, today),
+        }
+        frame.render_widget(Paragraph::new(self.footer_line()), rows[2]);
+    }
+
+    fn header_line(&self, db: &BoardDatabase, today: NaiveDate) -> Line<'static> {
+        let cursor = self.cursor(today);
+        let title = match self.mode {
+            CalendarMode::Month => cursor.format("%B %Y").to_string(),
+            CalendarMode::Week => format!("Week of {}", week_start(cursor).format("%Y-%m-%d")),
+            CalendarMode::Day => cursor.format("%A %Y-%m-%d").to_string(),
+        };
+        let mut spans = vec![
+            Span::styled(
+                format!(" ▦ {} ", title),
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                format!("{} unscheduled", self.unscheduled(db)),
+                Style::default().fg(Color::Gray),
+            ),
+        ];
+        if self.filtering {
+            spans.push(Span::styled(
+                format!("  / {}█", self.filter.query()),
+                Style::default().fg(Color::Yellow),
+            ));
+        } else if self.filter.is_active() {
+            spans.push(Span::styled(
+                format!("  filter: {}", self.filter.query()),
+                Style::default().fg(Color::Yellow),
+            ));
+        }
+        Line::from(spans)
+    }
+
+    fn footer_line(&self) -> Line<'static> {
+        let hints = if self.filtering {
+            "Type to filter  Enter keep  Esc clear"
+        } else {
+            "h/l day  j/k week  [/] period  Tab card  H/L move due date  m mode  w weekends  t today  / filter  Esc back"
+        };
+        Line::from(Span::styled(hints, Style::default().fg(Color::DarkGray)))
+    }
+
+    fn render_month(&self, frame: &mut Frame, area: Rect, db: &BoardDatabase, today: NaiveDate) {
+        let cursor = self.cursor(today);
+        let first = cursor.with_day(1).unwrap_or(cursor);
+        let start = week_start(first);
+        let last = first
+            .checked_add_months(Months::new(1))
+            .map_or(first, |next| next - Duration::days(1));
+        let weeks = ((last - start).num_days() / 7 + 1) as usize;
+
+        let parts = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(1), Constraint::Min(0)])
+            .split(area);
+        let weekdays = self.weekdays();
+        let names = split_columns(parts[0], weekdays.len());
+        for (weekday, name_area) in weekdays.iter().zip(names.iter()) {
+            frame.render_widget(
+                Paragraph::new(Span::styled(
+                    format!(" {}", weekday),
+                    Style::default().fg(Color::Gray),
+                )),
+                *name_area,
+            );
+        }
+
+        let week_rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(vec![Constraint::Ratio(1, weeks as u32); weeks])
+            .split(parts[1]);
+        for (week, row) in week_rows.iter().enumerate() {
+            let monday = start + Duration::days(7 * week as i64);
+            for (weekday, cell) in weekdays.iter().zip(split_columns(*row, weekdays.len())) {
+                let date = monday + Duration::days(weekday.num_days_from_monday() as i64);
+                let title = Span::styled(
+                    format!(" {} ", date.day()),
+                    if date.month() == cursor.month() {
+                        Style::default()
+                    } else {
+                        Style::default().fg(Color::DarkGray)
+                    },
+                );
+                self.render_day_cell(frame, cell, db, date, today, title, false);
+            }
+        }
+    }
+
+    fn render_week(&self, frame: &mut Frame, area: Rect, db: &BoardDatabase, today: NaiveDate) {
+        let monday = week_start(self.cursor(today));
+        let weekdays = self.weekdays();
+        for (weekday, cell) in weekdays.iter().zip(split_columns(area, weekdays.len())) {
+            let date = monday + Duration::days(weekday.num_days_from_monday() as i64);
+            let title = Span::raw(format!(" {} ", date.format("%a %d")));
+            self.render_day_cell(frame, cell, db, date, today, title, true);
+        }
+    }
+
+    fn render_day(&self, frame: &mut Frame, area: Rect, db: &BoardDatabase, today: NaiveDate) {
+        let date = self.cursor(today);
+        let title = Span::raw(format!(" {} ", date.format("%A %d %B")));
+        let block = day_block(title, true, date == today);
+        let cards = self.cards_on(db, date);
+        let lines: Vec<Line> = if cards.is_empty() {
+            vec![Line::from(Span::styled(
+                "Nothing due",
+                Style::default().fg(Color::DarkGray),
+            ))]
+        } else {
+            cards
+                .iter()
+                .enumerate()
+                .map(|(index, card)| {
+                    let assignees: Vec<&str> = card
+                        .assignees
+                        .iter()
+                        .map(|a| a.display_name.as_str())
+                        .collect();
+                    let mut spans = vec![
+                        Span::styled(
+                            card.due_date
+                                .map(|due| due.format("%H:%M ").to_string())
+                                .unwrap_or_default(),
+                            Style::default().fg(Color::Gray),
+                        ),
+                        Span::styled(
+                            format!("{} ", priority_label(card.priority)),
+                            Style::default().fg(priority_color(card.priority)),
+                        ),
+                        Span::styled(card.title.clone(), self.card_style(index)),
+                        Span::styled(
+                            format!("  [{}]", card.status),
+                            Style::default().fg(Color::DarkGray),
+                        ),
+                    ];
+                    if !assignees.is_empty() {
+                        spans.push(Span::styled(
+                            format!(" @{}", assignees.join(", @")),
+                            Style::default().fg(Color::Magenta),
+                        ));
+                    }
+                    Line::from(spans)
+                })
+                .collect()
+        };
+        frame.render_widget(Paragraph::new(lines).block(block), area);
+    }
+
+    /// One day of the month or week layout
+    #[allow(clippy::too_many_arguments)]
+    fn render_day_cell(
+        &self,
+        frame: &mut Frame,
+        area: Rect,
+        db: &BoardDatabase,
+        date: NaiveDate,
+        today: NaiveDate,
+        title: Span<'static>,
+        show_time: bool,
+    ) {
+        let selected = date == self.cursor(today);
+        let block = day_block(title, selected, date == today);
+        let capacity = block.inner(area).height as usize;
+        let cards = self.cards_on(db, date);
+        let shown = if cards.len() > capacity {
+            capacity.saturating_sub(1)
+        } else {
+            cards.len()
+        };
+
+        let mut lines: Vec<Line> = cards
+            .iter()
+            .take(shown)
+            .enumerate()
+            .map(|(index, card)| {
+                let time = if show_time {
+                    card.due_date
+                        .map(|due| due.format("%H:%M ").to_string())
+                        .unwrap_or_default()
+                } else {
+                    String::new()
+                };
+                let style = if selected {
+                    self.card_style(index)
+                } else {
+                    Style::default()
+                };
+                Line::from(vec![
+                    Span::styled("•", Style::default().fg(priority_color(card.priority))),
+                    Span::styled(format!("{}{}", time, card.title), style),
+                ])
+            })
+            .collect();
+        if cards.len() > shown {
+            lines.push(Line::from(Span::styled(
+                format!("+{} more", cards.len() - shown),
+                Style::default().fg(Color::DarkGray),
+            )));
+        }
+        frame.render_widget(Paragraph::new(lines).block(block), area);
+    }
+
+    fn card_style(&self, index: usize) -> Style {
+        if index == self.card {
+            Style::default().add_modifier(Modifier::REVERSED)
+        } else {
+            Style::default()
+        }
+    }
+}
+
+/// Bordered block for a day, highlighting the cursor and today
+fn day_block(title: Span<'static>, selected: bool, today: bool) -> Block<'static> {
+    let color = if selected {
+        Color::Yellow
+    } else if today {
+        Color::Cyan
+    } else {
+        Color::DarkGray
+    };
+    Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(color))
+        .title(title)
+}
+
+/// Split an area into equal columns
+fn split_columns(area: Rect, count: usize) -> Vec<Rect> {
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(vec![Constraint::Ratio(1, count as u32); count])
+        .split(area)
+        .to_vec()
+}
+
+/// Monday of the week containing `date`
+fn week_start(date: NaiveDate) -> NaiveDate {
+    date - Duration::days(date.weekday().num_days_from_monday() as i64)
+}
+
+fn is_weekend(date: NaiveDate) -> bool {
+    matches!(date.weekday(), Weekday::Sat | Weekday::Sun)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::rich_task_cards::Priority;
+    use chrono::{DateTime, TimeZone};
+    use crossterm::event::KeyModifiers;
+    use ratatui::{Terminal, backend::TestBackend};
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn today() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, 3, 10).unwrap()
+    }
+
+    fn at(day: u32, hour: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 3, day, hour, 0, 0).unwrap()
+    }
+
+    fn add_card(db: &mut BoardDatabase, title: &str, due: Option<DateTime<Utc>>) -> String {
+        let id = db.cards.create_card(
+            title.to_string(),
+            String::new(),
+            "To Do".to_string(),
+            Priority::Medium,
+            "me".to_string(),
+        );
+        db.cards.get_card_mut(&id).unwrap().due_date = due;
+        id
+    }
+
+    fn render(screen: &mut CalendarScreen, db: &BoardDatabase) -> String {
+        let mut terminal = Terminal::new(TestBackend::new(120, 40)).unwrap();
+        terminal
+            .draw(|f| screen.render(f, f.area(), db, today()))
+            .unwrap();
+        terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|c| c.symbol())
+            .collect()
+    }
+
+    #[test]
+    fn test_month_shows_cards_on_due_days() {
+        let mut db = BoardDatabase::default();
+        add_card(&mut db, "Release notes", Some(at(12, 9)));
+        add_card(&mut db, "Retro", Some(at(28, 15)));
+        add_card(&mut db, "Someday", None);
+        let mut screen = CalendarScreen::new();
+
+        let output = render(&mut screen, &db);
+        assert!(output.contains("March 2025"));
+        assert!(output.contains("1 unscheduled"));
+        assert!(output.contains("•Release notes"));
+        assert!(output.contains("•Retro"));
+    }
+
+    #[test]
+    fn test_week_and_day_modes() {
+        let mut db = BoardDatabase::default();
+        add_card(&mut db, "Standup", Some(at(10, 9)));
+        add_card(&mut db, "Next week", Some(at(17, 9)));
+        let mut screen = CalendarScreen::new();
+
+        screen.handle_key(key(KeyCode::Char('m')), &mut db, today());
+        let output = render(&mut screen, &db);
+        assert!(output.contains("Week of 2025-03-10"));
+        assert!(output.contains("09:00 Standup"));
+        assert!(!output.contains("Next week"));
+
+        screen.handle_key(key(KeyCode::Char('m')), &mut db, today());
+        assert_eq!(screen.mode(), CalendarMode::Day);
+        let output = render(&mut screen, &db);
+        assert!(output.contains("Monday 2025-03-10"));
+        assert!(output.contains("09:00 P2 Standup  [To Do]"));
+
+        screen.handle_key(key(KeyCode::Char(']')), &mut db, today());
+        assert_eq!(
+            screen.cursor(today()),
+            NaiveDate::from_ymd_opt(2025, 3, 11).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_reschedule_selected_card() {
+        let mut db = BoardDatabase::default();
+        add_card(&mut db, "Early", Some(at(10, 9)));
+        let late = add_card(&mut db, "Late", Some(at(10, 16)));
+        let mut screen = CalendarScreen::new();
+
+        screen.handle_key(key(KeyCode::Tab), &mut db, today());
+        assert_eq!(screen.selected_card_id(&db, today()), Some(late.clone()));
+
+        let outcome = screen.handle_key(key(KeyCode::Char('L')), &mut db, today());
+        assert_eq!(
+            outcome,
+            CalendarOutcome::Rescheduled("Late due Tue 2025-03-11".to_string())
+        );
+        assert_eq!(db.cards.get_card(&late).unwrap().due_date, Some(at(11, 16)));
+        assert_eq!(screen.selected_card_id(&db, today()), Some(late.clone()));
+        assert_eq!(db.comments.activities_for_card(&late).len(), 1);
+
+        let mut locked = CalendarScreen::from_settings(&ViewSettings::Calendar {
+            mode: CalendarMode::Month,
+            show_weekends: true,
+            allow_reschedule: false,
+        });
+        locked.cursor = Some(NaiveDate::from_ymd_opt(2025, 3, 11).unwrap());
+        assert!(matches!(
+            locked.handle_key(key(KeyCode::Char('H')), &mut db, today()),
+            CalendarOutcome::Message(_)
+        ));
+    }
+
+    #[test]
+    fn test_hidden_weekends_are_skipped() {
+        let mut db = BoardDatabase::default();
+        let mut screen = CalendarScreen::new();
+        screen.handle_key(key(KeyCode::Char('w')), &mut db, today());
+        for _ in 0..4 {
+            screen.handle_key(key(KeyCode::Char('l')), &mut db, today());
+        }
+        assert_eq!(
+            screen.cursor(today()),
+            NaiveDate::from_ymd_opt(2025, 3, 14).unwrap()
+        );
+        screen.handle_key(key(KeyCode::Char('l')), &mut db, today());
+        assert_eq!(
+            screen.cursor(today()),
+            NaiveDate::from_ymd_opt(2025, 3, 17).unwrap()
+        );
+        screen.handle_key(key(KeyCode::Char('h')), &mut db, today());
+        assert_eq!(
+            screen.cursor(today()),
+            NaiveDate::from_ymd_opt(2025, 3, 14).unwrap()
+        );
+        assert!(!render(&mut screen, &db).contains(" Sat"));
+    }
+
+    #[test]
+    fn test_filter_hides_cards() {
+        let mut db = BoardDatabase::default();
+        add_card(&mut db, "Release notes", Some(at(12, 9)));
+        add_card(&mut db, "Retro", Some(at(12, 15)));
+        let mut screen = CalendarScreen::new();
+
+        screen.handle_key(key(KeyCode::Char('/')), &mut db, today());
+        for c in "retro".chars() {
+            screen.handle_key(key(KeyCode::Char(c)), &mut db, today());
+        }
+        screen.handle_key(key(KeyCode::Enter), &mut db, today());
+        let output = render(&mut screen, &db);
+        assert!(output.contains("filter: retro"));
+        assert!(!output.contains("Release notes"));
+
+        assert!(matches!(
+            screen.handle_key(key(KeyCode::Esc), &mut db, today()),
+            CalendarOutcome::Message(_)
+        ));
+        assert_eq!(
+            screen.handle_key(key(KeyCode::Esc), &mut db, today()),
+            CalendarOutcome::Close
+        );
+    }
+}
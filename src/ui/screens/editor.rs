@@ -0,0 +1,1353 @@
+//! Editor screen - Embedded modal file editor
+//!
+//! Shows open [`Buffer`]s next to a [`FileTree`] of the workspace. Editing is
+//! modal in the vim style: Normal mode for motions and commands, Insert mode
+//! for typing, Visual mode for selections and a `:` command line for saving,
+//! reloading and closing.
+//!
+//! # Architecture
+//!
+//! The screen owns the buffers and the editor state shared between them
+//! (marks, macro registers, the visual selection). Key handling returns an
+//! [`EditorOutcome`] so the app can show toasts, copy to the clipboard or leave
+//! the screen without the screen knowing about the app.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use toad::ui::screens::editor::{EditorMode, EditorScreen};
+//!
+//! let mut screen = EditorScreen::new(".");
+//! screen.open("src/main.rs").unwrap();
+//! assert_eq!(screen.mode(), EditorMode::Normal);
+//! ```
+
+use crate::editor::{
+    Buffer, BufferError, ExternalChange, MacroAction, MacroManager, MarksManager, Motion, Position,
+    SelectionMode, VisualSelection,
+};
+use crate::ui::syntax::{HighlightedSpan, SyntaxHighlighter};
+use crate::ui::widgets::files::tree::FileTree;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+/// Width of the file tree pane
+const TREE_WIDTH: u16 = 30;
+
+/// Editing mode
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EditorMode {
+    /// Motions and commands
+    Normal,
+    /// Typing inserts text
+    Insert,
+    /// Extending a selection
+    Visual,
+    /// Typing a `:` command
+    Command,
+}
+
+impl EditorMode {
+    /// Label shown in the status line
+    pub fn label(&self) -> &'static str {
+        match self {
+            EditorMode::Normal => "NORMAL",
+            EditorMode::Insert => "INSERT",
+            EditorMode::Visual => "VISUAL",
+            EditorMode::Command => "COMMAND",
+        }
+    }
+}
+
+/// Which pane receives keys
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EditorFocus {
+    /// The file tree
+    Tree,
+    /// The active buffer
+    Buffer,
+}
+
+/// Result of handling a key, for the app to act on
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EditorOutcome {
+    /// Nothing for the app to do
+    None,
+    /// Informational message
+    Message(String),
+    /// A buffer was written to disk
+    Saved(PathBuf),
+    /// An operation failed
+    Error(String),
+    /// Text to copy to the clipboard
+    Copy(String),
+    /// Leave the editor screen
+    Close,
+}
+
+/// Highlighted lines cached for the current viewport
+struct HighlightCache {
+    path: Option<PathBuf>,
+    revision: u64,
+    start: usize,
+    spans: Vec<Vec<HighlightedSpan>>,
+}
+
+/// Embedded file editor screen
+pub struct EditorScreen {
+    /// Workspace root, used to resolve relative paths
+    root: PathBuf,
+    /// Workspace file tree (None when the root cannot be read)
+    tree: Option<FileTree>,
+    /// Whether the tree pane is visible
+    show_tree: bool,
+    /// Focused pane
+    focus: EditorFocus,
+    /// Open buffers
+    buffers: Vec<Buffer>,
+    /// Index of the active buffer
+    active: usize,
+    /// Editing mode
+    mode: EditorMode,
+    /// Visual mode selection
+    selection: VisualSelection,
+    /// Marks shared by all buffers
+    marks: MarksManager,
+    /// Macro registers
+    macros: MacroManager,
+    /// Syntax highlighter
+    highlighter: SyntaxHighlighter,
+    /// First visible line of the active buffer
+    scroll: usize,
+    /// First visible column of the active buffer
+    hscroll: usize,
+    /// Key waiting for its argument (`m`, `'`, `q`, `@`, `d`, `g`, `f`, `t`)
+    pending: Option<char>,
+    /// Count typed before a command
+    count: Option<usize>,
+    /// Command line contents
+    command: String,
+    /// Whether a macro is being replayed (replayed keys are not recorded)
+    replaying: bool,
+    /// Paths already reported as changed on disk
+    stale: HashSet<PathBuf>,
+    /// Highlight cache for the viewport
+    cache: Option<HighlightCache>,
+}
+
+impl EditorScreen {
+    /// Create the screen for a workspace root
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        let root = root.into();
+        let tree = FileTree::new(root.clone())
+            .ok()
+            .map(|tree| tree.with_title("Files"));
+        Self {
+            root,
+            tree,
+            show_tree: true,
+            focus: EditorFocus::Tree,
+            buffers: Vec::new(),
+            active: 0,
+            mode: EditorMode::Normal,
+            selection: VisualSelection::new(),
+            marks: MarksManager::new(),
+            macros: MacroManager::new(),
+            highlighter: SyntaxHighlighter::new(),
+            scroll: 0,
+            hscroll: 0,
+            pending: None,
+            count: None,
+            command: String::new(),
+            replaying: false,
+            stale: HashSet::new(),
+            cache: None,
+        }
+    }
+
+    /// Open a file (relative paths are resolved against the root)
+    ///
+    /// Switches to the buffer if the file is already open.
+    ///
+    /// # Errors
+    ///
+    /// Fails when the file cannot be read or is not text.
+    pub fn open(&mut self, path: impl AsRef<Path>) -> Result<(), BufferError> {
+        let path = self.resolve(path.as_ref());
+        if let Some(index) = self.buffers.iter().position(|b| b.path() == Some(&path)) {
+            self.switch_to(index);
+        } else {
+            self.buffers.push(Buffer::open(&path)?);
+            self.switch_to(self.buffers.len() - 1);
+        }
+        self.focus = EditorFocus::Buffer;
+        Ok(())
+    }
+
+    fn resolve(&self, path: &Path) -> PathBuf {
+        if path.is_absolute() {
+            path.to_path_buf()
+        } else {
+            self.root.join(path)
+        }
+    }
+
+    /// Open buffers
+    pub fn buffers(&self) -> &[Buffer] {
+        &self.buffers
+    }
+
+    /// The active buffer
+    pub fn active_buffer(&self) -> Option<&Buffer> {
+        self.buffers.get(self.active)
+    }
+
+    /// The active buffer, mutably
+    pub fn active_buffer_mut(&mut self) -> Option<&mut Buffer> {
+        self.buffers.get_mut(self.active)
+    }
+
+    /// Current mode
+    pub fn mode(&self) -> EditorMode {
+        self.mode
+    }
+
+    /// Focused pane
+    pub fn focus(&self) -> EditorFocus {
+        self.focus
+    }
+
+    /// Command line contents
+    pub fn command_line(&self) -> &str {
+        &self.command
+    }
+
+    /// Whether any buffer has unsaved changes
+    pub fn has_unsaved_changes(&self) -> bool {
+        self.buffers.iter().any(Buffer::is_dirty)
+    }
+
+    fn switch_to(&mut self, index: usize) {
+        if let Some(buffer) = self.active_buffer_mut() {
+            buffer.end_undo_group();
+        }
+        self.active = index;
+        self.mode = EditorMode::Normal;
+        self.selection.cancel();
+        self.scroll = 0;
+        self.hscroll = 0;
+        if let Some(path) = self.buffers[index].path() {
+            self.marks
+                .set_current_file(path.to_string_lossy().to_string());
+        }
+    }
+
+    /// Check every buffer for changes made on disk
+    ///
+    /// Clean buffers are reloaded silently except for a message; buffers with
+    /// unsaved edits are left alone and reported once per change.
+    pub fn check_external_changes(&mut self) -> Vec<EditorOutcome> {
+        let mut outcomes = Vec::new();
+        for buffer in &mut self.buffers {
+            let Some(path) = buffer.path().map(Path::to_path_buf) else {
+                continue;
+            };
+            let name = buffer.display_name();
+            match buffer.check_external_change() {
+                None => {
+                    self.stale.remove(&path);
+                }
+                Some(ExternalChange::Modified) if !buffer.is_dirty() => match buffer.reload() {
+                    Ok(()) => outcomes.push(EditorOutcome::Message(format!(
+                        "Reloaded {} (changed on disk)",
+                        name
+                    ))),
+                    Err(e) => outcomes.push(EditorOutcome::Error(e.to_string())),
+                },
+                Some(change) => {
+                    if self.stale.insert(path) {
+                        let message = match change {
+                            ExternalChange::Modified => {
+                                format!("{} changed on disk: :e! to reload, :w! to overwrite", name)
+                            }
+                            ExternalChange::Deleted => {
+                                format!("{} was deleted on disk: :w! to recreate", name)
+                            }
+                        };
+                        outcomes.push(EditorOutcome::Error(message));
+                    }
+                }
+            }
+        }
+        outcomes
+    }
+
+    // ---- keys -------------------------------------------------------------
+
+    /// Handle a key
+    pub fn handle_key(&mut self, key: KeyEvent) -> EditorOutcome {
+        if self.focus == EditorFocus::Tree || self.buffers.is_empty() {
+            return self.handle_tree_key(key);
+        }
+
+        self.record_key(key);
+        match self.mode {
+            EditorMode::Normal => self.handle_normal_key(key),
+            EditorMode::Insert => self.handle_insert_key(key),
+            EditorMode::Visual => self.handle_visual_key(key),
+            EditorMode::Command => self.handle_command_key(key),
+        }
+    }
+
+    fn handle_tree_key(&mut self, key: KeyEvent) -> EditorOutcome {
+        match (key.code, key.modifiers) {
+            (KeyCode::Esc, _) | (KeyCode::Char('q'), KeyModifiers::NONE) => {
+                if self.buffers.is_empty() {
+                    return EditorOutcome::Close;
+                }
+                self.focus = EditorFocus::Buffer;
+            }
+            (KeyCode::Tab, _) | (KeyCode::Char('b'), KeyModifiers::CONTROL)
+                if !self.buffers.is_empty() =>
+            {
+                self.focus = EditorFocus::Buffer;
+            }
+            (KeyCode::Char('j'), _) | (KeyCode::Down, _) => {
+                if let Some(tree) = self.tree.as_mut() {
+                    tree.select_next();
+                }
+            }
+            (KeyCode::Char('k'), _) | (KeyCode::Up, _) => {
+                if let Some(tree) = self.tree.as_mut() {
+                    tree.select_previous();
+                }
+            }
+            (KeyCode::Enter, _) | (KeyCode::Char('l'), _) => {
+                let Some(tree) = self.tree.as_mut() else {
+                    return EditorOutcome::None;
+                };
+                let Some(path) = tree.selected_path().map(Path::to_path_buf) else {
+                    return EditorOutcome::None;
+                };
+                if path.is_dir() {
+                    tree.toggle_selected();
+                } else if let Err(e) = self.open(&path) {
+                    return EditorOutcome::Error(e.to_string());
+                }
+            }
+            _ => {}
+        }
+        EditorOutcome::None
+    }
+
+    fn take_count(&mut self) -> usize {
+        self.count.take().unwrap_or(1)
+    }
+
+    fn handle_normal_key(&mut self, key: KeyEvent) -> EditorOutcome {
+        if let Some(pending) = self.pending.take() {
+            return self.handle_pending(pending, key);
+        }
+
+        if let (KeyCode::Char(c @ '0'..='9'), KeyModifiers::NONE) = (key.code, key.modifiers)
+            && (c != '0' || self.count.is_some())
+        {
+            let digit = c.to_digit(10).unwrap_or(0) as usize;
+            self.count = Some(self.count.unwrap_or(0) * 10 + digit);
+            return EditorOutcome::None;
+        }
+
+        let count = self.take_count();
+        let Some(buffer) = self.buffers.get_mut(self.active) else {
+            return EditorOutcome::None;
+        };
+
+        match (key.code, key.modifiers) {
+            (KeyCode::Esc, _) => {
+                if buffer.cursor_count() > 1 {
+                    buffer.clear_secondary_cursors();
+                } else {
+                    return EditorOutcome::Close;
+                }
+            }
+            (KeyCode::Char('s'), KeyModifiers::CONTROL) => return self.save(false),
+            (KeyCode::Char('r'), KeyModifiers::CONTROL) => {
+                for _ in 0..count {
+                    buffer.redo();
+                }
+            }
+            (KeyCode::Char('n'), KeyModifiers::CONTROL) => {
+                for _ in 0..count {
+                    buffer.add_cursor_below();
+                }
+            }
+            (KeyCode::Char('b'), KeyModifiers::CONTROL) => {
+                self.show_tree = true;
+                self.focus = EditorFocus::Tree;
+            }
+            (KeyCode::Char('v'), KeyModifiers::CONTROL) => self.start_visual(SelectionMode::Block),
+            (KeyCode::Tab, _) if self.show_tree => self.focus = EditorFocus::Tree,
+            (KeyCode::Char('h'), _) | (KeyCode::Left, _) => buffer.move_left(count),
+            (KeyCode::Char('l'), _) | (KeyCode::Right, _) => buffer.move_right(count),
+            (KeyCode::Char('j'), _) | (KeyCode::Down, _) => buffer.move_down(count),
+            (KeyCode::Char('k'), _) | (KeyCode::Up, _) => buffer.move_up(count),
+            (KeyCode::Char('0'), _) | (KeyCode::Home, _) => buffer.move_line_start(),
+            (KeyCode::Char('$'), _) | (KeyCode::End, _) => buffer.move_line_end(),
+            (KeyCode::Char('w'), _) => buffer.apply_motion(Motion::WordForward, count),
+            (KeyCode::Char('b'), _) => buffer.apply_motion(Motion::WordBackward, count),
+            (KeyCode::Char('e'), _) => buffer.apply_motion(Motion::EndOfWord, count),
+            (KeyCode::Char('W'), _) => buffer.apply_motion(Motion::WORDForward, count),
+            (KeyCode::Char('B'), _) => buffer.apply_motion(Motion::WORDBackward, count),
+            (KeyCode::Char('E'), _) => buffer.apply_motion(Motion::EndOfWORD, count),
+            (KeyCode::Char('G'), _) => {
+                let last = buffer.line_count() - 1;
+                buffer.goto_line(last);
+            }
+            (KeyCode::Char('x'), _) | (KeyCode::Delete, _) => {
+                buffer.begin_undo_group();
+                for _ in 0..count {
+                    buffer.delete_forward();
+                }
+                buffer.end_undo_group();
+            }
+            (KeyCode::Char('u'), _) => {
+                for _ in 0..count {
+                    buffer.undo();
+                }
+            }
+            (KeyCode::Char('i'), _) => self.enter_insert(),
+            (KeyCode::Char('a'), _) => {
+                buffer.move_right_in_line();
+                self.enter_insert();
+            }
+            (KeyCode::Char('A'), _) => {
+                buffer.move_line_end();
+                self.enter_insert();
+            }
+            (KeyCode::Char('I'), _) => {
+                buffer.move_line_start();
+                self.enter_insert();
+            }
+            (KeyCode::Char('o'), _) => {
+                buffer.move_line_end();
+                self.enter_insert();
+                if let Some(buffer) = self.active_buffer_mut() {
+                    buffer.insert_newline();
+                }
+            }
+            (KeyCode::Char('O'), _) => {
+                buffer.move_line_start();
+                self.enter_insert();
+                if let Some(buffer) = self.active_buffer_mut() {
+                    buffer.insert_newline();
+                    buffer.move_up(1);
+                }
+            }
+            (KeyCode::Char('v'), _) => self.start_visual(SelectionMode::Character),
+            (KeyCode::Char('V'), _) => self.start_visual(SelectionMode::Line),
+            (KeyCode::Char(':'), _) => {
+                self.command.clear();
+                self.mode = EditorMode::Command;
+            }
+            (KeyCode::Char(']'), _) => {
+                let next = (self.active + 1) % self.buffers.len();
+                self.switch_to(next);
+            }
+            (KeyCode::Char('['), _) => {
+                let previous = (self.active + self.buffers.len() - 1) % self.buffers.len();
+                self.switch_to(previous);
+            }
+            (KeyCode::Char('q'), _) if self.macros.is_recording() => {
+                self.macros.stop_recording();
+                return EditorOutcome::Message("Macro recorded".to_string());
+            }
+            (KeyCode::Char(c @ ('m' | '\'' | 'q' | '@' | 'd' | 'g' | 'f' | 't')), _) => {
+                self.pending = Some(c);
+                self.count = (count > 1).then_some(count);
+            }
+            _ => {}
+        }
+        EditorOutcome::None
+    }
+
+    /// Second key of a two-key normal mode command
+    fn handle_pending(&mut self, pending: char, key: KeyEvent) -> EditorOutcome {
+        let KeyCode::Char(arg) = key.code else {
+            self.count = None;
+            return EditorOutcome::None;
+        };
+        let count = self.take_count();
+        let Some(buffer) = self.buffers.get_mut(self.active) else {
+            return EditorOutcome::None;
+        };
+
+        match pending {
+            'm' => {
+                let set = buffer.set_mark(&mut self.marks, arg);
+                if set {
+                    return EditorOutcome::Message(format!("Mark '{}' set", arg));
+                }
+            }
+            '\'' => {
+                if buffer.jump_to_mark(&self.marks, arg) {
+                    return EditorOutcome::None;
+                }
+                // Global marks may point into another file
+                let Some(mark) = self.marks.get_mark(arg).cloned() else {
+                    return EditorOutcome::Error(format!("Mark '{}' not set", arg));
+                };
+                if let Err(e) = self.open(&mark.file) {
+                    return EditorOutcome::Error(e.to_string());
+                }
+                if let Some(buffer) = self.active_buffer_mut() {
+                    buffer.set_cursor(Position::new(mark.line, mark.col));
+                }
+            }
+            'q' => {
+                let started = self.macros.start_recording(arg);
+                if started {
+                    return EditorOutcome::Message(format!("Recording @{}", arg));
+                }
+            }
+            '@' => return self.replay_macro(arg, count),
+            'd' if arg == 'd' => {
+                buffer.begin_undo_group();
+                for _ in 0..count {
+                    buffer.delete_line();
+                }
+                buffer.end_undo_group();
+            }
+            'g' if arg == 'g' => buffer.goto_line(count.saturating_sub(1)),
+            'f' => buffer.apply_motion(Motion::FindChar(arg), count),
+            't' => buffer.apply_motion(Motion::TillChar(arg), count),
+            _ => {}
+        }
+        EditorOutcome::None
+    }
+
+    fn enter_insert(&mut self) {
+        self.mode = EditorMode::Insert;
+        if let Some(buffer) = self.active_buffer_mut() {
+            buffer.begin_undo_group();
+        }
+    }
+
+    fn handle_insert_key(&mut self, key: KeyEvent) -> EditorOutcome {
+        let Some(buffer) = self.buffers.get_mut(self.active) else {
+            return EditorOutcome::None;
+        };
+        match (key.code, key.modifiers) {
+            (KeyCode::Esc, _) => {
+                buffer.end_undo_group();
+                buffer.move_left_in_line();
+                self.mode = EditorMode::Normal;
+            }
+            (KeyCode::Char('s'), KeyModifiers::CONTROL) => return self.save(false),
+            (KeyCode::Enter, _) => {
+                buffer.insert_newline();
+            }
+            (KeyCode::Backspace, _) => {
+                buffer.delete_backward();
+            }
+            (KeyCode::Delete, _) => {
+                buffer.delete_forward();
+            }
+            (KeyCode::Tab, _) => {
+                buffer.insert_str("    ");
+            }
+            (KeyCode::Left, _) => buffer.move_left(1),
+            (KeyCode::Right, _) => buffer.move_right(1),
+            (KeyCode::Up, _) => buffer.move_up(1),
+            (KeyCode::Down, _) => buffer.move_down(1),
+            (KeyCode::Home, _) => buffer.move_line_start(),
+            (KeyCode::End, _) => buffer.move_line_end(),
+            (KeyCode::Char(c), KeyModifiers::NONE | KeyModifiers::SHIFT) => {
+                buffer.insert_char(c);
+            }
+            _ => {}
+        }
+        EditorOutcome::None
+    }
+
+    fn start_visual(&mut self, mode: SelectionMode) {
+        let Some(cursor) = self.active_buffer().map(Buffer::cursor) else {
+            return;
+        };
+        if let Some(buffer) = self.active_buffer_mut() {
+            buffer.clear_secondary_cursors();
+        }
+        self.selection.start(mode, cursor);
+        self.mode = EditorMode::Visual;
+    }
+
+    fn handle_visual_key(&mut self, key: KeyEvent) -> EditorOutcome {
+        let count = self.take_count();
+        let Some(buffer) = self.buffers.get_mut(self.active) else {
+            return EditorOutcome::None;
+        };
+        match key.code {
+            KeyCode::Esc => {
+                self.selection.cancel();
+                self.mode = EditorMode::Normal;
+                return EditorOutcome::None;
+            }
+            KeyCode::Char('d') | KeyCode::Char('x') | KeyCode::Delete => {
+                if let Some(range) = self.selection.end() {
+                    buffer.delete_selection(&range);
+                }
+                self.mode = EditorMode::Normal;
+                return EditorOutcome::None;
+            }
+            KeyCode::Char('y') => {
+                let mut text = String::new();
+                if let Some(range) = self.selection.end() {
+                    text = buffer.selection_text(&range);
+                    buffer.set_cursor(range.start);
+                }
+                self.mode = EditorMode::Normal;
+                return EditorOutcome::Copy(text);
+            }
+            KeyCode::Char('v') => self.selection.change_mode(SelectionMode::Character),
+            KeyCode::Char('V') => self.selection.change_mode(SelectionMode::Line),
+            KeyCode::Char('h') | KeyCode::Left => buffer.move_left(count),
+            KeyCode::Char('l') | KeyCode::Right => buffer.move_right(count),
+            KeyCode::Char('j') | KeyCode::Down => buffer.move_down(count),
+            KeyCode::Char('k') | KeyCode::Up => buffer.move_up(count),
+            KeyCode::Char('0') | KeyCode::Home => buffer.move_line_start(),
+            KeyCode::Char('$') | KeyCode::End => buffer.move_line_end(),
+            KeyCode::Char('w') => buffer.apply_motion(Motion::WordForward, count),
+            KeyCode::Char('b') => buffer.apply_motion(Motion::WordBackward, count),
+            KeyCode::Char('e') => buffer.apply_motion(Motion::EndOfWord, count),
+            KeyCode::Char('G') => {
+                let last = buffer.line_count() - 1;
+                buffer.goto_line(last);
+            }
+            _ => {}
+        }
+        self.selection.update_end(buffer.cursor());
+        EditorOutcome::None
+    }
+
+    fn handle_command_key(&mut self, key: KeyEvent) -> EditorOutcome {
+        match key.code {
+            KeyCode::Esc => {
+                self.command.clear();
+                self.mode = EditorMode::Normal;
+            }
+            KeyCode::Backspace => {
+                let popped = self.command.pop();
+                if popped.is_none() {
+                    self.mode = EditorMode::Normal;
+                }
+            }
+            KeyCode::Enter => {
+                let command = std::mem::take(&mut self.command);
+                self.mode = EditorMode::Normal;
+                return self.execute_command(command.trim());
+            }
+            KeyCode::Char(c) => self.command.push(c),
+            _ => {}
+        }
+        EditorOutcome::None
+    }
+
+    /// Run a `:` command
+    fn execute_command(&mut self, command: &str) -> EditorOutcome {
+        if let Ok(line) = command.parse::<usize>() {
+            if let Some(buffer) = self.active_buffer_mut() {
+                buffer.goto_line(line.saturating_sub(1));
+            }
+            return EditorOutcome::None;
+        }
+
+        match command {
+            "w" => self.save(false),
+            "w!" => self.save(true),
+            "wq" | "x" => match self.save(false) {
+                EditorOutcome::Saved(_) => EditorOutcome::Close,
+                other => other,
+            },
+            "q" => {
+                if self.has_unsaved_changes() {
+                    EditorOutcome::Error(
+                        "Unsaved changes: :w to save or :q! to discard".to_string(),
+                    )
+                } else {
+                    EditorOutcome::Close
+                }
+            }
+            "q!" => {
+                for buffer in &mut self.buffers {
+                    if buffer.path().is_some() && buffer.is_dirty() {
+                        let _ = buffer.reload();
+                    }
+                }
+                EditorOutcome::Close
+            }
+            "bd" => self.close_buffer(),
+            "e!" => match self.active_buffer_mut().map(Buffer::reload) {
+                Some(Ok(())) => {
+                    if let Some(path) = self.active_buffer().and_then(Buffer::path) {
+                        let path = path.to_path_buf();
+                        self.stale.remove(&path);
+                    }
+                    EditorOutcome::Message("Reloaded from disk".to_string())
+                }
+                Some(Err(e)) => EditorOutcome::Error(e.to_string()),
+                None => EditorOutcome::None,
+            },
+            _ => match command.strip_prefix("e ") {
+                Some(path) => match self.open(path.trim()) {
+                    Ok(()) => EditorOutcome::None,
+                    Err(e) => EditorOutcome::Error(e.to_string()),
+                },
+                None => EditorOutcome::Error(format!("Unknown command: :{}", command)),
+            },
+        }
+    }
+
+    /// Save the active buffer
+    fn save(&mut self, force: bool) -> EditorOutcome {
+        let Some(buffer) = self.buffers.get_mut(self.active) else {
+            return EditorOutcome::None;
+        };
+        let result = if force {
+            buffer.force_save()
+        } else {
+            buffer.save()
+        };
+        match (result, buffer.path()) {
+            (Ok(()), Some(path)) => {
+                let path = path.to_path_buf();
+                self.stale.remove(&path);
+                EditorOutcome::Saved(path)
+            }
+            (Ok(()), None) => EditorOutcome::None,
+            (Err(BufferError::ExternallyModified(path)), _) => EditorOutcome::Error(format!(
+                "{} changed on disk: :w! to overwrite, :e! to reload",
+                path.display()
+            )),
+            (Err(e), _) => EditorOutcome::Error(e.to_string()),
+        }
+    }
+
+    /// Close the active buffer unless it has unsaved changes
+    fn close_buffer(&mut self) -> EditorOutcome {
+        if self.active_buffer().is_some_and(Buffer::is_dirty) {
+            return EditorOutcome::Error("Unsaved changes: :w first".to_string());
+        }
+        if self.active < self.buffers.len() {
+            self.buffers.remove(self.active);
+        }
+        if self.buffers.is_empty() {
+            self.active = 0;
+            self.focus = EditorFocus::Tree;
+        } else {
+            self.switch_to(self.active.min(self.buffers.len() - 1));
+        }
+        EditorOutcome::None
+    }
+
+    // ---- macros -----------------------------------------------------------
+
+    /// Record a key into the macro being recorded
+    ///
+    /// Typed text and backspaces in Insert mode are stored as text actions,
+    /// every other key as a command naming the key.
+    fn record_key(&mut self, key: KeyEvent) {
+        if self.replaying || !self.macros.is_recording() {
+            return;
+        }
+        // The `q` that stops recording is not part of the macro
+        if self.mode == EditorMode::Normal
+            && self.pending.is_none()
+            && key.code == KeyCode::Char('q')
+        {
+            return;
+        }
+
+        let action = match (self.mode, key.code) {
+            (EditorMode::Insert, KeyCode::Char(c))
+                if matches!(key.modifiers, KeyModifiers::NONE | KeyModifiers::SHIFT) =>
+            {
+                MacroAction::InsertText(c.to_string())
+            }
+            (EditorMode::Insert, KeyCode::Backspace) => MacroAction::DeleteText(1),
+            _ => match key_name(key) {
+                Some(name) => MacroAction::Command(name),
+                None => return,
+            },
+        };
+        self.macros.record_action(action);
+    }
+
+    /// Replay a macro register (`@` repeats the last one) `count` times
+    fn replay_macro(&mut self, register: char, count: usize) -> EditorOutcome {
+        let actions = if register == '@' {
+            self.macros.repeat_last()
+        } else if self.macros.has_macro(register) {
+            Some(self.macros.execute(register))
+        } else {
+            None
+        };
+        let Some(actions) = actions else {
+            return EditorOutcome::Error(format!("Register @{} is empty", register));
+        };
+
+        self.replaying = true;
+        for _ in 0..count {
+            for action in &actions {
+                match action {
+                    MacroAction::Command(name) => {
+                        if let Some(key) = key_from_name(name) {
+                            self.handle_key(key);
+                        }
+                    }
+                    other => {
+                        if let Some(buffer) = self.active_buffer_mut() {
+                            buffer.apply_macro_action(other);
+                        }
+                    }
+                }
+            }
+        }
+        self.replaying = false;
+        EditorOutcome::None
+    }
+
+    // ---- rendering --------------------------------------------------------
+
+    /// Render the screen
+    pub fn render(&mut self, frame: &mut Frame, area: Rect) {
+        let columns = if self.show_tree && self.tree.is_some() {
+            Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([Constraint::Length(TREE_WIDTH), Constraint::Min(0)])
+                .split(area)
+        } else {
+            Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([Constraint::Length(0), Constraint::Min(0)])
+                .split(area)
+        };
+
+        if let Some(tree) = self.tree.as_mut()
+            && self.show_tree
+        {
+            tree.render(frame, columns[0]);
+        }
+
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(1), // Buffer tabs
+                Constraint::Min(0),    // Text
+                Constraint::Length(1), // Status line
+                Constraint::Length(1), // Command line / key hints
+            ])
+            .split(columns[1]);
+
+        frame.render_widget(Paragraph::new(self.tabs_line()), rows[0]);
+        self.render_text(frame, rows[1]);
+        frame.render_widget(Paragraph::new(self.status_line()), rows[2]);
+
+        let bottom = if self.mode == EditorMode::Command {
+            Line::from(format!(":{}", self.command))
+        } else {
+            Line::from(Span::styled(
+                self.key_hints(),
+                Style::default().fg(Color::DarkGray),
+            ))
+        };
+        frame.render_widget(Paragraph::new(bottom), rows[3]);
+    }
+
+    fn tabs_line(&self) -> Line<'static> {
+        let mut spans = vec![Span::raw(" ")];
+        for (i, buffer) in self.buffers.iter().enumerate() {
+            if i > 0 {
+                spans.push(Span::styled(" │ ", Style::default().fg(Color::DarkGray)));
+            }
+            let dirty = if buffer.is_dirty() { "*" } else { "" };
+            let style = if i == self.active {
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::Gray)
+            };
+            spans.push(Span::styled(
+                format!("{}{}", buffer.display_name(), dirty),
+                style,
+            ));
+        }
+        Line::from(spans)
+    }
+
+    fn status_line(&self) -> Line<'static> {
+        let mode_style = match self.mode {
+            EditorMode::Normal => Style::default().fg(Color::Black).bg(Color::Blue),
+            EditorMode::Insert => Style::default().fg(Color::Black).bg(Color::Green),
+            EditorMode::Visual => Style::default().fg(Color::Black).bg(Color::Magenta),
+            EditorMode::Command => Style::default().fg(Color::Black).bg(Color::Yellow),
+        };
+        let mut spans = vec![Span::styled(format!(" {} ", self.mode.label()), mode_style)];
+
+        if let Some(buffer) = self.active_buffer() {
+            let cursor = buffer.cursor();
+            let path = buffer
+                .path()
+                .map(|p| {
+                    p.strip_prefix(&self.root)
+                        .unwrap_or(p)
+                        .display()
+                        .to_string()
+                })
+                .unwrap_or_else(|| buffer.display_name());
+            spans.push(Span::raw(format!(" {}", path)));
+            if buffer.is_dirty() {
+                spans.push(Span::styled(" [+]", Style::default().fg(Color::Yellow)));
+            }
+            if let Some(path) = buffer.path()
+                && self.stale.contains(path)
+            {
+                spans.push(Span::styled(
+                    " [changed on disk]",
+                    Style::default().fg(Color::Red),
+                ));
+            }
+            spans.push(Span::styled(
+                format!(
+                    "  {}:{}  {:?}",
+                    cursor.line + 1,
+                    cursor.col + 1,
+                    buffer.language()
+                ),
+                Style::default().fg(Color::Gray),
+            ));
+            if buffer.cursor_count() > 1 {
+                spans.push(Span::styled(
+                    format!("  {} cursors", buffer.cursor_count()),
+                    Style::default().fg(Color::Cyan),
+                ));
+            }
+        }
+        if let Some(register) = self.macros.recording_register() {
+            spans.push(Span::styled(
+                format!("  recording @{}", register),
+                Style::default().fg(Color::Red),
+            ));
+        }
+        Line::from(spans)
+    }
+
+    fn key_hints(&self) -> &'static str {
+        match (self.focus, self.mode) {
+            (EditorFocus::Tree, _) => "j/k: Move  Enter: Open  Tab: Editor  Esc: Back",
+            (_, EditorMode::Insert) => "Esc: Normal  Ctrl+S: Save",
+            (_, EditorMode::Visual) => "d: Delete  y: Copy  Esc: Cancel",
+            _ => {
+                "i/a/o: Insert  v/V: Visual  u/Ctrl+R: Undo/Redo  Ctrl+N: Add cursor  \
+                 m/': Marks  q/@: Macros  ]/[: Buffers  :w :q :e!  Tab: Files"
+            }
+        }
+    }
+
+    /// Render the active buffer with line numbers, highlighting, cursors and
+    /// the visual selection
+    fn render_text(&mut self, frame: &mut Frame, area: Rect) {
+        let border_style = if self.focus == EditorFocus::Buffer {
+            Style::default().fg(Color::Green)
+        } else {
+            Style::default().fg(Color::DarkGray)
+        };
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(border_style);
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let Some(buffer) = self.buffers.get(self.active) else {
+            let message = Paragraph::new("Select a file in the tree and press Enter to open it")
+                .style(Style::default().fg(Color::DarkGray));
+            frame.render_widget(message, inner);
+            return;
+        };
+
+        let height = inner.height as usize;
+        let gutter = buffer.line_count().to_string().len() + 1;
+        let width = (inner.width as usize).saturating_sub(gutter + 1);
+        let cursor = buffer.cursor();
+
+        // Keep the primary cursor in view
+        if cursor.line < self.scroll {
+            self.scroll = cursor.line;
+        } else if height > 0 && cursor.line >= self.scroll + height {
+            self.scroll = cursor.line + 1 - height;
+        }
+        if cursor.col < self.hscroll {
+            self.hscroll = cursor.col;
+        } else if width > 0 && cursor.col >= self.hscroll + width {
+            self.hscroll = cursor.col + 1 - width;
+        }
+
+        let path = buffer.path().map(Path::to_path_buf);
+        let fresh = self.cache.as_ref().is_some_and(|c| {
+            c.path == path
+                && c.revision == buffer.revision()
+                && c.start == self.scroll
+                && c.spans.len() >= height.min(buffer.line_count() - self.scroll)
+        });
+        if !fresh {
+            self.cache = Some(HighlightCache {
+                path,
+                revision: buffer.revision(),
+                start: self.scroll,
+                spans: buffer.highlight_lines(&self.highlighter, self.scroll, height),
+            });
+        }
+        let spans = self.cache.as_ref().map(|c| &c.spans[..]).unwrap_or(&[]);
+
+        let cursors = buffer.cursors();
+        let selection = (self.mode == EditorMode::Visual)
+            .then(|| self.selection.get_range())
+            .flatten();
+
+        let lines: Vec<Line> = buffer
+            .lines_from(self.scroll)
+            .take(height)
+            .enumerate()
+            .map(|(row, text)| {
+                let line_index = self.scroll + row;
+                let mut styled: Vec<(char, Style)> = Vec::with_capacity(text.len() + 1);
+                let line_spans = spans.get(row).map(|s| &s[..]).unwrap_or(&[]);
+                for (offset, ch) in text.char_indices() {
+                    let style = line_spans
+                        .iter()
+                        .find(|s| s.start <= offset && offset < s.end)
+                        .map(|s| s.style)
+                        .unwrap_or_default();
+                    styled.push((ch, style));
+                }
+                // Room for a cursor past the end of the line
+                styled.push((' ', Style::default()));
+
+                for (col, (_, style)) in styled.iter_mut().enumerate() {
+                    let pos = Position::new(line_index, col);
+                    if selection.as_ref().is_some_and(|range| range.contains(pos)) {
+                        *style = style.bg(Color::DarkGray);
+                    }
+                    if cursors.contains(&pos) {
+                        *style = style.add_modifier(Modifier::REVERSED);
+                    }
+                }
+
+                let mut out = vec![Span::styled(
+                    format!("{:>width$} ", line_index + 1, width = gutter - 1),
+                    Style::default().fg(Color::DarkGray),
+                )];
+                out.extend(group_styled(&styled[self.hscroll.min(styled.len())..]));
+                Line::from(out)
+            })
+            .collect();
+
+        frame.render_widget(Paragraph::new(lines), inner);
+    }
+}
+
+/// Merge runs of equally styled characters into spans
+fn group_styled(chars: &[(char, Style)]) -> Vec<Span<'static>> {
+    let mut spans = Vec::new();
+    let mut current = String::new();
+    let mut current_style = None;
+    for (ch, style) in chars {
+        if current_style.is_some_and(|s| s != *style) {
+            spans.push(Span::styled(
+                std::mem::take(&mut current),
+                current_style.unwrap_or_default(),
+            ));
+        }
+        current_style = Some(*style);
+        current.push(*ch);
+    }
+    if !current.is_empty() {
+        spans.push(Span::styled(current, current_style.unwrap_or_default()));
+    }
+    spans
+}
+
+/// Name of a key as stored in macro registers
+fn key_name(key: KeyEvent) -> Option<String> {
+    let base = match key.code {
+        KeyCode::Char(c) => c.to_string(),
+        KeyCode::Esc => "Esc".to_string(),
+        KeyCode::Enter => "Enter".to_string(),
+        KeyCode::Backspace => "Backspace".to_string(),
+        KeyCode::Delete => "Delete".to_string(),
+        KeyCode::Tab => "Tab".to_string(),
+        KeyCode::Left => "Left".to_string(),
+        KeyCode::Right => "Right".to_string(),
+        KeyCode::Up => "Up".to_string(),
+        KeyCode::Down => "Down".to_string(),
+        KeyCode::Home => "Home".to_string(),
+        KeyCode::End => "End".to_string(),
+        _ => return None,
+    };
+    if key.modifiers.contains(KeyModifiers::CONTROL) {
+        Some(format!("Ctrl+{}", base))
+    } else {
+        Some(base)
+    }
+}
+
+/// Key for a name produced by [`key_name`]
+fn key_from_name(name: &str) -> Option<KeyEvent> {
+    let (name, modifiers) = match name.strip_prefix("Ctrl+") {
+        Some(rest) if !rest.is_empty() => (rest, KeyModifiers::CONTROL),
+        _ => (name, KeyModifiers::NONE),
+    };
+    let code = match name {
+        "Esc" => KeyCode::Esc,
+        "Enter" => KeyCode::Enter,
+        "Backspace" => KeyCode::Backspace,
+        "Delete" => KeyCode::Delete,
+        "Tab" => KeyCode::Tab,
+        "Left" => KeyCode::Left,
+        "Right" => KeyCode::Right,
+        "Up" => KeyCode::Up,
+        "Down" => KeyCode::Down,
+        "Home" => KeyCode::Home,
+        "End" => KeyCode::End,
+        _ => {
+            let mut chars = name.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => KeyCode::Char(c),
+                _ => return None,
+            }
+        }
+    };
+    Some(KeyEvent::new(code, modifiers))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::{Terminal, backend::TestBackend};
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn ctrl(c: char) -> KeyEvent {
+        KeyEvent::new(KeyCode::Char(c), KeyModifiers::CONTROL)
+    }
+
+    fn type_keys(screen: &mut EditorScreen, keys: &str) {
+        for c in keys.chars() {
+            screen.handle_key(key(KeyCode::Char(c)));
+        }
+    }
+
+    fn workspace(content: &str) -> (TempDir, EditorScreen) {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("main.rs"), content).unwrap();
+        let mut screen = EditorScreen::new(dir.path());
+        screen.open("main.rs").unwrap();
+        (dir, screen)
+    }
+
+    fn text(screen: &EditorScreen) -> String {
+        screen.active_buffer().unwrap().text()
+    }
+
+    #[test]
+    fn test_open_resolves_relative_and_dedupes() {
+        let (dir, mut screen) = workspace("fn main() {}\n");
+        assert_eq!(screen.focus(), EditorFocus::Buffer);
+        screen.open(dir.path().join("main.rs")).unwrap();
+        assert_eq!(screen.buffers().len(), 1);
+        assert!(screen.open("missing.rs").is_err());
+    }
+
+    #[test]
+    fn test_insert_mode_and_save() {
+        let (dir, mut screen) = workspace("b\n");
+        type_keys(&mut screen, "ia");
+        assert_eq!(screen.mode(), EditorMode::Insert);
+        screen.handle_key(key(KeyCode::Esc));
+        assert_eq!(screen.mode(), EditorMode::Normal);
+        assert!(screen.has_unsaved_changes());
+
+        type_keys(&mut screen, ":w");
+        let outcome = screen.handle_key(key(KeyCode::Enter));
+        assert_eq!(outcome, EditorOutcome::Saved(dir.path().join("main.rs")));
+        assert_eq!(
+            fs::read_to_string(dir.path().join("main.rs")).unwrap(),
+            "ab\n"
+        );
+        assert!(!screen.has_unsaved_changes());
+    }
+
+    #[test]
+    fn test_insert_session_is_one_undo_step() {
+        let (_dir, mut screen) = workspace("");
+        type_keys(&mut screen, "ihello");
+        screen.handle_key(key(KeyCode::Esc));
+        type_keys(&mut screen, "u");
+        assert_eq!(text(&screen), "");
+    }
+
+    #[test]
+    fn test_normal_mode_commands() {
+        let (_dir, mut screen) = workspace("one two three\nsecond\nthird\n");
+        type_keys(&mut screen, "2w");
+        assert_eq!(
+            screen.active_buffer().unwrap().cursor(),
+            Position::new(0, 8)
+        );
+        type_keys(&mut screen, "x");
+        assert_eq!(
+            screen.active_buffer().unwrap().line(0),
+            Some("one two hree")
+        );
+        type_keys(&mut screen, "jdd");
+        assert_eq!(text(&screen), "one two hree\nthird");
+        type_keys(&mut screen, "gg");
+        assert_eq!(screen.active_buffer().unwrap().cursor().line, 0);
+        type_keys(&mut screen, ":2");
+        screen.handle_key(key(KeyCode::Enter));
+        assert_eq!(screen.active_buffer().unwrap().cursor().line, 1);
+    }
+
+    #[test]
+    fn test_visual_delete_and_copy() {
+        let (_dir, mut screen) = workspace("abcdef\n");
+        type_keys(&mut screen, "lvll");
+        assert_eq!(screen.mode(), EditorMode::Visual);
+        let outcome = screen.handle_key(key(KeyCode::Char('y')));
+        assert_eq!(outcome, EditorOutcome::Copy("bcd".to_string()));
+
+        type_keys(&mut screen, "vld");
+        assert_eq!(text(&screen), "adef");
+    }
+
+    #[test]
+    fn test_multicursor_insert() {
+        let (_dir, mut screen) = workspace("a\nb\n");
+        screen.handle_key(ctrl('n'));
+        type_keys(&mut screen, "I#");
+        screen.handle_key(key(KeyCode::Esc));
+        assert_eq!(text(&screen), "#a\n#b");
+        screen.handle_key(key(KeyCode::Esc));
+        assert_eq!(screen.active_buffer().unwrap().cursor_count(), 1);
+    }
+
+    #[test]
+    fn test_marks_and_macros() {
+        let (_dir, mut screen) = workspace("x\ny\nz\n");
+        type_keys(&mut screen, "jma");
+        type_keys(&mut screen, "G'a");
+        assert_eq!(screen.active_buffer().unwrap().cursor().line, 1);
+
+        // Record: prepend "-" to the line and move down
+        type_keys(&mut screen, "qwI-");
+        screen.handle_key(key(KeyCode::Esc));
+        type_keys(&mut screen, "jq");
+        assert_eq!(text(&screen), "x\n-y\nz");
+
+        type_keys(&mut screen, "@w");
+        assert_eq!(text(&screen), "x\n-y\n-z");
+    }
+
+    #[test]
+    fn test_quit_requires_saved_buffers() {
+        let (_dir, mut screen) = workspace("a\n");
+        type_keys(&mut screen, "x:q");
+        assert!(matches!(
+            screen.handle_key(key(KeyCode::Enter)),
+            EditorOutcome::Error(_)
+        ));
+        type_keys(&mut screen, ":q!");
+        assert_eq!(screen.handle_key(key(KeyCode::Enter)), EditorOutcome::Close);
+        assert!(!screen.has_unsaved_changes());
+    }
+
+    #[test]
+    fn test_external_change_reloads_clean_and_warns_dirty() {
+        let (dir, mut screen) = workspace("old\n");
+        let path = dir.path().join("main.rs");
+
+        fs::write(&path, "new text\n").unwrap();
+        let outcomes = screen.check_external_changes();
+        assert!(matches!(&outcomes[..], [EditorOutcome::Message(_)]));
+        assert_eq!(text(&screen), "new text");
+
+        type_keys(&mut screen, "x");
+        fs::write(&path, "newer\n").unwrap();
+        let outcomes = screen.check_external_changes();
+        assert!(matches!(&outcomes[..], [EditorOutcome::Error(_)]));
+        // Reported once
+        assert!(screen.check_external_changes().is_empty());
+
+        type_keys(&mut screen, ":w");
+        assert!(matches!(
+            screen.handle_key(key(KeyCode::Enter)),
+            EditorOutcome::Error(_)
+        ));
+        type_keys(&mut screen, ":e!");
+        screen.handle_key(key(KeyCode::Enter));
+        assert_eq!(text(&screen), "newer");
+    }
+
+    #[test]
+    fn test_tree_opens_files() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("only.txt"), "hi").unwrap();
+        let mut screen = EditorScreen::new(dir.path());
+        assert_eq!(screen.focus(), EditorFocus::Tree);
+
+        // The first entry is the root directory itself
+        screen.handle_key(key(KeyCode::Char('j')));
+        screen.handle_key(key(KeyCode::Enter));
+        assert_eq!(screen.buffers().len(), 1);
+        assert_eq!(screen.focus(), EditorFocus::Buffer);
+    }
+
+    #[test]
+    fn test_key_names_roundtrip() {
+        for k in [key(KeyCode::Char('x')), key(KeyCode::Esc), ctrl('r')] {
+            assert_eq!(key_from_name(&key_name(k).unwrap()), Some(k));
+        }
+    }
+
+    #[test]
+    fn test_render() {
+        let (_dir, mut screen) = workspace("fn main() {\n    println!(\"hi\");\n}\n");
+        let backend = TestBackend::new(100, 20);
+        let mut terminal = Terminal::new(backend).unwrap();
+        terminal
+            .draw(|frame| screen.render(frame, frame.area()))
+            .unwrap();
+
+        let content: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|c| c.symbol())
+            .collect();
+        assert!(content.contains("println"));
+        assert!(content.contains("NORMAL"));
+        assert!(content.contains("main.rs"));
+    }
+}
@@ -0,0 +1,733 @@
+//! Git screen - Integrated git workflow
+//!
+//! Composes the git widgets (staging, commit dialog, branch manager, commit
+//! graph, diff viewer and conflict resolver) into one tabbed screen.
+//!
+//! # Architecture
+//!
+//! The screen only holds view state. Repository data is fetched off the UI
+//! thread through [`GitService`] and handed back as a [`GitRefresh`], which
+//! [`GitScreen::apply_refresh`] distributes to the widgets.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use toad::git::GitService;
+//! use toad::ui::screens::git::{GitPanel, GitScreen};
+//!
+//! # fn example() -> anyhow::Result<()> {
+//! let service = GitService::new(".")?;
+//! let mut screen = GitScreen::new(service);
+//! screen.set_panel(GitPanel::Graph);
+//! assert_eq!(screen.panel(), GitPanel::Graph);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::git::{GitService, MergeVersions, PullRequestDescription, RepoSnapshot};
+use crate::ui::widgets::git::{
+    DiffViewer, conflict_resolver::ConflictResolver, git_branch_manager::GitBranchManager,
+    git_commit_dialog::GitCommitDialog, git_graph::GitGraph, git_stage_ui::GitStageUI,
+};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+};
+use std::path::{Path, PathBuf};
+
+/// Panels of the git screen
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GitPanel {
+    /// Working tree status, staging and diffs
+    Status,
+    /// Branch list and branch operations
+    Branches,
+    /// Commit graph
+    Graph,
+    /// Merge conflict resolution
+    Conflicts,
+}
+
+impl GitPanel {
+    /// All panels in tab order
+    pub const ALL: [GitPanel; 4] = [
+        GitPanel::Status,
+        GitPanel::Branches,
+        GitPanel::Graph,
+        GitPanel::Conflicts,
+    ];
+
+    /// Display title
+    pub fn title(&self) -> &'static str {
+        match self {
+            GitPanel::Status => "Status",
+            GitPanel::Branches => "Branches",
+            GitPanel::Graph => "Graph",
+            GitPanel::Conflicts => "Conflicts",
+        }
+    }
+
+    /// Next panel (wraps around)
+    pub fn next(&self) -> Self {
+        let idx = Self::ALL.iter().position(|p| p == self).unwrap_or(0);
+        Self::ALL[(idx + 1) % Self::ALL.len()]
+    }
+
+    /// Previous panel (wraps around)
+    pub fn previous(&self) -> Self {
+        let idx = Self::ALL.iter().position(|p| p == self).unwrap_or(0);
+        Self::ALL[(idx + Self::ALL.len() - 1) % Self::ALL.len()]
+    }
+}
+
+/// A conflicted file as read from the working tree and the index
+#[derive(Debug, Clone, Default)]
+pub struct ConflictFile {
+    /// Path relative to the repository root
+    pub path: PathBuf,
+    /// Working tree content, with conflict markers
+    pub content: String,
+    /// Base, ours and theirs versions from the index
+    pub versions: MergeVersions,
+}
+
+/// Repository data collected asynchronously for the git screen
+#[derive(Debug, Clone, Default)]
+pub struct GitRefresh {
+    /// Status, branch and branch list
+    pub snapshot: RepoSnapshot,
+    /// Commit graph
+    pub graph: GitGraph,
+    /// Every conflicted file
+    pub conflicts: Vec<ConflictFile>,
+}
+
+/// Integrated git workflow screen
+pub struct GitScreen {
+    /// Service the screen operates on
+    service: GitService,
+    /// Active panel
+    panel: GitPanel,
+    /// Staging widget
+    stage: GitStageUI,
+    /// Commit dialog overlay
+    commit_dialog: GitCommitDialog,
+    /// Branch manager
+    branches: GitBranchManager,
+    /// Commit graph
+    graph: GitGraph,
+    /// Diff of the file selected on the status panel
+    diff: Option<DiffViewer>,
+    /// File shown in the diff viewer and whether it is the staged diff
+    diff_target: Option<(PathBuf, bool)>,
+    /// Conflict resolver
+    conflicts: ConflictResolver,
+    /// Files currently loaded into the conflict resolver
+    conflict_files: Vec<PathBuf>,
+    /// Latest repository snapshot
+    snapshot: RepoSnapshot,
+    /// Whether a refresh is in flight
+    loading: bool,
+    /// Generated pull request description shown as an overlay
+    pull_request: Option<PullRequestDescription>,
+}
+
+impl GitScreen {
+    /// Create a git screen for a repository
+    pub fn new(service: GitService) -> Self {
+        Self {
+            stage: GitStageUI::new(service.clone()),
+            commit_dialog: GitCommitDialog::new(service.clone()),
+            branches: GitBranchManager::new(service.clone()),
+            service,
+            panel: GitPanel::Status,
+            graph: GitGraph::new(),
+            diff: None,
+            diff_target: None,
+            conflicts: ConflictResolver::new(),
+            conflict_files: Vec::new(),
+            snapshot: RepoSnapshot::default(),
+            loading: false,
+            pull_request: None,
+        }
+    }
+
+    /// Get the git service
+    pub fn service(&self) -> &GitService {
+        &self.service
+    }
+
+    /// Get the active panel
+    pub fn panel(&self) -> GitPanel {
+        self.panel
+    }
+
+    /// Switch to a panel
+    pub fn set_panel(&mut self, panel: GitPanel) {
+        self.panel = panel;
+    }
+
+    /// Whether a refresh is in flight
+    pub fn is_loading(&self) -> bool {
+        self.loading
+    }
+
+    /// Mark a refresh as started or finished
+    pub fn set_loading(&mut self, loading: bool) {
+        self.loading = loading;
+    }
+
+    /// Latest repository snapshot
+    pub fn snapshot(&self) -> &RepoSnapshot {
+        &self.snapshot
+    }
+
+    /// Get the staging widget
+    pub fn stage(&self) -> &GitStageUI {
+        &self.stage
+    }
+
+    /// Get the staging widget mutably
+    pub fn stage_mut(&mut self) -> &mut GitStageUI {
+        &mut self.stage
+    }
+
+    /// Get the commit dialog
+    pub fn commit_dialog(&self) -> &GitCommitDialog {
+        &self.commit_dialog
+    }
+
+    /// Get the commit dialog mutably
+    pub fn commit_dialog_mut(&mut self) -> &mut GitCommitDialog {
+        &mut self.commit_dialog
+    }
+
+    /// Get the branch manager
+    pub fn branches(&self) -> &GitBranchManager {
+        &self.branches
+    }
+
+    /// Get the branch manager mutably
+    pub fn branches_mut(&mut self) -> &mut GitBranchManager {
+        &mut self.branches
+    }
+
+    /// Get the commit graph mutably
+    pub fn graph_mut(&mut self) -> &mut GitGraph {
+        &mut self.graph
+    }
+
+    /// Get the diff viewer, if a diff is loaded
+    pub fn diff(&self) -> Option<&DiffViewer> {
+        self.diff.as_ref()
+    }
+
+    /// Get the diff viewer mutably, if a diff is loaded
+    pub fn diff_mut(&mut self) -> Option<&mut DiffViewer> {
+        self.diff.as_mut()
+    }
+
+    /// File shown in the diff viewer and whether it is the staged diff
+    pub fn diff_target(&self) -> Option<(&Path, bool)> {
+        self.diff_target
+            .as_ref()
+            .map(|(path, staged)| (path.as_path(), *staged))
+    }
+
+    /// Show a diff for a file
+    pub fn set_diff(&mut self, path: impl Into<PathBuf>, staged: bool, diff: &str) {
+        let path = path.into();
+        let viewer = DiffViewer::new(diff).with_file_path(path.display().to_string());
+        self.diff = Some(viewer);
+        self.diff_target = Some((path, staged));
+    }
+
+    /// Close the diff viewer
+    pub fn clear_diff(&mut self) {
+        self.diff = None;
+        self.diff_target = None;
+    }
+
+    /// Generated pull request description, if shown
+    pub fn pull_request(&self) -> Option<&PullRequestDescription> {
+        self.pull_request.as_ref()
+    }
+
+    /// Show or hide a generated pull request description
+    pub fn set_pull_request(&mut self, description: Option<PullRequestDescription>) {
+        self.pull_request = description;
+    }
+
+    /// Get the conflict resolver
+    pub fn conflicts(&self) -> &ConflictResolver {
+        &self.conflicts
+    }
+
+    /// Get the conflict resolver mutably
+    pub fn conflicts_mut(&mut self) -> &mut ConflictResolver {
+        &mut self.conflicts
+    }
+
+    /// Files currently loaded into the conflict resolver
+    pub fn conflict_files(&self) -> &[PathBuf] {
+        &self.conflict_files
+    }
+
+    /// Distribute freshly fetched repository data to the widgets
+    ///
+    /// Returns `true` when conflicts were newly detected, in which case the
+    /// new files have been loaded into the conflict resolver, non-overlapping
+    /// regions auto-resolved and the conflicts panel activated. Files that are
+    /// no longer conflicted are dropped; choices made in the others are kept.
+    pub fn apply_refresh(&mut self, refresh: GitRefresh) -> bool {
+        let GitRefresh {
+            snapshot,
+            graph,
+            conflicts,
+        } = refresh;
+
+        self.loading = false;
+        self.stage
+            .apply_status(snapshot.branch.clone(), snapshot.changes.clone());
+        self.branches
+            .apply_branches(snapshot.branch.clone(), snapshot.branches.clone());
+        self.commit_dialog.set_staged_count(snapshot.staged_count());
+
+        let scroll = self.graph.scroll_offset();
+        self.graph = graph;
+        self.graph.set_scroll_offset(scroll);
+
+        // Drop the diff when its file no longer has changes
+        if let Some((path, _)) = &self.diff_target
+            && !snapshot.changes.iter().any(|c| c.path() == path.as_path())
+        {
+            self.clear_diff();
+        }
+
+        let conflict_paths: Vec<PathBuf> = conflicts.iter().map(|c| c.path.clone()).collect();
+        let newly_detected = conflict_paths
+            .iter()
+            .any(|path| !self.conflict_files.contains(path));
+
+        for path in &self.conflict_files {
+            if !conflict_paths.contains(path) {
+                self.conflicts.remove_file(&path.display().to_string());
+            }
+        }
+
+        if newly_detected {
+            self.conflicts
+                .set_branches(snapshot.branch.clone(), "MERGE_HEAD");
+            for file in &conflicts {
+                if !self.conflict_files.contains(&file.path) {
+                    self.conflicts.load_versions(
+                        file.path.display().to_string(),
+                        &file.content,
+                        &file.versions,
+                    );
+                }
+            }
+            self.conflicts.auto_resolve();
+            self.panel = GitPanel::Conflicts;
+        } else if conflict_paths.is_empty() && !self.conflict_files.is_empty() {
+            self.conflicts.clear();
+            if self.panel == GitPanel::Conflicts {
+                self.panel = GitPanel::Status;
+            }
+        }
+        self.conflict_files = conflict_paths;
+        self.snapshot = snapshot;
+
+        newly_detected
+    }
+
+    /// Render the screen
+    pub fn render(&mut self, frame: &mut Frame, area: Rect) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(1), // Panel tabs
+                Constraint::Min(0),    // Panel content
+                Constraint::Length(1), // Key hints
+            ])
+            .split(area);
+
+        frame.render_widget(Paragraph::new(self.tabs_line()), chunks[0]);
+
+        match self.panel {
+            GitPanel::Status => self.render_status(frame, chunks[1]),
+            GitPanel::Branches => frame.render_widget(&mut self.branches, chunks[1]),
+            GitPanel::Graph => frame.render_widget(&self.graph, chunks[1]),
+            GitPanel::Conflicts => {
+                if self.conflicts.conflict_count() == 0 {
+                    render_placeholder(frame, chunks[1], "Conflicts", "No merge conflicts");
+                } else {
+                    frame.render_widget(&self.conflicts, chunks[1]);
+                }
+            }
+        }
+
+        let hints = Paragraph::new(Line::from(Span::styled(
+            self.key_hints(),
+            Style::default().fg(Color::DarkGray),
+        )));
+        frame.render_widget(hints, chunks[2]);
+
+        if self.commit_dialog.is_visible() {
+            frame.render_widget(&self.commit_dialog, area);
+        }
+        if let Some(description) = &self.pull_request {
+            render_pull_request(frame, area, description);
+        }
+    }
+
+    /// Render the status panel: staging lists and the selected file's diff
+    fn render_status(&mut self, frame: &mut Frame, area: Rect) {
+        let chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(45), Constraint::Percentage(55)])
+            .split(area);
+
+        frame.render_widget(&mut self.stage, chunks[0]);
+
+        match self.diff.as_mut() {
+            Some(diff) => diff.render(frame, chunks[1]),
+            None => render_placeholder(
+                frame,
+                chunks[1],
+                "Diff",
+                "Press Enter on a file to view its diff",
+            ),
+        }
+    }
+
+    /// Build the panel tab line
+    fn tabs_line(&self) -> Line<'static> {
+        let mut spans = vec![Span::raw(" ")];
+        for (i, panel) in GitPanel::ALL.iter().enumerate() {
+            if i > 0 {
+                spans.push(Span::styled(" │ ", Style::default().fg(Color::DarkGray)));
+            }
+
+            let label = match panel {
+                GitPanel::Status => format!(
+                    "{} {} ({})",
+                    i + 1,
+                    panel.title(),
+                    self.snapshot.changes.len()
+                ),
+                GitPanel::Conflicts if !self.conflict_files.is_empty() => {
+                    format!(
+                        "{} {} ({})",
+                        i + 1,
+                        panel.title(),
+                        self.conflict_files.len()
+                    )
+                }
+                _ => format!("{} {}", i + 1, panel.title()),
+            };
+
+            let style = if *panel == self.panel {
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD)
+            } else if *panel == GitPanel::Conflicts && !self.conflict_files.is_empty() {
+                Style::default().fg(Color::Magenta)
+            } else {
+                Style::default().fg(Color::Gray)
+            };
+            spans.push(Span::styled(label, style));
+        }
+
+        if !self.snapshot.branch.is_empty() {
+            spans.push(Span::styled(
+                format!("   ⎇ {}", self.snapshot.branch),
+                Style::default().fg(Color::Green),
+            ));
+        }
+        if self.loading {
+            spans.push(Span::styled(
+                "   refreshing…",
+                Style::default().fg(Color::Yellow),
+            ));
+        }
+
+        Line::from(spans)
+    }
+
+    /// Key hints for the active panel
+    fn key_hints(&self) -> &'static str {
+        if self.pull_request.is_some() {
+            return " y Copy to clipboard · Esc Close";
+        }
+        if self.commit_dialog.is_visible() {
+            return " Ctrl+S Commit · Ctrl+G Generate · Enter Newline · Esc Cancel";
+        }
+        match self.panel {
+            GitPanel::Status => {
+                " Space Stage/unstage · a/u All · Enter Diff · [/] Hunk · s Stage hunk · c Commit · p PR text · r Refresh · Tab Panel · Esc Back"
+            }
+            GitPanel::Branches => {
+                " j/k Move · Enter Switch · n New branch · r Refresh · Tab Panel · Esc Back"
+            }
+            GitPanel::Graph => " j/k Scroll · r Refresh · Tab Panel · Esc Back",
+            GitPanel::Conflicts => {
+                " j/k Conflict · o/t/b Pick · a Ask Agent · y/n Proposal · w Write · v View · Esc Back"
+            }
+        }
+    }
+}
+
+/// Render a generated pull request description as a centered overlay
+fn render_pull_request(frame: &mut Frame, area: Rect, description: &PullRequestDescription) {
+    let width = (area.width * 4 / 5).max(20).min(area.width);
+    let height = (area.height * 3 / 4).max(6).min(area.height);
+    let popup = Rect {
+        x: area.x + (area.width - width) / 2,
+        y: area.y + (area.height - height) / 2,
+        width,
+        height,
+    };
+
+    let mut lines = vec![
+        Line::from(Span::styled(
+            description.title.clone(),
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        )),
+        Line::from(""),
+    ];
+    lines.extend(description.body.lines().map(|l| Line::from(l.to_string())));
+
+    let paragraph = Paragraph::new(lines).wrap(Wrap { trim: false }).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan))
+            .title(" Pull Request Description "),
+    );
+    frame.render_widget(Clear, popup);
+    frame.render_widget(paragraph, popup);
+}
+
+/// Render a bordered panel with a hint message
+fn render_placeholder(frame: &mut Frame, area: Rect, title: &str, message: &str) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(" {} ", title));
+    let paragraph = Paragraph::new(message.to_string())
+        .style(Style::default().fg(Color::DarkGray))
+        .wrap(Wrap { trim: true })
+        .block(block);
+    frame.render_widget(paragraph, area);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::{BranchInfo, FileChange};
+    use ratatui::{Terminal, backend::TestBackend};
+    use tempfile::TempDir;
+
+    fn screen() -> (TempDir, GitScreen) {
+        let dir = TempDir::new().unwrap();
+        std::fs::create_dir(dir.path().join(".git")).unwrap();
+        let service = GitService::new(dir.path()).unwrap();
+        (dir, GitScreen::new(service))
+    }
+
+    fn conflict(path: &str, content: &str) -> ConflictFile {
+        ConflictFile {
+            path: PathBuf::from(path),
+            content: content.to_string(),
+            versions: MergeVersions::default(),
+        }
+    }
+
+    fn refresh_with(changes: Vec<FileChange>, conflicts: Vec<ConflictFile>) -> GitRefresh {
+        GitRefresh {
+            snapshot: RepoSnapshot {
+                branch: "main".to_string(),
+                ahead: 0,
+                behind: 0,
+                changes,
+                branches: vec![BranchInfo {
+                    name: "main".to_string(),
+                    is_current: true,
+                    ahead: 0,
+                    behind: 0,
+                }],
+            },
+            graph: GitGraph::new(),
+            conflicts,
+        }
+    }
+
+    #[test]
+    fn test_panel_cycle() {
+        assert_eq!(GitPanel::Status.next(), GitPanel::Branches);
+        assert_eq!(GitPanel::Conflicts.next(), GitPanel::Status);
+        assert_eq!(GitPanel::Status.previous(), GitPanel::Conflicts);
+        assert_eq!(GitPanel::Graph.previous(), GitPanel::Branches);
+    }
+
+    #[test]
+    fn test_apply_refresh_populates_widgets() {
+        let (_dir, mut screen) = screen();
+        screen.set_loading(true);
+
+        let detected = screen.apply_refresh(refresh_with(
+            vec![
+                FileChange::Modified(PathBuf::from("a.rs")),
+                FileChange::Staged(PathBuf::from("b.rs")),
+            ],
+            Vec::new(),
+        ));
+
+        assert!(!detected);
+        assert!(!screen.is_loading());
+        assert_eq!(screen.stage().unstaged_count(), 1);
+        assert_eq!(screen.stage().staged_count(), 1);
+        assert_eq!(screen.commit_dialog().staged_count(), 1);
+        assert_eq!(screen.branches().current_branch(), "main");
+        assert_eq!(screen.branches().branch_count(), 1);
+    }
+
+    #[test]
+    fn test_apply_refresh_opens_conflicts() {
+        let (_dir, mut screen) = screen();
+        let content = "<<<<<<< HEAD\nours\n=======\ntheirs\n>>>>>>> feature\n".to_string();
+
+        let detected = screen.apply_refresh(refresh_with(
+            vec![FileChange::Conflicted(PathBuf::from("c.rs"))],
+            vec![conflict("c.rs", &content)],
+        ));
+
+        assert!(detected);
+        assert_eq!(screen.panel(), GitPanel::Conflicts);
+        assert_eq!(screen.conflicts().conflict_count(), 1);
+        assert_eq!(screen.conflict_files(), &[PathBuf::from("c.rs")]);
+
+        // Same conflicts again are not reported as new
+        screen.set_panel(GitPanel::Status);
+        let detected = screen.apply_refresh(refresh_with(
+            vec![FileChange::Conflicted(PathBuf::from("c.rs"))],
+            vec![conflict("c.rs", &content)],
+        ));
+        assert!(!detected);
+        assert_eq!(screen.panel(), GitPanel::Status);
+    }
+
+    #[test]
+    fn test_apply_refresh_clears_resolved_conflicts() {
+        let (_dir, mut screen) = screen();
+        screen.apply_refresh(refresh_with(
+            vec![FileChange::Conflicted(PathBuf::from("c.rs"))],
+            vec![conflict("c.rs", "<<<<<<< HEAD\na\n=======\nb\n>>>>>>> x\n")],
+        ));
+
+        screen.apply_refresh(refresh_with(Vec::new(), Vec::new()));
+
+        assert_eq!(screen.panel(), GitPanel::Status);
+        assert_eq!(screen.conflicts().conflict_count(), 0);
+        assert!(screen.conflict_files().is_empty());
+    }
+
+    #[test]
+    fn test_apply_refresh_keeps_choices_of_remaining_files() {
+        let (_dir, mut screen) = screen();
+        let a = conflict("a.rs", "<<<<<<< HEAD\na\n=======\nb\n>>>>>>> x\n");
+        let b = conflict("b.rs", "<<<<<<< HEAD\nc\n=======\nd\n>>>>>>> x\n");
+        screen.apply_refresh(refresh_with(Vec::new(), vec![a.clone(), b.clone()]));
+        screen.conflicts_mut().choose_ours();
+        screen.conflicts_mut().choose_theirs();
+
+        // a.rs was resolved on disk and a new conflict appeared in c.rs
+        let c = conflict("c.rs", "<<<<<<< HEAD\ne\n=======\nf\n>>>>>>> x\n");
+        let detected = screen.apply_refresh(refresh_with(Vec::new(), vec![b, c]));
+
+        assert!(detected);
+        assert_eq!(screen.conflicts().files(), vec!["b.rs", "c.rs"]);
+        assert_eq!(screen.conflicts().resolved_count(), 1);
+    }
+
+    #[test]
+    fn test_apply_refresh_auto_resolves_from_versions() {
+        let (_dir, mut screen) = screen();
+        let file = ConflictFile {
+            path: PathBuf::from("c.rs"),
+            content: String::new(),
+            versions: MergeVersions {
+                base: Some("a\nb\nc\n".to_string()),
+                ours: Some("a\nours\nc\n".to_string()),
+                theirs: Some("a\ntheirs\nc\n".to_string()),
+            },
+        };
+
+        assert!(screen.apply_refresh(refresh_with(Vec::new(), vec![file])));
+        let conflict = screen.conflicts().current().unwrap();
+        assert_eq!(conflict.base, Some(vec!["b".to_string()]));
+        assert_eq!(conflict.ours, vec!["ours"]);
+    }
+
+    #[test]
+    fn test_diff_cleared_when_file_clean() {
+        let (_dir, mut screen) = screen();
+        screen.set_diff("a.rs", false, "@@ -1,1 +1,1 @@\n-a\n+b\n");
+        assert_eq!(screen.diff().map(|d| d.hunk_count()), Some(1));
+
+        screen.apply_refresh(refresh_with(
+            vec![FileChange::Modified(PathBuf::from("a.rs"))],
+            Vec::new(),
+        ));
+        assert!(screen.diff().is_some());
+
+        screen.apply_refresh(refresh_with(Vec::new(), Vec::new()));
+        assert!(screen.diff().is_none());
+        assert!(screen.diff_target().is_none());
+    }
+
+    #[test]
+    fn test_render_all_panels() {
+        let (_dir, mut screen) = screen();
+        screen.apply_refresh(refresh_with(
+            vec![FileChange::Modified(PathBuf::from("a.rs"))],
+            Vec::new(),
+        ));
+        screen.set_diff("a.rs", false, "@@ -1,1 +1,1 @@\n-a\n+b\n");
+
+        let mut terminal = Terminal::new(TestBackend::new(120, 40)).unwrap();
+        for panel in GitPanel::ALL {
+            screen.set_panel(panel);
+            terminal.draw(|f| screen.render(f, f.area())).unwrap();
+        }
+
+        screen.commit_dialog_mut().open(1);
+        terminal.draw(|f| screen.render(f, f.area())).unwrap();
+    }
+
+    #[test]
+    fn test_render_pull_request_overlay() {
+        let (_dir, mut screen) = screen();
+        screen.set_pull_request(Some(PullRequestDescription {
+            title: "Add sync".to_string(),
+            body: "## Summary\n- sync".to_string(),
+        }));
+
+        let mut terminal = Terminal::new(TestBackend::new(80, 24)).unwrap();
+        terminal.draw(|f| screen.render(f, f.area())).unwrap();
+
+        let content: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|c| c.symbol())
+            .collect();
+        assert!(content.contains("Add sync"));
+        assert!(content.contains("Copy to clipboard"));
+    }
+}
@@ -0,0 +1,1287 @@
+//! Kanban screen - Task board with columns of cards
+//!
+//! Renders a [`KanbanBoard`](crate::infrastructure::visual_kanban_board::KanbanBoard)
+//! from the app's [`BoardDatabase`] as side-by-side columns of
+//! [`RichTaskCard`]s. Cards move between and within columns with the keyboard
+//! or by dragging them with the mouse, columns over their WIP limit are
+//! highlighted, and a query bar filters cards live using the
+//! [`filtering_search`](crate::infrastructure::filtering_search) syntax.
+//!
+//! # Architecture
+//!
+//! The screen only holds view state (selection, drag state and the areas hit
+//! by the last render) plus the live filter, which the app owns and lends to
+//! whichever board view is open. The board itself lives in the app and is
+//! passed into every call, so key and mouse handlers return a
+//! [`KanbanOutcome`] telling the app whether the board changed and needs
+//! saving.
+//!
+//! # Examples
+//!
+//! ```
+//! use toad::infrastructure::board_store::BoardDatabase;
+//! use toad::ui::screens::kanban::KanbanScreen;
+//!
+//! let mut db = BoardDatabase::default();
+//! let mut screen = KanbanScreen::new();
+//! screen.sync(&mut db);
+//! assert!(screen.board_id().is_some());
+//! ```
+
+use crate::infrastructure::advanced_mouse::{AdvancedMouseHandler, MouseGesture};
+use crate::infrastructure::board_store::BoardDatabase;
+use crate::infrastructure::filtering_search::LiveFilter;
+use crate::infrastructure::rich_task_cards::{Priority, RichTaskCard};
+use crate::infrastructure::visual_kanban_board::KanbanColumn;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+};
+
+/// Rows taken by one card (title, details, gap)
+const CARD_HEIGHT: u16 = 3;
+
+/// Activity entries shown in the card detail popup
+const DETAIL_ACTIVITY: usize = 5;
+
+/// Input and popup state
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KanbanMode {
+    /// Navigating and moving cards
+    Normal,
+    /// Typing a filter query
+    Filter,
+    /// Typing the title of a new card
+    NewCard,
+    /// Card detail popup
+    Detail,
+}
+
+/// Result of handling an input event, for the app to act on
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KanbanOutcome {
+    /// Nothing for the app to do
+    None,
+    /// The board changed and should be saved
+    Changed,
+    /// The board changed and a column is now over its WIP limit
+    WipExceeded(String),
+    /// Informational message
+    Message(String),
+    /// An operation failed
+    Error(String),
+    /// Hand a card to the agent
+    RunAgent(String),
+    /// Leave the kanban screen
+    Close,
+}
+
+/// Kanban board screen
+#[derive(Debug)]
+pub struct KanbanScreen {
+    /// Board being shown
+    board_id: Option<String>,
+    /// Selected column index
+    column: usize,
+    /// Selected card index among the column's visible cards
+    card: usize,
+    /// Input mode
+    mode: KanbanMode,
+    /// New card title as typed
+    title: String,
+    /// Live filter applied to cards
+    filter: LiveFilter,
+    /// Click, double-click and drag detection
+    mouse: AdvancedMouseHandler,
+    /// Card picked up by the mouse
+    drag_card: Option<String>,
+    /// Column under the pointer while dragging
+    drop_target: Option<usize>,
+    /// Detail popup scroll offset
+    detail_scroll: u16,
+    /// Column areas from the last render
+    column_areas: Vec<Rect>,
+    /// Card areas from the last render (column, visible index, area)
+    card_areas: Vec<(usize, usize, Rect)>,
+}
+
+impl Default for KanbanScreen {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl KanbanScreen {
+    /// Create a screen with no board selected
+    pub fn new() -> Self {
+        Self {
+            board_id: None,
+            column: 0,
+            card: 0,
+            mode: KanbanMode::Normal,
+            title: String::new(),
+            filter: LiveFilter::new(),
+            mouse: AdvancedMouseHandler::new(),
+            drag_card: None,
+            drop_target: None,
+            detail_scroll: 0,
+            column_areas: Vec::new(),
+            card_areas: Vec::new(),
+        }
+    }
+
+    /// Make sure a board is selected and every card is on it
+    ///
+    /// Creates a default board when the database has none and places cards
+    /// without a column. Returns whether the database changed.
+    pub fn sync(&mut self, db: &mut BoardDatabase) -> bool {
+        let boards_before = db.boards.total_boards();
+        let board_id = match self.board_id.as_deref() {
+            Some(id) if db.boards.get_board(id).is_some() => id.to_string(),
+            _ => db.ensure_board(),
+        };
+        let placed = db.place_cards(&board_id);
+        self.board_id = Some(board_id);
+        self.clamp_selection(db);
+        placed > 0 || db.boards.total_boards() != boards_before
+    }
+
+    /// Board being shown
+    pub fn board_id(&self) -> Option<&str> {
+        self.board_id.as_deref()
+    }
+
+    /// Current input mode
+    pub fn mode(&self) -> KanbanMode {
+        self.mode
+    }
+
+    /// Selected column index
+    pub fn selected_column(&self) -> usize {
+        self.column
+    }
+
+    /// Filter query as typed
+    pub fn query(&self) -> &str {
+        self.filter.query()
+    }
+
+    /// Card filter
+    pub fn filter_mut(&mut self) -> &mut LiveFilter {
+        &mut self.filter
+    }
+
+    /// ID of the selected card
+    pub fn selected_card_id(&self, db: &BoardDatabase) -> Option<String> {
+        let columns = self.columns(db);
+        let column = columns.get(self.column)?;
+        self.visible_cards(db, column)
+            .get(self.card)
+            .map(|card| card.id.clone())
+    }
+
+    /// Columns of the shown board
+    fn columns<'a>(&self, db: &'a BoardDatabase) -> Vec<&'a KanbanColumn> {
+        self.board_id
+            .as_deref()
+            .map(|id| db.boards.columns_for_board(id))
+            .unwrap_or_default()
+    }
+
+    /// Cards of a column that pass the filter, in column order
+    fn visible_cards<'a>(
+        &self,
+        db: &'a BoardDatabase,
+        column: &KanbanColumn,
+    ) -> Vec<&'a RichTaskCard> {
+        column
+            .card_ids
+            .iter()
+            .filter_map(|id| db.cards.get_card(id))
+            .filter(|card| self.filter.matches(card))
+            .collect()
+    }
+
+    /// Keep the selection inside the board
+    fn clamp_selection(&mut self, db: &BoardDatabase) {
+        let columns = self.columns(db);
+        self.column = self.column.min(columns.len().saturating_sub(1));
+        let visible = columns
+            .get(self.column)
+            .map_or(0, |column| self.visible_cards(db, column).len());
+        self.card = self.card.min(visible.saturating_sub(1));
+    }
+
+    /// Select a card by ID (if it is visible)
+    fn select_card(&mut self, db: &BoardDatabase, card_id: &str) {
+        for (c, column) in self.columns(db).iter().enumerate() {
+            if let Some(index) = self
+                .visible_cards(db, column)
+                .iter()
+                .position(|card| card.id == card_id)
+            {
+                self.column = c;
+                self.card = index;
+                return;
+            }
+        }
+    }
+
+    // ---- input ------------------------------------------------------------
+
+    /// Handle a key press
+    pub fn handle_key(&mut self, key: KeyEvent, db: &mut BoardDatabase) -> KanbanOutcome {
+        match self.mode {
+            KanbanMode::Normal => self.handle_normal_key(key, db),
+            KanbanMode::Filter => self.handle_filter_key(key, db),
+            KanbanMode::NewCard => self.handle_new_card_key(key, db),
+            KanbanMode::Detail => {
+                match key.code {
+                    KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q') => {
+                        self.mode = KanbanMode::Normal;
+                    }
+                    KeyCode::Char('j') | KeyCode::Down => {
+                        self.detail_scroll = self.detail_scroll.saturating_add(1);
+                    }
+                    KeyCode::Char('k') | KeyCode::Up => {
+                        self.detail_scroll = self.detail_scroll.saturating_sub(1);
+                    }
+                    _ => {}
+                }
+                KanbanOutcome::None
+            }
+        }
+    }
+
+    fn handle_normal_key(&mut self, key: KeyEvent, db: &mut BoardDatabase) -> KanbanOutcome {
+        let shift = key.modifiers.contains(KeyModifiers::SHIFT);
+        match key.code {
+            KeyCode::Char('H') => self.move_selected_across(db, -1),
+            KeyCode::Char('L') => self.move_selected_across(db, 1),
+            KeyCode::Char('J') => self.move_selected_within(db, 1),
+            KeyCode::Char('K') => self.move_selected_within(db, -1),
+            KeyCode::Left if shift => self.move_selected_across(db, -1),
+            KeyCode::Right if shift => self.move_selected_across(db, 1),
+            KeyCode::Down if shift => self.move_selected_within(db, 1),
+            KeyCode::Up if shift => self.move_selected_within(db, -1),
+            KeyCode::Char('h') | KeyCode::Left => {
+                self.column = self.column.saturating_sub(1);
+                self.clamp_selection(db);
+                KanbanOutcome::None
+            }
+            KeyCode::Char('l') | KeyCode::Right => {
+                self.column += 1;
+                self.clamp_selection(db);
+                KanbanOutcome::None
+            }
+            KeyCode::Char('j') | KeyCode::Down => {
+                self.card += 1;
+                self.clamp_selection(db);
+                KanbanOutcome::None
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.card = self.card.saturating_sub(1);
+                KanbanOutcome::None
+            }
+            KeyCode::Enter | KeyCode::Char(' ') => {
+                if self.selected_card_id(db).is_some() {
+                    self.mode = KanbanMode::Detail;
+                    self.detail_scroll = 0;
+                }
+                KanbanOutcome::None
+            }
+            KeyCode::Char('r') => self
+                .selected_card_id(db)
+                .map_or(KanbanOutcome::None, KanbanOutcome::RunAgent),
+            KeyCode::Char('n') => {
+                if self.columns(db).is_empty() {
+                    return KanbanOutcome::Error("Board has no columns".to_string());
+                }
+                self.title.clear();
+                self.mode = KanbanMode::NewCard;
+                KanbanOutcome::None
+            }
+            KeyCode::Char('/') => {
+                self.mode = KanbanMode::Filter;
+                KanbanOutcome::None
+            }
+            KeyCode::Char(']') | KeyCode::Tab => self.switch_board(db, 1),
+            KeyCode::Char('[') | KeyCode::BackTab => self.switch_board(db, -1),
+            KeyCode::Esc if !self.filter.query().is_empty() => {
+                self.filter.clear();
+                self.clamp_selection(db);
+                KanbanOutcome::Message("Filter cleared".to_string())
+            }
+            KeyCode::Esc | KeyCode::Char('q') => KanbanOutcome::Close,
+            _ => KanbanOutcome::None,
+        }
+    }
+
+    fn handle_filter_key(&mut self, key: KeyEvent, db: &mut BoardDatabase) -> KanbanOutcome {
+        match key.code {
+            KeyCode::Enter => {
+                // Record the query in the search history
+                self.filter.commit();
+                self.mode = KanbanMode::Normal;
+            }
+            KeyCode::Esc => {
+                self.filter.clear();
+                self.clamp_selection(db);
+                self.mode = KanbanMode::Normal;
+            }
+            KeyCode::Backspace => {
+                self.filter.pop();
+                self.clamp_selection(db);
+            }
+            KeyCode::Char(c) => {
+                self.filter.push(c);
+                self.clamp_selection(db);
+            }
+            _ => {}
+        }
+        KanbanOutcome::None
+    }
+
+    fn handle_new_card_key(&mut self, key: KeyEvent, db: &mut BoardDatabase) -> KanbanOutcome {
+        match key.code {
+            KeyCode::Esc => {
+                self.mode = KanbanMode::Normal;
+                KanbanOutcome::None
+            }
+            KeyCode::Backspace => {
+                self.title.pop();
+                KanbanOutcome::None
+            }
+            KeyCode::Char(c) => {
+                self.title.push(c);
+                KanbanOutcome::None
+            }
+            KeyCode::Enter => {
+                self.mode = KanbanMode::Normal;
+                let title = self.title.trim().to_string();
+                if title.is_empty() {
+                    return KanbanOutcome::None;
+                }
+                let Some(column) = self.columns(db).get(self.column).map(|c| c.id.clone()) else {
+                    return KanbanOutcome::Error("Board has no columns".to_string());
+                };
+                let card_id = db.cards.create_card(
+                    title,
+                    String::new(),
+                    String::new(),
+                    Priority::Medium,
+                    "me".to_string(),
+                );
+                self.apply_move(db, &card_id, &column, usize::MAX)
+            }
+            _ => KanbanOutcome::None,
+        }
+    }
+
+    /// Move the selected card to the end of a neighbouring column
+    fn move_selected_across(&mut self, db: &mut BoardDatabase, delta: isize) -> KanbanOutcome {
+        let Some(card_id) = self.selected_card_id(db) else {
+            return KanbanOutcome::None;
+        };
+        let target = self.column as isize + delta;
+        let Some(column) = usize::try_from(target)
+            .ok()
+            .and_then(|t| self.columns(db).get(t).map(|c| c.id.clone()))
+        else {
+            return KanbanOutcome::None;
+        };
+        self.apply_move(db, &card_id, &column, usize::MAX)
+    }
+
+    /// Swap the selected card with its visible neighbour
+    fn move_selected_within(&mut self, db: &mut BoardDatabase, delta: isize) -> KanbanOutcome {
+        let columns = self.columns(db);
+        let Some(column) = columns.get(self.column) else {
+            return KanbanOutcome::None;
+        };
+        let visible = self.visible_cards(db, column);
+        let Some(card_id) = visible.get(self.card).map(|c| c.id.clone()) else {
+            return KanbanOutcome::None;
+        };
+        let Some(neighbour) = usize::try_from(self.card as isize + delta)
+            .ok()
+            .and_then(|i| visible.get(i))
+        else {
+            return KanbanOutcome::None;
+        };
+        let position = column
+            .card_ids
+            .iter()
+            .position(|id| *id == neighbour.id)
+            .unwrap_or(0);
+        let column_id = column.id.clone();
+        self.apply_move(db, &card_id, &column_id, position)
+    }
+
+    /// Move a card and follow it with the selection
+    fn apply_move(
+        &mut self,
+        db: &mut BoardDatabase,
+        card_id: &str,
+        column_id: &str,
+        position: usize,
+    ) -> KanbanOutcome {
+        match db.move_card(card_id, column_id, position) {
+            Ok(warning) => {
+                self.select_card(db, card_id);
+                warning.map_or(KanbanOutcome::Changed, KanbanOutcome::WipExceeded)
+            }
+            Err(e) => KanbanOutcome::Error(e),
+        }
+    }
+
+    /// Show the next or previous board
+    fn switch_board(&mut self, db: &mut BoardDatabase, delta: isize) -> KanbanOutcome {
+        let ids: Vec<String> = db.boards.boards().iter().map(|b| b.id.clone()).collect();
+        if ids.len() < 2 {
+            return KanbanOutcome::None;
+        }
+        let current = self
+            .board_id
+            .as_ref()
+            .and_then(|id| ids.iter().position(|b| b == id))
+            .unwrap_or(0);
+        let next = (current as isize + delta).rem_euclid(ids.len() as isize) as usize;
+        self.board_id = Some(ids[next].clone());
+        self.column = 0;
+        self.card = 0;
+        let changed = self.sync(db);
+        let name = db
+            .boards
+            .get_board(&ids[next])
+            .map(|b| b.name.clone())
+            .unwrap_or_default();
+        if changed {
+            KanbanOutcome::Changed
+        } else {
+            KanbanOutcome::Message(format!("Board: {}", name))
+        }
+    }
+
+    /// Handle a mouse event (uses the areas from the last render)
+    ///
+    /// Click selects a card, double-click opens its details, dragging a card
+    /// onto a column drops it there, and the wheel moves the selection.
+    pub fn handle_mouse(&mut self, event: MouseEvent, db: &mut BoardDatabase) -> KanbanOutcome {
+        let (x, y) = (event.column, event.row);
+        if self.mode == KanbanMode::Detail {
+            match event.kind {
+                MouseEventKind::ScrollDown => {
+                    self.detail_scroll = self.detail_scroll.saturating_add(1)
+                }
+                MouseEventKind::ScrollUp => {
+                    self.detail_scroll = self.detail_scroll.saturating_sub(1)
+                }
+                MouseEventKind::Down(_) => self.mode = KanbanMode::Normal,
+                _ => {}
+            }
+            return KanbanOutcome::None;
+        }
+        if self.mode != KanbanMode::Normal {
+            return KanbanOutcome::None;
+        }
+
+        match event.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                let gesture = self.mouse.process_click(x, y);
+                self.mouse.start_drag(x, y);
+                self.drag_card = None;
+                if let Some((column, index)) = self.card_at(x, y) {
+                    self.column = column;
+                    self.card = index;
+                    self.drag_card = self.selected_card_id(db);
+                    if gesture == MouseGesture::DoubleClick {
+                        self.mode = KanbanMode::Detail;
+                        self.detail_scroll = 0;
+                    }
+                } else if let Some(column) = self.column_at(x, y) {
+                    self.column = column;
+                    self.clamp_selection(db);
+                }
+                KanbanOutcome::None
+            }
+            MouseEventKind::Drag(MouseButton::Left) => {
+                if self.mouse.update_drag(x, y) == Some(MouseGesture::Drag) {
+                    self.drop_target = self.column_at(x, y);
+                }
+                KanbanOutcome::None
+            }
+            MouseEventKind::Up(MouseButton::Left) => {
+                let dropped = self.mouse.end_drag() == Some(MouseGesture::Drop);
+                let card_id = self.drag_card.take();
+                self.drop_target = None;
+                match (dropped, card_id, self.column_at(x, y)) {
+                    (true, Some(card_id), Some(column)) => {
+                        self.drop_card(db, &card_id, column, x, y)
+                    }
+                    _ => KanbanOutcome::None,
+                }
+            }
+            MouseEventKind::ScrollDown => {
+                self.card += 1;
+                self.clamp_selection(db);
+                KanbanOutcome::None
+            }
+            MouseEventKind::ScrollUp => {
+                self.card = self.card.saturating_sub(1);
+                KanbanOutcome::None
+            }
+            _ => KanbanOutcome::None,
+        }
+    }
+
+    /// Drop a dragged card onto a column, taking the slot of the card under
+    /// the pointer (or the end of the column)
+    fn drop_card(
+        &mut self,
+        db: &mut BoardDatabase,
+        card_id: &str,
+        column: usize,
+        x: u16,
+        y: u16,
+    ) -> KanbanOutcome {
+        let columns = self.columns(db);
+        let Some(target) = columns.get(column) else {
+            return KanbanOutcome::None;
+        };
+        let position = match self.card_at(x, y) {
+            Some((c, index)) if c == column => self
+                .visible_cards(db, target)
+                .get(index)
+                .and_then(|card| target.card_ids.iter().position(|id| *id == card.id))
+                .unwrap_or(usize::MAX),
+            _ => usize::MAX,
+        };
+        let column_id = target.id.clone();
+        self.apply_move(db, card_id, &column_id, position)
+    }
+
+    fn column_at(&self, x: u16, y: u16) -> Option<usize> {
+        self.column_areas
+            .iter()
+            .position(|area| contains(*area, x, y))
+    }
+
+    fn card_at(&self, x: u16, y: u16) -> Option<(usize, usize)> {
+        self.card_areas
+            .iter()
+            .find(|(_, _, area)| contains(*area, x, y))
+            .map(|(column, index, _)| (*column, *index))
+    }
+
+    // ---- rendering --------------------------------------------------------
+
+    /// Render the board
+    pub fn render(&mut self, frame: &mut Frame, area: Rect, db: &BoardDatabase) {
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(1), // Board name and filter
+                Constraint::Min(0),    // Columns
+                Constraint::Length(1), // Input line / key hints
+            ])
+            .split(area);
+
+        frame.render_widget(Paragraph::new(self.header_line(db)), rows[0]);
+        self.render_columns(frame, rows[1], db);
+        frame.render_widget(Paragraph::new(self.footer_line()), rows[2]);
+
+        if self.mode == KanbanMode::Detail {
+            self.render_detail(frame, area, db);
+        }
+    }
+
+    fn header_line(&self, db: &BoardDatabase) -> Line<'static> {
+        let boards = db.boards.boards();
+        let Some(board) = self
+            .board_id
+            .as_deref()
+            .and_then(|id| db.boards.get_board(id))
+        else {
+            return Line::from(Span::styled(
+                " No board",
+                Style::default().fg(Color::DarkGray),
+            ));
+        };
+        let index = boards.iter().position(|b| b.id == board.id).unwrap_or(0);
+
+        let columns = self.columns(db);
+        let total: usize = columns.iter().map(|c| c.card_count()).sum();
+        let shown: usize = columns
+            .iter()
+            .map(|c| self.visible_cards(db, c).len())
+            .sum();
+
+        let mut spans = vec![
+            Span::styled(
+                format!(" ▦ {} ", board.name),
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                format!("[{}/{}] ", index + 1, boards.len()),
+                Style::default().fg(Color::DarkGray),
+            ),
+            Span::styled(
+                format!("{}/{} cards", shown, total),
+                Style::default().fg(Color::Gray),
+            ),
+        ];
+
+        let over: Vec<&str> = columns
+            .iter()
+            .filter(|c| c.is_over_wip_limit())
+            .map(|c| c.name.as_str())
+            .collect();
+        if !over.is_empty() {
+            spans.push(Span::styled(
+                format!("  ⚠ WIP exceeded: {}", over.join(", ")),
+                Style::default().fg(Color::Red),
+            ));
+        }
+
+        if self.mode == KanbanMode::Filter {
+            spans.push(Span::styled(
+                format!("  / {}█", self.filter.query()),
+                Style::default().fg(Color::Yellow),
+            ));
+        } else if !self.filter.query().is_empty() {
+            spans.push(Span::styled(
+                format!("  filter: {}", self.filter.query()),
+                Style::default().fg(Color::Yellow),
+            ));
+        }
+        Line::from(spans)
+    }
+
+    fn footer_line(&self) -> Line<'static> {
+        match self.mode {
+            KanbanMode::NewCard => Line::from(vec![
+                Span::styled("New card: ", Style::default().fg(Color::Green)),
+                Span::raw(format!("{}█", self.title)),
+            ]),
+            KanbanMode::Filter => Line::from(Span::styled(
+                "Type to filter (title:, tag:, assignee:me, priority:P0, status:, -tag:)  Enter keep  Esc clear",
+                Style::default().fg(Color::DarkGray),
+            )),
+            KanbanMode::Detail => Line::from(Span::styled(
+                "j/k scroll  Esc close",
+                Style::default().fg(Color::DarkGray),
+            )),
+            KanbanMode::Normal => Line::from(Span::styled(
+                "h/l column  j/k card  H/L move  J/K reorder  Enter details  r run agent  n new  / filter  [/] board  Esc back",
+                Style::default().fg(Color::DarkGray),
+            )),
+        }
+    }
+
+    fn render_columns(&mut self, frame: &mut Frame, area: Rect, db: &BoardDatabase) {
+        self.column_areas.clear();
+        self.card_areas.clear();
+
+        let columns = self.columns(db);
+        if columns.is_empty() {
+            let message = Paragraph::new("This board has no columns")
+                .style(Style::default().fg(Color::DarkGray))
+                .block(Block::default().borders(Borders::ALL));
+            frame.render_widget(message, area);
+            return;
+        }
+
+        let areas = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints(vec![
+                Constraint::Ratio(1, columns.len() as u32);
+                columns.len()
+            ])
+            .split(area);
+
+        for (index, (column, column_area)) in columns.iter().zip(areas.iter()).enumerate() {
+            self.column_areas.push(*column_area);
+            self.render_column(frame, *column_area, index, column, db);
+        }
+    }
+
+    fn render_column(
+        &mut self,
+        frame: &mut Frame,
+        area: Rect,
+        index: usize,
+        column: &KanbanColumn,
+        db: &BoardDatabase,
+    ) {
+        let selected = index == self.column;
+        let at_limit = column
+            .wip_limit
+            .is_some_and(|limit| column.card_count() == limit);
+        let border = if self.drop_target == Some(index) {
+            Style::default().fg(Color::Cyan)
+        } else if column.is_over_wip_limit() {
+            Style::default().fg(Color::Red)
+        } else if at_limit {
+            Style::default().fg(Color::Yellow)
+        } else if selected {
+            Style::default().fg(Color::Green)
+        } else {
+            Style::default().fg(Color::DarkGray)
+        };
+        let count = match column.wip_limit {
+            Some(limit) => format!("{}/{}", column.card_count(), limit),
+            None => column.card_count().to_string(),
+        };
+        let warning = if column.is_over_wip_limit() {
+            "⚠ "
+        } else {
+            ""
+        };
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(border)
+            .title(format!(" {}{} {} ", warning, column.name, count));
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let cards = self.visible_cards(db, column);
+        if cards.is_empty() {
+            let hint = if column.card_ids.is_empty() {
+                "(empty)"
+            } else {
+                "(no matches)"
+            };
+            frame.render_widget(
+                Paragraph::new(hint).style(Style::default().fg(Color::DarkGray)),
+                inner,
+            );
+            return;
+        }
+
+        let capacity = (inner.height / CARD_HEIGHT).max(1) as usize;
+        let offset = if selected {
+            self.card.saturating_sub(capacity - 1)
+        } else {
+            0
+        };
+        for (slot, (card_index, card)) in cards
+            .iter()
+            .enumerate()
+            .skip(offset)
+            .take(capacity)
+            .enumerate()
+        {
+            let card_area = Rect {
+                x: inner.x,
+                y: inner.y + slot as u16 * CARD_HEIGHT,
+                width: inner.width,
+                height: (CARD_HEIGHT - 1)
+                    .min(inner.height.saturating_sub(slot as u16 * CARD_HEIGHT)),
+            };
+            self.card_areas.push((index, card_index, card_area));
+            let is_selected = selected && card_index == self.card;
+            let dragged =
+                self.drag_card.as_deref() == Some(card.id.as_str()) && self.drop_target.is_some();
+            frame.render_widget(
+                Paragraph::new(card_lines(card, db, is_selected, dragged)),
+                card_area,
+            );
+        }
+    }
+
+    fn render_detail(&self, frame: &mut Frame, area: Rect, db: &BoardDatabase) {
+        let Some(card) = self
+            .selected_card_id(db)
+            .and_then(|id| db.cards.get_card(&id))
+        else {
+            return;
+        };
+        let width = area.width * 7 / 10;
+        let height = area.height * 8 / 10;
+        let popup = Rect {
+            x: area.x + (area.width - width) / 2,
+            y: area.y + (area.height - height) / 2,
+            width,
+            height,
+        };
+
+        let paragraph = Paragraph::new(detail_lines(card, db))
+            .wrap(Wrap { trim: false })
+            .scroll((self.detail_scroll, 0))
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Cyan))
+                    .title(format!(" {} ", card.id)),
+            );
+        frame.render_widget(Clear, popup);
+        frame.render_widget(paragraph, popup);
+    }
+}
+
+/// Whether a point lies inside an area
+fn contains(area: Rect, x: u16, y: u16) -> bool {
+    x >= area.x && x < area.x + area.width && y >= area.y && y < area.y + area.height
+}
+
+/// Short priority label (`P0`-`P3`)
+pub(crate) fn priority_label(priority: Priority) -> String {
+    format!("P{}", priority.to_value())
+}
+
+/// Display color of a priority
+pub(crate) fn priority_color(priority: Priority) -> Color {
+    match priority {
+        Priority::Critical => Color::Red,
+        Priority::High => Color::LightRed,
+        Priority::Medium => Color::Yellow,
+        Priority::Low => Color::Green,
+    }
+}
+
+/// Title and summary lines of a card tile
+fn card_lines(
+    card: &RichTaskCard,
+    db: &BoardDatabase,
+    selected: bool,
+    dragged: bool,
+) -> Vec<Line<'static>> {
+    let mut title_style = Style::default().add_modifier(Modifier::BOLD);
+    if selected {
+        title_style = title_style.bg(Color::DarkGray);
+    }
+    if dragged {
+        title_style = title_style.add_modifier(Modifier::DIM | Modifier::ITALIC);
+    }
+
+    let mut details = vec![priority_label(card.priority)];
+    if card.total_subtasks() > 0 {
+        details.push(format!(
+            "✓{}/{}",
+            card.completed_subtasks(),
+            card.total_subtasks()
+        ));
+    }
+    details.extend(card.assignees.iter().map(|a| format!("@{}", a.user_id)));
+    details.extend(card.tags.iter().map(|t| format!("#{}", t.name)));
+    let comments = db
+        .comments
+        .comments_for_card(&card.id)
+        .iter()
+        .filter(|c| !c.deleted)
+        .count();
+    if comments > 0 {
+        details.push(format!("💬{}", comments));
+    }
+
+    let mut detail_spans = vec![Span::styled(
+        format!("  {}", details.join(" · ")),
+        Style::default().fg(Color::Gray),
+    )];
+    if let Some(due) = card.due_date {
+        let style = if card.is_overdue() {
+            Style::default().fg(Color::Red)
+        } else {
+            Style::default().fg(Color::Gray)
+        };
+        detail_spans.push(Span::styled(
+            format!(" · due {}", due.format("%m-%d")),
+            style,
+        ));
+    }
+
+    vec![
+        Line::from(vec![
+            Span::styled("▌", Style::default().fg(priority_color(card.priority))),
+            Span::styled(card.title.clone(), title_style),
+        ]),
+        Line::from(detail_spans),
+    ]
+}
+
+/// Contents of the card detail popup
+fn detail_lines(card: &RichTaskCard, db: &BoardDatabase) -> Vec<Line<'static>> {
+    let heading = |text: String| {
+        Line::from(Span::styled(
+            text,
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        ))
+    };
+    let field = |name: &str, value: String| {
+        Line::from(vec![
+            Span::styled(
+                format!("{:<10}", name),
+                Style::default().fg(Color::DarkGray),
+            ),
+            Span::raw(value),
+        ])
+    };
+
+    let mut lines = vec![
+        Line::from(Span::styled(
+            card.title.clone(),
+            Style::default().add_modifier(Modifier::BOLD),
+        )),
+        Line::from(""),
+        field("Status", card.status.clone()),
+        field(
+            "Priority",
+            format!("{} {:?}", priority_label(card.priority), card.priority),
+        ),
+    ];
+    if !card.assignees.is_empty() {
+        let names: Vec<&str> = card
+            .assignees
+            .iter()
+            .map(|a| a.display_name.as_str())
+            .collect();
+        lines.push(field("Assignees", names.join(", ")));
+    }
+    if !card.tags.is_empty() {
+        let tags: Vec<&str> = card.tags.iter().map(|t| t.name.as_str()).collect();
+        lines.push(field("Tags", tags.join(", ")));
+    }
+    if let Some(due) = card.due_date {
+        lines.push(field("Due", due.format("%Y-%m-%d %H:%M").to_string()));
+    }
+    if let Some(estimate) = &card.effort_estimate {
+        lines.push(field("Estimate", format!("{:.1}h", estimate.to_hours())));
+    }
+    lines.push(field("Progress", format!("{}%", card.progress)));
+
+    if !card.description.is_empty() {
+        lines.push(Line::from(""));
+        lines.extend(card.description.lines().map(|l| Line::from(l.to_string())));
+    }
+
+    if !card.subtasks.is_empty() {
+        lines.push(Line::from(""));
+        lines.push(heading(format!(
+            "Subtasks ({}/{})",
+            card.completed_subtasks(),
+            card.total_subtasks()
+        )));
+        for subtask in &card.subtasks {
+            let mark = if subtask.completed { "[x]" } else { "[ ]" };
+            lines.push(Line::from(format!("  {} {}", mark, subtask.title)));
+        }
+    }
+
+    let mut comments: Vec<_> = db
+        .comments
+        .comments_for_card(&card.id)
+        .into_iter()
+        .filter(|c| !c.deleted)
+        .collect();
+    comments.sort_by_key(|c| c.created_at);
+    lines.push(Line::from(""));
+    lines.push(heading(format!("Comments ({})", comments.len())));
+    for comment in comments {
+        let indent = if comment.parent_id.is_some() {
+            "    "
+        } else {
+            "  "
+        };
+        lines.push(Line::from(vec![
+            Span::raw(indent.to_string()),
+            Span::styled(
+                comment.author_name.clone(),
+                Style::default().fg(Color::Yellow),
+            ),
+            Span::styled(
+                format!(" · {}", comment.created_at.format("%Y-%m-%d %H:%M")),
+                Style::default().fg(Color::DarkGray),
+            ),
+        ]));
+        for line in comment.content.lines() {
+            lines.push(Line::from(format!("{}  {}", indent, line)));
+        }
+    }
+
+    let attachments = db.attachments.attachments_for_card(&card.id);
+    lines.push(Line::from(""));
+    lines.push(heading(format!("Attachments ({})", attachments.len())));
+    for attachment in attachments {
+        let size = attachment
+            .current_version()
+            .map(|v| v.human_readable_size())
+            .unwrap_or_default();
+        lines.push(Line::from(format!(
+            "  {} ({}, {})",
+            attachment.name,
+            attachment.attachment_type.name(),
+            size
+        )));
+    }
+
+    let activity = db.comments.activities_for_card(&card.id);
+    if !activity.is_empty() {
+        lines.push(Line::from(""));
+        lines.push(heading("Activity".to_string()));
+        for entry in activity.iter().rev().take(DETAIL_ACTIVITY) {
+            lines.push(Line::from(Span::styled(
+                format!(
+                    "  {} {}",
+                    entry.created_at.format("%m-%d %H:%M"),
+                    entry.description
+                ),
+                Style::default().fg(Color::Gray),
+            )));
+        }
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::file_attachments::AttachmentType;
+    use crossterm::event::KeyModifiers;
+    use ratatui::{Terminal, backend::TestBackend};
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn mouse(kind: MouseEventKind, x: u16, y: u16) -> MouseEvent {
+        MouseEvent {
+            kind,
+            column: x,
+            row: y,
+            modifiers: KeyModifiers::NONE,
+        }
+    }
+
+    fn board_with_cards(titles: &[&str]) -> (BoardDatabase, KanbanScreen) {
+        let mut db = BoardDatabase::default();
+        for title in titles {
+            db.cards.create_card(
+                title.to_string(),
+                String::new(),
+                "To Do".to_string(),
+                Priority::Medium,
+                "me".to_string(),
+            );
+        }
+        let mut screen = KanbanScreen::new();
+        screen.sync(&mut db);
+        (db, screen)
+    }
+
+    fn render(screen: &mut KanbanScreen, db: &BoardDatabase) -> String {
+        let mut terminal = Terminal::new(TestBackend::new(100, 20)).unwrap();
+        terminal.draw(|f| screen.render(f, f.area(), db)).unwrap();
+        terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|c| c.symbol())
+            .collect()
+    }
+
+    fn column_cards(db: &BoardDatabase, screen: &KanbanScreen, index: usize) -> Vec<String> {
+        screen.columns(db)[index].card_ids.clone()
+    }
+
+    #[test]
+    fn test_sync_creates_board_and_places_cards() {
+        let (db, screen) = board_with_cards(&["a", "b"]);
+        assert!(screen.board_id().is_some());
+        assert_eq!(column_cards(&db, &screen, 0).len(), 2);
+        assert_eq!(screen.selected_card_id(&db).as_deref(), Some("card-1"));
+    }
+
+    #[test]
+    fn test_keyboard_moves_between_columns() {
+        let (mut db, mut screen) = board_with_cards(&["a", "b"]);
+
+        let outcome = screen.handle_key(key(KeyCode::Char('L')), &mut db);
+        assert_eq!(outcome, KanbanOutcome::Changed);
+        assert_eq!(screen.selected_column(), 1);
+        assert_eq!(column_cards(&db, &screen, 1), vec!["card-1".to_string()]);
+        assert_eq!(db.cards.get_card("card-1").unwrap().status, "In Progress");
+
+        screen.handle_key(key(KeyCode::Char('H')), &mut db);
+        assert_eq!(screen.selected_column(), 0);
+        assert_eq!(
+            column_cards(&db, &screen, 0),
+            vec!["card-2".to_string(), "card-1".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_keyboard_reorders_within_column() {
+        let (mut db, mut screen) = board_with_cards(&["a", "b", "c"]);
+        screen.handle_key(key(KeyCode::Char('J')), &mut db);
+        assert_eq!(
+            column_cards(&db, &screen, 0),
+            vec![
+                "card-2".to_string(),
+                "card-1".to_string(),
+                "card-3".to_string()
+            ]
+        );
+        assert_eq!(screen.selected_card_id(&db).as_deref(), Some("card-1"));
+
+        screen.handle_key(key(KeyCode::Char('K')), &mut db);
+        assert_eq!(column_cards(&db, &screen, 0)[0], "card-1");
+    }
+
+    #[test]
+    fn test_wip_limit_warning() {
+        let (mut db, mut screen) = board_with_cards(&["a"]);
+        let doing = screen.columns(&db)[1].id.clone();
+        db.boards.set_wip_limit(&doing, Some(0));
+
+        let outcome = screen.handle_key(key(KeyCode::Char('L')), &mut db);
+        assert!(matches!(outcome, KanbanOutcome::WipExceeded(_)));
+        assert!(render(&mut screen, &db).contains("WIP exceeded: In Progress"));
+    }
+
+    #[test]
+    fn test_live_filter() {
+        let (mut db, mut screen) = board_with_cards(&["Fix login", "Write docs"]);
+        screen.handle_key(key(KeyCode::Char('/')), &mut db);
+        for c in "docs".chars() {
+            screen.handle_key(key(KeyCode::Char(c)), &mut db);
+        }
+        assert_eq!(screen.mode(), KanbanMode::Filter);
+        assert_eq!(screen.selected_card_id(&db).as_deref(), Some("card-2"));
+        let screen_text = render(&mut screen, &db);
+        assert!(screen_text.contains("Write docs"));
+        assert!(!screen_text.contains("Fix login"));
+
+        screen.handle_key(key(KeyCode::Enter), &mut db);
+        assert_eq!(screen.mode(), KanbanMode::Normal);
+        assert_eq!(screen.query(), "docs");
+
+        // Esc clears the filter before leaving the screen
+        screen.handle_key(key(KeyCode::Esc), &mut db);
+        assert!(screen.query().is_empty());
+        assert_eq!(
+            screen.handle_key(key(KeyCode::Esc), &mut db),
+            KanbanOutcome::Close
+        );
+    }
+
+    #[test]
+    fn test_new_card() {
+        let (mut db, mut screen) = board_with_cards(&[]);
+        screen.handle_key(key(KeyCode::Char('l')), &mut db);
+        screen.handle_key(key(KeyCode::Char('n')), &mut db);
+        for c in "Ship it".chars() {
+            screen.handle_key(key(KeyCode::Char(c)), &mut db);
+        }
+        let outcome = screen.handle_key(key(KeyCode::Enter), &mut db);
+        assert_eq!(outcome, KanbanOutcome::Changed);
+        let card_id = screen.selected_card_id(&db).unwrap();
+        let card = db.cards.get_card(&card_id).unwrap();
+        assert_eq!(card.title, "Ship it");
+        assert_eq!(card.status, "In Progress");
+    }
+
+    #[test]
+    fn test_detail_popup_shows_comments_and_attachments() {
+        let (mut db, mut screen) = board_with_cards(&["Fix login"]);
+        db.comments.create_comment(
+            "card-1".to_string(),
+            None,
+            "Repro steps attached".to_string(),
+            "bob".to_string(),
+            "Bob".to_string(),
+        );
+        db.attachments.create_attachment(
+            "card-1".to_string(),
+            AttachmentType::Upload,
+            "trace.log".to_string(),
+            "/tmp/trace.log".to_string(),
+            "bob".to_string(),
+        );
+
+        screen.handle_key(key(KeyCode::Enter), &mut db);
+        assert_eq!(screen.mode(), KanbanMode::Detail);
+        let text = render(&mut screen, &db);
+        assert!(text.contains("Repro steps attached"));
+        assert!(text.contains("trace.log"));
+
+        screen.handle_key(key(KeyCode::Esc), &mut db);
+        assert_eq!(screen.mode(), KanbanMode::Normal);
+    }
+
+    #[test]
+    fn test_mouse_drag_and_drop() {
+        let (mut db, mut screen) = board_with_cards(&["a", "b"]);
+        render(&mut screen, &db);
+
+        let (_, _, card) = screen.card_areas[1];
+        let target = screen.column_areas[2];
+        screen.handle_mouse(
+            mouse(MouseEventKind::Down(MouseButton::Left), card.x + 1, card.y),
+            &mut db,
+        );
+        assert_eq!(screen.selected_card_id(&db).as_deref(), Some("card-2"));
+
+        let (x, y) = (target.x + 2, target.y + 2);
+        screen.handle_mouse(
+            mouse(MouseEventKind::Drag(MouseButton::Left), x, y),
+            &mut db,
+        );
+        assert_eq!(screen.drop_target, Some(2));
+        let outcome =
+            screen.handle_mouse(mouse(MouseEventKind::Up(MouseButton::Left), x, y), &mut db);
+
+        assert_eq!(outcome, KanbanOutcome::Changed);
+        assert_eq!(column_cards(&db, &screen, 2), vec!["card-2".to_string()]);
+        assert_eq!(screen.selected_column(), 2);
+    }
+
+    #[test]
+    fn test_mouse_click_without_drag_only_selects() {
+        let (mut db, mut screen) = board_with_cards(&["a", "b"]);
+        render(&mut screen, &db);
+        let (_, _, card) = screen.card_areas[1];
+        let (x, y) = (card.x + 1, card.y);
+        screen.handle_mouse(
+            mouse(MouseEventKind::Down(MouseButton::Left), x, y),
+            &mut db,
+        );
+        let outcome =
+            screen.handle_mouse(mouse(MouseEventKind::Up(MouseButton::Left), x, y), &mut db);
+        assert_eq!(outcome, KanbanOutcome::None);
+        assert_eq!(column_cards(&db, &screen, 0).len(), 2);
+    }
+
+    #[test]
+    fn test_run_agent_on_selected_card() {
+        let (mut db, mut screen) = board_with_cards(&["a"]);
+        assert_eq!(
+            screen.handle_key(key(KeyCode::Char('r')), &mut db),
+            KanbanOutcome::RunAgent("card-1".to_string())
+        );
+        screen.handle_key(key(KeyCode::Char('l')), &mut db);
+        assert_eq!(
+            screen.handle_key(key(KeyCode::Char('r')), &mut db),
+            KanbanOutcome::None
+        );
+    }
+
+    #[test]
+    fn test_switch_board() {
+        let (mut db, mut screen) = board_with_cards(&[]);
+        let second = db
+            .boards
+            .create_board("Second".to_string(), String::new(), "me".to_string());
+        screen.handle_key(key(KeyCode::Char(']')), &mut db);
+        assert_eq!(screen.board_id(), Some(second.as_str()));
+    }
+}
@@ -0,0 +1,759 @@
+//! Mind map screen - Cards as a collapsible tree
+//!
+//! Shows the board as a tree: the board at the root, one branch per epic,
+//! the epic's cards below it, and under each card its subtasks and
+//! dependency links. A card's epic is its `epic` custom field; cards without
+//! one are grouped under "No epic".
+//!
+//! # Architecture
+//!
+//! The screen stores which nodes differ from the default expansion and
+//! flattens the tree from the board on every key and render, so it always
+//! reflects the current cards. It borrows the live filter from the app like
+//! the other board views; epics whose cards are all filtered out disappear.
+//! Settings round-trip through [`ViewSettings::MindMap`]. The orientation is
+//! kept for other renderers but the terminal always draws an indented tree.
+//!
+//! # Examples
+//!
+//! ```
+//! use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+//! use toad::infrastructure::board_store::BoardDatabase;
+//! use toad::infrastructure::Priority;
+//! use toad::ui::screens::mind_map::MindMapScreen;
+//!
+//! let mut db = BoardDatabase::default();
+//! db.cards.create_card(
+//!     "Login".to_string(),
+//!     String::new(),
+//!     "To Do".to_string(),
+//!     Priority::High,
+//!     "me".to_string(),
+//! );
+//! let mut screen = MindMapScreen::new();
+//! assert_eq!(screen.visible_nodes(&db), 3); // board, "No epic", card
+//! screen.handle_key(KeyEvent::new(KeyCode::Char('C'), KeyModifiers::NONE), &mut db);
+//! assert_eq!(screen.visible_nodes(&db), 2);
+//! ```
+
+use crate::infrastructure::board_store::BoardDatabase;
+use crate::infrastructure::filtering_search::LiveFilter;
+use crate::infrastructure::multiple_views::{MindMapOrientation, ViewSettings};
+use crate::infrastructure::rich_task_cards::{CustomFieldType, RichTaskCard};
+use crate::infrastructure::task_dependencies::{Dependency, DependencyType};
+use crate::ui::screens::kanban::{priority_color, priority_label};
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
+};
+use std::collections::{BTreeMap, HashSet};
+
+/// Custom field holding a card's epic
+pub const EPIC_FIELD: &str = "epic";
+
+/// Branch for cards without an epic
+const NO_EPIC: &str = "No epic";
+
+/// Key of the root node
+const ROOT_KEY: &str = "board";
+
+/// Result of handling an input event, for the app to act on
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MindMapOutcome {
+    /// Nothing for the app to do
+    None,
+    /// Informational message
+    Message(String),
+    /// Leave the mind map screen
+    Close,
+}
+
+/// What a tree node stands for
+#[derive(Debug, Clone, PartialEq)]
+enum NodeKind {
+    Board,
+    Epic {
+        name: String,
+        cards: usize,
+    },
+    Card(String),
+    Subtask {
+        title: String,
+        completed: bool,
+    },
+    Link {
+        arrow: &'static str,
+        label: String,
+        target: String,
+    },
+}
+
+/// A visible row of the flattened tree
+#[derive(Debug, Clone)]
+struct Node {
+    /// Stable key for expansion state
+    key: String,
+    kind: NodeKind,
+    depth: usize,
+    /// Tree guide drawn before the node
+    guide: String,
+    has_children: bool,
+    expanded: bool,
+}
+
+/// Mind map screen
+#[derive(Debug)]
+pub struct MindMapScreen {
+    /// Selected row of the flattened tree
+    selected: usize,
+    /// Expand every node by default (otherwise only the board and epics)
+    expand_all: bool,
+    /// Nodes whose expansion differs from the default
+    toggled: HashSet<String>,
+    /// Show card details next to titles
+    show_details: bool,
+    /// Saved layout orientation
+    orientation: MindMapOrientation,
+    /// Typing a filter query
+    filtering: bool,
+    /// Live filter applied to cards
+    filter: LiveFilter,
+    /// Scroll state of the tree
+    list_state: ListState,
+}
+
+impl Default for MindMapScreen {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MindMapScreen {
+    /// Create a mind map with epics expanded
+    pub fn new() -> Self {
+        Self {
+            selected: 0,
+            expand_all: false,
+            toggled: HashSet::new(),
+            show_details: true,
+            orientation: MindMapOrientation::TopDown,
+            filtering: false,
+            filter: LiveFilter::new(),
+            list_state: ListState::default(),
+        }
+    }
+
+    /// Create a screen from saved mind map view settings
+    pub fn from_settings(settings: &ViewSettings) -> Self {
+        let mut screen = Self::new();
+        if let ViewSettings::MindMap {
+            expand_all,
+            show_details,
+            orientation,
+        } = settings
+        {
+            screen.expand_all = *expand_all;
+            screen.show_details = *show_details;
+            screen.orientation = *orientation;
+        }
+        screen
+    }
+
+    /// Current view settings
+    pub fn settings(&self) -> ViewSettings {
+        ViewSettings::MindMap {
+            expand_all: self.expand_all,
+            show_details: self.show_details,
+            orientation: self.orientation,
+        }
+    }
+
+    /// Card filter
+    pub fn filter_mut(&mut self) -> &mut LiveFilter {
+        &mut self.filter
+    }
+
+    /// Number of rows currently shown
+    pub fn visible_nodes(&self, db: &BoardDatabase) -> usize {
+        self.nodes(db).len()
+    }
+
+    /// ID of the selected card, if a card row is selected
+    pub fn selected_card_id(&self, db: &BoardDatabase) -> Option<String> {
+        match self.nodes(db).get(self.selected)?.kind.clone() {
+            NodeKind::Card(id) => Some(id),
+            _ => None,
+        }
+    }
+
+    fn is_expanded(&self, key: &str) -> bool {
+        let default = self.expand_all || key == ROOT_KEY || key.starts_with("epic:");
+        default != self.toggled.contains(key)
+    }
+
+    fn set_expanded(&mut self, key: &str, expanded: bool) {
+        if self.is_expanded(key) != expanded && !self.toggled.remove(key) {
+            self.toggled.insert(key.to_string());
+        }
+    }
+
+    /// Cards passing the filter, grouped by epic ("No epic" last)
+    fn epics<'a>(&self, db: &'a BoardDatabase) -> Vec<(String, Vec<&'a RichTaskCard>)> {
+        let mut epics: BTreeMap<String, Vec<&RichTaskCard>> = BTreeMap::new();
+        let mut loose = Vec::new();
+        for card in db.cards.cards() {
+            if !self.filter.matches(card) {
+                continue;
+            }
+            match epic_of(card) {
+                Some(epic) => epics.entry(epic).or_default().push(card),
+                None => loose.push(card),
+            }
+        }
+        let mut epics: Vec<(String, Vec<&RichTaskCard>)> = epics.into_iter().collect();
+        if !loose.is_empty() {
+            epics.push((NO_EPIC.to_string(), loose));
+        }
+        epics
+    }
+
+    /// Flatten the visible part of the tree
+    fn nodes(&self, db: &BoardDatabase) -> Vec<Node> {
+        let epics = self.epics(db);
+        let root_expanded = self.is_expanded(ROOT_KEY);
+        let mut nodes = vec![Node {
+            key: ROOT_KEY.to_string(),
+            kind: NodeKind::Board,
+            depth: 0,
+            guide: String::new(),
+            has_children: !epics.is_empty(),
+            expanded: root_expanded,
+        }];
+        if !root_expanded {
+            return nodes;
+        }
+
+        for (epic_index, (epic, cards)) in epics.iter().enumerate() {
+            let epic_last = epic_index + 1 == epics.len();
+            let epic_key = format!("epic:{}", epic);
+            let epic_expanded = self.is_expanded(&epic_key);
+            nodes.push(Node {
+                key: epic_key,
+                kind: NodeKind::Epic {
+                    name: epic.clone(),
+                    cards: cards.len(),
+                },
+                depth: 1,
+                guide: branch(epic_last).to_string(),
+                has_children: true,
+                expanded: epic_expanded,
+            });
+            if !epic_expanded {
+                continue;
+            }
+
+            let epic_stem = stem(epic_last);
+            for (card_index, card) in cards.iter().enumerate() {
+                let card_last = card_index + 1 == cards.len();
+                let card_key = format!("card:{}", card.id);
+                let links = db.dependencies.dependencies_for_task(&card.id);
+                let leaves = card.subtasks.len() + links.len();
+                let card_expanded = self.is_expanded(&card_key);
+                nodes.push(Node {
+                    key: card_key,
+                    kind: NodeKind::Card(card.id.clone()),
+                    depth: 2,
+                    guide: format!("{}{}", epic_stem, branch(card_last)),
+                    has_children: leaves > 0,
+                    expanded: card_expanded,
+                });
+                if !card_expanded {
+                    continue;
+                }
+
+                let card_stem = format!("{}{}", epic_stem, stem(card_last));
+                let mut leaf_index = 0;
+                for subtask in &card.subtasks {
+                    leaf_index += 1;
+                    nodes.push(Node {
+                        key: format!("subtask:{}:{}", card.id, subtask.id),
+                        kind: NodeKind::Subtask {
+                            title: subtask.title.clone(),
+                            completed: subtask.completed,
+                        },
+                        depth: 3,
+                        guide: format!("{}{}", card_stem, branch(leaf_index == leaves)),
+                        has_children: false,
+                        expanded: false,
+                    });
+                }
+                for dep in links {
+                    leaf_index += 1;
+                    let (arrow, label, target) = link(dep, &card.id);
+                    let title = db
+                        .cards
+                        .get_card(&target)
+                        .map_or_else(|| target.clone(), |card| card.title.clone());
+                    nodes.push(Node {
+                        key: format!("link:{}:{}", card.id, dep.id),
+                        kind: NodeKind::Link {
+                            arrow,
+                            label: format!("{} {}", label, title),
+                            target,
+                        },
+                        depth: 3,
+                        guide: format!("{}{}", card_stem, branch(leaf_index == leaves)),
+                        has_children: false,
+                        expanded: false,
+                    });
+                }
+            }
+        }
+        nodes
+    }
+
+    // ---- input ------------------------------------------------------------
+
+    /// Handle a key press
+    pub fn handle_key(&mut self, key: KeyEvent, db: &mut BoardDatabase) -> MindMapOutcome {
+        if self.filtering {
+            match key.code {
+                KeyCode::Enter => {
+                    self.filter.commit();
+                    self.filtering = false;
+                }
+                KeyCode::Esc => {
+                    self.filter.clear();
+                    self.filtering = false;
+                }
+                KeyCode::Backspace => self.filter.pop(),
+                KeyCode::Char(c) => self.filter.push(c),
+                _ => {}
+            }
+            self.clamp_selection(db);
+            return MindMapOutcome::None;
+        }
+
+        let nodes = self.nodes(db);
+        let Some(node) = nodes.get(self.selected).cloned() else {
+            self.selected = 0;
+            return MindMapOutcome::None;
+        };
+        let outcome = match key.code {
+            KeyCode::Char('j') | KeyCode::Down => {
+                self.selected = (self.selected + 1).min(nodes.len() - 1);
+                MindMapOutcome::None
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.selected = self.selected.saturating_sub(1);
+                MindMapOutcome::None
+            }
+            KeyCode::Char('g') | KeyCode::Home => {
+                self.selected = 0;
+                MindMapOutcome::None
+            }
+            KeyCode::Char('G') | KeyCode::End => {
+                self.selected = nodes.len() - 1;
+                MindMapOutcome::None
+            }
+            KeyCode::Char('l') | KeyCode::Right => {
+                if node.has_children {
+                    if node.expanded {
+                        self.selected += 1;
+                    } else {
+                        self.set_expanded(&node.key, true);
+                    }
+                }
+                MindMapOutcome::None
+            }
+            KeyCode::Char('h') | KeyCode::Left => {
+                if node.has_children && node.expanded {
+                    self.set_expanded(&node.key, false);
+                } else if let Some(parent) = nodes[..self.selected]
+                    .iter()
+                    .rposition(|n| n.depth < node.depth)
+                {
+                    self.selected = parent;
+                }
+                MindMapOutcome::None
+            }
+            KeyCode::Char(' ') => {
+                if node.has_children {
+                    self.set_expanded(&node.key, !node.expanded);
+                }
+                MindMapOutcome::None
+            }
+            KeyCode::Enter => match &node.kind {
+                NodeKind::Link { target, .. } => self.jump_to_card(db, target),
+                _ if node.has_children => {
+                    self.set_expanded(&node.key, !node.expanded);
+                    MindMapOutcome::None
+                }
+                _ => MindMapOutcome::None,
+            },
+            KeyCode::Char('E') => {
+                self.expand_all = true;
+                self.toggled.clear();
+                MindMapOutcome::None
+            }
+            KeyCode::Char('C') => {
+                self.expand_all = false;
+                self.toggled = self
+                    .epics(db)
+                    .into_iter()
+                    .map(|(epic, _)| format!("epic:{}", epic))
+                    .collect();
+                self.selected = 0;
+                MindMapOutcome::None
+            }
+            KeyCode::Char('d') => {
+                self.show_details = !self.show_details;
+                MindMapOutcome::None
+            }
+            KeyCode::Char('/') => {
+                self.filtering = true;
+                MindMapOutcome::None
+            }
+            KeyCode::Esc if self.filter.is_active() => {
+                self.filter.clear();
+                MindMapOutcome::Message("Filter cleared".to_string())
+            }
+            KeyCode::Esc | KeyCode::Char('q') => MindMapOutcome::Close,
+            _ => MindMapOutcome::None,
+        };
+        self.clamp_selection(db);
+        outcome
+    }
+
+    /// Select a card's node, expanding its epic if needed
+    fn jump_to_card(&mut self, db: &BoardDatabase, card_id: &str) -> MindMapOutcome {
+        let Some(card) = db.cards.get_card(card_id) else {
+            return MindMapOutcome::Message(format!("Card {} no longer exists", card_id));
+        };
+        if !self.filter.matches(card) {
+            return MindMapOutcome::Message(format!("{} is hidden by the filter", card.title));
+        }
+        let epic = epic_of(card).unwrap_or_else(|| NO_EPIC.to_string());
+        self.set_expanded(ROOT_KEY, true);
+        self.set_expanded(&format!("epic:{}", epic), true);
+        let key = format!("card:{}", card_id);
+        if let Some(index) = self.nodes(db).iter().position(|node| node.key == key) {
+            self.selected = index;
+        }
+        MindMapOutcome::None
+    }
+
+    fn clamp_selection(&mut self, db: &BoardDatabase) {
+        self.selected = self.selected.min(self.visible_nodes(db).saturating_sub(1));
+    }
+
+    // ---- rendering --------------------------------------------------------
+
+    /// Render the tree
+    pub fn render(&mut self, frame: &mut Frame, area: Rect, db: &BoardDatabase) {
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(0), Constraint::Length(1)])
+            .split(area);
+
+        let nodes = self.nodes(db);
+        self.selected = self.selected.min(nodes.len().saturating_sub(1));
+        let items: Vec<ListItem> = nodes
+            .iter()
+            .map(|node| ListItem::new(self.node_line(node, db)))
+            .collect();
+
+        let mut title = " Mind map ".to_string();
+        if self.filter.is_active() {
+            title = format!(" Mind map · filter: {} ", self.filter.query());
+        }
+        let list = List::new(items)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Cyan))
+                    .title(title),
+            )
+            .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+        self.list_state.select(Some(self.selected));
+        frame.render_stateful_widget(list, rows[0], &mut self.list_state);
+
+        let hints = if self.filtering {
+            Line::from(Span::styled(
+                format!(" / {}█", self.filter.query()),
+                Style::default().fg(Color::Yellow),
+            ))
+        } else {
+            Line::from(Span::styled(
+                "j/k move  l expand  h collapse  Space toggle  E/C expand/collapse all  Enter follow link  d details  / filter  Esc back",
+                Style::default().fg(Color::DarkGray),
+            ))
+        };
+        frame.render_widget(Paragraph::new(hints), rows[1]);
+    }
+
+    fn node_line(&self, node: &Node, db: &BoardDatabase) -> Line<'static> {
+        let marker = match (node.has_children, node.expanded) {
+            (false, _) => "  ",
+            (true, true) => "▾ ",
+            (true, false) => "▸ ",
+        };
+        let mut spans = vec![
+            Span::styled(node.guide.clone(), Style::default().fg(Color::DarkGray)),
+            Span::raw(marker),
+        ];
+        match &node.kind {
+            NodeKind::Board => {
+                let name = db
+                    .boards
+                    .boards()
+                    .first()
+                    .map_or_else(|| "Board".to_string(), |board| board.name.clone());
+                spans.push(Span::styled(
+                    name,
+                    Style::default()
+                        .fg(Color::Cyan)
+                        .add_modifier(Modifier::BOLD),
+                ));
+            }
+            NodeKind::Epic { name, cards } => {
+                spans.push(Span::styled(
+                    format!("◆ {}", name),
+                    Style::default()
+                        .fg(Color::Magenta)
+                        .add_modifier(Modifier::BOLD),
+                ));
+                spans.push(Span::styled(
+                    format!(" ({})", cards),
+                    Style::default().fg(Color::DarkGray),
+                ));
+            }
+            NodeKind::Card(card_id) => {
+                let Some(card) = db.cards.get_card(card_id) else {
+                    return Line::from(spans);
+                };
+                spans.push(Span::styled(
+                    format!("{} ", priority_label(card.priority)),
+                    Style::default().fg(priority_color(card.priority)),
+                ));
+                spans.push(Span::raw(card.title.clone()));
+                if self.show_details {
+                    spans.push(Span::styled(
+                        card_details(card),
+                        Style::default().fg(Color::DarkGray),
+                    ));
+                }
+            }
+            NodeKind::Subtask { title, completed } => {
+                let (check, style) = if *completed {
+                    ("☑ ", Style::default().fg(Color::DarkGray))
+                } else {
+                    ("☐ ", Style::default())
+                };
+                spans.push(Span::styled(format!("{}{}", check, title), style));
+            }
+            NodeKind::Link { arrow, label, .. } => {
+                spans.push(Span::styled(
+                    format!("{} {}", arrow, label),
+                    Style::default().fg(Color::Blue),
+                ));
+            }
+        }
+        Line::from(spans)
+    }
+}
+
+/// Epic name of a card, from its `epic` custom field
+fn epic_of(card: &RichTaskCard) -> Option<String> {
+    let epic = match &card.custom_fields.get(EPIC_FIELD)?.value {
+        CustomFieldType::Text(text) => text.trim().to_string(),
+        CustomFieldType::Dropdown(selected, _) => selected.trim().to_string(),
+        _ => return None,
+    };
+    (!epic.is_empty()).then_some(epic)
+}
+
+/// Arrow, label and other card of a dependency, as seen from `card_id`
+fn link(dep: &Dependency, card_id: &str) -> (&'static str, &'static str, String) {
+    let outgoing = dep.from_task == card_id;
+    let other = if outgoing {
+        dep.to_task.clone()
+    } else {
+        dep.from_task.clone()
+    };
+    let (arrow, label) = match (dep.dependency_type, outgoing) {
+        (DependencyType::Blocks, true) | (DependencyType::BlockedBy, false) => ("→", "blocks"),
+        (DependencyType::Blocks, false) | (DependencyType::BlockedBy, true) => ("←", "blocked by"),
+        (DependencyType::RelatesTo, _) => ("~", "relates to"),
+        (DependencyType::Duplicates, true) => ("≡", "duplicates"),
+        (DependencyType::Duplicates, false) => ("≡", "duplicated by"),
+    };
+    (arrow, label, other)
+}
+
+/// Status, subtask count, due date and assignees of a card
+fn card_details(card: &RichTaskCard) -> String {
+    let mut details = format!("  [{}]", card.status);
+    if !card.subtasks.is_empty() {
+        details.push_str(&format!(
+            " {}/{} subtasks",
+            card.completed_subtasks(),
+            card.total_subtasks()
+        ));
+    }
+    if let Some(due) = card.due_date {
+        details.push_str(&format!(" due {}", due.format("%Y-%m-%d")));
+    }
+    for assignee in &card.assignees {
+        details.push_str(&format!(" @{}", assignee.display_name));
+    }
+    details
+}
+
+/// Guide before a node: a tee, or an elbow for the last child
+fn branch(last: bool) -> &'static str {
+    if last { "└─ " } else { "├─ " }
+}
+
+/// Guide continuing below a node to its children
+fn stem(last: bool) -> &'static str {
+    if last { "   " } else { "│  " }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::rich_task_cards::{CustomField, Priority};
+    use chrono::Utc;
+    use crossterm::event::KeyModifiers;
+    use ratatui::{Terminal, backend::TestBackend};
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn add_card(db: &mut BoardDatabase, title: &str, epic: Option<&str>) -> String {
+        let id = db.cards.create_card(
+            title.to_string(),
+            String::new(),
+            "To Do".to_string(),
+            Priority::Medium,
+            "me".to_string(),
+        );
+        if let Some(epic) = epic {
+            db.cards
+                .get_card_mut(&id)
+                .unwrap()
+                .set_custom_field(CustomField {
+                    name: EPIC_FIELD.to_string(),
+                    value: CustomFieldType::Text(epic.to_string()),
+                    created_at: Utc::now(),
+                });
+        }
+        id
+    }
+
+    fn render(screen: &mut MindMapScreen, db: &BoardDatabase) -> String {
+        let mut terminal = Terminal::new(TestBackend::new(100, 20)).unwrap();
+        terminal.draw(|f| screen.render(f, f.area(), db)).unwrap();
+        terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|c| c.symbol())
+            .collect()
+    }
+
+    fn sample() -> (BoardDatabase, String, String) {
+        let mut db = BoardDatabase::default();
+        let login = add_card(&mut db, "Login", Some("Auth"));
+        let signup = add_card(&mut db, "Signup", Some("Auth"));
+        add_card(&mut db, "Cleanup", None);
+        db.cards.create_subtask(&login, "Form".to_string()).unwrap();
+        db.dependencies
+            .create_dependency(
+                login.clone(),
+                signup.clone(),
+                DependencyType::Blocks,
+                "me".to_string(),
+            )
+            .unwrap();
+        (db, login, signup)
+    }
+
+    #[test]
+    fn test_tree_groups_cards_by_epic() {
+        let (db, _, _) = sample();
+        let mut screen = MindMapScreen::new();
+        let output = render(&mut screen, &db);
+        assert!(output.contains("├─ ▾ ◆ Auth (2)"));
+        assert!(output.contains("│  ├─ ▸ P2 Login"));
+        assert!(output.contains("│  └─ ▸ P2 Signup"));
+        assert!(output.contains("└─ ▾ ◆ No epic (1)"));
+        assert!(output.contains("   └─   P2 Cleanup  [To Do]"));
+    }
+
+    #[test]
+    fn test_expand_card_shows_subtasks_and_links() {
+        let (mut db, _, _) = sample();
+        let mut screen = MindMapScreen::new();
+        screen.handle_key(key(KeyCode::Char('j')), &mut db);
+        screen.handle_key(key(KeyCode::Char('j')), &mut db);
+        screen.handle_key(key(KeyCode::Char('l')), &mut db);
+
+        let output = render(&mut screen, &db);
+        assert!(output.contains("│  │  ├─   ☐ Form"));
+        assert!(output.contains("│  │  └─   → blocks Signup"));
+        assert!(output.contains("0/1 subtasks"));
+
+        // Collapse again, then step out to the epic
+        screen.handle_key(key(KeyCode::Char('h')), &mut db);
+        assert_eq!(screen.visible_nodes(&db), 6);
+        screen.handle_key(key(KeyCode::Char('h')), &mut db);
+        assert_eq!(screen.selected, 1);
+    }
+
+    #[test]
+    fn test_follow_link_to_card() {
+        let (mut db, _, signup) = sample();
+        let mut screen = MindMapScreen::new();
+        screen.handle_key(key(KeyCode::Char('E')), &mut db);
+        // board, Auth, Login, Form, link
+        for _ in 0..4 {
+            screen.handle_key(key(KeyCode::Char('j')), &mut db);
+        }
+        screen.handle_key(key(KeyCode::Enter), &mut db);
+        assert_eq!(screen.selected_card_id(&db), Some(signup));
+    }
+
+    #[test]
+    fn test_collapse_all_and_filter() {
+        let (mut db, _, _) = sample();
+        let mut screen = MindMapScreen::new();
+        screen.handle_key(key(KeyCode::Char('C')), &mut db);
+        assert_eq!(screen.visible_nodes(&db), 3);
+        screen.handle_key(key(KeyCode::Char('E')), &mut db);
+        assert_eq!(screen.visible_nodes(&db), 9);
+
+        screen.handle_key(key(KeyCode::Char('/')), &mut db);
+        for c in "cleanup".chars() {
+            screen.handle_key(key(KeyCode::Char(c)), &mut db);
+        }
+        screen.handle_key(key(KeyCode::Enter), &mut db);
+        let output = render(&mut screen, &db);
+        assert!(!output.contains("Auth"));
+        assert!(output.contains("filter: cleanup"));
+        assert!(matches!(
+            screen.settings(),
+            ViewSettings::MindMap {
+                expand_all: true,
+                ..
+            }
+        ));
+    }
+}
@@ -16,6 +16,13 @@
 //! - [`welcome`]: Welcome splash screen with logo and quick tips
 //! - [`main_screen`]: Main command interface with input and status
 //! - [`evaluation`]: Real-time evaluation progress display
+//! - [`git`]: Integrated git workflow (staging, commits, branches, graph, conflicts)
+//! - [`editor`]: Embedded modal editor for workspace files
+//! - [`kanban`]: Kanban board of task cards
+//! - [`timeline`]: Gantt timeline of the card schedule
+//! - [`calendar`]: Month, week and day calendar of card due dates
+//! - [`table_view`]: Sortable, editable table of cards
+//! - [`mind_map`]: Collapsible tree of epics, cards, subtasks and dependencies
 //!
 //! # Examples
 //!
@@ -41,12 +48,26 @@
 //! screen.render(area, &mut buf);
 //! ```
 
+pub mod calendar;
+pub mod editor;
 pub mod evaluation;
+pub mod git;
+pub mod kanban;
 pub mod main_screen;
+pub mod mind_map;
 pub mod results;
+pub mod table_view;
+pub mod timeline;
 pub mod welcome;
 
+pub use calendar::{CalendarOutcome, CalendarScreen};
+pub use editor::{EditorMode, EditorOutcome, EditorScreen};
 pub use evaluation::EvaluationScreen;
+pub use git::{GitPanel, GitRefresh, GitScreen};
+pub use kanban::{KanbanMode, KanbanOutcome, KanbanScreen};
 pub use main_screen::MainScreen;
+pub use mind_map::{MindMapOutcome, MindMapScreen};
 pub use results::ResultsScreen;
+pub use table_view::{TableOutcome, TableViewScreen};
+pub use timeline::{TimelineOutcome, TimelineScreen};
 pub use welcome::WelcomeScreen;
@@ -0,0 +1,955 @@
+//! Table screen - Cards as sortable, editable rows
+//!
+//! Lists the cards of the app's [`BoardDatabase`] in a [`DataTable`], one row
+//! per card. The configured columns come first, followed by a column for
+//! every custom field used on the board. Any column can be sorted, and cells
+//! can be edited in place.
+//!
+//! # Architecture
+//!
+//! The screen keeps view state only: the selected column, the sort, the edit
+//! buffer and the live filter the app lends it. Rows are rebuilt from the
+//! board before every key and render, so edits made elsewhere show up
+//! immediately. Settings round-trip through [`ViewSettings::Table`].
+//!
+//! Edits are parsed per column: priorities accept `P0`-`P3` or a name, dates
+//! use `YYYY-MM-DD`, effort takes `3h`, `2d` or `5sp`, and custom fields keep
+//! the type they already have (or that other cards use for the same field).
+//! Clearing a date, effort or custom field removes it.
+//!
+//! # Examples
+//!
+//! ```
+//! use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+//! use toad::infrastructure::board_store::BoardDatabase;
+//! use toad::infrastructure::Priority;
+//! use toad::ui::screens::table_view::TableViewScreen;
+//!
+//! let mut db = BoardDatabase::default();
+//! db.cards.create_card(
+//!     "Write docs".to_string(),
+//!     String::new(),
+//!     "To Do".to_string(),
+//!     Priority::Low,
+//!     "me".to_string(),
+//! );
+//! let mut screen = TableViewScreen::new();
+//! screen.handle_key(KeyEvent::new(KeyCode::Char('l'), KeyModifiers::NONE), &mut db);
+//! assert_eq!(screen.selected_column(&db), Some("status".to_string()));
+//! ```
+
+use crate::infrastructure::board_store::BoardDatabase;
+use crate::infrastructure::filtering_search::LiveFilter;
+use crate::infrastructure::multiple_views::{SortOrder, ViewSettings, ViewType};
+use crate::infrastructure::rich_task_cards::{
+    Assignee, CustomField, CustomFieldType, EffortEstimate, Priority, RichTaskCard,
+};
+use crate::ui::screens::kanban::priority_label;
+use crate::ui::widgets::core::table::{DataTable, TableColumn};
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+};
+use std::cmp::Ordering;
+use std::collections::BTreeSet;
+
+/// Built-in columns, by settings key
+const BUILTIN_COLUMNS: [(&str, &str); 10] = [
+    ("title", "Title"),
+    ("status", "Status"),
+    ("priority", "Priority"),
+    ("assignee", "Assignee"),
+    ("due_date", "Due"),
+    ("start_date", "Start"),
+    ("progress", "Progress"),
+    ("effort", "Effort"),
+    ("tags", "Tags"),
+    ("created_at", "Created"),
+];
+
+/// Columns that can't be edited in place
+const READ_ONLY_COLUMNS: [&str; 2] = ["tags", "created_at"];
+
+/// Narrowest and widest column widths
+const MIN_WIDTH: u16 = 4;
+const MAX_WIDTH: u16 = 32;
+
+/// Result of handling an input event, for the app to act on
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TableOutcome {
+    /// Nothing for the app to do
+    None,
+    /// A cell was edited and the board should be saved
+    Edited(String),
+    /// Informational message
+    Message(String),
+    /// An operation failed
+    Error(String),
+    /// Leave the table screen
+    Close,
+}
+
+/// Input state
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum TableMode {
+    /// Moving between cells
+    Normal,
+    /// Typing a filter query
+    Filter,
+    /// Editing the selected cell
+    Edit(String),
+}
+
+/// Table screen
+#[derive(Debug)]
+pub struct TableViewScreen {
+    /// Table widget holding the rendered rows and row selection
+    table: DataTable,
+    /// Card IDs in row order, as of the last refresh
+    row_ids: Vec<String>,
+    /// Configured columns (settings keys)
+    visible_columns: Vec<String>,
+    /// Allow editing cells
+    allow_inline_edit: bool,
+    /// Show a row number column
+    show_row_numbers: bool,
+    /// Selected column (index into all columns)
+    column: usize,
+    /// Sorted column and direction
+    sort: Option<(String, SortOrder)>,
+    /// Input state
+    mode: TableMode,
+    /// Live filter applied to cards
+    filter: LiveFilter,
+}
+
+impl Default for TableViewScreen {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TableViewScreen {
+    /// Create a table with the default columns
+    pub fn new() -> Self {
+        Self::from_settings(&ViewSettings::default_for_type(ViewType::Table))
+    }
+
+    /// Create a screen from saved table view settings
+    pub fn from_settings(settings: &ViewSettings) -> Self {
+        let (visible_columns, allow_inline_edit, show_row_numbers) = match settings {
+            ViewSettings::Table {
+                visible_columns,
+                allow_inline_edit,
+                show_row_numbers,
+            } => (
+                visible_columns.clone(),
+                *allow_inline_edit,
+                *show_row_numbers,
+            ),
+            _ => (vec!["title".to_string()], true, true),
+        };
+        Self {
+            table: DataTable::new("Table", Vec::new()),
+            row_ids: Vec::new(),
+            visible_columns,
+            allow_inline_edit,
+            show_row_numbers,
+            column: 0,
+            sort: None,
+            mode: TableMode::Normal,
+            filter: LiveFilter::new(),
+        }
+    }
+
+    /// Current view settings
+    pub fn settings(&self) -> ViewSettings {
+        ViewSettings::Table {
+            visible_columns: self.visible_columns.clone(),
+            allow_inline_edit: self.allow_inline_edit,
+            show_row_numbers: self.show_row_numbers,
+        }
+    }
+
+    /// Card filter
+    pub fn filter_mut(&mut self) -> &mut LiveFilter {
+        &mut self.filter
+    }
+
+    /// Whether a cell is being edited
+    pub fn is_editing(&self) -> bool {
+        matches!(self.mode, TableMode::Edit(_))
+    }
+
+    /// All columns: the configured ones, then custom fields by name
+    pub fn columns(&self, db: &BoardDatabase) -> Vec<String> {
+        let mut columns = self.visible_columns.clone();
+        let custom: BTreeSet<&String> = db
+            .cards
+            .cards()
+            .into_iter()
+            .flat_map(|card| card.custom_fields.keys())
+            .collect();
+        for name in custom {
+            if !columns.contains(name) {
+                columns.push(name.clone());
+            }
+        }
+        columns
+    }
+
+    /// Key of the selected column
+    pub fn selected_column(&self, db: &BoardDatabase) -> Option<String> {
+        self.columns(db).get(self.column).cloned()
+    }
+
+    /// ID of the card on the selected row
+    pub fn selected_card_id(&self) -> Option<&str> {
+        self.table
+            .selected()
+            .and_then(|row| self.row_ids.get(row))
+            .map(String::as_str)
+    }
+
+    /// Card IDs in displayed order
+    pub fn row_ids(&self) -> &[String] {
+        &self.row_ids
+    }
+
+    /// Rebuild the rows from the board, keeping the selected card selected
+    fn refresh(&mut self, db: &BoardDatabase) {
+        let selected = self.selected_card_id().map(str::to_string);
+        let columns = self.columns(db);
+        self.column = self.column.min(columns.len().saturating_sub(1));
+
+        let mut cards: Vec<&RichTaskCard> = db
+            .cards
+            .cards()
+            .into_iter()
+            .filter(|card| self.filter.matches(card))
+            .collect();
+        if let Some((column, order)) = &self.sort {
+            cards.sort_by(|a, b| compare_cells(&sort_key(a, column), &sort_key(b, column), *order));
+        }
+
+        let rows: Vec<Vec<String>> = cards
+            .iter()
+            .enumerate()
+            .map(|(index, card)| {
+                let mut row = Vec::with_capacity(columns.len() + 1);
+                if self.show_row_numbers {
+                    row.push((index + 1).to_string());
+                }
+                row.extend(columns.iter().map(|column| cell_text(card, column)));
+                row
+            })
+            .collect();
+
+        let mut table_columns = Vec::with_capacity(columns.len() + 1);
+        if self.show_row_numbers {
+            table_columns.push(TableColumn::new(
+                "#",
+                (rows.len().to_string().len() as u16).max(2),
+            ));
+        }
+        for (index, column) in columns.iter().enumerate() {
+            let mut header = column_header(column);
+            if let Some((sorted, order)) = &self.sort
+                && sorted == column
+            {
+                header.push_str(match order {
+                    SortOrder::Ascending => " ▲",
+                    SortOrder::Descending => " ▼",
+                });
+            }
+            if index == self.column {
+                header = format!("[{}]", header);
+            }
+            let offset = usize::from(self.show_row_numbers);
+            let width = rows
+                .iter()
+                .map(|row| row[index + offset].chars().count())
+                .chain(std::iter::once(header.chars().count()))
+                .max()
+                .unwrap_or_default() as u16;
+            table_columns.push(TableColumn::new(header, width.clamp(MIN_WIDTH, MAX_WIDTH)));
+        }
+
+        self.row_ids = cards.iter().map(|card| card.id.clone()).collect();
+        self.table.set_columns(table_columns);
+        self.table.set_rows(rows);
+        let row = selected
+            .and_then(|id| self.row_ids.iter().position(|row_id| *row_id == id))
+            .or(self.table.selected())
+            .unwrap_or(0);
+        self.table.select(row);
+
+        let mut title = format!("Table · {} cards", self.row_ids.len());
+        if self.filter.is_active() {
+            title.push_str(&format!(" · filter: {}", self.filter.query()));
+        }
+        self.table.set_title(title);
+    }
+
+    // ---- input ------------------------------------------------------------
+
+    /// Handle a key press
+    pub fn handle_key(&mut self, key: KeyEvent, db: &mut BoardDatabase) -> TableOutcome {
+        self.refresh(db);
+        let outcome = match self.mode.clone() {
+            TableMode::Normal => self.handle_normal_key(key, db),
+            TableMode::Filter => {
+                match key.code {
+                    KeyCode::Enter => {
+                        self.filter.commit();
+                        self.mode = TableMode::Normal;
+                    }
+                    KeyCode::Esc => {
+                        self.filter.clear();
+                        self.mode = TableMode::Normal;
+                    }
+                    KeyCode::Backspace => self.filter.pop(),
+                    KeyCode::Char(c) => self.filter.push(c),
+                    _ => {}
+                }
+                TableOutcome::None
+            }
+            TableMode::Edit(mut buffer) => match key.code {
+                KeyCode::Enter => {
+                    self.mode = TableMode::Normal;
+                    self.commit_edit(db, &buffer)
+                }
+                KeyCode::Esc => {
+                    self.mode = TableMode::Normal;
+                    TableOutcome::None
+                }
+                KeyCode::Backspace => {
+                    buffer.pop();
+                    self.mode = TableMode::Edit(buffer);
+                    TableOutcome::None
+                }
+                KeyCode::Char(c) => {
+                    buffer.push(c);
+                    self.mode = TableMode::Edit(buffer);
+                    TableOutcome::None
+                }
+                _ => TableOutcome::None,
+            },
+        };
+        self.refresh(db);
+        outcome
+    }
+
+    fn handle_normal_key(&mut self, key: KeyEvent, db: &BoardDatabase) -> TableOutcome {
+        let column_count = self.columns(db).len();
+        match key.code {
+            KeyCode::Char('j') | KeyCode::Down => self.table.select_next(),
+            KeyCode::Char('k') | KeyCode::Up => self.table.select_previous(),
+            KeyCode::Char('g') | KeyCode::Home => self.table.select_first(),
+            KeyCode::Char('G') | KeyCode::End => self.table.select_last(),
+            KeyCode::Char('h') | KeyCode::Left => self.column = self.column.saturating_sub(1),
+            KeyCode::Char('l') | KeyCode::Right => {
+                self.column = (self.column + 1).min(column_count.saturating_sub(1));
+            }
+            KeyCode::Char('s') => {
+                let Some(column) = self.selected_column(db) else {
+                    return TableOutcome::None;
+                };
+                self.sort = match self.sort.take() {
+                    Some((sorted, SortOrder::Ascending)) if sorted == column => {
+                        Some((column, SortOrder::Descending))
+                    }
+                    _ => Some((column, SortOrder::Ascending)),
+                };
+            }
+            KeyCode::Char('S') => self.sort = None,
+            KeyCode::Char('#') => self.show_row_numbers = !self.show_row_numbers,
+            KeyCode::Enter | KeyCode::Char('e') => return self.start_edit(db),
+            KeyCode::Char('/') => self.mode = TableMode::Filter,
+            KeyCode::Esc if self.filter.is_active() => {
+                self.filter.clear();
+                return TableOutcome::Message("Filter cleared".to_string());
+            }
+            KeyCode::Esc | KeyCode::Char('q') => return TableOutcome::Close,
+            _ => {}
+        }
+        TableOutcome::None
+    }
+
+    fn start_edit(&mut self, db: &BoardDatabase) -> TableOutcome {
+        if !self.allow_inline_edit {
+            return TableOutcome::Message("Editing is off for this view".to_string());
+        }
+        let (Some(column), Some(card)) = (
+            self.selected_column(db),
+            self.selected_card_id().and_then(|id| db.cards.get_card(id)),
+        ) else {
+            return TableOutcome::None;
+        };
+        if READ_ONLY_COLUMNS.contains(&column.as_str()) {
+            return TableOutcome::Message(format!(
+                "{} can't be edited here",
+                column_header(&column)
+            ));
+        }
+        self.mode = TableMode::Edit(cell_text(card, &column).trim_end_matches('%').to_string());
+        TableOutcome::None
+    }
+
+    fn commit_edit(&mut self, db: &mut BoardDatabase, value: &str) -> TableOutcome {
+        let (Some(column), Some(card_id)) = (
+            self.selected_column(db),
+            self.selected_card_id().map(str::to_string),
+        ) else {
+            return TableOutcome::None;
+        };
+        match apply_edit(db, &card_id, &column, value.trim()) {
+            Ok(message) => TableOutcome::Edited(message),
+            Err(err) => TableOutcome::Error(err),
+        }
+    }
+
+    // ---- rendering --------------------------------------------------------
+
+    /// Render the table
+    pub fn render(&mut self, frame: &mut Frame, area: Rect, db: &BoardDatabase) {
+        self.refresh(db);
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(0), Constraint::Length(1)])
+            .split(area);
+        self.table.render(frame, rows[0]);
+
+        let line = match &self.mode {
+            TableMode::Edit(buffer) => Line::from(vec![
+                Span::styled(
+                    format!(
+                        " {}: ",
+                        self.selected_column(db)
+                            .as_deref()
+                            .map(column_header)
+                            .unwrap_or_default()
+                    ),
+                    Style::default().fg(Color::Yellow),
+                ),
+                Span::raw(format!("{}█", buffer)),
+                Span::styled(
+                    "  Enter save  Esc cancel",
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ]),
+            TableMode::Filter => Line::from(Span::styled(
+                format!(" / {}█", self.filter.query()),
+                Style::default().fg(Color::Yellow),
+            )),
+            TableMode::Normal => Line::from(Span::styled(
+                "j/k row  h/l column  s sort  S unsort  e edit  # row numbers  / filter  Esc back",
+                Style::default().fg(Color::DarkGray),
+            )),
+        };
+        frame.render_widget(Paragraph::new(line), rows[1]);
+    }
+}
+
+/// Header text of a column
+fn column_header(column: &str) -> String {
+    BUILTIN_COLUMNS
+        .iter()
+        .find(|(key, _)| *key == column)
+        .map(|(_, header)| header.to_string())
+        .unwrap_or_else(|| column.to_string())
+}
+
+/// Display text of a card's cell
+fn cell_text(card: &RichTaskCard, column: &str) -> String {
+    match column {
+        "title" => card.title.clone(),
+        "status" => card.status.clone(),
+        "priority" => priority_label(card.priority),
+        "assignee" => card
+            .assignees
+            .iter()
+            .map(|a| a.display_name.as_str())
+            .collect::<Vec<_>>()
+            .join(", "),
+        "due_date" => format_date(card.due_date),
+        "start_date" => format_date(card.start_date),
+        "progress" => format!("{}%", card.progress),
+        "effort" => card
+            .effort_estimate
+            .as_ref()
+            .map(format_effort)
+            .unwrap_or_default(),
+        "tags" => card
+            .tags
+            .iter()
+            .map(|t| t.name.as_str())
+            .collect::<Vec<_>>()
+            .join(", "),
+        "created_at" => format_date(Some(card.created_at)),
+        name => card
+            .custom_fields
+            .get(name)
+            .map(|field| match &field.value {
+                CustomFieldType::Text(text) => text.clone(),
+                CustomFieldType::Number(number) => number.to_string(),
+                CustomFieldType::Dropdown(selected, _) => selected.clone(),
+                CustomFieldType::Date(date) => format_date(Some(*date)),
+            })
+            .unwrap_or_default(),
+    }
+}
+
+fn format_date(date: Option<DateTime<Utc>>) -> String {
+    date.map(|date| date.format("%Y-%m-%d").to_string())
+        .unwrap_or_default()
+}
+
+fn format_effort(effort: &EffortEstimate) -> String {
+    match effort {
+        EffortEstimate::StoryPoints(points) => format!("{}sp", points),
+        EffortEstimate::Hours(hours) => format!("{}h", hours),
+        EffortEstimate::Days(days) => format!("{}d", days),
+    }
+}
+
+/// Typed value used to sort a column
+#[derive(Debug, PartialEq)]
+enum SortKey {
+    Number(f64),
+    Text(String),
+    Missing,
+}
+
+fn sort_key(card: &RichTaskCard, column: &str) -> SortKey {
+    let timestamp = |date: Option<DateTime<Utc>>| {
+        date.map_or(SortKey::Missing, |date| {
+            SortKey::Number(date.timestamp() as f64)
+        })
+    };
+    match column {
+        "priority" => SortKey::Number(card.priority.to_value() as f64),
+        "due_date" => timestamp(card.due_date),
+        "start_date" => timestamp(card.start_date),
+        "created_at" => timestamp(Some(card.created_at)),
+        "progress" => SortKey::Number(card.progress as f64),
+        "effort" => card
+            .effort_estimate
+            .as_ref()
+            .map_or(SortKey::Missing, |effort| {
+                SortKey::Number(effort.to_hours() as f64)
+            }),
+        name => match card.custom_fields.get(name).map(|field| &field.value) {
+            Some(CustomFieldType::Number(number)) => SortKey::Number(*number),
+            Some(CustomFieldType::Date(date)) => timestamp(Some(*date)),
+            _ => {
+                let text = cell_text(card, column);
+                if text.is_empty() {
+                    SortKey::Missing
+                } else {
+                    SortKey::Text(text.to_lowercase())
+                }
+            }
+        },
+    }
+}
+
+/// Compare two sort keys; empty cells sort last in either direction
+fn compare_cells(a: &SortKey, b: &SortKey, order: SortOrder) -> Ordering {
+    let ordering = match (a, b) {
+        (SortKey::Missing, SortKey::Missing) => return Ordering::Equal,
+        (SortKey::Missing, _) => return Ordering::Greater,
+        (_, SortKey::Missing) => return Ordering::Less,
+        (SortKey::Number(a), SortKey::Number(b)) => a.total_cmp(b),
+        (SortKey::Text(a), SortKey::Text(b)) => a.cmp(b),
+        (SortKey::Number(_), SortKey::Text(_)) => Ordering::Less,
+        (SortKey::Text(_), SortKey::Number(_)) => Ordering::Greater,
+    };
+    match order {
+        SortOrder::Ascending => ordering,
+        SortOrder::Descending => ordering.reverse(),
+    }
+}
+
+/// Apply an edited cell value to a card
+///
+/// Returns a message describing the change.
+fn apply_edit(
+    db: &mut BoardDatabase,
+    card_id: &str,
+    column: &str,
+    value: &str,
+) -> Result<String, String> {
+    if column == "status" {
+        let warning = db.move_card_to_named(card_id, value)?;
+        return Ok(warning.unwrap_or_else(|| format!("Moved to {}", value)));
+    }
+    let inferred = infer_field_type(db, card_id, column);
+    let card = db
+        .cards
+        .get_card_mut(card_id)
+        .ok_or_else(|| format!("Card {} not found", card_id))?;
+    let header = column_header(column);
+
+    match column {
+        "title" => {
+            if value.is_empty() {
+                return Err("Title can't be empty".to_string());
+            }
+            card.title = value.to_string();
+        }
+        "priority" => card.priority = parse_priority(value)?,
+        "assignee" => {
+            card.assignees = value
+                .split(',')
+                .map(str::trim)
+                .filter(|name| !name.is_empty())
+                .map(|name| Assignee {
+                    user_id: name.to_lowercase(),
+                    display_name: name.to_string(),
+                    avatar: None,
+                    assigned_at: Utc::now(),
+                })
+                .collect();
+        }
+        "due_date" => card.due_date = parse_date(value, card.due_date)?,
+        "start_date" => card.start_date = parse_date(value, card.start_date)?,
+        "progress" => {
+            card.progress = value
+                .trim_end_matches('%')
+                .parse::<u8>()
+                .ok()
+                .filter(|progress| *progress <= 100)
+                .ok_or_else(|| "Progress must be 0-100".to_string())?;
+        }
+        "effort" => card.effort_estimate = parse_effort(value)?,
+        "tags" | "created_at" => return Err(format!("{} can't be edited here", header)),
+        name => {
+            if value.is_empty() {
+                card.remove_custom_field(name);
+                return Ok(format!("Cleared {}", name));
+            }
+            let existing = card
+                .custom_fields
+                .get(name)
+                .map(|field| field.value.clone())
+                .or(inferred);
+            let value = parse_custom_value(value, existing)?;
+            card.set_custom_field(CustomField {
+                name: name.to_string(),
+                value,
+                created_at: Utc::now(),
+            });
+            return Ok(format!("Set {}", name));
+        }
+    }
+    card.updated_at = Utc::now();
+    Ok(format!("Updated {}", header.to_lowercase()))
+}
+
+/// Type of a custom field as used on other cards
+fn infer_field_type(db: &BoardDatabase, card_id: &str, name: &str) -> Option<CustomFieldType> {
+    db.cards
+        .cards()
+        .into_iter()
+        .filter(|card| card.id != card_id)
+        .find_map(|card| card.custom_fields.get(name))
+        .map(|field| field.value.clone())
+}
+
+fn parse_priority(value: &str) -> Result<Priority, String> {
+    match value.to_lowercase().as_str() {
+        "p0" | "0" | "critical" => Ok(Priority::Critical),
+        "p1" | "1" | "high" => Ok(Priority::High),
+        "p2" | "2" | "medium" => Ok(Priority::Medium),
+        "p3" | "3" | "low" => Ok(Priority::Low),
+        _ => Err(format!("Unknown priority: {} (use P0-P3)", value)),
+    }
+}
+
+/// Parse `YYYY-MM-DD`, keeping the time of day of the previous value
+fn parse_date(
+    value: &str,
+    previous: Option<DateTime<Utc>>,
+) -> Result<Option<DateTime<Utc>>, String> {
+    if value.is_empty() {
+        return Ok(None);
+    }
+    let date = NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .map_err(|_| format!("Invalid date: {} (use YYYY-MM-DD)", value))?;
+    let time = previous.map_or(NaiveTime::MIN, |previous| previous.time());
+    Ok(Some(date.and_time(time).and_utc()))
+}
+
+fn parse_effort(value: &str) -> Result<Option<EffortEstimate>, String> {
+    if value.is_empty() {
+        return Ok(None);
+    }
+    let invalid = || format!("Invalid effort: {} (use 3h, 2d or 5sp)", value);
+    let value = value.to_lowercase();
+    if let Some(points) = value.strip_suffix("sp") {
+        return points
+            .trim()
+            .parse()
+            .map(|points| Some(EffortEstimate::StoryPoints(points)))
+            .map_err(|_| invalid());
+    }
+    if let Some(hours) = value.strip_suffix('h') {
+        return hours
+            .trim()
+            .parse()
+            .map(|hours| Some(EffortEstimate::Hours(hours)))
+            .map_err(|_| invalid());
+    }
+    if let Some(days) = value.strip_suffix('d') {
+        return days
+            .trim()
+            .parse()
+            .map(|days| Some(EffortEstimate::Days(days)))
+            .map_err(|_| invalid());
+    }
+    Err(invalid())
+}
+
+/// Parse a custom field value as the same type as `existing` (text if new)
+fn parse_custom_value(
+    value: &str,
+    existing: Option<CustomFieldType>,
+) -> Result<CustomFieldType, String> {
+    match existing {
+        None | Some(CustomFieldType::Text(_)) => Ok(CustomFieldType::Text(value.to_string())),
+        Some(CustomFieldType::Number(_)) => value
+            .parse()
+            .map(CustomFieldType::Number)
+            .map_err(|_| format!("Not a number: {}", value)),
+        Some(CustomFieldType::Dropdown(_, options)) => options
+            .iter()
+            .find(|option| option.eq_ignore_ascii_case(value))
+            .cloned()
+            .map(|selected| CustomFieldType::Dropdown(selected, options.clone()))
+            .ok_or_else(|| format!("Choose one of: {}", options.join(", "))),
+        Some(CustomFieldType::Date(_)) => parse_date(value, None)?
+            .map(CustomFieldType::Date)
+            .ok_or_else(|| "Date can't be empty".to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use crossterm::event::KeyModifiers;
+    use ratatui::{Terminal, backend::TestBackend};
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn type_text(screen: &mut TableViewScreen, db: &mut BoardDatabase, text: &str) {
+        for c in text.chars() {
+            screen.handle_key(key(KeyCode::Char(c)), db);
+        }
+    }
+
+    fn add_card(db: &mut BoardDatabase, title: &str, priority: Priority) -> String {
+        db.cards.create_card(
+            title.to_string(),
+            String::new(),
+            "To Do".to_string(),
+            priority,
+            "me".to_string(),
+        )
+    }
+
+    fn render(screen: &mut TableViewScreen, db: &BoardDatabase) -> String {
+        let mut terminal = Terminal::new(TestBackend::new(120, 20)).unwrap();
+        terminal.draw(|f| screen.render(f, f.area(), db)).unwrap();
+        terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|c| c.symbol())
+            .collect()
+    }
+
+    /// Select a column by key
+    fn select_column(screen: &mut TableViewScreen, db: &mut BoardDatabase, column: &str) {
+        screen.handle_key(key(KeyCode::Home), db);
+        let index = screen.columns(db).iter().position(|c| c == column).unwrap();
+        screen.column = index;
+    }
+
+    #[test]
+    fn test_custom_fields_become_columns() {
+        let mut db = BoardDatabase::default();
+        let id = add_card(&mut db, "Spike", Priority::High);
+        db.cards
+            .get_card_mut(&id)
+            .unwrap()
+            .set_custom_field(CustomField {
+                name: "estimate".to_string(),
+                value: CustomFieldType::Number(3.0),
+                created_at: Utc::now(),
+            });
+        let mut screen = TableViewScreen::new();
+
+        assert_eq!(
+            screen.columns(&db),
+            vec![
+                "title", "status", "priority", "assignee", "due_date", "estimate"
+            ]
+        );
+        let output = render(&mut screen, &db);
+        assert!(output.contains("Table · 1 cards"));
+        assert!(output.contains("[Title]"));
+        assert!(output.contains("estimate"));
+        assert!(output.contains("Spike"));
+        assert!(output.contains("P1"));
+    }
+
+    #[test]
+    fn test_sort_by_priority_and_due_date() {
+        let mut db = BoardDatabase::default();
+        let low = add_card(&mut db, "Low", Priority::Low);
+        let critical = add_card(&mut db, "Critical", Priority::Critical);
+        let undated = add_card(&mut db, "Undated", Priority::Medium);
+        db.cards.get_card_mut(&low).unwrap().due_date =
+            Some(Utc.with_ymd_and_hms(2025, 3, 12, 0, 0, 0).unwrap());
+        db.cards.get_card_mut(&critical).unwrap().due_date =
+            Some(Utc.with_ymd_and_hms(2025, 3, 11, 0, 0, 0).unwrap());
+        let mut screen = TableViewScreen::new();
+
+        select_column(&mut screen, &mut db, "priority");
+        screen.handle_key(key(KeyCode::Char('s')), &mut db);
+        assert_eq!(
+            screen.row_ids(),
+            [critical.clone(), undated.clone(), low.clone()]
+        );
+        screen.handle_key(key(KeyCode::Char('s')), &mut db);
+        assert_eq!(
+            screen.row_ids(),
+            [low.clone(), undated.clone(), critical.clone()]
+        );
+        assert!(render(&mut screen, &db).contains("Priority ▼"));
+
+        // Cards without a due date stay last in both directions
+        select_column(&mut screen, &mut db, "due_date");
+        screen.handle_key(key(KeyCode::Char('s')), &mut db);
+        assert_eq!(
+            screen.row_ids(),
+            [critical.clone(), low.clone(), undated.clone()]
+        );
+        screen.handle_key(key(KeyCode::Char('s')), &mut db);
+        assert_eq!(screen.row_ids(), [low, critical, undated]);
+    }
+
+    #[test]
+    fn test_edit_cells() {
+        let mut db = BoardDatabase::default();
+        let id = add_card(&mut db, "Draft", Priority::Low);
+        let mut screen = TableViewScreen::new();
+
+        select_column(&mut screen, &mut db, "priority");
+        screen.handle_key(key(KeyCode::Char('e')), &mut db);
+        assert!(screen.is_editing());
+        screen.handle_key(key(KeyCode::Backspace), &mut db);
+        screen.handle_key(key(KeyCode::Backspace), &mut db);
+        type_text(&mut screen, &mut db, "high");
+        assert!(matches!(
+            screen.handle_key(key(KeyCode::Enter), &mut db),
+            TableOutcome::Edited(_)
+        ));
+        assert_eq!(db.cards.get_card(&id).unwrap().priority, Priority::High);
+
+        select_column(&mut screen, &mut db, "due_date");
+        screen.handle_key(key(KeyCode::Enter), &mut db);
+        type_text(&mut screen, &mut db, "2025-13-01");
+        assert!(matches!(
+            screen.handle_key(key(KeyCode::Enter), &mut db),
+            TableOutcome::Error(_)
+        ));
+        assert!(db.cards.get_card(&id).unwrap().due_date.is_none());
+
+        select_column(&mut screen, &mut db, "title");
+        screen.handle_key(key(KeyCode::Enter), &mut db);
+        for _ in 0.."Draft".len() {
+            screen.handle_key(key(KeyCode::Backspace), &mut db);
+        }
+        assert_eq!(
+            screen.handle_key(key(KeyCode::Enter), &mut db),
+            TableOutcome::Error("Title can't be empty".to_string())
+        );
+        screen.handle_key(key(KeyCode::Enter), &mut db);
+        type_text(&mut screen, &mut db, "!");
+        screen.handle_key(key(KeyCode::Esc), &mut db);
+        assert_eq!(db.cards.get_card(&id).unwrap().title, "Draft");
+    }
+
+    #[test]
+    fn test_custom_field_edits_keep_their_type() {
+        let mut db = BoardDatabase::default();
+        let first = add_card(&mut db, "First", Priority::Low);
+        let second = add_card(&mut db, "Second", Priority::Low);
+        db.cards
+            .get_card_mut(&first)
+            .unwrap()
+            .set_custom_field(CustomField {
+                name: "size".to_string(),
+                value: CustomFieldType::Dropdown(
+                    "S".to_string(),
+                    vec!["S".to_string(), "M".to_string(), "L".to_string()],
+                ),
+                created_at: Utc::now(),
+            });
+
+        assert!(apply_edit(&mut db, &second, "size", "XL").is_err());
+        apply_edit(&mut db, &second, "size", "m").unwrap();
+        assert_eq!(
+            db.cards.get_card(&second).unwrap().custom_fields["size"].value,
+            CustomFieldType::Dropdown(
+                "M".to_string(),
+                vec!["S".to_string(), "M".to_string(), "L".to_string()]
+            )
+        );
+        apply_edit(&mut db, &second, "size", "").unwrap();
+        assert!(db.cards.get_card(&second).unwrap().custom_fields.is_empty());
+
+        apply_edit(&mut db, &first, "effort", "5sp").unwrap();
+        assert_eq!(
+            db.cards.get_card(&first).unwrap().effort_estimate,
+            Some(EffortEstimate::StoryPoints(5))
+        );
+        assert!(apply_edit(&mut db, &first, "progress", "120").is_err());
+        assert!(apply_edit(&mut db, &first, "tags", "x").is_err());
+    }
+
+    #[test]
+    fn test_filter_and_read_only_settings() {
+        let mut db = BoardDatabase::default();
+        add_card(&mut db, "Alpha", Priority::Low);
+        add_card(&mut db, "Beta", Priority::Low);
+        let mut screen = TableViewScreen::from_settings(&ViewSettings::Table {
+            visible_columns: vec!["title".to_string()],
+            allow_inline_edit: false,
+            show_row_numbers: false,
+        });
+
+        screen.handle_key(key(KeyCode::Char('/')), &mut db);
+        type_text(&mut screen, &mut db, "beta");
+        screen.handle_key(key(KeyCode::Enter), &mut db);
+        assert_eq!(screen.row_ids().len(), 1);
+        assert!(render(&mut screen, &db).contains("filter: beta"));
+
+        assert!(matches!(
+            screen.handle_key(key(KeyCode::Char('e')), &mut db),
+            TableOutcome::Message(_)
+        ));
+        assert!(!screen.is_editing());
+    }
+}
@@ -0,0 +1,693 @@
+//! Timeline screen - Gantt chart of the board's card schedule
+//!
+//! Schedules the cards in the app's [`BoardDatabase`] with the critical path
+//! method (see [`card_schedule`](crate::infrastructure::card_schedule)) and
+//! draws one bar per card. Cards on the critical path are drawn in red, and
+//! each card's slack (how long it can slip without delaying the board) trails
+//! its bar as a shaded segment.
+//!
+//! # Architecture
+//!
+//! Like the kanban screen, this screen only holds view state (selection,
+//! zoom and the critical path toggle) plus the live filter the app lends it.
+//! The schedule is rebuilt from the board on every render, so moving a card's
+//! start with the keyboard pins it through [`reschedule_card`] and its
+//! dependents follow on the next frame. Key handlers return a
+//! [`TimelineOutcome`] telling the app whether the board changed and needs
+//! saving.
+//!
+//! # Examples
+//!
+//! ```
+//! use chrono::Utc;
+//! use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+//! use toad::infrastructure::board_store::BoardDatabase;
+//! use toad::infrastructure::TimelineZoom;
+//! use toad::ui::screens::timeline::TimelineScreen;
+//!
+//! let mut db = BoardDatabase::default();
+//! let mut screen = TimelineScreen::new();
+//! assert_eq!(screen.zoom(), TimelineZoom::Days);
+//! screen.handle_key(KeyEvent::new(KeyCode::Char('z'), KeyModifiers::NONE), &mut db, Utc::now());
+//! assert_eq!(screen.zoom(), TimelineZoom::Weeks);
+//! ```
+
+use crate::infrastructure::board_store::BoardDatabase;
+use crate::infrastructure::card_schedule::{
+    CardSchedule, ScheduledCard, date_at, days_between, reschedule_card,
+};
+use crate::infrastructure::filtering_search::LiveFilter;
+use crate::infrastructure::multiple_views::{TimelineZoom, ViewSettings};
+use chrono::{DateTime, Duration, Utc};
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+};
+
+/// Columns taken by the card titles left of the chart
+const LABEL_WIDTH: u16 = 24;
+
+/// Chart cells between two date labels in the header
+const LABEL_SPACING: usize = 12;
+
+/// Result of handling an input event, for the app to act on
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TimelineOutcome {
+    /// Nothing for the app to do
+    None,
+    /// A card was moved and the board should be saved
+    Rescheduled(String),
+    /// The board changed and should be saved
+    Changed,
+    /// Informational message
+    Message(String),
+    /// An operation failed
+    Error(String),
+    /// Leave the timeline screen
+    Close,
+}
+
+/// Gantt timeline screen
+#[derive(Debug)]
+pub struct TimelineScreen {
+    /// Selected row
+    selected: usize,
+    /// First row shown
+    scroll: usize,
+    /// Days per chart cell
+    zoom: TimelineZoom,
+    /// Only show cards on the critical path
+    critical_only: bool,
+    /// Dependency display flag, kept for the view settings
+    show_dependencies: bool,
+    /// Live filter applied to cards
+    filter: LiveFilter,
+}
+
+impl Default for TimelineScreen {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TimelineScreen {
+    /// Create a screen zoomed to days
+    pub fn new() -> Self {
+        Self {
+            selected: 0,
+            scroll: 0,
+            zoom: TimelineZoom::Days,
+            critical_only: false,
+            show_dependencies: true,
+            filter: LiveFilter::new(),
+        }
+    }
+
+    /// Create a screen from saved timeline view settings
+    pub fn from_settings(settings: &ViewSettings) -> Self {
+        let mut screen = Self::new();
+        if let ViewSettings::Timeline {
+            zoom,
+            show_dependencies,
+            show_critical_path,
+        } = settings
+        {
+            screen.zoom = *zoom;
+            screen.show_dependencies = *show_dependencies;
+            screen.critical_only = *show_critical_path;
+        }
+        screen
+    }
+
+    /// Current view settings
+    pub fn settings(&self) -> ViewSettings {
+        ViewSettings::Timeline {
+            zoom: self.zoom,
+            show_dependencies: self.show_dependencies,
+            show_critical_path: self.critical_only,
+        }
+    }
+
+    /// Card filter
+    pub fn filter_mut(&mut self) -> &mut LiveFilter {
+        &mut self.filter
+    }
+
+    /// Current zoom level
+    pub fn zoom(&self) -> TimelineZoom {
+        self.zoom
+    }
+
+    /// Whether only critical cards are shown
+    pub fn critical_only(&self) -> bool {
+        self.critical_only
+    }
+
+    /// ID of the selected card
+    pub fn selected_card_id(&self, db: &BoardDatabase, now: DateTime<Utc>) -> Option<String> {
+        let schedule = CardSchedule::build(db, now).ok()?;
+        self.rows(&schedule, db)
+            .get(self.selected)
+            .map(|card| card.card_id.clone())
+    }
+
+    /// Cards shown as rows
+    fn rows<'a>(&self, schedule: &'a CardSchedule, db: &BoardDatabase) -> Vec<&'a ScheduledCard> {
+        schedule
+            .cards
+            .iter()
+            .filter(|card| !self.critical_only || card.node.is_critical)
+            .filter(|card| {
+                db.cards
+                    .get_card(&card.card_id)
+                    .is_some_and(|card| self.filter.matches(card))
+            })
+            .collect()
+    }
+
+    // ---- input ------------------------------------------------------------
+
+    /// Handle a key press
+    pub fn handle_key(
+        &mut self,
+        key: KeyEvent,
+        db: &mut BoardDatabase,
+        now: DateTime<Utc>,
+    ) -> TimelineOutcome {
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => TimelineOutcome::Close,
+            KeyCode::Char('j') | KeyCode::Down => {
+                self.selected += 1;
+                TimelineOutcome::None
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.selected = self.selected.saturating_sub(1);
+                TimelineOutcome::None
+            }
+            KeyCode::Char('h') | KeyCode::Left => self.shift_selected(db, now, -1),
+            KeyCode::Char('l') | KeyCode::Right => self.shift_selected(db, now, 1),
+            KeyCode::Char('H') => self.shift_selected(db, now, -7),
+            KeyCode::Char('L') => self.shift_selected(db, now, 7),
+            KeyCode::Char('u') => self.unpin_selected(db, now),
+            KeyCode::Char('z') => {
+                self.zoom = next_zoom(self.zoom);
+                TimelineOutcome::None
+            }
+            KeyCode::Char('c') => {
+                self.critical_only = !self.critical_only;
+                self.selected = 0;
+                self.scroll = 0;
+                TimelineOutcome::None
+            }
+            _ => TimelineOutcome::None,
+        }
+    }
+
+    /// Move the selected card's start by `days` and reschedule its dependents
+    fn shift_selected(
+        &mut self,
+        db: &mut BoardDatabase,
+        now: DateTime<Utc>,
+        days: i64,
+    ) -> TimelineOutcome {
+        let schedule = match CardSchedule::build(db, now) {
+            Ok(schedule) => schedule,
+            Err(cycle) => return cycle_error(&cycle),
+        };
+        let Some(card) = self.rows(&schedule, db).get(self.selected).copied() else {
+            return TimelineOutcome::None;
+        };
+        let start = card.start + Duration::days(days);
+        let card_id = card.card_id.clone();
+        let title = card.title.clone();
+
+        match reschedule_card(db, &card_id, start, now) {
+            Ok(dependents) => {
+                let scheduled = CardSchedule::build(db, now)
+                    .ok()
+                    .and_then(|schedule| schedule.get(&card_id).map(|card| card.start))
+                    .unwrap_or(start);
+                let mut message = format!("{} starts {}", title, scheduled.format("%Y-%m-%d"));
+                if scheduled > start {
+                    message.push_str(" (held back by dependencies)");
+                }
+                if !dependents.is_empty() {
+                    message.push_str(&format!(", {} dependents rescheduled", dependents.len()));
+                }
+                TimelineOutcome::Rescheduled(message)
+            }
+            Err(e) => TimelineOutcome::Error(e),
+        }
+    }
+
+    /// Let the selected card start as early as its dependencies allow
+    fn unpin_selected(&mut self, db: &mut BoardDatabase, now: DateTime<Utc>) -> TimelineOutcome {
+        let Some(card_id) = self.selected_card_id(db, now) else {
+            return TimelineOutcome::None;
+        };
+        match db.cards.get_card_mut(&card_id) {
+            Some(card) if card.start_date.is_some() => {
+                card.start_date = None;
+                card.updated_at = Utc::now();
+                TimelineOutcome::Changed
+            }
+            _ => TimelineOutcome::Message("Card start is not pinned".to_string()),
+        }
+    }
+
+    // ---- rendering --------------------------------------------------------
+
+    /// Render the timeline
+    pub fn render(
+        &mut self,
+        frame: &mut Frame,
+        area: Rect,
+        db: &BoardDatabase,
+        now: DateTime<Utc>,
+    ) {
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(1), // Summary
+                Constraint::Length(1), // Date axis
+                Constraint::Min(0),    // Bars
+                Constraint::Length(1), // Key hints
+            ])
+            .split(area);
+
+        let schedule = match CardSchedule::build(db, now) {
+            Ok(schedule) => schedule,
+            Err(cycle) => {
+                frame.render_widget(
+                    Paragraph::new(Line::from(Span::styled(
+                        format!(" ⚠ Dependency cycle between {}", cycle.join(", ")),
+                        Style::default().fg(Color::Red),
+                    ))),
+                    rows[0],
+                );
+                frame.render_widget(Paragraph::new(self.footer_line()), rows[3]);
+                return;
+            }
+        };
+
+        let cards = self.rows(&schedule, db);
+        self.selected = self.selected.min(cards.len().saturating_sub(1));
+        let height = rows[2].height as usize;
+        if self.selected < self.scroll {
+            self.scroll = self.selected;
+        } else if height > 0 && self.selected >= self.scroll + height {
+            self.scroll = self.selected + 1 - height;
+        }
+
+        let chart_width = rows[2].width.saturating_sub(LABEL_WIDTH) as usize;
+        let per_cell = days_per_cell(self.zoom);
+        // Pan so the selected card's start stays on screen
+        let first_day = cards
+            .get(self.selected)
+            .map(|card| {
+                let cells = (card.node.earliest_start / per_cell) as usize;
+                let visible = chart_width.saturating_sub(LABEL_SPACING).max(1);
+                (cells / visible * visible) as f32 * per_cell
+            })
+            .unwrap_or(0.0);
+        let axis = Axis {
+            origin: schedule.origin,
+            first_day,
+            per_cell,
+            width: chart_width,
+            today: days_between(schedule.origin, now),
+        };
+
+        frame.render_widget(
+            Paragraph::new(self.header_line(&schedule, cards.len())),
+            rows[0],
+        );
+        frame.render_widget(Paragraph::new(axis.date_line()), rows[1]);
+        let lines: Vec<Line> = cards
+            .iter()
+            .enumerate()
+            .skip(self.scroll)
+            .take(height)
+            .map(|(index, card)| bar_line(card, &axis, index == self.selected))
+            .collect();
+        frame.render_widget(Paragraph::new(lines), rows[2]);
+        frame.render_widget(Paragraph::new(self.footer_line()), rows[3]);
+    }
+
+    fn header_line(&self, schedule: &CardSchedule, shown: usize) -> Line<'static> {
+        let mut spans = vec![
+            Span::styled(
+                " ◷ Timeline ",
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                format!("{}/{} cards", shown, schedule.cards.len()),
+                Style::default().fg(Color::Gray),
+            ),
+        ];
+        if !schedule.cards.is_empty() {
+            spans.push(Span::styled(
+                format!(
+                    "  finishes {}  critical path: {}",
+                    schedule.finish().format("%Y-%m-%d"),
+                    schedule.critical_path().len()
+                ),
+                Style::default().fg(Color::Gray),
+            ));
+        }
+        spans.push(Span::styled(
+            format!("  zoom: {}", zoom_name(self.zoom)),
+            Style::default().fg(Color::DarkGray),
+        ));
+        if self.critical_only {
+            spans.push(Span::styled(
+                "  critical only",
+                Style::default().fg(Color::Red),
+            ));
+        }
+        if self.filter.is_active() {
+            spans.push(Span::styled(
+                format!("  filter: {}", self.filter.query()),
+                Style::default().fg(Color::Yellow),
+            ));
+        }
+        Line::from(spans)
+    }
+
+    fn footer_line(&self) -> Line<'static> {
+        Line::from(Span::styled(
+            "j/k card  h/l move a day  H/L move a week  u unpin  z zoom  c critical only  Esc back",
+            Style::default().fg(Color::DarkGray),
+        ))
+    }
+}
+
+/// Mapping between chart cells and schedule days
+struct Axis {
+    /// Day zero of the schedule
+    origin: DateTime<Utc>,
+    /// Day shown in the first cell
+    first_day: f32,
+    /// Days covered by one cell
+    per_cell: f32,
+    /// Number of cells
+    width: usize,
+    /// Today, in schedule days
+    today: f32,
+}
+
+impl Axis {
+    /// Days covered by a cell, as `[start, end)`
+    fn cell(&self, index: usize) -> (f32, f32) {
+        let start = self.first_day + index as f32 * self.per_cell;
+        (start, start + self.per_cell)
+    }
+
+    /// Date labels above the chart, with a marker for today
+    fn date_line(&self) -> Line<'static> {
+        let mut axis = vec![' '; self.width];
+        for index in (0..self.width).step_by(LABEL_SPACING) {
+            let label = date_at(self.origin, self.cell(index).0)
+                .format("%m-%d")
+                .to_string();
+            for (offset, ch) in label.chars().enumerate() {
+                if let Some(slot) = axis.get_mut(index + offset) {
+                    *slot = ch;
+                }
+            }
+        }
+        let today = (0..self.width).find(|&index| {
+            let (start, end) = self.cell(index);
+            start <= self.today && self.today < end
+        });
+
+        let mut spans = vec![Span::raw(" ".repeat(LABEL_WIDTH as usize))];
+        for (index, ch) in axis.into_iter().enumerate() {
+            if Some(index) == today && ch == ' ' {
+                spans.push(Span::styled("▼", Style::default().fg(Color::Yellow)));
+            } else if Some(index) == today {
+                spans.push(Span::styled(
+                    ch.to_string(),
+                    Style::default().fg(Color::Yellow),
+                ));
+            } else {
+                spans.push(Span::styled(
+                    ch.to_string(),
+                    Style::default().fg(Color::DarkGray),
+                ));
+            }
+        }
+        Line::from(spans)
+    }
+}
+
+/// Title and bar of one card
+fn bar_line(card: &ScheduledCard, axis: &Axis, selected: bool) -> Line<'static> {
+    let marker = match (card.node.is_critical, card.pinned) {
+        (true, _) => "◆",
+        (false, true) => "⊙",
+        (false, false) => "·",
+    };
+    let width = LABEL_WIDTH as usize - 3;
+    let title: String = card.title.chars().take(width).collect();
+    let label_style = if selected {
+        Style::default().add_modifier(Modifier::REVERSED)
+    } else {
+        Style::default()
+    };
+    let bar_color = if card.node.is_critical {
+        Color::Red
+    } else {
+        Color::Cyan
+    };
+
+    let mut spans = vec![
+        Span::styled(format!("{} ", marker), Style::default().fg(bar_color)),
+        Span::styled(format!("{:<width$}", title, width = width), label_style),
+        Span::raw(" "),
+    ];
+
+    let start = card.node.earliest_start;
+    let finish = card.node.earliest_finish();
+    let latest = card.node.latest_finish();
+    for index in 0..axis.width {
+        let (cell_start, cell_end) = axis.cell(index);
+        let cell = if start < cell_end && finish > cell_start {
+            Span::styled("█", Style::default().fg(bar_color))
+        } else if card.node.duration == 0.0 && cell_start <= start && start < cell_end {
+            Span::styled("◆", Style::default().fg(bar_color))
+        } else if finish < cell_end && latest > cell_start && latest > finish {
+            Span::styled("░", Style::default().fg(Color::DarkGray))
+        } else if cell_start <= axis.today && axis.today < cell_end {
+            Span::styled("│", Style::default().fg(Color::Yellow))
+        } else {
+            Span::raw(" ")
+        };
+        spans.push(cell);
+    }
+    Line::from(spans)
+}
+
+/// Days covered by one chart cell at a zoom level
+fn days_per_cell(zoom: TimelineZoom) -> f32 {
+    match zoom {
+        TimelineZoom::Days => 0.25,
+        TimelineZoom::Weeks => 1.0,
+        TimelineZoom::Months => 7.0,
+        TimelineZoom::Quarters => 14.0,
+    }
+}
+
+fn next_zoom(zoom: TimelineZoom) -> TimelineZoom {
+    match zoom {
+        TimelineZoom::Days => TimelineZoom::Weeks,
+        TimelineZoom::Weeks => TimelineZoom::Months,
+        TimelineZoom::Months => TimelineZoom::Quarters,
+        TimelineZoom::Quarters => TimelineZoom::Days,
+    }
+}
+
+fn zoom_name(zoom: TimelineZoom) -> &'static str {
+    match zoom {
+        TimelineZoom::Days => "days",
+        TimelineZoom::Weeks => "weeks",
+        TimelineZoom::Months => "months",
+        TimelineZoom::Quarters => "quarters",
+    }
+}
+
+fn cycle_error(cycle: &[String]) -> TimelineOutcome {
+    TimelineOutcome::Error(format!("Dependency cycle between {}", cycle.join(", ")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::rich_task_cards::{EffortEstimate, Priority};
+    use crate::infrastructure::task_dependencies::DependencyType;
+    use chrono::TimeZone;
+    use crossterm::event::KeyModifiers;
+    use ratatui::{Terminal, backend::TestBackend};
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 3, 10, 9, 0, 0).unwrap()
+    }
+
+    /// Design (2d) blocks Build (1d); Docs (1d) is independent
+    fn project() -> (BoardDatabase, Vec<String>) {
+        let mut db = BoardDatabase::default();
+        let ids: Vec<String> = [("Design", 2.0), ("Build", 1.0), ("Docs", 1.0)]
+            .iter()
+            .map(|(title, days)| {
+                let id = db.cards.create_card(
+                    title.to_string(),
+                    String::new(),
+                    "To Do".to_string(),
+                    Priority::Medium,
+                    "me".to_string(),
+                );
+                db.cards.get_card_mut(&id).unwrap().effort_estimate =
+                    Some(EffortEstimate::Days(*days));
+                id
+            })
+            .collect();
+        db.dependencies
+            .create_dependency(
+                ids[0].clone(),
+                ids[1].clone(),
+                DependencyType::Blocks,
+                "me".to_string(),
+            )
+            .unwrap();
+        (db, ids)
+    }
+
+    fn render(screen: &mut TimelineScreen, db: &BoardDatabase) -> Vec<String> {
+        let mut terminal = Terminal::new(TestBackend::new(60, 8)).unwrap();
+        terminal
+            .draw(|f| screen.render(f, f.area(), db, now()))
+            .unwrap();
+        let buffer = terminal.backend().buffer();
+        (0..buffer.area.height)
+            .map(|y| {
+                (0..buffer.area.width)
+                    .map(|x| buffer[(x, y)].symbol())
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn row<'a>(lines: &'a [String], title: &str) -> &'a str {
+        lines.iter().find(|line| line.contains(title)).unwrap()
+    }
+
+    #[test]
+    fn test_renders_critical_path_and_slack() {
+        let (db, _) = project();
+        let mut screen = TimelineScreen::new();
+        let lines = render(&mut screen, &db);
+
+        assert!(lines[0].contains("3/3 cards"));
+        assert!(lines[0].contains("finishes 2025-03-13"));
+        assert!(lines[0].contains("critical path: 2"));
+        assert!(lines[1].contains("03-10"));
+
+        let design = row(&lines, "Design");
+        assert!(design.starts_with("◆ Design"));
+        assert_eq!(design.matches('█').count(), 8);
+        let docs = row(&lines, "Docs");
+        assert!(docs.starts_with("· Docs"));
+        assert_eq!(docs.matches('█').count(), 4);
+        assert_eq!(docs.matches('░').count(), 8);
+    }
+
+    #[test]
+    fn test_moving_card_reschedules_dependents() {
+        let (mut db, ids) = project();
+        let mut screen = TimelineScreen::new();
+        assert_eq!(screen.selected_card_id(&db, now()), Some(ids[0].clone()));
+
+        let outcome = screen.handle_key(key(KeyCode::Char('l')), &mut db, now());
+        assert_eq!(
+            outcome,
+            TimelineOutcome::Rescheduled(
+                "Design starts 2025-03-11, 1 dependents rescheduled".to_string()
+            )
+        );
+        let origin = Utc.with_ymd_and_hms(2025, 3, 10, 0, 0, 0).unwrap();
+        assert_eq!(
+            db.cards.get_card(&ids[1]).unwrap().due_date,
+            Some(origin + Duration::days(4))
+        );
+
+        let outcome = screen.handle_key(key(KeyCode::Char('u')), &mut db, now());
+        assert_eq!(outcome, TimelineOutcome::Changed);
+        assert!(db.cards.get_card(&ids[0]).unwrap().start_date.is_none());
+    }
+
+    #[test]
+    fn test_moving_before_blocker_is_held_back() {
+        let (mut db, ids) = project();
+        let mut screen = TimelineScreen::new();
+        screen.handle_key(key(KeyCode::Char('j')), &mut db, now());
+        assert_eq!(screen.selected_card_id(&db, now()), Some(ids[1].clone()));
+
+        let outcome = screen.handle_key(key(KeyCode::Char('h')), &mut db, now());
+        assert_eq!(
+            outcome,
+            TimelineOutcome::Rescheduled(
+                "Build starts 2025-03-12 (held back by dependencies)".to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn test_zoom_and_critical_filter() {
+        let (mut db, _) = project();
+        let mut screen = TimelineScreen::new();
+        screen.handle_key(key(KeyCode::Char('z')), &mut db, now());
+        assert_eq!(screen.zoom(), TimelineZoom::Weeks);
+        let lines = render(&mut screen, &db);
+        assert_eq!(row(&lines, "Design").matches('█').count(), 2);
+
+        screen.handle_key(key(KeyCode::Char('c')), &mut db, now());
+        assert!(screen.critical_only());
+        let lines = render(&mut screen, &db);
+        assert!(lines[0].contains("2/3 cards"));
+        assert!(!lines.iter().any(|line| line.contains("Docs")));
+    }
+
+    #[test]
+    fn test_cycle_is_reported() {
+        let (mut db, ids) = project();
+        db.dependencies
+            .create_dependency(
+                ids[0].clone(),
+                ids[1].clone(),
+                DependencyType::Duplicates,
+                "me".to_string(),
+            )
+            .unwrap();
+        let mut screen = TimelineScreen::new();
+        let lines = render(&mut screen, &db);
+        assert!(lines[0].contains("Dependency cycle"));
+        assert!(matches!(
+            screen.handle_key(key(KeyCode::Char('l')), &mut db, now()),
+            TimelineOutcome::Error(_)
+        ));
+        assert_eq!(
+            screen.handle_key(key(KeyCode::Esc), &mut db, now()),
+            TimelineOutcome::Close
+        );
+    }
+}
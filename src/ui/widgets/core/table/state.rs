@@ -47,6 +47,7 @@ impl TableColumn {
 }
 
 /// Table widget with multi-column support
+#[derive(Debug)]
 pub struct DataTable {
     pub(super) title: String,
     pub(super) columns: Vec<TableColumn>,
@@ -70,6 +71,16 @@ impl DataTable {
         }
     }
 
+    /// Set the table title
+    pub fn set_title(&mut self, title: impl Into<String>) {
+        self.title = title.into();
+    }
+
+    /// Replace the column definitions
+    pub fn set_columns(&mut self, columns: Vec<TableColumn>) {
+        self.columns = columns;
+    }
+
     /// Add a row to the table
     pub fn add_row(&mut self, row: Vec<String>) {
         self.rows.push(row);
@@ -131,6 +142,13 @@ impl DataTable {
         self.state.select(Some(i));
     }
 
+    /// Select a row by index (clamped to the last row)
+    pub fn select(&mut self, index: usize) {
+        if !self.rows.is_empty() {
+            self.state.select(Some(index.min(self.rows.len() - 1)));
+        }
+    }
+
     /// Select first row
     pub fn select_first(&mut self) {
         if !self.rows.is_empty() {
@@ -361,6 +379,21 @@ mod tests {
         assert_eq!(table.selected(), Some(0));
     }
 
+    #[test]
+    fn test_select_clamps_to_last_row() {
+        let columns = vec![TableColumn::new("Name", 20)];
+        let mut table = DataTable::new("Test", columns);
+
+        table.add_row(vec!["Alice".to_string()]);
+        table.add_row(vec!["Bob".to_string()]);
+
+        table.select(1);
+        assert_eq!(table.selected(), Some(1));
+
+        table.select(5);
+        assert_eq!(table.selected(), Some(1));
+    }
+
     #[test]
     fn test_select_last() {
         let columns = vec![TableColumn::new("Name", 20)];
@@ -1,7 +1,10 @@
 //! Git conflict resolution widget
 //!
 //! Provides a visual interface for resolving merge conflicts with
-//! side-by-side comparison and intelligent conflict detection.
+//! side-by-side comparison and intelligent conflict detection. Regions whose
+//! changes do not overlap can be resolved automatically from the merge base,
+//! an agent's proposal can be reviewed as a diff, and fully resolved files are
+//! rebuilt for writing back.
 //!
 //! # Examples
 //!
@@ -24,6 +27,7 @@
 //! resolver.load_conflict("src/main.rs", conflict);
 //! ```
 
+use crate::git::merge::{self, BASE_MARKER, LineChange, MergeVersions};
 use crate::ui::atoms::{block::Block as AtomBlock, text::Text};
 use ratatui::{
     buffer::Buffer,
@@ -32,6 +36,7 @@ use ratatui::{
     text::Line,
     widgets::{Borders, List, ListItem, ListState, Paragraph, StatefulWidget, Widget},
 };
+use std::collections::HashMap;
 
 /// Conflict region in a file
 #[derive(Debug, Clone)]
@@ -50,6 +55,10 @@ pub struct ConflictRegion {
     pub end_line: usize,
     /// Current resolution choice
     pub resolution: ConflictResolution,
+    /// Resolved lines for automatic and manual resolutions
+    pub merged: Option<Vec<String>>,
+    /// Resolution proposed by an agent, awaiting review
+    pub proposal: Option<Vec<String>>,
 }
 
 impl ConflictRegion {
@@ -63,6 +72,8 @@ impl ConflictRegion {
             start_line,
             end_line,
             resolution: ConflictResolution::Unresolved,
+            merged: None,
+            proposal: None,
         }
     }
 
@@ -101,6 +112,37 @@ impl ConflictRegion {
         self.resolution = ConflictResolution::ManualEdit;
     }
 
+    /// Resolve with explicitly edited lines
+    pub fn set_manual_content(&mut self, lines: Vec<String>) {
+        self.merged = Some(lines);
+        self.resolution = ConflictResolution::ManualEdit;
+    }
+
+    /// Resolve automatically when the changes do not overlap
+    ///
+    /// Needs the base lines; returns `true` if the region was resolved.
+    pub fn auto_merge(&mut self) -> bool {
+        let Some(base) = &self.base else {
+            return false;
+        };
+
+        match merge::resolve_region(base, &self.ours, &self.theirs) {
+            Some(lines) => {
+                self.merged = Some(lines);
+                self.resolution = ConflictResolution::AutoMerged;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Diff from "ours" to the pending proposal
+    pub fn proposal_diff(&self) -> Option<Vec<LineChange>> {
+        self.proposal
+            .as_ref()
+            .map(|proposal| merge::line_diff(&self.ours, proposal))
+    }
+
     /// Get resolved content
     pub fn get_resolved_content(&self) -> Vec<String> {
         match &self.resolution {
@@ -111,9 +153,8 @@ impl ConflictRegion {
                 result.extend(self.theirs.clone());
                 result
             }
-            ConflictResolution::ManualEdit => {
-                // Would be handled by external editor
-                self.ours.clone()
+            ConflictResolution::ManualEdit | ConflictResolution::AutoMerged => {
+                self.merged.clone().unwrap_or_else(|| self.ours.clone())
             }
             ConflictResolution::Unresolved => Vec::new(),
         }
@@ -138,6 +179,8 @@ pub enum ConflictResolution {
     ChooseBoth,
     /// Manually edited resolution
     ManualEdit,
+    /// Non-overlapping changes merged automatically
+    AutoMerged,
 }
 
 impl ConflictResolution {
@@ -149,6 +192,7 @@ impl ConflictResolution {
             ConflictResolution::ChooseTheirs => Color::Green,
             ConflictResolution::ChooseBoth => Color::Yellow,
             ConflictResolution::ManualEdit => Color::Magenta,
+            ConflictResolution::AutoMerged => Color::Cyan,
         }
     }
 
@@ -160,6 +204,7 @@ impl ConflictResolution {
             ConflictResolution::ChooseTheirs => "▶",
             ConflictResolution::ChooseBoth => "⬌",
             ConflictResolution::ManualEdit => "✎",
+            ConflictResolution::AutoMerged => "⚙",
         }
     }
 
@@ -171,6 +216,7 @@ impl ConflictResolution {
             ConflictResolution::ChooseTheirs => "THEIRS",
             ConflictResolution::ChooseBoth => "BOTH",
             ConflictResolution::ManualEdit => "MANUAL",
+            ConflictResolution::AutoMerged => "AUTO",
         }
     }
 }
@@ -186,6 +232,17 @@ pub enum ConflictViewMode {
     ThreeWay,
 }
 
+/// Original file content a set of conflict regions was parsed from
+#[derive(Debug, Clone)]
+struct ConflictSource {
+    /// Lines including conflict markers
+    lines: Vec<String>,
+    /// Line terminator used by the file
+    line_ending: &'static str,
+    /// Whether the file ends with a line terminator
+    trailing_newline: bool,
+}
+
 /// Conflict resolver widget
 pub struct ConflictResolver {
     /// List of conflicts
@@ -199,6 +256,8 @@ pub struct ConflictResolver {
     /// Branch names
     our_branch: String,
     their_branch: String,
+    /// Parsed file contents, keyed by path
+    sources: HashMap<String, ConflictSource>,
 }
 
 impl ConflictResolver {
@@ -220,6 +279,7 @@ impl ConflictResolver {
             view_mode: ConflictViewMode::SideBySide,
             our_branch: "HEAD".to_string(),
             their_branch: "MERGE_HEAD".to_string(),
+            sources: HashMap::new(),
         }
     }
 
@@ -230,9 +290,25 @@ impl ConflictResolver {
     }
 
     /// Load a conflict from text with markers
+    ///
+    /// diff3-style markers (`|||||||`) fill in each region's base. Loading a
+    /// file again replaces its previous regions.
     pub fn load_conflict(&mut self, file_path: impl Into<String>, content: &str) {
         let path = file_path.into();
+        self.remove_file(&path);
         let lines: Vec<&str> = content.lines().collect();
+        self.sources.insert(
+            path.clone(),
+            ConflictSource {
+                lines: lines.iter().map(|l| l.to_string()).collect(),
+                line_ending: if content.contains("\r\n") {
+                    "\r\n"
+                } else {
+                    "\n"
+                },
+                trailing_newline: content.ends_with('\n'),
+            },
+        );
 
         let mut i = 0;
         while i < lines.len() {
@@ -240,13 +316,20 @@ impl ConflictResolver {
                 let start_line = i;
                 let mut ours = Vec::new();
                 let mut theirs = Vec::new();
+                let mut base: Option<Vec<String>> = None;
 
                 // Skip conflict marker
                 i += 1;
 
-                // Read "ours" section
+                // Read "ours" section, then the base section if present
                 while i < lines.len() && !lines[i].starts_with("=======") {
-                    ours.push(lines[i].to_string());
+                    if lines[i].starts_with(BASE_MARKER) {
+                        base = Some(Vec::new());
+                    } else if let Some(base) = base.as_mut() {
+                        base.push(lines[i].to_string());
+                    } else {
+                        ours.push(lines[i].to_string());
+                    }
                     i += 1;
                 }
 
@@ -266,6 +349,7 @@ impl ConflictResolver {
                 let mut conflict = ConflictRegion::new(&path, start_line, end_line);
                 conflict.ours = ours;
                 conflict.theirs = theirs;
+                conflict.base = base;
 
                 self.conflicts.push(conflict);
             }
@@ -277,6 +361,120 @@ impl ConflictResolver {
         }
     }
 
+    /// Load a conflicted file from its index stages
+    ///
+    /// When base, ours and theirs are all available the file is re-merged so
+    /// every region knows its base; otherwise the working tree `content` with
+    /// git's markers is used. Returns the number of regions loaded.
+    pub fn load_versions(
+        &mut self,
+        file_path: impl Into<String>,
+        content: &str,
+        versions: &MergeVersions,
+    ) -> usize {
+        let path = file_path.into();
+        match versions.merge() {
+            Some(merged) => {
+                let text = merged.to_text(&self.our_branch, &self.their_branch);
+                self.load_conflict(path.clone(), &text);
+            }
+            None => self.load_conflict(path.clone(), content),
+        }
+        self.conflicts
+            .iter()
+            .filter(|c| c.file_path == path)
+            .count()
+    }
+
+    /// Resolve every unresolved region whose changes do not overlap
+    ///
+    /// Returns the number of regions resolved.
+    pub fn auto_resolve(&mut self) -> usize {
+        self.conflicts
+            .iter_mut()
+            .filter(|c| !c.is_resolved())
+            .map(|c| c.auto_merge())
+            .filter(|resolved| *resolved)
+            .count()
+    }
+
+    /// Remove all regions of a file
+    pub fn remove_file(&mut self, file_path: &str) {
+        self.sources.remove(file_path);
+        self.conflicts.retain(|c| c.file_path != file_path);
+        if self.current_conflict >= self.conflicts.len() {
+            self.current_conflict = self.conflicts.len().saturating_sub(1);
+        }
+        self.list_state
+            .select((!self.conflicts.is_empty()).then_some(self.current_conflict));
+    }
+
+    /// Paths of all loaded files, in load order
+    pub fn files(&self) -> Vec<String> {
+        let mut files: Vec<String> = Vec::new();
+        for conflict in &self.conflicts {
+            if !files.contains(&conflict.file_path) {
+                files.push(conflict.file_path.clone());
+            }
+        }
+        files
+    }
+
+    /// Whether every region of a file is resolved
+    pub fn is_file_resolved(&self, file_path: &str) -> bool {
+        let mut regions = self
+            .conflicts
+            .iter()
+            .filter(|c| c.file_path == file_path)
+            .peekable();
+        regions.peek().is_some() && regions.all(|c| c.is_resolved())
+    }
+
+    /// Rebuilt content of every fully resolved file
+    pub fn resolved_files(&self) -> Vec<(String, String)> {
+        self.files()
+            .into_iter()
+            .filter_map(|path| {
+                let content = self.get_resolved_file(&path)?;
+                Some((path, content))
+            })
+            .collect()
+    }
+
+    /// Index of the current conflict
+    pub fn current_index(&self) -> usize {
+        self.current_conflict
+    }
+
+    /// Attach an agent's proposal to a region for review
+    pub fn set_proposal(&mut self, index: usize, lines: Vec<String>) {
+        if let Some(conflict) = self.conflicts.get_mut(index) {
+            conflict.proposal = Some(lines);
+        }
+    }
+
+    /// Accept the current region's proposal as its resolution
+    pub fn accept_proposal(&mut self) -> bool {
+        let Some(conflict) = self.conflicts.get_mut(self.current_conflict) else {
+            return false;
+        };
+        match conflict.proposal.take() {
+            Some(lines) => {
+                conflict.set_manual_content(lines);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Discard the current region's proposal
+    pub fn reject_proposal(&mut self) -> bool {
+        self.conflicts
+            .get_mut(self.current_conflict)
+            .and_then(|c| c.proposal.take())
+            .is_some()
+    }
+
     /// Add a conflict region manually
     pub fn add_conflict(&mut self, conflict: ConflictRegion) {
         self.conflicts.push(conflict);
@@ -353,37 +551,64 @@ impl ConflictResolver {
         self.conflicts.iter().filter(|c| c.is_resolved()).count()
     }
 
+    /// Number of regions resolved automatically
+    pub fn auto_resolved_count(&self) -> usize {
+        self.conflicts
+            .iter()
+            .filter(|c| c.resolution == ConflictResolution::AutoMerged)
+            .count()
+    }
+
+    /// All conflict regions
+    pub fn conflicts(&self) -> &[ConflictRegion] {
+        &self.conflicts
+    }
+
     /// Check if all conflicts are resolved
     pub fn all_resolved(&self) -> bool {
         !self.conflicts.is_empty() && self.conflicts.iter().all(|c| c.is_resolved())
     }
 
     /// Get resolved file content
+    ///
+    /// Rebuilds the whole file with every region replaced by its resolution.
+    /// Returns `None` until all regions of the file are resolved.
     pub fn get_resolved_file(&self, file_path: &str) -> Option<String> {
-        let file_conflicts: Vec<&ConflictRegion> = self
+        if !self.is_file_resolved(file_path) {
+            return None;
+        }
+        let source = self.sources.get(file_path)?;
+
+        let mut regions: Vec<&ConflictRegion> = self
             .conflicts
             .iter()
-            .filter(|c| c.file_path == file_path && c.is_resolved())
+            .filter(|c| c.file_path == file_path)
             .collect();
-
-        if file_conflicts.is_empty() {
-            return None;
+        regions.sort_by_key(|c| c.start_line);
+
+        let mut lines = Vec::new();
+        let mut next = 0;
+        for region in regions {
+            let start = region.start_line.min(source.lines.len());
+            lines.extend(source.lines[next.min(start)..start].iter().cloned());
+            lines.extend(region.get_resolved_content());
+            next = region.end_line + 1;
         }
-
-        let mut result = String::new();
-        for conflict in file_conflicts {
-            for line in conflict.get_resolved_content() {
-                result.push_str(&line);
-                result.push('\n');
-            }
+        if next < source.lines.len() {
+            lines.extend(source.lines[next..].iter().cloned());
         }
 
+        let mut result = lines.join(source.line_ending);
+        if source.trailing_newline && !lines.is_empty() {
+            result.push_str(source.line_ending);
+        }
         Some(result)
     }
 
     /// Clear all conflicts
     pub fn clear(&mut self) {
         self.conflicts.clear();
+        self.sources.clear();
         self.current_conflict = 0;
         self.list_state.select(None);
     }
@@ -456,6 +681,7 @@ impl Widget for &ConflictResolver {
         // Render current conflict
         if let Some(conflict) = self.current() {
             match self.view_mode {
+                _ if conflict.proposal.is_some() => render_proposal(conflict, chunks[1], buf),
                 ConflictViewMode::SideBySide => {
                     let content_chunks = Layout::horizontal([
                         Constraint::Percentage(50),
@@ -658,8 +884,11 @@ impl Widget for &ConflictResolver {
                 height: 1,
             };
 
-            let footer_text =
-                "o: Choose Ours | t: Choose Theirs | b: Both | v: Toggle View | ↑↓: Navigate";
+            let footer_text = if self.current().is_some_and(|c| c.proposal.is_some()) {
+                "y: Accept Proposal | n: Reject Proposal | ↑↓: Navigate"
+            } else {
+                "o: Ours | t: Theirs | b: Both | a: Ask Agent | w: Write Resolved | v: View | ↑↓: Navigate"
+            };
             let footer = Paragraph::new(footer_text).style(Style::default().fg(Color::DarkGray));
 
             footer.render(footer_area, buf);
@@ -667,6 +896,39 @@ impl Widget for &ConflictResolver {
     }
 }
 
+/// Render a pending proposal as a diff against "ours"
+fn render_proposal(conflict: &ConflictRegion, area: Rect, buf: &mut Buffer) {
+    let lines: Vec<Line> = conflict
+        .proposal_diff()
+        .unwrap_or_default()
+        .into_iter()
+        .map(|change| {
+            let (prefix, line, color) = match change {
+                LineChange::Same(line) => (" ", line, Color::Gray),
+                LineChange::Removed(line) => ("-", line, Color::Red),
+                LineChange::Added(line) => ("+", line, Color::Green),
+            };
+            Line::from(
+                Text::new(format!("{} {}", prefix, line))
+                    .style(Style::default().fg(color))
+                    .to_span(),
+            )
+        })
+        .collect();
+
+    let para = Paragraph::new(lines)
+        .block(
+            AtomBlock::new()
+                .borders(Borders::ALL)
+                .title("Proposed Resolution (vs Ours)")
+                .border_style(Style::default().fg(Color::Magenta))
+                .to_ratatui(),
+        )
+        .wrap(ratatui::widgets::Wrap { trim: false });
+
+    para.render(area, buf);
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -812,4 +1074,145 @@ fn hello() {
         assert_eq!(resolver.our_branch, "main");
         assert_eq!(resolver.their_branch, "feature");
     }
+
+    #[test]
+    fn test_load_diff3_base() {
+        let mut resolver = ConflictResolver::new();
+        let content =
+            "a\n<<<<<<< HEAD\nours\n||||||| base\norig\n=======\ntheirs\n>>>>>>> feature\nb\n";
+        resolver.load_conflict("f.txt", content);
+
+        let conflict = resolver.current().unwrap();
+        assert_eq!(conflict.ours, vec!["ours"]);
+        assert_eq!(conflict.base, Some(vec!["orig".to_string()]));
+        assert_eq!(conflict.theirs, vec!["theirs"]);
+    }
+
+    #[test]
+    fn test_get_resolved_file_rebuilds_content() {
+        let mut resolver = ConflictResolver::new();
+        let content = "top\n<<<<<<< HEAD\nA\n=======\nB\n>>>>>>> x\nmid\n<<<<<<< HEAD\nC\n=======\nD\n>>>>>>> x\nend\n";
+        resolver.load_conflict("f.txt", content);
+
+        resolver.choose_ours();
+        assert_eq!(resolver.get_resolved_file("f.txt"), None);
+
+        resolver.choose_theirs();
+        assert_eq!(
+            resolver.get_resolved_file("f.txt").as_deref(),
+            Some("top\nA\nmid\nD\nend\n")
+        );
+        assert_eq!(resolver.resolved_files().len(), 1);
+    }
+
+    #[test]
+    fn test_get_resolved_file_keeps_crlf() {
+        let mut resolver = ConflictResolver::new();
+        resolver.load_conflict(
+            "f.txt",
+            "<<<<<<< HEAD\r\nA\r\n=======\r\nB\r\n>>>>>>> x\r\nz",
+        );
+        resolver.choose_both();
+
+        assert_eq!(
+            resolver.get_resolved_file("f.txt").as_deref(),
+            Some("A\r\nB\r\nz")
+        );
+    }
+
+    #[test]
+    fn test_auto_resolve() {
+        let region = |base: &[&str], ours: &[&str], theirs: &[&str]| {
+            let mut region = ConflictRegion::new("f.txt", 0, 6);
+            region.base = Some(base.iter().map(|l| l.to_string()).collect());
+            region.ours = ours.iter().map(|l| l.to_string()).collect();
+            region.theirs = theirs.iter().map(|l| l.to_string()).collect();
+            region
+        };
+        let mut resolver = ConflictResolver::new();
+        resolver.add_conflict(region(&["x", "y"], &["X", "y"], &["x", "Y"]));
+        resolver.add_conflict(region(&["b"], &["o"], &["t"]));
+        resolver.add_conflict(ConflictRegion::new("g.txt", 0, 4));
+
+        assert_eq!(resolver.auto_resolve(), 1);
+        assert_eq!(resolver.resolved_count(), 1);
+        assert_eq!(
+            resolver.conflicts[0].resolution,
+            ConflictResolution::AutoMerged
+        );
+    }
+
+    #[test]
+    fn test_auto_merge_region() {
+        let mut region = ConflictRegion::new("f.txt", 0, 6);
+        region.base = Some(vec!["x".to_string(), "y".to_string()]);
+        region.ours = vec!["X".to_string(), "y".to_string()];
+        region.theirs = vec!["x".to_string(), "Y".to_string()];
+
+        assert!(region.auto_merge());
+        assert_eq!(region.resolution, ConflictResolution::AutoMerged);
+        assert_eq!(region.get_resolved_content(), vec!["X", "Y"]);
+    }
+
+    #[test]
+    fn test_load_versions_conflicting() {
+        let mut resolver = ConflictResolver::new();
+        let versions = MergeVersions {
+            base: Some("a\nb\nc\n".to_string()),
+            ours: Some("a\nours\nc\n".to_string()),
+            theirs: Some("a\ntheirs\nc\n".to_string()),
+        };
+
+        assert_eq!(resolver.load_versions("f.txt", "", &versions), 1);
+        assert_eq!(resolver.auto_resolve(), 0);
+        let conflict = resolver.current().unwrap();
+        assert_eq!(conflict.base, Some(vec!["b".to_string()]));
+
+        resolver.choose_theirs();
+        assert_eq!(
+            resolver.get_resolved_file("f.txt").as_deref(),
+            Some("a\ntheirs\nc\n")
+        );
+    }
+
+    #[test]
+    fn test_proposal_accept_and_reject() {
+        let mut resolver = ConflictResolver::new();
+        resolver.load_conflict("f.txt", "<<<<<<< HEAD\nA\n=======\nB\n>>>>>>> x\n");
+
+        resolver.set_proposal(0, vec!["AB".to_string()]);
+        assert_eq!(
+            resolver.current().unwrap().proposal_diff(),
+            Some(vec![
+                LineChange::Removed("A".to_string()),
+                LineChange::Added("AB".to_string()),
+            ])
+        );
+        assert!(resolver.reject_proposal());
+        assert!(!resolver.accept_proposal());
+
+        resolver.set_proposal(0, vec!["AB".to_string()]);
+        assert!(resolver.accept_proposal());
+        assert_eq!(
+            resolver.current().unwrap().resolution,
+            ConflictResolution::ManualEdit
+        );
+        assert_eq!(resolver.get_resolved_file("f.txt").as_deref(), Some("AB\n"));
+    }
+
+    #[test]
+    fn test_remove_file() {
+        let mut resolver = ConflictResolver::new();
+        resolver.load_conflict("a.txt", "<<<<<<< HEAD\nA\n=======\nB\n>>>>>>> x\n");
+        resolver.load_conflict("b.txt", "<<<<<<< HEAD\nC\n=======\nD\n>>>>>>> x\n");
+        resolver.next_conflict();
+
+        resolver.remove_file("b.txt");
+        assert_eq!(resolver.files(), vec!["a.txt"]);
+        assert_eq!(resolver.current_index(), 0);
+
+        // Reloading a file replaces its regions
+        resolver.load_conflict("a.txt", "<<<<<<< HEAD\nA\n=======\nB\n>>>>>>> x\n");
+        assert_eq!(resolver.conflict_count(), 1);
+    }
 }
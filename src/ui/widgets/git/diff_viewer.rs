@@ -29,6 +29,7 @@
 //! let viewer = DiffViewer::new(diff_text);
 //! ```
 
+pub use crate::git::diff::{ChangeType, DiffHunk, DiffLine};
 use crate::ui::atoms::Block;
 use crate::ui::theme::ToadTheme;
 use ratatui::{
@@ -48,118 +49,13 @@ pub enum DiffMode {
     SideBySide,
 }
 
-/// Line change type
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum ChangeType {
-    /// Line added
-    Added,
-    /// Line removed
-    Removed,
-    /// Line modified (removed + added)
-    Modified,
-    /// Context line (unchanged)
-    Context,
-}
-
-impl ChangeType {
-    /// Get color for change type
-    pub fn color(&self) -> Color {
-        match self {
-            ChangeType::Added => ToadTheme::TOAD_GREEN,
-            ChangeType::Removed => ToadTheme::ERROR,
-            ChangeType::Modified => ToadTheme::YELLOW,
-            ChangeType::Context => ToadTheme::GRAY,
-        }
-    }
-
-    /// Get marker symbol
-    pub fn marker(&self) -> &'static str {
-        match self {
-            ChangeType::Added => "+",
-            ChangeType::Removed => "-",
-            ChangeType::Modified => "~",
-            ChangeType::Context => " ",
-        }
-    }
-}
-
-/// A single diff line
-#[derive(Debug, Clone)]
-pub struct DiffLine {
-    /// Line content
-    pub content: String,
-    /// Change type
-    pub change_type: ChangeType,
-    /// Old line number (if applicable)
-    pub old_line_num: Option<usize>,
-    /// New line number (if applicable)
-    pub new_line_num: Option<usize>,
-}
-
-impl DiffLine {
-    /// Create a new diff line
-    pub fn new(
-        content: String,
-        change_type: ChangeType,
-        old_line_num: Option<usize>,
-        new_line_num: Option<usize>,
-    ) -> Self {
-        Self {
-            content,
-            change_type,
-            old_line_num,
-            new_line_num,
-        }
-    }
-}
-
-/// A diff hunk (contiguous block of changes)
-#[derive(Debug, Clone)]
-pub struct DiffHunk {
-    /// Hunk header (e.g., "@@ -1,3 +1,4 @@")
-    pub header: String,
-    /// Lines in this hunk
-    pub lines: Vec<DiffLine>,
-    /// Old start line
-    pub old_start: usize,
-    /// Old line count
-    pub old_count: usize,
-    /// New start line
-    pub new_start: usize,
-    /// New line count
-    pub new_count: usize,
-    /// Whether this hunk is selected for application
-    pub selected: bool,
-}
-
-impl DiffHunk {
-    /// Create a new diff hunk
-    pub fn new(
-        header: String,
-        old_start: usize,
-        old_count: usize,
-        new_start: usize,
-        new_count: usize,
-    ) -> Self {
-        Self {
-            header,
-            lines: Vec::new(),
-            old_start,
-            old_count,
-            new_start,
-            new_count,
-            selected: true, // Selected by default
-        }
-    }
-
-    /// Add a line to the hunk
-    pub fn add_line(&mut self, line: DiffLine) {
-        self.lines.push(line);
-    }
-
-    /// Get number of lines
-    pub fn line_count(&self) -> usize {
-        self.lines.len()
+/// Color of a line with the given change type
+fn change_color(change_type: ChangeType) -> Color {
+    match change_type {
+        ChangeType::Added => ToadTheme::TOAD_GREEN,
+        ChangeType::Removed => ToadTheme::ERROR,
+        ChangeType::Modified => ToadTheme::YELLOW,
+        ChangeType::Context => ToadTheme::GRAY,
     }
 }
 
@@ -504,13 +400,13 @@ impl DiffViewer {
                 // Marker
                 spans.push(Span::styled(
                     format!("{} ", line.change_type.marker()),
-                    Style::default().fg(line.change_type.color()),
+                    Style::default().fg(change_color(line.change_type)),
                 ));
 
                 // Content
                 spans.push(Span::styled(
                     &line.content,
-                    Style::default().fg(line.change_type.color()),
+                    Style::default().fg(change_color(line.change_type)),
                 ));
 
                 lines.push(Line::from(spans));
@@ -567,10 +463,10 @@ mod tests {
 
     #[test]
     fn test_change_type_color() {
-        assert_eq!(ChangeType::Added.color(), ToadTheme::TOAD_GREEN);
-        assert_eq!(ChangeType::Removed.color(), ToadTheme::ERROR);
-        assert_eq!(ChangeType::Modified.color(), ToadTheme::YELLOW);
-        assert_eq!(ChangeType::Context.color(), ToadTheme::GRAY);
+        assert_eq!(change_color(ChangeType::Added), ToadTheme::TOAD_GREEN);
+        assert_eq!(change_color(ChangeType::Removed), ToadTheme::ERROR);
+        assert_eq!(change_color(ChangeType::Modified), ToadTheme::YELLOW);
+        assert_eq!(change_color(ChangeType::Context), ToadTheme::GRAY);
     }
 
     #[test]
@@ -126,8 +126,17 @@ impl GitBranchManager {
     /// # }
     /// ```
     pub async fn refresh(&mut self) -> Result<()> {
-        self.current_branch = self.service.current_branch().await?;
-        self.branches = self.service.list_branches().await?;
+        let current = self.service.current_branch().await?;
+        let branches = self.service.list_branches().await?;
+        self.apply_branches(current, branches);
+
+        Ok(())
+    }
+
+    /// Populate the branch list from an already fetched listing
+    pub fn apply_branches(&mut self, current: impl Into<String>, branches: Vec<BranchInfo>) {
+        self.current_branch = current.into();
+        self.branches = branches;
 
         // Ensure selection is valid
         if !self.branches.is_empty() && self.list_state.selected().is_none() {
@@ -138,8 +147,6 @@ impl GitBranchManager {
         if let Some(pos) = self.branches.iter().position(|b| b.is_current) {
             self.list_state.select(Some(pos));
         }
-
-        Ok(())
     }
 
     /// Get the current mode
@@ -381,6 +388,17 @@ impl GitBranchManager {
         self.error.as_deref()
     }
 
+    /// Set the operation message
+    pub fn set_message(&mut self, message: impl Into<String>) {
+        self.message = Some(message.into());
+        self.error = None;
+    }
+
+    /// Set the error message
+    pub fn set_error(&mut self, error: impl Into<String>) {
+        self.error = Some(error.into());
+    }
+
     /// Clear messages
     pub fn clear_messages(&mut self) {
         self.message = None;
@@ -55,6 +55,7 @@ pub enum CommitDialogState {
 ///
 /// - `Enter`: Newline in message
 /// - `Ctrl+Enter`: Commit
+/// - `Ctrl+G`: Generate a message from the staged diff
 /// - `Esc`: Cancel
 pub struct GitCommitDialog {
     /// Git service for operations
@@ -71,6 +72,8 @@ pub struct GitCommitDialog {
     result_message: Option<String>,
     /// Error message if commit failed
     error_message: Option<String>,
+    /// Whether a message is being generated
+    generating: bool,
 }
 
 impl GitCommitDialog {
@@ -97,29 +100,111 @@ impl GitCommitDialog {
             staged_count: 0,
             result_message: None,
             error_message: None,
+            generating: false,
         }
     }
 
     /// Show the dialog and refresh staged file count
     pub async fn show(&mut self) -> Result<()> {
-        self.state = CommitDialogState::Editing;
-        self.message.clear();
-        self.cursor_pos = 0;
-        self.result_message = None;
-        self.error_message = None;
-
         // Count staged files
         let changes = self.service.status().await?;
-        self.staged_count = changes
+        let staged_count = changes
             .iter()
             .filter(|c| matches!(c, crate::git::FileChange::Staged(_)))
             .count();
+        self.open(staged_count);
 
         Ok(())
     }
 
+    /// Show the dialog with an already known staged file count
+    pub fn open(&mut self, staged_count: usize) {
+        self.state = CommitDialogState::Editing;
+        self.message.clear();
+        self.cursor_pos = 0;
+        self.result_message = None;
+        self.error_message = None;
+        self.staged_count = staged_count;
+        self.generating = false;
+    }
+
+    /// Mark a message as being generated
+    ///
+    /// Returns `false` when the dialog is not editing or a generation is
+    /// already running.
+    pub fn begin_generating(&mut self) -> bool {
+        if self.state != CommitDialogState::Editing || self.generating {
+            return false;
+        }
+        self.generating = true;
+        true
+    }
+
+    /// Finish generating, replacing the message with the generated text
+    ///
+    /// `None` (generation failed) keeps the current message. The generated
+    /// text stays editable.
+    pub fn finish_generating(&mut self, message: Option<String>) {
+        if !self.generating {
+            return;
+        }
+        self.generating = false;
+        if let Some(message) = message {
+            self.set_message(message);
+        }
+    }
+
+    /// Whether a message is being generated
+    pub fn is_generating(&self) -> bool {
+        self.generating
+    }
+
+    /// Update the staged file count while the dialog is open
+    pub fn set_staged_count(&mut self, staged_count: usize) {
+        self.staged_count = staged_count;
+    }
+
+    /// Validate and mark the dialog as committing
+    ///
+    /// Returns the message to commit, or `None` when validation failed
+    /// (the dialog then shows the validation error). Used when the commit
+    /// itself runs outside the dialog.
+    pub fn begin_commit(&mut self) -> Option<String> {
+        if !self.is_valid() {
+            self.state = CommitDialogState::Error;
+            self.error_message = self.validation_error().map(String::from);
+            return None;
+        }
+
+        self.state = CommitDialogState::Committing;
+        Some(self.message.clone())
+    }
+
+    /// Record the outcome of a commit started with [`begin_commit`](Self::begin_commit)
+    pub fn finish_commit(&mut self, result: std::result::Result<String, String>) {
+        match result {
+            Ok(output) => {
+                self.state = CommitDialogState::Success;
+                self.result_message = Some(output);
+            }
+            Err(e) => {
+                self.state = CommitDialogState::Error;
+                self.error_message = Some(format!("Commit failed: {}", e));
+            }
+        }
+    }
+
+    /// Return to editing after an error, keeping the message
+    pub fn resume_editing(&mut self) {
+        if self.state == CommitDialogState::Error {
+            self.state = CommitDialogState::Editing;
+            self.error_message = None;
+        }
+    }
+
     /// Hide the dialog
     pub fn hide(&mut self) {
+        self.generating = false;
         self.state = CommitDialogState::Hidden;
         self.message.clear();
         self.cursor_pos = 0;
@@ -326,6 +411,10 @@ impl Widget for &GitCommitDialog {
 
         // Render title based on state
         let (title, title_style) = match self.state {
+            CommitDialogState::Editing if self.generating => (
+                "Generating Message...",
+                Style::default().fg(Color::Yellow),
+            ),
             CommitDialogState::Editing => ("Commit Changes", Style::default().fg(Color::Cyan)),
             CommitDialogState::Committing => ("Committing...", Style::default().fg(Color::Yellow)),
             CommitDialogState::Success => ("Commit Successful", Style::default().fg(Color::Green)),
@@ -457,6 +546,7 @@ impl Widget for &GitCommitDialog {
             } else {
                 vec![Line::from(vec![
                     AtomText::new("Ctrl+Enter: Commit | ").to_span(),
+                    AtomText::new("Ctrl+G: Generate | ").to_span(),
                     AtomText::new("Enter: New line | ").to_span(),
                     AtomText::new("Esc: Cancel").to_span(),
                 ])]
@@ -655,4 +745,33 @@ mod tests {
         assert_eq!(dialog.state(), CommitDialogState::Success);
         assert!(dialog.result_message().is_some());
     }
+
+    #[tokio::test]
+    async fn test_git_commit_dialog_generating() {
+        let temp_dir = TempDir::new().unwrap();
+        init_git_repo(temp_dir.path()).await;
+
+        let service = GitService::new(temp_dir.path()).unwrap();
+        let mut dialog = GitCommitDialog::new(service);
+        assert!(!dialog.begin_generating());
+
+        dialog.open(1);
+        dialog.set_message("wip");
+        assert!(dialog.begin_generating());
+        assert!(!dialog.begin_generating());
+
+        // A failed generation keeps the typed message
+        dialog.finish_generating(None);
+        assert!(!dialog.is_generating());
+        assert_eq!(dialog.message(), "wip");
+
+        dialog.begin_generating();
+        dialog.finish_generating(Some("feat: add x".to_string()));
+        assert_eq!(dialog.message(), "feat: add x");
+        assert_eq!(dialog.cursor_pos(), "feat: add x".len());
+
+        // Late results after the generation was abandoned are ignored
+        dialog.finish_generating(Some("fix: y".to_string()));
+        assert_eq!(dialog.message(), "feat: add x");
+    }
 }
@@ -242,6 +242,8 @@ pub struct GitGraph {
     pub(super) compact: bool,
     /// Maximum commits to display
     pub(super) max_commits: Option<usize>,
+    /// Number of rendered lines scrolled past
+    pub(super) scroll_offset: usize,
 }
 
 impl Default for GitGraph {
@@ -269,6 +271,7 @@ impl GitGraph {
             show_branches: true,
             compact: false,
             max_commits: None,
+            scroll_offset: 0,
         }
     }
 
@@ -413,6 +416,41 @@ impl GitGraph {
         self.commits.len()
     }
 
+    /// Scroll the graph down by one line
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use toad::widgets::{GitGraph, GitCommit};
+    ///
+    /// let mut graph = GitGraph::new();
+    /// graph.add_commit(GitCommit::new("abc", "Test"));
+    /// graph.scroll_down();
+    /// assert_eq!(graph.scroll_offset(), 1);
+    /// ```
+    pub fn scroll_down(&mut self) {
+        let max_offset = self.commit_count().saturating_mul(2);
+        if self.scroll_offset < max_offset {
+            self.scroll_offset += 1;
+        }
+    }
+
+    /// Scroll the graph up by one line
+    pub fn scroll_up(&mut self) {
+        self.scroll_offset = self.scroll_offset.saturating_sub(1);
+    }
+
+    /// Get the current scroll offset
+    pub fn scroll_offset(&self) -> usize {
+        self.scroll_offset
+    }
+
+    /// Set the scroll offset, clamped to the graph length
+    pub fn set_scroll_offset(&mut self, offset: usize) {
+        let max_offset = self.commit_count().saturating_mul(2);
+        self.scroll_offset = offset.min(max_offset);
+    }
+
     /// Render graph lines
     pub(super) fn render_lines(&self, width: u16) -> Vec<Line<'static>> {
         let mut lines = Vec::new();
@@ -539,7 +577,7 @@ impl Widget for &GitGraph {
 
         block.render(area, buf);
 
-        for (i, line) in lines.iter().enumerate() {
+        for (i, line) in lines.iter().skip(self.scroll_offset).enumerate() {
             if i >= inner.height as usize {
                 break;
             }
@@ -188,7 +188,7 @@ impl GitStageUI {
     /// ```
     pub async fn refresh(&mut self) -> Result<()> {
         // Get current branch
-        self.branch = self
+        let branch = self
             .service
             .current_branch()
             .await
@@ -196,7 +196,16 @@ impl GitStageUI {
 
         // Get file changes
         let changes = self.service.status().await?;
+        self.apply_status(branch, changes);
 
+        Ok(())
+    }
+
+    /// Populate the file lists from an already fetched status
+    ///
+    /// Used when git status is collected off the UI thread.
+    pub fn apply_status(&mut self, branch: impl Into<String>, changes: Vec<FileChange>) {
+        self.branch = branch.into();
         self.unstaged.clear();
         self.staged.clear();
 
@@ -232,7 +241,19 @@ impl GitStageUI {
             self.staged_state.select(Some(0));
         }
 
-        Ok(())
+        // Keep selection within bounds after files disappear
+        if let Some(idx) = self.unstaged_state.selected()
+            && idx >= self.unstaged.len()
+        {
+            self.unstaged_state
+                .select(Some(self.unstaged.len().saturating_sub(1)));
+        }
+        if let Some(idx) = self.staged_state.selected()
+            && idx >= self.staged.len()
+        {
+            self.staged_state
+                .select(Some(self.staged.len().saturating_sub(1)));
+        }
     }
 
     /// Stage the currently selected file
@@ -404,6 +425,35 @@ impl GitStageUI {
         self.message = None;
     }
 
+    /// Set the operation message shown in the footer
+    pub fn set_message(&mut self, message: impl Into<String>) {
+        self.message = Some(message.into());
+    }
+
+    /// Get the entry under the cursor in the focused pane
+    pub fn selected_entry(&self) -> Option<&StageFileEntry> {
+        match self.focus {
+            StagePane::Unstaged => self
+                .unstaged_state
+                .selected()
+                .and_then(|i| self.unstaged.get(i)),
+            StagePane::Staged => self
+                .staged_state
+                .selected()
+                .and_then(|i| self.staged.get(i)),
+        }
+    }
+
+    /// Get unstaged entries
+    pub fn unstaged_entries(&self) -> &[StageFileEntry] {
+        &self.unstaged
+    }
+
+    /// Get staged entries
+    pub fn staged_entries(&self) -> &[StageFileEntry] {
+        &self.staged
+    }
+
     /// Get the current branch name
     pub fn branch(&self) -> &str {
         &self.branch
@@ -64,6 +64,41 @@ impl CommandPalette {
                 label: "Open Evaluation Center".to_string(),
                 description: "Open the M0 evaluation framework (F9)".to_string(),
             },
+            PaletteCommand {
+                id: "git_status".to_string(),
+                label: "Open Git".to_string(),
+                description: "Stage changes, commit, switch branches and browse history".to_string(),
+            },
+            PaletteCommand {
+                id: "open_editor".to_string(),
+                label: "Open Editor".to_string(),
+                description: "Browse and edit workspace files".to_string(),
+            },
+            PaletteCommand {
+                id: "open_kanban".to_string(),
+                label: "Kanban Board".to_string(),
+                description: "Move task cards across board columns".to_string(),
+            },
+            PaletteCommand {
+                id: "open_timeline".to_string(),
+                label: "Timeline".to_string(),
+                description: "Gantt chart of the card schedule and critical path".to_string(),
+            },
+            PaletteCommand {
+                id: "open_calendar".to_string(),
+                label: "Calendar".to_string(),
+                description: "Month, week and day calendar of card due dates".to_string(),
+            },
+            PaletteCommand {
+                id: "open_table".to_string(),
+                label: "Table".to_string(),
+                description: "Sort and edit cards as spreadsheet rows".to_string(),
+            },
+            PaletteCommand {
+                id: "open_mind_map".to_string(),
+                label: "Mind Map".to_string(),
+                description: "Tree of epics, cards, subtasks and dependencies".to_string(),
+            },
             PaletteCommand {
                 id: "quit".to_string(),
                 label: "Quit Application".to_string(),
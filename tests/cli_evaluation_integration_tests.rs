@@ -119,6 +119,8 @@ fn test_benchmark_parsing() {
 /// correct default values.
 #[test]
 fn test_orchestrator_config_creation() {
+    use toad::ai::evaluation::SamplingConfig;
+    use toad::benchmarks::scheduler::SchedulerConfig;
     use toad::benchmarks::{OrchestratorConfig, ExecutionContext};
     use std::time::Duration;
 
@@ -131,7 +133,10 @@ fn test_orchestrator_config_creation() {
             max_steps: 25,
             system_config: serde_json::json!({}),
             sandbox_config: None,
+            sample: None,
         },
+        sampling: SamplingConfig::default(),
+        scheduler: SchedulerConfig::default(),
     };
 
     assert_eq!(config.benchmarks.len(), 1);
@@ -142,6 +147,8 @@ fn test_orchestrator_config_creation() {
 /// Test that OrchestratorConfig with multiple benchmarks works
 #[test]
 fn test_orchestrator_multi_benchmark_config() {
+    use toad::ai::evaluation::SamplingConfig;
+    use toad::benchmarks::scheduler::SchedulerConfig;
     use toad::benchmarks::{OrchestratorConfig, ExecutionContext};
 
     let config = OrchestratorConfig {
@@ -152,6 +159,8 @@ fn test_orchestrator_multi_benchmark_config() {
         task_limit: Some(5),
         max_concurrent_benchmarks: 2,
         execution_context: ExecutionContext::default(),
+        sampling: SamplingConfig::default(),
+        scheduler: SchedulerConfig::default(),
     };
 
     assert_eq!(config.benchmarks.len(), 2);
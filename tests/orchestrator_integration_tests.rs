@@ -9,6 +9,8 @@
 //! (SWE-bench, LiveCodeBench) are stubs that call unimplemented!(). They will
 //! be enabled once Phase 6 (benchmark implementation) is complete.
 
+use toad::ai::evaluation::SamplingConfig;
+use toad::benchmarks::scheduler::SchedulerConfig;
 use toad::benchmarks::{Orchestrator, OrchestratorConfig, ExecutionContext};
 use toad::benchmarks::types::ProgressEvent;
 use tokio_util::sync::CancellationToken;
@@ -35,7 +37,10 @@ async fn test_orchestrator_completes_successfully() {
             max_steps: 10,
             system_config: serde_json::json!({}),
             sandbox_config: None,
+            sample: None,
         },
+        sampling: SamplingConfig::default(),
+        scheduler: SchedulerConfig::default(),
     };
 
     let cancel_token = CancellationToken::new();
@@ -77,6 +82,8 @@ async fn test_orchestrator_emits_progress_events() {
         task_limit: Some(3), // Run 3 tasks
         max_concurrent_benchmarks: 1,
         execution_context: ExecutionContext::default(),
+        sampling: SamplingConfig::default(),
+        scheduler: SchedulerConfig::default(),
     };
 
     let cancel_token = CancellationToken::new();
@@ -135,7 +142,10 @@ async fn test_orchestrator_cancellation() {
             max_steps: 25,
             system_config: serde_json::json!({}),
             sandbox_config: None,
+            sample: None,
         },
+        sampling: SamplingConfig::default(),
+        scheduler: SchedulerConfig::default(),
     };
 
     let cancel_token = CancellationToken::new();
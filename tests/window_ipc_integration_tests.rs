@@ -0,0 +1,118 @@
+//! Integration tests for IPC between TOAD windows
+//!
+//! These tests run real endpoints on Unix sockets in a temporary directory
+//! and verify:
+//! - Two windows discover each other and exchange messages both ways
+//! - A window leaves discovery when it shuts down
+//! - The app folds messages from other windows into its context on tick
+
+use std::time::Duration;
+use toad::core::App;
+use toad::core::event::Event;
+use toad::infrastructure::cross_window_context::{
+    ClipboardContentType, ClipboardEntry, CrossWindowContextManager,
+};
+use toad::infrastructure::window_ipc::{
+    Delivery, IncomingMessage, IpcConfig, IpcEvent, IpcMessage, WindowEndpoint,
+};
+
+/// Wait for the next message on `endpoint`, skipping connection events
+fn next_message(endpoint: &mut WindowEndpoint) -> IncomingMessage {
+    loop {
+        match endpoint.recv_timeout(Duration::from_secs(5)) {
+            Some(IpcEvent::Message(incoming)) => return incoming,
+            Some(_) => {}
+            None => panic!("no message for window {}", endpoint.window_id()),
+        }
+    }
+}
+
+fn clipboard(from: &str, text: &str) -> IpcMessage {
+    IpcMessage::Clipboard {
+        entry: ClipboardEntry::new(
+            "clip-1".to_string(),
+            from.to_string(),
+            ClipboardContentType::Text,
+            text.to_string(),
+        ),
+    }
+}
+
+#[test]
+fn test_two_windows_exchange_messages() {
+    let dir = tempfile::tempdir().unwrap();
+    let config = IpcConfig::in_dir(dir.path()).with_title("project");
+    let mut left = WindowEndpoint::start("left", &config).unwrap();
+    let mut right = WindowEndpoint::start("right", &config).unwrap();
+
+    let peers = left.peers().unwrap();
+    assert_eq!(peers.len(), 1);
+    assert_eq!(peers[0].window_id, "right");
+    assert_eq!(peers[0].title.as_deref(), Some("project"));
+
+    // left copies, right's clipboard gets the entry
+    let mut right_context = CrossWindowContextManager::new();
+    assert_eq!(
+        left.send("right", clipboard("left", "copied")).unwrap(),
+        Delivery::Sent
+    );
+    let incoming = next_message(&mut right);
+    assert_eq!(incoming.from, "left");
+    assert!(incoming.apply(&mut right_context));
+    assert_eq!(
+        right_context.get_latest_clipboard().unwrap().content,
+        "copied"
+    );
+
+    // right answers by asking left to open a file
+    right
+        .send(
+            "left",
+            IpcMessage::OpenFile {
+                path: "src/lib.rs".into(),
+                line: Some(7),
+            },
+        )
+        .unwrap();
+    let incoming = next_message(&mut left);
+    assert_eq!(incoming.from, "right");
+    match incoming.message {
+        IpcMessage::OpenFile { path, line } => {
+            assert_eq!(path, std::path::PathBuf::from("src/lib.rs"));
+            assert_eq!(line, Some(7));
+        }
+        other => panic!("unexpected message {:?}", other),
+    }
+
+    // A window that shuts down leaves discovery
+    drop(right);
+    assert!(left.peers().unwrap().is_empty());
+}
+
+#[test]
+fn test_app_applies_messages_on_tick() {
+    let dir = tempfile::tempdir().unwrap();
+    let config = IpcConfig::in_dir(dir.path());
+    let mut app = App::new();
+    app.start_window_ipc(&config);
+    let window_id = app.window_id().expect("IPC started").to_string();
+
+    let mut other = WindowEndpoint::start("other", &config).unwrap();
+    assert_eq!(other.peers().unwrap()[0].window_id, window_id);
+    other
+        .send(&window_id, clipboard("other", "from another window"))
+        .unwrap();
+
+    for _ in 0..100 {
+        app.update(Event::Tick).unwrap();
+        if app.window_context().get_latest_clipboard().is_some() {
+            break;
+        }
+        std::thread::sleep(Duration::from_millis(20));
+    }
+    let latest = app.window_context().get_latest_clipboard().unwrap();
+    assert_eq!(latest.content, "from another window");
+
+    app.stop_window_ipc();
+    assert!(other.peers().unwrap().is_empty());
+}